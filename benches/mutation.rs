@@ -1,8 +1,8 @@
 mod config_generator;
 
 use config_generator::generate_config;
-use criterion::{criterion_group, criterion_main, Criterion};
-use hyprlang::Config;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use hyprlang::{Config, ConfigOptions};
 
 fn mutation_benchmarks(c: &mut Criterion) {
     let small = generate_config(50);
@@ -31,18 +31,74 @@ fn mutation_benchmarks(c: &mut Criterion) {
         b.iter(|| config.serialize())
     });
 
+    // Parsing with and without building a document
+    group.bench_function("parse_large_tracked", |b| {
+        b.iter(|| {
+            let mut config = Config::new();
+            config.parse(&large).unwrap()
+        })
+    });
+
+    group.bench_function("parse_large_read_only", |b| {
+        b.iter(|| {
+            let mut config = Config::with_options(ConfigOptions {
+                track_document: false,
+                ..Default::default()
+            });
+            config.parse(&large).unwrap()
+        })
+    });
+
     // Round-trip: parse -> mutate -> serialize
     group.bench_function("round_trip", |b| {
         b.iter(|| {
             let mut config = Config::new();
             config.parse(&small).unwrap();
-            config.set_int("test:value", 42);
+            config.set_int("test:value", 42).unwrap();
             let output = config.serialize();
             let mut config2 = Config::new();
             config2.parse(&output).unwrap();
         })
     });
 
+    // Batched edits on a 10k-line document
+    let huge = generate_config(10_000);
+    let parsed_huge = || {
+        let mut config = Config::new();
+        config.parse(&huge).unwrap();
+        config
+    };
+
+    group.bench_function("batch_insert_10k", |b| {
+        b.iter_batched(
+            parsed_huge,
+            |mut config| {
+                for i in 0..500 {
+                    config
+                        .set_int(format!("category{}:new_{}", i % 300, i), i)
+                        .unwrap();
+                }
+                config
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.bench_function("batch_remove_10k", |b| {
+        b.iter_batched(
+            parsed_huge,
+            |mut config| {
+                for category in 0..300 {
+                    config
+                        .remove(&format!("category{}:int_{}", category, category * 25))
+                        .unwrap();
+                }
+                config
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
     group.finish();
 }
 
@@ -2,7 +2,7 @@ mod config_generator;
 
 use config_generator::generate_config;
 use criterion::{criterion_group, criterion_main, Criterion};
-use hyprlang::Config;
+use hyprlang::{Config, SpecialCategoryDescriptor};
 
 fn parsing_benchmarks(c: &mut Criterion) {
     // Generate configs of different sizes
@@ -61,5 +61,45 @@ fn perf_benchmark(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, parsing_benchmarks, perf_benchmark);
+/// Generate `blocks` windowrule-style keyed special category blocks after some plain values
+fn generate_special_blocks(blocks: usize) -> String {
+    let mut config = String::new();
+    for i in 0..500 {
+        config.push_str(&format!("general:option_{} = {}\n", i, i));
+    }
+    for i in 0..blocks {
+        config.push_str(&format!(
+            "windowrule {{\n    name = rule-{i}\n    match:class = ^(app-{i})$\n    \
+             float = true\n    opacity = 0.9\n}}\n"
+        ));
+    }
+    config
+}
+
+fn special_category_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("special_categories");
+
+    for blocks in [100, 500, 1_000] {
+        let input = generate_special_blocks(blocks);
+        group.bench_function(format!("windowrule_blocks_{}", blocks), |b| {
+            b.iter(|| {
+                let mut config = Config::new();
+                config.register_special_category(SpecialCategoryDescriptor::keyed(
+                    "windowrule",
+                    "name",
+                ));
+                config.parse(&input).unwrap()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    parsing_benchmarks,
+    special_category_benchmarks,
+    perf_benchmark
+);
 criterion_main!(benches);
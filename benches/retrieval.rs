@@ -33,6 +33,24 @@ fn retrieval_benchmarks(c: &mut Criterion) {
         b.iter(|| config.keys().len())
     });
 
+    // Read-heavy workload: every value read as text, as a status bar redrawing would
+    let keys = config.keys();
+    group.bench_function("get_str_cow_all_keys", |b| {
+        b.iter(|| {
+            keys.iter()
+                .map(|key| config.get_str_cow(key).map_or(0, |text| text.len()))
+                .sum::<usize>()
+        })
+    });
+
+    group.bench_function("to_string_all_keys", |b| {
+        b.iter(|| {
+            keys.iter()
+                .map(|key| config.get(key).map_or(0, |value| value.to_string().len()))
+                .sum::<usize>()
+        })
+    });
+
     group.finish();
 }
 
@@ -0,0 +1,59 @@
+//! Collecting handler calls into application-owned state.
+//!
+//! Handlers registered with `register_handler_with_state` receive the value set with
+//! `Config::set_handler_state`, so they can fill plain structs without `Rc<RefCell<_>>`.
+//!
+//! ```bash
+//! cargo run --example handler_state
+//! ```
+
+use hyprlang::Config;
+
+#[derive(Debug, Default)]
+struct Keybinds {
+    binds: Vec<String>,
+    env: Vec<(String, String)>,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = Config::new();
+
+    config.register_handler_with_state("bind", |ctx, state: &mut Keybinds| {
+        state.binds.push(ctx.value.clone());
+        Ok(())
+    });
+    config.register_handler_with_state("env", |ctx, state: &mut Keybinds| {
+        if let Some((name, value)) = ctx.value.split_once(',') {
+            state
+                .env
+                .push((name.trim().to_string(), value.trim().to_string()));
+        }
+        Ok(())
+    });
+
+    config.set_handler_state(Keybinds::default());
+    config.parse(
+        r#"
+$mod = SUPER
+env = XCURSOR_SIZE, 24
+bind = $mod, Q, exec, kitty
+bind = $mod, C, killactive
+bind = $mod, M, exit
+"#,
+    )?;
+
+    let keybinds: Keybinds = config
+        .take_handler_state()
+        .expect("state was set before parsing");
+
+    println!("Collected {} binds:", keybinds.binds.len());
+    for bind in &keybinds.binds {
+        println!("  {}", bind);
+    }
+    println!("Environment:");
+    for (name, value) in &keybinds.env {
+        println!("  {} = {}", name, value);
+    }
+
+    Ok(())
+}
@@ -255,6 +255,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("│                        WINDOW RULES                          │");
     println!("└─────────────────────────────────────────────────────────────┘\n");
 
+    #[allow(deprecated)]
     let windowrules = hypr.all_windowrules();
     println!("  Window rules ({}):", windowrules.len());
     for (i, rule) in windowrules.iter().enumerate() {
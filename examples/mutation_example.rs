@@ -46,15 +46,15 @@ decoration {
     println!("🔧 Mutating configuration values...\n");
 
     // Set a new value using the typed setter
-    config.set_int("general:border_size", 5);
+    config.set_int("general:border_size", 5)?;
     println!("✓ Changed border_size: {} -> 5", 3);
 
     // Set a float value
-    config.set_float("general:active_opacity", 1.0);
+    config.set_float("general:active_opacity", 1.0)?;
     println!("✓ Changed active_opacity: {} -> 1.0", 0.9);
 
     // Add a new value that doesn't exist
-    config.set("decoration:blur", ConfigValue::Int(1));
+    config.set("decoration:blur", ConfigValue::Int(1))?;
     println!("✓ Added new value: decoration:blur = 1");
 
     // Remove a value
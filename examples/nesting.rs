@@ -10,9 +10,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("║                         Nested Config                         ║");
     println!("╚═══════════════════════════════════════════════════════════════╝\n");
 
-    let mut options = ConfigOptions::default();
-    options.base_dir = Some(config_path.parent().unwrap().to_path_buf());
-    options.throw_all_errors = false;
+    let options = ConfigOptions {
+        base_dir: Some(config_path.parent().unwrap().to_path_buf()),
+        throw_all_errors: false,
+        ..Default::default()
+    };
 
     let mut config = Config::with_options(options);
 
@@ -66,12 +68,12 @@ fn pretty_print_config(config: &Config) {
 
                 by_category
                     .entry(category)
-                    .or_insert_with(Vec::new)
+                    .or_default()
                     .push((subkey, value_str));
             } else if !key.starts_with('$') {
                 by_category
                     .entry("".to_string())
-                    .or_insert_with(Vec::new)
+                    .or_default()
                     .push((key.to_string(), value_str));
             }
         }
@@ -206,6 +208,7 @@ fn format_value(value: &ConfigValue) -> String {
         }
         ConfigValue::Vec2(v) => format!("({}, {})", v.x, v.y),
         ConfigValue::Color(c) => format_color(c),
+        ConfigValue::Quantity { value, unit } => format!("{}{}", value, unit),
         ConfigValue::Custom { type_name, .. } => format!("<{}>", type_name),
     }
 }
@@ -222,7 +225,7 @@ fn get_indent_level(key: &str) -> usize {
 }
 
 fn get_display_key(key: &str) -> String {
-    if let Some(last_part) = key.split(':').last() {
+    if let Some(last_part) = key.split(':').next_back() {
         last_part.to_string()
     } else {
         key.to_string()
@@ -14,9 +14,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("📄 Parsing: {}\n", config_path.display());
 
     // Create config with options
-    let mut options = ConfigOptions::default();
-    options.base_dir = Some(config_path.parent().unwrap().to_path_buf());
-    options.throw_all_errors = false;
+    let options = ConfigOptions {
+        base_dir: Some(config_path.parent().unwrap().to_path_buf()),
+        throw_all_errors: false,
+        ..Default::default()
+    };
 
     let mut config = Config::with_options(options);
 
@@ -106,12 +108,12 @@ fn pretty_print_config(config: &Config) {
 
                 by_category
                     .entry(category)
-                    .or_insert_with(Vec::new)
+                    .or_default()
                     .push((subkey, value_str));
             } else if !key.starts_with('$') {
                 by_category
                     .entry("".to_string())
-                    .or_insert_with(Vec::new)
+                    .or_default()
                     .push((key.to_string(), value_str));
             }
         }
@@ -203,13 +205,20 @@ fn pretty_print_config(config: &Config) {
     println!("│                          SUMMARY                            │");
     println!("╰─────────────────────────────────────────────────────────────╯\n");
 
-    let total_values = all_keys.len();
-    let total_categories = by_category.keys().filter(|k| !k.is_empty()).count();
-    let total_handlers: usize = handler_calls.values().map(|v| v.len()).sum();
+    let stats = config.stats();
+    let total_categories = stats
+        .keys_per_category
+        .keys()
+        .filter(|k| !k.is_empty())
+        .count();
 
-    println!("  📊 Total configuration values: {}", total_values);
+    println!("  📊 Total configuration values: {}", stats.total_keys());
     println!("  📁 Total categories: {}", total_categories);
-    println!("  🔧 Total handler calls: {}", total_handlers);
+    println!("  🔧 Total handler calls: {}", stats.total_handler_calls());
+    println!(
+        "  📄 Parsed {} line(s) in {} file(s) in {:?}",
+        stats.lines, stats.files, stats.parse_duration
+    );
 
     // Count different value types
     let mut type_counts: HashMap<String, usize> = HashMap::new();
@@ -246,6 +255,7 @@ fn format_value(value: &ConfigValue) -> String {
         }
         ConfigValue::Vec2(v) => format!("({}, {})", v.x, v.y),
         ConfigValue::Color(c) => format_color(c),
+        ConfigValue::Quantity { value, unit } => format!("{}{}", value, unit),
         ConfigValue::Custom { type_name, .. } => format!("<{}>", type_name),
     }
 }
@@ -262,7 +272,7 @@ fn get_indent_level(key: &str) -> usize {
 }
 
 fn get_display_key(key: &str) -> String {
-    if let Some(last_part) = key.split(':').last() {
+    if let Some(last_part) = key.split(':').next_back() {
         last_part.to_string()
     } else {
         key.to_string()
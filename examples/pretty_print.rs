@@ -57,12 +57,11 @@ fn print_variables(config: &Config) {
         // Variables are stored with their $ prefix
         if !key.contains(':') && !key.starts_with('$') {
             // Check if it's using a variable
-            if let Ok(value) = config.get(key) {
-                if let ConfigValue::String(s) = value {
-                    if !s.is_empty() {
-                        continue;
-                    }
-                }
+            if let Ok(value) = config.get(key)
+                && let ConfigValue::String(s) = value
+                && !s.is_empty()
+            {
+                continue;
             }
         }
     }
@@ -70,19 +69,19 @@ fn print_variables(config: &Config) {
     // Print all root-level values that look like variables
     println!("  Variables defined:");
     for key in &all_keys {
-        if !key.contains(':') {
-            if let Ok(value) = config.get(key) {
-                match value {
-                    ConfigValue::String(s) if !s.is_empty() && !key.starts_with('$') => {
-                        println!("  ${:<20} = \"{}\"", key, s);
-                        var_count += 1;
-                    }
-                    ConfigValue::Int(i) if !key.starts_with('$') => {
-                        println!("  ${:<20} = {}", key, i);
-                        var_count += 1;
-                    }
-                    _ => {}
+        if !key.contains(':')
+            && let Ok(value) = config.get(key)
+        {
+            match value {
+                ConfigValue::String(s) if !s.is_empty() && !key.starts_with('$') => {
+                    println!("  ${:<20} = \"{}\"", key, s);
+                    var_count += 1;
+                }
+                ConfigValue::Int(i) if !key.starts_with('$') => {
+                    println!("  ${:<20} = {}", key, i);
+                    var_count += 1;
                 }
+                _ => {}
             }
         }
     }
@@ -106,12 +105,12 @@ fn print_categorized_values(config: &Config) {
 
                 by_category
                     .entry(category)
-                    .or_insert_with(Vec::new)
+                    .or_default()
                     .push((subkey, value.clone()));
             } else if !is_variable_like(key, value) {
                 by_category
                     .entry("".to_string())
-                    .or_insert_with(Vec::new)
+                    .or_default()
                     .push((key.to_string(), value.clone()));
             }
         }
@@ -174,10 +173,10 @@ fn print_summary(config: &Config) {
     // Count categories
     let mut categories = std::collections::HashSet::new();
     for key in &all_keys {
-        if let Some(category) = key.split(':').next() {
-            if key.contains(':') {
-                categories.insert(category);
-            }
+        if let Some(category) = key.split(':').next()
+            && key.contains(':')
+        {
+            categories.insert(category);
         }
     }
 
@@ -227,6 +226,7 @@ fn format_value(value: &ConfigValue) -> String {
         }
         ConfigValue::Vec2(v) => format!("({}, {})", v.x, v.y),
         ConfigValue::Color(c) => format_color(c),
+        ConfigValue::Quantity { value, unit } => format!("{}{}", value, unit),
         ConfigValue::Custom { type_name, .. } => format!("<{}>", type_name),
     }
 }
@@ -243,7 +243,7 @@ fn get_indent_level(key: &str) -> usize {
 }
 
 fn get_display_key(key: &str) -> String {
-    if let Some(last_part) = key.split(':').last() {
+    if let Some(last_part) = key.split(':').next_back() {
         last_part.to_string()
     } else {
         key.to_string()
@@ -0,0 +1,296 @@
+//! Building configs programmatically
+//!
+//! A [`ConfigBuilder`] collects variables, values, handler calls and categories in the
+//! order they are added and renders them as hyprlang source. Building parses that
+//! source, so the resulting [`Config`] holds both the values and (with the `mutation`
+//! feature) a document that serializes back to the same, consistently formatted text.
+
+use crate::config::Config;
+use crate::error::ParseResult;
+use crate::special_categories::SpecialCategoryDescriptor;
+use crate::types::{Color, ConfigValue};
+use std::fmt;
+
+const INDENT: &str = "    ";
+
+#[derive(Debug, Clone)]
+enum Item {
+    Variable(String, String),
+    Value(String, String),
+    Handler(String, String),
+    Category {
+        name: String,
+        key: Option<String>,
+        body: ConfigBuilder,
+    },
+}
+
+/// Fluent builder for a config written from scratch
+///
+/// Handler keywords and special categories used by the builder are registered on the
+/// target config if it doesn't already know them. Values are written verbatim apart
+/// from `#`, which is escaped, so they may refer to `$VARIABLES` or contain
+/// `{{expressions}}`.
+///
+/// # Example
+///
+/// ```
+/// use hyprlang::ConfigBuilder;
+///
+/// let builder = ConfigBuilder::new()
+///     .variable("mod", "SUPER")
+///     .category("general", |c| c.int("gaps_in", 5).string("layout", "dwindle"))
+///     .handler("bind", "$mod, Q, exec, kitty");
+///
+/// assert_eq!(
+///     builder.to_source(),
+///     "$mod = SUPER\ngeneral {\n    gaps_in = 5\n    layout = dwindle\n}\nbind = $mod, Q, exec, kitty\n"
+/// );
+///
+/// let config = builder.build().unwrap();
+/// assert_eq!(config.get_int("general:gaps_in").unwrap(), 5);
+/// assert_eq!(config.get_handler_calls("bind").unwrap()[0], "SUPER, Q, exec, kitty");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ConfigBuilder {
+    items: Vec<Item>,
+}
+
+impl ConfigBuilder {
+    /// Create an empty builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Define a variable (`$name = value`)
+    pub fn variable(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.items.push(Item::Variable(name.into(), value.into()));
+        self
+    }
+
+    /// Set a key to a raw value
+    pub fn raw(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.items.push(Item::Value(key.into(), value.into()));
+        self
+    }
+
+    /// Set a key to an integer
+    pub fn int(self, key: impl Into<String>, value: i64) -> Self {
+        self.raw(key, value.to_string())
+    }
+
+    /// Set a key to a float, written so it reads back as a float
+    pub fn float(self, key: impl Into<String>, value: f64) -> Self {
+        self.raw(key, ConfigValue::format_float(value))
+    }
+
+    /// Set a key to a string
+    pub fn string(self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.raw(key, value)
+    }
+
+    /// Set a key to `true` or `false`
+    pub fn bool(self, key: impl Into<String>, value: bool) -> Self {
+        self.raw(key, value.to_string())
+    }
+
+    /// Set a key to a color, written as `rgba(RRGGBBAA)`
+    pub fn color(self, key: impl Into<String>, value: Color) -> Self {
+        self.raw(key, format_color(value))
+    }
+
+    /// Set a key to any config value
+    pub fn value(self, key: impl Into<String>, value: impl Into<ConfigValue>) -> Self {
+        let raw = match &value.into() {
+            ConfigValue::Float(v) => ConfigValue::format_float(*v),
+            ConfigValue::Color(c) => format_color(*c),
+            ConfigValue::Vec2(v) => format!("{}, {}", v.x, v.y),
+            other => other.to_string(),
+        };
+        self.raw(key, raw)
+    }
+
+    /// Add a handler call (`keyword = value`)
+    pub fn handler(mut self, keyword: impl Into<String>, value: impl Into<String>) -> Self {
+        self.items.push(Item::Handler(keyword.into(), value.into()));
+        self
+    }
+
+    /// Add a category block whose contents are added by the closure
+    pub fn category<F>(mut self, name: impl Into<String>, build: F) -> Self
+    where
+        F: FnOnce(ConfigBuilder) -> ConfigBuilder,
+    {
+        self.items.push(Item::Category {
+            name: name.into(),
+            key: None,
+            body: build(ConfigBuilder::new()),
+        });
+        self
+    }
+
+    /// Add a keyed special category block (`name[key] { ... }`) whose contents are added by
+    /// the closure
+    pub fn special_category<F>(
+        mut self,
+        name: impl Into<String>,
+        key: impl Into<String>,
+        build: F,
+    ) -> Self
+    where
+        F: FnOnce(ConfigBuilder) -> ConfigBuilder,
+    {
+        self.items.push(Item::Category {
+            name: name.into(),
+            key: Some(key.into()),
+            body: build(ConfigBuilder::new()),
+        });
+        self
+    }
+
+    /// Whether nothing has been added
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Render the config source, with block contents indented by four spaces
+    pub fn to_source(&self) -> String {
+        let mut output = String::new();
+        self.write_items(&mut output, 0);
+        output
+    }
+
+    /// Build a new [`Config`]
+    pub fn build(&self) -> ParseResult<Config> {
+        let mut config = Config::new();
+        self.build_into(&mut config)?;
+        Ok(config)
+    }
+
+    /// Parse the built source into an existing config
+    ///
+    /// Use this to build on top of a config with handlers, special categories or
+    /// options already registered, e.g. from a [`ConfigProfile`](crate::ConfigProfile).
+    pub fn build_into(&self, config: &mut Config) -> ParseResult<()> {
+        self.register(config, &mut Vec::new());
+        config.parse(&self.to_source())
+    }
+
+    /// Register handlers and special categories the target config doesn't know yet
+    fn register(&self, config: &mut Config, path: &mut Vec<String>) {
+        for item in &self.items {
+            match item {
+                Item::Handler(keyword, _) if !config.has_handler(path, keyword) => {
+                    if path.is_empty() {
+                        config.register_handler_fn(keyword.as_str(), |_| Ok(()));
+                    } else {
+                        config.register_category_handler_fn(
+                            path.join(":"),
+                            keyword.as_str(),
+                            |_| Ok(()),
+                        );
+                    }
+                }
+                Item::Category { name, key, body } => {
+                    let segment = match key {
+                        Some(key) => {
+                            let mut category = path.clone();
+                            category.push(name.clone());
+                            let category = category.join(":");
+                            if !config.is_special_category(&category) {
+                                config.register_special_category(SpecialCategoryDescriptor::keyed(
+                                    category, "name",
+                                ));
+                            }
+                            format!("{}[{}]", name, key)
+                        }
+                        None => name.clone(),
+                    };
+                    path.push(segment);
+                    body.register(config, path);
+                    path.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn write_items(&self, output: &mut String, depth: usize) {
+        let indent = INDENT.repeat(depth);
+
+        for item in &self.items {
+            match item {
+                Item::Variable(name, value) => {
+                    output.push_str(&format!("{}${} = {}\n", indent, name, escape(value)));
+                }
+                Item::Value(key, value) | Item::Handler(key, value) => {
+                    output.push_str(&format!("{}{} = {}\n", indent, key, escape(value)));
+                }
+                Item::Category { name, key, body } => {
+                    match key {
+                        Some(key) => output.push_str(&format!("{}{}[{}] {{\n", indent, name, key)),
+                        None => output.push_str(&format!("{}{} {{\n", indent, name)),
+                    }
+                    body.write_items(output, depth + 1);
+                    output.push_str(&format!("{}}}\n", indent));
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for ConfigBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_source())
+    }
+}
+
+/// Escape `#` so it isn't read as the start of a comment
+fn escape(value: &str) -> String {
+    value.replace('#', "##")
+}
+
+/// Write a color as `rgba(rrggbbaa)`
+pub(crate) fn format_color(color: Color) -> String {
+    format!("rgba({:08x})", color.to_rgba())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nested_blocks() {
+        let source = ConfigBuilder::new()
+            .int("a", 1)
+            .int("b", 2)
+            .category("outer", |c| {
+                c.int("x", 1)
+                    .category("inner", |c| c.int("y", 2))
+                    .int("z", 3)
+            })
+            .category("next", |c| c)
+            .to_source();
+
+        assert_eq!(
+            source,
+            "a = 1\nb = 2\nouter {\n    x = 1\n    inner {\n        y = 2\n    }\n    z = 3\n}\nnext {\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_value_formatting() {
+        let source = ConfigBuilder::new()
+            .float("f", 2.0)
+            .float("g", 0.25)
+            .bool("b", true)
+            .color("c", Color::from_rgba(0x33, 0xcc, 0xff, 0xee))
+            .string("s", "a # b")
+            .to_source();
+
+        assert_eq!(
+            source,
+            "f = 2.0\ng = 0.25\nb = true\nc = rgba(33ccffee)\ns = a ## b\n"
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! Compatibility with the upstream C++ hyprlang implementation
+//!
+//! The parser follows the behavior exercised by upstream hyprlang's test corpus
+//! (`tests/config/config.conf`, checked by the `tests/spec` suite). The few places
+//! where this crate knowingly behaves differently are listed by [`CompatibilityReport`].
+
+use std::fmt;
+
+/// A behavior where this crate differs from upstream hyprlang
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Divergence {
+    /// Stable identifier for the behavior
+    pub id: &'static str,
+
+    /// What upstream hyprlang does
+    pub upstream: &'static str,
+
+    /// What this crate does instead
+    pub current: &'static str,
+}
+
+const DIVERGENCES: &[Divergence] = &[
+    Divergence {
+        id: "quoted-strings",
+        upstream: "double quotes around a value are kept: `key = \"a\"` yields `\"a\"`",
+        current: "surrounding double quotes are stripped from values and variables: \
+                  `key = \"a\"` yields `a`; handler calls keep them",
+    },
+    Divergence {
+        id: "unregistered-keys",
+        upstream: "assigning a key that was not registered with a default is an error, \
+                   which `# hyprlang noerror true` suppresses",
+        current: "any key is accepted and stored, so `noerror` has no unknown keys to suppress",
+    },
+    Divergence {
+        id: "nested-category-handlers",
+        upstream: "a handler registered for `category:keyword` only runs directly inside `category`",
+        current: "the handler also runs for `keyword` inside nested subcategories of `category`",
+    },
+];
+
+/// Known divergences between this crate and upstream hyprlang
+///
+/// # Example
+///
+/// ```
+/// use hyprlang::CompatibilityReport;
+///
+/// let report = CompatibilityReport::current();
+/// assert!(report.diverges("quoted-strings"));
+/// for divergence in report.divergences() {
+///     println!("{}: {}", divergence.id, divergence.current);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct CompatibilityReport {
+    divergences: &'static [Divergence],
+}
+
+impl CompatibilityReport {
+    /// Report for this version of the crate
+    pub fn current() -> Self {
+        Self {
+            divergences: DIVERGENCES,
+        }
+    }
+
+    /// All known divergences
+    pub fn divergences(&self) -> &[Divergence] {
+        self.divergences
+    }
+
+    /// Look up a divergence by its identifier
+    pub fn get(&self, id: &str) -> Option<&Divergence> {
+        self.divergences.iter().find(|d| d.id == id)
+    }
+
+    /// Check whether a behavior is known to diverge from upstream
+    pub fn diverges(&self, id: &str) -> bool {
+        self.get(id).is_some()
+    }
+
+    /// Check whether no divergences are known
+    pub fn is_fully_compatible(&self) -> bool {
+        self.divergences.is_empty()
+    }
+}
+
+impl fmt::Display for CompatibilityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.divergences.is_empty() {
+            return writeln!(f, "No known divergences from upstream hyprlang");
+        }
+
+        writeln!(
+            f,
+            "{} known divergence(s) from upstream hyprlang:",
+            self.divergences.len()
+        )?;
+        for divergence in self.divergences {
+            writeln!(f, "- {}", divergence.id)?;
+            writeln!(f, "  upstream: {}", divergence.upstream)?;
+            writeln!(f, "  hyprlang-rs: {}", divergence.current)?;
+        }
+        Ok(())
+    }
+}
@@ -1,17 +1,64 @@
-use crate::error::{ConfigError, ParseResult};
-use crate::escaping::{process_escapes, restore_escaped_braces};
+#[cfg(feature = "mutation")]
+use crate::document::{ConditionalRegion, ConfigDocument, DocumentNode, SerializeOptions};
+use crate::error::{ConfigError, Diagnostic, ParseResult, Severity, SuppressedError};
+#[cfg(feature = "mutation")]
+use crate::escaping::escape_comments;
+use crate::escaping::{Syntax, escape_raw, process_escapes_with, restore_escaped_with, unquote};
 use crate::expressions::ExpressionEvaluator;
-use crate::features::{DirectiveProcessor, MultilineProcessor, SourceResolver};
-use crate::handlers::{FunctionHandler, Handler, HandlerManager};
+use crate::features::{
+    DirectiveProcessor, DirectiveState, MultilineProcessor, SourceResolver, absolute_path,
+    canonical_path, condition_variable,
+};
+use crate::handlers::{
+    FunctionHandler, Handler, HandlerCallRecord, HandlerContext, HandlerDeprecation,
+    HandlerManager, HandlerState, RejectedHandlerCall, UnifiedEntry, UnknownHandlerCall,
+    downcast_state,
+};
+#[cfg(feature = "mutation")]
+use crate::migration::{MigratedBlock, Migration, MigrationChange, MigrationReport, MigrationRule};
+#[cfg(feature = "mutation")]
+use crate::mutation::SaveReport;
 use crate::parser::{HyprlangParser, Statement, Value};
-use crate::special_categories::{SpecialCategoryDescriptor, SpecialCategoryManager};
-use crate::types::{Color, ConfigValue, ConfigValueEntry, CustomValueType, Vec2};
+use crate::profile::{ConfigProfile, option_accepts};
+use crate::special_categories::{
+    CategoryInstanceView, SpecialCategoryDescriptor, SpecialCategoryInstance,
+    SpecialCategoryManager, SpecialCategoryType,
+};
+use crate::stats::{ConfigStats, FileStats};
+use crate::theme::{Theme, glob_matches};
+use crate::types::{
+    CategoryDefinition, CategoryMerge, Color, ConfigValue, ConfigValueEntry, CustomValueType,
+    ExpansionTrace, ExpressionStep, FromConfigValue, KeyCase, Unit, ValueKind, VariableEntry,
+    VariableSource, VariableSubstitution, Vec2, normalize_key,
+};
 use crate::variables::VariableManager;
-use std::collections::HashMap;
+use std::any::Any;
+use std::borrow::Cow;
+use std::cell::OnceCell;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// Validator callback registered with [`Config::add_validator`]
+type ValidatorFn = Rc<dyn Fn(&ConfigValue) -> Result<(), String>>;
+
+/// Validator registered with [`Config::add_handler_validator`]
+type HandlerValidatorFn = Rc<dyn Fn(&HandlerContext) -> Result<(), String>>;
+
+/// Hook registered with [`Config::set_redaction_hook`]
+type RedactionFn = Rc<dyn Fn(&str, &str) -> Option<String>>;
+
+/// Text shown in place of a redacted value
+const REDACTED: &str = "[redacted]";
+type SpecialBlockValues = (String, String, Vec<(String, ConfigValueEntry)>);
 
 /// Main configuration manager
+///
+/// Cloning produces an independent working copy: values, variables, special category
+/// instances and documents are deep-copied, while registered handlers, validators and
+/// custom types are shared with the original.
+#[derive(Clone)]
 pub struct Config {
     /// Configuration values: category_path:key -> value
     values: HashMap<String, ConfigValueEntry>,
@@ -19,9 +66,31 @@ pub struct Config {
     /// Handler call values (stored as arrays): handler_name -> [values]
     handler_calls: HashMap<String, Vec<String>>,
 
+    /// Flags and source locations for `handler_calls`, index for index
+    handler_call_records: HashMap<String, Vec<HandlerCallRecord>>,
+
+    /// Sequence number for the next handler call record
+    handler_call_sequence: u64,
+
+    /// Records of the calls handlers rejected, whose lines the documents still hold
+    rejected_call_records: HashMap<String, Vec<HandlerCallRecord>>,
+
     /// Variable manager
     variables: VariableManager,
 
+    /// Where each variable was defined
+    variable_sources: HashMap<String, VariableSource>,
+
+    /// Text each variable was defined with, before escapes and expansion
+    variable_definitions: HashMap<String, String>,
+
+    /// Variables referenced outside variable definitions, which keep the variables they
+    /// depend on in use too
+    variable_uses: HashSet<String>,
+
+    /// Variables set with `seed_variable` and not defined by the config since
+    seeded_variables: HashSet<String>,
+
     /// Expression evaluator
     expressions: ExpressionEvaluator,
 
@@ -34,6 +103,37 @@ pub struct Config {
     /// Custom type handlers
     custom_types: HashMap<String, Rc<dyn CustomValueType>>,
 
+    /// Keys whose values are parsed as numbers with a unit suffix
+    quantity_keys: HashSet<String>,
+
+    /// Keys registered with `register_option`
+    registered_options: HashSet<String>,
+
+    /// Keys whose values keep `{{...}}` as written
+    literal_keys: HashSet<String>,
+
+    /// Other paths to a value: alias -> canonical key
+    key_aliases: HashMap<String, String>,
+
+    /// Aliases that values were written with in the config: canonical key -> alias
+    key_spellings: HashMap<String, String>,
+
+    /// Colors that `rgb()`/`rgba()` can refer to by name
+    named_colors: HashMap<String, Color>,
+
+    /// Value validators: key or category prefix -> validator, in registration order
+    validators: Vec<(String, ValidatorFn)>,
+
+    /// Handler call validators: keyword or `category:keyword` -> validator, in
+    /// registration order
+    handler_validators: Vec<(String, HandlerValidatorFn)>,
+
+    /// Glob patterns of keys whose values are masked when displayed
+    redactions: Vec<String>,
+
+    /// Masks parts of displayed values: (key, text) -> replacement
+    redaction_hook: Option<RedactionFn>,
+
     /// Directive processor
     directives: DirectiveProcessor,
 
@@ -46,9 +146,38 @@ pub struct Config {
     /// Current category path (for nested categories)
     current_path: Vec<String>,
 
+    /// Values assigned inside the special category blocks being processed, innermost
+    /// last: (category, key prefix of the block) -> (property, entry)
+    special_block_values: Vec<SpecialBlockValues>,
+
+    /// Application state passed to stateful handlers
+    handler_state: HandlerState,
+
     /// Collected errors (when throw_all_errors is enabled)
     errors: Vec<ConfigError>,
 
+    /// Errors ignored under `# hyprlang noerror true`
+    suppressed_errors: Vec<SuppressedError>,
+
+    /// Handler calls written with a deprecated alias
+    deprecations: Vec<HandlerDeprecation>,
+
+    /// Handler calls their handler rejected, which were not stored
+    rejected_handler_calls: Vec<RejectedHandlerCall>,
+
+    /// Regular category and special category instance blocks by full path, in the order
+    /// they were parsed
+    category_definitions: HashMap<String, Vec<CategoryDefinition>>,
+
+    /// Blocks repeating an earlier one, under [`CategoryMerge::Warn`]
+    category_redefinitions: Vec<CategoryDefinition>,
+
+    /// Handler-style lines that reached no handler, when capturing them: full key -> calls
+    unknown_handlers: HashMap<String, Vec<UnknownHandlerCall>>,
+
+    /// Errors found by a running `check`, which records them instead of stopping
+    check_diagnostics: Option<Vec<Diagnostic>>,
+
     /// Document structure (for full-fidelity serialization)
     #[cfg(feature = "mutation")]
     document: Option<crate::document::ConfigDocument>,
@@ -61,9 +190,50 @@ pub struct Config {
     #[cfg(feature = "mutation")]
     multi_document: Option<crate::document::MultiFileDocument>,
 
-    /// Current source file being parsed (for key tracking)
+    /// Order in which values, variables and handler calls were first declared
+    /// (used by synthetic serialization when no document exists)
+    #[cfg(feature = "mutation")]
+    declaration_order: Vec<Declaration>,
+
+    /// Special category headers written with variables: expanded path -> path as written,
+    /// e.g. `device[mouse]` -> `device[$MOUSE]`
     #[cfg(feature = "mutation")]
+    header_spellings: HashMap<String, String>,
+
+    /// Current source file being parsed (for key tracking and entry metadata)
     current_source_file: Option<PathBuf>,
+
+    /// Counter bumped on every value write
+    generation: u64,
+
+    /// Text of non-string values, formatted on the first
+    /// [`get_str_cow`](Config::get_str_cow) and kept for the generation it was written at
+    value_text: HashMap<String, (u64, OnceCell<String>)>,
+
+    /// Number of files parsed, including `source`d ones
+    files_parsed: usize,
+
+    /// Number of source lines parsed
+    lines_parsed: usize,
+
+    /// Time spent in `parse` and `parse_file`
+    parse_duration: Duration,
+
+    /// Metrics of each parsed file, in the order they were opened
+    file_stats: Vec<FileStats>,
+
+    /// Index in `file_stats` of the file being parsed
+    current_file_stats: Option<usize>,
+
+    /// Directive state at the end of each parsed file or string
+    directive_states: Vec<DirectiveState>,
+
+    /// Time spent in files that finished parsing, each counted once with the files it
+    /// sources
+    sourced_duration: Duration,
+
+    /// Number of errors attributed to a file so far, to count each error where it arose
+    errors_counted: usize,
 }
 
 /// Configuration options
@@ -77,6 +247,58 @@ pub struct ConfigOptions {
 
     /// Base directory for resolving source directives
     pub base_dir: Option<PathBuf>,
+
+    /// Record `keyword = value` lines that reach no registered handler or registered
+    /// option in [`Config::unknown_handlers`]; they are still stored as values
+    pub capture_unknown_handlers: bool,
+
+    /// Evaluate `{{expr}}` in values; when false every value keeps its braces as written,
+    /// as values marked with [`Config::mark_literal`] do
+    pub evaluate_expressions: bool,
+
+    /// Treat an undefined `$VAR` in a handler value as an error instead of keeping it as
+    /// written
+    pub strict_handler_variables: bool,
+
+    /// Build documents of the parsed files for saving them
+    ///
+    /// Configs that are only read can turn this off to save the time and memory. Saving
+    /// them then fails, and `serialize` falls back to synthetic output.
+    #[cfg(feature = "mutation")]
+    pub track_document: bool,
+
+    /// Whether keys differing only in letter case are the same key; see [`normalize_key`]
+    pub key_case: KeyCase,
+
+    /// Deepest nesting of category blocks a parsed file may have; deeper blocks are a
+    /// parse error (defaults to [`HyprlangParser::DEFAULT_MAX_DEPTH`])
+    pub max_nesting_depth: usize,
+
+    /// What happens when a category block or a keyed special category instance appears
+    /// more than once (merging their values by default)
+    pub category_merge: CategoryMerge,
+
+    /// Call handlers with [`HandlerContext::dry_run`] set, for tools that validate a
+    /// config without acting on it; handlers are expected to check their value and skip
+    /// side effects. [`Config::check`] then runs handlers too, instead of only checking
+    /// that they exist.
+    pub dry_run_handlers: bool,
+
+    /// How variables and expressions are written in values (`$VAR` and `{{expr}}` by
+    /// default), for applications whose values already use those characters
+    pub syntax: Syntax,
+
+    /// Resolve symlinks in the paths of parsed and sourced files (the default)
+    ///
+    /// When false, files are tracked by the absolute path they were reached through, so
+    /// [`Config::get_key_source_file`] names the symlink and saves write through it rather
+    /// than to the resolved target, as dotfile repositories expect.
+    pub canonicalize_paths: bool,
+
+    /// Report variables that are never used as warnings from [`Config::check`]
+    ///
+    /// See [`Config::unused_variables`].
+    pub warn_unused_variables: bool,
 }
 
 impl Default for ConfigOptions {
@@ -85,26 +307,73 @@ impl Default for ConfigOptions {
             throw_all_errors: false,
             allow_dynamic_parsing: true,
             base_dir: None,
+            capture_unknown_handlers: false,
+            evaluate_expressions: true,
+            strict_handler_variables: false,
+            #[cfg(feature = "mutation")]
+            track_document: true,
+            key_case: KeyCase::Sensitive,
+            max_nesting_depth: HyprlangParser::DEFAULT_MAX_DEPTH,
+            category_merge: CategoryMerge::Merge,
+            dry_run_handlers: false,
+            syntax: Syntax::default(),
+            canonicalize_paths: true,
+            warn_unused_variables: false,
         }
     }
 }
 
+/// Something declared in the config, in the order it first appeared
+#[cfg(feature = "mutation")]
+#[derive(Debug, Clone)]
+enum Declaration {
+    Variable(String),
+    Value(String),
+    HandlerCall(String),
+}
+
 impl Config {
     /// Create a new configuration with default options
     pub fn new() -> Self {
         Self {
             values: HashMap::new(),
             handler_calls: HashMap::new(),
+            handler_call_records: HashMap::new(),
+            handler_call_sequence: 0,
+            rejected_call_records: HashMap::new(),
             variables: VariableManager::new(),
+            variable_sources: HashMap::new(),
+            variable_definitions: HashMap::new(),
+            variable_uses: HashSet::new(),
+            seeded_variables: HashSet::new(),
             expressions: ExpressionEvaluator::new(),
             handlers: HandlerManager::new(),
             special_categories: SpecialCategoryManager::new(),
             custom_types: HashMap::new(),
+            quantity_keys: HashSet::new(),
+            registered_options: HashSet::new(),
+            literal_keys: HashSet::new(),
+            key_aliases: HashMap::new(),
+            key_spellings: HashMap::new(),
+            named_colors: HashMap::new(),
+            redactions: Vec::new(),
+            redaction_hook: None,
+            validators: Vec::new(),
+            handler_validators: Vec::new(),
             directives: DirectiveProcessor::new(),
             source_resolver: None,
             options: ConfigOptions::default(),
             current_path: Vec::new(),
+            special_block_values: Vec::new(),
+            handler_state: HandlerState::default(),
             errors: Vec::new(),
+            suppressed_errors: Vec::new(),
+            deprecations: Vec::new(),
+            rejected_handler_calls: Vec::new(),
+            category_definitions: HashMap::new(),
+            category_redefinitions: Vec::new(),
+            unknown_handlers: HashMap::new(),
+            check_diagnostics: None,
             #[cfg(feature = "mutation")]
             document: None,
             #[cfg(feature = "mutation")]
@@ -112,27 +381,68 @@ impl Config {
             #[cfg(feature = "mutation")]
             multi_document: None,
             #[cfg(feature = "mutation")]
+            declaration_order: Vec::new(),
+            #[cfg(feature = "mutation")]
+            header_spellings: HashMap::new(),
             current_source_file: None,
+            generation: 0,
+            value_text: HashMap::new(),
+            files_parsed: 0,
+            lines_parsed: 0,
+            parse_duration: Duration::ZERO,
+            file_stats: Vec::new(),
+            current_file_stats: None,
+            directive_states: Vec::new(),
+            sourced_duration: Duration::ZERO,
+            errors_counted: 0,
         }
     }
 
     /// Create a new configuration with custom options
     pub fn with_options(options: ConfigOptions) -> Self {
-        let source_resolver = options.base_dir.as_ref().map(SourceResolver::new);
+        let source_resolver = options.base_dir.as_ref().map(|base_dir| {
+            SourceResolver::new(base_dir).with_canonicalize(options.canonicalize_paths)
+        });
 
         Self {
             values: HashMap::new(),
             handler_calls: HashMap::new(),
-            variables: VariableManager::new(),
+            handler_call_records: HashMap::new(),
+            handler_call_sequence: 0,
+            rejected_call_records: HashMap::new(),
+            variables: VariableManager::with_syntax(options.syntax.clone()),
+            variable_sources: HashMap::new(),
+            variable_definitions: HashMap::new(),
+            variable_uses: HashSet::new(),
+            seeded_variables: HashSet::new(),
             expressions: ExpressionEvaluator::new(),
             handlers: HandlerManager::new(),
             special_categories: SpecialCategoryManager::new(),
             custom_types: HashMap::new(),
+            quantity_keys: HashSet::new(),
+            registered_options: HashSet::new(),
+            literal_keys: HashSet::new(),
+            key_aliases: HashMap::new(),
+            key_spellings: HashMap::new(),
+            named_colors: HashMap::new(),
+            redactions: Vec::new(),
+            redaction_hook: None,
+            validators: Vec::new(),
+            handler_validators: Vec::new(),
             directives: DirectiveProcessor::new(),
             source_resolver,
             options,
             current_path: Vec::new(),
+            special_block_values: Vec::new(),
+            handler_state: HandlerState::default(),
             errors: Vec::new(),
+            suppressed_errors: Vec::new(),
+            deprecations: Vec::new(),
+            rejected_handler_calls: Vec::new(),
+            category_definitions: HashMap::new(),
+            category_redefinitions: Vec::new(),
+            unknown_handlers: HashMap::new(),
+            check_diagnostics: None,
             #[cfg(feature = "mutation")]
             document: None,
             #[cfg(feature = "mutation")]
@@ -140,7 +450,20 @@ impl Config {
             #[cfg(feature = "mutation")]
             multi_document: None,
             #[cfg(feature = "mutation")]
+            declaration_order: Vec::new(),
+            #[cfg(feature = "mutation")]
+            header_spellings: HashMap::new(),
             current_source_file: None,
+            generation: 0,
+            value_text: HashMap::new(),
+            files_parsed: 0,
+            lines_parsed: 0,
+            parse_duration: Duration::ZERO,
+            file_stats: Vec::new(),
+            current_file_stats: None,
+            directive_states: Vec::new(),
+            sourced_duration: Duration::ZERO,
+            errors_counted: 0,
         }
     }
 
@@ -155,21 +478,21 @@ impl Config {
     /// Parse a configuration file
     pub fn parse_file(&mut self, path: impl AsRef<Path>) -> ParseResult<()> {
         let path = path.as_ref();
-        let canonical_path = path
-            .canonicalize()
-            .unwrap_or_else(|_| path.to_path_buf());
+        let canonical_path = self.tracked_path(path);
 
         // Set base dir from file path if not already set
         if self.options.base_dir.is_none()
             && let Some(parent) = path.parent()
         {
             self.options.base_dir = Some(parent.to_path_buf());
-            self.source_resolver = Some(SourceResolver::new(parent));
+            self.source_resolver = Some(
+                SourceResolver::new(parent).with_canonicalize(self.options.canonicalize_paths),
+            );
         }
 
         // Initialize multi_document if this is the primary file
         #[cfg(feature = "mutation")]
-        let is_primary = self.multi_document.is_none();
+        let is_primary = self.multi_document.is_none() && self.options.track_document;
 
         #[cfg(feature = "mutation")]
         if is_primary {
@@ -180,35 +503,102 @@ impl Config {
         }
 
         // Parse the file with path tracking
-        self.parse_file_internal(&canonical_path)
+        let started = Instant::now();
+        let result = self.parse_file_internal(&canonical_path);
+        self.parse_duration += started.elapsed();
+        result
+    }
+
+    /// The path a file is tracked by, following [`ConfigOptions::canonicalize_paths`]
+    fn tracked_path(&self, path: &Path) -> PathBuf {
+        let tracked = if self.options.canonicalize_paths {
+            canonical_path(path)
+        } else {
+            absolute_path(path)
+        };
+        tracked.unwrap_or_else(|_| path.to_path_buf())
     }
 
     /// Internal method to parse a file with path tracking
     fn parse_file_internal(&mut self, path: &Path) -> ParseResult<()> {
+        let started = Instant::now();
         let content = std::fs::read_to_string(path)
             .map_err(|e| ConfigError::io(path.display().to_string(), e.to_string()))?;
 
-        // Set current source file for key tracking
-        #[cfg(feature = "mutation")]
-        {
-            self.current_source_file = Some(path.to_path_buf());
-        }
+        // Set current source file for key tracking, restoring the parent file afterwards
+        // so values following a `source =` line are attributed correctly
+        let parent_file = self.current_source_file.replace(path.to_path_buf());
+        let index = self.file_stats.len();
+        self.file_stats.push(FileStats {
+            path: path.to_path_buf(),
+            bytes: content.len(),
+            lines: content.lines().count(),
+            statements: 0,
+            duration: Duration::ZERO,
+            errors: 0,
+        });
+        let parent_stats = self.current_file_stats.replace(index);
+        let sourced_before = self.sourced_duration;
 
         // Parse the content
-        self.parse_with_path(&content, Some(path))
+        let result = self.parse_with_path(&content, Some(path));
+        self.current_source_file = parent_file;
+        self.current_file_stats = parent_stats;
+
+        let elapsed = started.elapsed();
+        let in_sourced = self.sourced_duration.saturating_sub(sourced_before);
+        self.file_stats[index].duration = elapsed.saturating_sub(in_sourced);
+        self.sourced_duration = sourced_before + elapsed;
+        result
+    }
+
+    /// Count an error against the file being parsed
+    fn count_file_error(&mut self) {
+        self.errors_counted += 1;
+        if let Some(index) = self.current_file_stats {
+            self.file_stats[index].errors += 1;
+        }
     }
 
     /// Parse content with an associated file path
+    #[cfg_attr(not(feature = "mutation"), allow(unused_variables))]
     fn parse_with_path(&mut self, input: &str, source_path: Option<&Path>) -> ParseResult<()> {
         self.commence()?;
+        self.files_parsed += usize::from(source_path.is_some());
+        self.lines_parsed += input.lines().count();
 
+        let input = &*self.options.syntax.to_grammar(input);
+        let max_depth = self.options.max_nesting_depth;
         #[cfg(feature = "mutation")]
-        let (parsed, mut document) = HyprlangParser::parse_with_document(input)?;
+        let parsed = if self.options.track_document {
+            HyprlangParser::parse_with_document_limit(input, max_depth)
+                .map(|(parsed, document)| (parsed, Some(document)))
+        } else {
+            HyprlangParser::parse_config_with_limit(input, max_depth).map(|parsed| (parsed, None))
+        };
         #[cfg(not(feature = "mutation"))]
-        let parsed = HyprlangParser::parse_config(input)?;
+        let parsed = HyprlangParser::parse_config_with_limit(input, max_depth);
+        let parsed = match parsed {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                self.count_file_error();
+                if self.check_diagnostics.is_none() {
+                    return Err(error);
+                }
+                let line = error.line();
+                self.record_check_error(error, line);
+                return Ok(());
+            }
+        };
+        #[cfg(feature = "mutation")]
+        let (parsed, document) = parsed;
 
         #[cfg(feature = "mutation")]
-        {
+        if let Some(mut document) = document {
+            if self.options.syntax != Syntax::default() {
+                restore_variable_syntax(&mut document, &self.options.syntax);
+            }
+
             // Set the source path on the document
             if let Some(path) = source_path {
                 document.source_path = Some(path.to_path_buf());
@@ -223,8 +613,24 @@ impl Config {
             self.document = Some(document);
         }
 
-        for statement in parsed.statements {
-            if let Err(e) = self.process_statement(&statement) {
+        if source_path.is_some()
+            && let Some(index) = self.current_file_stats
+        {
+            self.file_stats[index].statements = count_statements(&parsed.statements);
+        }
+
+        // Directives such as `noerror` apply until the end of the file they are in
+        let outer_directives = std::mem::take(&mut self.directives);
+        let result = self.process_file_statements(&parsed.statements);
+        let state = self.directives.state(source_path.map(Path::to_path_buf));
+        self.directive_states.push(state);
+        self.directives = outer_directives;
+        result
+    }
+
+    fn process_file_statements(&mut self, statements: &[Statement]) -> ParseResult<()> {
+        for statement in statements {
+            if let Err(e) = self.process_statement(statement) {
                 if self.options.throw_all_errors {
                     self.errors.push(e);
                 } else {
@@ -242,7 +648,10 @@ impl Config {
 
     /// Parse a configuration string
     pub fn parse(&mut self, input: &str) -> ParseResult<()> {
-        self.parse_with_path(input, None)
+        let started = Instant::now();
+        let result = self.parse_with_path(input, None);
+        self.parse_duration += started.elapsed();
+        result
     }
 
     /// Parse a single line dynamically (after initial parse)
@@ -251,23 +660,197 @@ impl Config {
             return Err(ConfigError::custom("Dynamic parsing is not enabled"));
         }
 
-        let parsed = HyprlangParser::parse_config(line)?;
+        let line = self.options.syntax.to_grammar(line);
+        let parsed =
+            HyprlangParser::parse_config_with_limit(&line, self.options.max_nesting_depth)?;
 
         for statement in parsed.statements {
             self.process_statement(&statement)?;
         }
 
+        // Errors collected under `throw_all_errors` while the line was kept
+        if !self.errors.is_empty() {
+            return Err(ConfigError::multiple(std::mem::take(&mut self.errors)));
+        }
+
         Ok(())
     }
 
+    /// Check a configuration string without changing this config
+    ///
+    /// The input goes through the same steps as [`parse`](Self::parse) on a copy of this
+    /// config, so registered handlers, options, validators and special categories apply.
+    /// Every error is reported rather than only the first, and handler calls are checked
+    /// against the registered handlers without running them (or run as dry runs, under
+    /// [`ConfigOptions::dry_run_handlers`]). Deprecated handler aliases and,
+    /// with `capture_unknown_handlers`, lines that reach no handler are reported as warnings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyprlang::{Config, Severity};
+    ///
+    /// let mut config = Config::new();
+    /// config.register_handler_fn("bind", |_| Ok(()));
+    ///
+    /// let diagnostics = config.check("bind = SUPER, Q, exec, kitty\nsize = {{2 +}}\n");
+    /// assert_eq!(diagnostics.len(), 1);
+    /// assert_eq!(diagnostics[0].severity, Severity::Error);
+    /// assert_eq!(diagnostics[0].line, Some(2));
+    /// assert!(config.get_handler_calls("bind").is_none());
+    /// ```
+    pub fn check(&self, input: &str) -> Vec<Diagnostic> {
+        self.check_with(|config| config.parse(input))
+    }
+
+    /// Check a configuration file, and the files it sources, without changing this config
+    ///
+    /// See [`check`](Self::check).
+    pub fn check_file(&self, path: impl AsRef<Path>) -> Vec<Diagnostic> {
+        let path = path.as_ref();
+        self.check_with(|config| config.parse_file(path))
+    }
+
+    fn check_with(&self, parse: impl FnOnce(&mut Config) -> ParseResult<()>) -> Vec<Diagnostic> {
+        let mut config = self.clone();
+        config.options.throw_all_errors = false;
+        config.deprecations.clear();
+        config.rejected_handler_calls.clear();
+        config.clear_category_definitions();
+        config.unknown_handlers.clear();
+        config.check_diagnostics = Some(Vec::new());
+
+        let result = parse(&mut config);
+        let mut diagnostics = config.check_diagnostics.take().unwrap_or_default();
+        if let Err(error) = result {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Error,
+                line: error.line(),
+                message: error.to_string(),
+                file: None,
+            });
+        }
+
+        for deprecation in &config.deprecations {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Warning,
+                message: format!(
+                    "'{}' is deprecated, use '{}' instead",
+                    deprecation.alias, deprecation.canonical
+                ),
+                file: deprecation.file.clone(),
+                line: Some(deprecation.line),
+            });
+        }
+        for rejected in &config.rejected_handler_calls {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Warning,
+                message: format!("'{}' call rejected: {}", rejected.handler, rejected.reason),
+                file: rejected.file.clone(),
+                line: Some(rejected.line),
+            });
+        }
+        for repeated in &config.category_redefinitions {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Warning,
+                message: format!("category '{}' was already defined", repeated.category),
+                file: repeated.file.clone(),
+                line: Some(repeated.line),
+            });
+        }
+        if config.options.warn_unused_variables {
+            for entry in config.unused_variables() {
+                diagnostics.push(Diagnostic {
+                    severity: Severity::Warning,
+                    message: format!(
+                        "variable '{}' is never used",
+                        config.options.syntax.variable(&entry.name)
+                    ),
+                    file: entry.file,
+                    line: entry.line,
+                });
+            }
+        }
+        for (key, calls) in &config.unknown_handlers {
+            for call in calls {
+                diagnostics.push(Diagnostic {
+                    severity: Severity::Warning,
+                    message: format!("'{}' is not a known handler or option", key),
+                    file: call.file.clone(),
+                    line: Some(call.line),
+                });
+            }
+        }
+
+        diagnostics.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+        diagnostics
+    }
+
     fn process_statement(&mut self, statement: &Statement) -> ParseResult<()> {
+        let counted = self.errors_counted;
+        let result = self.execute_statement(statement);
+        // Errors from inside blocks and sourced files were counted where they arose
+        if result.is_err() && self.errors_counted == counted {
+            self.count_file_error();
+        }
+
+        // Under `# hyprlang noerror true` errors are recorded instead of returned.
+        // Block errors come from their statements, which record their own.
+        match result {
+            Err(error)
+                if self.directives.should_suppress_errors()
+                    && !matches!(statement, Statement::CommentDirective { .. }) =>
+            {
+                let line = match statement {
+                    Statement::VariableDef { line, .. }
+                    | Statement::Assignment { line, .. }
+                    | Statement::HandlerCall { line, .. } => Some(*line),
+                    _ => None,
+                };
+                self.suppressed_errors.push(SuppressedError {
+                    error,
+                    file: self.current_source_file.clone(),
+                    line,
+                });
+                Ok(())
+            }
+            // While checking, every other error is recorded and parsing goes on
+            Err(error) if self.check_diagnostics.is_some() => {
+                let line = match statement {
+                    Statement::VariableDef { line, .. }
+                    | Statement::Assignment { line, .. }
+                    | Statement::HandlerCall { line, .. } => Some(*line),
+                    _ => error.line(),
+                };
+                self.record_check_error(error, line);
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    /// Record an error found by a running `check`
+    fn record_check_error(&mut self, error: ConfigError, line: Option<usize>) {
+        let diagnostic = Diagnostic {
+            severity: Severity::Error,
+            message: error.to_string(),
+            file: self.current_source_file.clone(),
+            line,
+        };
+        if let Some(diagnostics) = &mut self.check_diagnostics {
+            diagnostics.push(diagnostic);
+        }
+    }
+
+    fn execute_statement(&mut self, statement: &Statement) -> ParseResult<()> {
         // Check if we should execute this statement based on directives
         if !self.directives.should_execute() {
-            // Still need to process directives even when not executing
+            // Still need to track nested if/endif even when not executing
             if let Statement::CommentDirective {
                 directive_type,
                 args,
             } = statement
+                && directive_type != "noerror"
             {
                 return self.directives.process_directive(
                     directive_type,
@@ -275,15 +858,20 @@ impl Config {
                     &self.variables,
                 );
             }
+            if !matches!(statement, Statement::CommentDirective { .. }) {
+                self.directives
+                    .suppress(count_statements(std::slice::from_ref(statement)));
+            }
             return Ok(());
         }
 
         match statement {
-            Statement::VariableDef { name, value } => {
+            Statement::VariableDef { name, value, line } => {
                 // Process escapes first, then expand variables
                 // Don't evaluate expressions here - they'll be evaluated when the variable is used
-                let escaped = process_escapes(value);
+                let escaped = process_escapes_with(value, &self.options.syntax);
                 let expanded = self.variables.expand(&escaped)?;
+                let dependencies = self.variable_references(&escaped, true);
 
                 // Track variable origin in multi_document
                 #[cfg(feature = "mutation")]
@@ -293,17 +881,40 @@ impl Config {
                     multi_doc.register_key(format!("${}", name), source_file.clone());
                 }
 
-                self.variables.set(name.clone(), expanded.clone());
+                #[cfg(feature = "mutation")]
+                if self.seeded_variables.contains(name) || !self.variables.contains(name) {
+                    self.declaration_order
+                        .push(Declaration::Variable(name.clone()));
+                }
+                self.seeded_variables.remove(name);
 
-                // Update expression evaluator if it's a number
-                if let Ok(num) = ConfigValue::parse_int(&expanded) {
+                self.variables.set(name.clone(), expanded.clone());
+                self.variables.set_dependencies(name.clone(), dependencies);
+                self.variable_sources.insert(
+                    name.clone(),
+                    VariableSource::Config {
+                        file: self.current_source_file.clone(),
+                        line: Some(*line),
+                    },
+                );
+                self.variable_definitions
+                    .insert(name.clone(), value.clone());
+
+                // Update expression evaluator if it's a number, including variables that
+                // are themselves defined by an expression such as `{{MY_VAR + 2}}`
+                let numeric = ConfigValue::parse_int(&expanded).ok().or_else(|| {
+                    self.evaluate_expressions_in_string(&expanded)
+                        .ok()
+                        .and_then(|evaluated| ConfigValue::parse_int(&evaluated).ok())
+                });
+                if let Some(num) = numeric {
                     self.expressions.set_variable(name.clone(), num);
                 }
 
                 Ok(())
             }
 
-            Statement::Assignment { key, value } => {
+            Statement::Assignment { key, value, line } => {
                 // Check if we're inside a special category block
                 // Special category paths contain brackets like "windowrule[test]"
                 let in_special_category = self.current_path.iter().any(|p| p.contains('['));
@@ -311,41 +922,66 @@ impl Config {
                 // Check if this is a potential handler call (single identifier and registered handler)
                 // But NOT if we're inside a special category (properties there should be assignments)
                 let is_potential_handler = key.len() == 1 && !in_special_category;
-                let keyword = &key[0];
+                let alias = is_potential_handler
+                    .then(|| self.handlers.resolve_alias(&key[0]))
+                    .flatten()
+                    .map(|canonical| (key[0].clone(), canonical.to_string()));
+                let written = alias.as_ref().map_or(&key[0], |(_, canonical)| canonical);
+                let (keyword, flags) = if is_potential_handler
+                    && !self.handlers.has_handler(&self.current_path, written)
+                    && let Some((keyword, flags)) =
+                        self.handlers.split_flags(&self.current_path, written)
+                {
+                    // `keywordflags = value` for handlers registered to accept flags
+                    (keyword, Some(flags))
+                } else {
+                    (written.clone(), None)
+                };
+                let keyword = &keyword;
 
                 if is_potential_handler && self.handlers.has_handler(&self.current_path, keyword) {
+                    let alias =
+                        alias.map(|(alias, _)| self.record_deprecation(alias, keyword, *line));
+
                     // Treat as handler call
                     let expanded_value = match value {
-                        Value::String(s) => self.variables.expand(s)?,
+                        Value::String(s) => {
+                            self.expand_handler_value(keyword, s.trim_end(), *line)?
+                        }
                         _ => self.value_to_string(value),
                     };
 
-                    // Create full key including category path for handler calls
-                    let full_key = if self.current_path.is_empty() {
-                        keyword.clone()
-                    } else {
-                        format!("{}:{}", self.current_path.join(":"), keyword)
-                    };
-
-                    self.handler_calls
-                        .entry(full_key.clone())
-                        .or_default()
-                        .push(expanded_value.clone());
-
-                    // Track handler origin in multi_document
-                    #[cfg(feature = "mutation")]
-                    if let (Some(multi_doc), Some(source_file)) =
-                        (&mut self.multi_document, &self.current_source_file)
-                    {
-                        multi_doc.register_handler(full_key, source_file.clone());
-                    }
-
-                    self.handlers
-                        .execute(&self.current_path, keyword, &expanded_value, None)?;
+                    self.handle_call(keyword, &expanded_value, flags, *line, alias, true)?;
                 } else {
                     // Regular assignment
-                    let full_key = self.make_full_key(key);
-                    let config_value = self.parse_config_value(value)?;
+                    let mut full_key = self.make_full_key(key);
+                    if let Some(canonical) = self.key_aliases.get(&full_key) {
+                        let canonical = canonical.clone();
+                        self.key_spellings.insert(
+                            canonical.clone(),
+                            std::mem::replace(&mut full_key, canonical),
+                        );
+                    }
+                    if is_potential_handler
+                        && self.options.capture_unknown_handlers
+                        && !self.registered_options.contains(&full_key)
+                    {
+                        let call = UnknownHandlerCall {
+                            value: self.value_to_string(value),
+                            file: self.current_source_file.clone(),
+                            line: *line,
+                        };
+                        self.unknown_handlers
+                            .entry(full_key.clone())
+                            .or_default()
+                            .push(call);
+                    }
+                    let mut config_value = self.parse_config_value(&full_key, value)?;
+                    if self.quantity_keys.contains(&full_key) {
+                        config_value = Self::parse_quantity_value(&full_key, config_value)?;
+                    }
+                    self.validate(&full_key, &config_value)?;
+                    self.check_constraints(&full_key, &config_value, *line)?;
                     let raw = self.value_to_string(value);
 
                     // Track key origin in multi_document
@@ -356,135 +992,100 @@ impl Config {
                         multi_doc.register_key(full_key.clone(), source_file.clone());
                     }
 
-                    self.values
-                        .insert(full_key, ConfigValueEntry::new(config_value, raw));
-                }
-
-                Ok(())
-            }
+                    let mut entry = ConfigValueEntry::new(config_value, raw);
+                    entry.source_file = self.current_source_file.clone();
+                    entry.line = Some(*line);
+                    entry.noerror = self.directives.should_suppress_errors();
+                    self.stamp_entry(&full_key, &mut entry);
 
-            Statement::CategoryBlock { name, statements } => {
-                self.current_path.push(name.clone());
+                    #[cfg(feature = "mutation")]
+                    if !self.values.contains_key(&full_key) {
+                        self.declaration_order
+                            .push(Declaration::Value(full_key.clone()));
+                    }
 
-                for stmt in statements {
-                    if let Err(e) = self.process_statement(stmt) {
-                        if self.options.throw_all_errors {
-                            self.errors.push(e);
-                        } else {
-                            self.current_path.pop();
-                            return Err(e);
+                    for (_, prefix, collected) in &mut self.special_block_values {
+                        if let Some(property) = full_key.strip_prefix(prefix.as_str()) {
+                            collected.push((property.to_string(), entry.clone()));
                         }
                     }
+
+                    self.values.insert(full_key, entry);
                 }
 
-                self.current_path.pop();
                 Ok(())
             }
 
-            Statement::SpecialCategoryBlock {
+            Statement::CategoryBlock {
                 name,
-                key,
                 statements,
+                line,
             } => {
-                // If category is not registered as special and has no key, treat as regular category
-                if !self.special_categories.is_registered(name) {
-                    if key.is_none() {
-                        // Fall back to regular category block behavior
-                        self.current_path.push(name.clone());
-
-                        for stmt in statements {
-                            if let Err(e) = self.process_statement(stmt) {
-                                if self.options.throw_all_errors {
-                                    self.errors.push(e);
-                                } else {
-                                    self.current_path.pop();
-                                    return Err(e);
-                                }
-                            }
-                        }
-
-                        self.current_path.pop();
-                        return Ok(());
-                    }
-                    return Err(ConfigError::category_not_found(name, None));
-                }
-
-                // Create the instance with the provided key (or auto-generate if none)
-                let instance_key = self.special_categories.create_instance(name, key.clone())?;
-
-                self.current_path
-                    .push(format!("{}[{}]", name, instance_key));
-
-                // Process statements within the category
-                for stmt in statements {
-                    if let Err(e) = self.process_statement(stmt) {
-                        if self.options.throw_all_errors {
-                            self.errors.push(e);
-                        } else {
-                            self.current_path.pop();
-                            return Err(e);
-                        }
-                    }
+                // Blocks of registered special categories create instances even without a key
+                if let Some(category) = self.special_category_name(name) {
+                    return self
+                        .process_special_category_block(&category, name, None, statements, *line);
                 }
+                self.process_category_block(name, statements, *line)
+            }
 
-                // Store values in the special category instance
-                let full_path = self.current_path.last().unwrap();
-                for (key, value) in &self.values {
-                    if key.starts_with(full_path) {
-                        let sub_key = key.strip_prefix(full_path).unwrap().trim_start_matches(':');
-
-                        if let Ok(instance) = self
-                            .special_categories
-                            .get_instance_mut(name, &instance_key)
-                        {
-                            instance.set(sub_key.to_string(), value.clone());
-                        }
+            Statement::SpecialCategoryBlock {
+                name,
+                key,
+                statements,
+                line,
+            } => {
+                let key = match key {
+                    Some(key) => Some(self.expand_category_key(name, key, *line)?),
+                    None => None,
+                };
+                match self.special_category_name(name) {
+                    Some(category) => {
+                        self.process_special_category_block(&category, name, key, statements, *line)
                     }
+                    // Unregistered blocks without a key are regular categories
+                    None if key.is_none() => self.process_category_block(name, statements, *line),
+                    None => Err(ConfigError::category_not_found(name, None)),
                 }
-
-                self.current_path.pop();
-                Ok(())
             }
 
             Statement::HandlerCall {
                 keyword,
                 flags,
                 value,
+                line,
             } => {
-                let expanded_value = self.variables.expand(value)?;
+                let (keyword, alias) = match self.handlers.resolve_alias(keyword) {
+                    Some(canonical) => {
+                        let canonical = canonical.to_string();
+                        let alias = self.record_deprecation(keyword.clone(), &canonical, *line);
+                        (canonical, Some(alias))
+                    }
+                    None => (keyword.clone(), None),
+                };
+                let keyword = &keyword;
+                let expanded_value = self.expand_handler_value(keyword, value, *line)?;
 
                 // Store the handler call value only if it's registered or at root level
                 let should_store = self.handlers.has_handler(&self.current_path, keyword)
                     || self.current_path.is_empty();
 
-                if should_store {
-                    let full_key = if self.current_path.is_empty() {
-                        keyword.clone()
-                    } else {
-                        format!("{}:{}", self.current_path.join(":"), keyword)
-                    };
-
-                    self.handler_calls
-                        .entry(full_key.clone())
-                        .or_default()
-                        .push(expanded_value.clone());
-
-                    // Track handler origin in multi_document
-                    #[cfg(feature = "mutation")]
-                    if let (Some(multi_doc), Some(source_file)) =
-                        (&mut self.multi_document, &self.current_source_file)
-                    {
-                        multi_doc.register_handler(full_key, source_file.clone());
-                    }
-                }
-
-                // Execute the handler if one is registered
-                self.handlers
-                    .execute(&self.current_path, keyword, &expanded_value, flags.clone())
+                self.handle_call(
+                    keyword,
+                    &expanded_value,
+                    flags.clone(),
+                    *line,
+                    alias,
+                    should_store,
+                )
             }
 
             Statement::Source { path } => {
+                if let Some(name) = self.variables.find_undefined(path) {
+                    return Err(ConfigError::variable_not_found(name));
+                }
                 let expanded_path = self.variables.expand(path)?;
+                self.note_variable_uses(path, false);
 
                 // Resolve and begin load
                 let resolved = if let Some(resolver) = &mut self.source_resolver {
@@ -495,10 +1096,7 @@ impl Config {
                     return Err(ConfigError::custom("Source resolver not initialized"));
                 };
 
-                // Canonicalize the resolved path
-                let canonical_resolved = resolved
-                    .canonicalize()
-                    .unwrap_or_else(|_| resolved.clone());
+                let canonical_resolved = self.tracked_path(&resolved);
 
                 // Parse the sourced file using internal method (avoids re-initializing multi_document)
                 let result = self.parse_file_internal(&canonical_resolved);
@@ -515,21 +1113,36 @@ impl Config {
                 directive_type,
                 args,
             } => {
+                if directive_type == "if"
+                    && let Some(name) = args.as_deref().and_then(condition_variable)
+                {
+                    self.variable_uses.insert(name.to_string());
+                }
                 self.directives
                     .process_directive(directive_type, args.as_deref(), &self.variables)
             }
         }
     }
 
-    fn parse_config_value(&mut self, value: &Value) -> ParseResult<ConfigValue> {
+    fn parse_config_value(&mut self, key: &str, value: &Value) -> ParseResult<ConfigValue> {
+        let evaluate = self.evaluates_expressions(key);
         match value {
+            // With other delimiters `{{expr}}` is plain text
+            Value::Expression(expr) if !evaluate || self.options.syntax != Syntax::default() => {
+                Ok(ConfigValue::String(format!("{{{{{}}}}}", expr)))
+            }
+
             Value::Expression(expr) => {
+                let used: Vec<_> = self.expression_variables(expr).collect();
+                self.variable_uses.extend(used);
                 let result = self.expressions.evaluate(expr)?;
                 Ok(ConfigValue::Int(result))
             }
 
             Value::Variable(name) => {
-                let expanded = self.variables.expand(&format!("${}", name))?;
+                let reference = self.options.syntax.variable(name);
+                self.note_variable_uses(&reference, false);
+                let expanded = self.variables.expand(&reference)?;
                 // Try to parse as a known type
                 self.parse_string_value(&expanded)
             }
@@ -552,205 +1165,517 @@ impl Config {
             Value::Boolean(b) => Ok(ConfigValue::Int(if *b { 1 } else { 0 })),
 
             Value::String(s) => {
+                // A quoted value stores the text between its quotes
+                let quoted = unquote(s);
+                let is_quoted = quoted.is_some();
+                let s = quoted.unwrap_or_else(|| s.clone());
                 // Process escapes first (converts escaped braces to placeholders)
-                let escaped = process_escapes(s);
+                let escaped = process_escapes_with(&s, &self.options.syntax);
+                self.note_variable_uses(&escaped, evaluate);
                 // Expand variables
                 let expanded = self.variables.expand(&escaped)?;
                 // Evaluate expressions (placeholders won't be evaluated)
-                let with_exprs = self.evaluate_expressions_in_string(&expanded)?;
+                let with_exprs = if evaluate {
+                    self.evaluate_expressions_in_string(&expanded)?
+                } else {
+                    expanded
+                };
                 // Restore escaped braces from placeholders to literal {{}}
-                let final_value = restore_escaped_braces(&with_exprs);
-                self.parse_string_value(&final_value)
+                let final_value = restore_escaped_with(&with_exprs, &self.options.syntax);
+                // Quoted strings keep their surrounding whitespace
+                match self.parse_string_value(&final_value)? {
+                    ConfigValue::String(_) if is_quoted => Ok(ConfigValue::String(final_value)),
+                    value => Ok(value),
+                }
             }
 
             Value::Multiline(lines) => {
                 let joined = MultilineProcessor::join_lines(lines);
                 // Process escapes before variable expansion
-                let escaped = process_escapes(&joined);
+                let escaped = process_escapes_with(&joined, &self.options.syntax);
+                self.note_variable_uses(&escaped, evaluate);
                 let expanded = self.variables.expand(&escaped)?;
                 // Evaluate expressions
-                let with_exprs = self.evaluate_expressions_in_string(&expanded)?;
+                let with_exprs = if evaluate {
+                    self.evaluate_expressions_in_string(&expanded)?
+                } else {
+                    expanded
+                };
                 // Restore escaped braces
-                let final_value = restore_escaped_braces(&with_exprs);
+                let final_value = restore_escaped_with(&with_exprs, &self.options.syntax);
                 Ok(ConfigValue::String(final_value))
             }
         }
     }
 
     fn parse_string_value(&self, s: &str) -> ParseResult<ConfigValue> {
-        let s = s.trim();
+        Ok(ConfigValue::parse_with_colors(s, &self.named_colors))
+    }
 
-        // Try to parse as various types
-        if let Ok(b) = ConfigValue::parse_bool(s) {
-            return Ok(ConfigValue::Int(if b { 1 } else { 0 }));
+    /// Turn a string like `500ms` into a [`ConfigValue::Quantity`]; unitless numbers pass through
+    fn parse_quantity_value(key: &str, value: ConfigValue) -> ParseResult<ConfigValue> {
+        match value {
+            ConfigValue::String(s) => Unit::parse_quantity(&s)
+                .map(|(value, unit)| ConfigValue::Quantity { value, unit })
+                .ok_or_else(|| {
+                    ConfigError::invalid_number(
+                        s,
+                        format!("expected a number with a unit suffix for '{}'", key),
+                    )
+                }),
+            other => Ok(other),
         }
+    }
 
-        // Try color formats: rgba(...), rgb(...), 0xHEXHEX
-        if s.starts_with("rgba(") && s.ends_with(')') {
-            if let Ok(color) = self.parse_rgba_string(s) {
-                return Ok(ConfigValue::Color(color));
-            }
-        } else if s.starts_with("rgb(") && s.ends_with(')') {
-            if let Ok(color) = self.parse_rgb_string(s) {
-                return Ok(ConfigValue::Color(color));
-            }
-        } else if s.starts_with("0x") && s.len() >= 8 && s.len() <= 10 {
-            // Hex color: 0xRRGGBB or 0xRRGGBBAA
-            if let Ok(color) = Color::from_hex(s) {
-                return Ok(ConfigValue::Color(color));
-            }
-        }
+    /// Keep `{{...}}` in a key's value as written instead of evaluating it
+    ///
+    /// Use this for values that are templates for other tools. Escapes such as `\{{`
+    /// are still processed and variables are still expanded. Handler calls never evaluate
+    /// expressions, so their values need no marking. Marking applies to values parsed
+    /// afterwards; set [`ConfigOptions::evaluate_expressions`] to false to keep braces
+    /// in every value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config.mark_literal("launcher:format");
+    /// config
+    ///     .parse("launcher {\n    format = {{name}}: {{exec}}\n    width = {{200 * 2}}\n}")
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.get_string("launcher:format").unwrap(), "{{name}}: {{exec}}");
+    /// assert_eq!(config.get_int("launcher:width").unwrap(), 400);
+    /// ```
+    pub fn mark_literal(&mut self, key: impl Into<String>) {
+        self.literal_keys.insert(key.into());
+    }
 
-        // Try Vec2: (x, y) or x, y
-        if let Ok(vec2) = self.parse_vec2_string(s) {
-            return Ok(ConfigValue::Vec2(vec2));
-        }
+    /// Check whether `{{...}}` in a key's value is evaluated
+    pub fn evaluates_expressions(&self, key: &str) -> bool {
+        self.options.evaluate_expressions && !self.literal_keys.contains(key)
+    }
 
-        if let Ok(i) = ConfigValue::parse_int(s) {
-            return Ok(ConfigValue::Int(i));
-        }
+    /// Record the variables `text` refers to as used
+    fn note_variable_uses(&mut self, text: &str, expressions: bool) {
+        let used = self.variable_references(text, expressions);
+        self.variable_uses.extend(used);
+    }
 
-        if let Ok(f) = ConfigValue::parse_float(s) {
-            return Ok(ConfigValue::Float(f));
+    /// Defined variables `text` refers to, including by name inside its expressions when
+    /// they are evaluated
+    fn variable_references(&self, text: &str, expressions: bool) -> HashSet<String> {
+        let mut names: HashSet<_> = self.variables.references(text).into_iter().collect();
+        let (open, close) = (
+            &self.options.syntax.expression_open,
+            &self.options.syntax.expression_close,
+        );
+        let mut rest = text;
+        while expressions
+            && !open.is_empty()
+            && let Some(start) = rest.find(open.as_str())
+        {
+            let body = &rest[start + open.len()..];
+            let end = body.find(close.as_str()).unwrap_or(body.len());
+            names.extend(self.expression_variables(&body[..end]));
+            rest = &body[end..];
         }
+        names
+    }
 
-        // Default to string (remove any trailing whitespace)
-        Ok(ConfigValue::String(s.to_string()))
+    /// Defined variables an expression reads, e.g. `gap` in `{{gap * 2}}`
+    fn expression_variables<'a>(&'a self, expr: &'a str) -> impl Iterator<Item = String> + 'a {
+        expr.split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .filter(|word| self.variables.contains(word))
+            .map(String::from)
     }
 
     /// Evaluate all {{expr}} expressions in a string
     fn evaluate_expressions_in_string(&self, input: &str) -> ParseResult<String> {
+        self.evaluate_expressions_traced(input, None)
+    }
+
+    /// Evaluate all {{expr}} expressions in a string, optionally recording each evaluation
+    fn evaluate_expressions_traced(
+        &self,
+        input: &str,
+        mut steps: Option<&mut Vec<ExpressionStep>>,
+    ) -> ParseResult<String> {
+        let syntax = &self.options.syntax;
+        let (open, close) = (&syntax.expression_open, &syntax.expression_close);
         let mut result = String::new();
-        let mut chars = input.chars().peekable();
-
-        while let Some(ch) = chars.next() {
-            if ch == '{' {
-                if chars.peek() == Some(&'{') {
-                    chars.next(); // consume second {
-
-                    // Find the closing }}
-                    let mut expr = String::new();
-                    let mut depth = 1;
-
-                    while let Some(c) = chars.next() {
-                        if c == '{' && chars.peek() == Some(&'{') {
-                            depth += 1;
-                            expr.push(c);
-                            chars.next();
-                            expr.push('{');
-                        } else if c == '}' && chars.peek() == Some(&'}') {
-                            depth -= 1;
-                            if depth == 0 {
-                                chars.next(); // consume second }
-                                break;
-                            }
-                            expr.push(c);
-                            chars.next();
-                            expr.push('}');
-                        } else {
-                            expr.push(c);
-                        }
-                    }
+        let mut rest = input;
+
+        while let Some(ch) = rest.chars().next() {
+            let Some(body) = rest
+                .strip_prefix(open.as_str())
+                .filter(|_| !open.is_empty())
+            else {
+                result.push(ch);
+                rest = &rest[ch.len_utf8()..];
+                continue;
+            };
 
-                    // Evaluate the expression
-                    let value = self.expressions.evaluate(&expr)?;
-                    result.push_str(&value.to_string());
+            // Find the closing }}
+            let mut expr = String::new();
+            let mut depth = 1;
+            rest = body;
+            while let Some(c) = rest.chars().next() {
+                if let Some(after) = rest.strip_prefix(open.as_str()) {
+                    depth += 1;
+                    expr.push_str(open);
+                    rest = after;
+                } else if let Some(after) = rest.strip_prefix(close.as_str()) {
+                    rest = after;
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                    expr.push_str(close);
                 } else {
-                    result.push(ch);
+                    expr.push(c);
+                    rest = &rest[c.len_utf8()..];
                 }
+            }
+
+            // Evaluate the expression, where variables may also be written without a prefix
+            let value = if *syntax == Syntax::default() {
+                self.expressions.evaluate(&expr)?
             } else {
-                result.push(ch);
+                self.expressions.evaluate(&bare_variables(&expr, syntax))?
+            };
+            result.push_str(&value.to_string());
+            if let Some(steps) = steps.as_deref_mut() {
+                steps.push(ExpressionStep {
+                    expression: expr,
+                    result: value,
+                });
             }
         }
 
         Ok(result)
     }
 
-    fn parse_rgba_string(&self, s: &str) -> ParseResult<Color> {
-        // rgba(hex) or rgba(r, g, b, a)
-        let inner = &s[5..s.len() - 1]; // Remove "rgba(" and ")"
-
-        if !inner.contains(',') {
-            // Hex format: rgba(RRGGBBAA)
-            Color::from_hex(inner)
+    /// Resolve a block name to the registered special category it opens, if any
+    ///
+    /// Special categories are registered by their full path, so `touch { }` inside
+    /// `plugin { }` opens the `plugin:touch` category.
+    fn special_category_name(&self, name: &str) -> Option<String> {
+        let category = if self.current_path.is_empty() {
+            name.to_string()
         } else {
-            // Component format: rgba(r, g, b, a)
-            let parts: Vec<&str> = inner.split(',').map(|p| p.trim()).collect();
-            if parts.len() != 4 {
-                return Err(ConfigError::invalid_color(s, "rgba needs 4 components"));
-            }
-
-            let r = parts[0]
-                .parse::<u8>()
-                .map_err(|_| ConfigError::invalid_color(s, "invalid r"))?;
-            let g = parts[1]
-                .parse::<u8>()
-                .map_err(|_| ConfigError::invalid_color(s, "invalid g"))?;
-            let b = parts[2]
-                .parse::<u8>()
-                .map_err(|_| ConfigError::invalid_color(s, "invalid b"))?;
-
-            // Alpha can be float (0.0-1.0) or int (0-255)
-            let a = if parts[3].contains('.') {
-                let a_float = parts[3]
-                    .parse::<f64>()
-                    .map_err(|_| ConfigError::invalid_color(s, "invalid a"))?;
-                (a_float * 255.0).round() as u8
-            } else {
-                parts[3]
-                    .parse::<u8>()
-                    .map_err(|_| ConfigError::invalid_color(s, "invalid a"))?
-            };
+            format!("{}:{}", self.current_path.join(":"), name)
+        };
+        self.special_categories
+            .is_registered(&category)
+            .then_some(category)
+    }
 
-            Ok(Color::from_rgba(r, g, b, a))
+    /// Process the statements of a regular category block
+    fn process_category_block(
+        &mut self,
+        name: &str,
+        statements: &[Statement],
+        line: usize,
+    ) -> ParseResult<()> {
+        self.current_path.push(name.to_string());
+        if let Err(e) = self.record_category_definition(line) {
+            self.current_path.pop();
+            return Err(e);
+        }
+
+        for stmt in statements {
+            if let Err(e) = self.process_statement(stmt) {
+                if self.options.throw_all_errors {
+                    self.errors.push(e);
+                } else {
+                    self.current_path.pop();
+                    return Err(e);
+                }
+            }
+        }
+
+        self.current_path.pop();
+        Ok(())
+    }
+
+    /// Record the block at the current path, applying [`ConfigOptions::category_merge`]
+    fn record_category_definition(&mut self, line: usize) -> ParseResult<()> {
+        let path = self.current_path.join(":");
+        let category = self.normalize_key(&path).into_owned();
+        let definition = CategoryDefinition {
+            category: category.clone(),
+            file: self.current_source_file.clone(),
+            line,
+        };
+        let definitions = self.category_definitions.entry(category).or_default();
+        let first = definitions.first().cloned();
+        definitions.push(definition.clone());
+
+        match (first, self.options.category_merge) {
+            (None, _) | (_, CategoryMerge::Merge) => Ok(()),
+            (Some(_), CategoryMerge::Warn) => {
+                self.category_redefinitions.push(definition);
+                Ok(())
+            }
+            (Some(first), CategoryMerge::Error) => Err(ConfigError::duplicate_category(
+                definition.category,
+                definition.file,
+                line,
+                first.file,
+                first.line,
+            )),
         }
     }
 
-    fn parse_rgb_string(&self, s: &str) -> ParseResult<Color> {
-        // rgb(r, g, b)
-        let inner = &s[4..s.len() - 1]; // Remove "rgb(" and ")"
-        let parts: Vec<&str> = inner.split(',').map(|p| p.trim()).collect();
+    /// Process a block of a registered special category, creating a new instance for it
+    ///
+    /// Keyed blocks use their `[key]` (or key field), static blocks share one instance and
+    /// each block of an anonymous category adds a new instance.
+    fn process_special_category_block(
+        &mut self,
+        category: &str,
+        name: &str,
+        key: Option<String>,
+        statements: &[Statement],
+        line: usize,
+    ) -> ParseResult<()> {
+        // A keyed block without `[key]` may name itself through its key field,
+        // e.g. `device { name = mouse }`
+        let key = match key {
+            Some(key) => Some(key),
+            None => self.key_from_key_field(category, statements)?,
+        };
 
-        if parts.len() != 3 {
-            return Err(ConfigError::invalid_color(s, "rgb needs 3 components"));
+        // A repeated keyed or static instance is a repeated block, checked before the
+        // instance is touched so that an error leaves it as it was
+        let category_type = self
+            .special_categories
+            .get_descriptor(category)
+            .map(|descriptor| descriptor.category_type);
+        let repeatable = match category_type {
+            Some(SpecialCategoryType::Keyed) => key.as_deref(),
+            Some(SpecialCategoryType::Static) => Some("static"),
+            _ => None,
+        };
+        if let Some(instance) = repeatable {
+            self.current_path.push(format!("{}[{}]", name, instance));
+            let recorded = self.record_category_definition(line);
+            self.current_path.pop();
+            recorded?;
         }
 
-        let r = parts[0]
-            .parse::<u8>()
-            .map_err(|_| ConfigError::invalid_color(s, "invalid r"))?;
-        let g = parts[1]
-            .parse::<u8>()
-            .map_err(|_| ConfigError::invalid_color(s, "invalid g"))?;
-        let b = parts[2]
-            .parse::<u8>()
-            .map_err(|_| ConfigError::invalid_color(s, "invalid b"))?;
+        // An earlier block for the same instance keeps its values
+        let earlier: Vec<(String, ConfigValueEntry)> = self
+            .special_categories
+            .get_instance(category, key.as_deref().unwrap_or("static"))
+            .map(|instance| {
+                instance
+                    .values
+                    .iter()
+                    .filter(|(_, entry)| entry.set_by_user)
+                    .map(|(property, entry)| (property.clone(), entry.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // Create the instance with the provided key (or auto-generate if none)
+        let instance_key = self.special_categories.create_instance(category, key)?;
+
+        self.current_path
+            .push(format!("{}[{}]", name, instance_key));
+        self.special_block_values.push((
+            category.to_string(),
+            format!("{}:", self.current_path.join(":")),
+            earlier,
+        ));
+
+        // Process statements within the category
+        let mut result = Ok(());
+        for stmt in statements {
+            if let Err(e) = self.process_statement(stmt) {
+                if self.options.throw_all_errors {
+                    self.errors.push(e);
+                } else {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
 
-        Ok(Color::from_rgb(r, g, b))
-    }
+        self.current_path.pop();
+        let collected = self
+            .special_block_values
+            .pop()
+            .map(|(_, _, collected)| collected)
+            .unwrap_or_default();
+        result?;
+
+        // Store values in the special category instance
+        if let Ok(instance) = self
+            .special_categories
+            .get_instance_mut(category, &instance_key)
+        {
+            for (property, entry) in collected {
+                instance.set(property, entry);
+            }
+        }
 
-    fn parse_vec2_string(&self, s: &str) -> ParseResult<Vec2> {
-        // Try (x, y) format
-        if s.starts_with('(') && s.ends_with(')') {
-            let inner = &s[1..s.len() - 1];
-            let parts: Vec<&str> = inner.split(',').map(|p| p.trim()).collect();
+        self.check_required_properties(category, &instance_key, line)
+    }
 
-            if parts.len() == 2
-                && let (Ok(x), Ok(y)) = (parts[0].parse::<f64>(), parts[1].parse::<f64>())
-            {
-                return Ok(Vec2::new(x, y));
+    /// Check a value assigned in special category blocks against their descriptors'
+    /// constraints
+    fn check_constraints(&self, key: &str, value: &ConfigValue, line: usize) -> ParseResult<()> {
+        for (category, prefix, _) in &self.special_block_values {
+            let constraint = key.strip_prefix(prefix.as_str()).and_then(|property| {
+                self.special_categories
+                    .get_descriptor(category)?
+                    .constraints
+                    .get(property)
+            });
+
+            if let Some(constraint) = constraint {
+                constraint.check(value).map_err(|reason| {
+                    ConfigError::constraint_violation(
+                        key,
+                        value.to_string(),
+                        reason,
+                        self.current_source_file.clone(),
+                        line,
+                    )
+                })?;
             }
-        } else if s.contains(',') {
-            // Try x, y format without parentheses
-            let parts: Vec<&str> = s.split(',').map(|p| p.trim()).collect();
+        }
+        Ok(())
+    }
+
+    /// Fail when an instance lacks properties its descriptor requires
+    fn check_required_properties(
+        &self,
+        category: &str,
+        instance_key: &str,
+        line: usize,
+    ) -> ParseResult<()> {
+        let (Some(descriptor), Ok(instance)) = (
+            self.special_categories.get_descriptor(category),
+            self.special_categories.get_instance(category, instance_key),
+        ) else {
+            return Ok(());
+        };
+
+        let missing: Vec<String> = descriptor
+            .required
+            .iter()
+            .filter(|pattern| {
+                !instance
+                    .values
+                    .iter()
+                    .any(|(property, entry)| entry.set_by_user && glob_matches(pattern, property))
+            })
+            .cloned()
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        Err(ConfigError::missing_properties(
+            category,
+            instance.key.clone(),
+            missing,
+            self.current_source_file.clone(),
+            line,
+        ))
+    }
+
+    /// Expand the variables in a block's `[key]`, which must all be defined
+    ///
+    /// The document keeps the key as written; with the `mutation` feature, values set
+    /// in the instance later are written to that block.
+    fn expand_category_key(&mut self, name: &str, key: &str, line: usize) -> ParseResult<String> {
+        if let Some(variable) = self.variables.find_undefined(key) {
+            return Err(ConfigError::parse(
+                line,
+                1,
+                format!(
+                    "variable '{}' in the key of '{}[{}]' is not defined",
+                    self.options.syntax.variable(&variable),
+                    name,
+                    key
+                ),
+            ));
+        }
+        let expanded = self.variables.expand(key)?;
+        self.note_variable_uses(key, false);
+
+        #[cfg(feature = "mutation")]
+        if expanded != key {
+            let parent = self.current_path.join(":");
+            let (path, written) = if parent.is_empty() {
+                (
+                    format!("{}[{}]", name, expanded),
+                    format!("{}[{}]", name, key),
+                )
+            } else {
+                (
+                    format!("{}:{}[{}]", parent, name, expanded),
+                    format!("{}:{}[{}]", self.written_path(&parent), name, key),
+                )
+            };
+            self.header_spellings.insert(path, written);
+        }
+
+        Ok(expanded)
+    }
+
+    /// A key or category path as written in the document, with the headers written with
+    /// variables in place of their expanded form
+    #[cfg(feature = "mutation")]
+    fn written_path(&self, path: &str) -> String {
+        let header = self
+            .header_spellings
+            .iter()
+            .filter(|(expanded, _)| {
+                path.strip_prefix(expanded.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with(':'))
+            })
+            .max_by_key(|(expanded, _)| expanded.len());
+        match header {
+            Some((expanded, written)) => format!("{}{}", written, &path[expanded.len()..]),
+            None => path.to_string(),
+        }
+    }
+
+    /// Find the instance key assigned to a keyed special category's key field in its block
+    fn key_from_key_field(
+        &mut self,
+        category: &str,
+        statements: &[Statement],
+    ) -> ParseResult<Option<String>> {
+        let Some(key_field) = self
+            .special_categories
+            .get_descriptor(category)
+            .and_then(|descriptor| descriptor.key_field.as_deref())
+        else {
+            return Ok(None);
+        };
 
-            if parts.len() == 2
-                && let (Ok(x), Ok(y)) = (parts[0].parse::<f64>(), parts[1].parse::<f64>())
+        for stmt in statements {
+            if let Statement::Assignment { key, value, .. } = stmt
+                && key.len() == 1
+                && key[0] == key_field
             {
-                return Ok(Vec2::new(x, y));
+                let raw = self.value_to_string(value);
+                let raw = unquote(&raw).unwrap_or(raw);
+                let expanded = self.variables.expand(&raw)?;
+                self.note_variable_uses(&raw, false);
+                return Ok(Some(expanded.trim().to_string()));
             }
         }
 
-        Err(ConfigError::custom("not a valid Vec2"))
+        Ok(None)
     }
 
     fn value_to_string(&self, value: &Value) -> String {
@@ -766,22 +1691,259 @@ impl Config {
         }
     }
 
+    /// Record the category path and write generation on an entry about to be stored
+    fn stamp_entry(&mut self, key: &str, entry: &mut ConfigValueEntry) {
+        self.generation += 1;
+        entry.generation = self.generation;
+        if !matches!(
+            entry.value,
+            ConfigValue::String(_) | ConfigValue::Custom { .. }
+        ) {
+            self.value_text
+                .insert(key.to_string(), (self.generation, OnceCell::new()));
+        }
+        entry.category_path = key
+            .rsplit_once(':')
+            .map(|(path, _)| path.split(':').map(String::from).collect())
+            .unwrap_or_default();
+    }
+
     fn make_full_key(&self, key: &[String]) -> String {
-        if self.current_path.is_empty() {
+        let full_key = if self.current_path.is_empty() {
             key.join(":")
         } else {
             format!("{}:{}", self.current_path.join(":"), key.join(":"))
+        };
+        match self.normalize_key(&full_key) {
+            Cow::Borrowed(_) => full_key,
+            Cow::Owned(normalized) => normalized,
         }
     }
 
-    /// Get a configuration value
-    pub fn get(&self, key: &str) -> ParseResult<&ConfigValue> {
+    /// The canonical form of `key` that values are stored under
+    ///
+    /// Getters and setters accept keys in any form that normalizes to the same canonical
+    /// key, so `"general : gaps_in"` reads `general:gaps_in`; with
+    /// [`KeyCase::Insensitive`] in the options, so does `"General:Gaps_In"`. See
+    /// [`normalize_key`](crate::normalize_key).
+    ///
+    /// ```rust
+    /// use hyprlang::{Config, ConfigOptions, KeyCase};
+    ///
+    /// let mut config = Config::with_options(ConfigOptions {
+    ///     key_case: KeyCase::Insensitive,
+    ///     ..Default::default()
+    /// });
+    /// config.parse("General {\n    Gaps_In = 5\n}").unwrap();
+    ///
+    /// assert_eq!(config.normalize_key("General : Gaps_In"), "general:gaps_in");
+    /// assert_eq!(config.get_int("general:gaps_in").unwrap(), 5);
+    /// assert_eq!(config.get_int(" GENERAL:gaps_in").unwrap(), 5);
+    /// ```
+    pub fn normalize_key<'a>(&self, key: &'a str) -> Cow<'a, str> {
+        normalize_key(key, self.options.key_case)
+    }
+
+    /// Make `alias` another path to the value of `canonical`
+    ///
+    /// Reading or setting either key reads or sets the same entry, which is stored under
+    /// `canonical`. A value written in the config under the alias stays written that way:
+    /// mutations update the line as the user spelled it. Useful for options that were
+    /// renamed, so code can use the new name while older configs keep working.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config.alias_key("general:col.active_border", "general:active_border_color");
+    /// config.parse("general {\n    active_border_color = rgb(ff0000)\n}").unwrap();
+    ///
+    /// assert_eq!(config.get_color("general:col.active_border").unwrap().r, 255);
+    /// assert_eq!(config.get_color("general:active_border_color").unwrap().r, 255);
+    /// assert_eq!(config.keys(), vec!["general:col.active_border"]);
+    /// ```
+    pub fn alias_key(&mut self, canonical: impl Into<String>, alias: impl Into<String>) {
+        let canonical = self.normalize_key(&canonical.into()).into_owned();
+        let alias = self.normalize_key(&alias.into()).into_owned();
+
+        // A value already parsed under the alias moves to the canonical key
+        if !self.values.contains_key(&canonical)
+            && let Some(mut entry) = self.values.remove(&alias)
+        {
+            self.stamp_entry(&canonical, &mut entry);
+            self.values.insert(canonical.clone(), entry);
+            self.key_spellings.insert(canonical.clone(), alias.clone());
+            #[cfg(feature = "mutation")]
+            for declaration in &mut self.declaration_order {
+                if matches!(declaration, Declaration::Value(key) if *key == alias) {
+                    *declaration = Declaration::Value(canonical.clone());
+                }
+            }
+        }
+        self.key_aliases.insert(alias, canonical);
+    }
+
+    /// Aliases registered with [`alias_key`](Self::alias_key): alias -> canonical key
+    pub fn key_aliases(&self) -> &HashMap<String, String> {
+        &self.key_aliases
+    }
+
+    /// The key a value is stored under: the canonical form of `key`, with aliases resolved
+    fn resolve_key<'a>(&self, key: &'a str) -> Cow<'a, str> {
+        let key = self.normalize_key(key);
+        match self.key_aliases.get(key.as_ref()) {
+            Some(canonical) => Cow::Owned(canonical.clone()),
+            None => key,
+        }
+    }
+
+    /// The entry stored under `key` or the key it resolves to
+    fn lookup(&self, key: &str) -> Option<&ConfigValueEntry> {
         self.values
             .get(key)
+            .or_else(|| self.values.get(self.resolve_key(key).as_ref()))
+    }
+
+    /// Get a configuration value
+    pub fn get(&self, key: &str) -> ParseResult<&ConfigValue> {
+        self.lookup(key)
             .map(|entry| &entry.value)
             .ok_or_else(|| ConfigError::key_not_found(key))
     }
 
+    /// Get a configuration value together with its metadata (origin, category path, generation)
+    ///
+    /// ```rust
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config.parse("decoration {\n    blur {\n        size = 8\n    }\n}").unwrap();
+    ///
+    /// let entry = config.entry("decoration:blur:size").unwrap();
+    /// assert_eq!(entry.line, Some(3));
+    /// assert_eq!(entry.category_path, vec!["decoration", "blur"]);
+    /// assert!(entry.set_by_user);
+    /// ```
+    pub fn entry(&self, key: &str) -> Option<&ConfigValueEntry> {
+        self.lookup(key)
+    }
+
+    /// Explain how the value of `key` was produced
+    ///
+    /// Replays the expansion of the value's raw text, recording each variable substitution
+    /// (with where the variable was defined) and each `{{expr}}` evaluation. Variables are
+    /// resolved against their current values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config
+    ///     .parse("$WIDTH = 800\ntotal_width = {{$WIDTH * 2}}")
+    ///     .unwrap();
+    ///
+    /// let trace = config.explain("total_width").unwrap();
+    /// assert_eq!(trace.raw, "{{$WIDTH * 2}}");
+    /// assert_eq!(trace.substitutions[0].name, "WIDTH");
+    /// assert_eq!(trace.substitutions[0].value, "800");
+    /// assert_eq!(trace.expressions[0].expression, "800 * 2");
+    /// assert_eq!(trace.expressions[0].result, 1600);
+    /// println!("{}", trace);
+    /// ```
+    pub fn explain(&self, key: &str) -> ParseResult<ExpansionTrace> {
+        let key = self.resolve_key(key);
+        let key = key.as_ref();
+        let entry = self
+            .values
+            .get(key)
+            .ok_or_else(|| ConfigError::key_not_found(key))?;
+
+        let mut substitutions = Vec::new();
+        let expanded = self.variables.expand_traced(
+            &process_escapes_with(&entry.raw, &self.options.syntax),
+            &mut substitutions,
+        )?;
+
+        let mut expressions = Vec::new();
+        if self.evaluates_expressions(key) {
+            self.evaluate_expressions_traced(&expanded, Some(&mut expressions))?;
+        }
+
+        let substitutions = substitutions
+            .into_iter()
+            .map(|(name, value)| {
+                let source = match self.variable_sources.get(&name) {
+                    Some(source) => source.clone(),
+                    None if self.variables.contains(&name) => VariableSource::Config {
+                        file: None,
+                        line: None,
+                    },
+                    None => VariableSource::Environment,
+                };
+                VariableSubstitution {
+                    name,
+                    value,
+                    source,
+                }
+            })
+            .collect();
+
+        Ok(ExpansionTrace {
+            key: key.to_string(),
+            raw: entry.raw.clone(),
+            substitutions,
+            expanded: restore_escaped_with(&expanded, &self.options.syntax),
+            expressions,
+            value: entry.value.clone(),
+            source_file: entry.source_file.clone(),
+            line: entry.line,
+        })
+    }
+
+    /// Errors ignored because they occurred under `# hyprlang noerror true`, in the
+    /// order they occurred
+    ///
+    /// `noerror` applies from the directive until `# hyprlang noerror false` or the end of
+    /// the file it is in. Suppressed errors accumulate across parses until
+    /// [`clear_suppressed_errors`](Config::clear_suppressed_errors) is called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config
+    ///     .parse("# hyprlang noerror true\ngaps = {{1 / 0}}\n# hyprlang noerror false\nborder = 2")
+    ///     .unwrap();
+    ///
+    /// let suppressed = config.suppressed_errors();
+    /// assert_eq!(suppressed.len(), 1);
+    /// assert_eq!(suppressed[0].line, Some(2));
+    /// assert!(config.get("gaps").is_err());
+    /// assert_eq!(config.get_int("border").unwrap(), 2);
+    /// ```
+    pub fn suppressed_errors(&self) -> &[SuppressedError] {
+        &self.suppressed_errors
+    }
+
+    /// Forget the errors recorded by [`suppressed_errors`](Config::suppressed_errors)
+    pub fn clear_suppressed_errors(&mut self) {
+        self.suppressed_errors.clear();
+    }
+
+    /// Current write generation
+    ///
+    /// Increases every time a value is stored, so comparing it with
+    /// [`ConfigValueEntry::generation`] tells which values changed since a given point.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
     /// Get a configuration value as a specific type
     pub fn get_int(&self, key: &str) -> ParseResult<i64> {
         self.get(key)?.as_int()
@@ -799,18 +1961,165 @@ impl Config {
         self.get(key)?.as_vec2()
     }
 
+    /// Get any value as text, borrowing instead of allocating where possible
+    ///
+    /// Strings are borrowed as stored. Other values are formatted on the first read and
+    /// the text is kept until the value is next written, so later reads borrow it too.
+    /// Values written without going through the `Config` are formatted on every read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyprlang::Config;
+    /// use std::borrow::Cow;
+    ///
+    /// let mut config = Config::new();
+    /// config.parse("layout = dwindle\ngaps = 5").unwrap();
+    ///
+    /// assert!(matches!(config.get_str_cow("layout").unwrap(), Cow::Borrowed("dwindle")));
+    /// assert_eq!(config.get_str_cow("gaps").unwrap(), "5");
+    /// ```
+    pub fn get_str_cow(&self, key: &str) -> ParseResult<Cow<'_, str>> {
+        let (key, entry) = self
+            .values
+            .get_key_value(key)
+            .or_else(|| self.values.get_key_value(self.resolve_key(key).as_ref()))
+            .ok_or_else(|| ConfigError::key_not_found(key))?;
+
+        match self.value_text.get(key) {
+            Some((generation, text)) if *generation == entry.generation => {
+                Ok(Cow::Borrowed(text.get_or_init(|| entry.value.to_string())))
+            }
+            _ => Ok(entry.text()),
+        }
+    }
+
     pub fn get_color(&self, key: &str) -> ParseResult<Color> {
         self.get(key)?.as_color()
     }
 
+    /// Get the first of several keys that is set and converts to `T`
+    ///
+    /// Useful when an option has been renamed between versions: list the current name
+    /// first and older names after it. Keys that are missing or hold a different type are
+    /// skipped. If none match, the conversion error of the last key that was set is
+    /// returned, or a key-not-found error if none were set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyprlang::{Config, Vec2};
+    ///
+    /// let mut config = Config::new();
+    /// config.parse("old_gaps = 5\nnew_offset = 10, 20").unwrap();
+    ///
+    /// assert_eq!(config.get_first::<i64>(&["new_gaps", "old_gaps"]).unwrap(), 5);
+    /// assert_eq!(
+    ///     config.get_first::<Vec2>(&["new_offset", "old_offset"]).unwrap(),
+    ///     Vec2::new(10.0, 20.0)
+    /// );
+    /// assert!(config.get_first::<i64>(&["missing", "also_missing"]).is_err());
+    /// ```
+    pub fn get_first<T: FromConfigValue>(&self, keys: &[&str]) -> ParseResult<T> {
+        let mut last_error = None;
+
+        for key in keys {
+            if let Some(entry) = self.lookup(key) {
+                match T::from_config_value(&entry.value) {
+                    Ok(value) => return Ok(value),
+                    Err(e) => last_error = Some(e),
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| ConfigError::key_not_found(keys.join(", "))))
+    }
+
+    /// Get the first of several keys that is set to a color
+    ///
+    /// Shorthand for [`get_first::<Color>`](Config::get_first).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config.parse("general:col.border = rgba(33ccffee)").unwrap();
+    ///
+    /// let color = config
+    ///     .get_color_first(&["general:col.active_border", "general:col.border"])
+    ///     .unwrap();
+    /// assert_eq!(color.a, 0xee);
+    /// ```
+    pub fn get_color_first(&self, keys: &[&str]) -> ParseResult<Color> {
+        self.get_first(keys)
+    }
+
+    /// Get a value registered with [`register_quantity_key`](Config::register_quantity_key)
+    /// as a number and its unit
+    pub fn get_quantity(&self, key: &str) -> ParseResult<(f64, Unit)> {
+        self.get(key)?.as_quantity()
+    }
+
+    /// Get a quantity converted to `unit` (e.g. `ms` to `s`, `deg` to `rad`)
+    ///
+    /// ```rust
+    /// use hyprlang::{Config, Unit};
+    ///
+    /// let mut config = Config::new();
+    /// config.register_quantity_key("animations:duration");
+    /// config.parse("animations {\n    duration = 500ms\n}").unwrap();
+    ///
+    /// assert_eq!(
+    ///     config.get_quantity("animations:duration").unwrap(),
+    ///     (500.0, Unit::Milliseconds)
+    /// );
+    /// assert_eq!(config.get_quantity_in("animations:duration", Unit::Seconds).unwrap(), 0.5);
+    /// assert!(config.get_quantity_in("animations:duration", Unit::Degrees).is_err());
+    /// ```
+    pub fn get_quantity_in(&self, key: &str, unit: Unit) -> ParseResult<f64> {
+        self.get(key)?.as_quantity_in(unit)
+    }
+
     /// Set a configuration value directly
-    pub fn set(&mut self, key: impl Into<String>, value: ConfigValue) {
-        let key = key.into();
-        let raw = value.to_string();
+    ///
+    /// Accepts a [`ConfigValue`] or anything convertible to one: integers, floats,
+    /// [`Color`], [`Vec2`] or an `(x, y)` pair, and text, which is read the way an unquoted
+    /// config value is (`"5"` stores an integer).
+    ///
+    /// Returns an error, leaving the config untouched, if a validator registered with
+    /// [`add_validator`](Config::add_validator) rejects the value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyprlang::{Color, Config};
+    ///
+    /// let mut config = Config::new();
+    /// config.set("general:gaps_in", 5).unwrap();
+    /// config.set("general:border_size", "2").unwrap();
+    /// config.set("decoration:active_opacity", 0.9).unwrap();
+    /// config.set("general:col.active_border", Color::from_rgb(51, 204, 255)).unwrap();
+    ///
+    /// let gaps: i64 = config.get("general:gaps_in").unwrap().clone().try_into().unwrap();
+    /// assert_eq!(gaps, 5);
+    /// assert_eq!(config.get_int("general:border_size").unwrap(), 2);
+    /// ```
+    pub fn set(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<ConfigValue>,
+    ) -> ParseResult<()> {
+        let written = key.into();
+        let value = value.into();
+        let (key, raw) = self.prepare_set(&written, &value)?;
 
         // Update document tree if mutation feature is enabled
         #[cfg(feature = "mutation")]
         {
+            let document_key = self.document_key(&key, &written);
+            let document_value = escape_comments(&raw);
             // Try to update in the correct source file using multi_document
             let updated_in_multi = if let Some(multi_doc) = &mut self.multi_document {
                 // Find which file this key belongs to
@@ -821,7 +2130,7 @@ impl Config {
 
                 // Update the document in that file
                 if let Some(doc) = multi_doc.get_document_mut(&source_file) {
-                    let _ = doc.update_or_insert_value(&key, &raw);
+                    let _ = doc.update_or_insert_value(&document_key, &document_value);
                     multi_doc.mark_dirty(&source_file);
 
                     // If this is a new key, register it with the primary file
@@ -840,34 +2149,203 @@ impl Config {
             if !updated_in_multi
                 && let Some(doc) = &mut self.document
             {
-                let _ = doc.update_or_insert_value(&key, &raw);
+                let _ = doc.update_or_insert_value(&document_key, &document_value);
             }
-        }
 
-        self.values.insert(key, ConfigValueEntry::new(value, raw));
-    }
+            if document_key != key {
+                self.key_spellings.insert(key.clone(), document_key);
+            }
+        }
 
-    /// Check if a key exists
-    pub fn contains(&self, key: &str) -> bool {
-        self.values.contains_key(key)
+        self.store_set_value(key, value, raw);
+        Ok(())
     }
 
-    /// Register a handler
-    pub fn register_handler<H>(&mut self, keyword: impl Into<String>, handler: H)
+    /// Set several values, editing each document in one pass
+    ///
+    /// Behaves like calling [`set`](Config::set) for each pair in order, but a document
+    /// gets all its new values before its index is rebuilt once, and each file is marked
+    /// dirty once, which makes generating large configs much faster. Every value is
+    /// checked first: if one is rejected, the config is left untouched.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyprlang::{Config, ConfigValue};
+    ///
+    /// let mut config = Config::new();
+    /// config
+    ///     .set_many([
+    ///         ("general:gaps_in", ConfigValue::Int(5)),
+    ///         ("general:gaps_out", ConfigValue::Int(10)),
+    ///         ("general:layout", ConfigValue::String("dwindle".to_string())),
+    ///     ])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.get_int("general:gaps_out").unwrap(), 10);
+    /// ```
+    pub fn set_many<K, V>(&mut self, values: impl IntoIterator<Item = (K, V)>) -> ParseResult<()>
     where
-        H: Handler + 'static,
+        K: Into<String>,
+        V: Into<ConfigValue>,
     {
-        self.handlers.register_global(keyword, handler);
-    }
+        let mut prepared = Vec::new();
+        for (written, value) in values {
+            let written = written.into();
+            let value = value.into();
+            let (key, raw) = self.prepare_set(&written, &value)?;
+            prepared.push((written, key, value, raw));
+        }
 
-    /// Register a function handler
-    pub fn register_handler_fn<F>(&mut self, keyword: impl Into<String>, handler: F)
-    where
-        F: Fn(&crate::handlers::HandlerContext) -> ParseResult<()> + 'static,
-    {
-        let keyword = keyword.into();
-        self.handlers
-            .register_global(keyword.clone(), FunctionHandler::new(keyword, handler));
+        #[cfg(feature = "mutation")]
+        self.write_document_values(&prepared);
+
+        for (_, key, value, raw) in prepared {
+            self.store_set_value(key, value, raw);
+        }
+        Ok(())
+    }
+
+    /// Resolve and validate a value about to be set, returning its key and raw text
+    fn prepare_set(&self, written: &str, value: &ConfigValue) -> ParseResult<(String, String)> {
+        let key = self.resolve_key(written).into_owned();
+        self.validate(&key, value)?;
+        // Strings are escaped so that they read back unchanged
+        let raw = match value {
+            ConfigValue::String(text) => {
+                escape_raw(text, &self.options.syntax).ok_or_else(|| {
+                    ConfigError::validation(&key, text, "cannot be written as a config value")
+                })?
+            }
+            _ => self.value_text(value),
+        };
+        Ok((key, raw))
+    }
+
+    /// The key a set value is written with in the document, keeping the spelling the
+    /// value was first written with
+    #[cfg(feature = "mutation")]
+    fn document_key(&self, key: &str, written: &str) -> String {
+        match self.key_spellings.get(key) {
+            Some(spelling) => spelling.clone(),
+            None => self.written_path(&self.normalize_key(written)),
+        }
+    }
+
+    /// Write prepared values of [`set_many`](Config::set_many) to their documents, one
+    /// batch per document
+    #[cfg(feature = "mutation")]
+    fn write_document_values(&mut self, prepared: &[(String, String, ConfigValue, String)]) {
+        // Keys and document values for each file, or for the single document under `None`
+        type Batch<'a> = (Option<PathBuf>, Vec<&'a str>, Vec<(String, String)>);
+        let mut batches: Vec<Batch> = Vec::new();
+        for (written, key, _, raw) in prepared {
+            let document_key = self.document_key(key, written);
+            let file = self.multi_document.as_ref().and_then(|multi_doc| {
+                let file = multi_doc
+                    .get_key_source(key)
+                    .unwrap_or(&multi_doc.primary_path);
+                multi_doc.get_document(file).map(|_| file.clone())
+            });
+            let index = match batches.iter().position(|(path, _, _)| *path == file) {
+                Some(index) => index,
+                None => {
+                    batches.push((file, Vec::new(), Vec::new()));
+                    batches.len() - 1
+                }
+            };
+            batches[index].1.push(key);
+            batches[index]
+                .2
+                .push((document_key.clone(), escape_comments(raw)));
+            if document_key != *key {
+                self.key_spellings.insert(key.clone(), document_key);
+            }
+        }
+
+        for (file, keys, values) in batches {
+            match (file, &mut self.multi_document) {
+                (Some(file), Some(multi_doc)) => {
+                    if let Some(doc) = multi_doc.get_document_mut(&file) {
+                        let _ = doc.update_or_insert_values(&values);
+                    }
+                    multi_doc.mark_dirty(&file);
+                    for key in keys {
+                        if multi_doc.get_key_source(key).is_none() {
+                            multi_doc.register_key(key.to_string(), file.clone());
+                        }
+                    }
+                }
+                _ => {
+                    if let Some(doc) = &mut self.document {
+                        let _ = doc.update_or_insert_values(&values);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Store a value set at runtime
+    fn store_set_value(&mut self, key: String, value: ConfigValue, raw: String) {
+        // Keep pointing at the original definition when overwriting a parsed value
+        let mut entry = ConfigValueEntry::new(value, raw);
+        if let Some(previous) = self.values.get(&key) {
+            entry.source_file = previous.source_file.clone();
+            entry.line = previous.line;
+        } else {
+            #[cfg(feature = "mutation")]
+            self.declaration_order.push(Declaration::Value(key.clone()));
+        }
+        self.stamp_entry(&key, &mut entry);
+
+        self.values.insert(key, entry);
+    }
+
+    /// Check if a key exists
+    pub fn contains(&self, key: &str) -> bool {
+        self.lookup(key).is_some()
+    }
+
+    /// The kind of value stored under a key, or `None` if the key is not set
+    ///
+    /// Lets callers branch on the stored type without a conversion error, e.g. to pick a
+    /// widget for an option they know nothing about.
+    ///
+    /// ```rust
+    /// use hyprlang::{Config, ValueKind};
+    ///
+    /// let mut config = Config::new();
+    /// config.parse("gaps = 5\nborder = rgb(ff0000)").unwrap();
+    ///
+    /// assert_eq!(config.kind_of("gaps"), Some(ValueKind::Int));
+    /// assert_eq!(config.kind_of("border"), Some(ValueKind::Color));
+    /// assert_eq!(config.kind_of("missing"), None);
+    /// ```
+    pub fn kind_of(&self, key: &str) -> Option<ValueKind> {
+        self.lookup(key).map(|entry| entry.value.kind())
+    }
+
+    /// Check if a key exists and holds a value of the given kind
+    pub fn contains_typed(&self, key: &str, kind: ValueKind) -> bool {
+        self.kind_of(key) == Some(kind)
+    }
+
+    /// Register a handler
+    pub fn register_handler<H>(&mut self, keyword: impl Into<String>, handler: H)
+    where
+        H: Handler + 'static,
+    {
+        self.handlers.register_global(keyword, handler);
+    }
+
+    /// Register a function handler
+    pub fn register_handler_fn<F>(&mut self, keyword: impl Into<String>, handler: F)
+    where
+        F: Fn(&crate::handlers::HandlerContext) -> ParseResult<()> + 'static,
+    {
+        let keyword = keyword.into();
+        self.handlers
+            .register_global(keyword.clone(), FunctionHandler::new(keyword, handler));
     }
 
     /// Register a category-specific handler
@@ -900,11 +2378,269 @@ impl Config {
         );
     }
 
+    /// Register `alias` as a deprecated name for the handler keyword `canonical`
+    ///
+    /// Calls written with the alias are stored and executed under the canonical keyword,
+    /// and each one is reported in [`deprecations`](Config::deprecations).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config.register_handler_fn("windowrule", |_| Ok(()));
+    /// config.register_handler_alias("windowrulev2", "windowrule");
+    /// config.parse("windowrulev2 = float, class:^(kitty)$").unwrap();
+    ///
+    /// assert_eq!(
+    ///     config.get_handler_calls("windowrule").unwrap(),
+    ///     &vec!["float, class:^(kitty)$".to_string()]
+    /// );
+    /// assert_eq!(
+    ///     config.deprecations()[0].to_string(),
+    ///     "1: 'windowrulev2' is deprecated, use 'windowrule' instead"
+    /// );
+    /// ```
+    pub fn register_handler_alias(
+        &mut self,
+        alias: impl Into<String>,
+        canonical: impl Into<String>,
+    ) {
+        self.handlers.register_alias(alias, canonical);
+    }
+
+    /// All registered handler aliases: alias -> canonical keyword
+    pub fn handler_aliases(&self) -> &HashMap<String, String> {
+        self.handlers.aliases()
+    }
+
+    /// Handler calls written with a deprecated alias, in the order they were parsed
+    ///
+    /// Deprecations accumulate across parses until
+    /// [`clear_deprecations`](Config::clear_deprecations) is called.
+    pub fn deprecations(&self) -> &[HandlerDeprecation] {
+        &self.deprecations
+    }
+
+    /// Forget the calls recorded by [`deprecations`](Config::deprecations)
+    pub fn clear_deprecations(&mut self) {
+        self.deprecations.clear();
+    }
+
+    /// Handler calls left out because their handler returned [`ConfigError::rejected`], in
+    /// the order they were parsed
+    ///
+    /// Rejected calls are not stored with the other handler calls and don't stop parsing.
+    /// Their lines stay in the document; editing handler calls by index skips them.
+    /// They accumulate across parses until
+    /// [`clear_rejected_handler_calls`](Config::clear_rejected_handler_calls) is called,
+    /// and [`check`](Config::check) reports them as warnings when
+    /// [`dry_run_handlers`](ConfigOptions::dry_run_handlers) lets it run the handlers.
+    pub fn rejected_handler_calls(&self) -> &[RejectedHandlerCall] {
+        &self.rejected_handler_calls
+    }
+
+    /// Forget the calls recorded by [`rejected_handler_calls`](Config::rejected_handler_calls)
+    pub fn clear_rejected_handler_calls(&mut self) {
+        self.rejected_handler_calls.clear();
+    }
+
+    /// Every block defining the regular category `category`, in the order they were parsed
+    ///
+    /// `category` is a full path such as `decoration:blur`; blocks of a keyed or static
+    /// special category instance are listed under paths like `device[mouse]` and
+    /// `plugin[static]`. Values written with a
+    /// `category:key` path outside a block are not listed. Definitions accumulate across
+    /// parses until [`clear_category_definitions`](Config::clear_category_definitions)
+    /// is called.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config
+    ///     .parse("general {\n    gaps_in = 5\n}\ngeneral {\n    gaps_out = 10\n}\n")
+    ///     .unwrap();
+    ///
+    /// let lines: Vec<usize> = config
+    ///     .category_definitions("general")
+    ///     .iter()
+    ///     .map(|definition| definition.line)
+    ///     .collect();
+    /// assert_eq!(lines, vec![1, 4]);
+    /// ```
+    pub fn category_definitions(&self, category: &str) -> &[CategoryDefinition] {
+        self.category_definitions
+            .get(self.normalize_key(category).as_ref())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Blocks that repeated an earlier block of the same category, in the order they
+    /// were parsed
+    ///
+    /// Only filled under [`CategoryMerge::Warn`]; [`check`](Config::check) reports them
+    /// as warnings.
+    pub fn category_redefinitions(&self) -> &[CategoryDefinition] {
+        &self.category_redefinitions
+    }
+
+    /// Forget the blocks recorded by [`category_definitions`](Config::category_definitions)
+    /// and [`category_redefinitions`](Config::category_redefinitions)
+    pub fn clear_category_definitions(&mut self) {
+        self.category_definitions.clear();
+        self.category_redefinitions.clear();
+    }
+
+    /// Lines that looked like handler calls but reached no handler, by full key
+    ///
+    /// Only filled when [`ConfigOptions::capture_unknown_handlers`] is set. Each line is
+    /// also stored as a value as usual, so repeated lines only keep their last value
+    /// there; this map keeps every one of them in order. Keys registered with
+    /// [`register_option`](Config::register_option) are not captured. Captured calls
+    /// accumulate across parses until
+    /// [`clear_unknown_handlers`](Config::clear_unknown_handlers) is called.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyprlang::{Config, ConfigOptions};
+    ///
+    /// let mut config = Config::with_options(ConfigOptions {
+    ///     capture_unknown_handlers: true,
+    ///     ..Default::default()
+    /// });
+    /// config.parse("plugin {\n    load = a.so\n    load = b.so\n}").unwrap();
+    ///
+    /// let calls = &config.unknown_handlers()["plugin:load"];
+    /// assert_eq!(calls.len(), 2);
+    /// assert_eq!(calls[0].value, "a.so");
+    /// assert_eq!(calls[1].line, 3);
+    /// assert_eq!(config.get_string("plugin:load").unwrap(), "b.so");
+    /// ```
+    pub fn unknown_handlers(&self) -> &HashMap<String, Vec<UnknownHandlerCall>> {
+        &self.unknown_handlers
+    }
+
+    /// Forget the calls recorded by [`unknown_handlers`](Config::unknown_handlers)
+    pub fn clear_unknown_handlers(&mut self) {
+        self.unknown_handlers.clear();
+    }
+
+    /// Record a call written with `alias`, returning the alias
+    fn record_deprecation(&mut self, alias: String, canonical: &str, line: usize) -> String {
+        self.deprecations.push(HandlerDeprecation {
+            alias: alias.clone(),
+            canonical: canonical.to_string(),
+            file: self.current_source_file.clone(),
+            line,
+        });
+        alias
+    }
+
+    /// Check whether a keyword reaches a registered handler at a category path, either
+    /// directly or as a flagged form like `bindl`
+    pub(crate) fn has_handler(&self, category_path: &[String], keyword: &str) -> bool {
+        self.handlers.has_handler(category_path, keyword)
+            || self.handlers.split_flags(category_path, keyword).is_some()
+    }
+
+    /// Register a handler that receives the state set with
+    /// [`set_handler_state`](Config::set_handler_state)
+    ///
+    /// Parsing fails with a handler error when the handler runs without state of type `T`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config.register_handler_with_state("bind", |ctx, binds: &mut Vec<String>| {
+    ///     binds.push(ctx.value.clone());
+    ///     Ok(())
+    /// });
+    ///
+    /// config.set_handler_state(Vec::<String>::new());
+    /// config.parse("bind = SUPER, Q, exec, kitty\nbind = SUPER, C, killactive").unwrap();
+    ///
+    /// let binds: Vec<String> = config.take_handler_state().unwrap();
+    /// assert_eq!(binds, vec!["SUPER, Q, exec, kitty", "SUPER, C, killactive"]);
+    /// ```
+    pub fn register_handler_with_state<T, F>(&mut self, keyword: impl Into<String>, handler: F)
+    where
+        T: Any,
+        F: Fn(&crate::handlers::HandlerContext, &mut T) -> ParseResult<()> + 'static,
+    {
+        let keyword = keyword.into();
+        self.handlers.register_global(
+            keyword.clone(),
+            FunctionHandler::stateful(keyword, downcast_state(handler)),
+        );
+    }
+
+    /// Register a category-specific handler that receives the state set with
+    /// [`set_handler_state`](Config::set_handler_state)
+    pub fn register_category_handler_with_state<T, F>(
+        &mut self,
+        category: impl Into<String>,
+        keyword: impl Into<String>,
+        handler: F,
+    ) where
+        T: Any,
+        F: Fn(&crate::handlers::HandlerContext, &mut T) -> ParseResult<()> + 'static,
+    {
+        let keyword = keyword.into();
+        self.handlers.register_category(
+            category,
+            keyword.clone(),
+            FunctionHandler::stateful(keyword, downcast_state(handler)),
+        );
+    }
+
+    /// Set the state passed to stateful handlers, replacing any previous state
+    ///
+    /// The state is not copied when the config is cloned.
+    pub fn set_handler_state<T: Any>(&mut self, state: T) {
+        self.handler_state.0 = Some(Box::new(state));
+    }
+
+    /// Get the handler state, if it is set and of type `T`
+    pub fn handler_state<T: Any>(&self) -> Option<&T> {
+        self.handler_state.0.as_ref()?.downcast_ref()
+    }
+
+    /// Get the handler state mutably, if it is set and of type `T`
+    pub fn handler_state_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.handler_state.0.as_mut()?.downcast_mut()
+    }
+
+    /// Remove and return the handler state, if it is of type `T`
+    ///
+    /// State of another type is left in place.
+    pub fn take_handler_state<T: Any>(&mut self) -> Option<T> {
+        match self.handler_state.0.take()?.downcast::<T>() {
+            Ok(state) => Some(*state),
+            Err(state) => {
+                self.handler_state.0 = Some(state);
+                None
+            }
+        }
+    }
+
     /// Register a special category
     pub fn register_special_category(&mut self, descriptor: SpecialCategoryDescriptor) {
         self.special_categories.register(descriptor);
     }
 
+    /// Check whether a special category is registered
+    pub(crate) fn is_special_category(&self, name: &str) -> bool {
+        self.special_categories.is_registered(name)
+    }
+
     /// Register a default value for a special category property
     /// This adds a default value that will be applied to all instances of the category
     pub fn register_special_category_value(
@@ -913,13 +2649,37 @@ impl Config {
         property: impl Into<String>,
         default_value: ConfigValue,
     ) {
-        let category = category.into();
-        let property = property.into();
+        if let Some(descriptor) = self.special_categories.get_descriptor_mut(&category.into()) {
+            descriptor
+                .default_values
+                .insert(property.into(), default_value);
+        }
+    }
+
+    /// Register default values for several properties of a special category at once
+    ///
+    /// Like [`Config::register_special_category_value`], this does nothing if the category
+    /// isn't registered, and only affects instances created afterwards.
+    pub fn extend_special_category_defaults<I, K>(&mut self, category: &str, defaults: I)
+    where
+        I: IntoIterator<Item = (K, ConfigValue)>,
+        K: Into<String>,
+    {
+        if let Some(descriptor) = self.special_categories.get_descriptor_mut(category) {
+            descriptor.default_values.extend(
+                defaults
+                    .into_iter()
+                    .map(|(property, value)| (property.into(), value)),
+            );
+        }
+    }
 
-        // Get the descriptor, add the default value, and re-register
-        if let Some(mut descriptor) = self.special_categories.get_descriptor(&category).cloned() {
-            descriptor.default_values.insert(property, default_value);
-            self.special_categories.register(descriptor);
+    /// Remove all default values registered for a special category
+    ///
+    /// Instances created afterwards start empty; existing instances keep their values.
+    pub fn clear_special_category_defaults(&mut self, category: &str) {
+        if let Some(descriptor) = self.special_categories.get_descriptor_mut(category) {
+            descriptor.default_values.clear();
         }
     }
 
@@ -939,9 +2699,139 @@ impl Config {
         Ok(result)
     }
 
+    /// View a special category instance, with typed getters and the origin of each value
+    ///
+    /// Unlike [`get_special_category`](Config::get_special_category), properties the
+    /// instance doesn't hold fall back to defaults registered for the category since.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyprlang::{Config, ConfigValue, SpecialCategoryDescriptor};
+    ///
+    /// let mut config = Config::new();
+    /// config.register_special_category(SpecialCategoryDescriptor::keyed("device", "name"));
+    /// config.register_special_category_value("device", "enabled", ConfigValue::Int(1));
+    /// config.parse("device[mouse] {\n    sensitivity = 0.5\n}").unwrap();
+    ///
+    /// let mouse = config.special_category_view("device", "mouse").unwrap();
+    /// assert_eq!(mouse.get_float("sensitivity").unwrap(), 0.5);
+    /// assert!(mouse.is_set_by_user("sensitivity"));
+    /// assert_eq!(mouse.get_int("enabled").unwrap(), 1);
+    /// assert!(mouse.is_default("enabled"));
+    /// ```
+    pub fn special_category_view(
+        &self,
+        category: &str,
+        key: &str,
+    ) -> ParseResult<CategoryInstanceView<'_>> {
+        let (category, instance) = self.special_categories.get_instance_entry(category, key)?;
+        Ok(CategoryInstanceView::new(
+            category,
+            instance,
+            self.special_categories.get_descriptor(category),
+        ))
+    }
+
+    /// Values of a special category instance that were set in the config rather than defaulted
+    #[cfg(feature = "hyprland")]
+    pub(crate) fn special_category_user_values(
+        &self,
+        category: &str,
+        key: &str,
+    ) -> ParseResult<impl Iterator<Item = (&str, &ConfigValue)>> {
+        let instance = self.special_categories.get_instance(category, key)?;
+        Ok(instance
+            .values
+            .iter()
+            .filter(|(_, entry)| entry.set_by_user)
+            .map(|(key, entry)| (key.as_str(), &entry.value)))
+    }
+
+    /// The text a special category property was written with, variables expanded
+    ///
+    /// Unlike the parsed value, this keeps `true` or `0x0` as written. Falls back to the
+    /// unexpanded text if a variable doesn't resolve.
+    #[cfg(feature = "hyprland")]
+    pub(crate) fn special_category_text(
+        &self,
+        category: &str,
+        key: &str,
+        property: &str,
+    ) -> Option<String> {
+        let entry = self
+            .special_categories
+            .get_instance(category, key)
+            .ok()?
+            .get(property)?;
+        let text = process_escapes_with(&entry.raw, &self.options.syntax);
+        Some(match self.variables.expand(&text) {
+            Ok(expanded) => restore_escaped_with(&expanded, &self.options.syntax),
+            Err(_) => entry.raw.clone(),
+        })
+    }
+
     /// List all keys for a special category
+    ///
+    /// Keys are returned in the order their instances were declared, like
+    /// [`find_special_category_instances`](Self::find_special_category_instances).
     pub fn list_special_category_keys(&self, category: &str) -> Vec<String> {
-        self.special_categories.list_keys(category)
+        self.find_special_category_instances(category, |_| true)
+            .into_iter()
+            .filter_map(|instance| instance.key.clone())
+            .collect()
+    }
+
+    /// Instances of a special category for which `predicate` returns `true`
+    ///
+    /// Instances are returned in the order they were declared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyprlang::{Config, SpecialCategoryDescriptor};
+    ///
+    /// let mut config = Config::new();
+    /// config.register_special_category(SpecialCategoryDescriptor::keyed("device", "name"));
+    /// config
+    ///     .parse("device[mouse] {\n    sensitivity = 0.5\n}\ndevice[pad] {\n    sensitivity = -1\n}")
+    ///     .unwrap();
+    ///
+    /// let slow = config.find_special_category_instances("device", |instance| {
+    ///     instance
+    ///         .get("sensitivity")
+    ///         .is_some_and(|entry| entry.value.as_float().is_ok_and(|s| s < 0.0))
+    /// });
+    /// assert_eq!(slow.len(), 1);
+    /// assert_eq!(slow[0].key.as_deref(), Some("pad"));
+    /// ```
+    pub fn find_special_category_instances<F>(
+        &self,
+        category: &str,
+        mut predicate: F,
+    ) -> Vec<&SpecialCategoryInstance>
+    where
+        F: FnMut(&SpecialCategoryInstance) -> bool,
+    {
+        let mut instances: Vec<_> = self
+            .special_categories
+            .get_all_instances(category)
+            .into_iter()
+            .filter(|instance| predicate(instance))
+            .collect();
+
+        // Values of an instance are stamped as they are parsed; instances without user-set
+        // values (only defaults) go last
+        instances.sort_by_cached_key(|instance| {
+            let declared = instance
+                .values
+                .values()
+                .filter(|entry| entry.set_by_user)
+                .map(|entry| entry.generation)
+                .min();
+            (declared.is_none(), declared, instance.key.clone())
+        });
+        instances
     }
 
     /// Register a custom value type
@@ -952,72 +2842,1082 @@ impl Config {
         self.custom_types.insert(type_name.into(), Rc::new(handler));
     }
 
-    /// Get a variable value
-    pub fn get_variable(&self, name: &str) -> Option<&str> {
-        self.variables.get(name)
+    /// Names of all registered custom value types, sorted
+    pub fn custom_types(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.custom_types.keys().map(|name| name.as_str()).collect();
+        names.sort_unstable();
+        names
     }
 
-    /// Set a variable value
-    pub fn set_variable(&mut self, name: String, value: String) {
-        self.variables.set(name.clone(), value.clone());
-
-        // Update expression evaluator if it's a number
-        if let Ok(num) = ConfigValue::parse_int(&value) {
-            self.expressions.set_variable(name.clone(), num);
+    /// Text of a value as written to the document
+    ///
+    /// Custom values of a registered type are formatted by their handler.
+    fn value_text(&self, value: &ConfigValue) -> String {
+        if let ConfigValue::Custom { type_name, value } = value
+            && let Some(handler) = self.custom_types.get(type_name)
+        {
+            return handler.to_string(value.as_ref());
         }
+        value.to_string()
+    }
 
-        // Update document tree if mutation feature is enabled
-        #[cfg(feature = "mutation")]
-        {
-            let var_key = format!("${}", name);
+    /// Opt a key in to unit parsing
+    ///
+    /// Values such as `50%`, `10px`, `500ms`, `2s`, `45deg` or `1.5rad` assigned to this key
+    /// are stored as [`ConfigValue::Quantity`] instead of plain strings. Unitless numbers
+    /// are kept as `Int`/`Float`, and any other string is reported as an error.
+    pub fn register_quantity_key(&mut self, key: impl Into<String>) {
+        self.quantity_keys.insert(key.into());
+    }
 
-            // Try to update in the correct source file using multi_document
-            let updated_in_multi = if let Some(multi_doc) = &mut self.multi_document {
-                // Find which file this variable belongs to
-                let source_file = multi_doc
-                    .get_key_source(&var_key)
-                    .cloned()
-                    .unwrap_or_else(|| multi_doc.primary_path.clone());
+    /// Register a color that `rgb()` and `rgba()` can refer to by name
+    ///
+    /// Named colors are only recognized inside the color functions, so `rgb(red)` and
+    /// `rgba(red, 0.5)` become colors while a plain `red` value stays a string.
+    pub fn register_named_color(&mut self, name: impl Into<String>, color: Color) {
+        self.named_colors.insert(name.into(), color);
+    }
 
-                // Update the document in that file
-                if let Some(doc) = multi_doc.get_document_mut(&source_file) {
-                    let _ = doc.update_or_insert_variable(&name, &value);
-                    multi_doc.mark_dirty(&source_file);
+    /// Register a whole palette of named colors, such as [`CATPPUCCIN_MOCHA`](crate::CATPPUCCIN_MOCHA)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyprlang::{CATPPUCCIN_MOCHA, Color, Config};
+    ///
+    /// let mut config = Config::new();
+    /// config.register_named_colors(CATPPUCCIN_MOCHA.iter().copied());
+    /// config
+    ///     .parse("border = rgb(mauve)\nshadow = rgba(crust, 0.5)")
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.get_color("border").unwrap(), Color::from_rgb(0xcb, 0xa6, 0xf7));
+    /// assert_eq!(config.get_color("shadow").unwrap(), Color::from_rgba(0x11, 0x11, 0x1b, 128));
+    /// ```
+    pub fn register_named_colors<I, S>(&mut self, colors: I)
+    where
+        I: IntoIterator<Item = (S, Color)>,
+        S: Into<String>,
+    {
+        for (name, color) in colors {
+            self.register_named_color(name, color);
+        }
+    }
+
+    /// Register a validator for a key or a whole category
+    ///
+    /// The validator runs for `key` itself and for every key nested below it, so
+    /// `"decoration"` covers `decoration:rounding` and `decoration:blur:size`. It is
+    /// called for parsed assignments and for [`set`](Config::set) and friends; returning
+    /// `Err(reason)` rejects the value with a [`ConfigError::ValidationError`] before it
+    /// is stored or written to the document.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyprlang::{Config, ConfigValue};
+    ///
+    /// let mut config = Config::new();
+    /// config.add_validator("decoration:rounding", |value| match value.as_int() {
+    ///     Ok(n) if n >= 0 => Ok(()),
+    ///     _ => Err("must be a non-negative integer".to_string()),
+    /// });
+    ///
+    /// assert!(config.parse("decoration:rounding = -5").is_err());
+    /// assert!(config.set("decoration:rounding", ConfigValue::Int(10)).is_ok());
+    /// assert!(config.set("decoration:rounding", ConfigValue::Int(-1)).is_err());
+    /// assert_eq!(config.get_int("decoration:rounding").unwrap(), 10);
+    /// ```
+    pub fn add_validator<F>(&mut self, key: impl Into<String>, validator: F)
+    where
+        F: Fn(&ConfigValue) -> Result<(), String> + 'static,
+    {
+        self.validators.push((key.into(), Rc::new(validator)));
+    }
+
+    /// Register a validator for the calls of a handler keyword
+    ///
+    /// A bare keyword such as `"bind"` matches its calls in any category, while
+    /// `"animations:bezier"` only matches calls inside `animations`. Bind variants like
+    /// `bindl` reach the `bind` validator with their suffix in
+    /// [`HandlerContext::flags`]. Validators run before the handler, including while
+    /// [`check`](Config::check)ing, and returning `Err(reason)` rejects the call with a
+    /// [`ConfigError::HandlerCallFailed`] naming its file and line. This keeps validation
+    /// apart from the handler that acts on the call, so both can come from different places.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config.register_handler_fn("exec", |_| Ok(()));
+    /// config.add_handler_validator("exec", |ctx| {
+    ///     if ctx.value.trim().is_empty() {
+    ///         Err("needs a command".to_string())
+    ///     } else {
+    ///         Ok(())
+    ///     }
+    /// });
+    ///
+    /// assert!(config.parse("exec = kitty").is_ok());
+    /// let error = config.parse("\nexec = ").unwrap_err();
+    /// assert!(error.to_string().contains("at line 2"));
+    /// ```
+    pub fn add_handler_validator<F>(&mut self, keyword: impl Into<String>, validator: F)
+    where
+        F: Fn(&HandlerContext) -> Result<(), String> + 'static,
+    {
+        self.handler_validators
+            .push((keyword.into(), Rc::new(validator)));
+    }
+
+    /// Register a known option with its default value
+    ///
+    /// The default is stored unless the key already has a value, with
+    /// [`set_by_user`](ConfigValueEntry::set_by_user) false. Later values for the key must
+    /// match the default's type; integers are also accepted for float, color and quantity
+    /// options, and string options accept any value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyprlang::{Config, ConfigValue};
+    ///
+    /// let mut config = Config::new();
+    /// config.register_option("general:gaps_in", ConfigValue::Int(5));
+    /// assert_eq!(config.get_int("general:gaps_in").unwrap(), 5);
+    /// assert!(!config.entry("general:gaps_in").unwrap().set_by_user);
+    ///
+    /// config.parse("general:gaps_in = 10").unwrap();
+    /// assert_eq!(config.get_int("general:gaps_in").unwrap(), 10);
+    /// assert!(config.parse("general:gaps_in = large").is_err());
+    /// ```
+    pub fn register_option(&mut self, key: impl Into<String>, default: ConfigValue) {
+        let key = self.resolve_key(&key.into()).into_owned();
+        self.registered_options.insert(key.clone());
+
+        let expected = default.clone();
+        self.add_validator(key.clone(), move |value| {
+            if option_accepts(&expected, value) {
+                Ok(())
+            } else {
+                Err(format!(
+                    "expected {}, got {}",
+                    expected.type_name(),
+                    value.type_name()
+                ))
+            }
+        });
+
+        if !self.values.contains_key(&key) {
+            let mut entry = ConfigValueEntry::with_default(default);
+            self.stamp_entry(&key, &mut entry);
+            self.values.insert(key, entry);
+        }
+    }
+
+    /// Register everything a [`ConfigProfile`] describes
+    ///
+    /// Handlers are registered first, then special categories, options and handler
+    /// aliases, and finally [`ConfigProfile::configure`] runs. Applying several profiles
+    /// is allowed; later registrations replace earlier ones for the same keyword or
+    /// category.
+    pub fn apply_profile<P: ConfigProfile + ?Sized>(&mut self, profile: &P) {
+        for handler in profile.handlers() {
+            let function = if handler.accepts_flags {
+                FunctionHandler::with_flags(handler.keyword.clone(), |_| Ok(()))
+            } else {
+                FunctionHandler::new(handler.keyword.clone(), |_| Ok(()))
+            };
+
+            match handler.category {
+                Some(category) => {
+                    self.register_category_handler(category, handler.keyword, function)
+                }
+                None => self.register_handler(handler.keyword, function),
+            }
+        }
+
+        for descriptor in profile.special_categories() {
+            self.register_special_category(descriptor);
+        }
+
+        for option in profile.options() {
+            self.register_option(option.key, option.default);
+        }
+
+        for (alias, canonical) in profile.handler_aliases() {
+            self.register_handler_alias(alias, canonical);
+        }
+
+        profile.configure(self);
+    }
+
+    /// Run every validator registered for `key` against `value`
+    fn validate(&self, key: &str, value: &ConfigValue) -> ParseResult<()> {
+        for (pattern, validator) in &self.validators {
+            let applies = key
+                .strip_prefix(pattern.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(':'));
+
+            if applies {
+                validator(value)
+                    .map_err(|reason| ConfigError::validation(key, value.to_string(), reason))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Look up environment variables with `provider` instead of the process environment
+    ///
+    /// The provider is used for `$VAR` expansion falling back to the environment and for
+    /// `# hyprlang if env:VAR` conditions, which keeps parsing reproducible in tests.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config.set_env_provider(|name| (name == "HOSTNAME").then(|| "laptop".to_string()));
+    /// config
+    ///     .parse("# hyprlang if env:HOSTNAME == laptop\nscale = 1.5\n# hyprlang endif")
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.get_float("scale").unwrap(), 1.5);
+    /// ```
+    pub fn set_env_provider<F>(&mut self, provider: F)
+    where
+        F: Fn(&str) -> Option<String> + 'static,
+    {
+        self.variables.set_env_provider(provider);
+    }
+
+    /// Mask values whose key matches a glob pattern when the config is displayed
+    ///
+    /// Applies to the [`Display`](std::fmt::Display) output and [`redacted`](Config::redacted).
+    /// Values are matched by full key (`plugin:api_token`), handler calls by keyword
+    /// (`exec-once`), variables by name with their `$` and special category properties as
+    /// `category:property`. Stored values are not changed.
+    pub fn redact(&mut self, pattern: impl Into<String>) {
+        self.redactions.push(pattern.into());
+    }
+
+    /// Rewrite displayed values, for masking secrets inside a longer value
+    ///
+    /// The hook is called with the key (as for [`redact`](Config::redact)) and the value's
+    /// text, and returns the text to show instead, or `None` to show it unchanged. Values
+    /// matched by a [`redact`](Config::redact) pattern are masked whole without calling it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config.register_handler_fn("exec-once", |_| Ok(()));
+    /// config.redact("*password*");
+    /// config.set_redaction_hook(|_, text| {
+    ///     let (before, _) = text.split_once("--token ")?;
+    ///     Some(format!("{}--token ***", before))
+    /// });
+    /// config
+    ///     .parse("mail_password = hunter2\nexec-once = sync --token abc123\n")
+    ///     .unwrap();
+    ///
+    /// let shown = config.to_string();
+    /// assert!(shown.contains("mail_password = [redacted]"));
+    /// assert!(shown.contains("exec-once = sync --token ***"));
+    /// assert_eq!(config.get_string("mail_password").unwrap(), "hunter2");
+    /// ```
+    pub fn set_redaction_hook<F>(&mut self, hook: F)
+    where
+        F: Fn(&str, &str) -> Option<String> + 'static,
+    {
+        self.redaction_hook = Some(Rc::new(hook));
+    }
+
+    /// A value's text as it is displayed, with [`redact`](Config::redact) patterns and the
+    /// [redaction hook](Config::set_redaction_hook) applied
+    pub fn redacted<'a>(&self, key: &str, text: &'a str) -> Cow<'a, str> {
+        if self
+            .redactions
+            .iter()
+            .any(|pattern| glob_matches(pattern, key))
+        {
+            return Cow::Borrowed(REDACTED);
+        }
+        let hook = self.redaction_hook.as_ref();
+        match hook.and_then(|hook| hook(key, text)) {
+            Some(replacement) => Cow::Owned(replacement),
+            None => Cow::Borrowed(text),
+        }
+    }
+
+    /// Get a variable value
+    pub fn get_variable(&self, name: &str) -> Option<&str> {
+        self.variables.get(name)
+    }
+
+    /// Provide a variable before parsing, such as `$HOSTNAME` or `$MONITOR_COUNT`
+    ///
+    /// A seeded variable expands in values, expressions and `# hyprlang if` directives like
+    /// one defined in the config, but it is not part of the document and is never written by
+    /// `serialize` or `save`. A `$NAME = value` line in the config replaces it, and seeding a
+    /// variable the config already defines has no effect.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config.seed_variable("MONITOR_COUNT", "2");
+    /// config.parse("columns = {{MONITOR_COUNT * 2}}").unwrap();
+    ///
+    /// assert_eq!(config.get_int("columns").unwrap(), 4);
+    /// ```
+    pub fn seed_variable(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        let name = name.into();
+        if self.variables.contains(&name) && !self.seeded_variables.contains(&name) {
+            return;
+        }
+
+        let value = value.into();
+        if let Ok(num) = ConfigValue::parse_int(&value) {
+            self.expressions.set_variable(name.clone(), num);
+        }
+        self.variables.set(name.clone(), value.clone());
+        self.variable_sources
+            .insert(name.clone(), VariableSource::Seeded);
+        self.variable_definitions.insert(name.clone(), value);
+        self.seeded_variables.insert(name);
+    }
+
+    /// Set a variable value
+    pub fn set_variable(&mut self, name: String, value: String) {
+        #[cfg(feature = "mutation")]
+        if self.seeded_variables.contains(&name) || !self.variables.contains(&name) {
+            self.declaration_order
+                .push(Declaration::Variable(name.clone()));
+        }
+        self.seeded_variables.remove(&name);
+
+        self.variables.set(name.clone(), value.clone());
+        // An existing definition is rewritten where it is, so it keeps its location
+        let source = match self.variable_sources.remove(&name) {
+            Some(source @ VariableSource::Config { .. }) => source,
+            _ => VariableSource::Config {
+                file: None,
+                line: None,
+            },
+        };
+        self.variable_sources.insert(name.clone(), source);
+        self.variable_definitions
+            .insert(name.clone(), value.clone());
+
+        // Update expression evaluator if it's a number
+        if let Ok(num) = ConfigValue::parse_int(&value) {
+            self.expressions.set_variable(name.clone(), num);
+        }
+
+        // Update document tree if mutation feature is enabled
+        #[cfg(feature = "mutation")]
+        {
+            let var_key = format!("${}", name);
+            let reference = self.options.syntax.variable(&name);
+
+            // Try to update in the correct source file using multi_document
+            let updated_in_multi = if let Some(multi_doc) = &mut self.multi_document {
+                // Find which file this variable belongs to
+                let source_file = multi_doc
+                    .get_key_source(&var_key)
+                    .cloned()
+                    .unwrap_or_else(|| multi_doc.primary_path.clone());
+
+                // Update the document in that file
+                if let Some(doc) = multi_doc.get_document_mut(&source_file) {
+                    let _ = doc.write_variable(&name, &value, &reference);
+                    multi_doc.mark_dirty(&source_file);
+
+                    // If this is a new variable, register it with the primary file
+                    if multi_doc.get_key_source(&var_key).is_none() {
+                        multi_doc.register_key(var_key, source_file);
+                    }
+                    true
+                } else {
+                    false
+                }
+            } else {
+                false
+            };
+
+            // Fallback: update single document if multi_document didn't handle it
+            if !updated_in_multi
+                && let Some(doc) = &mut self.document
+            {
+                let _ = doc.write_variable(&name, &value, &reference);
+            }
+        }
+    }
+
+    /// Get all configuration keys
+    pub fn keys(&self) -> Vec<&str> {
+        self.values.keys().map(|s| s.as_str()).collect()
+    }
+
+    /// Size, statement count, parse time and errors of each file parsed with
+    /// [`parse_file`](Config::parse_file), including `source`d ones, in the order they
+    /// were opened
+    ///
+    /// Each file's time excludes the files it sources, so the slowest entry points at the
+    /// file to look at. A file sourced twice has two entries. Entries accumulate across
+    /// parses.
+    pub fn file_stats(&self) -> &[FileStats] {
+        &self.file_stats
+    }
+
+    /// Where the parse in progress stands with its `# hyprlang if` and `noerror` directives
+    ///
+    /// Mostly useful between [`parse_dynamic`](Config::parse_dynamic) calls, which share
+    /// their directives; each parsed file starts afresh and is recorded in
+    /// [`file_directive_states`](Config::file_directive_states).
+    pub fn directive_state(&self) -> DirectiveState {
+        self.directives.state(self.current_source_file.clone())
+    }
+
+    /// The directive state at the end of each parsed file or string, in the order they
+    /// finished
+    ///
+    /// Conditions still open at the end of a file are listed, and `suppressed_statements`
+    /// counts the statements its false conditions skipped, for tools that gray out
+    /// inactive regions. Entries accumulate across parses.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config
+    ///     .parse("# hyprlang if MISSING\ngaps = 5\nborder = 2\n# hyprlang endif\n")
+    ///     .unwrap();
+    ///
+    /// let state = &config.file_directive_states()[0];
+    /// assert_eq!(state.suppressed_statements, 2);
+    /// assert!(!state.is_inactive());
+    /// ```
+    pub fn file_directive_states(&self) -> &[DirectiveState] {
+        &self.directive_states
+    }
+
+    /// Counts of keys, variables, handler calls and parsed files and lines
+    ///
+    /// Useful for summaries and for spotting anomalies such as thousands of rules. Keys
+    /// are counted by top-level category, so `device[mouse]:sensitivity` counts towards
+    /// `device`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config.register_handler_fn("bind", |_| Ok(()));
+    /// config
+    ///     .parse("$mod = SUPER\ngeneral {\n    gaps_in = 5\n    gaps_out = 10\n}\nbind = $mod, Q, exit")
+    ///     .unwrap();
+    ///
+    /// let stats = config.stats();
+    /// assert_eq!(stats.keys_per_category["general"], 2);
+    /// assert_eq!(stats.handler_calls["bind"], 1);
+    /// assert_eq!(stats.variables, 1);
+    /// assert_eq!(stats.lines, 6);
+    /// ```
+    pub fn stats(&self) -> ConfigStats {
+        let mut stats = ConfigStats {
+            variables: self.variables.all().len(),
+            files: self.files_parsed,
+            lines: self.lines_parsed,
+            parse_duration: self.parse_duration,
+            file_stats: self.file_stats.clone(),
+            ..Default::default()
+        };
+
+        for key in self.values.keys() {
+            let category = match key.split_once(':') {
+                Some((category, _)) => category.split('[').next().unwrap_or(category),
+                None => "",
+            };
+            *stats
+                .keys_per_category
+                .entry(category.to_string())
+                .or_default() += 1;
+        }
+
+        for (keyword, calls) in &self.handler_calls {
+            stats.handler_calls.insert(keyword.clone(), calls.len());
+        }
+
+        for category in self.special_categories.category_names() {
+            let instances = self.special_categories.get_all_instances(category).len();
+            stats
+                .special_category_instances
+                .insert(category.to_string(), instances);
+        }
+
+        stats
+    }
+
+    /// Iterate over all configuration values
+    ///
+    /// Yields `(key, value)` pairs with full keys (e.g. `general:gaps_in`) in no
+    /// particular order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config.parse("a = 1\nb = 2").unwrap();
+    ///
+    /// let total: i64 = config.iter().filter_map(|(_, v)| v.as_int().ok()).sum();
+    /// assert_eq!(total, 3);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &ConfigValue)> {
+        self.values
+            .iter()
+            .map(|(key, entry)| (key.as_str(), &entry.value))
+    }
+
+    /// Iterate over all values that are colors
+    pub fn iter_colors(&self) -> impl Iterator<Item = (&str, Color)> {
+        self.iter().filter_map(|(key, value)| match value {
+            ConfigValue::Color(color) => Some((key, *color)),
+            _ => None,
+        })
+    }
+
+    /// Iterate over all values inside a category, including nested categories
+    ///
+    /// `category` may itself be nested (`"decoration:blur"`). Keys are yielded in full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config
+    ///     .parse("general {\n    gaps_in = 5\n}\ngeneral_other = 1")
+    ///     .unwrap();
+    ///
+    /// let keys: Vec<&str> = config.iter_in_category("general").map(|(k, _)| k).collect();
+    /// assert_eq!(keys, vec!["general:gaps_in"]);
+    /// ```
+    pub fn iter_in_category<'a>(
+        &'a self,
+        category: &'a str,
+    ) -> impl Iterator<Item = (&'a str, &'a ConfigValue)> {
+        self.iter().filter(move |(key, _)| {
+            key.strip_prefix(category)
+                .is_some_and(|rest| rest.starts_with(':'))
+        })
+    }
+
+    /// Get all variables
+    pub fn variables(&self) -> &HashMap<String, String> {
+        self.variables.all()
+    }
+
+    /// List every variable with its definition and where it was defined, sorted by name
+    ///
+    /// Variables set with [`set_variable`](Config::set_variable) keep the location of the
+    /// definition they replaced, or have none if they are new. Seeded variables have no location.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config.seed_variable("HOME_DIR", "/home/me");
+    /// config.parse("$gap = 5\n$outer = {{gap * 2}}").unwrap();
+    ///
+    /// let entries = config.variable_entries();
+    /// assert_eq!(entries[0].name, "HOME_DIR");
+    /// assert!(entries[0].seeded);
+    /// assert_eq!((entries[1].name.as_str(), entries[1].line), ("gap", Some(1)));
+    /// assert_eq!(entries[2].raw, "{{gap * 2}}");
+    /// ```
+    pub fn variable_entries(&self) -> Vec<VariableEntry> {
+        let mut entries: Vec<_> = self
+            .variables
+            .all()
+            .iter()
+            .map(|(name, value)| {
+                let (file, line) = match self.variable_sources.get(name) {
+                    Some(VariableSource::Config { file, line }) => (file.clone(), *line),
+                    _ => (None, None),
+                };
+                VariableEntry {
+                    name: name.clone(),
+                    value: value.clone(),
+                    raw: self.variable_definitions.get(name).unwrap_or(value).clone(),
+                    file,
+                    line,
+                    seeded: self.seeded_variables.contains(name),
+                }
+            })
+            .collect();
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        entries
+    }
+
+    /// Variables that nothing refers to, sorted by name
+    ///
+    /// A variable is used when a value, handler call, `source =` path, category key,
+    /// `# hyprlang if` condition or evaluated expression refers to it, or when a variable
+    /// that is used was defined from it. Seeded variables are the application's and are
+    /// never reported. Set [`ConfigOptions::warn_unused_variables`] to have
+    /// [`check`](Self::check) report these as warnings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config
+    ///     .parse("$accent = red\n$border = $accent\n$old = blue\nborder_color = $border\n")
+    ///     .unwrap();
+    ///
+    /// let unused = config.unused_variables();
+    /// assert_eq!(unused.len(), 1);
+    /// assert_eq!((unused[0].name.as_str(), unused[0].line), ("old", Some(3)));
+    /// ```
+    pub fn unused_variables(&self) -> Vec<VariableEntry> {
+        let mut used = HashSet::new();
+        let mut pending: Vec<&str> = self.variable_uses.iter().map(String::as_str).collect();
+        while let Some(name) = pending.pop() {
+            if used.insert(name) {
+                pending.extend(self.variables.get_dependencies(name));
+            }
+        }
+
+        self.variable_entries()
+            .into_iter()
+            .filter(|entry| !entry.seeded && !used.contains(entry.name.as_str()))
+            .collect()
+    }
+
+    /// Collect the variables whose names match a glob pattern into a [`Theme`]
+    ///
+    /// `*` matches any run of characters and `?` a single character, so `col_*` picks
+    /// up `$col_bg`, `$col_fg` and so on.
+    pub fn extract_variables_matching(&self, pattern: &str) -> Theme {
+        self.variables
+            .all()
+            .iter()
+            .filter(|(name, _)| glob_matches(pattern, name))
+            .collect()
+    }
+
+    /// Update variable definitions from a [`Theme`]
+    ///
+    /// Only variables the config already defines are changed; with the `mutation`
+    /// feature their definitions are rewritten in whichever file declares them, so
+    /// [`save_all`](Config::save_all) writes the new scheme without touching anything
+    /// else. Values that were expanded from the old variables keep their old contents
+    /// until the config is parsed again.
+    ///
+    /// Returns the names of theme variables that the config doesn't define.
+    pub fn apply_theme(&mut self, theme: &Theme) -> Vec<String> {
+        let mut skipped = Vec::new();
+        for (name, value) in theme.iter() {
+            if self.variables.contains(name) {
+                self.set_variable(name.to_string(), value.to_string());
+            } else {
+                skipped.push(name.to_string());
+            }
+        }
+        skipped
+    }
+
+    /// Get all handler calls for a specific handler
+    pub fn get_handler_calls(&self, handler: &str) -> Option<&Vec<String>> {
+        self.handler_calls.get(handler)
+    }
+
+    /// Get all handler calls for a specific handler with their flags and source locations
+    ///
+    /// Records are in the same order as [`get_handler_calls`](Config::get_handler_calls).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config.register_handler_fn("bind", |_| Ok(()));
+    /// config.parse("# keys\nbind = SUPER, Q, exec, kitty").unwrap();
+    ///
+    /// let records = config.get_handler_call_records("bind").unwrap();
+    /// assert_eq!(records[0].value, "SUPER, Q, exec, kitty");
+    /// assert_eq!(records[0].line, Some(2));
+    /// ```
+    pub fn get_handler_call_records(&self, handler: &str) -> Option<&Vec<HandlerCallRecord>> {
+        self.handler_call_records.get(handler)
+    }
+
+    /// Find the index of the first call of a handler with the given value
+    ///
+    /// The value is compared after variable expansion, as
+    /// [`get_handler_calls`](Config::get_handler_calls) lists it. The index is the one
+    /// [`remove_handler_call`](Config::remove_handler_call),
+    /// [`update_handler_call`](Config::update_handler_call) and
+    /// [`insert_handler_call`](Config::insert_handler_call) take, which they map to the
+    /// call's line in the file holding it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config.register_handler_fn("bind", |_| Ok(()));
+    /// config.parse("bind = SUPER, Q, exec, kitty\nbind = SUPER, C, killactive").unwrap();
+    ///
+    /// assert_eq!(config.find_handler_call("bind", "SUPER, C, killactive"), Some(1));
+    /// assert_eq!(config.find_handler_call("bind", "SUPER, F, fullscreen"), None);
+    /// ```
+    pub fn find_handler_call(&self, handler: &str, value: &str) -> Option<usize> {
+        self.handler_calls
+            .get(handler)?
+            .iter()
+            .position(|call| call == value)
+    }
+
+    /// Find the indices of the calls of a handler whose records match a predicate
+    ///
+    /// Indices are in ascending order and follow
+    /// [`find_handler_call`](Config::find_handler_call). Removing a call shifts the calls
+    /// after it, so remove the matches from the last one back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "mutation")] {
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config.register_handler_fn("bind", |_| Ok(()));
+    /// config
+    ///     .parse("bind = SUPER, Q, exec, kitty\nbind = SUPER, C, killactive\nbind = SUPER, B, exec, firefox")
+    ///     .unwrap();
+    ///
+    /// let execs = config.handler_call_indices_matching("bind", |record| record.value.contains("exec"));
+    /// assert_eq!(execs, [0, 2]);
+    ///
+    /// for index in execs.into_iter().rev() {
+    ///     config.remove_handler_call("bind", index).unwrap();
+    /// }
+    /// assert_eq!(config.serialize(), "bind = SUPER, C, killactive\n");
+    /// # }
+    /// ```
+    pub fn handler_call_indices_matching(
+        &self,
+        handler: &str,
+        mut predicate: impl FnMut(&HandlerCallRecord) -> bool,
+    ) -> Vec<usize> {
+        self.handler_call_records
+            .get(handler)
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .filter(|(_, record)| predicate(record))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Get the calls of several handlers merged into the order they were parsed in
+    ///
+    /// Each record comes with the name of its handler. The order follows
+    /// [`HandlerCallRecord::sequence`], so calls from sourced files sit where the `source`
+    /// line was.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config.register_handler_fn("exec", |_| Ok(()));
+    /// config.register_handler_fn("exec-once", |_| Ok(()));
+    /// config.parse("exec-once = waybar\nexec = notify\nexec-once = dunst").unwrap();
+    ///
+    /// let merged: Vec<_> = config
+    ///     .handler_call_records_in_order(&["exec", "exec-once"])
+    ///     .into_iter()
+    ///     .map(|(handler, record)| format!("{} = {}", handler, record.value))
+    ///     .collect();
+    /// assert_eq!(merged, ["exec-once = waybar", "exec = notify", "exec-once = dunst"]);
+    /// ```
+    pub fn handler_call_records_in_order<'a>(
+        &'a self,
+        handlers: &[&'a str],
+    ) -> Vec<(&'a str, &'a HandlerCallRecord)> {
+        let mut merged: Vec<_> = handlers
+            .iter()
+            .flat_map(|handler| {
+                self.handler_call_records
+                    .get(*handler)
+                    .into_iter()
+                    .flatten()
+                    .map(move |record| (*handler, record))
+            })
+            .collect();
+        merged.sort_by_key(|(_, record)| record.sequence);
+        merged
+    }
+
+    /// List values and handler calls together, sorted by key
+    ///
+    /// Values and handler calls are stored apart, so a category like `animations` holds
+    /// both `animations:enabled` and `animation` calls. Each handler's calls keep their
+    /// order. Registered defaults are included, as with [`iter`](Config::iter).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyprlang::{Config, UnifiedEntry};
+    ///
+    /// let mut config = Config::new();
+    /// config.register_category_handler_fn("animations", "animation", |_| Ok(()));
+    /// config
+    ///     .parse("animations {\n    enabled = true\n    animation = windows, 1, 4, default\n}")
+    ///     .unwrap();
+    ///
+    /// let lines: Vec<String> = config
+    ///     .unified_entries()
+    ///     .iter()
+    ///     .filter(|entry| entry.key().starts_with("animations:"))
+    ///     .map(|entry| format!("{} = {}", entry.key(), entry.text()))
+    ///     .collect();
+    /// assert_eq!(
+    ///     lines,
+    ///     ["animations:animation = windows, 1, 4, default", "animations:enabled = 1"]
+    /// );
+    /// ```
+    pub fn unified_entries(&self) -> Vec<UnifiedEntry<'_>> {
+        let mut entries: Vec<_> = self
+            .values
+            .iter()
+            .map(|(key, entry)| UnifiedEntry::Value { key, entry })
+            .collect();
+        for (handler, records) in &self.handler_call_records {
+            let calls = records.iter().enumerate();
+            entries.extend(calls.map(|(index, record)| UnifiedEntry::HandlerCall {
+                handler,
+                index,
+                record,
+            }));
+        }
+
+        // Stable, so the calls of one handler stay in order
+        entries.sort_by(|a, b| a.key().cmp(b.key()));
+        entries
+    }
+
+    /// Run the handler for a call, or only check that it exists while checking
+    fn run_handler(
+        &mut self,
+        keyword: &str,
+        value: &str,
+        flags: Option<String>,
+        line: usize,
+    ) -> ParseResult<()> {
+        let dry_run = self.options.dry_run_handlers;
+        let has_flags = flags.is_some();
+        let context = HandlerContext::new(keyword.to_string(), value.to_string())
+            .with_category(self.current_path.clone())
+            .with_flags(flags.unwrap_or_default())
+            .with_dry_run(dry_run)
+            .with_location(self.current_source_file.clone(), line);
+        self.validate_handler_call(&context)?;
+
+        let result = if self.check_diagnostics.is_some() && !dry_run {
+            self.handlers.check(&self.current_path, keyword, has_flags)
+        } else {
+            self.handlers
+                .execute_context(context, self.handler_state.0.as_deref_mut())
+        };
+        result.map_err(|error| error.with_handler_location(self.current_source_file.clone(), line))
+    }
+
+    /// Run the handler for a call and store the call, unless the handler rejects it
+    ///
+    /// Calls a handler fails on are stored all the same. `store` is false for calls that
+    /// are only run, such as unregistered keywords inside categories.
+    fn handle_call(
+        &mut self,
+        keyword: &str,
+        value: &str,
+        flags: Option<String>,
+        line: usize,
+        alias: Option<String>,
+        store: bool,
+    ) -> ParseResult<()> {
+        // Create full key including category path for handler calls
+        let full_key = if self.current_path.is_empty() {
+            keyword.to_string()
+        } else {
+            format!("{}:{}", self.current_path.join(":"), keyword)
+        };
 
-                    // If this is a new variable, register it with the primary file
-                    if multi_doc.get_key_source(&var_key).is_none() {
-                        multi_doc.register_key(var_key, source_file);
-                    }
-                    true
-                } else {
-                    false
-                }
-            } else {
-                false
-            };
+        let result = self.run_handler(keyword, value, flags.clone(), line);
+        if let Err(error) = &result
+            && let Some(reason) = error.rejection()
+        {
+            self.rejected_handler_calls.push(RejectedHandlerCall {
+                handler: full_key.clone(),
+                value: value.to_string(),
+                reason: reason.to_string(),
+                file: self.current_source_file.clone(),
+                line,
+            });
+
+            // The line stays in the document, so edits by index have to skip it
+            self.rejected_call_records
+                .entry(full_key)
+                .or_default()
+                .push(HandlerCallRecord {
+                    value: value.to_string(),
+                    flags,
+                    file: self.current_source_file.clone(),
+                    line: Some(line),
+                    noerror: self.directives.should_suppress_errors(),
+                    alias,
+                    sequence: self.handler_call_sequence,
+                });
+            self.handler_call_sequence += 1;
+            return Ok(());
+        }
 
-            // Fallback: update single document if multi_document didn't handle it
-            if !updated_in_multi
-                && let Some(doc) = &mut self.document
+        if store {
+            self.record_handler_call(&full_key, value, flags, Some(line), alias);
+
+            // Track handler origin in multi_document
+            #[cfg(feature = "mutation")]
+            if let (Some(multi_doc), Some(source_file)) =
+                (&mut self.multi_document, &self.current_source_file)
             {
-                let _ = doc.update_or_insert_variable(&name, &value);
+                multi_doc.register_handler(full_key, source_file.clone());
             }
         }
+        result
     }
 
-    /// Get all configuration keys
-    pub fn keys(&self) -> Vec<&str> {
-        self.values.keys().map(|s| s.as_str()).collect()
+    /// Run every validator registered for the keyword of a handler call
+    fn validate_handler_call(&self, context: &HandlerContext) -> ParseResult<()> {
+        let full_key = match context.category.is_empty() {
+            true => context.keyword.clone(),
+            false => format!("{}:{}", context.category.join(":"), context.keyword),
+        };
+        for (pattern, validator) in &self.handler_validators {
+            if *pattern != context.keyword && *pattern != full_key {
+                continue;
+            }
+            validator(context).map_err(|reason| ConfigError::HandlerCallFailed {
+                keyword: context.keyword.clone(),
+                value: context.value.clone(),
+                category: context.category.clone(),
+                file: context.file.clone(),
+                line: context.line,
+                error: Box::new(ConfigError::validation(&full_key, &context.value, reason)),
+            })?;
+        }
+        Ok(())
     }
 
-    /// Get all variables
-    pub fn variables(&self) -> &HashMap<String, String> {
-        self.variables.all()
+    /// Expand variables in a handler value, reporting failures with the call they were in
+    ///
+    /// Under `throw_all_errors` the error is collected and the value is kept as written.
+    fn expand_handler_value(
+        &mut self,
+        keyword: &str,
+        value: &str,
+        line: usize,
+    ) -> ParseResult<String> {
+        let undefined = self
+            .options
+            .strict_handler_variables
+            .then(|| self.variables.find_undefined(value))
+            .flatten();
+        let error = match undefined {
+            Some(name) => ConfigError::variable_not_found(name),
+            None => match self.variables.expand(value) {
+                Ok(expanded) => {
+                    self.note_variable_uses(value, false);
+                    return Ok(expanded);
+                }
+                Err(error) => error,
+            },
+        };
+
+        let variable = match &error {
+            ConfigError::VariableNotFound { name } => Some(name.clone()),
+            ConfigError::CircularDependency { chain } => chain.first().cloned(),
+            _ => None,
+        };
+        let error = ConfigError::HandlerExpansionFailed {
+            keyword: keyword.to_string(),
+            value: value.to_string(),
+            variable,
+            file: self.current_source_file.clone(),
+            line,
+            error: Box::new(error),
+        };
+        if self.options.throw_all_errors {
+            self.errors.push(error);
+            Ok(value.to_string())
+        } else {
+            Err(error)
+        }
     }
 
-    /// Get all handler calls for a specific handler
-    pub fn get_handler_calls(&self, handler: &str) -> Option<&Vec<String>> {
-        self.handler_calls.get(handler)
+    /// Store a handler call value along with its record
+    fn record_handler_call(
+        &mut self,
+        handler: &str,
+        value: &str,
+        flags: Option<String>,
+        line: Option<usize>,
+        alias: Option<String>,
+    ) {
+        self.handler_calls
+            .entry(handler.to_string())
+            .or_default()
+            .push(value.to_string());
+        self.handler_call_records
+            .entry(handler.to_string())
+            .or_default()
+            .push(HandlerCallRecord {
+                value: value.to_string(),
+                flags,
+                file: self.current_source_file.clone(),
+                line,
+                noerror: line.is_some() && self.directives.should_suppress_errors(),
+                alias,
+                sequence: self.handler_call_sequence,
+            });
+        self.handler_call_sequence += 1;
+
+        #[cfg(feature = "mutation")]
+        self.declaration_order
+            .push(Declaration::HandlerCall(handler.to_string()));
     }
 
     /// Get all handler names that have been called
@@ -1030,6 +3930,133 @@ impl Config {
         &self.handler_calls
     }
 
+    /// An immutable copy of the parsed data that can be shared across threads
+    ///
+    /// Holds values, variables, handler calls and special category instances; see
+    /// [`SharedSnapshot`](crate::SharedSnapshot) to publish snapshots to concurrent readers.
+    #[cfg(feature = "snapshot")]
+    pub fn snapshot(&self) -> crate::snapshot::ConfigSnapshot {
+        use crate::snapshot::{ConfigSnapshot, SnapshotValue};
+
+        let values = self
+            .values
+            .iter()
+            .map(|(key, entry)| (key.clone(), SnapshotValue::from_entry(entry)))
+            .collect();
+
+        let mut special_categories: HashMap<String, HashMap<_, _>> = HashMap::new();
+        for category in self.special_categories.category_names() {
+            for key in self.special_categories.list_keys(category) {
+                let Ok(instance) = self.special_categories.get_instance(category, &key) else {
+                    continue;
+                };
+                let properties = instance
+                    .values
+                    .iter()
+                    .map(|(property, entry)| (property.clone(), SnapshotValue::from_entry(entry)))
+                    .collect();
+                special_categories
+                    .entry(category.to_string())
+                    .or_default()
+                    .insert(key, properties);
+            }
+        }
+
+        ConfigSnapshot {
+            values,
+            variables: self.variables.all().clone(),
+            handler_calls: self.handler_calls.clone(),
+            special_categories,
+            generation: self.generation,
+        }
+    }
+
+    /// Check whether two configs hold the same data, regardless of how it was written
+    ///
+    /// Compares values, variables, handler call lists (in order) and special category
+    /// instances. Formatting, comments, declaration order, source files and entry metadata
+    /// are ignored. Custom-typed values are compared by their raw text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyprlang::Config;
+    ///
+    /// let mut a = Config::new();
+    /// a.parse("general {\n    gaps_in = 5\n}\n$GAP = 5").unwrap();
+    ///
+    /// let mut b = Config::new();
+    /// b.parse("$GAP = 5\ngeneral:gaps_in   =   5").unwrap();
+    ///
+    /// assert!(a.semantically_equal(&b));
+    /// ```
+    pub fn semantically_equal(&self, other: &Config) -> bool {
+        fn entries_equal(a: &ConfigValueEntry, b: &ConfigValueEntry) -> bool {
+            match (&a.value, &b.value) {
+                (ConfigValue::Int(x), ConfigValue::Int(y)) => x == y,
+                (ConfigValue::Float(x), ConfigValue::Float(y)) => x == y,
+                (ConfigValue::String(x), ConfigValue::String(y)) => x == y,
+                (ConfigValue::Vec2(x), ConfigValue::Vec2(y)) => x == y,
+                (ConfigValue::Color(x), ConfigValue::Color(y)) => x == y,
+                (
+                    ConfigValue::Quantity { value: x, unit: u },
+                    ConfigValue::Quantity { value: y, unit: v },
+                ) => x == y && u == v,
+                (
+                    ConfigValue::Custom { type_name: x, .. },
+                    ConfigValue::Custom { type_name: y, .. },
+                ) => x == y && a.raw == b.raw,
+                _ => false,
+            }
+        }
+
+        fn maps_equal(
+            a: &HashMap<String, ConfigValueEntry>,
+            b: &HashMap<String, ConfigValueEntry>,
+        ) -> bool {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(key, entry)| b.get(key).is_some_and(|o| entries_equal(entry, o)))
+        }
+
+        fn non_empty_calls(calls: &HashMap<String, Vec<String>>) -> HashMap<&str, &[String]> {
+            calls
+                .iter()
+                .filter(|(_, values)| !values.is_empty())
+                .map(|(name, values)| (name.as_str(), values.as_slice()))
+                .collect()
+        }
+
+        if !maps_equal(&self.values, &other.values)
+            || self.variables.all() != other.variables.all()
+            || non_empty_calls(&self.handler_calls) != non_empty_calls(&other.handler_calls)
+        {
+            return false;
+        }
+
+        let mut categories = self.special_categories.category_names();
+        let mut other_categories = other.special_categories.category_names();
+        categories.sort_unstable();
+        other_categories.sort_unstable();
+        if categories != other_categories {
+            return false;
+        }
+
+        categories.into_iter().all(|category| {
+            let keys = self.special_categories.list_keys(category);
+            keys.len() == other.special_categories.list_keys(category).len()
+                && keys.iter().all(|key| {
+                    match (
+                        self.special_categories.get_instance(category, key),
+                        other.special_categories.get_instance(category, key),
+                    ) {
+                        (Ok(a), Ok(b)) => maps_equal(&a.values, &b.values),
+                        _ => false,
+                    }
+                })
+        })
+    }
+
     // ========== MUTATION METHODS (mutation feature) ==========
 
     /// Set an integer configuration value.
@@ -1043,12 +4070,12 @@ impl Config {
     /// use hyprlang::Config;
     ///
     /// let mut config = Config::new();
-    /// config.set_int("border_size", 5);
+    /// config.set_int("border_size", 5).unwrap();
     /// assert_eq!(config.get_int("border_size").unwrap(), 5);
     /// # }
     /// ```
     #[cfg(feature = "mutation")]
-    pub fn set_int(&mut self, key: impl Into<String>, value: i64) {
+    pub fn set_int(&mut self, key: impl Into<String>, value: i64) -> ParseResult<()> {
         self.set(key, ConfigValue::Int(value))
     }
 
@@ -1063,12 +4090,12 @@ impl Config {
     /// use hyprlang::Config;
     ///
     /// let mut config = Config::new();
-    /// config.set_float("opacity", 0.95);
+    /// config.set_float("opacity", 0.95).unwrap();
     /// assert_eq!(config.get_float("opacity").unwrap(), 0.95);
     /// # }
     /// ```
     #[cfg(feature = "mutation")]
-    pub fn set_float(&mut self, key: impl Into<String>, value: f64) {
+    pub fn set_float(&mut self, key: impl Into<String>, value: f64) -> ParseResult<()> {
         self.set(key, ConfigValue::Float(value))
     }
 
@@ -1083,12 +4110,16 @@ impl Config {
     /// use hyprlang::Config;
     ///
     /// let mut config = Config::new();
-    /// config.set_string("terminal", "kitty");
+    /// config.set_string("terminal", "kitty").unwrap();
     /// assert_eq!(config.get_string("terminal").unwrap(), "kitty");
     /// # }
     /// ```
     #[cfg(feature = "mutation")]
-    pub fn set_string(&mut self, key: impl Into<String>, value: impl Into<String>) {
+    pub fn set_string(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> ParseResult<()> {
         self.set(key, ConfigValue::String(value.into()))
     }
 
@@ -1103,7 +4134,7 @@ impl Config {
     /// use hyprlang::{Config, ConfigValue};
     ///
     /// let mut config = Config::new();
-    /// config.set_int("test", 42);
+    /// config.set_int("test", 42).unwrap();
     ///
     /// let removed = config.remove("test").unwrap();
     /// assert_eq!(removed.as_int().unwrap(), 42);
@@ -1112,15 +4143,20 @@ impl Config {
     /// ```
     #[cfg(feature = "mutation")]
     pub fn remove(&mut self, key: &str) -> ParseResult<ConfigValue> {
+        let key = self.resolve_key(key).into_owned();
         let entry = self
             .values
-            .remove(key)
-            .ok_or_else(|| ConfigError::key_not_found(key))?;
+            .remove(&key)
+            .ok_or_else(|| ConfigError::key_not_found(&key))?;
+        let document_key = match self.key_spellings.remove(&key) {
+            Some(spelling) => spelling,
+            None => self.written_path(&key),
+        };
 
         #[cfg(feature = "mutation")]
         {
             if let Some(doc) = &mut self.document {
-                let _ = doc.remove_value(key);
+                let _ = doc.remove_value(&document_key);
             }
         }
 
@@ -1159,18 +4195,10 @@ impl Config {
     #[cfg(feature = "mutation")]
     pub fn get_variable_mut(&mut self, name: &str) -> Option<crate::mutation::MutableVariable<'_>> {
         if self.variables.contains(name) {
-            // We need to use unsafe here to work around the borrow checker
-            // This is safe because we're only accessing disjoint fields
-            let manager_ptr = &mut self.variables as *mut VariableManager;
-            let doc_ptr = &mut self.document as *mut Option<crate::document::ConfigDocument>;
-
-            unsafe {
-                Some(crate::mutation::MutableVariable::new(
-                    name.to_string(),
-                    &mut *manager_ptr,
-                    (*doc_ptr).as_mut(),
-                ))
-            }
+            Some(crate::mutation::MutableVariable::new(
+                name.to_string(),
+                self,
+            ))
         } else {
             None
         }
@@ -1197,6 +4225,9 @@ impl Config {
     #[cfg(feature = "mutation")]
     pub fn remove_variable(&mut self, name: &str) -> Option<String> {
         let value = self.variables.remove(name);
+        self.variable_sources.remove(name);
+        self.variable_definitions.remove(name);
+        self.seeded_variables.remove(name);
 
         #[cfg(feature = "mutation")]
         {
@@ -1205,15 +4236,234 @@ impl Config {
             }
         }
 
-        value
-    }
+        value
+    }
+
+    // ========== HANDLER MUTATIONS ==========
+
+    /// Add a handler call.
+    ///
+    /// Handler calls are stored as arrays, so you can add multiple calls for the same handler.
+    /// The handler must be registered before parsing, but you can add calls dynamically after parsing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "mutation")] {
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config.register_handler_fn("bind", |_| Ok(()));
+    ///
+    /// config.add_handler_call("bind", "SUPER, Q, exec, terminal".to_string()).unwrap();
+    /// config.add_handler_call("bind", "SUPER, C, killactive".to_string()).unwrap();
+    ///
+    /// let binds = config.get_handler_calls("bind").unwrap();
+    /// assert_eq!(binds.len(), 2);
+    /// # }
+    /// ```
+    #[cfg(feature = "mutation")]
+    pub fn add_handler_call(
+        &mut self,
+        handler: impl Into<String>,
+        value: String,
+    ) -> ParseResult<()> {
+        let keyword = handler.into();
+        let (handler, flags) = self.split_handler_flags(&keyword);
+
+        // Update in-memory state
+        self.record_handler_call(&handler, &value, flags, None, None);
+
+        #[cfg(feature = "mutation")]
+        {
+            // Try to update in the correct source file using multi_document
+            let updated_in_multi = if let Some(multi_doc) = &mut self.multi_document {
+                // Find which file has this handler, or use primary file
+                let source_file = multi_doc
+                    .get_handler_source(&handler)
+                    .cloned()
+                    .unwrap_or_else(|| multi_doc.primary_path.clone());
+
+                if let Some(doc) = multi_doc.get_document_mut(&source_file) {
+                    let _ = doc.add_handler_call(&keyword, &value);
+                    multi_doc.mark_dirty(&source_file);
+                    true
+                } else {
+                    false
+                }
+            } else {
+                false
+            };
+
+            // Fallback: update single document if multi_document didn't handle it
+            if !updated_in_multi && let Some(doc) = &mut self.document {
+                let _ = doc.add_handler_call(&keyword, &value);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remove all handler calls for a specific handler.
+    ///
+    /// Returns the array of handler call values if the handler had any calls, or `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "mutation")] {
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config.register_handler_fn("bind", |_| Ok(()));
+    /// config.parse("bind = SUPER, Q, exec, terminal\nbind = SUPER, C, killactive").unwrap();
+    ///
+    /// let removed = config.remove_handler_calls("bind");
+    /// assert_eq!(removed.unwrap().len(), 2);
+    /// assert!(config.get_handler_calls("bind").is_none());
+    /// # }
+    /// ```
+    #[cfg(feature = "mutation")]
+    pub fn remove_handler_calls(&mut self, handler: &str) -> Option<Vec<String>> {
+        let written = self.written_handler_keys(handler);
+        self.handler_call_records.remove(handler);
+        self.rejected_call_records.remove(handler);
+        let removed = self.handler_calls.remove(handler);
+
+        // Calls may be spread over several sourced files, so every document is checked
+        let mut removed_in_multi = false;
+        if let Some(multi_doc) = &mut self.multi_document {
+            let mut touched = Vec::new();
+            for (path, doc) in multi_doc.documents.iter_mut() {
+                let count: usize = written
+                    .iter()
+                    .map(|key| doc.remove_handler_calls(key))
+                    .sum();
+                if count > 0 {
+                    touched.push(path.clone());
+                }
+            }
+            removed_in_multi = !touched.is_empty();
+            for path in touched {
+                multi_doc.mark_dirty(&path);
+            }
+        }
+
+        if !removed_in_multi && let Some(doc) = &mut self.document {
+            for key in &written {
+                doc.remove_handler_calls(key);
+            }
+        }
+
+        removed
+    }
+
+    /// Remove a specific handler call by index.
+    ///
+    /// Returns an error if the handler doesn't exist or if the index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "mutation")] {
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config.register_handler_fn("bind", |_| Ok(()));
+    /// config.parse("bind = SUPER, Q, exec, terminal\nbind = SUPER, C, killactive").unwrap();
+    ///
+    /// // Remove the first bind
+    /// let removed = config.remove_handler_call("bind", 0).unwrap();
+    /// assert_eq!(removed, "SUPER, Q, exec, terminal");
+    ///
+    /// // Only one bind remains
+    /// assert_eq!(config.get_handler_calls("bind").unwrap().len(), 1);
+    /// # }
+    /// ```
+    #[cfg(feature = "mutation")]
+    pub fn remove_handler_call(&mut self, handler: &str, index: usize) -> ParseResult<String> {
+        let calls = self
+            .handler_calls
+            .get_mut(handler)
+            .ok_or_else(|| ConfigError::handler(handler, "no calls found"))?;
+
+        if index >= calls.len() {
+            return Err(ConfigError::custom("index out of bounds"));
+        }
+
+        let value = calls.remove(index);
+        let (source, written, doc_index) = self.written_handler_call(handler, index);
+        if let Some(records) = self.handler_call_records.get_mut(handler) {
+            records.remove(index);
+        }
+
+        // Remove from the document of the file holding the call
+        self.edit_source_document(source, |doc| doc.remove_handler_call(&written, doc_index));
+
+        Ok(value)
+    }
+
+    /// Replace the value of a specific handler call by index.
+    ///
+    /// Returns the previous value, or an error if the handler doesn't exist or if the
+    /// index is out of bounds. The matching line in the document is rewritten in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "mutation")] {
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config.register_handler_fn("bind", |_| Ok(()));
+    /// config.parse("bind = SUPER, Q, exec, terminal").unwrap();
+    ///
+    /// let old = config.update_handler_call("bind", 0, "SUPER, Q, exec, kitty".to_string()).unwrap();
+    /// assert_eq!(old, "SUPER, Q, exec, terminal");
+    /// assert!(config.serialize().contains("bind = SUPER, Q, exec, kitty"));
+    /// # }
+    /// ```
+    #[cfg(feature = "mutation")]
+    pub fn update_handler_call(
+        &mut self,
+        handler: &str,
+        index: usize,
+        value: String,
+    ) -> ParseResult<String> {
+        let calls = self
+            .handler_calls
+            .get_mut(handler)
+            .ok_or_else(|| ConfigError::handler(handler, "no calls found"))?;
+
+        if index >= calls.len() {
+            return Err(ConfigError::custom("index out of bounds"));
+        }
+
+        let old = std::mem::replace(&mut calls[index], value.clone());
+        let (source, written, doc_index) = self.written_handler_call(handler, index);
+        if let Some(record) = self
+            .handler_call_records
+            .get_mut(handler)
+            .and_then(|records| records.get_mut(index))
+        {
+            record.value = value.clone();
+        }
+
+        // Update the line in the document of the file holding the call
+        self.edit_source_document(source, |doc| {
+            doc.update_handler_call(&written, doc_index, &value)
+        });
 
-    // ========== HANDLER MUTATIONS ==========
+        Ok(old)
+    }
 
-    /// Add a handler call.
+    /// Rewrite every call of a handler with a function of its value.
     ///
-    /// Handler calls are stored as arrays, so you can add multiple calls for the same handler.
-    /// The handler must be registered before parsing, but you can add calls dynamically after parsing.
+    /// All new values are computed before any call changes, then each call whose value
+    /// changed is updated like [`update_handler_call`](Config::update_handler_call), in the
+    /// file holding it. Lines whose value is unchanged are left as written; a changed line
+    /// gets the new value as given, with variables it used expanded. Returns the number of
+    /// calls changed; a handler without calls changes none.
     ///
     /// # Examples
     ///
@@ -1223,63 +4473,47 @@ impl Config {
     ///
     /// let mut config = Config::new();
     /// config.register_handler_fn("bind", |_| Ok(()));
+    /// config.parse("bind = ALT, Q, exec, kitty\nbind = SUPER, C, killactive").unwrap();
     ///
-    /// config.add_handler_call("bind", "SUPER, Q, exec, terminal".to_string()).unwrap();
-    /// config.add_handler_call("bind", "SUPER, C, killactive".to_string()).unwrap();
-    ///
-    /// let binds = config.get_handler_calls("bind").unwrap();
-    /// assert_eq!(binds.len(), 2);
+    /// let changed = config.map_handler_calls("bind", |value| value.replace("ALT", "SUPER"));
+    /// assert_eq!(changed, 1);
+    /// assert_eq!(
+    ///     config.serialize(),
+    ///     "bind = SUPER, Q, exec, kitty\nbind = SUPER, C, killactive\n"
+    /// );
     /// # }
     /// ```
     #[cfg(feature = "mutation")]
-    pub fn add_handler_call(
+    pub fn map_handler_calls(
         &mut self,
-        handler: impl Into<String>,
-        value: String,
-    ) -> ParseResult<()> {
-        let handler = handler.into();
-
-        // Update in-memory state
-        self.handler_calls
-            .entry(handler.clone())
-            .or_default()
-            .push(value.clone());
-
-        #[cfg(feature = "mutation")]
-        {
-            // Try to update in the correct source file using multi_document
-            let updated_in_multi = if let Some(multi_doc) = &mut self.multi_document {
-                // Find which file has this handler, or use primary file
-                let source_file = multi_doc
-                    .get_handler_source(&handler)
-                    .cloned()
-                    .unwrap_or_else(|| multi_doc.primary_path.clone());
-
-                if let Some(doc) = multi_doc.get_document_mut(&source_file) {
-                    let _ = doc.add_handler_call(&handler, &value);
-                    multi_doc.mark_dirty(&source_file);
-                    true
-                } else {
-                    false
-                }
-            } else {
-                false
-            };
-
-            // Fallback: update single document if multi_document didn't handle it
-            if !updated_in_multi {
-                if let Some(doc) = &mut self.document {
-                    let _ = doc.add_handler_call(&handler, &value);
-                }
-            }
+        handler: &str,
+        mut rewrite: impl FnMut(&str) -> String,
+    ) -> usize {
+        let changed: Vec<(usize, String)> = self
+            .handler_calls
+            .get(handler)
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .filter_map(|(index, value)| {
+                let rewritten = rewrite(value);
+                (rewritten != *value).then_some((index, rewritten))
+            })
+            .collect();
+
+        for (index, value) in &changed {
+            let _ = self.update_handler_call(handler, *index, value.clone());
         }
-
-        Ok(())
+        changed.len()
     }
 
-    /// Remove all handler calls for a specific handler.
+    /// Insert a handler call so that it ends up at `index` among the handler's calls.
     ///
-    /// Returns the array of handler call values if the handler had any calls, or `None` otherwise.
+    /// The new line is written right after the call currently at `index - 1` (or right
+    /// before the first call when `index` is 0), in the same file and block, so the saved
+    /// files parse back in the same order. Returns an error if `index` is past the end;
+    /// a handler without calls gets the call appended like
+    /// [`add_handler_call`](Config::add_handler_call).
     ///
     /// # Examples
     ///
@@ -1289,84 +4523,236 @@ impl Config {
     ///
     /// let mut config = Config::new();
     /// config.register_handler_fn("bind", |_| Ok(()));
-    /// config.parse("bind = SUPER, Q, exec, terminal\nbind = SUPER, C, killactive").unwrap();
+    /// config.parse("bind = SUPER, Q, exec, kitty\ngaps = 5\nbind = SUPER, C, killactive").unwrap();
     ///
-    /// let removed = config.remove_handler_calls("bind");
-    /// assert_eq!(removed.unwrap().len(), 2);
-    /// assert!(config.get_handler_calls("bind").is_none());
+    /// config.insert_handler_call("bind", 1, "SUPER, F, fullscreen".to_string()).unwrap();
+    /// assert_eq!(config.get_handler_calls("bind").unwrap()[1], "SUPER, F, fullscreen");
+    /// assert_eq!(
+    ///     config.serialize(),
+    ///     "bind = SUPER, Q, exec, kitty\nbind = SUPER, F, fullscreen\ngaps = 5\nbind = SUPER, C, killactive\n"
+    /// );
     /// # }
     /// ```
     #[cfg(feature = "mutation")]
-    pub fn remove_handler_calls(&mut self, handler: &str) -> Option<Vec<String>> {
-        // TODO: Remove from document tree
-        // if let Some(doc) = &mut self.document {
-        //     let _ = doc.remove_handler_calls(handler);
-        // }
+    pub fn insert_handler_call(
+        &mut self,
+        handler: &str,
+        index: usize,
+        value: String,
+    ) -> ParseResult<()> {
+        let keyword = handler;
+        let (handler, flags) = self.split_handler_flags(keyword);
+        let handler = handler.as_str();
+        let (Some(calls), Some(records)) = (
+            self.handler_calls.get(handler),
+            self.handler_call_records.get(handler),
+        ) else {
+            return self.add_handler_call(keyword, value);
+        };
+        if index > calls.len() {
+            return Err(ConfigError::custom("index out of bounds"));
+        }
+        if calls.is_empty() {
+            return self.add_handler_call(keyword, value);
+        }
+
+        // Anchor on the call the new one will follow, or precede at the front
+        let after = index > 0;
+        let anchor = &records[index.saturating_sub(1)];
+        let file = anchor.file.clone();
+        let sequence = anchor.sequence + u64::from(after);
+        let doc_index = records[..index.saturating_sub(1)]
+            .iter()
+            .chain(
+                self.rejected_call_records
+                    .get(handler)
+                    .into_iter()
+                    .flatten(),
+            )
+            .filter(|record| {
+                record.flags == anchor.flags
+                    && record.file == file
+                    && record.sequence < anchor.sequence
+            })
+            .count();
+        let written = format!("{}{}", handler, anchor.flags.as_deref().unwrap_or_default());
+
+        let source = file.clone().or_else(|| {
+            self.multi_document.as_ref().map(|multi_doc| {
+                multi_doc
+                    .get_handler_source(handler)
+                    .cloned()
+                    .unwrap_or_else(|| multi_doc.primary_path.clone())
+            })
+        });
+        self.edit_source_document(source, |doc| {
+            doc.insert_handler_call_beside(&written, doc_index, after, keyword, &value)
+        });
+
+        // Make room in the parse order for the new call
+        let rejected = self.rejected_call_records.values_mut().flatten();
+        for record in self
+            .handler_call_records
+            .values_mut()
+            .flatten()
+            .chain(rejected)
+        {
+            if record.sequence >= sequence {
+                record.sequence += 1;
+            }
+        }
+        self.handler_call_sequence += 1;
 
-        self.handler_calls.remove(handler)
+        if let Some(calls) = self.handler_calls.get_mut(handler) {
+            calls.insert(index, value.clone());
+        }
+        if let Some(records) = self.handler_call_records.get_mut(handler) {
+            records.insert(
+                index,
+                HandlerCallRecord {
+                    value,
+                    flags,
+                    file,
+                    line: None,
+                    noerror: false,
+                    alias: None,
+                    sequence,
+                },
+            );
+        }
+        self.declaration_order
+            .push(Declaration::HandlerCall(handler.to_string()));
+
+        Ok(())
     }
 
-    /// Remove a specific handler call by index.
+    /// Add a handler call right after the last call of that handler from `file`.
     ///
-    /// Returns an error if the handler doesn't exist or if the index is out of bounds.
+    /// `file` is matched against the paths reported by
+    /// [`get_source_files`](Config::get_source_files). Returns the index of the new call, or
+    /// an error if the file has no calls for the handler.
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
     /// # #[cfg(feature = "mutation")] {
     /// use hyprlang::Config;
+    /// use std::path::Path;
     ///
     /// let mut config = Config::new();
     /// config.register_handler_fn("bind", |_| Ok(()));
-    /// config.parse("bind = SUPER, Q, exec, terminal\nbind = SUPER, C, killactive").unwrap();
-    ///
-    /// // Remove the first bind
-    /// let removed = config.remove_handler_call("bind", 0).unwrap();
-    /// assert_eq!(removed, "SUPER, Q, exec, terminal");
+    /// config.parse_file("hyprland.conf").unwrap();
     ///
-    /// // Only one bind remains
-    /// assert_eq!(config.get_handler_calls("bind").unwrap().len(), 1);
+    /// // Keep the new bind next to the others in keys.conf
+    /// let keys = Path::new("/home/user/.config/hypr/keys.conf");
+    /// config.add_handler_call_after_file("bind", keys, "SUPER, F, fullscreen".to_string()).unwrap();
+    /// config.save_all().unwrap();
     /// # }
     /// ```
     #[cfg(feature = "mutation")]
-    pub fn remove_handler_call(&mut self, handler: &str, index: usize) -> ParseResult<String> {
-        let calls = self
-            .handler_calls
-            .get_mut(handler)
-            .ok_or_else(|| ConfigError::handler(handler, "no calls found"))?;
-
-        if index >= calls.len() {
-            return Err(ConfigError::custom("index out of bounds"));
-        }
-
-        let value = calls.remove(index);
+    pub fn add_handler_call_after_file(
+        &mut self,
+        handler: &str,
+        file: &Path,
+        value: String,
+    ) -> ParseResult<usize> {
+        let index = self
+            .handler_call_records
+            .get(handler)
+            .and_then(|records| {
+                records
+                    .iter()
+                    .rposition(|record| record.file.as_deref() == Some(file))
+            })
+            .ok_or_else(|| {
+                ConfigError::handler(handler, format!("no calls found in {}", file.display()))
+            })?
+            + 1;
+
+        self.insert_handler_call(handler, index, value)?;
+        Ok(index)
+    }
 
-        // Remove from document tree for serialization consistency
-        // Try multi_document first, then fall back to single document
-        let removed_in_multi = if let Some(multi_doc) = &mut self.multi_document {
-            // Find which file has this handler
-            if let Some(source_file) = multi_doc.get_handler_source(handler).cloned() {
-                if let Some(doc) = multi_doc.get_document_mut(&source_file) {
-                    let _ = doc.remove_handler_call(handler, index);
-                    multi_doc.mark_dirty(&source_file);
-                    true
-                } else {
-                    false
-                }
-            } else {
-                false
-            }
-        } else {
-            false
+    /// Where a handler call is written: the file holding it, the keyword it was written
+    /// with (`bindl` for a `bind` call with flags `l`, or the alias it used) and its index
+    /// among the calls written that way in that file, as the document counts them
+    ///
+    /// Calls added at runtime live in the file [`add_handler_call`](Config::add_handler_call)
+    /// writes them to.
+    #[cfg(feature = "mutation")]
+    fn written_handler_call(
+        &self,
+        handler: &str,
+        index: usize,
+    ) -> (Option<PathBuf>, String, usize) {
+        let fallback = self.multi_document.as_ref().map(|multi_doc| {
+            multi_doc
+                .get_handler_source(handler)
+                .cloned()
+                .unwrap_or_else(|| multi_doc.primary_path.clone())
+        });
+        let Some(records) = self.handler_call_records.get(handler) else {
+            return (fallback, handler.to_string(), index);
         };
+        let file_of = |record: &HandlerCallRecord| record.file.clone().or_else(|| fallback.clone());
+
+        let record = records.get(index);
+        let file = record.map_or_else(|| fallback.clone(), file_of);
+        let flags = record.and_then(|record| record.flags.as_deref());
+        let alias = record.and_then(|record| record.alias.as_deref());
+        // Rejected calls before this one still have their lines in the document
+        let sequence = record.map_or(u64::MAX, |record| record.sequence);
+        let rejected = self
+            .rejected_call_records
+            .get(handler)
+            .into_iter()
+            .flatten();
+        let position = records[..index.min(records.len())]
+            .iter()
+            .chain(rejected.filter(|record| record.sequence < sequence))
+            .filter(|record| {
+                record.flags.as_deref() == flags
+                    && record.alias.as_deref() == alias
+                    && file_of(record) == file
+            })
+            .count();
+        let written = match alias {
+            Some(alias) => alias.to_string(),
+            None => format!("{}{}", handler, flags.unwrap_or_default()),
+        };
+        (file, written, position)
+    }
 
-        if !removed_in_multi {
-            if let Some(doc) = &mut self.document {
-                let _ = doc.remove_handler_call(handler, index);
+    /// Every scoped keyword the calls of a handler were written with, e.g. `bind` and `bindl`
+    #[cfg(feature = "mutation")]
+    fn written_handler_keys(&self, handler: &str) -> Vec<String> {
+        let scope = handler.rsplit_once(':').map(|(scope, _)| scope);
+        let mut keys = vec![handler.to_string()];
+        for record in self.handler_call_records.get(handler).into_iter().flatten() {
+            let key = match (&record.alias, scope) {
+                (Some(alias), Some(scope)) if !alias.contains(':') => {
+                    format!("{}:{}", scope, alias)
+                }
+                (Some(alias), _) => alias.clone(),
+                (None, _) => format!("{}{}", handler, record.flags.as_deref().unwrap_or_default()),
+            };
+            if !keys.contains(&key) {
+                keys.push(key);
             }
         }
+        keys
+    }
 
-        Ok(value)
+    /// Split a flagged keyword like `bindl` into its handler and flags, as parsing does
+    #[cfg(feature = "mutation")]
+    fn split_handler_flags(&self, keyword: &str) -> (String, Option<String>) {
+        if !self.handlers.has_handler(&[], keyword)
+            && let Some((handler, flags)) = self.handlers.split_flags(&[], keyword)
+        {
+            (handler, Some(flags))
+        } else {
+            (keyword.to_string(), None)
+        }
     }
 
     // ========== SPECIAL CATEGORY MUTATIONS ==========
@@ -1440,20 +4826,441 @@ impl Config {
     /// # }
     /// ```
     #[cfg(feature = "mutation")]
-    pub fn remove_special_category_instance(
+    pub fn remove_special_category_instance(
+        &mut self,
+        category: &str,
+        key: &str,
+    ) -> ParseResult<()> {
+        self.special_categories.remove_instance(category, key)?;
+
+        // A header written with variables is found by its key as written
+        let written = self.written_path(&format!("{}[{}]", category, key));
+        let written_key = written
+            .strip_prefix(category)
+            .and_then(|rest| rest.strip_prefix('['))
+            .and_then(|rest| rest.strip_suffix(']'))
+            .unwrap_or(key);
+
+        // Remove from document tree for serialization consistency
+        if let Some(doc) = &mut self.document {
+            // Ignore error if document doesn't have this category (e.g., manually added)
+            let _ = doc.remove_special_category_instance(category, written_key);
+        }
+
+        Ok(())
+    }
+
+    /// Write a special category property line, or remove it with `None`
+    ///
+    /// Only the document changes; follow up with
+    /// [`replace_special_category_instance`](Config::replace_special_category_instance).
+    #[cfg(all(feature = "mutation", feature = "hyprland"))]
+    pub(crate) fn write_special_category_value(
+        &mut self,
+        category: &str,
+        key: &str,
+        property: &str,
+        raw: Option<&str>,
+    ) {
+        let path = self.written_path(&format!("{}[{}]:{}", category, key, property));
+        let source = self.special_category_source(category, key);
+        self.edit_source_document(source, |doc| match raw {
+            Some(raw) => doc.update_or_insert_value(&path, raw),
+            None => doc.remove_value(&path),
+        });
+    }
+
+    /// File a special category instance was read from, or the primary file for new ones
+    ///
+    /// `None` when the config was not parsed from a file.
+    #[cfg(all(feature = "mutation", feature = "hyprland"))]
+    fn special_category_source(&self, category: &str, key: &str) -> Option<PathBuf> {
+        let multi_doc = self.multi_document.as_ref()?;
+        self.special_categories
+            .try_get_instance(category, key)
+            .and_then(|instance| {
+                instance
+                    .values
+                    .values()
+                    .find_map(|entry| entry.source_file.clone())
+            })
+            .or_else(|| Some(multi_doc.primary_path.clone()))
+    }
+
+    /// Set a special category instance to the properties of a block, as if it had been parsed
+    ///
+    /// A new instance is appended to the document as a block; the lines of an existing one
+    /// are left to [`write_special_category_value`](Config::write_special_category_value).
+    #[cfg(all(feature = "mutation", feature = "hyprland"))]
+    pub(crate) fn replace_special_category_instance(
+        &mut self,
+        category: &str,
+        block: &MigratedBlock,
+    ) -> ParseResult<()> {
+        let source = self.special_category_source(category, &block.key);
+        if self
+            .special_categories
+            .instance_exists(category, &block.key)
+        {
+            self.special_categories
+                .remove_instance(category, &block.key)?;
+        } else {
+            self.edit_source_document(source.clone(), |doc| {
+                doc.append_node(block.to_node(category))
+            });
+        }
+
+        // The replacement is not a repeated block, and its values stay with the file it is in
+        let parsed = HyprlangParser::parse_config(&block.to_source(category))?;
+        let merge = std::mem::take(&mut self.options.category_merge);
+        let parent_file = std::mem::replace(&mut self.current_source_file, source);
+        let result = self.process_file_statements(&parsed.statements);
+        self.current_source_file = parent_file;
+        self.options.category_merge = merge;
+        let path = self
+            .normalize_key(&format!("{}[{}]", category, block.key))
+            .into_owned();
+        if let Some(definitions) = self.category_definitions.get_mut(&path) {
+            definitions.pop();
+        }
+        result
+    }
+
+    /// The document built from the most recently parsed input
+    ///
+    /// Node paths from [`ConfigDocument::get_locations`] can be passed to
+    /// [`wrap_in_condition`](Config::wrap_in_condition).
+    #[cfg(feature = "mutation")]
+    pub fn document(&self) -> Option<&ConfigDocument> {
+        self.document.as_ref()
+    }
+
+    /// List the `# hyprlang if` regions of the document, in document order
+    #[cfg(feature = "mutation")]
+    pub fn conditional_regions(&self) -> Vec<ConditionalRegion> {
+        self.document
+            .as_ref()
+            .map(ConfigDocument::conditional_regions)
+            .unwrap_or_default()
+    }
+
+    /// Wrap a range of document nodes in `# hyprlang if condition` / `# hyprlang endif`
+    ///
+    /// See [`ConfigDocument::wrap_in_condition`]. Only the document changes; values that
+    /// were already parsed stay as they are until the saved file is parsed again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "mutation")] {
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config.parse("general {\n    gaps_in = 5\n}\nborder_size = 2").unwrap();
+    ///
+    /// // Only apply the general block on laptops
+    /// let region = config.wrap_in_condition(&[], 0..1, "LAPTOP").unwrap();
+    /// assert_eq!(
+    ///     config.serialize(),
+    ///     "# hyprlang if LAPTOP\ngeneral {\n    gaps_in = 5\n}\n# hyprlang endif\nborder_size = 2\n"
+    /// );
+    ///
+    /// config.remove_condition(&region).unwrap();
+    /// assert!(config.conditional_regions().is_empty());
+    /// # }
+    /// ```
+    #[cfg(feature = "mutation")]
+    pub fn wrap_in_condition(
+        &mut self,
+        parent: &[usize],
+        range: std::ops::Range<usize>,
+        condition: &str,
+    ) -> ParseResult<ConditionalRegion> {
+        self.edit_document(|doc| doc.wrap_in_condition(parent, range.clone(), condition))
+    }
+
+    /// Remove the directives of a conditional region, keeping the nodes inside it
+    #[cfg(feature = "mutation")]
+    pub fn remove_condition(&mut self, region: &ConditionalRegion) -> ParseResult<()> {
+        self.edit_document(|doc| doc.remove_condition(region))
+    }
+
+    /// Apply an edit to the document, and to its copy in the multi-file document
+    #[cfg(feature = "mutation")]
+    fn edit_document<R>(
+        &mut self,
+        edit: impl Fn(&mut ConfigDocument) -> ParseResult<R>,
+    ) -> ParseResult<R> {
+        let doc = self
+            .document
+            .as_mut()
+            .ok_or_else(|| ConfigError::custom("No document to edit"))?;
+        let result = edit(doc)?;
+
+        if let (Some(multi_doc), Some(path)) = (&mut self.multi_document, doc.source_path.clone())
+            && let Some(file_doc) = multi_doc.get_document_mut(&path)
+        {
+            edit(file_doc)?;
+            multi_doc.mark_dirty(&path);
+        }
+
+        Ok(result)
+    }
+
+    // ========== MIGRATIONS (mutation feature) ==========
+
+    /// Apply a [`Migration`] to the values and the document
+    ///
+    /// Rules run in order and only affect values and handler calls written in the config,
+    /// not registered defaults. Lines are rewritten in place, so the rest of the document
+    /// stays as it was. If a rule fails, e.g. because a handler call is converted to an
+    /// unregistered special category, the rules before it stay applied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "mutation")] {
+    /// use hyprlang::{Config, MigratedBlock, Migration, SpecialCategoryDescriptor};
+    ///
+    /// let mut config = Config::new();
+    /// config.register_handler_fn("windowrulev2", |_| Ok(()));
+    /// config.register_special_category(SpecialCategoryDescriptor::keyed("windowrule", "name"));
+    /// config.parse("windowrulev2 = float, class:^(kitty)$").unwrap();
+    ///
+    /// let migration = Migration::new("0.53").handler_to_special_category(
+    ///     "windowrulev2",
+    ///     "windowrule",
+    ///     |value| {
+    ///         let (effect, class) = value.split_once(", class:")?;
+    ///         Some(
+    ///             MigratedBlock::new(format!("{}-{}", effect, class.trim_matches(['^', '(', ')', '$'])))
+    ///                 .with("match:class", class)
+    ///                 .with(effect, "true"),
+    ///         )
+    ///     },
+    /// );
+    /// let report = config.migrate(&migration).unwrap();
+    ///
+    /// assert_eq!(
+    ///     report.changes[0].to_string(),
+    ///     "converted windowrulev2 = float, class:^(kitty)$ to windowrule[float-kitty]"
+    /// );
+    /// assert_eq!(
+    ///     config.serialize(),
+    ///     "windowrule[float-kitty] {\n  match:class = ^(kitty)$\n  float = true\n}\n"
+    /// );
+    /// assert!(config.get_special_category("windowrule", "float-kitty").is_ok());
+    /// # }
+    /// ```
+    #[cfg(feature = "mutation")]
+    pub fn migrate(&mut self, migration: &Migration) -> ParseResult<MigrationReport> {
+        let mut changes = Vec::new();
+
+        for rule in &migration.rules {
+            match rule {
+                MigrationRule::Rename { from, to } => {
+                    changes.extend(self.migrate_rename(from, to)?);
+                }
+                MigrationRule::Remove { key } => {
+                    changes.extend(self.migrate_remove(key));
+                }
+                MigrationRule::RewriteValue { key, rewrite } => {
+                    let Some(old) = self.written_value(key) else {
+                        continue;
+                    };
+                    if let Some(new) = rewrite(&old).filter(|new| *new != old) {
+                        self.set_raw_value(key, &new)?;
+                        changes.push(MigrationChange::Rewritten {
+                            key: key.clone(),
+                            from: old,
+                            to: new,
+                        });
+                    }
+                }
+                MigrationRule::HandlerToSpecialCategory {
+                    keyword,
+                    category,
+                    convert,
+                } => {
+                    let converted: Vec<(usize, String, MigratedBlock)> = self
+                        .handler_calls
+                        .get(keyword)
+                        .into_iter()
+                        .flatten()
+                        .enumerate()
+                        .filter_map(|(index, value)| {
+                            convert(value).map(|block| (index, value.clone(), block))
+                        })
+                        .collect();
+                    if !converted.is_empty() && !self.is_special_category(category) {
+                        return Err(ConfigError::category_not_found(category, None));
+                    }
+
+                    self.migrate_handler_calls(keyword, category, &converted)?;
+                    changes.extend(converted.into_iter().map(|(_, value, block)| {
+                        MigrationChange::Converted {
+                            keyword: keyword.clone(),
+                            value,
+                            category: category.clone(),
+                            key: block.key,
+                        }
+                    }));
+                }
+            }
+        }
+
+        Ok(MigrationReport {
+            migration: migration.name().to_string(),
+            changes,
+        })
+    }
+
+    /// Raw value of a key written in the config, as opposed to a registered default
+    #[cfg(feature = "mutation")]
+    fn written_value(&self, key: &str) -> Option<String> {
+        self.values
+            .get(key)
+            .filter(|entry| entry.set_by_user)
+            .map(|entry| entry.raw.clone())
+    }
+
+    /// Apply an edit to the document holding a value key or handler, like the other
+    /// mutation methods do
+    #[cfg(feature = "mutation")]
+    fn edit_source_document(
+        &mut self,
+        source: Option<PathBuf>,
+        edit: impl FnOnce(&mut ConfigDocument) -> ParseResult<()>,
+    ) {
+        if let (Some(multi_doc), Some(source_file)) = (&mut self.multi_document, source)
+            && let Some(doc) = multi_doc.get_document_mut(&source_file)
+        {
+            let _ = edit(doc);
+            multi_doc.mark_dirty(&source_file);
+        } else if let Some(doc) = &mut self.document {
+            let _ = edit(doc);
+        }
+    }
+
+    #[cfg(feature = "mutation")]
+    fn key_source(&self, key: &str) -> Option<PathBuf> {
+        self.multi_document
+            .as_ref()
+            .and_then(|multi_doc| multi_doc.get_key_source(key).cloned())
+    }
+
+    #[cfg(feature = "mutation")]
+    fn migrate_rename(&mut self, from: &str, to: &str) -> ParseResult<Option<MigrationChange>> {
+        if self.written_value(from).is_none() {
+            return Ok(None);
+        }
+        if self.written_value(to).is_some() {
+            return Ok(self.migrate_remove(from));
+        }
+        self.validate(to, &self.values[from].value)?;
+
+        let source = self.key_source(from);
+        self.edit_source_document(source.clone(), |doc| doc.rename_value(from, to));
+        if let (Some(multi_doc), Some(source_file)) = (&mut self.multi_document, source) {
+            multi_doc.register_key(to.to_string(), source_file);
+        }
+
+        for declaration in &mut self.declaration_order {
+            if matches!(declaration, Declaration::Value(key) if key == from) {
+                *declaration = Declaration::Value(to.to_string());
+            }
+        }
+
+        if let Some(mut entry) = self.values.remove(from) {
+            self.stamp_entry(to, &mut entry);
+            self.values.insert(to.to_string(), entry);
+        }
+
+        Ok(Some(MigrationChange::Renamed {
+            from: from.to_string(),
+            to: to.to_string(),
+        }))
+    }
+
+    #[cfg(feature = "mutation")]
+    fn migrate_remove(&mut self, key: &str) -> Option<MigrationChange> {
+        let value = self.written_value(key)?;
+        let source = self.key_source(key);
+        self.edit_source_document(source, |doc| doc.remove_value(key));
+        self.values.remove(key);
+
+        Some(MigrationChange::Removed {
+            key: key.to_string(),
+            value,
+        })
+    }
+
+    /// Replace a written value with new raw text, parsed as if it had been in the config
+    #[cfg(feature = "mutation")]
+    pub(crate) fn set_raw_value(&mut self, key: &str, raw: &str) -> ParseResult<()> {
+        let parsed = HyprlangParser::parse_config(&format!("{} = {}", key, raw))?;
+        let Some(Statement::Assignment { value, .. }) = parsed.statements.first() else {
+            return Err(ConfigError::custom(format!(
+                "'{}' is not a value for '{}'",
+                raw, key
+            )));
+        };
+
+        let mut config_value = self.parse_config_value(key, value)?;
+        if self.quantity_keys.contains(key) {
+            config_value = Self::parse_quantity_value(key, config_value)?;
+        }
+        self.validate(key, &config_value)?;
+
+        let mut entry = ConfigValueEntry::new(config_value, self.value_to_string(value));
+        if let Some(previous) = self.values.get(key) {
+            entry.source_file = previous.source_file.clone();
+            entry.line = previous.line;
+            entry.noerror = previous.noerror;
+        }
+        self.stamp_entry(key, &mut entry);
+        self.values.insert(key.to_string(), entry);
+
+        let source = self.key_source(key);
+        self.edit_source_document(source, |doc| doc.update_or_insert_value(key, raw));
+        Ok(())
+    }
+
+    /// Replace handler calls, given by index, with special category instances
+    #[cfg(feature = "mutation")]
+    fn migrate_handler_calls(
         &mut self,
+        keyword: &str,
         category: &str,
-        key: &str,
+        converted: &[(usize, String, MigratedBlock)],
     ) -> ParseResult<()> {
-        self.special_categories.remove_instance(category, key)?;
+        let source = self
+            .multi_document
+            .as_ref()
+            .and_then(|multi_doc| multi_doc.get_handler_source(keyword).cloned());
 
-        // Remove from document tree for serialization consistency
-        if let Some(doc) = &mut self.document {
-            // Ignore error if document doesn't have this category (e.g., manually added)
-            let _ = doc.remove_special_category_instance(category, key);
+        // Back to front, so the indices of the remaining calls stay valid
+        for (index, _, block) in converted.iter().rev() {
+            let (file, written, doc_index) = self.written_handler_call(keyword, *index);
+            self.edit_source_document(file, |doc| {
+                doc.replace_handler_call(&written, doc_index, block.to_node(category))
+            });
+
+            if let Some(calls) = self.handler_calls.get_mut(keyword) {
+                calls.remove(*index);
+            }
+            if let Some(records) = self.handler_call_records.get_mut(keyword) {
+                records.remove(*index);
+            }
         }
 
-        Ok(())
+        let parent_file = std::mem::replace(&mut self.current_source_file, source);
+        let result = converted.iter().try_for_each(|(_, _, block)| {
+            let parsed = HyprlangParser::parse_config(&block.to_source(category))?;
+            self.process_file_statements(&parsed.statements)
+        });
+        self.current_source_file = parent_file;
+        result
     }
 
     // ========== SERIALIZATION METHODS (mutation feature) ==========
@@ -1474,7 +5281,7 @@ impl Config {
     ///
     /// let mut config = Config::new();
     /// config.parse("$GAPS = 10\nborder_size = 3").unwrap();
-    /// config.set_int("opacity", 1);
+    /// config.set_int("opacity", 1).unwrap();
     ///
     /// let output = config.serialize();
     /// assert!(output.contains("$GAPS = 10"));
@@ -1484,11 +5291,50 @@ impl Config {
     /// ```
     #[cfg(feature = "mutation")]
     pub fn serialize(&self) -> String {
+        self.serialize_with_options(&SerializeOptions::as_parsed())
+    }
+
+    /// Serialize the configuration using custom formatting options.
+    ///
+    /// See [`SerializeOptions`] for the available settings. Lines are otherwise written as
+    /// they were parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "mutation")] {
+    /// use hyprlang::{Config, IndentStyle, SerializeOptions};
+    ///
+    /// let mut config = Config::new();
+    /// config.parse("general {\n  border_size = 2\n}").unwrap();
+    ///
+    /// let options = SerializeOptions {
+    ///     indent: IndentStyle::Spaces(4),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     config.serialize_with_options(&options),
+    ///     "general {\n    border_size = 2\n}\n"
+    /// );
+    /// # }
+    /// ```
+    #[cfg(feature = "mutation")]
+    pub fn serialize_with_options(&self, options: &SerializeOptions) -> String {
         if let Some(doc) = &self.document {
-            doc.serialize()
+            if !options.sort_keys {
+                return doc.serialize_with_options(options);
+            }
+
+            // Handler calls are read as assignments; keep them out of the sorted runs
+            let mut doc = doc.clone();
+            doc.mark_handler_calls(&|path, keyword| {
+                let keyword = self.handlers.resolve_alias(keyword).unwrap_or(keyword);
+                self.has_handler(path, keyword)
+            });
+            doc.serialize_with_options(options)
         } else {
             // Fallback: generate from scratch (no formatting preserved)
-            self.serialize_synthetic()
+            self.serialize_synthetic(options)
         }
     }
 
@@ -1510,7 +5356,7 @@ impl Config {
     /// let mut config = Config::new();
     /// config.parse_file(Path::new("config.conf")).unwrap();
     ///
-    /// config.set_int("border_size", 5);
+    /// config.set_int("border_size", 5).unwrap();
     ///
     /// // Save back to config.conf
     /// config.save().unwrap();
@@ -1518,13 +5364,22 @@ impl Config {
     /// ```
     #[cfg(feature = "mutation")]
     pub fn save(&self) -> ParseResult<()> {
+        self.save_with_options(&SerializeOptions::as_parsed())
+    }
+
+    /// Save the configuration to its source file using custom formatting options.
+    ///
+    /// See [`save`](Config::save) and [`SerializeOptions`].
+    #[cfg(feature = "mutation")]
+    pub fn save_with_options(&self, options: &SerializeOptions) -> ParseResult<()> {
+        self.ensure_document_tracked()?;
         let path = self.source_file.as_ref().ok_or_else(|| {
             ConfigError::custom(
                 "No source file associated with this config. Use save_as() instead.",
             )
         })?;
 
-        let content = self.serialize();
+        let content = self.serialize_with_options(options);
         std::fs::write(path, content)
             .map_err(|e| ConfigError::io(path.display().to_string(), e.to_string()))
     }
@@ -1542,7 +5397,7 @@ impl Config {
     ///
     /// let mut config = Config::new();
     /// config.parse("$GAPS = 10\nborder_size = 3").unwrap();
-    /// config.set_int("opacity", 1);
+    /// config.set_int("opacity", 1).unwrap();
     ///
     /// // Save to a new file
     /// config.save_as("modified_config.conf").unwrap();
@@ -1553,7 +5408,20 @@ impl Config {
     /// ```
     #[cfg(feature = "mutation")]
     pub fn save_as(&self, path: impl AsRef<Path>) -> ParseResult<()> {
-        let content = self.serialize();
+        self.save_as_with_options(path, &SerializeOptions::as_parsed())
+    }
+
+    /// Save the configuration to a specific file using custom formatting options.
+    ///
+    /// See [`save_as`](Config::save_as) and [`SerializeOptions`].
+    #[cfg(feature = "mutation")]
+    pub fn save_as_with_options(
+        &self,
+        path: impl AsRef<Path>,
+        options: &SerializeOptions,
+    ) -> ParseResult<()> {
+        self.ensure_document_tracked()?;
+        let content = self.serialize_with_options(options);
         std::fs::write(&path, content)
             .map_err(|e| ConfigError::io(path.as_ref().display().to_string(), e.to_string()))
     }
@@ -1563,7 +5431,10 @@ impl Config {
     /// When configuration is loaded from multiple files via `source = path` directives,
     /// this method saves only the files that have been modified since parsing.
     ///
-    /// Returns a list of file paths that were written.
+    /// Returns a list of file paths that were written. If a write fails, the error of the
+    /// first failing file is returned; files written before it stay written. Use
+    /// [`save_all_with_report`](Config::save_all_with_report) to see every outcome or to roll
+    /// back on failure.
     ///
     /// # Examples
     ///
@@ -1576,7 +5447,7 @@ impl Config {
     /// config.parse_file("main.conf").unwrap();
     ///
     /// // Modify a value from appearance.conf
-    /// config.set_int("decoration:rounding", 15);
+    /// config.set_int("decoration:rounding", 15).unwrap();
     ///
     /// // Save only the modified files (appearance.conf in this case)
     /// let saved_files = config.save_all().unwrap();
@@ -1584,27 +5455,89 @@ impl Config {
     /// ```
     #[cfg(feature = "mutation")]
     pub fn save_all(&mut self) -> ParseResult<Vec<PathBuf>> {
-        let mut saved = Vec::new();
+        let mut report = self.save_all_with_report(false)?;
+        match report.failed.drain(..).next() {
+            Some((_, error)) => Err(error),
+            None => Ok(report.saved),
+        }
+    }
+
+    /// Save all modified files and report the outcome for each one.
+    ///
+    /// Every dirty file is attempted, in path order, even after a failure. Written files
+    /// have their dirty flags cleared; failed files stay dirty. With `rollback` set, a
+    /// failure restores the files already written to their contents from before the save
+    /// (removing files that did not exist), and those files stay dirty too.
+    ///
+    /// Returns an error only if the configuration was not loaded from a file.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "mutation")] {
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config.parse_file("main.conf").unwrap();
+    /// config.set_int("decoration:rounding", 15).unwrap();
+    ///
+    /// let report = config.save_all_with_report(true).unwrap();
+    /// for (path, error) in &report.failed {
+    ///     eprintln!("{}: {}", path.display(), error);
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "mutation")]
+    pub fn save_all_with_report(&mut self, rollback: bool) -> ParseResult<SaveReport> {
+        self.ensure_document_tracked()?;
+        let mut report = SaveReport::default();
+        let Some(multi_doc) = &mut self.multi_document else {
+            return Ok(report);
+        };
 
-        if let Some(multi_doc) = &self.multi_document {
-            let dirty_files: Vec<PathBuf> = multi_doc.get_dirty_files().iter().map(|p| (*p).clone()).collect();
+        let mut dirty_files: Vec<PathBuf> =
+            multi_doc.get_dirty_files().into_iter().cloned().collect();
+        dirty_files.sort();
 
-            for path in dirty_files {
-                if let Some(doc) = multi_doc.get_document(&path) {
-                    let content = doc.serialize();
-                    std::fs::write(&path, content)
-                        .map_err(|e| ConfigError::io(path.display().to_string(), e.to_string()))?;
-                    saved.push(path);
+        let mut backups = Vec::new();
+        for path in dirty_files {
+            let Some(doc) = multi_doc.get_document(&path) else {
+                continue;
+            };
+            let previous = std::fs::read(&path).ok();
+            match std::fs::write(&path, doc.serialize()) {
+                Ok(()) => {
+                    backups.push(previous);
+                    report.saved.push(path);
+                }
+                Err(e) => {
+                    let error = ConfigError::io(path.display().to_string(), e.to_string());
+                    report.failed.push((path, error));
                 }
             }
         }
 
-        // Clear dirty flags after successful save
-        if let Some(multi_doc) = &mut self.multi_document {
-            multi_doc.clear_dirty();
+        if rollback && !report.failed.is_empty() {
+            for (path, previous) in std::mem::take(&mut report.saved).into_iter().zip(backups) {
+                let restored = match previous {
+                    Some(contents) => std::fs::write(&path, contents),
+                    None => std::fs::remove_file(&path),
+                };
+                match restored {
+                    Ok(()) => report.rolled_back.push(path),
+                    Err(e) => {
+                        let error = ConfigError::io(path.display().to_string(), e.to_string());
+                        report.failed.push((path, error));
+                    }
+                }
+            }
+        }
+
+        for path in &report.saved {
+            multi_doc.clear_dirty_file(path);
         }
 
-        Ok(saved)
+        Ok(report)
     }
 
     /// Serialize a specific source file.
@@ -1628,6 +5561,7 @@ impl Config {
     /// ```
     #[cfg(feature = "mutation")]
     pub fn serialize_file(&self, path: &Path) -> ParseResult<String> {
+        self.ensure_document_tracked()?;
         if let Some(multi_doc) = &self.multi_document
             && let Some(doc) = multi_doc.get_document(path)
         {
@@ -1640,6 +5574,245 @@ impl Config {
         )))
     }
 
+    /// Serialize the configuration as one file, with every `source =` line replaced by
+    /// the content of the file it includes.
+    ///
+    /// Included files are written as they currently are, mutations included, between
+    /// `# begin include: path` and `# end include: path` comments. Returns an error if the
+    /// configuration was not loaded with [`parse_file`](Config::parse_file).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "mutation")] {
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// // main.conf contains `source = ./vars.conf`
+    /// config.parse_file("main.conf").unwrap();
+    ///
+    /// // A single self-contained snapshot of the whole setup
+    /// let snapshot = config.serialize_flattened().unwrap();
+    /// assert!(snapshot.contains("# begin include: ./vars.conf"));
+    /// # }
+    /// ```
+    #[cfg(feature = "mutation")]
+    pub fn serialize_flattened(&self) -> ParseResult<String> {
+        self.ensure_document_tracked()?;
+        let multi_doc = self.multi_document.as_ref().ok_or_else(|| {
+            ConfigError::custom(
+                "No source file associated with this config. Use parse_file() to load it.",
+            )
+        })?;
+
+        let primary = &multi_doc.primary_path;
+        let doc = self.flattened_document(multi_doc, primary, &mut vec![primary.clone()])?;
+        Ok(doc.serialize())
+    }
+
+    /// A copy of a file's document with its `source =` lines replaced by the included nodes
+    #[cfg(feature = "mutation")]
+    fn flattened_document(
+        &self,
+        multi_doc: &crate::document::MultiFileDocument,
+        path: &Path,
+        stack: &mut Vec<PathBuf>,
+    ) -> ParseResult<ConfigDocument> {
+        let mut doc = multi_doc.get_document(path).cloned().ok_or_else(|| {
+            ConfigError::custom(format!(
+                "File not found in configuration: {}",
+                path.display()
+            ))
+        })?;
+
+        let mut error = None;
+        doc.visit_mut(|_, nodes| {
+            let mut flattened = Vec::with_capacity(nodes.len());
+            for node in nodes.drain(..) {
+                let DocumentNode::Source { path: source, .. } = &node else {
+                    flattened.push(node);
+                    continue;
+                };
+
+                match self.flattened_source(multi_doc, source, stack) {
+                    Ok(included) => {
+                        let comment = |text: String| DocumentNode::Comment { text, line: 0 };
+                        flattened.push(comment(format!(" begin include: {}", source)));
+                        flattened.extend(included.nodes);
+                        flattened.push(comment(format!(" end include: {}", source)));
+                    }
+                    Err(e) => {
+                        error.get_or_insert(e);
+                        flattened.push(node);
+                    }
+                }
+            }
+            *nodes = flattened;
+        });
+
+        match error {
+            Some(e) => Err(e),
+            None => Ok(doc),
+        }
+    }
+
+    /// The flattened document of the file a `source =` line includes
+    #[cfg(feature = "mutation")]
+    fn flattened_source(
+        &self,
+        multi_doc: &crate::document::MultiFileDocument,
+        source: &str,
+        stack: &mut Vec<PathBuf>,
+    ) -> ParseResult<ConfigDocument> {
+        let resolver = self
+            .source_resolver
+            .as_ref()
+            .ok_or_else(|| ConfigError::custom("Source resolver not initialized"))?;
+        let resolved = resolver.resolve_path(&self.variables.expand(source)?)?;
+
+        if stack.contains(&resolved) {
+            return Err(ConfigError::custom(format!(
+                "Circular source directive detected: {}",
+                resolved.display()
+            )));
+        }
+
+        stack.push(resolved.clone());
+        let doc = self.flattened_document(multi_doc, &resolved, stack);
+        stack.pop();
+        doc
+    }
+
+    /// Move values, variables and handler calls out of the primary file into a new file.
+    ///
+    /// Every line of the file loaded with [`parse_file`](Config::parse_file) whose key
+    /// matches the glob `pattern` is moved, e.g. `bind*` for all binds or `decoration:*` for
+    /// a category (`*` also matches `:`; variables are matched as `$name`). Moved lines keep
+    /// their blocks, blocks left empty are removed, and a `source = path` line is put where
+    /// the first moved line was. Relative paths are resolved like `source =` lines.
+    ///
+    /// Both files are marked as modified; nothing is written until
+    /// [`save_all`](Config::save_all). Returns how many lines were moved. Returns an error
+    /// if the file already exists or the config was not loaded from a file.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "mutation")] {
+    /// use hyprlang::Config;
+    ///
+    /// let mut config = Config::new();
+    /// config.parse_file("hyprland.conf").unwrap();
+    ///
+    /// // hyprland.conf now has `source = binds.conf` where the first bind was
+    /// let moved = config.extract_to_file("bind*", "binds.conf").unwrap();
+    /// println!("moved {} binds", moved);
+    /// config.save_all().unwrap();
+    /// # }
+    /// ```
+    #[cfg(feature = "mutation")]
+    pub fn extract_to_file(&mut self, pattern: &str, path: impl AsRef<Path>) -> ParseResult<usize> {
+        self.ensure_document_tracked()?;
+        let path = path.as_ref();
+
+        // Resolve relative paths against the base directory, as `source =` does
+        let target = match &self.options.base_dir {
+            Some(base_dir) if path.is_relative() => base_dir.join(path),
+            _ => path.to_path_buf(),
+        };
+        let target = match (
+            target
+                .parent()
+                .filter(|dir| dir.exists())
+                .map(|dir| self.tracked_path(dir)),
+            target.file_name(),
+        ) {
+            (Some(dir), Some(name)) => dir.join(name),
+            _ => target,
+        };
+        let multi_doc = self.multi_document.as_mut().ok_or_else(|| {
+            ConfigError::custom(
+                "No source file associated with this config. Use parse_file() to load it.",
+            )
+        })?;
+
+        if target.exists() || multi_doc.get_document(&target).is_some() {
+            return Err(ConfigError::custom(format!(
+                "'{}' already exists",
+                target.display()
+            )));
+        }
+
+        let primary = multi_doc.primary_path.clone();
+        let doc = multi_doc.get_document_mut(&primary).ok_or_else(|| {
+            ConfigError::custom(format!(
+                "File not found in configuration: {}",
+                primary.display()
+            ))
+        })?;
+        let Some((nodes, index)) = doc.extract_nodes(|key| glob_matches(pattern, key)) else {
+            return Ok(0);
+        };
+
+        let source = path.display().to_string();
+        doc.insert_node(
+            &[],
+            index,
+            DocumentNode::Source {
+                raw: format!("source = {}", source),
+                path: source,
+                line: 0,
+                resolved_path: Some(target.clone()),
+            },
+        )?;
+        if self
+            .document
+            .as_ref()
+            .is_some_and(|single| single.source_path.as_ref() == Some(&primary))
+        {
+            self.document = Some(doc.clone());
+        }
+
+        let mut extracted = ConfigDocument::with_nodes(nodes);
+        extracted.source_path = Some(target.clone());
+        let keys: Vec<String> = extracted
+            .iter_nodes()
+            .filter(|node| node.node.block().is_none())
+            .filter_map(|node| node.key())
+            .collect();
+        multi_doc.add_document(target.clone(), extracted);
+        multi_doc.mark_dirty(&primary);
+        multi_doc.mark_dirty(&target);
+
+        // Moved lines now come from the new file
+        for key in &keys {
+            if let Some(name) = key.strip_prefix('$') {
+                multi_doc.register_key(key.clone(), target.clone());
+                self.variable_sources.insert(
+                    name.to_string(),
+                    VariableSource::Config {
+                        file: Some(target.clone()),
+                        line: None,
+                    },
+                );
+            } else if let Some(entry) = self.values.get_mut(key) {
+                multi_doc.register_key(key.clone(), target.clone());
+                entry.source_file = Some(target.clone());
+                entry.line = None;
+            } else {
+                multi_doc.register_handler(key.clone(), target.clone());
+                for record in self.handler_call_records.get_mut(key).into_iter().flatten() {
+                    if record.file.as_ref() == Some(&primary) {
+                        record.file = Some(target.clone());
+                        record.line = None;
+                    }
+                }
+            }
+        }
+
+        Ok(keys.len())
+    }
+
     /// Get which source file a key is defined in.
     ///
     /// Returns the path to the source file that contains the given key,
@@ -1707,7 +5880,7 @@ impl Config {
     /// let mut config = Config::new();
     /// config.parse_file("main.conf").unwrap();
     ///
-    /// config.set_int("decoration:rounding", 15);
+    /// config.set_int("decoration:rounding", 15).unwrap();
     ///
     /// for path in config.get_modified_files() {
     ///     println!("Modified file: {}", path.display());
@@ -1722,54 +5895,293 @@ impl Config {
             .unwrap_or_default()
     }
 
+    /// Fail if documents are not built for this config, as saving would drop its formatting
+    #[cfg(feature = "mutation")]
+    fn ensure_document_tracked(&self) -> ParseResult<()> {
+        if self.options.track_document {
+            Ok(())
+        } else {
+            Err(ConfigError::custom(
+                "This config was opened read-only (ConfigOptions::track_document is false) \
+                 and cannot be saved",
+            ))
+        }
+    }
+
     /// Generate a synthetic config (when no document exists)
+    ///
+    /// Entries are written in the order they were first declared, with `a:b:key` values and
+    /// category handler calls grouped into nested blocks placed where the category first appeared.
+    /// Root-level handler calls matched by `options.sections` follow in their sections.
     #[cfg(feature = "mutation")]
-    fn serialize_synthetic(&self) -> String {
-        let mut output = String::new();
+    fn serialize_synthetic(&self, options: &SerializeOptions) -> String {
+        let mut root = SyntheticBlock::default();
+        let mut emitted_variables = HashSet::new();
+        let mut emitted_values = HashSet::new();
+        let mut handler_cursors: HashMap<&str, usize> = HashMap::new();
+        let layout = options.sections.as_ref();
+        let mut sections: Vec<Vec<DocumentNode>> = layout
+            .map(|layout| layout.titles().map(|_| Vec::new()).collect())
+            .unwrap_or_default();
+        // Root-level calls go to their section, if the layout has one for them
+        let mut push_call = |root: &mut SyntheticBlock, handler: &str, call: &str| {
+            let (path, keyword) = split_category_key(handler);
+            let node = synthetic_handler_call(keyword, call);
+            match layout.and_then(|layout| layout.section_of(keyword)) {
+                Some(index) if path.is_empty() => sections[index].push(node),
+                _ => root.push(&path, node),
+            }
+        };
+
+        for declaration in &self.declaration_order {
+            match declaration {
+                Declaration::Variable(name) => {
+                    if emitted_variables.insert(name.as_str())
+                        && let Some(value) = self.variables.get(name)
+                    {
+                        root.push(
+                            &[],
+                            DocumentNode::VariableDef {
+                                name: name.clone(),
+                                value: value.to_string(),
+                                raw: format!("{} = {}", self.options.syntax.variable(name), value),
+                                line: 0,
+                            },
+                        );
+                    }
+                }
+
+                Declaration::Value(key) => {
+                    if emitted_values.insert(key.as_str())
+                        && let Some(entry) = self.values.get(key)
+                    {
+                        let (path, name) = split_category_key(key);
+                        let raw = escape_comments(&entry.raw);
+                        root.push(&path, synthetic_assignment(name, &raw));
+                    }
+                }
 
-        // Variables
-        let vars = self.variables.all();
-        if !vars.is_empty() {
-            for (name, value) in vars {
-                output.push_str(&format!("${} = {}\n", name, value));
+                Declaration::HandlerCall(handler) => {
+                    let cursor = handler_cursors.entry(handler.as_str()).or_default();
+                    if let Some(call) = self
+                        .handler_calls
+                        .get(handler)
+                        .and_then(|calls| calls.get(*cursor))
+                    {
+                        *cursor += 1;
+                        push_call(&mut root, handler, call);
+                    }
+                }
             }
-            output.push('\n');
         }
 
-        // Regular values (need to reconstruct categories)
-        let mut keys: Vec<_> = self.values.keys().collect();
+        // Anything stored without a recorded declaration goes last, in a stable order
+        let mut keys: Vec<_> = self
+            .values
+            .keys()
+            .filter(|key| !emitted_values.contains(key.as_str()))
+            .collect();
         keys.sort();
-
         for key in keys {
-            if let Some(entry) = self.values.get(key.as_str()) {
-                if key.contains(':') {
-                    // Nested key - format with categories
-                    let parts: Vec<&str> = key.split(':').collect();
-                    output.push_str(&format!("{} = {}\n", parts.join(":"), entry.raw));
-                } else {
-                    // Root-level key
-                    output.push_str(&format!("{} = {}\n", key, entry.raw));
-                }
+            let (path, name) = split_category_key(key);
+            let raw = escape_comments(&self.values[key].raw);
+            root.push(&path, synthetic_assignment(name, &raw));
+        }
+
+        let mut handlers: Vec<_> = self.handler_calls.keys().collect();
+        handlers.sort();
+        for handler in handlers {
+            let emitted = handler_cursors.get(handler.as_str()).copied().unwrap_or(0);
+            for call in self.handler_calls[handler].iter().skip(emitted) {
+                push_call(&mut root, handler, call);
+            }
+        }
+
+        let mut nodes = root.into_nodes(true);
+        let titles = layout.into_iter().flat_map(|layout| layout.titles());
+        for (title, calls) in titles.zip(sections) {
+            if calls.is_empty() {
+                continue;
+            }
+            if !nodes.is_empty() {
+                nodes.push(DocumentNode::BlankLine { line: 0 });
             }
+            nodes.push(DocumentNode::Comment {
+                text: format!(" {}", title),
+                line: 0,
+            });
+            nodes.extend(calls);
         }
 
-        if !self.values.is_empty() {
-            output.push('\n');
+        ConfigDocument::with_nodes(nodes).serialize_with_options(options)
+    }
+}
+
+/// Number of statements, counting those inside blocks
+fn count_statements(statements: &[Statement]) -> usize {
+    statements
+        .iter()
+        .map(|statement| match statement {
+            Statement::CategoryBlock { statements, .. }
+            | Statement::SpecialCategoryBlock { statements, .. } => {
+                1 + count_statements(statements)
+            }
+            _ => 1,
+        })
+        .sum()
+}
+
+/// `expr` with each variable reference written as its bare name, which expressions read as
+/// the variable
+fn bare_variables(expr: &str, syntax: &Syntax) -> String {
+    let mut bare = String::with_capacity(expr.len());
+    let mut rest = expr;
+    while let Some(ch) = rest.chars().next() {
+        match syntax.variable_at(rest) {
+            Some((name, length)) if !name.is_empty() => {
+                bare.push_str(name);
+                rest = &rest[length..];
+            }
+            _ => {
+                bare.push(ch);
+                rest = &rest[ch.len_utf8()..];
+            }
         }
+    }
+    bare
+}
 
-        // Handler calls
-        let mut handler_names: Vec<_> = self.handler_calls.keys().collect();
-        handler_names.sort();
+/// Write variable definitions that [`Syntax::to_grammar`] rewrote as they were written
+#[cfg(feature = "mutation")]
+fn restore_variable_syntax(document: &mut ConfigDocument, syntax: &Syntax) {
+    document.visit_mut(|_, nodes| {
+        for node in nodes {
+            if let DocumentNode::VariableDef { name, raw, .. } = node
+                && let Some(rest) = raw
+                    .strip_prefix('$')
+                    .and_then(|rest| rest.strip_prefix(name.as_str()))
+            {
+                *raw = format!("{}{}", syntax.variable(name), rest);
+            }
+        }
+    });
+}
 
-        for handler in handler_names {
-            if let Some(calls) = self.handler_calls.get(handler.as_str()) {
-                for call in calls {
-                    output.push_str(&format!("{} = {}\n", handler, call));
-                }
+/// Split `a:b:key` into its category path and final component
+#[cfg(feature = "mutation")]
+fn split_category_key(key: &str) -> (Vec<&str>, &str) {
+    match key.rsplit_once(':') {
+        Some((path, name)) => (path.split(':').collect(), name),
+        None => (Vec::new(), key),
+    }
+}
+
+#[cfg(feature = "mutation")]
+fn synthetic_assignment(name: &str, value: &str) -> DocumentNode {
+    DocumentNode::Assignment {
+        key: vec![name.to_string()],
+        value: value.to_string(),
+        raw: format!("{} = {}", name, value),
+        equals: " = ".to_string(),
+        line: 0,
+    }
+}
+
+#[cfg(feature = "mutation")]
+fn synthetic_handler_call(keyword: &str, value: &str) -> DocumentNode {
+    DocumentNode::HandlerCall {
+        keyword: keyword.to_string(),
+        flags: None,
+        value: value.to_string(),
+        raw: format!("{} = {}", keyword, value),
+        line: 0,
+    }
+}
+
+/// Category tree built up by [`Config::serialize_synthetic`]
+#[cfg(feature = "mutation")]
+#[derive(Default)]
+struct SyntheticBlock {
+    items: Vec<SyntheticItem>,
+}
+
+#[cfg(feature = "mutation")]
+enum SyntheticItem {
+    Node(DocumentNode),
+    Block(String, SyntheticBlock),
+}
+
+#[cfg(feature = "mutation")]
+impl SyntheticBlock {
+    /// Add a node under `path`, reusing the first block with a matching name at each level
+    fn push(&mut self, path: &[&str], node: DocumentNode) {
+        let Some((name, rest)) = path.split_first() else {
+            self.items.push(SyntheticItem::Node(node));
+            return;
+        };
+
+        let index = self
+            .items
+            .iter()
+            .position(|item| matches!(item, SyntheticItem::Block(block, _) if block == name))
+            .unwrap_or_else(|| {
+                self.items.push(SyntheticItem::Block(
+                    name.to_string(),
+                    SyntheticBlock::default(),
+                ));
+                self.items.len() - 1
+            });
+
+        if let SyntheticItem::Block(_, block) = &mut self.items[index] {
+            block.push(rest, node);
+        }
+    }
+
+    /// Convert to document nodes, separating top-level blocks from their neighbours
+    fn into_nodes(self, top_level: bool) -> Vec<DocumentNode> {
+        let mut nodes = Vec::new();
+        let mut previous_was_block = false;
+
+        for item in self.items {
+            let is_block = matches!(item, SyntheticItem::Block(..));
+            if top_level && !nodes.is_empty() && (is_block || previous_was_block) {
+                nodes.push(DocumentNode::BlankLine { line: 0 });
             }
+            previous_was_block = is_block;
+
+            nodes.push(match item {
+                SyntheticItem::Node(node) => node,
+                SyntheticItem::Block(name, block) => {
+                    let raw_open = format!("{} {{", name);
+                    let child_nodes = block.into_nodes(false);
+                    match name.split_once('[') {
+                        Some((category, key)) => DocumentNode::SpecialCategoryBlock {
+                            name: category.to_string(),
+                            key: Some(key.trim_end_matches(']').to_string()),
+                            nodes: child_nodes,
+                            open_line: 0,
+                            close_line: 0,
+                            raw_open,
+                            indent: None,
+                            open_comment: None,
+                            close_comment: None,
+                        },
+                        None => DocumentNode::CategoryBlock {
+                            name,
+                            nodes: child_nodes,
+                            open_line: 0,
+                            close_line: 0,
+                            raw_open,
+                            indent: None,
+                            open_comment: None,
+                            close_comment: None,
+                        },
+                    }
+                }
+            });
         }
 
-        output
+        nodes
     }
 }
 
@@ -1778,3 +6190,119 @@ impl Default for Config {
         Self::new()
     }
 }
+
+/// A readable tree of the config's data, with secrets masked
+///
+/// Variables come first, then values nested in their categories, handler calls and special
+/// category instances, each sorted by name. Values are shown as parsed (`rgba(r, g, b, a)`
+/// for colors, `(x, y)` for Vec2s), so the output is meant for logs and support bundles
+/// rather than for parsing back; use [`serialize`](Config::serialize) for that. Values
+/// are passed through [`redacted`](Config::redacted).
+impl std::fmt::Display for Config {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const INDENT: &str = "    ";
+
+        let mut variables: Vec<_> = self.variables.all().iter().collect();
+        variables.sort();
+        for (name, value) in variables {
+            let key = format!("${}", name);
+            let reference = self.options.syntax.variable(name);
+            writeln!(f, "{} = {}", reference, self.redacted(&key, value))?;
+        }
+
+        // Values of a category come before its subcategories
+        let mut values: Vec<(Vec<&str>, &str, &str)> = self
+            .values
+            .keys()
+            .map(|key| {
+                let (path, name) = match key.rsplit_once(':') {
+                    Some((path, name)) => (path.split(':').collect(), name),
+                    None => (Vec::new(), key.as_str()),
+                };
+                (path, name, key.as_str())
+            })
+            .collect();
+        values.sort();
+
+        let mut open: Vec<&str> = Vec::new();
+        for (path, name, key) in values {
+            let common = open
+                .iter()
+                .zip(&path)
+                .take_while(|(open, segment)| open == segment)
+                .count();
+            while open.len() > common {
+                open.pop();
+                writeln!(f, "{}}}", INDENT.repeat(open.len()))?;
+            }
+            for segment in &path[common..] {
+                writeln!(f, "{}{} {{", INDENT.repeat(open.len()), segment)?;
+                open.push(segment);
+            }
+            let text = self.values[key].text();
+            writeln!(
+                f,
+                "{}{} = {}",
+                INDENT.repeat(open.len()),
+                name,
+                self.redacted(key, &text)
+            )?;
+        }
+        while !open.is_empty() {
+            open.pop();
+            writeln!(f, "{}}}", INDENT.repeat(open.len()))?;
+        }
+
+        let mut keywords: Vec<&String> = self.handler_calls.keys().collect();
+        keywords.sort();
+        for keyword in keywords {
+            for call in &self.handler_calls[keyword] {
+                writeln!(f, "{} = {}", keyword, self.redacted(keyword, call))?;
+            }
+        }
+
+        let mut categories = self.special_categories.category_names();
+        categories.sort();
+        for category in categories {
+            let mut keys = self.special_categories.list_keys(category);
+            keys.sort();
+            for key in keys {
+                let Ok(instance) = self.special_categories.get_instance(category, &key) else {
+                    continue;
+                };
+                writeln!(f, "{}[{}] {{", category, key)?;
+                let mut properties: Vec<_> = instance.values.iter().collect();
+                properties.sort_by(|a, b| a.0.cmp(b.0));
+                for (property, entry) in properties {
+                    let key = format!("{}:{}", category, property);
+                    writeln!(
+                        f,
+                        "{}{} = {}",
+                        INDENT,
+                        property,
+                        self.redacted(&key, &entry.text())
+                    )?;
+                }
+                writeln!(f, "}}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A summary that never includes values, so it is safe to log
+impl std::fmt::Debug for Config {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Config")
+            .field("values", &self.values.len())
+            .field("variables", &self.variables.all().len())
+            .field("handler_calls", &self.handler_calls.len())
+            .field(
+                "special_categories",
+                &self.special_categories.category_names().len(),
+            )
+            .field("generation", &self.generation)
+            .finish_non_exhaustive()
+    }
+}
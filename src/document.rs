@@ -25,6 +25,9 @@ pub struct ConfigDocument {
 
     /// Source file path (if parsed from a file)
     pub source_path: Option<PathBuf>,
+
+    /// Line ending used by the parsed file, written back on serialize
+    pub line_ending: LineEnding,
 }
 
 /// A node in the configuration document
@@ -56,6 +59,9 @@ pub enum DocumentNode {
         value: String,
         /// Original formatting
         raw: String,
+        /// The `=` with the whitespace around it as written, e.g. ` = ` or `=`, reused when
+        /// the value is rewritten
+        equals: String,
         line: usize,
     },
 
@@ -69,6 +75,13 @@ pub enum DocumentNode {
         close_line: usize,
         /// Raw opening line (e.g., "category {")
         raw_open: String,
+        /// Leading whitespace of the block's lines as found in the source
+        /// (`None` for empty or newly created blocks)
+        indent: Option<String>,
+        /// Comment after the opening brace, without the `#` (also kept in `raw_open`)
+        open_comment: Option<String>,
+        /// Comment after the closing brace, without the `#`
+        close_comment: Option<String>,
     },
 
     /// Special category block: category[key] { ... }
@@ -80,6 +93,13 @@ pub enum DocumentNode {
         close_line: usize,
         /// Raw opening line (e.g., "device[mouse] {")
         raw_open: String,
+        /// Leading whitespace of the block's lines as found in the source
+        /// (`None` for empty or newly created blocks)
+        indent: Option<String>,
+        /// Comment after the opening brace, without the `#` (also kept in `raw_open`)
+        open_comment: Option<String>,
+        /// Comment after the closing brace, without the `#`
+        close_comment: Option<String>,
     },
 
     /// Handler call: keyword [flags] = value
@@ -109,6 +129,358 @@ pub enum DocumentNode {
     },
 }
 
+impl DocumentNode {
+    /// For a block, the category it adds to keys (`name` or `name[key]`) and its children
+    pub(crate) fn block(&self) -> Option<(String, &[DocumentNode])> {
+        match self {
+            DocumentNode::CategoryBlock { name, nodes, .. } => Some((name.clone(), nodes)),
+            DocumentNode::SpecialCategoryBlock {
+                name, key, nodes, ..
+            } => {
+                let scope = match key {
+                    Some(k) => format!("{}[{}]", name, k),
+                    None => name.clone(),
+                };
+                Some((scope, nodes))
+            }
+            _ => None,
+        }
+    }
+
+    /// The opening line of a block as written, e.g. `general {` or `device[mouse]{ # pad`
+    ///
+    /// `None` for nodes that are not blocks.
+    pub fn raw_open(&self) -> Option<&str> {
+        match self {
+            DocumentNode::CategoryBlock { raw_open, .. }
+            | DocumentNode::SpecialCategoryBlock { raw_open, .. } => Some(raw_open),
+            _ => None,
+        }
+    }
+
+    /// How the opening brace of a block is written, `None` for nodes that are not blocks
+    pub fn brace_style(&self) -> Option<BraceStyle> {
+        let raw_open = self.raw_open()?;
+        let brace = open_brace(raw_open)?;
+        if raw_open[..brace].ends_with([' ', '\t']) {
+            Some(BraceStyle::Spaced)
+        } else {
+            Some(BraceStyle::Attached)
+        }
+    }
+
+    /// Mutable children of a block
+    fn block_nodes_mut(&mut self) -> Option<&mut Vec<DocumentNode>> {
+        match self {
+            DocumentNode::CategoryBlock { nodes, .. }
+            | DocumentNode::SpecialCategoryBlock { nodes, .. } => Some(nodes),
+            _ => None,
+        }
+    }
+
+    /// Key of the node when nested in `category`, see [`NodeRef::key`]
+    fn key_in(&self, category: &[String]) -> Option<String> {
+        let name = match self {
+            DocumentNode::VariableDef { name, .. } => return Some(format!("${}", name)),
+            DocumentNode::Assignment { key, .. } => key.join(":"),
+            DocumentNode::HandlerCall { keyword, .. } => keyword.clone(),
+            node => node.block()?.0,
+        };
+
+        if category.is_empty() {
+            Some(name)
+        } else {
+            Some(format!("{}:{}", category.join(":"), name))
+        }
+    }
+}
+
+/// Indentation used for lines nested inside category blocks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndentStyle {
+    /// The given number of spaces per level
+    Spaces(usize),
+
+    /// One tab per level
+    Tabs,
+}
+
+impl IndentStyle {
+    /// The indentation string for the given nesting depth
+    pub fn repeat(&self, depth: usize) -> String {
+        match self {
+            IndentStyle::Spaces(n) => " ".repeat(n * depth),
+            IndentStyle::Tabs => "\t".repeat(depth),
+        }
+    }
+}
+
+impl Default for IndentStyle {
+    fn default() -> Self {
+        IndentStyle::Spaces(2)
+    }
+}
+
+/// How the opening brace of a block is separated from its header
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BraceStyle {
+    /// Whitespace before the brace: `general {`
+    #[default]
+    Spaced,
+
+    /// The brace right after the header: `general{`
+    Attached,
+}
+
+/// Line ending written between serialized lines
+///
+/// Parsing always works on `\n`; the ending of the input is remembered in
+/// [`ConfigDocument::line_ending`] so saving a CRLF file does not rewrite every line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineEnding {
+    /// Unix style `\n`
+    #[default]
+    Lf,
+
+    /// Windows style `\r\n`
+    CrLf,
+}
+
+impl LineEnding {
+    /// The line ending used by `input`: `CrLf` if its first line ends in `\r\n`
+    pub fn detect(input: &str) -> Self {
+        match input.find('\n') {
+            Some(end) if input[..end].ends_with('\r') => LineEnding::CrLf,
+            _ => LineEnding::Lf,
+        }
+    }
+
+    /// The line ending as a string
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+}
+
+/// Formatting options for [`ConfigDocument::serialize_with_options`] and
+/// [`Config::serialize_with_options`](crate::Config::serialize_with_options)
+///
+/// The default writes lines exactly as they were parsed, indented with `indent`. Set
+/// `keep_indent` to reuse the indentation detected in the parsed file instead, as
+/// [`ConfigDocument::serialize`] does.
+///
+/// ```rust
+/// # #[cfg(feature = "mutation")] {
+/// use hyprlang::{Config, IndentStyle, SerializeOptions};
+///
+/// let mut config = Config::new();
+/// config.parse("general {\nborder_size = 2\ngaps_in = 5\n}").unwrap();
+///
+/// let options = SerializeOptions {
+///     indent: IndentStyle::Tabs,
+///     align_equals: true,
+///     ..Default::default()
+/// };
+/// assert_eq!(
+///     config.serialize_with_options(&options),
+///     "general {\n\tborder_size = 2\n\tgaps_in     = 5\n}\n"
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SerializeOptions {
+    /// Indentation for nested lines
+    pub indent: IndentStyle,
+
+    /// Pad keys so the `=` of consecutive `key = value` lines line up
+    pub align_equals: bool,
+
+    /// Separate category blocks from neighbouring lines with a blank line
+    pub blank_line_between_categories: bool,
+
+    /// Sort consecutive assignments by key (variables and handler calls keep their order)
+    pub sort_keys: bool,
+
+    /// Reuse the indentation found in the parsed file, using `indent` only for blocks
+    /// where none was detected
+    pub keep_indent: bool,
+
+    /// Line ending to write instead of the one detected in the parsed file
+    pub line_ending: Option<LineEnding>,
+
+    /// Group root-level handler calls into commented sections after the other lines
+    ///
+    /// Only used by synthetic serialization, i.e. for configs without a parsed document.
+    pub sections: Option<SectionLayout>,
+
+    /// Write `category:key = value` lines inside the block of their category, when the
+    /// file has one; see [`ConfigDocument::fold_flat_assignments`]
+    pub fold_flat_assignments: bool,
+
+    /// Rewrite the opening line of every block with this brace style instead of keeping
+    /// it as written
+    pub brace_style: Option<BraceStyle>,
+}
+
+impl SerializeOptions {
+    /// The options [`ConfigDocument::serialize`] writes with: everything as parsed
+    pub(crate) fn as_parsed() -> Self {
+        SerializeOptions {
+            keep_indent: true,
+            ..Default::default()
+        }
+    }
+}
+
+/// Sections that synthetic serialization groups root-level handler calls into
+///
+/// Each section has a title, written as a `# title` comment above it, and the handler
+/// keywords it collects. A keyword pattern ending in `*` matches every keyword starting
+/// with the rest, so `bind*` collects `bind`, `bindm` and `binde`. Sections are written
+/// in the order they were added, after everything else; calls in a section keep their
+/// declaration order, and a call goes to the first section that matches it.
+///
+/// ```rust
+/// # #[cfg(feature = "mutation")] {
+/// use hyprlang::{Config, SectionLayout, SerializeOptions};
+///
+/// let mut config = Config::new();
+/// config.add_handler_call("bind", "SUPER, Q, exec, kitty".to_string()).unwrap();
+/// config.set_int("general:gaps_in", 5).unwrap();
+/// config.add_handler_call("env", "XCURSOR_SIZE,24".to_string()).unwrap();
+///
+/// let options = SerializeOptions {
+///     sections: Some(
+///         SectionLayout::new()
+///             .section("Environment", ["env"])
+///             .section("Keybindings", ["bind*"]),
+///     ),
+///     ..Default::default()
+/// };
+/// assert_eq!(
+///     config.serialize_with_options(&options),
+///     "general {\n  gaps_in = 5\n}\n\n# Environment\n\
+///      env = XCURSOR_SIZE,24\n\n# Keybindings\n\
+///      bind = SUPER, Q, exec, kitty\n"
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SectionLayout {
+    sections: Vec<(String, Vec<String>)>,
+}
+
+impl SectionLayout {
+    /// Create a layout without sections
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sections for a Hyprland config: environment, autostart, keybindings and rules
+    pub fn hyprland() -> Self {
+        Self::new()
+            .section("Environment", ["env"])
+            .section("Autostart", ["exec-once", "execr-once", "exec", "execr"])
+            .section("Keybindings", ["bind*", "unbind"])
+            .section(
+                "Window rules",
+                ["windowrule", "windowrulev2", "windowrulev3", "layerrule"],
+            )
+    }
+
+    /// Add a section collecting the given keyword patterns
+    pub fn section(
+        mut self,
+        title: impl Into<String>,
+        keywords: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.sections
+            .push((title.into(), keywords.into_iter().map(Into::into).collect()));
+        self
+    }
+
+    /// Section titles, in order
+    pub fn titles(&self) -> impl Iterator<Item = &str> {
+        self.sections.iter().map(|(title, _)| title.as_str())
+    }
+
+    /// Index of the first section collecting `keyword`
+    pub fn section_of(&self, keyword: &str) -> Option<usize> {
+        self.sections.iter().position(|(_, patterns)| {
+            patterns
+                .iter()
+                .any(|pattern| match pattern.strip_suffix('*') {
+                    Some(prefix) => keyword.starts_with(prefix),
+                    None => keyword == pattern,
+                })
+        })
+    }
+}
+
+/// The `=` of a `key = value` line with the whitespace around it
+///
+/// A line without a value gets a single space after the `=`, so a value written into it
+/// later is not glued to the `=`.
+pub(crate) fn equals_of(raw: &str) -> String {
+    let Some(at) = raw.find('=') else {
+        return " = ".to_string();
+    };
+    let before = raw[..at].len() - raw[..at].trim_end().len();
+    let rest = &raw[at + 1..];
+    let after = rest.len() - rest.trim_start().len();
+    if rest.trim().is_empty() {
+        format!("{}= ", &raw[at - before..at])
+    } else {
+        raw[at - before..at + 1 + after].to_string()
+    }
+}
+
+/// Rewrite `key=value` as `key<padding> = value` with the key padded to `width`
+fn align_raw(raw: &str, width: usize) -> String {
+    match raw.split_once('=') {
+        Some((lhs, rhs)) => {
+            let rhs = rhs.trim_start();
+            if rhs.is_empty() {
+                format!("{:<width$} =", lhs.trim_end())
+            } else {
+                format!("{:<width$} = {}", lhs.trim_end(), rhs)
+            }
+        }
+        None => raw.to_string(),
+    }
+}
+
+/// Byte offset of the opening brace in a block's opening line, skipping over its `[key]`
+fn open_brace(raw_open: &str) -> Option<usize> {
+    let start = match raw_open.find(['[', '{'])? {
+        at if raw_open[at..].starts_with('[') => at + raw_open[at..].find(']')? + 1,
+        _ => 0,
+    };
+    Some(start + raw_open[start..].find('{')?)
+}
+
+/// A block's opening line with the whitespace before its brace rewritten for `style`
+fn restyle_block_open(raw_open: &str, style: BraceStyle) -> String {
+    let Some(brace) = open_brace(raw_open) else {
+        return raw_open.to_string();
+    };
+    let header = raw_open[..brace].trim_end();
+    match style {
+        BraceStyle::Spaced => format!("{} {}", header, &raw_open[brace..]),
+        BraceStyle::Attached => format!("{}{}", header, &raw_open[brace..]),
+    }
+}
+
+/// The opening line (`name {`) for a block written as `name { }`, keeping a comment after
+/// its `}`
+fn inline_block_open(raw_open: &str) -> Option<String> {
+    let brace = raw_open.find('{')?;
+    let after_close = raw_open[brace + 1..].trim_start().strip_prefix('}')?;
+    Some(format!("{}{}", &raw_open[..=brace], after_close.trim_end()))
+}
+
 /// Location of a node in the document tree
 #[derive(Clone, Debug, PartialEq)]
 pub struct NodeLocation {
@@ -120,6 +492,47 @@ pub struct NodeLocation {
     pub node_type: NodeType,
 }
 
+/// Nodes between a `# hyprlang if` directive and its matching `# hyprlang endif`
+///
+/// Returned by [`ConfigDocument::conditional_regions`]. Indices refer to the node list
+/// the directives are in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConditionalRegion {
+    /// Condition as written after `if`, e.g. `LAPTOP`, `!LAPTOP` or `env:HOST == laptop`
+    pub condition: String,
+
+    /// Path to the block holding the directives (empty for root level)
+    pub parent: Vec<usize>,
+
+    /// Index of the `if` directive
+    pub start: usize,
+
+    /// Index of the matching `endif` directive
+    pub end: usize,
+}
+
+impl ConditionalRegion {
+    /// The variable the condition tests, e.g. `LAPTOP` or `env:HOST`
+    pub fn variable(&self) -> &str {
+        let condition = self.condition.trim_start_matches('!');
+        condition
+            .split_once("!=")
+            .or_else(|| condition.split_once("=="))
+            .map_or(condition, |(operand, _)| operand)
+            .trim()
+    }
+
+    /// Whether the condition is negated (`if !VAR`)
+    pub fn is_negated(&self) -> bool {
+        self.condition.starts_with('!')
+    }
+
+    /// Indices of the nodes the condition applies to
+    pub fn body(&self) -> std::ops::Range<usize> {
+        self.start + 1..self.end
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum NodeType {
     VariableDef,
@@ -136,6 +549,7 @@ impl ConfigDocument {
             nodes: Vec::new(),
             key_index: HashMap::new(),
             source_path: None,
+            line_ending: LineEnding::Lf,
         }
     }
 
@@ -145,119 +559,291 @@ impl ConfigDocument {
             nodes,
             key_index: HashMap::new(),
             source_path: None,
+            line_ending: LineEnding::Lf,
         };
         doc.rebuild_index();
         doc
     }
 
     /// Rebuild the key index from the current nodes
+    ///
+    /// The document's own mutation methods keep the index up to date; this is only
+    /// needed after editing [`nodes`](Self::nodes) directly.
     pub fn rebuild_index(&mut self) {
         self.key_index.clear();
-        self.build_index_recursive(&self.nodes.clone(), &Vec::new(), &Vec::new());
+        for (idx, node) in self.nodes.iter().enumerate() {
+            Self::index_node(&mut self.key_index, node, vec![idx], &[]);
+        }
     }
 
-    /// Recursively build the index
-    fn build_index_recursive(
-        &mut self,
-        nodes: &[DocumentNode],
-        path_prefix: &[usize],
+    /// Add index entries for a node and everything nested in it, in document order
+    fn index_node(
+        key_index: &mut HashMap<String, Vec<NodeLocation>>,
+        node: &DocumentNode,
+        path: Vec<usize>,
         category_stack: &[String],
     ) {
-        for (idx, node) in nodes.iter().enumerate() {
-            let mut current_path = path_prefix.to_vec();
-            current_path.push(idx);
+        let scoped = |name: &str| {
+            if category_stack.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}:{}", category_stack.join(":"), name)
+            }
+        };
 
-            match node {
-                DocumentNode::VariableDef { name, .. } => {
-                    let location = NodeLocation {
-                        path: current_path.clone(),
-                        node_type: NodeType::VariableDef,
-                    };
-                    self.key_index
-                        .entry(format!("${}", name))
-                        .or_default()
-                        .push(location);
+        let (key, node_type) = match node {
+            DocumentNode::VariableDef { name, .. } => (format!("${}", name), NodeType::VariableDef),
+            DocumentNode::Assignment { key, .. } => (scoped(&key.join(":")), NodeType::Assignment),
+            DocumentNode::HandlerCall { keyword, .. } => (scoped(keyword), NodeType::HandlerCall),
+            DocumentNode::CategoryBlock { .. } | DocumentNode::SpecialCategoryBlock { .. } => {
+                let (scope, child_nodes) = node.block().expect("matched a block");
+                let mut new_stack = category_stack.to_vec();
+                new_stack.push(scope);
+                for (idx, child) in child_nodes.iter().enumerate() {
+                    let mut child_path = path.clone();
+                    child_path.push(idx);
+                    Self::index_node(key_index, child, child_path, &new_stack);
                 }
+                return;
+            }
+            _ => return,
+        };
 
-                DocumentNode::Assignment { key, .. } => {
-                    let full_key = if category_stack.is_empty() {
-                        key.join(":")
-                    } else {
-                        format!("{}:{}", category_stack.join(":"), key.join(":"))
-                    };
+        key_index
+            .entry(key)
+            .or_default()
+            .push(NodeLocation { path, node_type });
+    }
 
-                    let location = NodeLocation {
-                        path: current_path.clone(),
-                        node_type: NodeType::Assignment,
-                    };
-                    self.key_index.entry(full_key).or_default().push(location);
+    /// Index entries of the node at `path` and everything nested in it
+    fn entries_at(&self, path: &[usize]) -> HashMap<String, Vec<NodeLocation>> {
+        let (&idx, parent) = path.split_last().expect("node paths are never empty");
+        let (nodes, category_stack) = self.walk(parent);
+
+        let mut entries = HashMap::new();
+        Self::index_node(&mut entries, &nodes[idx], path.to_vec(), &category_stack);
+        entries
+    }
+
+    /// The children of the block at `parent` and the categories leading to it
+    fn walk(&self, parent: &[usize]) -> (&[DocumentNode], Vec<String>) {
+        let mut nodes = self.nodes.as_slice();
+        let mut category_stack = Vec::new();
+
+        for &block in parent {
+            let (scope, child_nodes) = nodes[block]
+                .block()
+                .expect("parent paths only go through blocks");
+            category_stack.push(scope);
+            nodes = child_nodes;
+        }
+
+        (nodes, category_stack)
+    }
+
+    /// Add entries to the index, keeping each key's locations in document order
+    fn index_entries(&mut self, entries: HashMap<String, Vec<NodeLocation>>) {
+        for (key, locations) in entries {
+            let indexed = self.key_index.entry(key).or_default();
+            for location in locations {
+                let position = indexed.partition_point(|other| other.path < location.path);
+                indexed.insert(position, location);
+            }
+        }
+    }
+
+    /// Remove entries from the index
+    fn unindex_entries(&mut self, entries: HashMap<String, Vec<NodeLocation>>) {
+        for (key, locations) in entries {
+            if let Some(indexed) = self.key_index.get_mut(&key) {
+                indexed.retain(|location| !locations.contains(location));
+                if indexed.is_empty() {
+                    self.key_index.remove(&key);
                 }
+            }
+        }
+    }
 
-                DocumentNode::HandlerCall { keyword, .. } => {
-                    let handler_key = if category_stack.is_empty() {
-                        keyword.clone()
+    /// Move the index entries of the siblings from `path` onwards by one position,
+    /// after a node was inserted at (`grow`) or removed from `path`
+    fn shift_index(&mut self, path: &[usize], grow: bool) {
+        let (&idx, parent) = path.split_last().expect("node paths are never empty");
+        let level = parent.len();
+        for locations in self.key_index.values_mut() {
+            for location in locations.iter_mut() {
+                if location.path.len() > level
+                    && location.path.starts_with(parent)
+                    && location.path[level] >= idx
+                {
+                    if grow {
+                        location.path[level] += 1;
                     } else {
-                        format!("{}:{}", category_stack.join(":"), keyword)
-                    };
-
-                    let location = NodeLocation {
-                        path: current_path.clone(),
-                        node_type: NodeType::HandlerCall,
-                    };
-                    self.key_index
-                        .entry(handler_key)
-                        .or_default()
-                        .push(location);
+                        location.path[level] -= 1;
+                    }
                 }
+            }
+        }
+    }
+
+    /// Insert a node at `index` in the block at `parent` (root level for an empty path)
+    pub(crate) fn insert_node(
+        &mut self,
+        parent: &[usize],
+        index: usize,
+        node: DocumentNode,
+    ) -> ParseResult<()> {
+        let nodes = self.children_mut(parent)?;
+        let appended = index == nodes.len();
+        nodes.insert(index, node);
+
+        let mut path = parent.to_vec();
+        path.push(index);
+        // Appending leaves every existing path as it was
+        if !appended {
+            self.shift_index(&path, true);
+        }
+        let entries = self.entries_at(&path);
+        self.index_entries(entries);
+        Ok(())
+    }
+
+    /// Replace the node at `path`, updating the index for it
+    fn replace_node_at(&mut self, path: &[usize], node: DocumentNode) -> ParseResult<()> {
+        let location = NodeLocation {
+            path: path.to_vec(),
+            node_type: NodeType::Assignment,
+        };
+        self.get_node_at(&location)?;
+        let old_entries = self.entries_at(path);
+        *self.get_node_at_mut(&location)? = node;
+
+        self.unindex_entries(old_entries);
+        let new_entries = self.entries_at(path);
+        self.index_entries(new_entries);
+        Ok(())
+    }
+
+    /// Serialize the document back to string format
+    pub fn serialize(&self) -> String {
+        self.serialize_with_options(&SerializeOptions::as_parsed())
+    }
 
+    /// Serialize the document using the given formatting options
+    pub fn serialize_with_options(&self, options: &SerializeOptions) -> String {
+        let unit = match self.detected_indent_style() {
+            Some(style) if options.keep_indent => style.repeat(1),
+            _ => options.indent.repeat(1),
+        };
+
+        let folded;
+        let nodes = if options.fold_flat_assignments {
+            let mut document = self.clone();
+            document.fold_flat_assignments();
+            folded = document.nodes;
+            &folded
+        } else {
+            &self.nodes
+        };
+
+        let mut output = String::new();
+        Self::serialize_nodes(nodes, &mut output, "", &unit, options);
+        match options.line_ending.unwrap_or(self.line_ending) {
+            LineEnding::Lf => output,
+            LineEnding::CrLf => output.replace('\n', "\r\n"),
+        }
+    }
+
+    /// The indentation style used by the parsed file, if it can be determined
+    ///
+    /// Looks at the first block whose lines are indented relative to their parent.
+    pub fn detected_indent_style(&self) -> Option<IndentStyle> {
+        fn find_unit<'a>(nodes: &'a [DocumentNode], parent: &str) -> Option<&'a str> {
+            nodes.iter().find_map(|node| match node {
                 DocumentNode::CategoryBlock {
-                    name,
                     nodes: child_nodes,
+                    indent,
                     ..
-                } => {
-                    let mut new_stack = category_stack.to_vec();
-                    new_stack.push(name.clone());
-                    self.build_index_recursive(child_nodes, &current_path, &new_stack);
                 }
-
-                DocumentNode::SpecialCategoryBlock {
-                    name,
-                    key: category_key,
+                | DocumentNode::SpecialCategoryBlock {
                     nodes: child_nodes,
+                    indent,
                     ..
                 } => {
-                    let mut new_stack = category_stack.to_vec();
-                    if let Some(k) = category_key {
-                        new_stack.push(format!("{}[{}]", name, k));
-                    } else {
-                        new_stack.push(name.clone());
+                    let indent = indent.as_deref()?;
+                    match indent.strip_prefix(parent) {
+                        Some(unit) if !unit.is_empty() => Some(unit),
+                        _ => find_unit(child_nodes, indent),
                     }
-                    self.build_index_recursive(child_nodes, &current_path, &new_stack);
                 }
+                _ => None,
+            })
+        }
 
-                _ => {}
-            }
+        let unit = find_unit(&self.nodes, "")?;
+        if unit.chars().all(|c| c == '\t') {
+            Some(IndentStyle::Tabs)
+        } else if unit.chars().all(|c| c == ' ') {
+            Some(IndentStyle::Spaces(unit.len()))
+        } else {
+            None
         }
     }
 
-    /// Serialize the document back to string format
-    pub fn serialize(&self) -> String {
-        let mut output = String::new();
-        self.serialize_nodes(&self.nodes, &mut output, 0);
-        output
-    }
+    /// Serialize nodes whose lines start with `indent`
+    fn serialize_nodes(
+        nodes: &[DocumentNode],
+        output: &mut String,
+        indent: &str,
+        unit: &str,
+        options: &SerializeOptions,
+    ) {
+        let mut ordered: Vec<&DocumentNode> = nodes.iter().collect();
+        if options.sort_keys {
+            // Only reorder runs of plain assignments; variables and handler calls are order-sensitive
+            for run in ordered.chunk_by_mut(|a, b| {
+                matches!(a, DocumentNode::Assignment { .. })
+                    && matches!(b, DocumentNode::Assignment { .. })
+            }) {
+                run.sort_by_key(|node| match node {
+                    DocumentNode::Assignment { key, .. } => key.join(":"),
+                    _ => String::new(),
+                });
+            }
+        }
+
+        let widths = if options.align_equals {
+            Self::alignment_widths(&ordered)
+        } else {
+            vec![None; ordered.len()]
+        };
+
+        for (i, node) in ordered.iter().enumerate() {
+            let is_block = matches!(
+                node,
+                DocumentNode::CategoryBlock { .. } | DocumentNode::SpecialCategoryBlock { .. }
+            );
+
+            if options.blank_line_between_categories && i > 0 {
+                let previous = ordered[i - 1];
+                let previous_is_block = matches!(
+                    previous,
+                    DocumentNode::CategoryBlock { .. } | DocumentNode::SpecialCategoryBlock { .. }
+                );
+                let is_blank = matches!(node, DocumentNode::BlankLine { .. });
+                let previous_is_blank = matches!(previous, DocumentNode::BlankLine { .. });
+
+                if (is_block || previous_is_block) && !is_blank && !previous_is_blank {
+                    output.push('\n');
+                }
+            }
 
-    /// Serialize nodes at a specific indentation level
-    #[allow(clippy::only_used_in_recursion)]
-    fn serialize_nodes(&self, nodes: &[DocumentNode], output: &mut String, indent: usize) {
-        for node in nodes {
             match node {
                 DocumentNode::Comment { text, .. } => {
                     // Preserve exact spacing in comments
                     if text.is_empty() {
-                        output.push_str(&format!("{}#\n", "  ".repeat(indent)));
+                        output.push_str(&format!("{}#\n", indent));
                     } else {
-                        output.push_str(&format!("{}#{}\n", "  ".repeat(indent), text));
+                        output.push_str(&format!("{}#{}\n", indent, text));
                     }
                 }
 
@@ -265,49 +851,92 @@ impl ConfigDocument {
                     output.push('\n');
                 }
 
-                DocumentNode::VariableDef { raw, .. } => {
-                    output.push_str(&format!("{}{}\n", "  ".repeat(indent), raw));
-                }
-
-                DocumentNode::Assignment { raw, .. } => {
-                    output.push_str(&format!("{}{}\n", "  ".repeat(indent), raw));
+                DocumentNode::VariableDef { raw, .. }
+                | DocumentNode::Assignment { raw, .. }
+                | DocumentNode::HandlerCall { raw, .. } => {
+                    let line = match widths[i] {
+                        Some(width) => align_raw(raw, width),
+                        None => raw.clone(),
+                    };
+                    output.push_str(&format!("{}{}\n", indent, line));
                 }
 
                 DocumentNode::CategoryBlock {
                     raw_open,
                     nodes: child_nodes,
+                    indent: block_indent,
+                    close_comment,
                     ..
-                } => {
-                    output.push_str(&format!("{}{}\n", "  ".repeat(indent), raw_open));
-                    self.serialize_nodes(child_nodes, output, indent + 1);
-                    output.push_str(&format!("{}}}\n", "  ".repeat(indent)));
                 }
-
-                DocumentNode::SpecialCategoryBlock {
+                | DocumentNode::SpecialCategoryBlock {
                     raw_open,
                     nodes: child_nodes,
+                    indent: block_indent,
+                    close_comment,
                     ..
                 } => {
-                    output.push_str(&format!("{}{}\n", "  ".repeat(indent), raw_open));
-                    self.serialize_nodes(child_nodes, output, indent + 1);
-                    output.push_str(&format!("{}}}\n", "  ".repeat(indent)));
-                }
+                    let child_indent = match block_indent {
+                        Some(detected) if options.keep_indent => detected.clone(),
+                        _ => format!("{}{}", indent, unit),
+                    };
+
+                    let raw_open = match options.brace_style {
+                        Some(style) => restyle_block_open(raw_open, style),
+                        None => raw_open.clone(),
+                    };
+
+                    // `name { }` written on one line stays that way while it's empty
+                    let open = match inline_block_open(&raw_open) {
+                        Some(_) if child_nodes.is_empty() => {
+                            output.push_str(&format!("{}{}\n", indent, raw_open));
+                            continue;
+                        }
+                        Some(open) => open,
+                        None => raw_open,
+                    };
 
-                DocumentNode::HandlerCall { raw, .. } => {
-                    output.push_str(&format!("{}{}\n", "  ".repeat(indent), raw));
+                    output.push_str(&format!("{}{}\n", indent, open));
+                    Self::serialize_nodes(child_nodes, output, &child_indent, unit, options);
+                    match close_comment {
+                        Some(comment) => output.push_str(&format!("{}}} #{}\n", indent, comment)),
+                        None => output.push_str(&format!("{}}}\n", indent)),
+                    }
                 }
 
                 DocumentNode::Source { raw, .. } => {
-                    output.push_str(&format!("{}{}\n", "  ".repeat(indent), raw));
+                    output.push_str(&format!("{}{}\n", indent, raw));
                 }
 
                 DocumentNode::CommentDirective { raw, .. } => {
-                    output.push_str(&format!("{}{}\n", "  ".repeat(indent), raw));
+                    output.push_str(&format!("{}{}\n", indent, raw));
                 }
             }
         }
     }
 
+    /// Width of the left-hand side for each `key = value` line, shared across runs of
+    /// consecutive lines so their `=` signs line up
+    fn alignment_widths(nodes: &[&DocumentNode]) -> Vec<Option<usize>> {
+        let lhs_widths: Vec<Option<usize>> = nodes
+            .iter()
+            .map(|node| match node {
+                DocumentNode::VariableDef { raw, .. }
+                | DocumentNode::Assignment { raw, .. }
+                | DocumentNode::HandlerCall { raw, .. } => raw
+                    .split_once('=')
+                    .map(|(lhs, _)| lhs.trim_end().chars().count()),
+                _ => None,
+            })
+            .collect();
+
+        let mut widths = Vec::with_capacity(nodes.len());
+        for run in lhs_widths.chunk_by(|a, b| a.is_some() == b.is_some()) {
+            let width = run.iter().flatten().max().copied();
+            widths.extend(std::iter::repeat_n(width, run.len()));
+        }
+        widths
+    }
+
     /// Find a node by its location
     pub fn get_node_at(&self, location: &NodeLocation) -> ParseResult<&DocumentNode> {
         let mut current_nodes = &self.nodes;
@@ -390,6 +1019,16 @@ impl ConfigDocument {
 
     /// Update or insert a variable definition
     pub fn update_or_insert_variable(&mut self, name: &str, value: &str) -> ParseResult<()> {
+        self.write_variable(name, value, &format!("${}", name))
+    }
+
+    /// Update or insert a variable definition, writing its name as `reference`, e.g. `@GAPS`
+    pub(crate) fn write_variable(
+        &mut self,
+        name: &str,
+        value: &str,
+        reference: &str,
+    ) -> ParseResult<()> {
         let key = format!("${}", name);
 
         if let Some(locations) = self.key_index.get(&key).cloned() {
@@ -404,18 +1043,17 @@ impl ConfigDocument {
             } = node
             {
                 *old_value = value.to_string();
-                *raw = format!("${} = {}", name, value);
+                *raw = format!("{} = {}", reference, value);
             }
         } else {
             // Insert new variable at the beginning
             let new_node = DocumentNode::VariableDef {
                 name: name.to_string(),
                 value: value.to_string(),
-                raw: format!("${} = {}", name, value),
+                raw: format!("{} = {}", reference, value),
                 line: 1,
             };
-            self.nodes.insert(0, new_node);
-            self.rebuild_index();
+            self.insert_node(&[], 0, new_node)?;
         }
 
         Ok(())
@@ -424,36 +1062,114 @@ impl ConfigDocument {
     /// Update or insert a value assignment
     pub fn update_or_insert_value(&mut self, key_path: &str, value: &str) -> ParseResult<()> {
         if let Some(locations) = self.key_index.get(key_path).cloned() {
-            // Update existing value (use first occurrence)
-            let location = &locations[0];
-            let node = self.get_node_at_mut(location)?;
+            // Update the last occurrence, the one that takes effect when parsed
+            let location = locations.last().expect("indexed keys have a location");
+            set_assignment_value(self.get_node_at_mut(location)?, value);
+        } else {
+            let (parent, new_node) = self.new_value_node(key_path, value)?;
+            let len = self.children_mut(&parent)?.len();
+            self.insert_node(&parent, len, new_node)?;
+        }
 
-            if let DocumentNode::Assignment {
-                value: old_value,
-                raw,
-                key,
-                ..
-            } = node
+        Ok(())
+    }
+
+    /// Update or insert several values in order, rebuilding the index once
+    ///
+    /// Gives the same document as calling [`update_or_insert_value`](Self::update_or_insert_value)
+    /// for each pair, without indexing each inserted value on its own.
+    pub fn update_or_insert_values<K, V>(&mut self, values: &[(K, V)]) -> ParseResult<()>
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        // Paths of the values inserted so far, which the index doesn't know yet
+        let mut inserted: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (key_path, value) in values {
+            let (key_path, value) = (key_path.as_ref(), value.as_ref());
+            if let Some((&index, parent)) =
+                inserted.get(key_path).and_then(|path| path.split_last())
             {
-                *old_value = value.to_string();
-                *raw = format!("{} = {}", key.join(":"), value);
+                set_assignment_value(&mut self.children_mut(parent)?[index], value);
+            } else if self.key_index.contains_key(key_path) {
+                self.update_or_insert_value(key_path, value)?;
+            } else {
+                // Appending leaves the paths of every other node as they were
+                let (mut path, new_node) = self.new_value_node(key_path, value)?;
+                let nodes = self.children_mut(&path)?;
+                nodes.push(new_node);
+                path.push(nodes.len() - 1);
+                inserted.insert(key_path, path);
             }
-        } else {
-            // Insert new value
-            let key_parts: Vec<String> = key_path.split(':').map(|s| s.to_string()).collect();
-            let new_node = DocumentNode::Assignment {
-                key: key_parts.clone(),
-                value: value.to_string(),
-                raw: format!("{} = {}", key_path, value),
-                line: self.nodes.len() + 1,
-            };
-            self.nodes.push(new_node);
-            self.rebuild_index();
         }
 
+        if !inserted.is_empty() {
+            self.rebuild_index();
+        }
         Ok(())
     }
 
+    /// A new assignment for `key_path` and the block to append it to
+    ///
+    /// That is the deepest existing block on its category path, so the value picks up the
+    /// block's indentation when serialized.
+    fn new_value_node(
+        &mut self,
+        key_path: &str,
+        value: &str,
+    ) -> ParseResult<(Vec<usize>, DocumentNode)> {
+        let key_parts: Vec<String> = key_path.split(':').map(|s| s.to_string()).collect();
+        let (parent, depth) = self.deepest_block(&key_parts);
+        let local_key = key_parts[depth..].to_vec();
+        let len = self.children_mut(&parent)?.len();
+
+        let new_node = DocumentNode::Assignment {
+            raw: format!("{} = {}", local_key.join(":"), value),
+            key: local_key,
+            value: value.to_string(),
+            equals: " = ".to_string(),
+            line: len + 1,
+        };
+        Ok((parent, new_node))
+    }
+
+    /// Walk the first block matching each category component of `key_parts`
+    ///
+    /// Returns the path of the deepest block found and how many components it consumed.
+    fn deepest_block(&self, key_parts: &[String]) -> (Vec<usize>, usize) {
+        let categories = &key_parts[..key_parts.len().saturating_sub(1)];
+        let mut nodes = &self.nodes;
+        let mut path = Vec::new();
+
+        for (depth, category) in categories.iter().enumerate() {
+            let position = nodes.iter().position(|node| match node {
+                DocumentNode::CategoryBlock { name, .. } => name == category,
+                DocumentNode::SpecialCategoryBlock { name, key, .. } => match key {
+                    Some(k) => format!("{}[{}]", name, k) == *category,
+                    None => name == category,
+                },
+                _ => false,
+            });
+
+            let Some(position) = position else {
+                return (path, depth);
+            };
+
+            match &nodes[position] {
+                DocumentNode::CategoryBlock {
+                    nodes: child_nodes, ..
+                }
+                | DocumentNode::SpecialCategoryBlock {
+                    nodes: child_nodes, ..
+                } => nodes = child_nodes,
+                _ => unreachable!("position only matches category blocks"),
+            }
+            path.push(position);
+        }
+
+        (path, categories.len())
+    }
+
     /// Update or insert a handler call
     pub fn add_handler_call(&mut self, keyword: &str, value: &str) -> ParseResult<()> {
         let new_node = DocumentNode::HandlerCall {
@@ -463,9 +1179,13 @@ impl ConfigDocument {
             raw: format!("{} = {}", keyword, value),
             line: self.nodes.len() + 1,
         };
-        self.nodes.push(new_node);
-        self.rebuild_index();
-        Ok(())
+        self.insert_node(&[], self.nodes.len(), new_node)
+    }
+
+    /// Append a node at the end of the root level
+    #[cfg(feature = "hyprland")]
+    pub(crate) fn append_node(&mut self, node: DocumentNode) -> ParseResult<()> {
+        self.insert_node(&[], self.nodes.len(), node)
     }
 
     /// Remove a value by key
@@ -473,8 +1193,7 @@ impl ConfigDocument {
         if let Some(locations) = self.key_index.get(key_path).cloned() {
             // Remove first occurrence
             let location = &locations[0];
-            self.remove_node_at(location)?;
-            self.rebuild_index();
+            self.remove_node_at(&location.path)?;
         }
         Ok(())
     }
@@ -484,133 +1203,458 @@ impl ConfigDocument {
         let key = format!("${}", name);
         if let Some(locations) = self.key_index.get(&key).cloned() {
             let location = &locations[0];
-            self.remove_node_at(location)?;
-            self.rebuild_index();
+            self.remove_node_at(&location.path)?;
         }
         Ok(())
     }
 
     /// Remove a node at a specific location
-    fn remove_node_at(&mut self, location: &NodeLocation) -> ParseResult<()> {
-        if location.path.is_empty() {
+    fn remove_node_at(&mut self, path: &[usize]) -> ParseResult<()> {
+        let Some((&idx, parent)) = path.split_last() else {
             return Err(ConfigError::custom("Cannot remove root"));
+        };
+
+        if idx >= self.children_mut(parent)?.len() {
+            return Err(ConfigError::custom(format!(
+                "Invalid node path: index {} out of bounds",
+                idx
+            )));
         }
+        let entries = self.entries_at(path);
+        self.children_mut(parent)?.remove(idx);
 
-        let mut current_nodes = &mut self.nodes;
+        self.unindex_entries(entries);
+        self.shift_index(path, false);
+        Ok(())
+    }
 
-        // Navigate to parent
-        for (i, &idx) in location.path.iter().enumerate() {
-            if i == location.path.len() - 1 {
-                // Last index - remove the node
-                if idx >= current_nodes.len() {
-                    return Err(ConfigError::custom(format!(
-                        "Invalid node path: index {} out of bounds",
-                        idx
-                    )));
+    /// Find the paths of all handler call nodes for a keyword, in document order
+    ///
+    /// Handler calls are parsed as assignments with a single-element key matching the
+    /// keyword, so both Assignment and explicit HandlerCall nodes are considered.
+    fn find_handler_call_paths(&self, keyword: &str) -> Vec<Vec<usize>> {
+        fn find_handler_calls(
+            nodes: &[DocumentNode],
+            keyword: &str,
+            current_path: &[usize],
+            results: &mut Vec<Vec<usize>>,
+        ) {
+            for (idx, node) in nodes.iter().enumerate() {
+                let mut path = current_path.to_vec();
+                path.push(idx);
+
+                match node {
+                    // Handler calls have a single key element matching the keyword
+                    DocumentNode::Assignment { key, .. } if key.len() == 1 && key[0] == keyword => {
+                        results.push(path);
+                    }
+                    // Also check for explicit HandlerCall nodes (if any exist)
+                    DocumentNode::HandlerCall { keyword: kw, .. } if kw == keyword => {
+                        results.push(path);
+                    }
+                    DocumentNode::CategoryBlock {
+                        nodes: child_nodes, ..
+                    } => {
+                        find_handler_calls(child_nodes, keyword, &path, results);
+                    }
+                    DocumentNode::SpecialCategoryBlock {
+                        nodes: child_nodes, ..
+                    } => {
+                        find_handler_calls(child_nodes, keyword, &path, results);
+                    }
+                    _ => {}
                 }
-                current_nodes.remove(idx);
-                return Ok(());
             }
+        }
 
-            // Navigate deeper
-            if idx >= current_nodes.len() {
-                return Err(ConfigError::custom(format!(
-                    "Invalid node path: index {} out of bounds at level {}",
-                    idx, i
-                )));
+        let mut matching_locations = Vec::new();
+        find_handler_calls(&self.nodes, keyword, &[], &mut matching_locations);
+        matching_locations
+    }
+
+    /// Resolve the path of the handler call at `index` for a keyword
+    fn handler_call_path(&self, keyword: &str, index: usize) -> ParseResult<Vec<usize>> {
+        let mut matching_locations = self.find_handler_call_paths(keyword);
+
+        if matching_locations.is_empty() {
+            return Err(ConfigError::handler(keyword, "no calls found in document"));
+        }
+
+        if index >= matching_locations.len() {
+            return Err(ConfigError::custom(format!(
+                "Handler call index {} out of bounds (found {} calls for '{}')",
+                index,
+                matching_locations.len(),
+                keyword
+            )));
+        }
+
+        Ok(matching_locations.swap_remove(index))
+    }
+
+    /// Remove a handler call by keyword and index
+    ///
+    /// Finds all handler calls (stored as assignments) with the given keyword and removes the one
+    /// at the specified index. Note: Handler calls are parsed as Assignments, so we look for
+    /// Assignment nodes where the key matches the handler keyword.
+    /// Returns an error if the handler or index doesn't exist.
+    pub fn remove_handler_call(&mut self, keyword: &str, index: usize) -> ParseResult<()> {
+        let path = self.handler_call_path(keyword, index)?;
+        self.remove_node_at(&path)
+    }
+
+    /// Remove every handler call written under a scoped keyword
+    ///
+    /// `key` is matched with its category path, so `animations:animation` finds the
+    /// `animation` lines inside every `animations { }` block. As with
+    /// [`remove_handler_call`](Self::remove_handler_call), only single-element keys count as
+    /// handler calls. Returns how many nodes were removed.
+    pub fn remove_handler_calls(&mut self, key: &str) -> usize {
+        let mut paths: Vec<Vec<usize>> = self
+            .key_index
+            .get(key)
+            .into_iter()
+            .flatten()
+            .filter(|location| {
+                matches!(
+                    self.get_node_at(location),
+                    Ok(DocumentNode::Assignment { key, .. }) if key.len() == 1
+                ) || location.node_type == NodeType::HandlerCall
+            })
+            .map(|location| location.path.clone())
+            .collect();
+
+        // Remove later nodes first so the remaining paths stay valid
+        paths.sort_unstable_by(|a, b| b.cmp(a));
+        paths
+            .iter()
+            .filter(|path| self.remove_node_at(path).is_ok())
+            .count()
+    }
+
+    /// Replace the value of a handler call by keyword and index
+    ///
+    /// Uses the same ordering as [`remove_handler_call`](Self::remove_handler_call).
+    /// The raw line is rewritten as `keyword = value`.
+    pub fn update_handler_call(
+        &mut self,
+        keyword: &str,
+        index: usize,
+        value: &str,
+    ) -> ParseResult<()> {
+        let location = NodeLocation {
+            path: self.handler_call_path(keyword, index)?,
+            node_type: NodeType::Assignment,
+        };
+
+        match self.get_node_at_mut(&location)? {
+            DocumentNode::Assignment {
+                key,
+                value: old_value,
+                raw,
+                equals,
+                ..
+            } => {
+                *old_value = value.to_string();
+                *raw = format!("{}{}{}", key.join(":"), equals, value);
+            }
+            DocumentNode::HandlerCall {
+                keyword,
+                flags,
+                value: old_value,
+                raw,
+                ..
+            } => {
+                *old_value = value.to_string();
+                *raw = format!("{}{} = {}", keyword, flags.as_deref().unwrap_or(""), value);
             }
+            _ => {}
+        }
 
-            let node = &mut current_nodes[idx];
-            current_nodes = match node {
-                DocumentNode::CategoryBlock {
-                    nodes: child_nodes, ..
-                } => child_nodes,
-                DocumentNode::SpecialCategoryBlock {
-                    nodes: child_nodes, ..
-                } => child_nodes,
-                _ => {
-                    return Err(ConfigError::custom(format!(
-                        "Node at path index {} is not a category block",
-                        i
-                    )));
-                }
+        Ok(())
+    }
+
+    /// Replace the handler call at `index` for a keyword with another node
+    ///
+    /// Uses the same ordering as [`remove_handler_call`](Self::remove_handler_call).
+    pub fn replace_handler_call(
+        &mut self,
+        keyword: &str,
+        index: usize,
+        node: DocumentNode,
+    ) -> ParseResult<()> {
+        let path = self.handler_call_path(keyword, index)?;
+        self.replace_node_at(&path, node)
+    }
+
+    /// Insert a handler call right before or after the call at `index` for `anchor`
+    ///
+    /// Uses the same ordering as [`remove_handler_call`](Self::remove_handler_call). The new
+    /// line goes into the same block as the anchor call.
+    pub fn insert_handler_call_beside(
+        &mut self,
+        anchor: &str,
+        index: usize,
+        after: bool,
+        keyword: &str,
+        value: &str,
+    ) -> ParseResult<()> {
+        let mut path = self.handler_call_path(anchor, index)?;
+        let position = path.pop().unwrap_or_default() + usize::from(after);
+        let new_node = DocumentNode::HandlerCall {
+            keyword: keyword.to_string(),
+            flags: None,
+            value: value.to_string(),
+            raw: format!("{} = {}", keyword, value),
+            line: 0,
+        };
+        self.insert_node(&path, position, new_node)
+    }
+
+    /// Rename the first assignment of a key, keeping its value
+    ///
+    /// The line is rewritten in place when the new key is in the same block (`a:b` keys
+    /// are written as-is); otherwise the value moves to where
+    /// [`update_or_insert_value`](Self::update_or_insert_value) would put it.
+    pub fn rename_value(&mut self, from: &str, to: &str) -> ParseResult<()> {
+        let location = self
+            .key_index
+            .get(from)
+            .and_then(|locations| locations.first())
+            .cloned()
+            .ok_or_else(|| ConfigError::key_not_found(from))?;
+
+        let DocumentNode::Assignment {
+            key,
+            value,
+            raw,
+            equals,
+            ..
+        } = self.get_node_at_mut(&location)?
+        else {
+            return Err(ConfigError::custom(format!("'{}' is not a value", from)));
+        };
+
+        let from_parts: Vec<&str> = from.split(':').collect();
+        let block = &from_parts[..from_parts.len().saturating_sub(key.len())];
+        let to_parts: Vec<&str> = to.split(':').collect();
+
+        if to_parts.len() > block.len() && to_parts[..block.len()] == *block {
+            let local_key: Vec<String> = to_parts[block.len()..]
+                .iter()
+                .map(|part| part.to_string())
+                .collect();
+            *raw = match raw.strip_prefix(key.join(":").as_str()) {
+                Some(rest) => format!("{}{}", local_key.join(":"), rest),
+                None => format!("{}{}{}", local_key.join(":"), equals, value),
             };
-        }
+            *key = local_key;
 
-        Err(ConfigError::custom("Failed to remove node"))
+            let path = location.path.clone();
+            self.unindex_entries(HashMap::from([(from.to_string(), vec![location])]));
+            let entries = self.entries_at(&path);
+            self.index_entries(entries);
+            Ok(())
+        } else {
+            let value = value.clone();
+            self.remove_node_at(&location.path)?;
+            self.update_or_insert_value(to, &value)
+        }
     }
 
-    /// Remove a handler call by keyword and index
+    /// Move `category:key = value` lines into the block of their category
     ///
-    /// Finds all handler calls (stored as assignments) with the given keyword and removes the one
-    /// at the specified index. Note: Handler calls are parsed as Assignments, so we look for
-    /// Assignment nodes where the key matches the handler keyword.
-    /// Returns an error if the handler or index doesn't exist.
-    pub fn remove_handler_call(&mut self, keyword: &str, index: usize) -> ParseResult<()> {
-        // Handler calls are parsed as assignments with a single-element key matching the keyword
-        // Find all matching nodes by searching through the document
-        let mut matching_locations = Vec::new();
+    /// `decoration:rounding = 10` next to a `decoration { ... }` block becomes
+    /// `rounding = 10` inside it, or updates the `rounding` line the block already has
+    /// when the flat line came after the block; a flat line before a block that sets the
+    /// same key is dropped, as the block overrides it. Lines go into the first matching
+    /// block, at its end (or its start, for lines written before the block), so parsing
+    /// the result gives the same values. Lines without a block of their category, and
+    /// lines inside a `# hyprlang if` region (or whose block is), are left as written;
+    /// blocks with a `[key]` are not used.
+    ///
+    /// Returns how many lines were moved or merged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "mutation")] {
+    /// use hyprlang::HyprlangParser;
+    ///
+    /// let (_, mut doc) = HyprlangParser::parse_with_document(
+    ///     "decoration {\n    rounding = 4\n}\ndecoration:blur:size = 3\n",
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(doc.fold_flat_assignments(), 1);
+    /// assert_eq!(
+    ///     doc.serialize(),
+    ///     "decoration {\n    rounding = 4\n    blur:size = 3\n}\n"
+    /// );
+    /// # }
+    /// ```
+    pub fn fold_flat_assignments(&mut self) -> usize {
+        let folded = Self::fold_level(&mut self.nodes);
+        if folded > 0 {
+            self.rebuild_index();
+        }
+        folded
+    }
 
-        fn find_handler_calls(
-            nodes: &[DocumentNode],
-            keyword: &str,
-            current_path: &[usize],
-            results: &mut Vec<Vec<usize>>,
+    /// Turn assignments that `is_handler` accepts into handler call nodes
+    ///
+    /// The parser can't tell `bind = ...` from a value without the registered handlers,
+    /// so it reads both as assignments. `is_handler` gets the category path of the line
+    /// and its keyword; lines inside keyed special category blocks are left alone.
+    pub(crate) fn mark_handler_calls(&mut self, is_handler: &dyn Fn(&[String], &str) -> bool) {
+        fn mark(
+            nodes: &mut [DocumentNode],
+            path: &mut Vec<String>,
+            is_handler: &dyn Fn(&[String], &str) -> bool,
         ) {
-            for (idx, node) in nodes.iter().enumerate() {
-                let mut path = current_path.to_vec();
-                path.push(idx);
-
+            for node in nodes {
                 match node {
-                    DocumentNode::Assignment { key, .. } => {
-                        // Handler calls have a single key element matching the keyword
-                        if key.len() == 1 && key[0] == keyword {
-                            results.push(path);
-                        }
-                    }
-                    DocumentNode::HandlerCall { keyword: kw, .. } => {
-                        // Also check for explicit HandlerCall nodes (if any exist)
-                        if kw == keyword {
-                            results.push(path);
-                        }
-                    }
-                    DocumentNode::CategoryBlock {
-                        nodes: child_nodes, ..
-                    } => {
-                        find_handler_calls(child_nodes, keyword, &path, results);
+                    DocumentNode::Assignment {
+                        key,
+                        value,
+                        raw,
+                        line,
+                        ..
+                    } if key.len() == 1 && is_handler(path, &key[0]) => {
+                        *node = DocumentNode::HandlerCall {
+                            keyword: key[0].clone(),
+                            flags: None,
+                            value: std::mem::take(value),
+                            raw: std::mem::take(raw),
+                            line: *line,
+                        };
                     }
-                    DocumentNode::SpecialCategoryBlock {
-                        nodes: child_nodes, ..
+                    DocumentNode::CategoryBlock { name, nodes, .. }
+                    | DocumentNode::SpecialCategoryBlock {
+                        name,
+                        key: None,
+                        nodes,
+                        ..
                     } => {
-                        find_handler_calls(child_nodes, keyword, &path, results);
+                        path.push(name.clone());
+                        mark(nodes, path, is_handler);
+                        path.pop();
                     }
                     _ => {}
                 }
             }
         }
 
-        find_handler_calls(&self.nodes, keyword, &[], &mut matching_locations);
+        mark(&mut self.nodes, &mut Vec::new(), is_handler);
+        self.rebuild_index();
+    }
 
-        if matching_locations.is_empty() {
-            return Err(ConfigError::handler(keyword, "no calls found in document"));
+    fn fold_level(nodes: &mut Vec<DocumentNode>) -> usize {
+        let mut folded = 0;
+        let mut idx = 0;
+        while idx < nodes.len() {
+            let conditional = Self::conditional_flags(nodes);
+            let target = match &nodes[idx] {
+                DocumentNode::Assignment { key, .. } if key.len() > 1 && !conditional[idx] => {
+                    nodes.iter().enumerate().position(|(position, node)| {
+                        !conditional[position]
+                            && matches!(node, DocumentNode::CategoryBlock { name, .. }
+                                | DocumentNode::SpecialCategoryBlock { name, key: None, .. }
+                                if *name == key[0])
+                    })
+                }
+                _ => None,
+            };
+            let Some(mut target) = target else {
+                idx += 1;
+                continue;
+            };
+
+            let DocumentNode::Assignment {
+                mut key,
+                value,
+                mut raw,
+                equals,
+                line,
+            } = nodes.remove(idx)
+            else {
+                unreachable!("only assignments have a target block");
+            };
+            let after_block = target < idx;
+            if !after_block {
+                target -= 1;
+            }
+
+            let written = key.join(":");
+            key.remove(0);
+            raw = match raw.strip_prefix(written.as_str()) {
+                Some(rest) => format!("{}{}", key.join(":"), rest),
+                None => format!("{}{}{}", key.join(":"), equals, value),
+            };
+
+            let children = nodes[target].block_nodes_mut().expect("targets are blocks");
+            let conditional = Self::conditional_flags(children);
+            let existing = children.iter().enumerate().rposition(|(position, node)| {
+                !conditional[position]
+                    && matches!(node, DocumentNode::Assignment { key: k, .. } if *k == key)
+            });
+            match existing {
+                Some(position) if after_block => {
+                    if let DocumentNode::Assignment {
+                        key,
+                        value: old_value,
+                        raw,
+                        equals,
+                        ..
+                    } = &mut children[position]
+                    {
+                        *raw = format!("{}{}{}", key.join(":"), equals, value);
+                        *old_value = value;
+                    }
+                }
+                Some(_) => {}
+                None => {
+                    let node = DocumentNode::Assignment {
+                        key,
+                        value,
+                        raw,
+                        equals,
+                        line,
+                    };
+                    let position = if after_block { children.len() } else { 0 };
+                    children.insert(position, node);
+                }
+            }
+            folded += 1;
         }
 
-        if index >= matching_locations.len() {
-            return Err(ConfigError::custom(format!(
-                "Handler call index {} out of bounds (found {} calls for '{}')",
-                index,
-                matching_locations.len(),
-                keyword
-            )));
+        for node in nodes.iter_mut() {
+            if let Some(children) = node.block_nodes_mut() {
+                folded += Self::fold_level(children);
+            }
         }
+        folded
+    }
 
-        let location = NodeLocation {
-            path: matching_locations[index].clone(),
-            node_type: NodeType::Assignment, // Handler calls are stored as assignments
-        };
-        self.remove_node_at(&location)?;
-        self.rebuild_index();
-        Ok(())
+    /// Whether each node is inside a `# hyprlang if` region, directives included
+    fn conditional_flags(nodes: &[DocumentNode]) -> Vec<bool> {
+        let mut depth = 0usize;
+        nodes
+            .iter()
+            .map(|node| match node {
+                DocumentNode::CommentDirective { directive_type, .. } if directive_type == "if" => {
+                    depth += 1;
+                    true
+                }
+                DocumentNode::CommentDirective { directive_type, .. }
+                    if directive_type == "endif" =>
+                {
+                    depth = depth.saturating_sub(1);
+                    true
+                }
+                _ => depth > 0,
+            })
+            .collect()
     }
 
     /// Remove a special category instance by category name and key
@@ -676,13 +1720,7 @@ impl ConfigDocument {
         let found_path = find_special_category(&self.nodes, category, key, &[]);
 
         if let Some(path) = found_path {
-            let location = NodeLocation {
-                path,
-                node_type: NodeType::SpecialCategoryBlock,
-            };
-            self.remove_node_at(&location)?;
-            self.rebuild_index();
-            Ok(())
+            self.remove_node_at(&path)
         } else {
             Err(ConfigError::category_not_found(
                 &search_key,
@@ -692,6 +1730,500 @@ impl ConfigDocument {
     }
 }
 
+/// A node of a [`ConfigDocument`] together with where it is
+///
+/// Returned by [`ConfigDocument::iter_nodes`] and the `find_*` methods.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeRef<'a> {
+    /// Indices from the root to the node, as in [`NodeLocation::path`]
+    pub path: Vec<usize>,
+
+    /// Categories the node is nested in, e.g. `["general", "gaps"]` or `["device[mouse]"]`
+    pub category: Vec<String>,
+
+    /// The node itself
+    pub node: &'a DocumentNode,
+}
+
+impl NodeRef<'_> {
+    /// Nesting depth, 0 for root-level nodes
+    pub fn depth(&self) -> usize {
+        self.category.len()
+    }
+
+    /// Key the node is known by, as used with [`Config`](crate::Config)
+    ///
+    /// `general:gaps_in` for assignments, `$NAME` for variables, `keyword` (with its
+    /// category) for handler calls and the category path for blocks, e.g. `device[mouse]`.
+    /// `None` for comments, directives and `source` lines.
+    pub fn key(&self) -> Option<String> {
+        self.node.key_in(&self.category)
+    }
+}
+
+/// Depth-first iterator over the nodes of a [`ConfigDocument`]
+///
+/// Created by [`ConfigDocument::iter_nodes`]. Blocks come before their children.
+#[derive(Debug)]
+pub struct NodeIter<'a> {
+    levels: Vec<std::iter::Enumerate<std::slice::Iter<'a, DocumentNode>>>,
+    parent: Vec<usize>,
+    category: Vec<String>,
+}
+
+impl<'a> Iterator for NodeIter<'a> {
+    type Item = NodeRef<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Some((idx, node)) = self.levels.last_mut()?.next() else {
+                self.levels.pop();
+                self.parent.pop();
+                self.category.pop();
+                continue;
+            };
+
+            let mut path = self.parent.clone();
+            path.push(idx);
+            let item = NodeRef {
+                path: path.clone(),
+                category: self.category.clone(),
+                node,
+            };
+
+            if let Some((scope, child_nodes)) = node.block() {
+                self.levels.push(child_nodes.iter().enumerate());
+                self.parent = path;
+                self.category.push(scope);
+            }
+            return Some(item);
+        }
+    }
+}
+
+impl ConfigDocument {
+    /// Iterate over every node, depth-first in document order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "mutation")] {
+    /// use hyprlang::HyprlangParser;
+    ///
+    /// let (_, doc) = HyprlangParser::parse_with_document(
+    ///     "general {\n    gaps_in = 5\n}\nbind = SUPER, Q, exec, kitty",
+    /// )
+    /// .unwrap();
+    ///
+    /// let keys: Vec<_> = doc.iter_nodes().filter_map(|node| node.key()).collect();
+    /// assert_eq!(keys, vec!["general", "general:gaps_in", "bind"]);
+    /// # }
+    /// ```
+    pub fn iter_nodes(&self) -> NodeIter<'_> {
+        NodeIter {
+            levels: vec![self.nodes.iter().enumerate()],
+            parent: Vec::new(),
+            category: Vec::new(),
+        }
+    }
+
+    /// Assignments of a key, e.g. `general:gaps_in`, in document order
+    ///
+    /// Values written with a category prefix (`general:gaps_in = 5`) and inside a block
+    /// are both found.
+    pub fn find_assignments(&self, key: &str) -> Vec<NodeRef<'_>> {
+        let Some(locations) = self.key_index.get(key) else {
+            return Vec::new();
+        };
+
+        locations
+            .iter()
+            .filter(|location| location.node_type == NodeType::Assignment)
+            .map(|location| {
+                let (&idx, parent) = location
+                    .path
+                    .split_last()
+                    .expect("indexed paths are never empty");
+                let (nodes, category) = self.walk(parent);
+                NodeRef {
+                    path: location.path.clone(),
+                    category,
+                    node: &nodes[idx],
+                }
+            })
+            .collect()
+    }
+
+    /// The first block for a category path, e.g. `general`, `general:gaps` or `device[mouse]`
+    pub fn find_category(&self, path: &str) -> Option<NodeRef<'_>> {
+        self.iter_nodes()
+            .find(|node| node.node.block().is_some() && node.key().as_deref() == Some(path))
+    }
+
+    /// The opening line of the first block for a category path, as written in the file
+    ///
+    /// See [`DocumentNode::raw_open`].
+    pub fn block_raw_open(&self, path: &str) -> Option<&str> {
+        self.find_category(path)?.node.raw_open()
+    }
+
+    /// How the opening brace of the first block for a category path is written
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "mutation")] {
+    /// use hyprlang::{BraceStyle, HyprlangParser};
+    ///
+    /// let (_, doc) = HyprlangParser::parse_with_document(
+    ///     "general {\n    gaps_in = 5\n}\ndecoration{\n    rounding = 4\n}",
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(doc.block_raw_open("decoration"), Some("decoration{"));
+    /// assert_eq!(doc.block_brace_style("general"), Some(BraceStyle::Spaced));
+    /// assert_eq!(doc.block_brace_style("decoration"), Some(BraceStyle::Attached));
+    /// assert_eq!(doc.detected_brace_style(), None);
+    /// # }
+    /// ```
+    pub fn block_brace_style(&self, path: &str) -> Option<BraceStyle> {
+        self.find_category(path)?.node.brace_style()
+    }
+
+    /// The brace style shared by every block in the document
+    ///
+    /// `None` if the document has no blocks or they are written inconsistently.
+    pub fn detected_brace_style(&self) -> Option<BraceStyle> {
+        let mut styles = self.iter_nodes().filter_map(|node| node.node.brace_style());
+        let first = styles.next()?;
+        styles.all(|style| style == first).then_some(first)
+    }
+
+    /// Rewrite the document by visiting every list of nodes
+    ///
+    /// `visit` is called with the root nodes and then the children of each block, parents
+    /// first, along with the categories the list is nested in. It may edit, reorder, add
+    /// or remove nodes; the key index is rebuilt afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "mutation")] {
+    /// use hyprlang::{DocumentNode, HyprlangParser};
+    ///
+    /// let (_, mut doc) = HyprlangParser::parse_with_document(
+    ///     "bind = SUPER, Q, exec, kitty\nbind = ALT, F4, killactive\n",
+    /// )
+    /// .unwrap();
+    ///
+    /// // Sort binds by their raw line
+    /// doc.visit_mut(|_, nodes| {
+    ///     nodes.sort_by_key(|node| match node {
+    ///         DocumentNode::Assignment { raw, .. } => raw.clone(),
+    ///         _ => String::new(),
+    ///     });
+    /// });
+    /// assert_eq!(
+    ///     doc.serialize(),
+    ///     "bind = ALT, F4, killactive\nbind = SUPER, Q, exec, kitty\n"
+    /// );
+    /// # }
+    /// ```
+    pub fn visit_mut<F>(&mut self, mut visit: F)
+    where
+        F: FnMut(&[String], &mut Vec<DocumentNode>),
+    {
+        fn visit_nodes<F>(nodes: &mut Vec<DocumentNode>, category: &mut Vec<String>, visit: &mut F)
+        where
+            F: FnMut(&[String], &mut Vec<DocumentNode>),
+        {
+            visit(category, nodes);
+            for node in nodes.iter_mut() {
+                let Some((scope, _)) = node.block() else {
+                    continue;
+                };
+                if let Some(child_nodes) = node.block_nodes_mut() {
+                    category.push(scope);
+                    visit_nodes(child_nodes, category, visit);
+                    category.pop();
+                }
+            }
+        }
+
+        visit_nodes(&mut self.nodes, &mut Vec::new(), &mut visit);
+        self.rebuild_index();
+    }
+
+    /// Move the values, variables and handler calls whose key is selected out of the document
+    ///
+    /// Nodes inside blocks are moved into copies of their blocks, and blocks left empty are
+    /// removed. Returns the moved nodes and the root index where the first of them was, or
+    /// `None` if nothing was selected.
+    pub(crate) fn extract_nodes(
+        &mut self,
+        select: impl Fn(&str) -> bool,
+    ) -> Option<(Vec<DocumentNode>, usize)> {
+        fn extract(
+            nodes: &mut Vec<DocumentNode>,
+            category: &mut Vec<String>,
+            select: &dyn Fn(&str) -> bool,
+            extracted: &mut Vec<DocumentNode>,
+            first: &mut Option<usize>,
+        ) {
+            let mut kept = Vec::with_capacity(nodes.len());
+            for mut node in nodes.drain(..) {
+                let moved = extracted.len();
+
+                if let Some((scope, children)) = node.block() {
+                    let had_children = !children.is_empty();
+                    let mut moved_children = Vec::new();
+                    category.push(scope);
+                    if let Some(children) = node.block_nodes_mut() {
+                        extract(children, category, select, &mut moved_children, first);
+                    }
+                    category.pop();
+
+                    let emptied = had_children && node.block().is_some_and(|(_, c)| c.is_empty());
+                    if !moved_children.is_empty() {
+                        let mut copy = node.clone();
+                        if let Some(children) = copy.block_nodes_mut() {
+                            *children = moved_children;
+                        }
+                        extracted.push(copy);
+                    }
+                    if !emptied {
+                        kept.push(node);
+                    }
+                } else if node.key_in(category).is_some_and(|key| select(&key)) {
+                    extracted.push(node);
+                } else {
+                    kept.push(node);
+                }
+
+                if category.is_empty() && first.is_none() && extracted.len() > moved {
+                    *first = Some(kept.len());
+                }
+            }
+            *nodes = kept;
+        }
+
+        let mut extracted = Vec::new();
+        let mut first = None;
+        extract(
+            &mut self.nodes,
+            &mut Vec::new(),
+            &select,
+            &mut extracted,
+            &mut first,
+        );
+        self.rebuild_index();
+        first.map(|index| (extracted, index))
+    }
+}
+
+impl ConfigDocument {
+    /// List `# hyprlang if` regions in document order
+    ///
+    /// Directives without a matching partner are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "mutation")] {
+    /// use hyprlang::HyprlangParser;
+    ///
+    /// let (_, doc) = HyprlangParser::parse_with_document(
+    ///     "# hyprlang if LAPTOP\nmonitor = eDP-1, preferred, auto, 1\n# hyprlang endif",
+    /// )
+    /// .unwrap();
+    ///
+    /// let regions = doc.conditional_regions();
+    /// assert_eq!(regions[0].variable(), "LAPTOP");
+    /// assert_eq!(regions[0].body(), 1..2);
+    /// # }
+    /// ```
+    pub fn conditional_regions(&self) -> Vec<ConditionalRegion> {
+        fn collect(
+            nodes: &[DocumentNode],
+            parent: &mut Vec<usize>,
+            regions: &mut Vec<ConditionalRegion>,
+        ) {
+            let mut open: Vec<(usize, String)> = Vec::new();
+            for (idx, node) in nodes.iter().enumerate() {
+                match node {
+                    DocumentNode::CommentDirective {
+                        directive_type,
+                        args,
+                        ..
+                    } => match directive_type.as_str() {
+                        "if" => open.push((idx, args.clone().unwrap_or_default())),
+                        "endif" => {
+                            if let Some((start, condition)) = open.pop() {
+                                regions.push(ConditionalRegion {
+                                    condition,
+                                    parent: parent.clone(),
+                                    start,
+                                    end: idx,
+                                });
+                            }
+                        }
+                        _ => {}
+                    },
+                    DocumentNode::CategoryBlock {
+                        nodes: child_nodes, ..
+                    }
+                    | DocumentNode::SpecialCategoryBlock {
+                        nodes: child_nodes, ..
+                    } => {
+                        parent.push(idx);
+                        collect(child_nodes, parent, regions);
+                        parent.pop();
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut regions = Vec::new();
+        collect(&self.nodes, &mut Vec::new(), &mut regions);
+        regions.sort_by_key(|region| {
+            let mut position = region.parent.clone();
+            position.push(region.start);
+            position
+        });
+        regions
+    }
+
+    /// Wrap a range of nodes in `# hyprlang if condition` / `# hyprlang endif`
+    ///
+    /// `parent` is the path to the block holding the nodes (empty for root level) and
+    /// `range` their indices within it. The range may contain whole conditional regions
+    /// but not just one of their directives. Returns the new region.
+    pub fn wrap_in_condition(
+        &mut self,
+        parent: &[usize],
+        range: std::ops::Range<usize>,
+        condition: &str,
+    ) -> ParseResult<ConditionalRegion> {
+        let condition = condition.trim();
+        let variable = condition.strip_prefix('!').unwrap_or(condition).trim();
+        if variable.is_empty() || variable.contains(char::is_whitespace) {
+            return Err(ConfigError::custom(format!(
+                "invalid condition '{}'",
+                condition
+            )));
+        }
+
+        let nodes = self.children_mut(parent)?;
+        if range.start > range.end || range.end > nodes.len() {
+            return Err(ConfigError::custom(format!(
+                "Invalid node range {}..{}",
+                range.start, range.end
+            )));
+        }
+
+        let mut depth = 0usize;
+        for node in &nodes[range.clone()] {
+            if let DocumentNode::CommentDirective { directive_type, .. } = node {
+                match directive_type.as_str() {
+                    "if" => depth += 1,
+                    "endif" if depth == 0 => {
+                        return Err(ConfigError::custom(
+                            "range would split a conditional region",
+                        ));
+                    }
+                    "endif" => depth -= 1,
+                    _ => {}
+                }
+            }
+        }
+        if depth != 0 {
+            return Err(ConfigError::custom(
+                "range would split a conditional region",
+            ));
+        }
+
+        let directive = |directive_type: &str, args: Option<&str>| DocumentNode::CommentDirective {
+            directive_type: directive_type.to_string(),
+            args: args.map(str::to_string),
+            raw: match args {
+                Some(args) => format!("# hyprlang {} {}", directive_type, args),
+                None => format!("# hyprlang {}", directive_type),
+            },
+            line: 0,
+        };
+        self.insert_node(parent, range.end, directive("endif", None))?;
+        self.insert_node(parent, range.start, directive("if", Some(condition)))?;
+
+        Ok(ConditionalRegion {
+            condition: condition.to_string(),
+            parent: parent.to_vec(),
+            start: range.start,
+            end: range.end + 1,
+        })
+    }
+
+    /// Remove the directives of a conditional region, keeping the nodes inside it
+    ///
+    /// The region must be one returned by
+    /// [`conditional_regions`](ConfigDocument::conditional_regions) for the current
+    /// document.
+    pub fn remove_condition(&mut self, region: &ConditionalRegion) -> ParseResult<()> {
+        if !self.conditional_regions().contains(region) {
+            return Err(ConfigError::custom(format!(
+                "no conditional region '{}' at index {}",
+                region.condition, region.start
+            )));
+        }
+
+        for idx in [region.end, region.start] {
+            let mut path = region.parent.clone();
+            path.push(idx);
+            self.remove_node_at(&path)?;
+        }
+        Ok(())
+    }
+
+    /// The child nodes of the block at `path` (the root nodes for an empty path)
+    fn children_mut(&mut self, path: &[usize]) -> ParseResult<&mut Vec<DocumentNode>> {
+        let mut current_nodes = &mut self.nodes;
+        for &idx in path {
+            match current_nodes.get_mut(idx) {
+                Some(
+                    DocumentNode::CategoryBlock {
+                        nodes: child_nodes, ..
+                    }
+                    | DocumentNode::SpecialCategoryBlock {
+                        nodes: child_nodes, ..
+                    },
+                ) => current_nodes = child_nodes,
+                _ => {
+                    return Err(ConfigError::custom(format!(
+                        "Invalid node path: index {} is not a block",
+                        idx
+                    )));
+                }
+            }
+        }
+        Ok(current_nodes)
+    }
+}
+
+/// Give an assignment a new value, keeping how its key and `=` were written
+fn set_assignment_value(node: &mut DocumentNode, value: &str) {
+    if let DocumentNode::Assignment {
+        value: old_value,
+        raw,
+        key,
+        equals,
+        ..
+    } = node
+    {
+        *old_value = value.to_string();
+        *raw = format!("{}{}{}", key.join(":"), equals, value);
+    }
+}
+
 impl Default for ConfigDocument {
     fn default() -> Self {
         Self::new()
@@ -792,13 +2324,7 @@ impl MultiFileDocument {
         self.documents.keys().collect()
     }
 
-    /// Clear dirty flags (after saving)
-    pub fn clear_dirty(&mut self) {
-        self.dirty_files.clear();
-    }
-
     /// Clear dirty flag for a specific file
-    #[allow(dead_code)]
     pub fn clear_dirty_file(&mut self, path: &Path) {
         self.dirty_files.remove(path);
     }
@@ -820,6 +2346,7 @@ mod tests {
             key: vec!["border_size".to_string()],
             value: "2".to_string(),
             raw: "border_size = 2".to_string(),
+            equals: " = ".to_string(),
             line: 1,
         }];
 
@@ -851,6 +2378,7 @@ mod tests {
                 key: vec!["key".to_string()],
                 value: "value".to_string(),
                 raw: "key = value".to_string(),
+                equals: " = ".to_string(),
                 line: 2,
             },
         ];
@@ -866,6 +2394,7 @@ mod tests {
                 key: vec!["key1".to_string()],
                 value: "value1".to_string(),
                 raw: "key1 = value1".to_string(),
+                equals: " = ".to_string(),
                 line: 1,
             },
             DocumentNode::BlankLine { line: 2 },
@@ -873,6 +2402,7 @@ mod tests {
                 key: vec!["key2".to_string()],
                 value: "value2".to_string(),
                 raw: "key2 = value2".to_string(),
+                equals: " = ".to_string(),
                 line: 3,
             },
         ];
@@ -889,11 +2419,15 @@ mod tests {
                 key: vec!["border_size".to_string()],
                 value: "2".to_string(),
                 raw: "border_size = 2".to_string(),
+                equals: " = ".to_string(),
                 line: 2,
             }],
             open_line: 1,
             close_line: 3,
             raw_open: "general {".to_string(),
+            indent: None,
+            open_comment: None,
+            close_comment: None,
         }];
 
         let doc = ConfigDocument::with_nodes(nodes);
@@ -910,15 +2444,22 @@ mod tests {
                     key: vec!["enabled".to_string()],
                     value: "true".to_string(),
                     raw: "enabled = true".to_string(),
+                    equals: " = ".to_string(),
                     line: 3,
                 }],
                 open_line: 2,
                 close_line: 4,
                 raw_open: "shadow {".to_string(),
+                indent: None,
+                open_comment: None,
+                close_comment: None,
             }],
             open_line: 1,
             close_line: 5,
             raw_open: "decoration {".to_string(),
+            indent: None,
+            open_comment: None,
+            close_comment: None,
         }];
 
         let doc = ConfigDocument::with_nodes(nodes);
@@ -941,6 +2482,7 @@ mod tests {
                 key: vec!["border_size".to_string()],
                 value: "2".to_string(),
                 raw: "border_size = 2".to_string(),
+                equals: " = ".to_string(),
                 line: 2,
             },
         ];
@@ -959,12 +2501,14 @@ mod tests {
                 key: vec!["key1".to_string()],
                 value: "value1".to_string(),
                 raw: "key1 = value1".to_string(),
+                equals: " = ".to_string(),
                 line: 1,
             },
             DocumentNode::Assignment {
                 key: vec!["key2".to_string()],
                 value: "value2".to_string(),
                 raw: "key2 = value2".to_string(),
+                equals: " = ".to_string(),
                 line: 2,
             },
         ];
@@ -983,4 +2527,52 @@ mod tests {
             _ => panic!("Expected Assignment node"),
         }
     }
+
+    #[test]
+    fn test_index_stays_in_sync_with_mutations() {
+        let (_, mut doc) = crate::parser::HyprlangParser::parse_with_document(
+            "$A = 1\nexec = a\ngeneral {\n  gaps = 5\n  border = 2\n}\n\
+             device[mouse] {\n  sens = 1\n}\nexec = b\nlast = 1\n",
+        )
+        .unwrap();
+
+        let assert_in_sync = |doc: &ConfigDocument| {
+            let mut rebuilt = doc.clone();
+            rebuilt.rebuild_index();
+            assert_eq!(doc.key_index, rebuilt.key_index);
+        };
+
+        doc.update_or_insert_variable("B", "2").unwrap();
+        assert_in_sync(&doc);
+        doc.update_or_insert_value("general:shadow", "true")
+            .unwrap();
+        assert_in_sync(&doc);
+        doc.update_or_insert_value("device[mouse]:accel", "flat")
+            .unwrap();
+        assert_in_sync(&doc);
+        doc.remove_value("general:gaps").unwrap();
+        assert_in_sync(&doc);
+        doc.rename_value("general:border", "general:border_size")
+            .unwrap();
+        assert_in_sync(&doc);
+        doc.rename_value("last", "general:last").unwrap();
+        assert_in_sync(&doc);
+        doc.add_handler_call("exec", "c").unwrap();
+        doc.remove_handler_call("exec", 0).unwrap();
+        assert_in_sync(&doc);
+        doc.remove_special_category_instance("device", "mouse")
+            .unwrap();
+        assert_in_sync(&doc);
+        doc.remove_variable("A").unwrap();
+        assert_in_sync(&doc);
+
+        let region = doc.wrap_in_condition(&[], 0..2, "LAPTOP").unwrap();
+        assert_in_sync(&doc);
+        doc.remove_condition(&region).unwrap();
+        assert_in_sync(&doc);
+
+        assert_eq!(doc.get_locations("general:border_size").unwrap().len(), 1);
+        assert_eq!(doc.get_locations("exec").unwrap().len(), 2);
+        assert!(doc.get_locations("general:gaps").is_none());
+    }
 }
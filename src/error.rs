@@ -1,4 +1,5 @@
 use std::fmt;
+use std::path::PathBuf;
 
 /// Result type alias for configuration operations
 pub type ParseResult<T> = Result<T, ConfigError>;
@@ -47,9 +48,85 @@ pub enum ConfigError {
     /// Handler error
     HandlerError { handler: String, message: String },
 
+    /// Returned by a handler to reject a call: the call is not stored and parsing goes on
+    ///
+    /// See [`Config::rejected_handler_calls`](crate::Config::rejected_handler_calls).
+    HandlerRejected { reason: String },
+
+    /// Error returned by a handler, with the call that triggered it
+    HandlerCallFailed {
+        /// Handler keyword, without flags
+        keyword: String,
+        /// Value passed to the handler, after variable expansion
+        value: String,
+        /// Categories the call was made in, e.g. `["animations"]`
+        category: Vec<String>,
+        file: Option<PathBuf>,
+        line: Option<usize>,
+        /// The error the handler returned
+        error: Box<ConfigError>,
+    },
+
+    /// Variable expansion failed for a handler value
+    HandlerExpansionFailed {
+        /// Handler keyword, without flags
+        keyword: String,
+        /// Value as written, before expansion
+        value: String,
+        /// The undefined variable, or the first variable of a cycle
+        variable: Option<String>,
+        file: Option<PathBuf>,
+        line: usize,
+        /// The expansion error
+        error: Box<ConfigError>,
+    },
+
     /// File I/O error
     IoError { path: String, message: String },
 
+    /// Value rejected by a registered validator
+    ValidationError {
+        key: String,
+        value: String,
+        reason: String,
+    },
+
+    /// Template placeholders without a substitution
+    MissingPlaceholders { names: Vec<String> },
+
+    /// Special category block without properties its descriptor requires
+    MissingProperties {
+        category: String,
+        key: Option<String>,
+        properties: Vec<String>,
+        file: Option<PathBuf>,
+        /// Line of the block's opening brace
+        line: usize,
+    },
+
+    /// Special category property outside the values its descriptor allows
+    ConstraintViolation {
+        key: String,
+        value: String,
+        reason: String,
+        file: Option<PathBuf>,
+        line: usize,
+    },
+
+    /// Category block repeating an earlier one under [`CategoryMerge::Error`]
+    ///
+    /// [`CategoryMerge::Error`]: crate::CategoryMerge::Error
+    DuplicateCategory {
+        /// Full path of the category
+        category: String,
+        file: Option<PathBuf>,
+        /// Line of the repeated block's opening brace
+        line: usize,
+        /// Where the category was first defined
+        first_file: Option<PathBuf>,
+        first_line: usize,
+    },
+
     /// Custom error with message
     Custom { message: String },
 
@@ -135,6 +212,80 @@ impl ConfigError {
         }
     }
 
+    /// Create the error a handler returns to reject a call
+    ///
+    /// The call is left out of the stored handler calls and reported in
+    /// [`Config::rejected_handler_calls`](crate::Config::rejected_handler_calls) instead of
+    /// failing the parse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "config")] {
+    /// use hyprlang::{Config, ConfigError};
+    ///
+    /// let mut config = Config::new();
+    /// config.register_handler_fn("bind", |ctx| {
+    ///     if ctx.args().len() < 3 {
+    ///         return Err(ConfigError::rejected("expected MODS, key, dispatcher"));
+    ///     }
+    ///     Ok(())
+    /// });
+    /// config
+    ///     .parse("bind = SUPER, Q, exec, kitty\nbind = SUPER\nbind = ALT, F4, killactive")
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.get_handler_calls("bind").unwrap().len(), 2);
+    /// assert_eq!(config.rejected_handler_calls()[0].line, 2);
+    /// # }
+    /// ```
+    pub fn rejected(reason: impl Into<String>) -> Self {
+        ConfigError::HandlerRejected {
+            reason: reason.into(),
+        }
+    }
+
+    /// The reason a handler gave for rejecting a call, if this is such a rejection
+    #[cfg(feature = "config")]
+    pub(crate) fn rejection(&self) -> Option<&str> {
+        match self {
+            ConfigError::HandlerRejected { reason } => Some(reason),
+            ConfigError::HandlerCallFailed { error, .. } => error.rejection(),
+            _ => None,
+        }
+    }
+
+    /// Record where a failed handler call was made
+    ///
+    /// Other errors are returned unchanged.
+    #[cfg(feature = "config")]
+    pub(crate) fn with_handler_location(mut self, file: Option<PathBuf>, at: usize) -> Self {
+        if let ConfigError::HandlerCallFailed {
+            file: call_file,
+            line,
+            ..
+        } = &mut self
+        {
+            *call_file = file;
+            *line = Some(at);
+        }
+        self
+    }
+
+    /// Line the error points at, for errors that carry one
+    #[cfg(feature = "config")]
+    pub(crate) fn line(&self) -> Option<usize> {
+        match self {
+            ConfigError::ParseError { line, .. }
+            | ConfigError::HandlerExpansionFailed { line, .. }
+            | ConfigError::MissingProperties { line, .. }
+            | ConfigError::ConstraintViolation { line, .. }
+            | ConfigError::DuplicateCategory { line, .. } => Some(*line),
+            ConfigError::HandlerCallFailed { line, .. } => *line,
+            _ => None,
+        }
+    }
+
     /// Create an I/O error
     pub fn io(path: impl Into<String>, message: impl Into<String>) -> Self {
         ConfigError::IoError {
@@ -143,6 +294,75 @@ impl ConfigError {
         }
     }
 
+    /// Create a validation error
+    pub fn validation(
+        key: impl Into<String>,
+        value: impl Into<String>,
+        reason: impl Into<String>,
+    ) -> Self {
+        ConfigError::ValidationError {
+            key: key.into(),
+            value: value.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Create a missing placeholders error
+    pub fn missing_placeholders(names: Vec<String>) -> Self {
+        ConfigError::MissingPlaceholders { names }
+    }
+
+    /// Create a missing required properties error
+    pub fn missing_properties(
+        category: impl Into<String>,
+        key: Option<String>,
+        properties: Vec<String>,
+        file: Option<PathBuf>,
+        line: usize,
+    ) -> Self {
+        ConfigError::MissingProperties {
+            category: category.into(),
+            key,
+            properties,
+            file,
+            line,
+        }
+    }
+
+    /// Create a duplicate category error
+    pub fn duplicate_category(
+        category: impl Into<String>,
+        file: Option<PathBuf>,
+        line: usize,
+        first_file: Option<PathBuf>,
+        first_line: usize,
+    ) -> Self {
+        ConfigError::DuplicateCategory {
+            category: category.into(),
+            file,
+            line,
+            first_file,
+            first_line,
+        }
+    }
+
+    /// Create a constraint violation error
+    pub fn constraint_violation(
+        key: impl Into<String>,
+        value: impl Into<String>,
+        reason: impl Into<String>,
+        file: Option<PathBuf>,
+        line: usize,
+    ) -> Self {
+        ConfigError::ConstraintViolation {
+            key: key.into(),
+            value: value.into(),
+            reason: reason.into(),
+            file,
+            line,
+        }
+    }
+
     /// Create a custom error
     pub fn custom(message: impl Into<String>) -> Self {
         ConfigError::Custom {
@@ -156,6 +376,21 @@ impl ConfigError {
     }
 }
 
+/// `file:line`, or `line N` for strings
+struct Location<'a>(&'a Option<PathBuf>, usize);
+
+/// Characters of a handler value shown in [`ConfigError::HandlerCallFailed`] messages
+const VALUE_EXCERPT_LEN: usize = 40;
+
+impl fmt::Display for Location<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(file) => write!(f, "{}:{}", file.display(), self.1),
+            None => write!(f, "line {}", self.1),
+        }
+    }
+}
+
 impl fmt::Display for ConfigError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -209,9 +444,105 @@ impl fmt::Display for ConfigError {
             ConfigError::HandlerError { handler, message } => {
                 write!(f, "Handler '{}' error: {}", handler, message)
             }
+            ConfigError::HandlerRejected { reason } => {
+                write!(f, "Call rejected: {}", reason)
+            }
+            ConfigError::HandlerCallFailed {
+                keyword,
+                value,
+                category,
+                file,
+                line,
+                error,
+            } => {
+                f.write_str("Handler call '")?;
+                for name in category {
+                    write!(f, "{}:", name)?;
+                }
+                write!(f, "{} = ", keyword)?;
+                match value.char_indices().nth(VALUE_EXCERPT_LEN) {
+                    Some((end, _)) => write!(f, "{}...", &value[..end])?,
+                    None => f.write_str(value)?,
+                }
+                f.write_str("'")?;
+                if let Some(line) = line {
+                    write!(f, " at {}", Location(file, *line))?;
+                }
+                write!(f, " failed: {}", error)
+            }
+            ConfigError::HandlerExpansionFailed {
+                keyword,
+                value,
+                file,
+                line,
+                error,
+                ..
+            } => write!(
+                f,
+                "Handler call '{} = {}' at {} failed: {}",
+                keyword,
+                value,
+                Location(file, *line),
+                error
+            ),
             ConfigError::IoError { path, message } => {
                 write!(f, "I/O error for '{}': {}", path, message)
             }
+            ConfigError::ValidationError { key, value, reason } => {
+                write!(f, "Invalid value '{}' for '{}': {}", value, key, reason)
+            }
+            ConfigError::MissingPlaceholders { names } => {
+                write!(
+                    f,
+                    "No value provided for placeholder(s): {}",
+                    names.join(", ")
+                )
+            }
+            ConfigError::MissingProperties {
+                category,
+                key,
+                properties,
+                file,
+                line,
+            } => {
+                write!(f, "Special category '{}", category)?;
+                if let Some(k) = key {
+                    write!(f, "[{}]", k)?;
+                }
+                write!(
+                    f,
+                    "' at {} is missing required properties: {}",
+                    Location(file, *line),
+                    properties.join(", ")
+                )
+            }
+            ConfigError::ConstraintViolation {
+                key,
+                value,
+                reason,
+                file,
+                line,
+            } => write!(
+                f,
+                "Invalid value '{}' for '{}' at {}: {}",
+                value,
+                key,
+                Location(file, *line),
+                reason
+            ),
+            ConfigError::DuplicateCategory {
+                category,
+                file,
+                line,
+                first_file,
+                first_line,
+            } => write!(
+                f,
+                "Category '{}' at {} was already defined at {}",
+                category,
+                Location(file, *line),
+                Location(first_file, *first_line)
+            ),
             ConfigError::Custom { message } => {
                 write!(f, "{}", message)
             }
@@ -226,7 +557,89 @@ impl fmt::Display for ConfigError {
     }
 }
 
-impl std::error::Error for ConfigError {}
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::HandlerCallFailed { error, .. }
+            | ConfigError::HandlerExpansionFailed { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// An error that was ignored because it occurred under `# hyprlang noerror true`
+///
+/// See [`Config::suppressed_errors`](crate::Config::suppressed_errors).
+#[derive(Debug, Clone)]
+pub struct SuppressedError {
+    /// The error that would have been returned
+    pub error: ConfigError,
+
+    /// File the statement was read from (`None` for strings)
+    pub file: Option<PathBuf>,
+
+    /// 1-based line of the statement (`None` for blocks)
+    pub line: Option<usize>,
+}
+
+impl fmt::Display for SuppressedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        if let Some(line) = self.line {
+            write!(f, "{}:", line)?;
+        }
+        if self.file.is_some() || self.line.is_some() {
+            f.write_str(" ")?;
+        }
+        write!(f, "{}", self.error)
+    }
+}
+
+/// How serious a [`Diagnostic`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Severity {
+    /// Parsing the input would fail
+    Error,
+
+    /// The input parses, but uses something deprecated or unknown
+    Warning,
+}
+
+/// A problem found by [`Config::check`](crate::Config::check)
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+
+    /// What is wrong
+    pub message: String,
+
+    /// File the problem is in (`None` for strings)
+    pub file: Option<PathBuf>,
+
+    /// 1-based line of the problem, when known
+    pub line: Option<usize>,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        if let Some(line) = self.line {
+            write!(f, "{}:", line)?;
+        }
+        if self.file.is_some() || self.line.is_some() {
+            f.write_str(" ")?;
+        }
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(f, "{}: {}", severity, self.message)
+    }
+}
 
 impl From<std::io::Error> for ConfigError {
     fn from(err: std::io::Error) -> Self {
@@ -236,6 +649,7 @@ impl From<std::io::Error> for ConfigError {
     }
 }
 
+#[cfg(feature = "core-parse")]
 impl<R: pest::RuleType> From<pest::error::Error<R>> for ConfigError {
     fn from(err: pest::error::Error<R>) -> Self {
         let (line, column) = match err.line_col {
@@ -7,160 +7,398 @@
 //! - `\{{expr}}` → `"{{expr}}"` (backslash escape)
 //! - `{\{expr}}` → `"{{expr}}"` (brace escape)
 //! - `\\{{expr}}` → `"\<evaluated>"` (escaped backslash, expression evaluated)
+//! - `\$VAR` → `"$VAR"` (dollar escape, variable not expanded)
 //!
-//! Implementation: Escaped braces are replaced with placeholders during processing,
-//! then restored after expression evaluation.
+//! Implementation: Escaped braces and dollars are replaced with placeholders during
+//! processing, then restored after variable expansion and expression evaluation.
+//!
+//! [`escape_value`] goes the other way, turning text into a value that reads back as it.
 
 const ESCAPED_OPEN: &str = "\x00ESC_OPEN\x00";
 const ESCAPED_CLOSE: &str = "\x00ESC_CLOSE\x00";
+const ESCAPED_DOLLAR: &str = "\x00ESC_DOLLAR\x00";
+
+/// The markers of variables and expressions in values
+///
+/// Applications whose values already use `$` or `{{` can pick other markers through
+/// [`ConfigOptions::syntax`](crate::ConfigOptions::syntax), such as `@VAR` or `${VAR}`
+/// variables. Variable definitions and escapes follow the markers: with `@` as the prefix,
+/// `@GAPS = 5` defines a variable and `\@GAPS` is literal text. Markers must not be empty, and
+/// the `{\{` form of escaping an expression needs an opening delimiter of two or more
+/// characters.
+///
+/// ```rust
+/// use hyprlang::Syntax;
+///
+/// let syntax = Syntax {
+///     variable_prefix: "${".to_string(),
+///     variable_suffix: "}".to_string(),
+///     ..Syntax::default()
+/// };
+/// assert_eq!(syntax.variable("HOME"), "${HOME}");
+/// assert_eq!(syntax.escape_value("${HOME}").unwrap(), "\\${HOME}");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Syntax {
+    /// Text before a variable name (`$`)
+    pub variable_prefix: String,
+
+    /// Text after a variable name (none by default)
+    ///
+    /// With a suffix, `${A}bc` reads variable `A` followed by `bc`, instead of falling back
+    /// to the longest defined variable that prefixes `Abc`.
+    pub variable_suffix: String,
+
+    /// Text opening an expression (`{{`)
+    pub expression_open: String,
+
+    /// Text closing an expression (`}}`)
+    pub expression_close: String,
+}
+
+impl Default for Syntax {
+    fn default() -> Self {
+        Self {
+            variable_prefix: "$".to_string(),
+            variable_suffix: String::new(),
+            expression_open: "{{".to_string(),
+            expression_close: "}}".to_string(),
+        }
+    }
+}
+
+impl Syntax {
+    /// How a reference to the variable `name` is written, e.g. `$GAPS`
+    pub fn variable(&self, name: &str) -> String {
+        format!("{}{}{}", self.variable_prefix, name, self.variable_suffix)
+    }
+
+    /// Write text as a value that reads back as it under this syntax; see [`escape_value`]
+    pub fn escape_value(&self, text: &str) -> Option<String> {
+        escape_raw(text, self).map(|raw| escape_comments(&raw))
+    }
+
+    /// The variable referenced at the start of `text` and the length of the reference
+    ///
+    /// The name may be empty, as in a lone `$`. With a suffix, a reference missing it is
+    /// no reference.
+    pub(crate) fn variable_at<'a>(&self, text: &'a str) -> Option<(&'a str, usize)> {
+        let rest = strip(text, &self.variable_prefix)?;
+        let end = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        let length = self.variable_prefix.len() + end;
+        if self.variable_suffix.is_empty() {
+            return Some((&rest[..end], length));
+        }
+        strip(&rest[end..], &self.variable_suffix)
+            .map(|_| (&rest[..end], length + self.variable_suffix.len()))
+    }
+
+    /// Rewrite variable definitions written in this syntax, like `@GAPS = 5`, to the
+    /// `$GAPS = 5` the grammar reads
+    #[cfg(feature = "config")]
+    pub(crate) fn to_grammar<'a>(&self, input: &'a str) -> std::borrow::Cow<'a, str> {
+        use std::borrow::Cow;
+
+        if self.variable_prefix == "$" && self.variable_suffix.is_empty() {
+            return Cow::Borrowed(input);
+        }
+
+        let mut rewritten = String::with_capacity(input.len());
+        let mut changed = false;
+        for line in input.split_inclusive('\n') {
+            let body = line.trim_start_matches([' ', '\t']);
+            let definition = self.variable_at(body).filter(|(name, length)| {
+                !name.is_empty()
+                    && body[*length..]
+                        .trim_start_matches([' ', '\t'])
+                        .starts_with('=')
+            });
+            match definition {
+                Some((name, length)) => {
+                    rewritten.push_str(&line[..line.len() - body.len()]);
+                    rewritten.push('$');
+                    rewritten.push_str(name);
+                    rewritten.push_str(&body[length..]);
+                    changed = true;
+                }
+                None => rewritten.push_str(line),
+            }
+        }
+
+        if changed {
+            Cow::Owned(rewritten)
+        } else {
+            Cow::Borrowed(input)
+        }
+    }
+
+    /// The opening delimiter with a backslash after its first character, like `{\{`
+    fn split_open(&self) -> Option<String> {
+        let mut chars = self.expression_open.chars();
+        let first = chars.next()?;
+        let rest = chars.as_str();
+        (!rest.is_empty()).then(|| format!("{}\\{}", first, rest))
+    }
+}
+
+/// `text` after `marker`, treating an empty marker as never present
+fn strip<'a>(text: &'a str, marker: &str) -> Option<&'a str> {
+    if marker.is_empty() {
+        return None;
+    }
+    text.strip_prefix(marker)
+}
 
 /// Process escape sequences, replacing escaped braces with placeholders
 ///
 /// This prevents escaped expressions from being evaluated. After expression
 /// evaluation, call `restore_escaped_braces` to convert back to literal braces.
 pub fn process_escapes(input: &str) -> String {
+    process_escapes_with(input, &Syntax::default())
+}
+
+/// [`process_escapes`] for the markers of `syntax`
+pub(crate) fn process_escapes_with(input: &str, syntax: &Syntax) -> String {
+    let split_open = syntax.split_open();
     let mut result = String::with_capacity(input.len());
-    let mut chars = input.chars().peekable();
+    let mut rest = input;
 
-    while let Some(ch) = chars.next() {
-        match ch {
-            '\\' => {
-                if let Some(&next) = chars.peek() {
-                    if next == '{' {
-                        let mut temp = chars.clone();
-                        temp.next(); // consume {
-
-                        if temp.peek() == Some(&'{') {
-                            // \{{ - escape for {{
-                            chars.next(); // consume {
-                            chars.next(); // consume {
-                            result.push_str(ESCAPED_OPEN);
-
-                            // Find and escape the closing }}
-                            let mut depth = 1;
-                            while let Some(c) = chars.next() {
-                                if c == '{' && chars.peek() == Some(&'{') {
-                                    depth += 1;
-                                    result.push(c);
-                                    result.push(chars.next().unwrap());
-                                } else if c == '}' && chars.peek() == Some(&'}') {
-                                    depth -= 1;
-                                    if depth == 0 {
-                                        chars.next(); // consume second }
-                                        result.push_str(ESCAPED_CLOSE);
-                                        break;
-                                    }
-                                    result.push(c);
-                                    result.push(chars.next().unwrap());
-                                } else {
-                                    result.push(c);
-                                }
-                            }
-                            continue;
-                        } else if temp.peek() == Some(&'\\') {
-                            // \{\ - check for \{\{
-                            temp.next(); // consume \
-                            if temp.peek() == Some(&'{') {
-                                // \{\{ - escape for {{
-                                chars.next(); // consume {
-                                chars.next(); // consume \
-                                chars.next(); // consume {
-                                result.push_str(ESCAPED_OPEN);
-
-                                // Find and escape the closing }}
-                                let mut depth = 1;
-                                while let Some(c) = chars.next() {
-                                    if c == '{' && chars.peek() == Some(&'{') {
-                                        depth += 1;
-                                        result.push(c);
-                                        result.push(chars.next().unwrap());
-                                    } else if c == '}' && chars.peek() == Some(&'}') {
-                                        depth -= 1;
-                                        if depth == 0 {
-                                            chars.next(); // consume second }
-                                            result.push_str(ESCAPED_CLOSE);
-                                            break;
-                                        }
-                                        result.push(c);
-                                        result.push(chars.next().unwrap());
-                                    } else {
-                                        result.push(c);
-                                    }
-                                }
-                                continue;
-                            }
-                        }
-                    } else if next == '\\' {
-                        // Check for \\{{
-                        let mut temp = chars.clone();
-                        temp.next(); // consume \
-                        if temp.peek() == Some(&'{') {
-                            temp.next(); // consume {
-                            if temp.peek() == Some(&'{') {
-                                // \\{{ - keep one \, expression will be evaluated
-                                chars.next(); // consume second \
-                                result.push('\\');
-                                continue;
-                            }
-                        }
-                    }
-                }
-                result.push(ch);
+    while let Some(ch) = rest.chars().next() {
+        let after = &rest[ch.len_utf8()..];
+        if ch == '\\' {
+            if let Some(text) = strip(after, &syntax.variable_prefix) {
+                // \$ - a literal dollar
+                result.push_str(ESCAPED_DOLLAR);
+                rest = text;
+                continue;
             }
-
-            '{' => {
-                if let Some(&next) = chars.peek()
-                    && next == '\\'
-                {
-                    let mut temp = chars.clone();
-                    temp.next(); // consume \
-                    if temp.peek() == Some(&'{') {
-                        // {\{ - escape for {{
-                        chars.next(); // consume \
-                        chars.next(); // consume {
-                        result.push_str(ESCAPED_OPEN);
-
-                        // Find and escape the closing }}
-                        let mut depth = 1;
-                        while let Some(c) = chars.next() {
-                            if c == '{' && chars.peek() == Some(&'{') {
-                                depth += 1;
-                                result.push(c);
-                                result.push(chars.next().unwrap());
-                            } else if c == '}' && chars.peek() == Some(&'}') {
-                                depth -= 1;
-                                if depth == 0 {
-                                    chars.next(); // consume second }
-                                    result.push_str(ESCAPED_CLOSE);
-                                    break;
-                                }
-                                result.push(c);
-                                result.push(chars.next().unwrap());
-                            } else {
-                                result.push(c);
-                            }
-                        }
-                        continue;
-                    }
-                }
-                result.push(ch);
+            // \{{ or \{\{ - escape for {{
+            let escaped = strip(after, &syntax.expression_open)
+                .or_else(|| strip(after, split_open.as_deref()?));
+            if let Some(text) = escaped {
+                result.push_str(ESCAPED_OPEN);
+                rest = copy_escaped_body(text, &mut result, syntax);
+                continue;
             }
-
-            _ => result.push(ch),
+            // \\{{ - keep one \, expression will be evaluated
+            if let Some(text) = after.strip_prefix('\\')
+                && strip(text, &syntax.expression_open).is_some()
+            {
+                result.push('\\');
+                rest = text;
+                continue;
+            }
+        } else if let Some(text) = split_open.as_deref().and_then(|split| strip(rest, split)) {
+            // {\{ - escape for {{
+            result.push_str(ESCAPED_OPEN);
+            rest = copy_escaped_body(text, &mut result, syntax);
+            continue;
         }
+        result.push(ch);
+        rest = after;
     }
 
     result
 }
 
+/// Copy the body of an escaped expression up to its closing `}}`, which becomes a placeholder,
+/// and return the text after it
+///
+/// Plain `{{...}}` pairs inside the body are still evaluated, nested escapes (`\{{` or
+/// `{\{`) and dollar escapes stay literal, and an escaped backslash (`\\{{`) collapses to a
+/// single `\` just like at the top level.
+fn copy_escaped_body<'a>(text: &'a str, result: &mut String, syntax: &Syntax) -> &'a str {
+    let split_open = syntax.split_open();
+    let mut depth = 1;
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let after = &rest[c.len_utf8()..];
+        if c == '\\'
+            && let Some(text) = after.strip_prefix('\\')
+            && strip(text, &syntax.expression_open).is_some()
+        {
+            result.push(c);
+            rest = text;
+        } else if let Some(text) = strip(after, &syntax.variable_prefix).filter(|_| c == '\\') {
+            result.push_str(ESCAPED_DOLLAR);
+            rest = text;
+        } else if let Some(text) = strip(after, &syntax.expression_open)
+            .filter(|_| c == '\\')
+            .or_else(|| strip(rest, split_open.as_deref()?))
+        {
+            result.push_str(ESCAPED_OPEN);
+            rest = copy_escaped_body(text, result, syntax);
+        } else if let Some(text) = strip(rest, &syntax.expression_open) {
+            depth += 1;
+            result.push_str(&syntax.expression_open);
+            rest = text;
+        } else if let Some(text) = strip(rest, &syntax.expression_close) {
+            depth -= 1;
+            if depth == 0 {
+                result.push_str(ESCAPED_CLOSE);
+                return text;
+            }
+            result.push_str(&syntax.expression_close);
+            rest = text;
+        } else {
+            result.push(c);
+            rest = after;
+        }
+    }
+
+    rest
+}
+
 /// Restore escaped braces from placeholders to literal {{ and }}
 ///
 /// Call this after expression evaluation to convert placeholders back to
-/// the literal brace sequences.
+/// the literal brace sequences. Escaped dollars become `$` again.
 pub fn restore_escaped_braces(input: &str) -> String {
+    restore_escaped_with(input, &Syntax::default())
+}
+
+/// [`restore_escaped_braces`] for the markers of `syntax`
+pub(crate) fn restore_escaped_with(input: &str, syntax: &Syntax) -> String {
     input
-        .replace(ESCAPED_OPEN, "{{")
-        .replace(ESCAPED_CLOSE, "}}")
+        .replace(ESCAPED_OPEN, &syntax.expression_open)
+        .replace(ESCAPED_CLOSE, &syntax.expression_close)
+        .replace(ESCAPED_DOLLAR, &syntax.variable_prefix)
+}
+
+/// Write text as a config value that reads back exactly as the same text
+///
+/// Returns what goes after `key = `:
+/// - `{{` and `$VAR` are escaped, so nothing is evaluated or expanded
+/// - `#` is doubled so it does not start a comment
+/// - text with leading or trailing whitespace, or starting with a quote, is quoted
+///
+/// Text that reads as another type, like `5` or `true`, reads back as that type.
+/// Returns `None` for text no value can hold: line breaks, quoted text ending in a
+/// backslash, and a few backslash and brace combinations that collide with the escapes
+/// above, such as `{\{`.
+///
+/// # Example
+///
+/// ```
+/// use hyprlang::escape_value;
+///
+/// assert_eq!(escape_value("kitty").unwrap(), "kitty");
+/// assert_eq!(escape_value("#1e1e2e").unwrap(), "##1e1e2e");
+/// assert_eq!(escape_value("  padded ").unwrap(), "\"  padded \"");
+/// assert_eq!(escape_value("{{not math}}").unwrap(), "{\\{not math}}");
+/// assert!(escape_value("two\nlines").is_none());
+/// ```
+pub fn escape_value(text: &str) -> Option<String> {
+    Syntax::default().escape_value(text)
+}
+
+/// Like [`escape_value`], but without doubling `#`, the way parsed values keep their raw text
+pub(crate) fn escape_raw(text: &str, syntax: &Syntax) -> Option<String> {
+    let escaped = escape_expansions(text, syntax)?;
+    if text.trim() == text && !text.starts_with('"') {
+        return Some(escaped);
+    }
+
+    // A quote right before the closing one would read as an escaped quote
+    if escaped.ends_with('\\') {
+        return None;
+    }
+    Some(format!("\"{}\"", escaped.replace('"', "\\\"")))
+}
+
+/// Double each `#` of a raw unquoted value, as it is written in a config
+///
+/// A trailing backslash would continue the value on the next line, so an empty comment
+/// follows it.
+pub(crate) fn escape_comments(raw: &str) -> String {
+    if unquote(raw).is_some() {
+        return raw.to_string();
+    }
+    let mut written = raw.replace('#', "##");
+    if written.ends_with('\\') {
+        written.push_str(" #");
+    }
+    written
+}
+
+/// The text of a value written as a single quoted string, with `\"` read as `"`
+///
+/// Returns `None` for any other value, including ones that only contain quotes.
+pub(crate) fn unquote(value: &str) -> Option<String> {
+    let mut chars = value.strip_prefix('"')?.chars();
+    let mut text = String::new();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' if chars.as_str().starts_with('"') => {
+                chars.next();
+                text.push('"');
+            }
+            '"' => return chars.as_str().is_empty().then_some(text),
+            _ => text.push(ch),
+        }
+    }
+    None
+}
+
+/// Escape `{{` and `$VAR` in text so that processing the escapes gives the text back
+fn escape_expansions(text: &str, syntax: &Syntax) -> Option<String> {
+    let open = syntax.expression_open.as_str();
+    let escaped_open = syntax.split_open().unwrap_or_else(|| format!("\\{}", open));
+    let mut result = String::with_capacity(text.len());
+    // Escaped `{{` open a body that runs to the matching `}}`
+    let mut open_bodies = 0;
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let after_backslash = text[..text.len() - rest.len()].ends_with('\\');
+        if c == '\n' || c == '\r' {
+            return None;
+        } else if c == '\\' {
+            let run = rest.len() - rest.trim_start_matches('\\').len();
+            rest = &rest[run..];
+            if let Some(text) = strip(rest, open) {
+                // At the top level `\{\{` takes the last backslash of the run with it
+                let extra = usize::from(open_bodies == 0);
+                result.extend(std::iter::repeat_n('\\', run + extra));
+                result.push_str(&escaped_open);
+                open_bodies += 1;
+                rest = text;
+            } else {
+                result.extend(std::iter::repeat_n('\\', run));
+            }
+        } else if let Some(text) = strip(rest, open) {
+            result.push_str(&escaped_open);
+            open_bodies += 1;
+            rest = text;
+        } else if let Some(text) = strip(rest, &syntax.expression_close).filter(|_| open_bodies > 0)
+        {
+            result.push_str(&syntax.expression_close);
+            open_bodies -= 1;
+            rest = text;
+        } else if let Some(text) = strip(rest, &syntax.variable_prefix).filter(|text| {
+            after_backslash || text.starts_with(|c: char| c.is_alphanumeric() || c == '_')
+        }) {
+            result.push('\\');
+            result.push_str(&syntax.variable_prefix);
+            rest = text;
+        } else {
+            result.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    // Some combinations, such as `{\{`, have no escaped form
+    let processed = process_escapes_with(&result, syntax);
+    let expands = processed.contains(open)
+        || processed.char_indices().any(|(i, _)| {
+            syntax
+                .variable_at(&processed[i..])
+                .is_some_and(|(name, _)| !name.is_empty())
+        });
+    (!expands && restore_escaped_with(&processed, syntax) == text).then_some(result)
 }
 
 #[cfg(test)]
@@ -211,6 +449,70 @@ mod tests {
         assert!(restored.contains("{{"));
     }
 
+    #[test]
+    fn test_escapes_inside_escaped_expression() {
+        // Nested escapes stay literal
+        let input = r"{{8 - 10}} \{{ \{{50 + 50}} / \{{10 * 5}} }}";
+        let escaped = process_escapes(input);
+        assert!(escaped.starts_with("{{8 - 10}} "));
+        assert!(!escaped[11..].contains("{{"));
+
+        // An escaped backslash keeps the inner expression live
+        let escaped = process_escapes(r"{\{8\\{{10 + 3}}}}");
+        assert_eq!(
+            escaped,
+            format!("{ESCAPED_OPEN}8\\{{{{10 + 3}}}}{ESCAPED_CLOSE}")
+        );
+    }
+
+    #[test]
+    fn test_dollar_escape() {
+        let escaped = process_escapes(r"\$HOME and \{{ \$PATH }}");
+        assert!(!escaped.contains('$'));
+        assert_eq!(restore_escaped_braces(&escaped), "$HOME and {{ $PATH }}");
+    }
+
+    #[test]
+    fn test_escape_expansions_round_trip() {
+        for text in [
+            "plain",
+            "{{1 + 2}}",
+            "a {{b {{c}} d}} e }}",
+            r"\{{x}}",
+            r"\\{{x}}",
+            "$HOME/$",
+            r"\$HOME",
+            r"{ \{ } \",
+        ] {
+            let escaped = escape_expansions(text, &Syntax::default()).unwrap();
+            let processed = process_escapes(&escaped);
+            assert!(!processed.contains("{{"), "{text}");
+            assert_eq!(restore_escaped_braces(&processed), text);
+        }
+        assert!(escape_expansions(r"{\{x", &Syntax::default()).is_none());
+    }
+
+    #[test]
+    fn test_custom_syntax_escapes() {
+        let syntax = Syntax {
+            variable_prefix: "@".to_string(),
+            expression_open: "<%".to_string(),
+            expression_close: "%>".to_string(),
+            ..Syntax::default()
+        };
+        let escaped = process_escapes_with(r"\@HOME \<% 1 %> <\% 2 %> {{$x}}", &syntax);
+        assert_eq!(
+            restore_escaped_with(&escaped, &syntax),
+            "@HOME <% 1 %> <% 2 %> {{$x}}"
+        );
+
+        for text in ["@HOME", "<% 1 %>", r"\<%", "{{1}} $x"] {
+            let escaped = escape_expansions(text, &syntax).unwrap();
+            let processed = process_escapes_with(&escaped, &syntax);
+            assert_eq!(restore_escaped_with(&processed, &syntax), text);
+        }
+    }
+
     #[test]
     fn test_no_escape() {
         let input = "{{10 + 5}}";
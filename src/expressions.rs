@@ -2,6 +2,7 @@ use crate::error::{ConfigError, ParseResult};
 use std::collections::HashMap;
 
 /// Expression evaluator for arithmetic expressions
+#[derive(Clone)]
 pub struct ExpressionEvaluator {
     variables: HashMap<String, i64>,
 }
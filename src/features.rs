@@ -1,14 +1,55 @@
 use crate::error::{ConfigError, ParseResult};
 use crate::variables::VariableManager;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+
+/// An open `# hyprlang if` condition and whether it held
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveCondition {
+    /// The condition as written after `if`, e.g. `env:HOSTNAME == laptop`
+    pub condition: String,
+
+    /// Whether the condition held, so the statements after it are executed
+    pub value: bool,
+}
+
+/// Where parsing stood with its `# hyprlang` directives
+///
+/// Returned by [`Config::directive_state`](crate::Config::directive_state) for the parse in
+/// progress, and recorded at the end of each parsed file in
+/// [`Config::file_directive_states`](crate::Config::file_directive_states).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DirectiveState {
+    /// The parsed file (`None` for strings passed to [`Config::parse`](crate::Config::parse))
+    pub file: Option<PathBuf>,
+
+    /// Open conditions, outermost first
+    pub conditions: Vec<ActiveCondition>,
+
+    /// Statements skipped inside false conditions, counting those inside blocks
+    pub suppressed_statements: usize,
+
+    /// Whether `# hyprlang noerror true` is in effect
+    pub noerror: bool,
+}
+
+impl DirectiveState {
+    /// Whether parsing is inside a false condition, where statements are skipped
+    pub fn is_inactive(&self) -> bool {
+        self.conditions.iter().any(|condition| !condition.value)
+    }
+}
 
 /// Directive processor for handling comment directives
+#[derive(Clone)]
 pub struct DirectiveProcessor {
     /// Stack of active if conditions
-    if_stack: Vec<bool>,
+    if_stack: Vec<ActiveCondition>,
 
     /// Whether to suppress errors
     suppress_errors: bool,
+
+    /// Statements skipped because a condition was false
+    suppressed: usize,
 }
 
 impl DirectiveProcessor {
@@ -16,6 +57,7 @@ impl DirectiveProcessor {
         Self {
             if_stack: Vec::new(),
             suppress_errors: false,
+            suppressed: 0,
         }
     }
 
@@ -28,23 +70,15 @@ impl DirectiveProcessor {
     ) -> ParseResult<()> {
         match directive_type {
             "if" => {
-                let var_name = args.ok_or_else(|| {
+                let condition = args.ok_or_else(|| {
                     ConfigError::custom("'if' directive requires a variable name")
                 })?;
 
-                let var_name = var_name.trim();
-
-                // Check for negation (!)
-                let (negated, var_name) = if let Some(stripped) = var_name.strip_prefix('!') {
-                    (true, stripped.trim())
-                } else {
-                    (false, var_name)
-                };
-
-                let condition = variables.contains(var_name);
-                let final_condition = if negated { !condition } else { condition };
-
-                self.if_stack.push(final_condition);
+                let value = evaluate_condition(condition, variables)?;
+                self.if_stack.push(ActiveCondition {
+                    condition: condition.trim().to_string(),
+                    value,
+                });
                 Ok(())
             }
 
@@ -76,11 +110,25 @@ impl DirectiveProcessor {
     /// Check if current code should be executed (based on if conditions)
     pub fn should_execute(&self) -> bool {
         // Execute if all conditions in the stack are true (or stack is empty)
-        self.if_stack.iter().all(|&cond| cond)
+        self.if_stack.iter().all(|condition| condition.value)
+    }
+
+    /// Count `count` statements as skipped by a false condition
+    pub fn suppress(&mut self, count: usize) {
+        self.suppressed += count;
+    }
+
+    /// The current state, for inspection
+    pub fn state(&self, file: Option<PathBuf>) -> DirectiveState {
+        DirectiveState {
+            file,
+            conditions: self.if_stack.clone(),
+            suppressed_statements: self.suppressed,
+            noerror: self.suppress_errors,
+        }
     }
 
     /// Check if errors should be suppressed
-    #[allow(dead_code)]
     pub fn should_suppress_errors(&self) -> bool {
         self.suppress_errors
     }
@@ -89,6 +137,7 @@ impl DirectiveProcessor {
     pub fn reset(&mut self) {
         self.if_stack.clear();
         self.suppress_errors = false;
+        self.suppressed = 0;
     }
 
     /// Check if there are unclosed if blocks
@@ -98,13 +147,136 @@ impl DirectiveProcessor {
     }
 }
 
+/// Evaluate the condition of an `if` directive
+///
+/// Supported forms are `VAR`, `env:VAR`, and either of them compared against a literal
+/// with `==` or `!=` (e.g. `env:HOSTNAME == laptop`). A leading `!` negates the result.
+/// A variable that is not set never equals a literal.
+fn evaluate_condition(condition: &str, variables: &VariableManager) -> ParseResult<bool> {
+    let (negated, operand, comparison) = split_condition(condition);
+
+    let value = match operand.strip_prefix("env:") {
+        Some(name) if !name.trim().is_empty() => variables.env(name.trim()),
+        None if !operand.is_empty() => variables.get(operand).map(String::from),
+        _ => {
+            return Err(ConfigError::custom(
+                "'if' directive requires a variable name",
+            ));
+        }
+    };
+
+    let result = match comparison {
+        Some((equal, literal)) => {
+            let literal = literal
+                .strip_prefix('"')
+                .and_then(|inner| inner.strip_suffix('"'))
+                .unwrap_or(literal);
+            (value.as_deref() == Some(literal)) == equal
+        }
+        None => value.is_some(),
+    };
+
+    Ok(result != negated)
+}
+
+/// Split a condition into its negation, operand and `==`/`!=` comparison
+fn split_condition(condition: &str) -> (bool, &str, Option<(bool, &str)>) {
+    let condition = condition.trim();
+
+    // Check for negation (!)
+    let (negated, condition) = match condition.strip_prefix('!') {
+        Some(stripped) => (true, stripped.trim()),
+        None => (false, condition),
+    };
+
+    let (operand, comparison) = if let Some((operand, literal)) = condition.split_once("!=") {
+        (operand.trim(), Some((false, literal.trim())))
+    } else if let Some((operand, literal)) = condition.split_once("==") {
+        (operand.trim(), Some((true, literal.trim())))
+    } else {
+        (condition, None)
+    };
+    (negated, operand, comparison)
+}
+
+/// The config variable an `if` condition reads, if it reads one rather than the environment
+pub(crate) fn condition_variable(condition: &str) -> Option<&str> {
+    let (_, operand, _) = split_condition(condition);
+    (!operand.is_empty() && !operand.starts_with("env:")).then_some(operand)
+}
+
 impl Default for DirectiveProcessor {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Turn the path written in a `source =` line into a path for this platform
+///
+/// Backslashes separate components on every platform, so a config written on Windows
+/// resolves on Linux and macOS too. Drive letters (`C:\`) and UNC shares (`\\server\share`)
+/// only name a location on Windows; elsewhere they are an error.
+pub(crate) fn source_path(path: &str) -> Result<PathBuf, String> {
+    if cfg!(windows) {
+        return Ok(PathBuf::from(path));
+    }
+
+    let bytes = path.as_bytes();
+    let drive = bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':';
+    if drive || path.starts_with("\\\\") {
+        return Err("drive letters and UNC paths are only supported on Windows".to_string());
+    }
+    Ok(PathBuf::from(path.replace('\\', "/")))
+}
+
+/// Canonicalize a path, keeping the `C:\` or `\\server\share` form on Windows
+///
+/// [`Path::canonicalize`] returns verbatim paths (`\\?\C:\...`) there, which neither match
+/// the paths users pass in nor read well in messages.
+pub(crate) fn canonical_path(path: &Path) -> std::io::Result<PathBuf> {
+    let canonical = path.canonicalize()?;
+
+    #[cfg(windows)]
+    {
+        use std::path::Prefix;
+
+        let mut components = canonical.components();
+        if let Some(Component::Prefix(prefix)) = components.next() {
+            let simple = match prefix.kind() {
+                Prefix::VerbatimDisk(drive) => format!("{}:\\", drive as char),
+                Prefix::VerbatimUNC(server, share) => format!(
+                    "\\\\{}\\{}\\",
+                    server.to_string_lossy(),
+                    share.to_string_lossy()
+                ),
+                _ => return Ok(canonical),
+            };
+            let mut simplified = PathBuf::from(simple);
+            simplified.extend(components.filter(|c| !matches!(c, Component::RootDir)));
+            return Ok(simplified);
+        }
+    }
+
+    Ok(canonical)
+}
+
+/// Make a path absolute and drop its `.` and `..` components without resolving symlinks
+pub(crate) fn absolute_path(path: &Path) -> std::io::Result<PathBuf> {
+    let mut normalized = PathBuf::new();
+    for component in std::path::absolute(path)?.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    Ok(normalized)
+}
+
 /// Source file resolver for handling source directives
+#[derive(Clone)]
 pub struct SourceResolver {
     /// Base directory for resolving relative paths
     base_dir: PathBuf,
@@ -114,6 +286,9 @@ pub struct SourceResolver {
 
     /// Maximum recursion depth
     max_depth: usize,
+
+    /// Whether resolved paths have their symlinks resolved too
+    canonicalize: bool,
 }
 
 impl SourceResolver {
@@ -122,9 +297,16 @@ impl SourceResolver {
             base_dir: base_dir.as_ref().to_path_buf(),
             loading_stack: Vec::new(),
             max_depth: 50,
+            canonicalize: true,
         }
     }
 
+    /// Keep symlinks in resolved paths, which are then only made absolute
+    pub fn with_canonicalize(mut self, canonicalize: bool) -> Self {
+        self.canonicalize = canonicalize;
+        self
+    }
+
     /// Set the maximum recursion depth
     #[allow(dead_code)]
     pub fn with_max_depth(mut self, max_depth: usize) -> Self {
@@ -134,18 +316,23 @@ impl SourceResolver {
 
     /// Resolve a source path relative to the base directory
     pub fn resolve_path(&self, path: &str) -> ParseResult<PathBuf> {
-        let path_obj = Path::new(path);
+        let path_obj = source_path(path).map_err(|reason| ConfigError::io(path, reason))?;
 
         let resolved = if path_obj.is_absolute() {
-            path_obj.to_path_buf()
+            path_obj
         } else {
             self.base_dir.join(path_obj)
         };
 
+        let error =
+            |e: std::io::Error| ConfigError::io(path, format!("failed to resolve path: {}", e));
+        if !self.canonicalize {
+            resolved.metadata().map_err(error)?;
+            return absolute_path(&resolved).map_err(error);
+        }
+
         // Canonicalize to resolve . and .. components
-        resolved
-            .canonicalize()
-            .map_err(|e| ConfigError::io(path, format!("failed to resolve path: {}", e)))
+        canonical_path(&resolved).map_err(error)
     }
 
     /// Begin loading a file (checks for cycles and depth)
@@ -247,6 +434,32 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn test_directive_if_env() {
+        let mut processor = DirectiveProcessor::new();
+        let mut variables = VariableManager::new();
+        variables.set_env_provider(|name| (name == "HOSTNAME").then(|| "laptop".to_string()));
+
+        for (condition, expected) in [
+            ("env:HOSTNAME", true),
+            ("env:MISSING", false),
+            ("!env:MISSING", true),
+            ("env:HOSTNAME == laptop", true),
+            ("env:HOSTNAME==desktop", false),
+            ("env:HOSTNAME != desktop", true),
+            ("env:MISSING != laptop", true),
+            ("!env:HOSTNAME == laptop", false),
+        ] {
+            processor
+                .process_directive("if", Some(condition), &variables)
+                .unwrap();
+            assert_eq!(processor.should_execute(), expected, "{}", condition);
+            processor
+                .process_directive("endif", None, &variables)
+                .unwrap();
+        }
+    }
+
     #[test]
     fn test_directive_noerror() {
         let mut processor = DirectiveProcessor::new();
@@ -289,4 +502,59 @@ mod tests {
         assert_eq!(MultilineProcessor::remove_backslash("line\\  "), "line");
         assert_eq!(MultilineProcessor::remove_backslash("line"), "line");
     }
+
+    #[test]
+    fn test_source_path_separators() {
+        let expected: PathBuf = ["sub", "dir", "file.conf"].iter().collect();
+        assert_eq!(source_path("sub/dir/file.conf").unwrap(), expected);
+        assert_eq!(
+            source_path("sub\\dir\\file.conf")
+                .unwrap()
+                .components()
+                .count(),
+            3
+        );
+        assert_eq!(
+            source_path("sub/dir\\file.conf")
+                .unwrap()
+                .components()
+                .count(),
+            3
+        );
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_source_path_windows_only_forms() {
+        assert!(source_path("C:\\Users\\me\\hypr.conf").is_err());
+        assert!(source_path("c:/hypr.conf").is_err());
+        assert!(source_path("\\\\server\\share\\hypr.conf").is_err());
+        assert_eq!(
+            source_path("\\hypr.conf").unwrap(),
+            PathBuf::from("/hypr.conf")
+        );
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_source_path_windows_forms() {
+        assert!(
+            source_path("C:\\Users\\me\\hypr.conf")
+                .unwrap()
+                .is_absolute()
+        );
+        assert!(source_path("c:/hypr.conf").unwrap().is_absolute());
+        assert!(
+            source_path("\\\\server\\share\\hypr.conf")
+                .unwrap()
+                .is_absolute()
+        );
+    }
+
+    #[test]
+    fn test_canonical_path_is_not_verbatim() {
+        let canonical = canonical_path(Path::new(".")).unwrap();
+        assert!(canonical.is_absolute());
+        assert!(!canonical.to_string_lossy().starts_with(r"\\?\"));
+    }
 }
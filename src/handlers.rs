@@ -1,10 +1,17 @@
 use crate::error::{ConfigError, ParseResult};
+use crate::types::ConfigValueEntry;
+use std::any::Any;
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::rc::Rc;
 
 /// Type alias for handler functions
 type HandlerFn = Rc<dyn Fn(&HandlerContext) -> ParseResult<()>>;
 
+/// Type alias for handler functions that receive the config's handler state
+type StatefulHandlerFn = Rc<dyn Fn(&HandlerContext, &mut dyn Any) -> ParseResult<()>>;
+
 /// Context for handler execution
 pub struct HandlerContext {
     /// The category path where this handler is being called
@@ -18,6 +25,18 @@ pub struct HandlerContext {
 
     /// Optional flags (e.g., "flagsabc" from "keywordflagsabc = value")
     pub flags: Option<String>,
+
+    /// Whether the call is only being validated; handlers should check the value and
+    /// return errors as usual, but skip side effects such as spawning processes
+    ///
+    /// Set under [`ConfigOptions::dry_run_handlers`](crate::ConfigOptions::dry_run_handlers).
+    pub dry_run: bool,
+
+    /// File the call is in (`None` for strings and contexts built by hand)
+    pub file: Option<PathBuf>,
+
+    /// 1-based line number of the call (`None` for contexts built by hand)
+    pub line: Option<usize>,
 }
 
 impl HandlerContext {
@@ -27,6 +46,9 @@ impl HandlerContext {
             keyword,
             value,
             flags: None,
+            dry_run: false,
+            file: None,
+            line: None,
         }
     }
 
@@ -40,10 +62,188 @@ impl HandlerContext {
         self
     }
 
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Set where the call was written, for handlers reporting problems with it
+    pub fn with_location(mut self, file: Option<PathBuf>, line: usize) -> Self {
+        self.file = file;
+        self.line = Some(line);
+        self
+    }
+
     /// Get the full category path as a string
     pub fn category_path(&self) -> String {
         self.category.join(":")
     }
+
+    /// The value split into comma-separated arguments
+    ///
+    /// Commas inside quotes don't split, so `exec = sh -c "a, b"` is one argument. Arguments
+    /// are trimmed and keep their quotes; see [`split_args`](crate::util::split_args).
+    pub fn args(&self) -> Vec<String> {
+        crate::util::split_args(&self.value)
+    }
+}
+
+/// A stored handler call together with where it came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandlerCallRecord {
+    /// The value passed to the handler (after variable expansion)
+    pub value: String,
+
+    /// Optional flags (e.g., "flagsabc" from "keywordflagsabc = value")
+    pub flags: Option<String>,
+
+    /// File the call was parsed from (`None` for strings or calls added at runtime)
+    pub file: Option<PathBuf>,
+
+    /// 1-based line number within `file` (`None` for calls added at runtime)
+    pub line: Option<usize>,
+
+    /// Whether the call was parsed under `# hyprlang noerror true`
+    pub noerror: bool,
+
+    /// The alias the call was written with, when it used one (see
+    /// [`Config::register_handler_alias`](crate::Config::register_handler_alias))
+    pub alias: Option<String>,
+
+    /// Position of the call in parse order across all handlers and files
+    ///
+    /// Calls inserted with [`Config::insert_handler_call`](crate::Config::insert_handler_call)
+    /// take the position they will have when the saved files are parsed again.
+    pub sequence: u64,
+}
+
+/// An assignment or a handler call, as listed by
+/// [`Config::unified_entries`](crate::Config::unified_entries)
+#[derive(Debug, Clone, Copy)]
+pub enum UnifiedEntry<'a> {
+    /// A value stored under `key`
+    Value {
+        key: &'a str,
+        entry: &'a ConfigValueEntry,
+    },
+
+    /// The call at `index` among the calls of `handler`
+    HandlerCall {
+        handler: &'a str,
+        index: usize,
+        record: &'a HandlerCallRecord,
+    },
+}
+
+impl UnifiedEntry<'_> {
+    /// The full key of a value, or the handler name of a call, e.g. `animations:bezier`
+    pub fn key(&self) -> &str {
+        match self {
+            UnifiedEntry::Value { key, .. } => key,
+            UnifiedEntry::HandlerCall { handler, .. } => handler,
+        }
+    }
+
+    /// The value as text; handler calls give the value passed to the handler
+    pub fn text(&self) -> Cow<'_, str> {
+        match self {
+            UnifiedEntry::Value { entry, .. } => entry.text(),
+            UnifiedEntry::HandlerCall { record, .. } => Cow::Borrowed(&record.value),
+        }
+    }
+
+    /// Whether this is a handler call
+    pub fn is_handler_call(&self) -> bool {
+        matches!(self, UnifiedEntry::HandlerCall { .. })
+    }
+
+    /// 1-based line the entry was parsed from, if it was parsed
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            UnifiedEntry::Value { entry, .. } => entry.line,
+            UnifiedEntry::HandlerCall { record, .. } => record.line,
+        }
+    }
+}
+
+/// A `keyword = value` line that reached no registered handler
+///
+/// See [`Config::unknown_handlers`](crate::Config::unknown_handlers).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownHandlerCall {
+    /// The value as written
+    pub value: String,
+
+    /// File the line was read from (`None` for strings)
+    pub file: Option<PathBuf>,
+
+    /// 1-based line number
+    pub line: usize,
+}
+
+/// A handler call written with a deprecated alias of its keyword
+///
+/// See [`Config::deprecations`](crate::Config::deprecations).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandlerDeprecation {
+    /// The keyword as written
+    pub alias: String,
+
+    /// The keyword the call was stored under
+    pub canonical: String,
+
+    /// File the call was read from (`None` for strings)
+    pub file: Option<PathBuf>,
+
+    /// 1-based line of the call
+    pub line: usize,
+}
+
+impl std::fmt::Display for HandlerDeprecation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        write!(
+            f,
+            "{}: '{}' is deprecated, use '{}' instead",
+            self.line, self.alias, self.canonical
+        )
+    }
+}
+
+/// A handler call left out because its handler rejected it
+///
+/// See [`Config::rejected_handler_calls`](crate::Config::rejected_handler_calls).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RejectedHandlerCall {
+    /// The handler the call would have been stored under, e.g. `bind` or `plugin:load`
+    pub handler: String,
+
+    /// The value passed to the handler (after variable expansion)
+    pub value: String,
+
+    /// The reason the handler gave, see [`ConfigError::rejected`]
+    pub reason: String,
+
+    /// File the call was read from (`None` for strings)
+    pub file: Option<PathBuf>,
+
+    /// 1-based line of the call
+    pub line: usize,
+}
+
+impl std::fmt::Display for RejectedHandlerCall {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        write!(
+            f,
+            "{}: '{}' call rejected: {}",
+            self.line, self.handler, self.reason
+        )
+    }
 }
 
 /// Trait for implementing custom keyword handlers
@@ -51,6 +251,19 @@ pub trait Handler: std::fmt::Debug {
     /// Handle a keyword with the given context
     fn handle(&self, context: &HandlerContext) -> ParseResult<()>;
 
+    /// Handle a keyword with access to the state set by
+    /// [`Config::set_handler_state`](crate::Config::set_handler_state), if any
+    ///
+    /// The default implementation ignores the state and calls [`handle`](Handler::handle).
+    fn handle_with_state(
+        &self,
+        context: &HandlerContext,
+        state: Option<&mut dyn Any>,
+    ) -> ParseResult<()> {
+        let _ = state;
+        self.handle(context)
+    }
+
     /// Get the handler name
     fn name(&self) -> &str;
 
@@ -60,12 +273,18 @@ pub trait Handler: std::fmt::Debug {
     }
 }
 
+#[derive(Clone)]
+enum HandlerFunction {
+    Plain(HandlerFn),
+    Stateful(StatefulHandlerFn),
+}
+
 /// Function-based handler wrapper
 #[derive(Clone)]
 pub struct FunctionHandler {
     name: String,
     accepts_flags: bool,
-    handler: HandlerFn,
+    handler: HandlerFunction,
 }
 
 impl FunctionHandler {
@@ -76,7 +295,7 @@ impl FunctionHandler {
         Self {
             name: name.into(),
             accepts_flags: false,
-            handler: Rc::new(handler),
+            handler: HandlerFunction::Plain(Rc::new(handler)),
         }
     }
 
@@ -87,14 +306,72 @@ impl FunctionHandler {
         Self {
             name: name.into(),
             accepts_flags: true,
-            handler: Rc::new(handler),
+            handler: HandlerFunction::Plain(Rc::new(handler)),
+        }
+    }
+
+    /// Create a handler that receives the state set with
+    /// [`Config::set_handler_state`](crate::Config::set_handler_state)
+    ///
+    /// Running it fails with a handler error if no state is set.
+    pub fn stateful<F>(name: impl Into<String>, handler: F) -> Self
+    where
+        F: Fn(&HandlerContext, &mut dyn Any) -> ParseResult<()> + 'static,
+    {
+        Self {
+            name: name.into(),
+            accepts_flags: false,
+            handler: HandlerFunction::Stateful(Rc::new(handler)),
+        }
+    }
+
+    /// Create a stateful handler that accepts flags
+    pub fn stateful_with_flags<F>(name: impl Into<String>, handler: F) -> Self
+    where
+        F: Fn(&HandlerContext, &mut dyn Any) -> ParseResult<()> + 'static,
+    {
+        Self {
+            accepts_flags: true,
+            ..Self::stateful(name, handler)
         }
     }
 }
 
+/// Adapt a handler taking `&mut T` to one taking the type-erased handler state
+pub(crate) fn downcast_state<T, F>(
+    handler: F,
+) -> impl Fn(&HandlerContext, &mut dyn Any) -> ParseResult<()> + 'static
+where
+    T: Any,
+    F: Fn(&HandlerContext, &mut T) -> ParseResult<()> + 'static,
+{
+    move |context, state| match state.downcast_mut::<T>() {
+        Some(state) => handler(context, state),
+        None => Err(ConfigError::handler(
+            &context.keyword,
+            format!("handler state is not a {}", std::any::type_name::<T>()),
+        )),
+    }
+}
+
 impl Handler for FunctionHandler {
     fn handle(&self, context: &HandlerContext) -> ParseResult<()> {
-        (self.handler)(context)
+        self.handle_with_state(context, None)
+    }
+
+    fn handle_with_state(
+        &self,
+        context: &HandlerContext,
+        state: Option<&mut dyn Any>,
+    ) -> ParseResult<()> {
+        match (&self.handler, state) {
+            (HandlerFunction::Plain(handler), _) => handler(context),
+            (HandlerFunction::Stateful(handler), Some(state)) => handler(context, state),
+            (HandlerFunction::Stateful(_), None) => Err(ConfigError::handler(
+                &self.name,
+                "no handler state set; call Config::set_handler_state first",
+            )),
+        }
     }
 
     fn name(&self) -> &str {
@@ -125,12 +402,18 @@ pub enum HandlerScope {
 }
 
 /// Manager for keyword handlers
+///
+/// Cloning shares the registered handlers rather than copying them.
+#[derive(Clone)]
 pub struct HandlerManager {
     /// Global handlers
-    global_handlers: HashMap<String, Box<dyn Handler>>,
+    global_handlers: HashMap<String, Rc<dyn Handler>>,
 
     /// Category-scoped handlers: category_path -> keyword -> handler
-    category_handlers: HashMap<String, HashMap<String, Box<dyn Handler>>>,
+    category_handlers: HashMap<String, HashMap<String, Rc<dyn Handler>>>,
+
+    /// Deprecated keywords: alias -> canonical keyword
+    aliases: HashMap<String, String>,
 }
 
 impl HandlerManager {
@@ -138,6 +421,7 @@ impl HandlerManager {
         Self {
             global_handlers: HashMap::new(),
             category_handlers: HashMap::new(),
+            aliases: HashMap::new(),
         }
     }
 
@@ -147,7 +431,7 @@ impl HandlerManager {
         H: Handler + 'static,
     {
         self.global_handlers
-            .insert(keyword.into(), Box::new(handler));
+            .insert(keyword.into(), Rc::new(handler));
     }
 
     /// Register a category-scoped handler
@@ -162,7 +446,22 @@ impl HandlerManager {
         self.category_handlers
             .entry(category.into())
             .or_default()
-            .insert(keyword.into(), Box::new(handler));
+            .insert(keyword.into(), Rc::new(handler));
+    }
+
+    /// Register `alias` as a deprecated name for the handler keyword `canonical`
+    pub fn register_alias(&mut self, alias: impl Into<String>, canonical: impl Into<String>) {
+        self.aliases.insert(alias.into(), canonical.into());
+    }
+
+    /// The canonical keyword for `keyword`, if it is a registered alias
+    pub fn resolve_alias(&self, keyword: &str) -> Option<&str> {
+        self.aliases.get(keyword).map(|s| s.as_str())
+    }
+
+    /// All registered aliases: alias -> canonical keyword
+    pub fn aliases(&self) -> &HashMap<String, String> {
+        &self.aliases
     }
 
     /// Find a handler for a keyword in a given category
@@ -181,6 +480,22 @@ impl HandlerManager {
         self.global_handlers.get(keyword).map(|h| h.as_ref())
     }
 
+    /// Split a keyword like `flagsabc` into a flag-accepting handler keyword and its flags
+    ///
+    /// Returns the longest registered keyword that prefixes `keyword` and accepts flags,
+    /// together with the remaining characters as flags. Exact matches are not split.
+    pub fn split_flags(&self, category_path: &[String], keyword: &str) -> Option<(String, String)> {
+        (1..keyword.len())
+            .rev()
+            .filter(|&end| keyword.is_char_boundary(end))
+            .find_map(|end| {
+                let (name, flags) = keyword.split_at(end);
+                self.find_handler(category_path, name)
+                    .filter(|handler| handler.accepts_flags())
+                    .map(|_| (name.to_string(), flags.to_string()))
+            })
+    }
+
     /// Check if a handler exists for a keyword
     pub fn has_handler(&self, category_path: &[String], keyword: &str) -> bool {
         self.find_handler(category_path, keyword).is_some()
@@ -194,29 +509,89 @@ impl HandlerManager {
         value: &str,
         flags: Option<String>,
     ) -> ParseResult<()> {
+        self.execute_with_state(category_path, keyword, value, flags, None)
+    }
+
+    /// Check that a call would reach a handler that accepts it, without running it
+    pub fn check(
+        &self,
+        category_path: &[String],
+        keyword: &str,
+        has_flags: bool,
+    ) -> ParseResult<()> {
+        self.checked_handler(category_path, keyword, has_flags)
+            .map(|_| ())
+    }
+
+    fn checked_handler(
+        &self,
+        category_path: &[String],
+        keyword: &str,
+        has_flags: bool,
+    ) -> ParseResult<&dyn Handler> {
         let handler = self
             .find_handler(category_path, keyword)
             .ok_or_else(|| ConfigError::handler(keyword, "handler not found"))?;
 
         // Check if flags are provided but not accepted
-        if flags.is_some() && !handler.accepts_flags() {
+        if has_flags && !handler.accepts_flags() {
             return Err(ConfigError::handler(
                 keyword,
                 "handler does not accept flags",
             ));
         }
+        Ok(handler)
+    }
 
+    /// Execute a handler, passing it the given handler state
+    ///
+    /// An error returned by the handler is wrapped in [`ConfigError::HandlerCallFailed`].
+    pub fn execute_with_state(
+        &self,
+        category_path: &[String],
+        keyword: &str,
+        value: &str,
+        flags: Option<String>,
+        state: Option<&mut dyn Any>,
+    ) -> ParseResult<()> {
         let context = HandlerContext::new(keyword.to_string(), value.to_string())
             .with_category(category_path.to_vec())
             .with_flags(flags.unwrap_or_default());
+        self.execute_context(context, state)
+    }
 
-        handler.handle(&context)
+    /// Execute the handler for a prepared context, e.g. one marked as a dry run
+    ///
+    /// The handler is looked up by the context's category and keyword. Empty flags count
+    /// as no flags.
+    pub fn execute_context(
+        &self,
+        context: HandlerContext,
+        state: Option<&mut dyn Any>,
+    ) -> ParseResult<()> {
+        let has_flags = context
+            .flags
+            .as_deref()
+            .is_some_and(|flags| !flags.is_empty());
+        let handler = self.checked_handler(&context.category, &context.keyword, has_flags)?;
+
+        handler
+            .handle_with_state(&context, state)
+            .map_err(|error| ConfigError::HandlerCallFailed {
+                keyword: context.keyword.clone(),
+                value: context.value.clone(),
+                category: context.category.clone(),
+                file: None,
+                line: None,
+                error: Box::new(error),
+            })
     }
 
     /// Clear all handlers
     pub fn clear(&mut self) {
         self.global_handlers.clear();
         self.category_handlers.clear();
+        self.aliases.clear();
     }
 
     /// Get all registered global handler keywords
@@ -239,6 +614,19 @@ impl Default for HandlerManager {
     }
 }
 
+/// Application state passed to stateful handlers
+///
+/// The state is owned by the config but not copied when the config is cloned, since
+/// arbitrary state can't be cloned; clones start without one.
+#[derive(Default)]
+pub(crate) struct HandlerState(pub(crate) Option<Box<dyn Any>>);
+
+impl Clone for HandlerState {
+    fn clone(&self) -> Self {
+        Self(None)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -273,6 +661,46 @@ mod tests {
         manager
             .execute(&[], "flagged", "value", Some("abc".to_string()))
             .unwrap();
+
+        assert_eq!(
+            manager.split_flags(&[], "flaggedabc"),
+            Some(("flagged".to_string(), "abc".to_string()))
+        );
+        assert_eq!(manager.split_flags(&[], "flagged"), None);
+        assert_eq!(manager.split_flags(&[], "other"), None);
+    }
+
+    #[test]
+    fn test_stateful_handler() {
+        let mut manager = HandlerManager::new();
+        manager.register_global(
+            "count",
+            FunctionHandler::stateful(
+                "count",
+                downcast_state(|_, count: &mut usize| {
+                    *count += 1;
+                    Ok(())
+                }),
+            ),
+        );
+
+        let mut count = 0usize;
+        manager
+            .execute_with_state(&[], "count", "value", None, Some(&mut count))
+            .unwrap();
+        manager
+            .execute_with_state(&[], "count", "value", None, Some(&mut count))
+            .unwrap();
+        assert_eq!(count, 2);
+
+        // Missing or mismatched state is an error rather than a panic
+        assert!(manager.execute(&[], "count", "value", None).is_err());
+        let mut wrong = String::new();
+        assert!(
+            manager
+                .execute_with_state(&[], "count", "value", None, Some(&mut wrong))
+                .is_err()
+        );
     }
 
     #[test]
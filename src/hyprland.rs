@@ -228,7 +228,10 @@
 //! **Root-level handlers:**
 //! - `monitor` - Monitor configuration
 //! - `env` - Environment variables
-//! - `bind`, `bindm`, `bindel`, `bindl`, `bindr`, `binde`, `bindn` - Keybindings
+//! - `bind` with any flag suffix (`bindl`, `bindm`, `bindel`, `bindu`, ...) - Keybindings,
+//!   recorded as `bind` calls with the suffix as flags (see [`Keybind`]). The variants have
+//!   no handler calls of their own: use [`Hyprland::binds_with_flags`] instead of
+//!   `get_handler_calls("bindl")`
 //! - `windowrule`, `windowrulev2` - Window rules
 //! - `layerrule` - Layer rules
 //! - `workspace` - Workspace configuration
@@ -356,12 +359,18 @@
 //!
 //! [`Config`]: crate::Config
 
+use crate::builder::format_color;
 use crate::config::{Config, ConfigOptions};
 use crate::error::{ConfigError, ParseResult};
+use crate::handlers::FunctionHandler;
+#[cfg(feature = "mutation")]
+use crate::migration::MigratedBlock;
+use crate::profile::{ConfigProfile, ProfileHandler, ProfileOption};
 use crate::special_categories::SpecialCategoryDescriptor;
-use crate::types::{Color, ConfigValue};
-use std::collections::HashMap;
-use std::path::Path;
+use crate::types::{Color, ConfigValue, Vec2};
+use crate::util::{join_args, split_args, splitn_args};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
 /// Wrapper around a windowrule or layerrule instance with type-safe value accessors.
 ///
@@ -391,11 +400,13 @@ use std::path::Path;
 /// let is_floating = rule.get_int("float").unwrap();  // 1 for true
 /// let opacity = rule.get_float("opacity").unwrap();
 /// let color = rule.get_color("border_color").unwrap();
+/// let size = rule.get_vec2("size").unwrap();
 ///
 /// assert_eq!(class_pattern, "^(kitty)$");
 /// assert_eq!(is_floating, 1);
 /// assert_eq!(opacity, 0.9);
 /// assert_eq!(color.r, 51);  // 0x33
+/// assert_eq!((size.x, size.y), (800.0, 600.0));
 /// ```
 pub struct RuleInstance<'a> {
     values: HashMap<String, &'a ConfigValue>,
@@ -445,6 +456,1544 @@ impl<'a> RuleInstance<'a> {
             v => Err(ConfigError::type_error(key, "Color", v.type_name())),
         }
     }
+
+    /// Get a pair of numbers, such as `size = 800 600` or `move = 100 200`
+    ///
+    /// Pairs using expressions like `monitor_w*0.5` are only available with
+    /// [`get_string`](Self::get_string).
+    pub fn get_vec2(&self, key: &str) -> ParseResult<Vec2> {
+        let value = self.get(key)?;
+        value
+            .as_vec2()
+            .map_err(|_| ConfigError::type_error(key, "Vec2", value.type_name()))
+    }
+}
+
+/// Properties of a window, checked against windowrule `match:*` fields
+///
+/// Used by [`Hyprland::windowrules_matching`]. Properties left as `None` are unknown and
+/// not checked; a rule only matches if it has at least one matcher that was checked and
+/// all checked matchers accept the window.
+///
+/// `class`, `title`, `initial_class`, `initial_title` and `tags` are matched like
+/// Hyprland does: the whole value against the regex, with a `negative:` prefix inverting
+/// the match. The other fields are compared with boolean matchers such as
+/// `match:float = true`.
+#[cfg(feature = "regex")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WindowProperties {
+    /// Current class, for `match:class`
+    pub class: Option<String>,
+    /// Current title, for `match:title`
+    pub title: Option<String>,
+    /// Class the window was created with, for `match:initial_class`
+    pub initial_class: Option<String>,
+    /// Title the window was created with, for `match:initial_title`
+    pub initial_title: Option<String>,
+    /// Tags of the window; `match:tag` matches if any of them does
+    pub tags: Option<Vec<String>>,
+    /// For `match:xwayland`
+    pub xwayland: Option<bool>,
+    /// For `match:float` / `match:floating`
+    pub floating: Option<bool>,
+    /// For `match:fullscreen`
+    pub fullscreen: Option<bool>,
+    /// For `match:pin` / `match:pinned`
+    pub pinned: Option<bool>,
+    /// For `match:focus`
+    pub focus: Option<bool>,
+    /// For `match:group`
+    pub group: Option<bool>,
+    /// For `match:modal`
+    pub modal: Option<bool>,
+}
+
+#[cfg(feature = "regex")]
+impl WindowProperties {
+    /// Properties of a window of which only the class is known
+    pub fn with_class(class: impl Into<String>) -> Self {
+        Self {
+            class: Some(class.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Whether the windowrule accepts this window, or `None` if none of its matchers
+    /// were checked
+    fn matches(&self, rule: &str, matchers: &[(&str, &ConfigValue)]) -> ParseResult<Option<bool>> {
+        let mut checked = false;
+        for (property, value) in matchers {
+            let text = value.to_string();
+            let accepted = match *property {
+                "class" | "title" | "initial_class" | "initial_title" => {
+                    let field = match *property {
+                        "class" => &self.class,
+                        "title" => &self.title,
+                        "initial_class" => &self.initial_class,
+                        _ => &self.initial_title,
+                    };
+                    match field {
+                        Some(field) => regex_matches(rule, property, &text, [field.as_str()])?,
+                        None => continue,
+                    }
+                }
+                "tag" => match &self.tags {
+                    Some(tags) => {
+                        regex_matches(rule, property, &text, tags.iter().map(String::as_str))?
+                    }
+                    None => continue,
+                },
+                _ => {
+                    let field = match *property {
+                        "xwayland" => self.xwayland,
+                        "floating" | "float" => self.floating,
+                        "fullscreen" => self.fullscreen,
+                        "pinned" | "pin" => self.pinned,
+                        "focus" => self.focus,
+                        "group" => self.group,
+                        "modal" => self.modal,
+                        _ => None,
+                    };
+                    match (field, ConfigValue::parse_bool(&text)) {
+                        (Some(field), Ok(expected)) => field == expected,
+                        _ => continue,
+                    }
+                }
+            };
+
+            if !accepted {
+                return Ok(Some(false));
+            }
+            checked = true;
+        }
+
+        Ok(checked.then_some(true))
+    }
+}
+
+/// Whether a Hyprland matcher regex matches any of `values` in full
+#[cfg(feature = "regex")]
+fn regex_matches<'v>(
+    rule: &str,
+    property: &str,
+    pattern: &str,
+    values: impl IntoIterator<Item = &'v str>,
+) -> ParseResult<bool> {
+    let (regex, negated) = crate::special_categories::matcher_regex(pattern).map_err(|e| {
+        ConfigError::custom(format!(
+            "windowrule '{}': invalid match:{} regex '{}': {}",
+            rule, property, pattern, e
+        ))
+    })?;
+
+    let found = values.into_iter().any(|value| regex.is_match(value));
+    Ok(found != negated)
+}
+
+/// A parsed `env = NAME,value` handler call.
+///
+/// The line is split on the first comma only, so values that themselves contain
+/// commas (e.g. `env = XDG_CURRENT_DESKTOP,Hyprland,sway`) are kept intact.
+/// Surrounding whitespace is trimmed from both parts.
+///
+/// # Example
+///
+/// ```rust
+/// use hyprlang::EnvVar;
+///
+/// let env = EnvVar::parse("QT_QPA_PLATFORM, wayland;xcb");
+/// assert_eq!(env.name, "QT_QPA_PLATFORM");
+/// assert_eq!(env.value, "wayland;xcb");
+/// assert_eq!(env.to_string(), "QT_QPA_PLATFORM,wayland;xcb");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnvVar {
+    /// Environment variable name
+    pub name: String,
+    /// Environment variable value (may be empty)
+    pub value: String,
+}
+
+impl EnvVar {
+    /// Create a new environment variable entry
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+
+    /// Parse the value of an `env` handler call
+    ///
+    /// A line without a comma yields an empty value, matching Hyprland.
+    pub fn parse(line: &str) -> Self {
+        match line.split_once(',') {
+            Some((name, value)) => Self::new(name.trim(), value.trim()),
+            None => Self::new(line.trim(), ""),
+        }
+    }
+}
+
+impl std::fmt::Display for EnvVar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.name, self.value)
+    }
+}
+
+/// A keybind behavior, written as a suffix letter of the `bind` keyword
+///
+/// `bindl` is a locked bind and `bindlr` a locked bind that triggers on release. The
+/// letters of every `bind*` keyword are looked up in one table, so new variants only
+/// need a new entry there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BindFlag {
+    /// `l` - also works while an input inhibitor (e.g. a lockscreen) is active
+    Locked,
+    /// `r` - triggers on key release
+    Release,
+    /// `c` - triggers on release after a click shorter than `binds:drag_threshold`
+    Click,
+    /// `g` - triggers on release after a drag longer than `binds:drag_threshold`
+    Drag,
+    /// `o` - triggers on a long press
+    LongPress,
+    /// `e` - repeats while held
+    Repeat,
+    /// `n` - non-consuming: the key event is also passed to the focused window
+    NonConsuming,
+    /// `m` - mouse bind
+    Mouse,
+    /// `t` - transparent: cannot be shadowed by other binds
+    Transparent,
+    /// `i` - ignores modifiers
+    IgnoreMods,
+    /// `s` - separate: keys may be combined with modifiers in any field
+    Separate,
+    /// `d` - has a description before the dispatcher
+    Description,
+    /// `p` - bypasses the app's shortcut inhibitor
+    Bypass,
+    /// `u` - universal: active in every submap (new in 0.53.0)
+    Universal,
+}
+
+/// Suffix letter of every bind flag
+const BIND_FLAGS: &[(char, BindFlag)] = &[
+    ('l', BindFlag::Locked),
+    ('r', BindFlag::Release),
+    ('c', BindFlag::Click),
+    ('g', BindFlag::Drag),
+    ('o', BindFlag::LongPress),
+    ('e', BindFlag::Repeat),
+    ('n', BindFlag::NonConsuming),
+    ('m', BindFlag::Mouse),
+    ('t', BindFlag::Transparent),
+    ('i', BindFlag::IgnoreMods),
+    ('s', BindFlag::Separate),
+    ('d', BindFlag::Description),
+    ('p', BindFlag::Bypass),
+    ('u', BindFlag::Universal),
+];
+
+impl BindFlag {
+    /// The flag written as `letter` after `bind`
+    pub fn from_suffix(letter: char) -> Option<Self> {
+        BIND_FLAGS
+            .iter()
+            .find(|(suffix, _)| *suffix == letter)
+            .map(|(_, flag)| *flag)
+    }
+
+    /// The letter written after `bind` for this flag
+    pub fn suffix(self) -> char {
+        BIND_FLAGS
+            .iter()
+            .find(|(_, flag)| *flag == self)
+            .map(|(suffix, _)| *suffix)
+            .unwrap_or_default()
+    }
+
+    /// Parse the suffix letters of a `bind*` keyword, e.g. `lr` for `bindlr`
+    ///
+    /// Returns `None` if any letter is not a known flag.
+    pub fn parse_suffix(suffix: &str) -> Option<Vec<Self>> {
+        suffix.chars().map(Self::from_suffix).collect()
+    }
+}
+
+/// A keybind from any `bind*` keyword, with the keyword's suffix letters as flags
+///
+/// Hyprland records every variant (`bindl`, `bindm`, `bindel`, ...) as a `bind` call with
+/// the suffix as its flags, so all of them parse the same way:
+/// `MODS, key, [description,] dispatcher, args`. The description field is only present
+/// with the [`Description`](BindFlag::Description) flag.
+///
+/// # Example
+///
+/// ```rust
+/// use hyprlang::{BindFlag, Keybind};
+///
+/// let bind = Keybind::from_keyword("bindel", ", XF86AudioRaiseVolume, exec, wpctl set-volume @DEFAULT_AUDIO_SINK@ 5%+").unwrap();
+/// assert!(bind.has_flag(BindFlag::Locked));
+/// assert!(bind.has_flag(BindFlag::Repeat));
+/// assert_eq!(bind.key, "XF86AudioRaiseVolume");
+/// assert_eq!(bind.dispatcher, "exec");
+/// assert_eq!(bind.keyword(), "bindel");
+///
+/// let bind = Keybind::parse(&[BindFlag::Description], "SUPER, Q, Open terminal, exec, kitty");
+/// assert_eq!(bind.description.as_deref(), Some("Open terminal"));
+/// assert_eq!(bind.args, "kitty");
+///
+/// assert!(Keybind::from_keyword("bindx", "SUPER, Q, exec, kitty").is_none());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Keybind {
+    /// Flags from the keyword suffix, in the order they were written
+    pub flags: Vec<BindFlag>,
+    /// Modifier keys, e.g. `SUPER SHIFT` (empty for none)
+    pub mods: String,
+    /// Key, e.g. `Q`, `code:24` or `mouse:272`
+    pub key: String,
+    /// Description (only with [`BindFlag::Description`])
+    pub description: Option<String>,
+    /// Dispatcher, e.g. `exec`
+    pub dispatcher: String,
+    /// Dispatcher arguments, which may themselves contain commas (empty for none)
+    pub args: String,
+}
+
+impl Keybind {
+    /// Parse a bind value given the flags of its keyword
+    ///
+    /// Missing fields are left empty, matching the leniency of [`Monitor::parse`].
+    pub fn parse(flags: &[BindFlag], value: &str) -> Self {
+        let has_description = flags.contains(&BindFlag::Description);
+        let fields = if has_description { 5 } else { 4 };
+        let mut parts = splitn_args(value, fields).into_iter();
+
+        let mut field = || parts.next().unwrap_or_default();
+        Self {
+            flags: flags.to_vec(),
+            mods: field(),
+            key: field(),
+            description: has_description.then(&mut field),
+            dispatcher: field(),
+            args: field(),
+        }
+    }
+
+    /// Parse a bind written with any `bind*` keyword, e.g. `bindlr`
+    ///
+    /// Returns `None` if the keyword is not `bind` followed by known flag letters.
+    pub fn from_keyword(keyword: &str, value: &str) -> Option<Self> {
+        let flags = BindFlag::parse_suffix(keyword.strip_prefix("bind")?)?;
+        Some(Self::parse(&flags, value))
+    }
+
+    /// Whether the bind has a flag
+    pub fn has_flag(&self, flag: BindFlag) -> bool {
+        self.flags.contains(&flag)
+    }
+
+    /// The keyword the bind is written with, e.g. `bindlr`
+    pub fn keyword(&self) -> String {
+        let mut keyword = "bind".to_string();
+        keyword.extend(self.flags.iter().map(|flag| flag.suffix()));
+        keyword
+    }
+
+    /// The value the bind is written with, e.g. `SUPER, Q, exec, kitty`
+    pub fn value(&self) -> String {
+        let mut fields = vec![self.mods.as_str(), self.key.as_str()];
+        fields.extend(self.description.as_deref());
+        fields.push(&self.dispatcher);
+        if !self.args.is_empty() {
+            fields.push(&self.args);
+        }
+        join_args(&fields)
+    }
+
+    /// Dispatchers Hyprland knows, for [`validate`](Self::validate)
+    pub const DISPATCHERS: &'static [&'static str] = &[
+        "alterzorder",
+        "bringactivetotop",
+        "centerwindow",
+        "changegroupactive",
+        "closewindow",
+        "cyclenext",
+        "denywindowfromgroup",
+        "dpms",
+        "event",
+        "exec",
+        "execr",
+        "exit",
+        "focuscurrentorlast",
+        "focusmonitor",
+        "focusurgentorlast",
+        "focuswindow",
+        "focusworkspaceoncurrentmonitor",
+        "forcekillactive",
+        "forcerendererreload",
+        "fullscreen",
+        "fullscreenstate",
+        "global",
+        "killactive",
+        "killwindow",
+        "layoutmsg",
+        "lockactivegroup",
+        "lockgroups",
+        "movecurrentworkspacetomonitor",
+        "movecursor",
+        "movecursortocorner",
+        "movefocus",
+        "movegroupwindow",
+        "moveintogroup",
+        "moveoutofgroup",
+        "movetoworkspace",
+        "movetoworkspacesilent",
+        "movewindow",
+        "movewindoworgroup",
+        "movewindowpixel",
+        "moveactive",
+        "moveworkspacetomonitor",
+        "pass",
+        "pin",
+        "pseudo",
+        "renameworkspace",
+        "resizeactive",
+        "resizewindow",
+        "resizewindowpixel",
+        "sendkeystate",
+        "sendshortcut",
+        "setfloating",
+        "setignoregrouplock",
+        "setprop",
+        "settiled",
+        "signal",
+        "signalwindow",
+        "splitratio",
+        "submap",
+        "swapactiveworkspaces",
+        "swapnext",
+        "swapsplit",
+        "swapwindow",
+        "tagwindow",
+        "togglefloating",
+        "togglegroup",
+        "togglespecialworkspace",
+        "togglesplit",
+        "toggleswallow",
+        "workspace",
+    ];
+
+    /// Check that the bind names a key and one of `dispatchers`
+    ///
+    /// ```rust
+    /// use hyprlang::Keybind;
+    ///
+    /// let bind = Keybind::from_keyword("bind", "SUPER, Q, exec, kitty").unwrap();
+    /// assert!(bind.validate(Keybind::DISPATCHERS).is_ok());
+    ///
+    /// let bind = Keybind::from_keyword("bind", "SUPER, Q, exce, kitty").unwrap();
+    /// assert_eq!(bind.validate(Keybind::DISPATCHERS).unwrap_err(), "unknown dispatcher 'exce'");
+    /// ```
+    pub fn validate(&self, dispatchers: &[&str]) -> Result<(), String> {
+        if self.key.is_empty() {
+            return Err("missing key".to_string());
+        }
+        if self.dispatcher.is_empty() {
+            return Err("missing dispatcher".to_string());
+        }
+        if !dispatchers.contains(&self.dispatcher.as_str()) {
+            return Err(format!("unknown dispatcher '{}'", self.dispatcher));
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Keybind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} = {}", self.keyword(), self.value())
+    }
+}
+
+/// A monitor as described by `monitor = ...` lines and `monitor[name] { ... }` blocks.
+///
+/// Handler lines are read positionally (`name, mode, position, scale, key, value, ...`),
+/// with `disable` in the mode position marking the monitor as disabled. Block properties
+/// are matched by name (`mode`, `position`, `scale`, `disabled`); anything else ends up
+/// in [`options`](Monitor::options), as do the trailing key/value pairs of handler lines.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Monitor {
+    /// Output name, e.g. `DP-1` or `desc:...` (empty for the `monitor = ,...` fallback rule)
+    pub name: String,
+    /// Resolution and refresh rate, e.g. `1920x1080@144` or `preferred`
+    pub mode: Option<String>,
+    /// Position, e.g. `0x0` or `auto`
+    pub position: Option<String>,
+    /// Scale, e.g. `1` or `auto`
+    pub scale: Option<String>,
+    /// Whether the monitor is disabled
+    pub disabled: bool,
+    /// Additional settings such as `transform` or `vrr`
+    pub options: BTreeMap<String, String>,
+    /// Whether a `monitor = ...` line describes this monitor
+    pub from_handler: bool,
+    /// Whether a `monitor[name] { ... }` block describes this monitor
+    pub from_block: bool,
+}
+
+impl Monitor {
+    /// Parse the value of a `monitor` handler call
+    ///
+    /// ```rust
+    /// use hyprlang::Monitor;
+    ///
+    /// let monitor = Monitor::parse("DP-1, 1920x1080@144, 0x0, 1, transform, 1");
+    /// assert_eq!(monitor.name, "DP-1");
+    /// assert_eq!(monitor.mode.as_deref(), Some("1920x1080@144"));
+    /// assert_eq!(monitor.options["transform"], "1");
+    /// assert_eq!(monitor.to_string(), "DP-1, 1920x1080@144, 0x0, 1, transform, 1");
+    ///
+    /// assert!(Monitor::parse("HDMI-A-1, disable").disabled);
+    /// ```
+    pub fn parse(line: &str) -> Self {
+        let parts = split_args(line);
+        let mut parts = parts.iter().map(String::as_str);
+        let mut monitor = Self {
+            name: parts.next().unwrap_or_default().to_string(),
+            from_handler: true,
+            ..Self::default()
+        };
+
+        match parts.next() {
+            Some("disable") | Some("disabled") => monitor.disabled = true,
+            Some("addreserved") => {
+                let reserved: Vec<&str> = parts.by_ref().collect();
+                monitor
+                    .options
+                    .insert("addreserved".to_string(), join_args(&reserved));
+            }
+            Some(mode) => {
+                monitor.mode = Some(mode.to_string());
+                monitor.position = parts.next().map(str::to_string);
+                monitor.scale = parts.next().map(str::to_string);
+            }
+            None => {}
+        }
+
+        while let Some(key) = parts.next() {
+            let value = parts.next().unwrap_or_default();
+            monitor.options.insert(key.to_string(), value.to_string());
+        }
+
+        monitor
+    }
+
+    /// Check the value of a `monitor` handler call
+    ///
+    /// Lines that don't disable the monitor or reserve space need a mode (a resolution
+    /// such as `1920x1080@144` or `preferred`, `highres`, `highrr`, `maxwidth`), a
+    /// position (`XxY` or `auto...`) and a scale (a positive number or `auto`).
+    ///
+    /// ```rust
+    /// use hyprlang::Monitor;
+    ///
+    /// assert!(Monitor::validate("DP-1, 1920x1080@144, 0x0, 1").is_ok());
+    /// assert!(Monitor::validate(", preferred, auto, auto").is_ok());
+    /// assert!(Monitor::validate("HDMI-A-1, disable").is_ok());
+    /// assert_eq!(
+    ///     Monitor::validate("DP-1, 1920x1080, 0x0, big").unwrap_err(),
+    ///     "invalid scale 'big'"
+    /// );
+    /// ```
+    pub fn validate(value: &str) -> Result<(), String> {
+        let monitor = Self::parse(value);
+        if monitor.disabled || monitor.options.contains_key("addreserved") {
+            return Ok(());
+        }
+
+        let mode = monitor.mode.as_deref().ok_or("missing mode")?;
+        let resolution = |mode: &str| {
+            let (size, rate) = match mode.split_once('@') {
+                Some((size, rate)) => (size, Some(rate)),
+                None => (mode, None),
+            };
+            let size_ok = size
+                .split_once('x')
+                .is_some_and(|(w, h)| w.parse::<u32>().is_ok() && h.parse::<u32>().is_ok());
+            size_ok && rate.is_none_or(|rate| rate.parse::<f64>().is_ok_and(|r| r > 0.0))
+        };
+        if !matches!(mode, "preferred" | "highres" | "highrr" | "maxwidth") && !resolution(mode) {
+            return Err(format!("invalid mode '{}'", mode));
+        }
+
+        let position = monitor.position.as_deref().ok_or("missing position")?;
+        let offset = position
+            .split_once('x')
+            .is_some_and(|(x, y)| x.parse::<i32>().is_ok() && y.parse::<i32>().is_ok());
+        if !position.starts_with("auto") && !offset {
+            return Err(format!("invalid position '{}'", position));
+        }
+
+        let scale = monitor.scale.as_deref().ok_or("missing scale")?;
+        if scale != "auto" && !scale.parse::<f64>().is_ok_and(|scale| scale > 0.0) {
+            return Err(format!("invalid scale '{}'", scale));
+        }
+        Ok(())
+    }
+
+    /// Apply a property from a `monitor[name] { ... }` block
+    fn set_property(&mut self, property: &str, value: String) {
+        match property {
+            "mode" | "resolution" => self.mode = Some(value),
+            "position" => self.position = Some(value),
+            "scale" => self.scale = Some(value),
+            "disabled" | "disable" => self.disabled = parse_disabled(&value),
+            _ => {
+                self.options.insert(property.to_string(), value);
+            }
+        }
+    }
+
+    /// Get a setting by the name a block would use for it
+    fn property(&self, property: &str) -> Option<String> {
+        match property {
+            "mode" | "resolution" => self.mode.clone(),
+            "position" => self.position.clone(),
+            "scale" => self.scale.clone(),
+            "disabled" | "disable" => self.disabled.then(|| true.to_string()),
+            _ => self.options.get(property).cloned(),
+        }
+    }
+}
+
+/// Writes the value of a `monitor = ...` line, with `preferred`, `auto` and `1` standing in
+/// for a missing mode, position and scale
+impl std::fmt::Display for Monitor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+        let reserved = self.options.get("addreserved");
+        if self.disabled {
+            write!(f, ", disable")?;
+        } else if let (Some(reserved), None) = (reserved, &self.mode) {
+            return write!(f, ", addreserved, {}", reserved);
+        } else {
+            write!(
+                f,
+                ", {}, {}, {}",
+                self.mode.as_deref().unwrap_or("preferred"),
+                self.position.as_deref().unwrap_or("auto"),
+                self.scale.as_deref().unwrap_or("1")
+            )?;
+        }
+        for (key, value) in &self.options {
+            write!(f, ", {}, {}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Read a block's `disabled` value, accepting booleans as well as `0`/`1`
+fn parse_disabled(value: &str) -> bool {
+    ConfigValue::parse_bool(value).unwrap_or(value.trim() == "1")
+}
+
+/// A setting that a `monitor = ...` line and a `monitor[name]` block disagree on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonitorConflict {
+    /// Monitor name
+    pub monitor: String,
+    /// Setting name, e.g. `mode` or `transform`
+    pub property: String,
+    /// Value from the handler line
+    pub handler_value: String,
+    /// Value from the block, which takes precedence
+    pub block_value: String,
+}
+
+/// Unified view of all monitors, merging `monitor = ...` lines with `monitor[name]` blocks
+///
+/// When several lines name the same monitor, the last one wins, as in Hyprland. Block
+/// properties are applied on top of the handler line and every disagreement is reported
+/// as a [`MonitorConflict`].
+///
+/// ```rust
+/// use hyprlang::Hyprland;
+///
+/// let mut hypr = Hyprland::new();
+/// hypr.parse(r#"
+///     monitor = DP-1, 1920x1080@144, 0x0, 1
+///     monitor[DP-1] {
+///         scale = 1.5
+///     }
+/// "#).unwrap();
+///
+/// let monitors = hypr.monitors();
+/// let dp1 = monitors.get("DP-1").unwrap();
+/// assert_eq!(dp1.mode.as_deref(), Some("1920x1080@144"));
+/// assert_eq!(dp1.scale.as_deref(), Some("1.5"));
+/// assert_eq!(monitors.conflicts()[0].property, "scale");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Monitors {
+    monitors: Vec<Monitor>,
+    conflicts: Vec<MonitorConflict>,
+}
+
+impl Monitors {
+    /// Get a monitor by name
+    pub fn get(&self, name: &str) -> Option<&Monitor> {
+        self.monitors.iter().find(|monitor| monitor.name == name)
+    }
+
+    /// Iterate over monitors: handler-defined ones in file order, then block-only ones by name
+    pub fn iter(&self) -> impl Iterator<Item = &Monitor> {
+        self.monitors.iter()
+    }
+
+    /// Names of all monitors
+    pub fn names(&self) -> Vec<&str> {
+        self.monitors.iter().map(|m| m.name.as_str()).collect()
+    }
+
+    /// Settings defined differently by a handler line and a block
+    pub fn conflicts(&self) -> &[MonitorConflict] {
+        &self.conflicts
+    }
+
+    /// Number of monitors
+    pub fn len(&self) -> usize {
+        self.monitors.len()
+    }
+
+    /// Whether no monitors are configured
+    pub fn is_empty(&self) -> bool {
+        self.monitors.is_empty()
+    }
+}
+
+/// What the definitions in a [`Conflict`] collide on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConflictKind {
+    /// Binds of the same key combination in one submap (`None` outside any submap)
+    Keybind { submap: Option<String> },
+    /// `monitor = ...` lines for the same monitor, of which only the last one applies
+    Monitor,
+    /// `windowrule[name]` blocks with the same properties
+    WindowRule,
+}
+
+/// One of the definitions in a [`Conflict`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictSite {
+    /// The line as written after variable expansion, e.g. `bindl = SUPER, Q, killactive`,
+    /// or the block's name
+    pub text: String,
+    /// File it was read from (`None` for strings or definitions added at runtime)
+    pub file: Option<PathBuf>,
+    /// Line of the handler call, or of the first property of a block
+    pub line: Option<usize>,
+}
+
+/// Definitions that collide, found by [`Hyprland::conflicts`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    /// What they collide on
+    pub kind: ConflictKind,
+    /// What they share: the key combination (`SUPER SHIFT, Q`), the monitor name or the
+    /// rule's properties
+    pub subject: String,
+    /// Every definition involved, in the order they were parsed
+    pub sites: Vec<ConflictSite>,
+}
+
+/// Modifier names Hyprland accepts, as the canonical name and the spellings it looks for
+const MODIFIERS: &[(&str, &[&str])] = &[
+    ("SHIFT", &["SHIFT"]),
+    ("CAPS", &["CAPS"]),
+    ("CTRL", &["CTRL", "CONTROL"]),
+    ("ALT", &["ALT", "MOD1"]),
+    ("MOD2", &["MOD2"]),
+    ("MOD3", &["MOD3"]),
+    ("SUPER", &["SUPER", "WIN", "LOGO", "MOD4", "META"]),
+    ("MOD5", &["MOD5"]),
+];
+
+/// The modifiers of a bind in a fixed order, so `SHIFT_SUPER` and `SUPER SHIFT` compare equal
+fn normalize_mods(mods: &str) -> String {
+    let mods = mods.to_uppercase();
+    MODIFIERS
+        .iter()
+        .filter(|(_, spellings)| spellings.iter().any(|spelling| mods.contains(spelling)))
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Where a [`LayerRule`] was written
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RuleOrigin {
+    /// A `layerrule = effect, namespace` line, with its position among those lines
+    Handler { index: usize },
+    /// A `layerrule[name] { ... }` block
+    Block { name: String },
+}
+
+/// A layer rule from either a `layerrule = ...` line (v1) or a `layerrule[name]` block (v2)
+///
+/// Effects are keyed by their v2 property names, so `ignorealpha 0.5` on a line and
+/// `ignore_alpha = 0.5` in a block both give `ignore_alpha` -> `0.5`. Effects written
+/// without an argument on a line, such as `blur`, get the value `true`.
+///
+/// ```rust
+/// use hyprlang::{LayerRule, RuleOrigin};
+///
+/// let rule = LayerRule::parse("ignorealpha 0.5, ^(waybar)$");
+/// assert_eq!(rule.namespace, "^(waybar)$");
+/// assert_eq!(rule.effects["ignore_alpha"], "0.5");
+/// assert_eq!(rule.origin, RuleOrigin::Handler { index: 0 });
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LayerRule {
+    /// Namespace pattern (`match:namespace`, or the last field of a line)
+    pub namespace: String,
+    /// Other `match:*` fields of a block, without the prefix, e.g. `monitor`
+    pub matchers: BTreeMap<String, String>,
+    /// Effects by v2 property name
+    pub effects: BTreeMap<String, String>,
+    /// Whether the rule applies (`enable = false` in a block turns it off)
+    pub enabled: bool,
+    /// Whether the rule came from a line or a block
+    pub origin: RuleOrigin,
+    /// File the rule was read from (`None` for strings or rules added at runtime)
+    pub file: Option<PathBuf>,
+    /// Line of the handler call, or of the first property of a block
+    pub line: Option<usize>,
+}
+
+impl LayerRule {
+    /// Parse the value of a `layerrule` handler call
+    ///
+    /// The rule is everything before the first comma and the namespace everything
+    /// after it, so namespace patterns may contain commas.
+    pub fn parse(line: &str) -> Self {
+        let (effect, namespace) = line.split_once(',').unwrap_or((line, ""));
+        let (effect, argument) = match effect.trim().split_once(char::is_whitespace) {
+            Some((effect, argument)) => (effect, argument.trim()),
+            None => (effect.trim(), "true"),
+        };
+
+        let mut effects = BTreeMap::new();
+        if !effect.is_empty() {
+            effects.insert(
+                layerrule_effect_name(effect).to_string(),
+                argument.to_string(),
+            );
+        }
+        Self {
+            namespace: namespace.trim().to_string(),
+            matchers: BTreeMap::new(),
+            effects,
+            enabled: true,
+            origin: RuleOrigin::Handler { index: 0 },
+            file: None,
+            line: None,
+        }
+    }
+
+    /// The block name, for rules written as `layerrule[name] { ... }`
+    pub fn name(&self) -> Option<&str> {
+        match &self.origin {
+            RuleOrigin::Block { name } => Some(name),
+            RuleOrigin::Handler { .. } => None,
+        }
+    }
+}
+
+/// The v2 name of a layerrule effect written with a v1 name or alias
+fn layerrule_effect_name(effect: &str) -> &str {
+    match effect {
+        "ignorealpha" => "ignore_alpha",
+        "noanim" => "no_anim",
+        "blurpopups" => "blur_popups",
+        "dimaround" => "dim_around",
+        "abovelock" => "above_lock",
+        "noscreenshare" => "no_screen_share",
+        effect => effect,
+    }
+}
+
+/// A plain-data copy of a [`Hyprland`] config, taken with [`Hyprland::snapshot`]
+///
+/// Typed options are `None` when the config has no value for them. The struct
+/// owns all of its data, so it can be sent to another thread (e.g. a UI) once the
+/// config is dropped. With the `serde` feature, all snapshot types implement
+/// `Serialize` and `Deserialize`.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HyprlandSettings {
+    pub general: GeneralSettings,
+    pub decoration: DecorationSettings,
+    pub input: InputSettings,
+    pub misc: MiscSettings,
+    pub dwindle: DwindleSettings,
+    pub master: MasterSettings,
+    pub animations: AnimationSettings,
+    /// Keybinds in config order, see [`Hyprland::keybinds`]
+    pub binds: Vec<Keybind>,
+    /// Merged monitors, see [`Hyprland::monitors`]
+    pub monitors: Vec<Monitor>,
+    /// `windowrule[name] { ... }` blocks, sorted by name
+    pub windowrules: Vec<RuleSettings>,
+    /// `layerrule[name] { ... }` blocks, sorted by name
+    pub layerrules: Vec<RuleSettings>,
+    /// `workspace = ...` lines
+    pub workspaces: Vec<String>,
+    /// `env = ...` lines
+    pub env: Vec<EnvVar>,
+    /// `exec-once = ...` lines
+    pub exec_once: Vec<String>,
+    /// `exec = ...` lines
+    pub exec: Vec<String>,
+    /// Config variables, without the `$`
+    pub variables: BTreeMap<String, String>,
+    /// Every option value, typed above or not, keyed like `decoration:blur:size`
+    pub options: BTreeMap<String, String>,
+}
+
+/// The `general` section of a [`HyprlandSettings`]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GeneralSettings {
+    pub border_size: Option<i64>,
+    pub gaps_in: Option<String>,
+    pub gaps_out: Option<String>,
+    pub active_border_color: Option<Color>,
+    pub inactive_border_color: Option<Color>,
+    pub layout: Option<String>,
+    pub allow_tearing: Option<bool>,
+    pub locale: Option<String>,
+}
+
+/// The `decoration` section of a [`HyprlandSettings`]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DecorationSettings {
+    pub rounding: Option<i64>,
+    pub active_opacity: Option<f64>,
+    pub inactive_opacity: Option<f64>,
+    pub blur_enabled: Option<bool>,
+    pub blur_size: Option<i64>,
+    pub blur_passes: Option<i64>,
+}
+
+/// The `input` section of a [`HyprlandSettings`]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputSettings {
+    pub kb_layout: Option<String>,
+    pub follow_mouse: Option<i64>,
+    pub sensitivity: Option<f64>,
+    pub touchpad_natural_scroll: Option<bool>,
+}
+
+/// The `misc` section of a [`HyprlandSettings`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MiscSettings {
+    pub disable_hyprland_logo: Option<bool>,
+    pub force_default_wallpaper: Option<i64>,
+}
+
+/// The `dwindle` section of a [`HyprlandSettings`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DwindleSettings {
+    pub pseudotile: Option<bool>,
+    pub preserve_split: Option<bool>,
+}
+
+/// The `master` section of a [`HyprlandSettings`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MasterSettings {
+    pub new_status: Option<String>,
+}
+
+/// The `animations` section of a [`HyprlandSettings`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnimationSettings {
+    pub enabled: Option<bool>,
+    /// `animation = ...` lines
+    pub animations: Vec<String>,
+    /// `bezier = ...` lines
+    pub beziers: Vec<String>,
+}
+
+/// A windowrule or layerrule block in a [`HyprlandSettings`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RuleSettings {
+    /// The block's key, e.g. `float-kitty` for `windowrule[float-kitty]`
+    pub name: String,
+    /// Properties set in the block; unset properties with defaults are left out
+    pub properties: BTreeMap<String, String>,
+}
+
+/// The handlers and special categories registered by [`Hyprland`]
+///
+/// [`Hyprland::new`] applies this profile to its config. It can also be applied to a
+/// plain [`Config`] with [`Config::apply_profile`], e.g. to combine Hyprland's setup with
+/// a tool's own profile.
+///
+/// # Example
+///
+/// ```rust
+/// use hyprlang::{Config, HyprlandProfile};
+///
+/// let mut config = Config::new();
+/// config.apply_profile(&HyprlandProfile);
+/// config.parse("bind = SUPER, Q, exec, kitty").unwrap();
+/// assert_eq!(config.get_handler_calls("bind").unwrap().len(), 1);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HyprlandProfile;
+
+impl ConfigProfile for HyprlandProfile {
+    fn name(&self) -> &str {
+        "hyprland"
+    }
+
+    fn handlers(&self) -> Vec<ProfileHandler> {
+        profile_handlers(None)
+    }
+
+    fn configure(&self, config: &mut Config) {
+        register_bind(config);
+    }
+
+    fn special_categories(&self) -> Vec<SpecialCategoryDescriptor> {
+        profile_special_categories(None)
+    }
+}
+
+/// A Hyprland release, e.g. `0.53` or `0.53.1`
+///
+/// Versions compare in release order and parse from strings with an optional `v` prefix
+/// and patch number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HyprlandVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl HyprlandVersion {
+    /// Create a version
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Parse a version like `0.53`, `0.53.1` or `v0.53.1`
+    pub fn parse(version: &str) -> ParseResult<Self> {
+        let invalid = || ConfigError::custom(format!("invalid Hyprland version '{}'", version));
+
+        let trimmed = version.trim();
+        let trimmed = trimmed.strip_prefix('v').unwrap_or(trimmed);
+        let parts = trimmed
+            .split('.')
+            .map(|part| part.parse::<u32>().map_err(|_| invalid()))
+            .collect::<ParseResult<Vec<_>>>()?;
+
+        match parts[..] {
+            [major, minor] => Ok(Self::new(major, minor, 0)),
+            [major, minor, patch] => Ok(Self::new(major, minor, patch)),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl std::str::FromStr for HyprlandVersion {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl std::fmt::Display for HyprlandVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// `windowrulev2` was renamed to `windowrule`, replacing the v1 syntax
+const WINDOWRULE_V2_RENAMED: HyprlandVersion = HyprlandVersion::new(0, 48, 0);
+
+/// Shadow options moved into `decoration:shadow`
+const SHADOW_CATEGORY_ADDED: HyprlandVersion = HyprlandVersion::new(0, 45, 0);
+
+/// `master:new_status` replaced `master:new_is_master`
+const MASTER_NEW_STATUS_ADDED: HyprlandVersion = HyprlandVersion::new(0, 41, 0);
+
+/// Windowrule v3 and layerrule v2 blocks, and the options noted as new in 0.53.0
+const RULE_BLOCKS_ADDED: HyprlandVersion = HyprlandVersion::new(0, 53, 0);
+
+/// A Hyprland option with its default and the releases it exists in
+#[derive(Debug, Clone)]
+pub struct HyprlandOption {
+    /// Full key, e.g. `general:border_size`
+    pub key: &'static str,
+
+    /// Default value
+    pub default: ConfigValue,
+
+    /// First release with the option (`None` if it predates the table)
+    pub since: Option<HyprlandVersion>,
+
+    /// First release without the option (`None` if it still exists)
+    pub until: Option<HyprlandVersion>,
+}
+
+impl HyprlandOption {
+    fn new(key: &'static str, default: ConfigValue) -> Self {
+        Self {
+            key,
+            default,
+            since: None,
+            until: None,
+        }
+    }
+
+    fn since(mut self, version: HyprlandVersion) -> Self {
+        self.since = Some(version);
+        self
+    }
+
+    fn until(mut self, version: HyprlandVersion) -> Self {
+        self.until = Some(version);
+        self
+    }
+
+    /// Check whether the option exists in a release
+    pub fn available_in(&self, version: HyprlandVersion) -> bool {
+        self.since.is_none_or(|since| since <= version)
+            && self.until.is_none_or(|until| version < until)
+    }
+
+    /// The options behind [`Hyprland`]'s typed accessors, across all releases
+    pub fn all() -> Vec<HyprlandOption> {
+        let int = ConfigValue::Int;
+        let float = ConfigValue::Float;
+        let string = |s: &str| ConfigValue::String(s.to_string());
+
+        vec![
+            Self::new("general:border_size", int(1)),
+            Self::new("general:gaps_in", string("5")),
+            Self::new("general:gaps_out", string("20")),
+            // Gradients ("rgba(..) rgba(..) 45deg") are valid, so these are not colors
+            Self::new("general:col.active_border", string("rgba(ffffffff)")),
+            Self::new("general:col.inactive_border", string("rgba(444444ff)")),
+            Self::new("general:layout", string("dwindle")),
+            Self::new("general:allow_tearing", int(0)),
+            Self::new("general:locale", string("")).since(RULE_BLOCKS_ADDED),
+            Self::new("decoration:rounding", int(0)),
+            Self::new("decoration:active_opacity", float(1.0)),
+            Self::new("decoration:inactive_opacity", float(1.0)),
+            Self::new("decoration:drop_shadow", int(1)).until(SHADOW_CATEGORY_ADDED),
+            Self::new("decoration:shadow:enabled", int(1)).since(SHADOW_CATEGORY_ADDED),
+            Self::new("decoration:blur:enabled", int(1)),
+            Self::new("decoration:blur:size", int(8)),
+            Self::new("decoration:blur:passes", int(1)),
+            Self::new("animations:enabled", int(1)),
+            Self::new("input:kb_layout", string("us")),
+            Self::new("input:follow_mouse", int(1)),
+            Self::new("input:sensitivity", float(0.0)),
+            Self::new("input:touchpad:natural_scroll", int(0)),
+            Self::new("misc:disable_hyprland_logo", int(0)),
+            Self::new("misc:force_default_wallpaper", int(-1)),
+            Self::new("quirks:prefer_hdr", int(0)).since(RULE_BLOCKS_ADDED),
+            Self::new("cursor:hide_on_tablet", int(0)).since(RULE_BLOCKS_ADDED),
+            Self::new("group:groupbar:blur", int(0)).since(RULE_BLOCKS_ADDED),
+            Self::new("dwindle:pseudotile", int(0)),
+            Self::new("dwindle:preserve_split", int(0)),
+            Self::new("master:new_is_master", int(1)).until(MASTER_NEW_STATUS_ADDED),
+            Self::new("master:new_status", string("slave")).since(MASTER_NEW_STATUS_ADDED),
+        ]
+    }
+}
+
+/// The handlers, special categories, options and aliases of one Hyprland release
+///
+/// Unlike [`HyprlandProfile`], which accepts every syntax this crate knows, this profile
+/// only registers what the given release supports and also registers the
+/// [`HyprlandOption`]s available in it with their defaults. Keywords the release renamed
+/// are registered as handler aliases, so their use shows up in
+/// [`Config::deprecations`].
+///
+/// # Example
+///
+/// ```rust
+/// use hyprlang::{Config, HyprlandVersion, HyprlandVersionProfile};
+///
+/// let mut config = Config::new();
+/// config.apply_profile(&HyprlandVersionProfile::new(HyprlandVersion::new(0, 53, 0)));
+/// config.parse("windowrulev2 = float, class:kitty").unwrap();
+///
+/// assert_eq!(config.get_handler_calls("windowrule").unwrap().len(), 1);
+/// assert_eq!(config.deprecations().len(), 1);
+/// assert_eq!(config.get_string("general:layout").unwrap(), "dwindle");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HyprlandVersionProfile {
+    version: HyprlandVersion,
+}
+
+impl HyprlandVersionProfile {
+    /// Profile for a release
+    pub fn new(version: HyprlandVersion) -> Self {
+        Self { version }
+    }
+
+    /// The targeted release
+    pub fn version(&self) -> HyprlandVersion {
+        self.version
+    }
+}
+
+impl ConfigProfile for HyprlandVersionProfile {
+    fn name(&self) -> &str {
+        "hyprland"
+    }
+
+    fn handlers(&self) -> Vec<ProfileHandler> {
+        profile_handlers(Some(self.version))
+    }
+
+    fn special_categories(&self) -> Vec<SpecialCategoryDescriptor> {
+        profile_special_categories(Some(self.version))
+    }
+
+    fn options(&self) -> Vec<ProfileOption> {
+        HyprlandOption::all()
+            .into_iter()
+            .filter(|option| option.available_in(self.version))
+            .map(|option| ProfileOption::new(option.key, option.default))
+            .collect()
+    }
+
+    fn handler_aliases(&self) -> Vec<(String, String)> {
+        if self.version >= WINDOWRULE_V2_RENAMED {
+            vec![("windowrulev2".to_string(), "windowrule".to_string())]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn configure(&self, config: &mut Config) {
+        register_bind(config);
+    }
+}
+
+/// Root and category handlers, for a release or for every release (`None`)
+fn profile_handlers(version: Option<HyprlandVersion>) -> Vec<ProfileHandler> {
+    // Root-level handlers
+    let root_handlers = [
+        "monitor",
+        "env",
+        "windowrule",
+        "windowrulev2",
+        "layerrule",
+        "workspace",
+        "exec",
+        "exec-once",
+        "source",
+        "blurls",
+        "plugin",
+        "submap",
+    ];
+
+    // Renamed keywords are aliases from their release on
+    let renamed = |keyword: &str| {
+        keyword == "windowrulev2" && version.is_some_and(|version| version >= WINDOWRULE_V2_RENAMED)
+    };
+
+    let mut handlers: Vec<ProfileHandler> = root_handlers
+        .into_iter()
+        .filter(|keyword| !renamed(keyword))
+        .map(ProfileHandler::global)
+        .collect();
+
+    // Category-specific handlers
+    handlers.push(ProfileHandler::in_category("animations", "animation"));
+    handlers.push(ProfileHandler::in_category("animations", "bezier"));
+    handlers
+}
+
+/// One handler for the whole bind family: `bindl`, `bindlr`, ... arrive as `bind` with
+/// their suffix letters as flags
+fn register_bind(config: &mut Config) {
+    config.register_handler(
+        "bind",
+        FunctionHandler::with_flags("bind", |ctx| {
+            let flags = ctx.flags.as_deref().unwrap_or_default();
+            match flags.chars().find(|&c| BindFlag::from_suffix(c).is_none()) {
+                Some(c) => Err(ConfigError::handler(
+                    "bind",
+                    format!("unknown bind flag '{}' in 'bind{}'", c, flags),
+                )),
+                None => Ok(()),
+            }
+        }),
+    );
+}
+
+/// Special categories, for a release or for every release (`None`)
+fn profile_special_categories(version: Option<HyprlandVersion>) -> Vec<SpecialCategoryDescriptor> {
+    let mut categories = vec![
+        // Device is a keyed category: device[name] { ... }
+        SpecialCategoryDescriptor::keyed("device", "name"),
+        // Monitor is a keyed category: monitor[name] { ... } (for per-monitor settings)
+        SpecialCategoryDescriptor::keyed("monitor", "name"),
+    ];
+
+    if version.is_some_and(|version| version < RULE_BLOCKS_ADDED) {
+        return categories;
+    }
+
+    categories.extend([
+        // Windowrule v3: windowrule { name = ... }
+        SpecialCategoryDescriptor::keyed("windowrule", "name")
+            .with_defaults(windowrule_properties())
+            .with_range("opacity", 0.0, 1.0)
+            .with_allowed_values("idle_inhibit", IDLE_INHIBIT_MODES)
+            .with_allowed_values("idleinhibit", IDLE_INHIBIT_MODES)
+            .with_regex("match:class")
+            .with_regex("match:title")
+            .with_regex("match:initial_class")
+            .with_regex("match:initial_title"),
+        // Layerrule v2: layerrule { name = ... }
+        SpecialCategoryDescriptor::keyed("layerrule", "name")
+            .with_defaults(layerrule_properties())
+            .with_range("ignorealpha", 0.0, 1.0)
+            .with_range("ignore_alpha", 0.0, 1.0)
+            .with_regex("match:namespace"),
+    ]);
+    categories
+}
+
+/// Values accepted by the windowrule `idle_inhibit` effect
+const IDLE_INHIBIT_MODES: [&str; 4] = ["none", "always", "focus", "fullscreen"];
+
+/// All windowrule match and effect properties with their defaults
+/// Based on Hyprland's Rule.hpp and WindowRuleEffectContainer.hpp
+fn windowrule_properties() -> Vec<(String, ConfigValue)> {
+    // Enable property (default: 1)
+    let mut properties = vec![("enable".to_string(), ConfigValue::Int(1))];
+
+    // Match properties (19 total from Rule.hpp enum eRuleProperty)
+    let match_props = [
+        "class",                    // Window class (regex)
+        "title",                    // Window title (regex)
+        "initial_class",            // Initial class on creation
+        "initial_title",            // Initial title on creation
+        "floating",                 // Is floating (bool)
+        "tag",                      // Window tag
+        "xwayland",                 // Is XWayland (bool)
+        "fullscreen",               // Is fullscreen (bool)
+        "pinned",                   // Is pinned (bool)
+        "focus",                    // Is focused (bool)
+        "group",                    // Is in group (bool)
+        "modal",                    // Is modal (bool)
+        "fullscreenstate_internal", // Internal fullscreen state
+        "fullscreenstate_client",   // Client fullscreen state
+        "on_workspace",             // On specific workspace
+        "content",                  // Content type
+        "xdg_tag",                  // XDG tag
+        "namespace",                // Namespace (for layer surfaces)
+        "exec_token",               // Exec token
+    ];
+
+    properties.extend(match_props.into_iter().map(|prop| {
+        (
+            format!("match:{}", prop),
+            ConfigValue::String(String::new()),
+        )
+    }));
+
+    // Match property aliases for Hyprland v3 naming (new in 0.53.0)
+    // These provide alternative names that match Hyprland's actual property names
+    let match_aliases = [
+        "float",                   // Alias for "floating"
+        "pin",                     // Alias for "pinned"
+        "workspace",               // Alias for "on_workspace"
+        "fullscreen_state_internal", // Alias for "fullscreenstate_internal"
+        "fullscreen_state_client",   // Alias for "fullscreenstate_client"
+    ];
+
+    properties.extend(match_aliases.into_iter().map(|prop| {
+        (
+            format!("match:{}", prop),
+            ConfigValue::String(String::new()),
+        )
+    }));
+
+    // Effect properties (60+ from WindowRuleEffectContainer.hpp)
+    // Note: Many properties have aliases (e.g., border_color / bordercolor)
+    let effect_props = [
+        // Static effects (applied once)
+        "float",
+        "tile",
+        "fullscreen",
+        "maximize",
+        "fullscreenstate",
+        "fullscreen_state", // Alias for fullscreenstate (new in 0.53.0)
+        "move",
+        "size",
+        "center",
+        "pseudo",
+        "monitor",
+        "workspace",
+        "noinitialfocus",
+        "no_initial_focus", // Alias for noinitialfocus (new in 0.53.0)
+        "pin",
+        "group",
+        "suppressevent",
+        "suppress_event", // Alias for suppressevent (new in 0.53.0)
+        "content",
+        "noclosefor",
+        "no_close_for", // Alias for noclosefor (new in 0.53.0)
+        // Dynamic effects (continuously applied)
+        "rounding",
+        "rounding_power",
+        "persistent_size",
+        "animation",
+        "border_color",
+        "bordercolor", // Aliases
+        "idle_inhibit",
+        "idleinhibit", // Aliases
+        "opacity",
+        "tag",
+        "max_size",
+        "maxsize", // Aliases
+        "min_size",
+        "minsize", // Aliases
+        "border_size",
+        "bordersize", // Aliases
+        "allows_input",
+        "dim_around",
+        "decorate",
+        "focus_on_activate",
+        "keep_aspect_ratio",
+        "keepaspectratio", // Aliases
+        "nearest_neighbor",
+        "nearestneighbor", // Aliases
+        "no_anim",
+        "noanim", // Aliases
+        "no_blur",
+        "noblur", // Aliases
+        "no_dim",
+        "nodim", // Aliases
+        "no_focus",
+        "nofocus", // Aliases
+        "no_follow_mouse",
+        "nofollowmouse", // Aliases
+        "no_max_size",
+        "nomaxsize", // Aliases
+        "no_shadow",
+        "noshadow", // Aliases
+        "no_shortcuts_inhibit",
+        "noshortcutsinhibit", // Aliases
+        "opaque",
+        "force_rgbx",
+        "forcergbx", // Aliases
+        "sync_fullscreen",
+        "syncfullscreen", // Aliases
+        "immediate",
+        "xray",
+        "render_unfocused",
+        "renderunfocused", // Aliases
+        "no_screen_share",
+        "noscreenshare", // Aliases
+        "no_vrr",
+        "novrr", // Aliases
+        "scroll_mouse",
+        "scrollmouse", // Aliases
+        "scroll_touchpad",
+        "scrolltouchpad", // Aliases
+        "stay_focused",
+        "stayfocused", // Aliases
+    ];
+
+    properties.extend(
+        effect_props
+            .into_iter()
+            .map(|prop| (prop.to_string(), ConfigValue::String(String::new()))),
+    );
+
+    properties
+}
+
+/// All layerrule properties with their defaults
+/// Based on Hyprland's LayerRule implementation
+fn layerrule_properties() -> Vec<(String, ConfigValue)> {
+    // Enable property (default: 1)
+    let mut properties = vec![("enable".to_string(), ConfigValue::Int(1))];
+
+    // Match properties for layer surfaces
+    let match_props = [
+        "namespace", // Layer namespace
+        "address",   // Layer address
+        "class",     // Associated class
+        "title",     // Associated title
+        "monitor",   // Monitor name
+        "layer",     // Layer level (background, bottom, top, overlay)
+    ];
+
+    properties.extend(match_props.into_iter().map(|prop| {
+        (
+            format!("match:{}", prop),
+            ConfigValue::String(String::new()),
+        )
+    }));
+
+    // Effect properties for layer surfaces
+    let effect_props = [
+        "blur",           // Enable blur
+        "blur_popups",    // Blur popups (new in 0.53.0)
+        "ignorealpha",    // Ignore alpha
+        "ignore_alpha",   // Alias for ignorealpha (new in 0.53.0)
+        "ignorezero",     // Ignore zero alpha
+        "animation",      // Animation style
+        "noanim",         // Disable animations
+        "no_anim",        // Alias for noanim (new in 0.53.0)
+        "xray",           // X-ray mode
+        "dim_around",     // Dim around layer (new in 0.53.0)
+        "order",          // Layer order (new in 0.53.0)
+        "above_lock",     // Display above lock screen (new in 0.53.0)
+        "no_screen_share", // Exclude from screen share (new in 0.53.0)
+        "noscreenshare",  // Alias for no_screen_share
+    ];
+
+    properties.extend(
+        effect_props
+            .into_iter()
+            .map(|prop| (prop.to_string(), ConfigValue::String(String::new()))),
+    );
+
+    properties
 }
 
 /// High-level wrapper for Hyprland configuration
@@ -474,25 +2023,118 @@ impl<'a> RuleInstance<'a> {
 /// }
 /// # }
 /// ```
+#[derive(Clone)]
 pub struct Hyprland {
     config: Config,
+    version: Option<HyprlandVersion>,
 }
 
 impl Hyprland {
     /// Create a new Hyprland configuration with default options
     pub fn new() -> Self {
         let mut config = Config::new();
-        Self::register_all_handlers(&mut config);
-        Self::register_all_special_categories(&mut config);
-        Self { config }
+        config.apply_profile(&HyprlandProfile);
+        Self {
+            config,
+            version: None,
+        }
     }
 
     /// Create a new Hyprland configuration with custom options
     pub fn with_options(options: ConfigOptions) -> Self {
         let mut config = Config::with_options(options);
-        Self::register_all_handlers(&mut config);
-        Self::register_all_special_categories(&mut config);
-        Self { config }
+        config.apply_profile(&HyprlandProfile);
+        Self {
+            config,
+            version: None,
+        }
+    }
+
+    /// Create a Hyprland configuration for a specific release, e.g. `"0.53"`
+    ///
+    /// Applies a [`HyprlandVersionProfile`]: only the handlers and rule blocks of that
+    /// release are registered, its known options get their defaults and type checks, and
+    /// renamed keywords are reported in [`Config::deprecations`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use hyprlang::Hyprland;
+    ///
+    /// let mut hypr = Hyprland::for_version("0.53").unwrap();
+    /// hypr.parse("decoration {\n    drop_shadow = 1\n}").unwrap();
+    ///
+    /// assert_eq!(hypr.general_layout().unwrap(), "dwindle");
+    /// assert_eq!(hypr.unsupported_options(), vec!["decoration:drop_shadow"]);
+    /// ```
+    pub fn for_version(version: &str) -> ParseResult<Self> {
+        let version = HyprlandVersion::parse(version)?;
+        let mut config = Config::new();
+        config.apply_profile(&HyprlandVersionProfile::new(version));
+        Ok(Self {
+            config,
+            version: Some(version),
+        })
+    }
+
+    /// The release targeted with [`for_version`](Self::for_version), if any
+    pub fn version(&self) -> Option<HyprlandVersion> {
+        self.version
+    }
+
+    /// Options set in the config that the targeted release does not have, sorted
+    ///
+    /// Only keys in the [`HyprlandOption`] table are checked. Empty when no release is
+    /// targeted.
+    pub fn unsupported_options(&self) -> Vec<&'static str> {
+        let Some(version) = self.version else {
+            return Vec::new();
+        };
+
+        let mut unsupported: Vec<&'static str> = HyprlandOption::all()
+            .into_iter()
+            .filter(|option| !option.available_in(version))
+            .filter(|option| {
+                self.config
+                    .entry(option.key)
+                    .is_some_and(|entry| entry.set_by_user)
+            })
+            .map(|option| option.key)
+            .collect();
+        unsupported.sort_unstable();
+        unsupported
+    }
+
+    /// Validate `monitor` lines with [`Monitor::validate`] and binds with
+    /// [`Keybind::validate`] against [`Keybind::DISPATCHERS`]
+    ///
+    /// The validators run while parsing and checking, before any handler, so applications
+    /// keep their own handlers for acting on the calls. To use another dispatcher list,
+    /// register a validator with [`Config::add_handler_validator`] instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use hyprlang::Hyprland;
+    ///
+    /// let mut hypr = Hyprland::new();
+    /// hypr.add_validators();
+    ///
+    /// let diagnostics = hypr
+    ///     .config()
+    ///     .check("bind = SUPER, Q, exec, kitty\nbindl = , XF86AudioMute, exce, mute\n");
+    /// assert_eq!(diagnostics.len(), 1);
+    /// assert_eq!(diagnostics[0].line, Some(2));
+    /// assert!(diagnostics[0].message.contains("unknown dispatcher 'exce'"));
+    /// ```
+    pub fn add_validators(&mut self) {
+        self.config
+            .add_handler_validator("monitor", |ctx| Monitor::validate(&ctx.value));
+        self.config.add_handler_validator("bind", |ctx| {
+            let suffix = ctx.flags.as_deref().unwrap_or_default();
+            let flags = BindFlag::parse_suffix(suffix).unwrap_or_default();
+            Keybind::parse(&flags, &ctx.value).validate(Keybind::DISPATCHERS)
+        });
     }
 
     /// Get a reference to the underlying Config
@@ -515,259 +2157,6 @@ impl Hyprland {
         self.config.parse_file(path)
     }
 
-    /// Register all Hyprland-specific handlers
-    fn register_all_handlers(config: &mut Config) {
-        // Root-level handlers
-        let root_handlers = [
-            "monitor",
-            "env",
-            "bind",
-            "bindu", // Universal bind flag for submaps (new in 0.53.0)
-            "bindm",
-            "bindel",
-            "bindl",
-            "bindr",
-            "binde",
-            "bindn",
-            "windowrule",
-            "windowrulev2",
-            "layerrule",
-            "workspace",
-            "exec",
-            "exec-once",
-            "source",
-            "blurls",
-            "plugin",
-        ];
-
-        for handler in root_handlers {
-            config.register_handler_fn(handler, |_ctx| Ok(()));
-        }
-
-        // Category-specific handlers
-        config.register_category_handler_fn("animations", "animation", |_ctx| Ok(()));
-        config.register_category_handler_fn("animations", "bezier", |_ctx| Ok(()));
-    }
-
-    /// Register all Hyprland-specific special categories
-    fn register_all_special_categories(config: &mut Config) {
-        // Device is a keyed category: device[name] { ... }
-        config.register_special_category(SpecialCategoryDescriptor::keyed("device", "name"));
-
-        // Monitor is a keyed category: monitor[name] { ... } (for per-monitor settings)
-        config.register_special_category(SpecialCategoryDescriptor::keyed("monitor", "name"));
-
-        // Windowrule v3: windowrule { name = ... }
-        config.register_special_category(SpecialCategoryDescriptor::keyed("windowrule", "name"));
-        Self::register_windowrule_properties(config);
-
-        // Layerrule v2: layerrule { name = ... }
-        config.register_special_category(SpecialCategoryDescriptor::keyed("layerrule", "name"));
-        Self::register_layerrule_properties(config);
-    }
-
-    /// Register all windowrule match and effect properties
-    /// Based on Hyprland's Rule.hpp and WindowRuleEffectContainer.hpp
-    fn register_windowrule_properties(config: &mut Config) {
-        // Enable property (default: 1)
-        config.register_special_category_value("windowrule", "enable", ConfigValue::Int(1));
-
-        // Match properties (19 total from Rule.hpp enum eRuleProperty)
-        let match_props = [
-            "class",                    // Window class (regex)
-            "title",                    // Window title (regex)
-            "initial_class",            // Initial class on creation
-            "initial_title",            // Initial title on creation
-            "floating",                 // Is floating (bool)
-            "tag",                      // Window tag
-            "xwayland",                 // Is XWayland (bool)
-            "fullscreen",               // Is fullscreen (bool)
-            "pinned",                   // Is pinned (bool)
-            "focus",                    // Is focused (bool)
-            "group",                    // Is in group (bool)
-            "modal",                    // Is modal (bool)
-            "fullscreenstate_internal", // Internal fullscreen state
-            "fullscreenstate_client",   // Client fullscreen state
-            "on_workspace",             // On specific workspace
-            "content",                  // Content type
-            "xdg_tag",                  // XDG tag
-            "namespace",                // Namespace (for layer surfaces)
-            "exec_token",               // Exec token
-        ];
-
-        for prop in match_props {
-            config.register_special_category_value(
-                "windowrule",
-                format!("match:{}", prop),
-                ConfigValue::String(String::new()),
-            );
-        }
-
-        // Match property aliases for Hyprland v3 naming (new in 0.53.0)
-        // These provide alternative names that match Hyprland's actual property names
-        let match_aliases = [
-            "float",                   // Alias for "floating"
-            "pin",                     // Alias for "pinned"
-            "workspace",               // Alias for "on_workspace"
-            "fullscreen_state_internal", // Alias for "fullscreenstate_internal"
-            "fullscreen_state_client",   // Alias for "fullscreenstate_client"
-        ];
-
-        for alias in match_aliases {
-            config.register_special_category_value(
-                "windowrule",
-                format!("match:{}", alias),
-                ConfigValue::String(String::new()),
-            );
-        }
-
-        // Effect properties (60+ from WindowRuleEffectContainer.hpp)
-        // Note: Many properties have aliases (e.g., border_color / bordercolor)
-        let effect_props = [
-            // Static effects (applied once)
-            "float",
-            "tile",
-            "fullscreen",
-            "maximize",
-            "fullscreenstate",
-            "fullscreen_state", // Alias for fullscreenstate (new in 0.53.0)
-            "move",
-            "size",
-            "center",
-            "pseudo",
-            "monitor",
-            "workspace",
-            "noinitialfocus",
-            "no_initial_focus", // Alias for noinitialfocus (new in 0.53.0)
-            "pin",
-            "group",
-            "suppressevent",
-            "suppress_event", // Alias for suppressevent (new in 0.53.0)
-            "content",
-            "noclosefor",
-            "no_close_for", // Alias for noclosefor (new in 0.53.0)
-            // Dynamic effects (continuously applied)
-            "rounding",
-            "rounding_power",
-            "persistent_size",
-            "animation",
-            "border_color",
-            "bordercolor", // Aliases
-            "idle_inhibit",
-            "idleinhibit", // Aliases
-            "opacity",
-            "tag",
-            "max_size",
-            "maxsize", // Aliases
-            "min_size",
-            "minsize", // Aliases
-            "border_size",
-            "bordersize", // Aliases
-            "allows_input",
-            "dim_around",
-            "decorate",
-            "focus_on_activate",
-            "keep_aspect_ratio",
-            "keepaspectratio", // Aliases
-            "nearest_neighbor",
-            "nearestneighbor", // Aliases
-            "no_anim",
-            "noanim", // Aliases
-            "no_blur",
-            "noblur", // Aliases
-            "no_dim",
-            "nodim", // Aliases
-            "no_focus",
-            "nofocus", // Aliases
-            "no_follow_mouse",
-            "nofollowmouse", // Aliases
-            "no_max_size",
-            "nomaxsize", // Aliases
-            "no_shadow",
-            "noshadow", // Aliases
-            "no_shortcuts_inhibit",
-            "noshortcutsinhibit", // Aliases
-            "opaque",
-            "force_rgbx",
-            "forcergbx", // Aliases
-            "sync_fullscreen",
-            "syncfullscreen", // Aliases
-            "immediate",
-            "xray",
-            "render_unfocused",
-            "renderunfocused", // Aliases
-            "no_screen_share",
-            "noscreenshare", // Aliases
-            "no_vrr",
-            "novrr", // Aliases
-            "scroll_mouse",
-            "scrollmouse", // Aliases
-            "scroll_touchpad",
-            "scrolltouchpad", // Aliases
-            "stay_focused",
-            "stayfocused", // Aliases
-        ];
-
-        for prop in effect_props {
-            config.register_special_category_value(
-                "windowrule",
-                prop,
-                ConfigValue::String(String::new()),
-            );
-        }
-    }
-
-    /// Register all layerrule properties
-    /// Based on Hyprland's LayerRule implementation
-    fn register_layerrule_properties(config: &mut Config) {
-        // Enable property (default: 1)
-        config.register_special_category_value("layerrule", "enable", ConfigValue::Int(1));
-
-        // Match properties for layer surfaces
-        let match_props = [
-            "namespace", // Layer namespace
-            "address",   // Layer address
-            "class",     // Associated class
-            "title",     // Associated title
-            "monitor",   // Monitor name
-            "layer",     // Layer level (background, bottom, top, overlay)
-        ];
-
-        for prop in match_props {
-            config.register_special_category_value(
-                "layerrule",
-                format!("match:{}", prop),
-                ConfigValue::String(String::new()),
-            );
-        }
-
-        // Effect properties for layer surfaces
-        let effect_props = [
-            "blur",           // Enable blur
-            "blur_popups",    // Blur popups (new in 0.53.0)
-            "ignorealpha",    // Ignore alpha
-            "ignore_alpha",   // Alias for ignorealpha (new in 0.53.0)
-            "ignorezero",     // Ignore zero alpha
-            "animation",      // Animation style
-            "noanim",         // Disable animations
-            "no_anim",        // Alias for noanim (new in 0.53.0)
-            "xray",           // X-ray mode
-            "dim_around",     // Dim around layer (new in 0.53.0)
-            "order",          // Layer order (new in 0.53.0)
-            "above_lock",     // Display above lock screen (new in 0.53.0)
-            "no_screen_share", // Exclude from screen share (new in 0.53.0)
-            "noscreenshare",  // Alias for no_screen_share
-        ];
-
-        for prop in effect_props {
-            config.register_special_category_value(
-                "layerrule",
-                prop,
-                ConfigValue::String(String::new()),
-            );
-        }
-    }
-
     // ==================== General Config ====================
 
     /// Get general:border_size
@@ -793,14 +2182,22 @@ impl Hyprland {
         }
     }
 
-    /// Get general:col.active_border
+    /// Get general:col.active_border (errors if it is a gradient)
     pub fn general_active_border_color(&self) -> ParseResult<Color> {
-        self.config.get_color("general:col.active_border")
+        self.border_color("general:col.active_border")
     }
 
-    /// Get general:col.inactive_border
+    /// Get general:col.inactive_border (errors if it is a gradient)
     pub fn general_inactive_border_color(&self) -> ParseResult<Color> {
-        self.config.get_color("general:col.inactive_border")
+        self.border_color("general:col.inactive_border")
+    }
+
+    /// A border color, which version profiles register as a string
+    fn border_color(&self, key: &str) -> ParseResult<Color> {
+        match self.config.get(key)? {
+            ConfigValue::String(s) => Color::parse(s),
+            value => value.as_color(),
+        }
     }
 
     /// Get general:layout
@@ -989,46 +2386,315 @@ impl Hyprland {
 
     // ==================== Handler Calls ====================
 
-    /// Get all bind definitions
+    /// Get all bind definitions without flags
     pub fn all_binds(&self) -> Vec<&String> {
-        self.config
-            .get_handler_calls("bind")
-            .map(|calls| calls.iter().collect())
-            .unwrap_or_default()
+        self.binds_with_flags(&[])
     }
 
     /// Get all bindm definitions
     pub fn all_bindm(&self) -> Vec<&String> {
-        self.config
-            .get_handler_calls("bindm")
-            .map(|calls| calls.iter().collect())
-            .unwrap_or_default()
+        self.binds_with_flags(&[BindFlag::Mouse])
     }
 
     /// Get all bindel definitions
     pub fn all_bindel(&self) -> Vec<&String> {
-        self.config
-            .get_handler_calls("bindel")
-            .map(|calls| calls.iter().collect())
-            .unwrap_or_default()
+        self.binds_with_flags(&[BindFlag::Repeat, BindFlag::Locked])
     }
 
     /// Get all bindl definitions
     pub fn all_bindl(&self) -> Vec<&String> {
+        self.binds_with_flags(&[BindFlag::Locked])
+    }
+
+    /// Get all bindu definitions (universal submap bindings, new in 0.53.0)
+    ///
+    /// Universal binds remain active across all submaps.
+    pub fn all_bindu(&self) -> Vec<&String> {
+        self.binds_with_flags(&[BindFlag::Universal])
+    }
+
+    /// Get the values of binds with exactly the given flags, in any order
+    /// (so `bindel` and `bindle` are the same)
+    pub fn binds_with_flags(&self, flags: &[BindFlag]) -> Vec<&String> {
+        let same_flags = |suffix: &str| {
+            BindFlag::parse_suffix(suffix).is_some_and(|written| {
+                written.iter().all(|flag| flags.contains(flag))
+                    && flags.iter().all(|flag| written.contains(flag))
+            })
+        };
+
+        self.config
+            .get_handler_call_records("bind")
+            .map(|records| {
+                records
+                    .iter()
+                    .filter(|record| same_flags(record.flags.as_deref().unwrap_or_default()))
+                    .map(|record| &record.value)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Get every keybind of the `bind` family, in the order they were written
+    ///
+    /// ```rust
+    /// use hyprlang::{BindFlag, Hyprland};
+    ///
+    /// let mut hypr = Hyprland::new();
+    /// hypr.parse("bind = SUPER, Q, exec, kitty\nbindlr = , XF86AudioMute, exec, mute").unwrap();
+    ///
+    /// let binds = hypr.keybinds();
+    /// assert_eq!(binds.len(), 2);
+    /// assert!(binds[1].has_flag(BindFlag::Locked) && binds[1].has_flag(BindFlag::Release));
+    /// assert_eq!(binds[1].key, "XF86AudioMute");
+    /// ```
+    pub fn keybinds(&self) -> Vec<Keybind> {
         self.config
-            .get_handler_calls("bindl")
-            .map(|calls| calls.iter().collect())
+            .get_handler_call_records("bind")
+            .map(|records| {
+                records
+                    .iter()
+                    .map(|record| {
+                        let suffix = record.flags.as_deref().unwrap_or_default();
+                        let flags = BindFlag::parse_suffix(suffix).unwrap_or_default();
+                        Keybind::parse(&flags, &record.value)
+                    })
+                    .collect()
+            })
             .unwrap_or_default()
     }
 
-    /// Get all bindu definitions (universal submap bindings, new in 0.53.0)
+    /// Find binds, monitors and window rules that are defined more than once
     ///
-    /// Universal binds remain active across all submaps.
-    pub fn all_bindu(&self) -> Vec<&String> {
-        self.config
-            .get_handler_calls("bindu")
-            .map(|calls| calls.iter().collect())
-            .unwrap_or_default()
+    /// - Binds conflict when they use the same modifiers and key in the same submap,
+    ///   whichever `bind*` variant they are written with. Modifiers compare as Hyprland reads
+    ///   them (`SUPER_SHIFT` is `SHIFT SUPER`) and keys ignore case. Release (`r`) and
+    ///   mouse (`m`) binds fire on other events, so they only conflict with binds of the
+    ///   same kind, and universal (`u`) binds conflict with binds in every submap.
+    /// - `monitor = ...` lines conflict when they name the same monitor.
+    /// - `windowrule[name]` blocks conflict when all their properties other than `name`
+    ///   are the same.
+    ///
+    /// Bind conflicts come first, then monitors, then window rules, each in the order
+    /// their first definition was parsed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use hyprlang::{ConflictKind, Hyprland};
+    ///
+    /// let mut hypr = Hyprland::new();
+    /// hypr.parse(
+    ///     "bind = SUPER, Q, exec, kitty\n\
+    ///      bindl = SUPER, q, killactive\n\
+    ///      submap = resize\n\
+    ///      bind = SUPER, Q, exec, foot\n\
+    ///      submap = reset\n",
+    /// )
+    /// .unwrap();
+    ///
+    /// let conflicts = hypr.conflicts();
+    /// assert_eq!(conflicts.len(), 1);
+    /// assert_eq!(conflicts[0].kind, ConflictKind::Keybind { submap: None });
+    /// assert_eq!(conflicts[0].subject, "SUPER, Q");
+    /// assert_eq!(conflicts[0].sites[1].line, Some(2));
+    /// ```
+    pub fn conflicts(&self) -> Vec<Conflict> {
+        let mut conflicts = self.keybind_conflicts();
+        conflicts.extend(self.monitor_conflicts());
+        conflicts.extend(self.windowrule_conflicts());
+        conflicts
+    }
+
+    fn keybind_conflicts(&self) -> Vec<Conflict> {
+        // Binds that fire on the same event: (mods, key, release, mouse)
+        type Trigger = (String, String, bool, bool);
+
+        struct Group {
+            submap: Option<String>,
+            trigger: Trigger,
+            key: String,
+            sites: Vec<(u64, ConflictSite)>,
+        }
+
+        let mut submap = None;
+        let mut groups: Vec<Group> = Vec::new();
+        let mut universal: Vec<(Trigger, String, u64, ConflictSite)> = Vec::new();
+        for (handler, record) in self
+            .config
+            .handler_call_records_in_order(&["bind", "submap"])
+        {
+            if handler == "submap" {
+                let name = record.value.trim();
+                submap = (name != "reset" && !name.is_empty()).then(|| name.to_string());
+                continue;
+            }
+
+            let flags = BindFlag::parse_suffix(record.flags.as_deref().unwrap_or_default())
+                .unwrap_or_default();
+            let bind = Keybind::parse(&flags, &record.value);
+            if bind.key.is_empty() {
+                continue;
+            }
+            let trigger = (
+                normalize_mods(&bind.mods),
+                bind.key.to_lowercase(),
+                bind.has_flag(BindFlag::Release),
+                bind.has_flag(BindFlag::Mouse),
+            );
+            let site = ConflictSite {
+                text: format!("{} = {}", bind.keyword(), record.value),
+                file: record.file.clone(),
+                line: record.line,
+            };
+            if bind.has_flag(BindFlag::Universal) {
+                universal.push((trigger, bind.key, record.sequence, site));
+                continue;
+            }
+            match groups
+                .iter_mut()
+                .find(|group| group.submap == submap && group.trigger == trigger)
+            {
+                Some(group) => group.sites.push((record.sequence, site)),
+                None => groups.push(Group {
+                    submap: submap.clone(),
+                    trigger,
+                    key: bind.key,
+                    sites: vec![(record.sequence, site)],
+                }),
+            }
+        }
+
+        // Universal binds are active in every submap
+        for (trigger, key, sequence, site) in universal {
+            let mut joined = false;
+            for group in groups.iter_mut().filter(|group| group.trigger == trigger) {
+                group.sites.push((sequence, site.clone()));
+                joined = true;
+            }
+            if !joined {
+                groups.push(Group {
+                    submap: None,
+                    trigger,
+                    key,
+                    sites: vec![(sequence, site)],
+                });
+            }
+        }
+
+        for group in &mut groups {
+            group.sites.sort_by_key(|(sequence, _)| *sequence);
+        }
+        groups.retain(|group| group.sites.len() > 1);
+        groups.sort_by_key(|group| group.sites[0].0);
+        groups
+            .into_iter()
+            .map(|group| Conflict {
+                kind: ConflictKind::Keybind {
+                    submap: group.submap,
+                },
+                subject: format!("{}, {}", group.trigger.0, group.key),
+                sites: group.sites.into_iter().map(|(_, site)| site).collect(),
+            })
+            .collect()
+    }
+
+    fn monitor_conflicts(&self) -> Vec<Conflict> {
+        let mut groups: Vec<(String, Vec<ConflictSite>)> = Vec::new();
+        for record in self
+            .config
+            .get_handler_call_records("monitor")
+            .into_iter()
+            .flatten()
+        {
+            let name = Monitor::parse(&record.value).name;
+            let site = ConflictSite {
+                text: format!("monitor = {}", record.value),
+                file: record.file.clone(),
+                line: record.line,
+            };
+            match groups.iter_mut().find(|(existing, _)| *existing == name) {
+                Some((_, sites)) => sites.push(site),
+                None => groups.push((name, vec![site])),
+            }
+        }
+
+        groups
+            .into_iter()
+            .filter(|(_, sites)| sites.len() > 1)
+            .map(|(name, sites)| Conflict {
+                kind: ConflictKind::Monitor,
+                subject: name,
+                sites,
+            })
+            .collect()
+    }
+
+    fn windowrule_conflicts(&self) -> Vec<Conflict> {
+        let mut groups: Vec<(String, Vec<(u64, ConflictSite)>)> = Vec::new();
+        for instance in self
+            .config
+            .find_special_category_instances("windowrule", |_| true)
+        {
+            let Some(name) = instance.key.clone() else {
+                continue;
+            };
+            let mut written: Vec<_> = instance
+                .values
+                .iter()
+                .filter(|(property, entry)| entry.set_by_user && *property != "name")
+                .collect();
+            if written.is_empty() {
+                continue;
+            }
+            written.sort_by(|a, b| a.0.cmp(b.0));
+
+            // Use the source text so `1` and `true` read as written
+            let properties: Vec<String> = written
+                .iter()
+                .map(|(property, entry)| {
+                    let value = self
+                        .config
+                        .special_category_text("windowrule", &name, property)
+                        .unwrap_or_else(|| entry.raw.clone());
+                    format!("{} = {}", property, value)
+                })
+                .collect();
+            let first = written
+                .iter()
+                .map(|(_, entry)| *entry)
+                .min_by_key(|entry| entry.generation);
+            let site = ConflictSite {
+                text: name,
+                file: first.and_then(|entry| entry.source_file.clone()),
+                line: first.and_then(|entry| entry.line),
+            };
+            let order = first.map_or(0, |entry| entry.generation);
+
+            let subject = properties.join(", ");
+            match groups.iter_mut().find(|(existing, _)| *existing == subject) {
+                Some((_, sites)) => sites.push((order, site)),
+                None => groups.push((subject, vec![(order, site)])),
+            }
+        }
+
+        let mut conflicts: Vec<_> = groups
+            .into_iter()
+            .filter(|(_, sites)| sites.len() > 1)
+            .map(|(subject, mut sites)| {
+                sites.sort_by_key(|(order, _)| *order);
+                (sites[0].0, subject, sites)
+            })
+            .collect();
+        conflicts.sort_by_key(|(order, _, _)| *order);
+        conflicts
+            .into_iter()
+            .map(|(_, subject, sites)| Conflict {
+                kind: ConflictKind::WindowRule,
+                subject,
+                sites: sites.into_iter().map(|(_, site)| site).collect(),
+            })
+            .collect()
     }
 
     /// Get all windowrule definitions (v1 handler-based syntax)
@@ -1104,7 +2770,8 @@ impl Hyprland {
     /// }
     /// ```
     ///
-    /// Returns `vec!["my-float-rule", "center-dialogs"]`
+    /// Returns `vec!["my-float-rule", "center-dialogs"]`, in the order the blocks were
+    /// declared, since rule precedence in Hyprland depends on that order.
     ///
     /// Use with [`get_windowrule()`](Self::get_windowrule) to iterate all rules:
     /// ```rust
@@ -1126,6 +2793,84 @@ impl Hyprland {
         self.config.list_special_category_keys("windowrule")
     }
 
+    /// Names of the windowrules whose `match:*` fields accept a window, in the order
+    /// they were declared
+    ///
+    /// See [`WindowProperties`] for how unknown properties are treated. Rules with
+    /// `enable = false` are skipped. Fails if a matcher is not a valid regex.
+    ///
+    /// ```rust
+    /// use hyprlang::{Hyprland, WindowProperties};
+    ///
+    /// let mut hypr = Hyprland::new();
+    /// hypr.parse(r#"
+    ///     windowrule[float-terminals] {
+    ///         match:class = kitty|foot
+    ///         float = true
+    ///     }
+    ///     windowrule[no-kitty-blur] {
+    ///         match:class = negative:kitty
+    ///         no_blur = true
+    ///     }
+    /// "#).unwrap();
+    ///
+    /// let window = WindowProperties {
+    ///     class: Some("foot".to_string()),
+    ///     title: Some("~".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     hypr.windowrules_matching(&window).unwrap(),
+    ///     vec!["float-terminals", "no-kitty-blur"]
+    /// );
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn windowrules_matching(&self, window: &WindowProperties) -> ParseResult<Vec<String>> {
+        let mut error = None;
+        let rules = self
+            .config
+            .find_special_category_instances("windowrule", |instance| {
+                let Some(name) = instance.key.as_deref() else {
+                    return false;
+                };
+                let enabled = instance
+                    .get("enable")
+                    .is_none_or(|entry| entry.value.as_int().map_or(true, |enable| enable != 0));
+                let matchers: Vec<_> = instance
+                    .values
+                    .iter()
+                    .filter(|(_, entry)| entry.set_by_user)
+                    .filter_map(|(key, entry)| Some((key.strip_prefix("match:")?, &entry.value)))
+                    .collect();
+
+                match window.matches(name, &matchers) {
+                    Ok(accepted) => enabled && accepted == Some(true),
+                    Err(e) => {
+                        error.get_or_insert(e);
+                        false
+                    }
+                }
+            });
+
+        match error {
+            Some(e) => Err(e),
+            None => Ok(rules
+                .into_iter()
+                .filter_map(|rule| rule.key.clone())
+                .collect()),
+        }
+    }
+
+    /// Names of the windowrules whose `match:class` accepts `class`
+    ///
+    /// Shorthand for [`windowrules_matching`](Self::windowrules_matching) with only the
+    /// class known: rules matching on other properties as well are included, rules without
+    /// a `match:class` are not.
+    #[cfg(feature = "regex")]
+    pub fn windowrules_matching_class(&self, class: &str) -> ParseResult<Vec<String>> {
+        self.windowrules_matching(&WindowProperties::with_class(class))
+    }
+
     /// Get a specific windowrule by name (v3 special category syntax)
     ///
     /// Returns a [`RuleInstance`] with all properties of a windowrule block:
@@ -1217,7 +2962,8 @@ impl Hyprland {
     /// }
     /// ```
     ///
-    /// Returns `vec!["blur-waybar", "dim-notifications"]`
+    /// Returns `vec!["blur-waybar", "dim-notifications"]`, in the order the blocks were
+    /// declared.
     ///
     /// Use with [`get_layerrule()`](Self::get_layerrule) to iterate all rules:
     /// ```rust
@@ -1272,6 +3018,96 @@ impl Hyprland {
             .map(RuleInstance::new)
     }
 
+    /// All layer rules, from `layerrule = ...` lines and `layerrule[name]` blocks
+    ///
+    /// Lines come first, in the order they were parsed, followed by blocks in the order
+    /// they were declared. See [`LayerRule`] for how both syntaxes are normalized.
+    ///
+    /// ```rust
+    /// use hyprlang::Hyprland;
+    ///
+    /// let mut hypr = Hyprland::new();
+    /// hypr.parse(r#"
+    ///     layerrule = blur, waybar
+    ///     layerrule[dim-launcher] {
+    ///         match:namespace = rofi
+    ///         dim_around = true
+    ///     }
+    /// "#).unwrap();
+    ///
+    /// let rules = hypr.layerrules();
+    /// assert_eq!(rules[0].namespace, "waybar");
+    /// assert_eq!(rules[0].effects["blur"], "true");
+    /// assert_eq!(rules[1].name(), Some("dim-launcher"));
+    /// assert_eq!(rules[1].effects["dim_around"], "true");
+    /// assert_eq!(rules[1].line, Some(4));
+    /// ```
+    pub fn layerrules(&self) -> Vec<LayerRule> {
+        let mut rules: Vec<LayerRule> = self
+            .config
+            .get_handler_call_records("layerrule")
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .map(|(index, record)| LayerRule {
+                origin: RuleOrigin::Handler { index },
+                file: record.file.clone(),
+                line: record.line,
+                ..LayerRule::parse(&record.value)
+            })
+            .collect();
+
+        for instance in self
+            .config
+            .find_special_category_instances("layerrule", |_| true)
+        {
+            let Some(name) = instance.key.clone() else {
+                continue;
+            };
+            let mut written: Vec<_> = instance
+                .values
+                .iter()
+                .filter(|(_, entry)| entry.set_by_user)
+                .collect();
+            written.sort_by_key(|(_, entry)| entry.generation);
+
+            let first = written.first().map(|(_, entry)| *entry);
+            let mut rule = LayerRule {
+                namespace: String::new(),
+                matchers: BTreeMap::new(),
+                effects: BTreeMap::new(),
+                enabled: true,
+                file: first.and_then(|entry| entry.source_file.clone()),
+                line: first.and_then(|entry| entry.line),
+                origin: RuleOrigin::Block { name: name.clone() },
+            };
+            for (property, entry) in written {
+                // Use the source text so values like `true` aren't read as numbers
+                let value = self
+                    .config
+                    .special_category_text("layerrule", &name, property)
+                    .unwrap_or_else(|| entry.raw.clone());
+                match property.strip_prefix("match:") {
+                    Some("namespace") => rule.namespace = value,
+                    Some(matcher) => {
+                        rule.matchers.insert(matcher.to_string(), value);
+                    }
+                    None if property == "name" => {}
+                    None if property == "enable" => {
+                        rule.enabled = ConfigValue::parse_bool(&value).unwrap_or(true);
+                    }
+                    None => {
+                        let effect = layerrule_effect_name(property).to_string();
+                        rule.effects.insert(effect, value);
+                    }
+                }
+            }
+            rules.push(rule);
+        }
+
+        rules
+    }
+
     /// Get all workspace definitions
     pub fn all_workspaces(&self) -> Vec<&String> {
         self.config
@@ -1288,6 +3124,78 @@ impl Hyprland {
             .unwrap_or_default()
     }
 
+    /// Get all monitors, merging `monitor = ...` lines with `monitor[name] { ... }` blocks
+    ///
+    /// See [`Monitors`] for how the two sources are combined.
+    pub fn monitors(&self) -> Monitors {
+        let mut monitors: Vec<Monitor> = Vec::new();
+        for line in self.all_monitors() {
+            let monitor = Monitor::parse(line);
+            match monitors.iter_mut().find(|m| m.name == monitor.name) {
+                Some(existing) => *existing = monitor,
+                None => monitors.push(monitor),
+            }
+        }
+
+        let mut conflicts = Vec::new();
+        let mut block_names = self.config.list_special_category_keys("monitor");
+        block_names.sort();
+
+        for name in block_names {
+            let Ok(properties) = self.config.get_special_category("monitor", &name) else {
+                continue;
+            };
+            let index = match monitors.iter().position(|m| m.name == name) {
+                Some(index) => index,
+                None => {
+                    monitors.push(Monitor {
+                        name: name.clone(),
+                        ..Monitor::default()
+                    });
+                    monitors.len() - 1
+                }
+            };
+            let monitor = &mut monitors[index];
+            monitor.from_block = true;
+
+            let mut properties: Vec<_> = properties.into_iter().collect();
+            properties.sort_by(|a, b| a.0.cmp(&b.0));
+
+            for (property, value) in properties {
+                if property == "name" || property == "output" {
+                    continue;
+                }
+                // Use the source text so values like `0x0` aren't read as numbers
+                let value = self
+                    .config
+                    .special_category_text("monitor", &name, &property)
+                    .unwrap_or_else(|| value.to_string());
+                let value = match property.as_str() {
+                    "disabled" | "disable" => parse_disabled(&value).to_string(),
+                    _ => value,
+                };
+
+                if monitor.from_handler
+                    && let Some(handler_value) = monitor.property(&property)
+                    && handler_value != value
+                {
+                    conflicts.push(MonitorConflict {
+                        monitor: name.clone(),
+                        property: property.clone(),
+                        handler_value,
+                        block_value: value.clone(),
+                    });
+                }
+                monitor.set_property(&property, value);
+            }
+        }
+
+        Monitors {
+            monitors,
+            conflicts,
+        }
+    }
+
     /// Get all env definitions
     pub fn all_env(&self) -> Vec<&String> {
         self.config
@@ -1296,6 +3204,57 @@ impl Hyprland {
             .unwrap_or_default()
     }
 
+    /// Get all env definitions parsed into name/value pairs
+    ///
+    /// ```rust
+    /// # use hyprlang::Hyprland;
+    /// let mut hypr = Hyprland::new();
+    /// hypr.parse("env = XCURSOR_SIZE,24\nenv = XDG_CURRENT_DESKTOP, Hyprland,sway").unwrap();
+    ///
+    /// let envs = hypr.parsed_env();
+    /// assert_eq!(envs[0].name, "XCURSOR_SIZE");
+    /// assert_eq!(envs[0].value, "24");
+    /// assert_eq!(envs[1].value, "Hyprland,sway");
+    /// ```
+    pub fn parsed_env(&self) -> Vec<EnvVar> {
+        self.all_env()
+            .into_iter()
+            .map(|e| EnvVar::parse(e))
+            .collect()
+    }
+
+    /// Set an environment variable, updating the existing `env` line or appending a new one
+    ///
+    /// If several `env` lines define the same name, the last one (the one that wins in
+    /// Hyprland) is updated.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mutation")] {
+    /// # use hyprlang::Hyprland;
+    /// let mut hypr = Hyprland::new();
+    /// hypr.parse("env = GTK_THEME,Breeze").unwrap();
+    ///
+    /// hypr.set_env("GTK_THEME", "Adwaita").unwrap();
+    /// hypr.set_env("XCURSOR_SIZE", "24").unwrap();
+    ///
+    /// let output = hypr.config().serialize();
+    /// assert!(output.contains("env = GTK_THEME,Adwaita"));
+    /// assert!(output.contains("env = XCURSOR_SIZE,24"));
+    /// # }
+    /// ```
+    #[cfg(feature = "mutation")]
+    pub fn set_env(&mut self, name: &str, value: &str) -> ParseResult<()> {
+        let line = EnvVar::new(name, value).to_string();
+
+        match self.parsed_env().iter().rposition(|env| env.name == name) {
+            Some(index) => self
+                .config
+                .update_handler_call("env", index, line)
+                .map(|_| ()),
+            None => self.config.add_handler_call("env", line),
+        }
+    }
+
     /// Get all exec-once definitions
     pub fn all_exec_once(&self) -> Vec<&String> {
         self.config
@@ -1312,6 +3271,173 @@ impl Hyprland {
             .unwrap_or_default()
     }
 
+    /// Add an exec-once entry unless the same command is already registered
+    ///
+    /// Returns `true` if the entry was added and `false` if it was already present.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mutation")] {
+    /// # use hyprlang::Hyprland;
+    /// let mut hypr = Hyprland::new();
+    /// hypr.parse("exec-once = waybar").unwrap();
+    ///
+    /// assert!(!hypr.add_exec_once("waybar").unwrap());
+    /// assert!(hypr.add_exec_once("dunst").unwrap());
+    /// assert_eq!(hypr.all_exec_once(), vec!["waybar", "dunst"]);
+    /// # }
+    /// ```
+    #[cfg(feature = "mutation")]
+    pub fn add_exec_once(&mut self, cmd: &str) -> ParseResult<bool> {
+        self.add_unique_handler_call("exec-once", cmd)
+    }
+
+    /// Add an exec entry unless the same command is already registered
+    ///
+    /// Returns `true` if the entry was added and `false` if it was already present.
+    #[cfg(feature = "mutation")]
+    pub fn add_exec(&mut self, cmd: &str) -> ParseResult<bool> {
+        self.add_unique_handler_call("exec", cmd)
+    }
+
+    /// Remove every exec-once entry whose command contains `pattern`
+    ///
+    /// Returns the removed commands in their original order.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mutation")] {
+    /// # use hyprlang::Hyprland;
+    /// let mut hypr = Hyprland::new();
+    /// hypr.parse("exec-once = waybar\nexec-once = nm-applet --indicator\nexec-once = dunst").unwrap();
+    ///
+    /// let removed = hypr.remove_exec_once_matching("applet").unwrap();
+    /// assert_eq!(removed, vec!["nm-applet --indicator"]);
+    /// assert_eq!(hypr.all_exec_once(), vec!["waybar", "dunst"]);
+    /// # }
+    /// ```
+    #[cfg(feature = "mutation")]
+    pub fn remove_exec_once_matching(&mut self, pattern: &str) -> ParseResult<Vec<String>> {
+        self.remove_handler_calls_matching("exec-once", pattern)
+    }
+
+    /// Remove every exec entry whose command contains `pattern`
+    ///
+    /// Returns the removed commands in their original order.
+    #[cfg(feature = "mutation")]
+    pub fn remove_exec_matching(&mut self, pattern: &str) -> ParseResult<Vec<String>> {
+        self.remove_handler_calls_matching("exec", pattern)
+    }
+
+    /// Move the exec-once entry at `from` so that it ends up at position `to`
+    ///
+    /// Entries in between shift by one. The existing lines are rewritten in place, so
+    /// the entries keep their position relative to the rest of the document.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mutation")] {
+    /// # use hyprlang::Hyprland;
+    /// let mut hypr = Hyprland::new();
+    /// hypr.parse("exec-once = waybar\nexec-once = dunst\nexec-once = hyprpaper").unwrap();
+    ///
+    /// hypr.move_exec_once(2, 0).unwrap();
+    /// assert_eq!(hypr.all_exec_once(), vec!["hyprpaper", "waybar", "dunst"]);
+    /// # }
+    /// ```
+    #[cfg(feature = "mutation")]
+    pub fn move_exec_once(&mut self, from: usize, to: usize) -> ParseResult<()> {
+        let calls = self
+            .config
+            .get_handler_calls("exec-once")
+            .cloned()
+            .ok_or_else(|| ConfigError::handler("exec-once", "no calls found"))?;
+
+        if from >= calls.len() || to >= calls.len() {
+            return Err(ConfigError::custom("index out of bounds"));
+        }
+
+        let mut reordered = calls.clone();
+        let entry = reordered.remove(from);
+        reordered.insert(to, entry);
+
+        for (index, (old, new)) in calls.iter().zip(reordered).enumerate() {
+            if *old != new {
+                self.config.update_handler_call("exec-once", index, new)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remove repeated exec-once entries, keeping the first occurrence of each command
+    ///
+    /// Returns the removed commands.
+    #[cfg(feature = "mutation")]
+    pub fn dedup_exec_once(&mut self) -> ParseResult<Vec<String>> {
+        let mut seen = std::collections::HashSet::new();
+        let duplicates: Vec<usize> = self
+            .all_exec_once()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, cmd)| !seen.insert(cmd.trim().to_string()))
+            .map(|(index, _)| index)
+            .collect();
+
+        self.remove_handler_call_indices("exec-once", &duplicates)
+    }
+
+    #[cfg(feature = "mutation")]
+    fn add_unique_handler_call(&mut self, handler: &str, cmd: &str) -> ParseResult<bool> {
+        let cmd = cmd.trim();
+        let exists = self
+            .config
+            .get_handler_calls(handler)
+            .is_some_and(|calls| calls.iter().any(|call| call.trim() == cmd));
+
+        if exists {
+            return Ok(false);
+        }
+
+        self.config.add_handler_call(handler, cmd.to_string())?;
+        Ok(true)
+    }
+
+    #[cfg(feature = "mutation")]
+    fn remove_handler_calls_matching(
+        &mut self,
+        handler: &str,
+        pattern: &str,
+    ) -> ParseResult<Vec<String>> {
+        let matching: Vec<usize> = self
+            .config
+            .get_handler_calls(handler)
+            .map(|calls| {
+                calls
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, call)| call.contains(pattern))
+                    .map(|(index, _)| index)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        self.remove_handler_call_indices(handler, &matching)
+    }
+
+    /// Remove handler calls at the given ascending indices, back to front so earlier
+    /// indices stay valid
+    #[cfg(feature = "mutation")]
+    fn remove_handler_call_indices(
+        &mut self,
+        handler: &str,
+        indices: &[usize],
+    ) -> ParseResult<Vec<String>> {
+        let mut removed = Vec::with_capacity(indices.len());
+        for &index in indices.iter().rev() {
+            removed.push(self.config.remove_handler_call(handler, index)?);
+        }
+        removed.reverse();
+        Ok(removed)
+    }
+
     // ==================== Variables ====================
 
     /// Get all variables defined in the config
@@ -1323,6 +3449,378 @@ impl Hyprland {
     pub fn get_variable(&self, name: &str) -> Option<&String> {
         self.variables().get(name)
     }
+
+    // ==================== Snapshot ====================
+
+    /// Read the whole config into an owned [`HyprlandSettings`]
+    ///
+    /// ```rust
+    /// use hyprlang::Hyprland;
+    ///
+    /// let mut hypr = Hyprland::new();
+    /// hypr.parse("general {\n    border_size = 2\n}\nbind = SUPER, Q, exec, kitty").unwrap();
+    ///
+    /// let settings = hypr.snapshot();
+    /// drop(hypr);
+    ///
+    /// assert_eq!(settings.general.border_size, Some(2));
+    /// assert_eq!(settings.general.layout, None);
+    /// assert_eq!(settings.binds[0].args, "kitty");
+    /// ```
+    pub fn snapshot(&self) -> HyprlandSettings {
+        let owned = |values: Vec<&String>| values.into_iter().cloned().collect();
+
+        HyprlandSettings {
+            general: GeneralSettings {
+                border_size: self.general_border_size().ok(),
+                gaps_in: self.general_gaps_in().ok(),
+                gaps_out: self.general_gaps_out().ok(),
+                active_border_color: self.general_active_border_color().ok(),
+                inactive_border_color: self.general_inactive_border_color().ok(),
+                layout: self.general_layout().ok().map(str::to_string),
+                allow_tearing: self.general_allow_tearing().ok(),
+                locale: self.general_locale().ok().map(str::to_string),
+            },
+            decoration: DecorationSettings {
+                rounding: self.decoration_rounding().ok(),
+                active_opacity: self.decoration_active_opacity().ok(),
+                inactive_opacity: self.decoration_inactive_opacity().ok(),
+                blur_enabled: self.decoration_blur_enabled().ok(),
+                blur_size: self.decoration_blur_size().ok(),
+                blur_passes: self.decoration_blur_passes().ok(),
+            },
+            input: InputSettings {
+                kb_layout: self.input_kb_layout().ok().map(str::to_string),
+                follow_mouse: self.input_follow_mouse().ok(),
+                sensitivity: self.input_sensitivity().ok(),
+                touchpad_natural_scroll: self.input_touchpad_natural_scroll().ok(),
+            },
+            misc: MiscSettings {
+                disable_hyprland_logo: self.misc_disable_hyprland_logo().ok(),
+                force_default_wallpaper: self.misc_force_default_wallpaper().ok(),
+            },
+            dwindle: DwindleSettings {
+                pseudotile: self.dwindle_pseudotile().ok(),
+                preserve_split: self.dwindle_preserve_split().ok(),
+            },
+            master: MasterSettings {
+                new_status: self.master_new_status().ok().map(str::to_string),
+            },
+            animations: AnimationSettings {
+                enabled: self.animations_enabled().ok(),
+                animations: owned(self.all_animations()),
+                beziers: owned(self.all_beziers()),
+            },
+            binds: self.keybinds(),
+            monitors: self.monitors().iter().cloned().collect(),
+            windowrules: self.rule_settings("windowrule"),
+            layerrules: self.rule_settings("layerrule"),
+            workspaces: owned(self.all_workspaces()),
+            env: self.parsed_env(),
+            exec_once: owned(self.all_exec_once()),
+            exec: owned(self.all_exec()),
+            variables: self
+                .variables()
+                .iter()
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect(),
+            options: self
+                .config
+                .iter()
+                .map(|(key, value)| (key.to_string(), value_text(value)))
+                .collect(),
+        }
+    }
+
+    fn rule_settings(&self, category: &str) -> Vec<RuleSettings> {
+        let mut names = self.config.list_special_category_keys(category);
+        names.sort();
+
+        names
+            .into_iter()
+            .filter_map(|name| {
+                let properties = self
+                    .config
+                    .special_category_user_values(category, &name)
+                    .ok()?
+                    .map(|(key, value)| (key.to_string(), value_text(value)))
+                    .collect();
+                Some(RuleSettings { name, properties })
+            })
+            .collect()
+    }
+
+    /// Write the changes made to a [`snapshot()`](Self::snapshot) back to the config
+    ///
+    /// `settings` is compared with a fresh snapshot and only what differs is written, so
+    /// untouched lines keep their formatting and variable references. When a typed field
+    /// and the same key in [`options`](HyprlandSettings::options) were both edited, the
+    /// typed field wins. Returns what changed: option keys, `$variables`, handler keywords
+    /// and rules as `windowrule[name]`.
+    ///
+    /// Monitors from `monitor[name] { ... }` blocks and `animation`/`bezier` lines can't be
+    /// written back; changing them is an error, returned before anything is written.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mutation")] {
+    /// use hyprlang::Hyprland;
+    ///
+    /// let mut hypr = Hyprland::new();
+    /// hypr.parse("general {\n    border_size = 2\n    gaps_in = 5\n}").unwrap();
+    ///
+    /// let mut settings = hypr.snapshot();
+    /// settings.general.border_size = Some(4);
+    /// settings.exec_once.push("waybar".to_string());
+    ///
+    /// let changed = hypr.apply(&settings).unwrap();
+    /// assert_eq!(changed, vec!["general:border_size", "exec-once"]);
+    /// assert_eq!(
+    ///     hypr.config().serialize(),
+    ///     "general {\n    border_size = 4\n    gaps_in = 5\n}\nexec-once = waybar\n"
+    /// );
+    /// # }
+    /// ```
+    #[cfg(feature = "mutation")]
+    pub fn apply(&mut self, settings: &HyprlandSettings) -> ParseResult<Vec<String>> {
+        let current = self.snapshot();
+
+        if current.animations.animations != settings.animations.animations
+            || current.animations.beziers != settings.animations.beziers
+        {
+            return Err(ConfigError::custom(
+                "animation and bezier lines can't be applied",
+            ));
+        }
+        let block_monitor = current.monitors.iter().find(|monitor| {
+            monitor.from_block
+                && settings.monitors.iter().find(|m| m.name == monitor.name) != Some(monitor)
+        });
+        if let Some(monitor) = block_monitor {
+            return Err(ConfigError::custom(format!(
+                "monitor '{}' is set by a block and can't be applied",
+                monitor.name
+            )));
+        }
+
+        let mut changed = Vec::new();
+
+        let mut options = settings.options.clone();
+        for ((key, old), (_, new)) in typed_options(&current)
+            .into_iter()
+            .zip(typed_options(settings))
+        {
+            if old != new {
+                match new {
+                    Some(value) => options.insert(key.to_string(), value),
+                    None => options.remove(key),
+                };
+            }
+        }
+        for (key, value) in &options {
+            if current.options.get(key) != Some(value) {
+                self.config.set_raw_value(key, value)?;
+                changed.push(key.clone());
+            }
+        }
+        for key in current.options.keys() {
+            if !options.contains_key(key) {
+                self.config.remove(key)?;
+                changed.push(key.clone());
+            }
+        }
+
+        for (name, value) in &settings.variables {
+            if current.variables.get(name) != Some(value) {
+                self.config.set_variable(name.clone(), value.clone());
+                changed.push(format!("${}", name));
+            }
+        }
+        for name in current.variables.keys() {
+            if !settings.variables.contains_key(name) {
+                self.config.remove_variable(name);
+                changed.push(format!("${}", name));
+            }
+        }
+
+        let env_lines = |env: &[EnvVar]| env.iter().map(EnvVar::to_string).collect::<Vec<_>>();
+        let handlers = [
+            ("workspace", current.workspaces, settings.workspaces.clone()),
+            ("env", env_lines(&current.env), env_lines(&settings.env)),
+            ("exec-once", current.exec_once, settings.exec_once.clone()),
+            ("exec", current.exec, settings.exec.clone()),
+        ];
+        for (handler, old, new) in handlers {
+            if old != new {
+                self.apply_handler_calls(handler, &old, &new)?;
+                changed.push(handler.to_string());
+            }
+        }
+
+        if current.binds != settings.binds {
+            self.apply_binds(&current.binds, &settings.binds)?;
+            changed.push("bind".to_string());
+        }
+        if current.monitors != settings.monitors {
+            self.apply_monitors(&current.monitors, &settings.monitors)?;
+            changed.push("monitor".to_string());
+        }
+
+        for (category, old, new) in [
+            ("windowrule", &current.windowrules, &settings.windowrules),
+            ("layerrule", &current.layerrules, &settings.layerrules),
+        ] {
+            changed.extend(self.apply_rules(category, old, new)?);
+        }
+
+        Ok(changed)
+    }
+
+    /// Update, remove and append handler calls so they match `wanted`
+    #[cfg(feature = "mutation")]
+    fn apply_handler_calls(
+        &mut self,
+        handler: &str,
+        current: &[String],
+        wanted: &[String],
+    ) -> ParseResult<()> {
+        for (index, (old, new)) in current.iter().zip(wanted).enumerate() {
+            if old != new {
+                self.config
+                    .update_handler_call(handler, index, new.clone())?;
+            }
+        }
+        for index in (wanted.len()..current.len()).rev() {
+            self.config.remove_handler_call(handler, index)?;
+        }
+        for value in &wanted[current.len().min(wanted.len())..] {
+            self.config.add_handler_call(handler, value.clone())?;
+        }
+        Ok(())
+    }
+
+    /// Like [`apply_handler_calls`](Self::apply_handler_calls), rewriting the keyword of
+    /// binds whose flags changed
+    #[cfg(feature = "mutation")]
+    fn apply_binds(&mut self, current: &[Keybind], wanted: &[Keybind]) -> ParseResult<()> {
+        for (index, (old, new)) in current.iter().zip(wanted).enumerate() {
+            if old.flags != new.flags {
+                self.config.remove_handler_call("bind", index)?;
+                self.config
+                    .insert_handler_call(&new.keyword(), index, new.value())?;
+            } else if old != new {
+                self.config
+                    .update_handler_call("bind", index, new.value())?;
+            }
+        }
+        for index in (wanted.len()..current.len()).rev() {
+            self.config.remove_handler_call("bind", index)?;
+        }
+        for bind in &wanted[current.len().min(wanted.len())..] {
+            self.config.add_handler_call(bind.keyword(), bind.value())?;
+        }
+        Ok(())
+    }
+
+    /// Write changed monitors as `monitor = ...` lines
+    ///
+    /// A changed monitor has its last line rewritten; a removed one loses all of its lines.
+    #[cfg(feature = "mutation")]
+    fn apply_monitors(&mut self, current: &[Monitor], wanted: &[Monitor]) -> ParseResult<()> {
+        let names: Vec<String> = self
+            .all_monitors()
+            .into_iter()
+            .map(|line| Monitor::parse(line).name)
+            .collect();
+        for (index, name) in names.iter().enumerate().rev() {
+            if !wanted.iter().any(|monitor| monitor.name == *name) {
+                self.config.remove_handler_call("monitor", index)?;
+            }
+        }
+
+        for monitor in wanted {
+            match current.iter().find(|m| m.name == monitor.name) {
+                Some(old) if old == monitor => {}
+                Some(_) => {
+                    let index = self
+                        .all_monitors()
+                        .iter()
+                        .rposition(|line| Monitor::parse(line).name == monitor.name);
+                    match index {
+                        Some(index) => {
+                            self.config.update_handler_call(
+                                "monitor",
+                                index,
+                                monitor.to_string(),
+                            )?;
+                        }
+                        None => self
+                            .config
+                            .add_handler_call("monitor", monitor.to_string())?,
+                    }
+                }
+                None => self
+                    .config
+                    .add_handler_call("monitor", monitor.to_string())?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove, rewrite and add the blocks of a rule category so they match `wanted`
+    #[cfg(feature = "mutation")]
+    fn apply_rules(
+        &mut self,
+        category: &str,
+        current: &[RuleSettings],
+        wanted: &[RuleSettings],
+    ) -> ParseResult<Vec<String>> {
+        let mut changed = Vec::new();
+
+        for rule in current {
+            if !wanted.iter().any(|r| r.name == rule.name) {
+                self.config
+                    .remove_special_category_instance(category, &rule.name)?;
+                changed.push(format!("{}[{}]", category, rule.name));
+            }
+        }
+
+        for rule in wanted {
+            let old = current.iter().find(|r| r.name == rule.name);
+            if old == Some(rule) {
+                continue;
+            }
+            if let Some(old) = old {
+                for (property, value) in &rule.properties {
+                    if old.properties.get(property) != Some(value) {
+                        self.config.write_special_category_value(
+                            category,
+                            &rule.name,
+                            property,
+                            Some(value),
+                        );
+                    }
+                }
+                for property in old.properties.keys() {
+                    if !rule.properties.contains_key(property) {
+                        self.config
+                            .write_special_category_value(category, &rule.name, property, None);
+                    }
+                }
+            }
+
+            let block = rule
+                .properties
+                .iter()
+                .fold(MigratedBlock::new(&rule.name), |block, (key, value)| {
+                    block.with(key, value)
+                });
+            self.config
+                .replace_special_category_instance(category, &block)?;
+            changed.push(format!("{}[{}]", category, rule.name));
+        }
+
+        Ok(changed)
+    }
 }
 
 impl Default for Hyprland {
@@ -1331,6 +3829,74 @@ impl Default for Hyprland {
     }
 }
 
+/// Text for a value that parses back to the same value
+fn value_text(value: &ConfigValue) -> String {
+    match value {
+        ConfigValue::Color(color) => format_color(*color),
+        _ => value.to_string(),
+    }
+}
+
+/// The options behind the typed fields of a [`HyprlandSettings`], as config text
+#[cfg(feature = "mutation")]
+fn typed_options(settings: &HyprlandSettings) -> Vec<(&'static str, Option<String>)> {
+    let boolean = |value: Option<bool>| value.map(|value| value.to_string());
+    let int = |value: Option<i64>| value.map(|value| value.to_string());
+    let float = |value: Option<f64>| value.map(ConfigValue::format_float);
+    let (general, decoration, input) = (&settings.general, &settings.decoration, &settings.input);
+
+    vec![
+        ("general:border_size", int(general.border_size)),
+        ("general:gaps_in", general.gaps_in.clone()),
+        ("general:gaps_out", general.gaps_out.clone()),
+        (
+            "general:col.active_border",
+            general.active_border_color.map(format_color),
+        ),
+        (
+            "general:col.inactive_border",
+            general.inactive_border_color.map(format_color),
+        ),
+        ("general:layout", general.layout.clone()),
+        ("general:allow_tearing", boolean(general.allow_tearing)),
+        ("general:locale", general.locale.clone()),
+        ("decoration:rounding", int(decoration.rounding)),
+        (
+            "decoration:active_opacity",
+            float(decoration.active_opacity),
+        ),
+        (
+            "decoration:inactive_opacity",
+            float(decoration.inactive_opacity),
+        ),
+        ("decoration:blur:enabled", boolean(decoration.blur_enabled)),
+        ("decoration:blur:size", int(decoration.blur_size)),
+        ("decoration:blur:passes", int(decoration.blur_passes)),
+        ("input:kb_layout", input.kb_layout.clone()),
+        ("input:follow_mouse", int(input.follow_mouse)),
+        ("input:sensitivity", float(input.sensitivity)),
+        (
+            "input:touchpad:natural_scroll",
+            boolean(input.touchpad_natural_scroll),
+        ),
+        (
+            "misc:disable_hyprland_logo",
+            boolean(settings.misc.disable_hyprland_logo),
+        ),
+        (
+            "misc:force_default_wallpaper",
+            int(settings.misc.force_default_wallpaper),
+        ),
+        ("dwindle:pseudotile", boolean(settings.dwindle.pseudotile)),
+        (
+            "dwindle:preserve_split",
+            boolean(settings.dwindle.preserve_split),
+        ),
+        ("master:new_status", settings.master.new_status.clone()),
+        ("animations:enabled", boolean(settings.animations.enabled)),
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1445,4 +4011,26 @@ mod tests {
         assert_eq!(hypr.decoration_blur_size().unwrap(), 3);
         assert_eq!(hypr.decoration_blur_passes().unwrap(), 1);
     }
+
+    #[test]
+    fn test_hyprland_parsed_env() {
+        let mut hypr = Hyprland::new();
+
+        hypr.parse(
+            r#"
+            env = XCURSOR_SIZE,24
+            env =  QT_QPA_PLATFORM , wayland;xcb
+            env = XDG_CURRENT_DESKTOP,Hyprland,sway
+            env = EMPTY_VALUE
+        "#,
+        )
+        .unwrap();
+
+        let envs = hypr.parsed_env();
+        assert_eq!(envs.len(), 4);
+        assert_eq!(envs[0], EnvVar::new("XCURSOR_SIZE", "24"));
+        assert_eq!(envs[1], EnvVar::new("QT_QPA_PLATFORM", "wayland;xcb"));
+        assert_eq!(envs[2], EnvVar::new("XDG_CURRENT_DESKTOP", "Hyprland,sway"));
+        assert_eq!(envs[3], EnvVar::new("EMPTY_VALUE", ""));
+    }
 }
@@ -7,7 +7,7 @@
 //!
 //! ## Features
 //!
-//! - **Multiple data types**: Int, Float, String, Vec2, Color, and custom types
+//! - **Multiple data types**: Int, Float, String, Vec2, Color, unit quantities, and custom types
 //! - **Variables**: User-defined and environment variables with recursive expansion
 //! - **Expressions**: Mathematical expressions with arithmetic operations
 //! - **Nested categories**: Hierarchical configuration structure
@@ -36,6 +36,8 @@
 //! - **Handler mutations**: [`Config::add_handler_call`], [`Config::remove_handler_call`]
 //! - **Category mutations**: [`Config::get_special_category_mut`], [`Config::remove_special_category_instance`]
 //! - **Serialization**: [`Config::serialize`], [`Config::save`], [`Config::save_as`]
+//! - **Migrations**: [`Config::migrate`] applies [`Migration`] rules for deprecated options
+//! - **Output formatting**: [`Config::serialize_with_options`] with [`SerializeOptions`]
 //!
 //! See the mutation API documentation on [`MutableVariable`] and [`MutableCategoryInstance`] for detailed examples.
 //!
@@ -44,11 +46,25 @@
 //! The `hyprland` feature provides a high-level API with pre-configured Hyprland handlers and typed accessors.
 //! See the [`Hyprland`] struct documentation for details.
 //!
+//! ### `core-parse` Feature
+//!
+//! The `config` feature, enabled by default, provides [`Config`] and everything around it.
+//! Consumers that only need to read the structure of small snippets can disable default
+//! features and enable `core-parse` instead, which keeps just [`HyprlangParser`] and the
+//! statement types: no variable or handler maps, no file access, and faster builds.
+//!
+//! ```toml
+//! [dependencies]
+//! hyprlang = { version = "0.4", default-features = false, features = ["core-parse"] }
+//! ```
+//!
 //! ## Example
 //!
 //! ```rust
+//! # #[cfg(feature = "config")]
 //! use hyprlang::{Config, ConfigValue};
 //!
+//! # #[cfg(feature = "config")]
 //! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! // Create a new configuration
 //! let mut config = Config::new();
@@ -76,6 +92,8 @@
 //!
 //! # Ok(())
 //! # }
+//! # #[cfg(not(feature = "config"))]
+//! # fn main() {}
 //! ```
 //!
 //! ## Advanced Usage
@@ -83,8 +101,10 @@
 //! ### Custom Handlers
 //!
 //! ```rust
+//! # #[cfg(feature = "config")]
 //! use hyprlang::{Config, HandlerContext};
 //!
+//! # #[cfg(feature = "config")]
 //! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! let mut config = Config::new();
 //!
@@ -97,13 +117,17 @@
 //! config.parse("exec = /usr/bin/app")?;
 //! # Ok(())
 //! # }
+//! # #[cfg(not(feature = "config"))]
+//! # fn main() {}
 //! ```
 //!
 //! ### Special Categories
 //!
 //! ```rust
+//! # #[cfg(feature = "config")]
 //! use hyprlang::{Config, SpecialCategoryDescriptor};
 //!
+//! # #[cfg(feature = "config")]
 //! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! let mut config = Config::new();
 //!
@@ -126,18 +150,45 @@
 //! assert!(keys.contains(&"mouse".to_string()));
 //! # Ok(())
 //! # }
+//! # #[cfg(not(feature = "config"))]
+//! # fn main() {}
 //! ```
 
+#![forbid(unsafe_code)]
+
 // Module declarations
-mod config;
 mod error;
 mod escaping;
+mod types;
+pub mod util;
+
+#[cfg(feature = "core-parse")]
+mod parser;
+
+// Modules of the `Config` runtime
+#[cfg(feature = "config")]
+mod builder;
+#[cfg(feature = "config")]
+mod compat;
+#[cfg(feature = "config")]
+mod config;
+#[cfg(feature = "config")]
 mod expressions;
+#[cfg(feature = "config")]
 mod features;
+#[cfg(feature = "config")]
 mod handlers;
-mod parser;
+#[cfg(feature = "config")]
+mod profile;
+#[cfg(feature = "config")]
 mod special_categories;
-mod types;
+#[cfg(feature = "config")]
+mod stats;
+#[cfg(feature = "config")]
+mod template;
+#[cfg(feature = "config")]
+mod theme;
+#[cfg(feature = "config")]
 mod variables;
 
 // Feature-gated modules
@@ -147,37 +198,91 @@ mod hyprland;
 #[cfg(feature = "mutation")]
 mod document;
 
+#[cfg(feature = "mutation")]
+mod migration;
+
 #[cfg(feature = "mutation")]
 mod mutation;
 
+#[cfg(feature = "snapshot")]
+mod snapshot;
+
 // Public API exports
+pub use error::{ConfigError, Diagnostic, ParseResult, Severity, SuppressedError};
+pub use types::{
+    CATPPUCCIN_MOCHA, CategoryDefinition, CategoryMerge, Color, ConfigValue, ConfigValueEntry,
+    CustomValueType, ExpansionTrace, ExpressionStep, FromConfigValue, KeyCase, Unit, ValueKind,
+    VariableEntry, VariableSource, VariableSubstitution, Vec2, normalize_key,
+};
+
+#[cfg(feature = "core-parse")]
+pub use parser::{HyprlangParser, ParsedConfig, Statement, Value};
+
+#[cfg(feature = "config")]
+pub use builder::ConfigBuilder;
+#[cfg(feature = "config")]
+pub use compat::{CompatibilityReport, Divergence};
+#[cfg(feature = "config")]
 pub use config::{Config, ConfigOptions};
-pub use error::{ConfigError, ParseResult};
-pub use types::{Color, ConfigValue, ConfigValueEntry, CustomValueType, Vec2};
+#[cfg(feature = "config")]
+pub use profile::{ConfigProfile, ProfileHandler, ProfileOption};
+#[cfg(feature = "config")]
+pub use stats::{ConfigStats, FileStats};
+#[cfg(feature = "config")]
+pub use template::Template;
+#[cfg(feature = "config")]
+pub use theme::Theme;
 
 // Re-export submodules for advanced usage
-pub use escaping::{process_escapes, restore_escaped_braces};
+pub use escaping::{Syntax, escape_value, process_escapes, restore_escaped_braces};
+#[cfg(feature = "config")]
 pub use expressions::ExpressionEvaluator;
-pub use handlers::{FunctionHandler, Handler, HandlerContext, HandlerManager, HandlerScope};
+#[cfg(feature = "config")]
+pub use features::{ActiveCondition, DirectiveState};
+#[cfg(feature = "config")]
+pub use handlers::{
+    FunctionHandler, Handler, HandlerCallRecord, HandlerContext, HandlerDeprecation,
+    HandlerManager, HandlerScope, RejectedHandlerCall, UnifiedEntry, UnknownHandlerCall,
+};
+#[cfg(feature = "config")]
 pub use special_categories::{
-    SpecialCategoryDescriptor, SpecialCategoryInstance, SpecialCategoryManager, SpecialCategoryType,
+    CategoryInstanceView, SpecialCategoryDescriptor, SpecialCategoryInstance,
+    SpecialCategoryManager, SpecialCategoryType, ValueConstraint,
 };
+#[cfg(feature = "config")]
 pub use variables::VariableManager;
 
 // Feature-gated exports
+#[cfg(all(feature = "hyprland", feature = "regex"))]
+pub use hyprland::WindowProperties;
 #[cfg(feature = "hyprland")]
-pub use hyprland::{Hyprland, RuleInstance};
+pub use hyprland::{
+    AnimationSettings, BindFlag, Conflict, ConflictKind, ConflictSite, DecorationSettings,
+    DwindleSettings, EnvVar, GeneralSettings, Hyprland, HyprlandOption, HyprlandProfile,
+    HyprlandSettings, HyprlandVersion, HyprlandVersionProfile, InputSettings, Keybind, LayerRule,
+    MasterSettings, MiscSettings, Monitor, MonitorConflict, Monitors, RuleInstance, RuleOrigin,
+    RuleSettings,
+};
+
+#[cfg(feature = "mutation")]
+pub use document::{
+    BraceStyle, ConditionalRegion, ConfigDocument, DocumentNode, IndentStyle, LineEnding, NodeIter,
+    NodeLocation, NodeRef, NodeType, SectionLayout, SerializeOptions,
+};
 
 #[cfg(feature = "mutation")]
-pub use document::{ConfigDocument, DocumentNode, NodeLocation, NodeType};
+pub use migration::{MigratedBlock, Migration, MigrationChange, MigrationReport};
 
 #[cfg(feature = "mutation")]
-pub use mutation::{MutableCategoryInstance, MutableVariable};
+pub use mutation::{MutableCategoryInstance, MutableVariable, SaveReport};
+
+#[cfg(feature = "snapshot")]
+pub use snapshot::{ConfigSnapshot, SharedSnapshot, SnapshotInstance, SnapshotValue};
 
 // Version information
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
-#[cfg(test)]
+#[cfg(all(test, feature = "config"))]
 mod tests {
     use super::*;
 
@@ -0,0 +1,283 @@
+//! Rules for moving configs off deprecated options
+//!
+//! A [`Migration`] is a named list of rules, such as "`decoration:drop_shadow` is now
+//! `decoration:shadow:enabled`", applied with [`Config::migrate`](crate::Config::migrate).
+//! Rules rewrite the document in place, so the rest of the file is left as it was, and the
+//! returned [`MigrationReport`] lists every change that was made.
+
+use crate::document::DocumentNode;
+use std::fmt;
+
+type RewriteFn = Box<dyn Fn(&str) -> Option<String>>;
+type ConvertFn = Box<dyn Fn(&str) -> Option<MigratedBlock>>;
+
+/// A named set of migration rules, applied in the order they were added
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "mutation")] {
+/// use hyprlang::{Config, Migration};
+///
+/// let mut config = Config::new();
+/// config.parse("decoration {\n    drop_shadow = true\n    rounding = 10\n}").unwrap();
+///
+/// let migration = Migration::new("0.45")
+///     .rename("decoration:drop_shadow", "decoration:shadow:enabled");
+/// let report = config.migrate(&migration).unwrap();
+///
+/// assert_eq!(report.len(), 1);
+/// assert_eq!(config.get_int("decoration:shadow:enabled").unwrap(), 1);
+/// assert_eq!(
+///     config.serialize(),
+///     "decoration {\n    shadow:enabled = true\n    rounding = 10\n}\n"
+/// );
+/// # }
+/// ```
+pub struct Migration {
+    name: String,
+    pub(crate) rules: Vec<MigrationRule>,
+}
+
+pub(crate) enum MigrationRule {
+    Rename {
+        from: String,
+        to: String,
+    },
+    Remove {
+        key: String,
+    },
+    RewriteValue {
+        key: String,
+        rewrite: RewriteFn,
+    },
+    HandlerToSpecialCategory {
+        keyword: String,
+        category: String,
+        convert: ConvertFn,
+    },
+}
+
+impl Migration {
+    /// Create an empty migration
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            rules: Vec::new(),
+        }
+    }
+
+    /// Move a value to a new key
+    ///
+    /// If the new key is already set, the old one is removed and the existing value kept.
+    pub fn rename(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.rules.push(MigrationRule::Rename {
+            from: from.into(),
+            to: to.into(),
+        });
+        self
+    }
+
+    /// Remove a value that no longer exists
+    pub fn remove(mut self, key: impl Into<String>) -> Self {
+        self.rules.push(MigrationRule::Remove { key: key.into() });
+        self
+    }
+
+    /// Rewrite the raw value of a key, e.g. to convert between units
+    ///
+    /// Returning `None` (or the same value) leaves it unchanged.
+    pub fn rewrite_value<F>(mut self, key: impl Into<String>, rewrite: F) -> Self
+    where
+        F: Fn(&str) -> Option<String> + 'static,
+    {
+        self.rules.push(MigrationRule::RewriteValue {
+            key: key.into(),
+            rewrite: Box::new(rewrite),
+        });
+        self
+    }
+
+    /// Replace calls of a handler with special category blocks
+    ///
+    /// Each call's value is passed to `convert`; calls it returns `None` for are kept.
+    /// The special category must be registered on the config.
+    pub fn handler_to_special_category<F>(
+        mut self,
+        keyword: impl Into<String>,
+        category: impl Into<String>,
+        convert: F,
+    ) -> Self
+    where
+        F: Fn(&str) -> Option<MigratedBlock> + 'static,
+    {
+        self.rules.push(MigrationRule::HandlerToSpecialCategory {
+            keyword: keyword.into(),
+            category: category.into(),
+            convert: Box::new(convert),
+        });
+        self
+    }
+
+    /// Name of the migration, e.g. the release it migrates to
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Number of rules
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Whether the migration has no rules
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+impl fmt::Debug for Migration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Migration")
+            .field("name", &self.name)
+            .field("rules", &self.rules.len())
+            .finish()
+    }
+}
+
+/// A special category instance produced from a handler call by
+/// [`Migration::handler_to_special_category`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigratedBlock {
+    /// Instance key, written as `category[key]`
+    pub key: String,
+    /// Properties in the order they are written
+    pub values: Vec<(String, String)>,
+}
+
+impl MigratedBlock {
+    /// Create an empty block for an instance key
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            values: Vec::new(),
+        }
+    }
+
+    /// Add a property, builder style
+    pub fn with(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.values.push((key.into(), value.into()));
+        self
+    }
+
+    /// Source text of the block
+    pub(crate) fn to_source(&self, category: &str) -> String {
+        let mut source = format!("{}[{}] {{\n", category, self.key);
+        for (key, value) in &self.values {
+            source.push_str(&format!("{} = {}\n", key, value));
+        }
+        source.push_str("}\n");
+        source
+    }
+
+    /// Document node of the block
+    pub(crate) fn to_node(&self, category: &str) -> DocumentNode {
+        let nodes = self
+            .values
+            .iter()
+            .map(|(key, value)| DocumentNode::Assignment {
+                key: key.split(':').map(String::from).collect(),
+                value: value.clone(),
+                raw: format!("{} = {}", key, value),
+                equals: " = ".to_string(),
+                line: 0,
+            })
+            .collect();
+
+        DocumentNode::SpecialCategoryBlock {
+            name: category.to_string(),
+            key: Some(self.key.clone()),
+            nodes,
+            open_line: 0,
+            close_line: 0,
+            raw_open: format!("{}[{}] {{", category, self.key),
+            indent: None,
+            open_comment: None,
+            close_comment: None,
+        }
+    }
+}
+
+/// A change made by [`Config::migrate`](crate::Config::migrate)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationChange {
+    /// A value moved to a new key
+    Renamed { from: String, to: String },
+    /// A value was removed
+    Removed { key: String, value: String },
+    /// A value was rewritten
+    Rewritten {
+        key: String,
+        from: String,
+        to: String,
+    },
+    /// A handler call became a special category instance
+    Converted {
+        keyword: String,
+        value: String,
+        category: String,
+        key: String,
+    },
+}
+
+impl fmt::Display for MigrationChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationChange::Renamed { from, to } => write!(f, "renamed {} to {}", from, to),
+            MigrationChange::Removed { key, value } => write!(f, "removed {} = {}", key, value),
+            MigrationChange::Rewritten { key, from, to } => {
+                write!(f, "rewrote {} from {} to {}", key, from, to)
+            }
+            MigrationChange::Converted {
+                keyword,
+                value,
+                category,
+                key,
+            } => write!(
+                f,
+                "converted {} = {} to {}[{}]",
+                keyword, value, category, key
+            ),
+        }
+    }
+}
+
+/// Changes made by applying a [`Migration`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// Name of the applied migration
+    pub migration: String,
+    /// Changes in the order they were made
+    pub changes: Vec<MigrationChange>,
+}
+
+impl MigrationReport {
+    /// Number of changes
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Whether the migration changed nothing
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for MigrationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "migration {}: {} change(s)", self.migration, self.len())?;
+        for change in &self.changes {
+            write!(f, "\n  {}", change)?;
+        }
+        Ok(())
+    }
+}
@@ -6,6 +6,7 @@
 //! The main types are:
 //! - [`MutableVariable`] - A mutable reference to a variable
 //! - [`MutableCategoryInstance`] - A mutable reference to a special category instance
+//! - [`SaveReport`] - The outcome of writing every modified file
 //!
 //! These types are typically obtained through methods on [`Config`](crate::Config):
 //! - [`Config::get_variable_mut`](crate::Config::get_variable_mut)
@@ -45,11 +46,11 @@
 //! # }
 //! ```
 
-use crate::document::ConfigDocument;
+use crate::config::Config;
 use crate::error::{ConfigError, ParseResult};
 use crate::special_categories::SpecialCategoryManager;
 use crate::types::{ConfigValue, ConfigValueEntry};
-use crate::variables::VariableManager;
+use std::path::PathBuf;
 
 /// A mutable reference to a variable.
 ///
@@ -77,23 +78,13 @@ use crate::variables::VariableManager;
 /// ```
 pub struct MutableVariable<'a> {
     name: String,
-    manager: &'a mut VariableManager,
-    #[allow(dead_code)]
-    document: Option<&'a mut ConfigDocument>,
+    config: &'a mut Config,
 }
 
 impl<'a> MutableVariable<'a> {
     /// Create a new mutable variable reference (internal use only)
-    pub(crate) fn new(
-        name: String,
-        manager: &'a mut VariableManager,
-        document: Option<&'a mut ConfigDocument>,
-    ) -> Self {
-        Self {
-            name,
-            manager,
-            document,
-        }
+    pub(crate) fn new(name: String, config: &'a mut Config) -> Self {
+        Self { name, config }
     }
 
     /// Get the current value of the variable.
@@ -113,11 +104,14 @@ impl<'a> MutableVariable<'a> {
     /// # }
     /// ```
     pub fn get(&self) -> &str {
-        self.manager.get(&self.name).unwrap_or("")
+        self.config.get_variable(&self.name).unwrap_or("")
     }
 
     /// Set a new value for the variable.
     ///
+    /// This is equivalent to [`Config::set_variable`], including updating the definition
+    /// in whichever source file declares the variable.
+    ///
     /// # Examples
     ///
     /// ```
@@ -136,13 +130,7 @@ impl<'a> MutableVariable<'a> {
     /// # }
     /// ```
     pub fn set(&mut self, value: impl Into<String>) -> ParseResult<()> {
-        let value = value.into();
-        self.manager.set(self.name.clone(), value.clone());
-
-        if let Some(doc) = &mut self.document {
-            doc.update_or_insert_variable(&self.name, &value)?;
-        }
-
+        self.config.set_variable(self.name.clone(), value.into());
         Ok(())
     }
 
@@ -259,8 +247,13 @@ impl<'a> MutableCategoryInstance<'a> {
     /// assert_eq!(mouse.get("sensitivity").unwrap().as_float().unwrap(), 2.5);
     /// # }
     /// ```
-    pub fn set(&mut self, key: impl Into<String>, value: ConfigValue) -> ParseResult<()> {
+    pub fn set(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<ConfigValue>,
+    ) -> ParseResult<()> {
         let key = key.into();
+        let value = value.into();
         let raw = value.to_string();
         let entry = ConfigValueEntry::new(value, raw.clone());
 
@@ -342,17 +335,40 @@ impl<'a> MutableCategoryInstance<'a> {
     }
 }
 
+/// The outcome of [`Config::save_all_with_report`](crate::Config::save_all_with_report)
+///
+/// Files that were written have their dirty flags cleared. Failed and rolled-back files stay
+/// dirty, so a later save tries them again.
+#[derive(Debug, Clone, Default)]
+pub struct SaveReport {
+    /// Files that were written and kept
+    pub saved: Vec<PathBuf>,
+
+    /// Files that could not be written, with the error for each
+    pub failed: Vec<(PathBuf, ConfigError)>,
+
+    /// Files that were written and then restored to their previous contents after a failure
+    pub rolled_back: Vec<PathBuf>,
+}
+
+impl SaveReport {
+    /// Whether every modified file was written
+    pub fn is_ok(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_mutable_variable() {
-        let mut manager = VariableManager::new();
-        manager.set("TEST".to_string(), "value1".to_string());
+        let mut config = Config::new();
+        config.set_variable("TEST".to_string(), "value1".to_string());
 
         {
-            let mut var = MutableVariable::new("TEST".to_string(), &mut manager, None);
+            let mut var = MutableVariable::new("TEST".to_string(), &mut config);
             assert_eq!(var.get(), "value1");
             assert_eq!(var.name(), "TEST");
 
@@ -361,6 +377,6 @@ mod tests {
         }
 
         // Verify the change persisted
-        assert_eq!(manager.get("TEST").unwrap(), "value2");
+        assert_eq!(config.get_variable("TEST").unwrap(), "value2");
     }
 }
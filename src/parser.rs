@@ -1,8 +1,17 @@
-use crate::error::ParseResult;
+//! Parsing of hyprlang source into statements
+//!
+//! This is the part of the crate available with only the `core-parse` feature: it turns
+//! source text into a list of [`Statement`]s without resolving variables, evaluating
+//! expressions, running handlers or reading `source`d files.
+
+use crate::error::{ConfigError, ParseResult};
+use crate::escaping::unquote;
 use crate::types::{Color, Vec2};
 use pest::Parser;
 use pest_derive::Parser;
+use std::borrow::Cow;
 
+/// The pest-generated hyprlang parser
 #[derive(Parser)]
 #[grammar = "hyprlang.pest"]
 pub struct HyprlangParser;
@@ -10,6 +19,7 @@ pub struct HyprlangParser;
 /// Parse result containing all statements from a config file
 #[derive(Debug)]
 pub struct ParsedConfig {
+    /// Top-level statements, in source order
     pub statements: Vec<Statement>,
 }
 
@@ -17,15 +27,25 @@ pub struct ParsedConfig {
 #[derive(Debug, Clone)]
 pub enum Statement {
     /// Variable definition: $VAR = value
-    VariableDef { name: String, value: String },
+    VariableDef {
+        name: String,
+        value: String,
+        line: usize,
+    },
 
     /// Assignment: key = value
-    Assignment { key: Vec<String>, value: Value },
+    Assignment {
+        key: Vec<String>,
+        value: Value,
+        line: usize,
+    },
 
     /// Category block: category { statements }
     CategoryBlock {
         name: String,
         statements: Vec<Statement>,
+        /// Line of the opening brace
+        line: usize,
     },
 
     /// Special category block: category[key] { statements }
@@ -33,6 +53,8 @@ pub enum Statement {
         name: String,
         key: Option<String>,
         statements: Vec<Statement>,
+        /// Line of the opening brace
+        line: usize,
     },
 
     /// Handler call: keyword [flags] = value
@@ -40,6 +62,7 @@ pub enum Statement {
         keyword: String,
         flags: Option<String>,
         value: String,
+        line: usize,
     },
 
     /// Source directive: source = path
@@ -74,7 +97,7 @@ pub enum Value {
     /// Boolean
     Boolean(bool),
 
-    /// String value
+    /// String value, with its quotes if it was written as a quoted string
     String(String),
 
     /// Multiline value
@@ -83,8 +106,48 @@ pub enum Value {
 
 impl HyprlangParser {
     /// Parse a configuration string
+    ///
+    /// Values are kept as written: `$VAR` references, colors and expressions are only
+    /// resolved by `Config`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyprlang::{HyprlangParser, Statement};
+    ///
+    /// let parsed = HyprlangParser::parse_config("general {\n    gaps_in = 5\n}").unwrap();
+    /// assert!(matches!(
+    ///     &parsed.statements[0],
+    ///     Statement::SpecialCategoryBlock { name, .. } if name == "general"
+    /// ));
+    /// ```
     pub fn parse_config(input: &str) -> ParseResult<ParsedConfig> {
-        let pairs = HyprlangParser::parse(Rule::file, input)?;
+        Self::parse_config_with_limit(input, Self::DEFAULT_MAX_DEPTH)
+    }
+
+    /// Deepest nesting of category blocks accepted by [`parse_config`](Self::parse_config)
+    pub const DEFAULT_MAX_DEPTH: usize = 64;
+
+    /// Parse a configuration string, rejecting category blocks nested deeper than
+    /// `max_depth`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyprlang::HyprlangParser;
+    ///
+    /// let input = "a {\n    b {\n        c {\n            x = 1\n        }\n    }\n}";
+    /// assert!(HyprlangParser::parse_config_with_limit(input, 3).is_ok());
+    ///
+    /// let error = HyprlangParser::parse_config_with_limit(input, 2).unwrap_err();
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "Parse error at line 3, column 9: category block 'c' is nested deeper than 2 levels"
+    /// );
+    /// ```
+    pub fn parse_config_with_limit(input: &str, max_depth: usize) -> ParseResult<ParsedConfig> {
+        let input = normalize_input(input);
+        let pairs = parse_file(&input, max_depth)?;
 
         let mut statements = Vec::new();
 
@@ -104,14 +167,16 @@ impl HyprlangParser {
     fn parse_statement(pair: pest::iterators::Pair<Rule>) -> ParseResult<Option<Statement>> {
         match pair.as_rule() {
             Rule::variable_def => {
+                let line = pair.line_col().0;
                 let mut inner = pair.into_inner();
                 let name = inner.next().unwrap().as_str().to_string();
                 let value_pair = inner.next().unwrap();
-                let value = Self::parse_value_to_string(value_pair)?;
-                Ok(Some(Statement::VariableDef { name, value }))
+                let value = Self::parse_unquoted_string(value_pair)?;
+                Ok(Some(Statement::VariableDef { name, value, line }))
             }
 
             Rule::assignment => {
+                let line = pair.line_col().0;
                 let mut inner = pair.into_inner();
                 let key_path = inner.next().unwrap();
                 let key = Self::parse_key_path(key_path)?;
@@ -123,10 +188,11 @@ impl HyprlangParser {
                     Value::String(String::new())
                 };
 
-                Ok(Some(Statement::Assignment { key, value }))
+                Ok(Some(Statement::Assignment { key, value, line }))
             }
 
             Rule::category_block => {
+                let line = pair.line_col().0;
                 let mut inner = pair.into_inner();
                 let name = inner.next().unwrap().as_str().to_string();
                 let mut statements = Vec::new();
@@ -137,10 +203,15 @@ impl HyprlangParser {
                     }
                 }
 
-                Ok(Some(Statement::CategoryBlock { name, statements }))
+                Ok(Some(Statement::CategoryBlock {
+                    name,
+                    statements,
+                    line,
+                }))
             }
 
             Rule::special_category_block => {
+                let line = pair.line_col().0;
                 let mut inner = pair.into_inner();
                 let name = inner.next().unwrap().as_str().to_string();
 
@@ -161,10 +232,12 @@ impl HyprlangParser {
                     name,
                     key,
                     statements,
+                    line,
                 }))
             }
 
             Rule::handler_call => {
+                let line = pair.line_col().0;
                 let mut inner = pair.into_inner();
                 let keyword = inner.next().unwrap().as_str().to_string();
 
@@ -182,13 +255,14 @@ impl HyprlangParser {
                     keyword,
                     flags,
                     value,
+                    line,
                 }))
             }
 
             Rule::directive => {
                 let mut inner = pair.into_inner();
                 let value_pair = inner.next().unwrap();
-                let path = Self::parse_value_to_string(value_pair)?;
+                let path = Self::parse_unquoted_string(value_pair)?;
                 Ok(Some(Statement::Source { path }))
             }
 
@@ -242,7 +316,7 @@ impl HyprlangParser {
             Rule::multiline_value => {
                 let lines: Result<Vec<_>, _> = inner
                     .into_inner()
-                    .map(|p| Self::parse_value_to_string(p))
+                    .map(|p| Self::parse_value_to_string(p).map(|line| line.trim().to_string()))
                     .collect();
                 Ok(Value::Multiline(lines?))
             }
@@ -258,14 +332,14 @@ impl HyprlangParser {
             }
 
             Rule::string_value => {
-                let s = pair.as_str();
-                // Remove quotes if present
-                let s = if s.starts_with('"') && s.ends_with('"') {
-                    &s[1..s.len() - 1]
-                } else {
-                    s
+                // Quoted values are kept with their quotes, so handlers see them as written;
+                // unquoted values use `##` for a literal `#`
+                let inner = pair.into_inner().next().unwrap();
+                let s = match inner.as_rule() {
+                    Rule::quoted_string => inner.as_str().to_string(),
+                    _ => inner.as_str().replace("##", "#"),
                 };
-                Ok(Value::String(s.to_string()))
+                Ok(Value::String(s))
             }
 
             _ => Ok(Value::String(pair.as_str().to_string())),
@@ -286,14 +360,32 @@ impl HyprlangParser {
         })
     }
 
+    /// Like `parse_value_to_string`, with the quotes of a quoted string removed
+    fn parse_unquoted_string(pair: pest::iterators::Pair<Rule>) -> ParseResult<String> {
+        let value = Self::parse_value_to_string(pair)?;
+        Ok(unquote(&value).unwrap_or(value))
+    }
+
     /// Parse configuration and build document tree (for mutation feature)
     #[cfg(feature = "mutation")]
     pub fn parse_with_document(
         input: &str,
     ) -> ParseResult<(ParsedConfig, crate::document::ConfigDocument)> {
-        use crate::document::ConfigDocument;
+        Self::parse_with_document_limit(input, Self::DEFAULT_MAX_DEPTH)
+    }
 
-        let pairs = HyprlangParser::parse(Rule::file, input)?;
+    /// [`parse_with_document`](Self::parse_with_document) with a nesting limit, as for
+    /// [`parse_config_with_limit`](Self::parse_config_with_limit)
+    #[cfg(feature = "mutation")]
+    pub(crate) fn parse_with_document_limit(
+        input: &str,
+        max_depth: usize,
+    ) -> ParseResult<(ParsedConfig, crate::document::ConfigDocument)> {
+        use crate::document::{ConfigDocument, LineEnding};
+
+        let line_ending = LineEnding::detect(input);
+        let input = &*normalize_input(input);
+        let pairs = parse_file(input, max_depth)?;
         let mut statements = Vec::new();
         let mut doc_nodes = Vec::new();
 
@@ -310,10 +402,30 @@ impl HyprlangParser {
             }
         }
 
-        let document = ConfigDocument::with_nodes(doc_nodes);
+        let mut document = ConfigDocument::with_nodes(doc_nodes);
+        document.line_ending = line_ending;
         Ok((ParsedConfig { statements }, document))
     }
 
+    /// Leading whitespace of the line a block's child statement starts on
+    ///
+    /// Returns `None` for children that share the block's opening line.
+    #[cfg(feature = "mutation")]
+    fn block_line_indent(pair: &pest::iterators::Pair<Rule>, open_line: usize) -> Option<String> {
+        let start = pair.as_span().start_pos();
+        if start.line_col().0 == open_line {
+            return None;
+        }
+
+        Some(
+            start
+                .line_of()
+                .chars()
+                .take_while(|c| *c == ' ' || *c == '\t')
+                .collect(),
+        )
+    }
+
     #[cfg(feature = "mutation")]
     #[allow(clippy::only_used_in_recursion)]
     fn parse_statement_with_node(
@@ -330,11 +442,12 @@ impl HyprlangParser {
                 let mut inner = pair.into_inner();
                 let name = inner.next().unwrap().as_str().to_string();
                 let value_pair = inner.next().unwrap();
-                let value = Self::parse_value_to_string(value_pair)?;
+                let value = Self::parse_unquoted_string(value_pair)?;
 
                 let stmt = Statement::VariableDef {
                     name: name.clone(),
                     value: value.clone(),
+                    line,
                 };
                 let node = DocumentNode::VariableDef {
                     name,
@@ -370,10 +483,12 @@ impl HyprlangParser {
                 let stmt = Statement::Assignment {
                     key: key.clone(),
                     value,
+                    line,
                 };
                 let node = DocumentNode::Assignment {
                     key,
                     value: value_str,
+                    equals: crate::document::equals_of(&raw),
                     raw,
                     line,
                 };
@@ -386,7 +501,23 @@ impl HyprlangParser {
                 let mut statements = Vec::new();
                 let mut nodes = Vec::new();
 
+                let mut indent = None;
+                let mut open_comment = None;
+                let mut close_comment = None;
+
                 for stmt_pair in inner {
+                    if stmt_pair.as_rule() == Rule::trailing_comment {
+                        let text = stmt_pair.as_str()[1..].to_string();
+                        if stmt_pair.line_col().0 == line {
+                            open_comment = Some(text);
+                        } else {
+                            close_comment = Some(text);
+                        }
+                        continue;
+                    }
+                    if indent.is_none() {
+                        indent = Self::block_line_indent(&stmt_pair, line);
+                    }
                     if let Some((stmt, node)) = Self::parse_statement_with_node(stmt_pair, input)? {
                         statements.push(stmt);
                         if let Some(n) = node {
@@ -398,6 +529,7 @@ impl HyprlangParser {
                 let stmt = Statement::CategoryBlock {
                     name: name.clone(),
                     statements,
+                    line,
                 };
 
                 // Extract just the opening line
@@ -414,6 +546,9 @@ impl HyprlangParser {
                     open_line: line,
                     close_line,
                     raw_open,
+                    indent,
+                    open_comment,
+                    close_comment,
                 };
                 Ok(Some((stmt, Some(node))))
             }
@@ -426,11 +561,29 @@ impl HyprlangParser {
                 let mut statements = Vec::new();
                 let mut nodes = Vec::new();
 
+                let mut indent = None;
+                let mut open_comment = None;
+                let mut close_comment = None;
+
                 for p in inner {
                     if p.as_rule() == Rule::category_key {
                         let key_inner = p.into_inner().next().unwrap();
                         key = Some(key_inner.as_str().to_string());
-                    } else if let Some((stmt, node)) = Self::parse_statement_with_node(p, input)? {
+                        continue;
+                    }
+                    if p.as_rule() == Rule::trailing_comment {
+                        let text = p.as_str()[1..].to_string();
+                        if p.line_col().0 == line {
+                            open_comment = Some(text);
+                        } else {
+                            close_comment = Some(text);
+                        }
+                        continue;
+                    }
+                    if indent.is_none() {
+                        indent = Self::block_line_indent(&p, line);
+                    }
+                    if let Some((stmt, node)) = Self::parse_statement_with_node(p, input)? {
                         statements.push(stmt);
                         if let Some(n) = node {
                             nodes.push(n);
@@ -442,6 +595,7 @@ impl HyprlangParser {
                     name: name.clone(),
                     key: key.clone(),
                     statements,
+                    line,
                 };
 
                 let raw_open = if let Some(first_line) = raw.lines().next() {
@@ -460,6 +614,9 @@ impl HyprlangParser {
                     open_line: line,
                     close_line,
                     raw_open,
+                    indent,
+                    open_comment,
+                    close_comment,
                 };
                 Ok(Some((stmt, Some(node))))
             }
@@ -482,6 +639,7 @@ impl HyprlangParser {
                     keyword: keyword.clone(),
                     flags: flags.clone(),
                     value: value.clone(),
+                    line,
                 };
                 let node = DocumentNode::HandlerCall {
                     keyword,
@@ -496,10 +654,15 @@ impl HyprlangParser {
             Rule::directive => {
                 let mut inner = pair.into_inner();
                 let value_pair = inner.next().unwrap();
-                let path = Self::parse_value_to_string(value_pair)?;
+                let path = Self::parse_unquoted_string(value_pair)?;
 
                 let stmt = Statement::Source { path: path.clone() };
-                let node = DocumentNode::Source { path, raw, line, resolved_path: None };
+                let node = DocumentNode::Source {
+                    path,
+                    raw,
+                    line,
+                    resolved_path: None,
+                };
                 Ok(Some((stmt, Some(node))))
             }
 
@@ -543,3 +706,115 @@ impl HyprlangParser {
         }
     }
 }
+
+/// Run the grammar over normalized input
+///
+/// Nesting deeper than `max_depth` is rejected before the grammar, which recurses for each
+/// level, sees it. When the grammar fails, unbalanced braces are reported by the block
+/// that was never closed or the `}` that closes nothing, rather than by where the grammar
+/// gave up.
+fn parse_file(input: &str, max_depth: usize) -> ParseResult<pest::iterators::Pairs<'_, Rule>> {
+    scan_blocks(input, max_depth, false)?;
+    HyprlangParser::parse(Rule::file, input).map_err(|error| {
+        scan_blocks(input, usize::MAX, true)
+            .err()
+            .unwrap_or_else(|| error.into())
+    })
+}
+
+/// Follow category blocks line by line, without the grammar
+///
+/// Fails when blocks nest deeper than `max_depth` and, with `balance`, on an unclosed
+/// block or a stray `}`. Comments and continuation lines of multiline values are skipped.
+fn scan_blocks(input: &str, max_depth: usize, balance: bool) -> ParseResult<()> {
+    let mut open: Vec<(&str, usize)> = Vec::new();
+    let mut continued = false;
+
+    for (index, line) in input.lines().enumerate() {
+        let number = index + 1;
+        let is_continuation = std::mem::replace(&mut continued, line.trim_end().ends_with('\\'));
+        let code = line.trim_start();
+        let column = line.len() - code.len() + 1;
+        if is_continuation || code.starts_with('#') {
+            continue;
+        }
+
+        if let Some((name, rest)) = block_opener(code) {
+            if open.len() >= max_depth {
+                return Err(ConfigError::parse(
+                    number,
+                    column,
+                    format!(
+                        "category block '{}' is nested deeper than {} levels",
+                        name, max_depth
+                    ),
+                ));
+            }
+            // `name { }` opens and closes on one line
+            if !rest.starts_with('}') {
+                open.push((name, number));
+            }
+            continue;
+        }
+
+        let mut rest = code;
+        while let Some(after) = rest.strip_prefix('}') {
+            if open.pop().is_none() && balance {
+                return Err(ConfigError::parse(
+                    number,
+                    column,
+                    "unexpected '}' without an open category block",
+                ));
+            }
+            rest = after.trim_start();
+        }
+    }
+
+    // Reported at the end of the input, where the grammar expected the `}`
+    match open.pop() {
+        Some((name, line)) if balance => {
+            let end_line = input.matches('\n').count() + 1;
+            let end_column = input.len() - input.rfind('\n').map_or(0, |i| i + 1) + 1;
+            Err(ConfigError::parse(
+                end_line,
+                end_column,
+                format!("unclosed category block '{}' opened at line {}", name, line),
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The name of the block a line opens (`name {`, `name[key] {`) and the text after its `{`
+fn block_opener(code: &str) -> Option<(&str, &str)> {
+    let end = code
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.')))
+        .unwrap_or(code.len());
+    if end == 0 {
+        return None;
+    }
+
+    let (name, mut rest) = code.split_at(end);
+    rest = rest.trim_start();
+    if let Some(key) = rest.strip_prefix('[') {
+        rest = key[key.find(']')? + 1..].trim_start();
+    }
+    let rest = rest.strip_prefix('{')?;
+    // `name {{...}}` is not a block
+    (!rest.starts_with('{')).then(|| (name, rest.trim_start()))
+}
+
+/// Input as the grammar expects it
+///
+/// Drops a UTF-8 byte order mark and the `\0`/`^Z` padding some editors leave at the end
+/// of a file, and turns `\r\n` and lone `\r` line endings into `\n` so no value ends up
+/// containing a `\r`. Line numbers are unchanged.
+fn normalize_input(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let input = input.trim_end_matches(['\0', '\u{1a}']);
+    if input.contains('\r') {
+        Cow::Owned(input.replace("\r\n", "\n").replace('\r', "\n"))
+    } else {
+        Cow::Borrowed(input)
+    }
+}
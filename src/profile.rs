@@ -0,0 +1,165 @@
+//! Reusable registration bundles for hyprlang-based config formats
+//!
+//! A [`ConfigProfile`] describes everything a tool registers before parsing its
+//! config: keyword handlers, special categories and known options with their
+//! defaults. Applying it with [`Config::apply_profile`] replaces a hand-written
+//! sequence of `register_*` calls, so the same setup can be shared between tools.
+
+use crate::config::Config;
+use crate::special_categories::SpecialCategoryDescriptor;
+use crate::types::ConfigValue;
+
+/// A keyword handler registered by a profile
+///
+/// Calls are collected (see [`Config::get_handler_calls`]) without further processing.
+/// Handlers that need to act on their values can be registered in
+/// [`ConfigProfile::configure`] instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileHandler {
+    /// Handler keyword, e.g. `bind`
+    pub keyword: String,
+
+    /// Category the handler is scoped to, e.g. `animations` (`None` for root level)
+    pub category: Option<String>,
+
+    /// Whether the handler accepts flags (`keywordflags = value`)
+    pub accepts_flags: bool,
+}
+
+impl ProfileHandler {
+    /// A handler available at the root level
+    pub fn global(keyword: impl Into<String>) -> Self {
+        Self {
+            keyword: keyword.into(),
+            category: None,
+            accepts_flags: false,
+        }
+    }
+
+    /// A handler scoped to a category
+    pub fn in_category(category: impl Into<String>, keyword: impl Into<String>) -> Self {
+        Self {
+            keyword: keyword.into(),
+            category: Some(category.into()),
+            accepts_flags: false,
+        }
+    }
+
+    /// Accept flags after the keyword
+    pub fn with_flags(mut self) -> Self {
+        self.accepts_flags = true;
+        self
+    }
+}
+
+/// An option a profile knows about, with its default value
+///
+/// The default's type is also the option's type; see [`Config::register_option`].
+#[derive(Debug, Clone)]
+pub struct ProfileOption {
+    /// Full key, e.g. `general:border_size`
+    pub key: String,
+
+    /// Default value
+    pub default: ConfigValue,
+}
+
+impl ProfileOption {
+    /// Create a new option
+    pub fn new(key: impl Into<String>, default: ConfigValue) -> Self {
+        Self {
+            key: key.into(),
+            default,
+        }
+    }
+}
+
+/// A bundle of handlers, special categories and options for one config format
+///
+/// Only [`name`](ConfigProfile::name) is required; every other method defaults to
+/// registering nothing.
+///
+/// # Example
+///
+/// ```
+/// use hyprlang::{
+///     Config, ConfigProfile, ConfigValue, ProfileHandler, ProfileOption,
+///     SpecialCategoryDescriptor,
+/// };
+///
+/// struct Launcher;
+///
+/// impl ConfigProfile for Launcher {
+///     fn name(&self) -> &str {
+///         "launcher"
+///     }
+///
+///     fn handlers(&self) -> Vec<ProfileHandler> {
+///         vec![ProfileHandler::global("entry")]
+///     }
+///
+///     fn special_categories(&self) -> Vec<SpecialCategoryDescriptor> {
+///         vec![SpecialCategoryDescriptor::keyed("theme", "name")]
+///     }
+///
+///     fn options(&self) -> Vec<ProfileOption> {
+///         vec![ProfileOption::new("window:width", ConfigValue::Int(600))]
+///     }
+/// }
+///
+/// let mut config = Config::new();
+/// config.apply_profile(&Launcher);
+/// config.parse("entry = firefox\ntheme[dark] {\n    bg = 0x000000ff\n}").unwrap();
+///
+/// assert_eq!(config.get_handler_calls("entry").unwrap().len(), 1);
+/// assert_eq!(config.get_int("window:width").unwrap(), 600);
+/// assert!(config.parse("window:width = wide").is_err());
+/// ```
+pub trait ConfigProfile {
+    /// Name of the profile, for diagnostics
+    fn name(&self) -> &str;
+
+    /// Keyword handlers to register
+    fn handlers(&self) -> Vec<ProfileHandler> {
+        Vec::new()
+    }
+
+    /// Special categories to register, including their default values
+    fn special_categories(&self) -> Vec<SpecialCategoryDescriptor> {
+        Vec::new()
+    }
+
+    /// Known options with their defaults
+    fn options(&self) -> Vec<ProfileOption> {
+        Vec::new()
+    }
+
+    /// Deprecated handler keywords as (alias, canonical keyword) pairs; see
+    /// [`Config::register_handler_alias`]
+    fn handler_aliases(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+
+    /// Any further setup, run after the handlers, categories and options are registered
+    fn configure(&self, _config: &mut Config) {}
+}
+
+/// Check whether `value` can be stored in an option whose default is `default`
+///
+/// Integers are accepted for float, color and quantity options since they parse the
+/// same way, and string options accept anything.
+pub(crate) fn option_accepts(default: &ConfigValue, value: &ConfigValue) -> bool {
+    match (default, value) {
+        (ConfigValue::String(_), _) => true,
+        (ConfigValue::Float(_), ConfigValue::Int(_)) => true,
+        (ConfigValue::Color(_), ConfigValue::Int(_)) => true,
+        (ConfigValue::Quantity { .. }, ConfigValue::Int(_) | ConfigValue::Float(_)) => true,
+        (
+            ConfigValue::Custom { type_name, .. },
+            ConfigValue::Custom {
+                type_name: other, ..
+            },
+        ) => type_name == other,
+        (default, value) => std::mem::discriminant(default) == std::mem::discriminant(value),
+    }
+}
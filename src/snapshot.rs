@@ -0,0 +1,321 @@
+//! Immutable configuration snapshots for concurrent readers
+//!
+//! [`Config`](crate::Config) is single-threaded. A [`ConfigSnapshot`] is a plain-data copy
+//! of its values, variables, handler calls and special category instances that can be
+//! shared across threads. A [`SharedSnapshot`] holds the current one: readers load it
+//! without locking while a writer re-parses or mutates its `Config` and publishes the
+//! result.
+
+use crate::error::{ConfigError, ParseResult};
+use crate::types::{Color, ConfigValue, ConfigValueEntry, Unit, ValueKind, Vec2};
+use arc_swap::ArcSwap;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A value in a [`ConfigSnapshot`]
+///
+/// Mirrors [`ConfigValue`]; custom-typed values are kept as the text they were parsed from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SnapshotValue {
+    /// 64-bit signed integer
+    Int(i64),
+
+    /// 64-bit floating point
+    Float(f64),
+
+    /// String value
+    String(String),
+
+    /// 2D vector
+    Vec2(Vec2),
+
+    /// RGBA color
+    Color(Color),
+
+    /// Number with a unit suffix
+    Quantity { value: f64, unit: Unit },
+
+    /// Custom type, as written
+    Custom { type_name: String, raw: String },
+}
+
+impl SnapshotValue {
+    /// Copy of an entry's value
+    pub(crate) fn from_entry(entry: &ConfigValueEntry) -> Self {
+        match &entry.value {
+            ConfigValue::Int(v) => SnapshotValue::Int(*v),
+            ConfigValue::Float(v) => SnapshotValue::Float(*v),
+            ConfigValue::String(v) => SnapshotValue::String(v.clone()),
+            ConfigValue::Vec2(v) => SnapshotValue::Vec2(*v),
+            ConfigValue::Color(v) => SnapshotValue::Color(*v),
+            ConfigValue::Quantity { value, unit } => SnapshotValue::Quantity {
+                value: *value,
+                unit: *unit,
+            },
+            ConfigValue::Custom { type_name, .. } => SnapshotValue::Custom {
+                type_name: type_name.clone(),
+                raw: entry.raw.clone(),
+            },
+        }
+    }
+
+    /// Get the variant of this value
+    pub fn kind(&self) -> ValueKind {
+        match self {
+            SnapshotValue::Int(_) => ValueKind::Int,
+            SnapshotValue::Float(_) => ValueKind::Float,
+            SnapshotValue::String(_) => ValueKind::String,
+            SnapshotValue::Vec2(_) => ValueKind::Vec2,
+            SnapshotValue::Color(_) => ValueKind::Color,
+            SnapshotValue::Quantity { .. } => ValueKind::Quantity,
+            SnapshotValue::Custom { .. } => ValueKind::Custom,
+        }
+    }
+
+    /// Get the type name of this value
+    pub fn type_name(&self) -> &str {
+        match self {
+            SnapshotValue::Int(_) => "Int",
+            SnapshotValue::Float(_) => "Float",
+            SnapshotValue::String(_) => "String",
+            SnapshotValue::Vec2(_) => "Vec2",
+            SnapshotValue::Color(_) => "Color",
+            SnapshotValue::Quantity { .. } => "Quantity",
+            SnapshotValue::Custom { type_name, .. } => type_name,
+        }
+    }
+
+    /// Try to get the value as an integer
+    pub fn as_int(&self) -> ParseResult<i64> {
+        match self {
+            SnapshotValue::Int(v) => Ok(*v),
+            _ => Err(ConfigError::type_error("value", "Int", self.type_name())),
+        }
+    }
+
+    /// Try to get the value as a float
+    pub fn as_float(&self) -> ParseResult<f64> {
+        match self {
+            SnapshotValue::Float(v) => Ok(*v),
+            SnapshotValue::Int(v) => Ok(*v as f64),
+            _ => Err(ConfigError::type_error("value", "Float", self.type_name())),
+        }
+    }
+
+    /// Try to get the value as a string
+    pub fn as_string(&self) -> ParseResult<&str> {
+        match self {
+            SnapshotValue::String(v) => Ok(v),
+            _ => Err(ConfigError::type_error("value", "String", self.type_name())),
+        }
+    }
+
+    /// Try to get the value as a Vec2, accepting strings holding a pair of numbers
+    pub fn as_vec2(&self) -> ParseResult<Vec2> {
+        match self {
+            SnapshotValue::Vec2(v) => Ok(*v),
+            SnapshotValue::String(s) => Vec2::parse(s)
+                .map_err(|_| ConfigError::type_error("value", "Vec2", self.type_name())),
+            _ => Err(ConfigError::type_error("value", "Vec2", self.type_name())),
+        }
+    }
+
+    /// Try to get the value as a Color
+    pub fn as_color(&self) -> ParseResult<Color> {
+        match self {
+            SnapshotValue::Color(v) => Ok(*v),
+            _ => Err(ConfigError::type_error("value", "Color", self.type_name())),
+        }
+    }
+}
+
+/// Values of one special category instance, by property
+pub type SnapshotInstance = HashMap<String, SnapshotValue>;
+
+/// An immutable copy of a [`Config`](crate::Config)'s data that is `Send + Sync`
+///
+/// Created with [`Config::snapshot`](crate::Config::snapshot). Handlers, validators,
+/// formatting and entry metadata are not part of a snapshot.
+///
+/// # Example
+///
+/// ```
+/// use hyprlang::Config;
+///
+/// let mut config = Config::new();
+/// config.parse("general {\n    gaps_in = 5\n}").unwrap();
+///
+/// let snapshot = config.snapshot();
+/// std::thread::spawn(move || {
+///     assert_eq!(snapshot.get_int("general:gaps_in").unwrap(), 5);
+/// })
+/// .join()
+/// .unwrap();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigSnapshot {
+    pub(crate) values: HashMap<String, SnapshotValue>,
+    pub(crate) variables: HashMap<String, String>,
+    pub(crate) handler_calls: HashMap<String, Vec<String>>,
+    pub(crate) special_categories: HashMap<String, HashMap<String, SnapshotInstance>>,
+    pub(crate) generation: u64,
+}
+
+impl ConfigSnapshot {
+    /// [`Config::generation`](crate::Config::generation) when the snapshot was taken
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Get a configuration value
+    pub fn get(&self, key: &str) -> ParseResult<&SnapshotValue> {
+        self.values
+            .get(key)
+            .ok_or_else(|| ConfigError::key_not_found(key))
+    }
+
+    pub fn get_int(&self, key: &str) -> ParseResult<i64> {
+        self.get(key)?.as_int()
+    }
+
+    pub fn get_float(&self, key: &str) -> ParseResult<f64> {
+        self.get(key)?.as_float()
+    }
+
+    pub fn get_string(&self, key: &str) -> ParseResult<&str> {
+        self.get(key)?.as_string()
+    }
+
+    pub fn get_vec2(&self, key: &str) -> ParseResult<Vec2> {
+        self.get(key)?.as_vec2()
+    }
+
+    pub fn get_color(&self, key: &str) -> ParseResult<Color> {
+        self.get(key)?.as_color()
+    }
+
+    /// Check if a key exists
+    pub fn contains(&self, key: &str) -> bool {
+        self.values.contains_key(key)
+    }
+
+    /// Get all configuration keys
+    pub fn keys(&self) -> Vec<&str> {
+        self.values.keys().map(|s| s.as_str()).collect()
+    }
+
+    /// Get a variable value
+    pub fn get_variable(&self, name: &str) -> Option<&str> {
+        self.variables.get(name).map(|s| s.as_str())
+    }
+
+    /// Get all variables
+    pub fn variables(&self) -> &HashMap<String, String> {
+        &self.variables
+    }
+
+    /// Get all calls of a handler, in order
+    pub fn get_handler_calls(&self, handler: &str) -> Option<&[String]> {
+        self.handler_calls
+            .get(handler)
+            .map(|calls| calls.as_slice())
+    }
+
+    /// Get all handler calls by handler
+    pub fn all_handler_calls(&self) -> &HashMap<String, Vec<String>> {
+        &self.handler_calls
+    }
+
+    /// Keys of all instances of a special category
+    pub fn list_special_category_keys(&self, category: &str) -> Vec<&str> {
+        self.special_categories
+            .get(category)
+            .map(|instances| instances.keys().map(|key| key.as_str()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Get the values of a special category instance
+    pub fn get_special_category(
+        &self,
+        category: &str,
+        key: &str,
+    ) -> ParseResult<&SnapshotInstance> {
+        self.special_categories
+            .get(category)
+            .and_then(|instances| instances.get(key))
+            .ok_or_else(|| ConfigError::category_not_found(category, Some(key.to_string())))
+    }
+
+    /// All instances of a special category, by key
+    pub fn special_category_instances(
+        &self,
+        category: &str,
+    ) -> impl Iterator<Item = (&str, &SnapshotInstance)> {
+        self.special_categories
+            .get(category)
+            .into_iter()
+            .flatten()
+            .map(|(key, instance)| (key.as_str(), instance))
+    }
+}
+
+/// The current [`ConfigSnapshot`], shared between threads
+///
+/// Readers call [`load`](Self::load) to get an `Arc` to the current snapshot; this does
+/// not lock and never waits for a writer. A writer keeps its own `Config`, re-parses or
+/// mutates it, then calls [`publish`](Self::publish) to atomically replace the snapshot.
+/// Readers holding the previous snapshot keep using it until they drop it.
+///
+/// # Example
+///
+/// ```
+/// use hyprlang::{Config, SharedSnapshot};
+/// use std::sync::Arc;
+///
+/// let mut config = Config::new();
+/// config.parse("gaps = 5").unwrap();
+/// let shared = Arc::new(SharedSnapshot::new(config.snapshot()));
+///
+/// let reader = Arc::clone(&shared);
+/// let old = reader.load();
+///
+/// config.parse("gaps = 10").unwrap();
+/// shared.publish(&config);
+///
+/// assert_eq!(old.get_int("gaps").unwrap(), 5);
+/// assert_eq!(reader.load().get_int("gaps").unwrap(), 10);
+/// ```
+#[derive(Debug)]
+pub struct SharedSnapshot {
+    current: ArcSwap<ConfigSnapshot>,
+}
+
+impl SharedSnapshot {
+    /// Share a snapshot
+    pub fn new(snapshot: ConfigSnapshot) -> Self {
+        Self {
+            current: ArcSwap::from_pointee(snapshot),
+        }
+    }
+
+    /// The current snapshot
+    pub fn load(&self) -> Arc<ConfigSnapshot> {
+        self.current.load_full()
+    }
+
+    /// Replace the current snapshot, returning the previous one
+    pub fn store(&self, snapshot: ConfigSnapshot) -> Arc<ConfigSnapshot> {
+        self.current.swap(Arc::new(snapshot))
+    }
+
+    /// Replace the current snapshot with one of `config`, returning the previous one
+    pub fn publish(&self, config: &crate::Config) -> Arc<ConfigSnapshot> {
+        self.store(config.snapshot())
+    }
+}
+
+impl Default for SharedSnapshot {
+    fn default() -> Self {
+        Self::new(ConfigSnapshot::default())
+    }
+}
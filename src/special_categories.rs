@@ -1,6 +1,7 @@
 use crate::error::{ConfigError, ParseResult};
-use crate::types::{ConfigValue, ConfigValueEntry};
+use crate::types::{Color, ConfigValue, ConfigValueEntry, FromConfigValue, Vec2};
 use std::collections::HashMap;
+use std::fmt;
 
 /// Type of special category
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -30,6 +31,13 @@ pub struct SpecialCategoryDescriptor {
 
     /// If true, accessing a non-existent instance returns None instead of an error
     pub ignore_missing: bool,
+
+    /// Properties every instance must set; `*` patterns such as `match:*` are satisfied
+    /// by any matching property
+    pub required: Vec<String>,
+
+    /// Values each property accepts when set in a block
+    pub constraints: HashMap<String, ValueConstraint>,
 }
 
 impl SpecialCategoryDescriptor {
@@ -41,6 +49,8 @@ impl SpecialCategoryDescriptor {
             key_field: Some(key_field.into()),
             default_values: HashMap::new(),
             ignore_missing: false,
+            required: Vec::new(),
+            constraints: HashMap::new(),
         }
     }
 
@@ -52,6 +62,8 @@ impl SpecialCategoryDescriptor {
             key_field: None,
             default_values: HashMap::new(),
             ignore_missing: false,
+            required: Vec::new(),
+            constraints: HashMap::new(),
         }
     }
 
@@ -63,6 +75,8 @@ impl SpecialCategoryDescriptor {
             key_field: None,
             default_values: HashMap::new(),
             ignore_missing: false,
+            required: Vec::new(),
+            constraints: HashMap::new(),
         }
     }
 
@@ -72,6 +86,105 @@ impl SpecialCategoryDescriptor {
         self
     }
 
+    /// Add default values for several properties at once
+    pub fn with_defaults<I, K>(mut self, defaults: I) -> Self
+    where
+        I: IntoIterator<Item = (K, ConfigValue)>,
+        K: Into<String>,
+    {
+        self.default_values.extend(
+            defaults
+                .into_iter()
+                .map(|(property, value)| (property.into(), value)),
+        );
+        self
+    }
+
+    /// Require instances to set a property, or one property matching a `*` pattern
+    ///
+    /// A block that leaves it out fails to parse with
+    /// [`ConfigError::MissingProperties`](crate::ConfigError::MissingProperties).
+    ///
+    /// ```
+    /// use hyprlang::{Config, ConfigError, SpecialCategoryDescriptor};
+    ///
+    /// let mut config = Config::new();
+    /// config.register_special_category(
+    ///     SpecialCategoryDescriptor::keyed("windowrule", "name").with_required("match:*"),
+    /// );
+    ///
+    /// assert!(config.parse("windowrule[kitty] {\n  match:class = kitty\n  float = true\n}").is_ok());
+    /// let error = config.parse("\nwindowrule[any] {\n  float = true\n}").unwrap_err();
+    /// assert!(matches!(error, ConfigError::MissingProperties { line: 2, .. }));
+    /// ```
+    pub fn with_required(mut self, property: impl Into<String>) -> Self {
+        self.required.push(property.into());
+        self
+    }
+
+    /// Only accept the listed values for a property
+    pub fn with_allowed_values<I, S>(mut self, property: impl Into<String>, values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.constraints.insert(
+            property.into(),
+            ValueConstraint::OneOf(values.into_iter().map(Into::into).collect()),
+        );
+        self
+    }
+
+    /// Only accept numbers within `min..=max` for a property
+    ///
+    /// See [`ValueConstraint::Range`] for values holding several numbers.
+    ///
+    /// ```
+    /// use hyprlang::{Config, ConfigError, SpecialCategoryDescriptor};
+    ///
+    /// let mut config = Config::new();
+    /// config.register_special_category(
+    ///     SpecialCategoryDescriptor::keyed("windowrule", "name")
+    ///         .with_range("opacity", 0.0, 1.0)
+    ///         .with_allowed_values("idle_inhibit", ["none", "always", "focus", "fullscreen"]),
+    /// );
+    ///
+    /// assert!(config.parse("windowrule[a] {\n  opacity = 0.8 0.9 override\n}").is_ok());
+    /// let error = config.parse("windowrule[b] {\n  opacity = 1.5\n}").unwrap_err();
+    /// assert!(matches!(error, ConfigError::ConstraintViolation { line: 2, .. }));
+    /// assert!(config.parse("windowrule[c] {\n  idle_inhibit = sometimes\n}").is_err());
+    /// ```
+    pub fn with_range(mut self, property: impl Into<String>, min: f64, max: f64) -> Self {
+        self.constraints
+            .insert(property.into(), ValueConstraint::Range { min, max });
+        self
+    }
+
+    /// Only accept valid regexes for a property, like Hyprland's `match:class`
+    ///
+    /// A `negative:` prefix is allowed. Patterns are only checked with the `regex` feature;
+    /// without it any value is accepted.
+    ///
+    /// ```
+    /// # #[cfg(feature = "regex")] {
+    /// use hyprlang::{Config, ConfigError, SpecialCategoryDescriptor};
+    ///
+    /// let mut config = Config::new();
+    /// config.register_special_category(
+    ///     SpecialCategoryDescriptor::keyed("windowrule", "name").with_regex("match:class"),
+    /// );
+    ///
+    /// assert!(config.parse("windowrule[a] {\n  match:class = ^(kitty|foot)$\n}").is_ok());
+    /// let error = config.parse("windowrule[b] {\n  match:class = ^(kitty\n}").unwrap_err();
+    /// assert!(matches!(error, ConfigError::ConstraintViolation { line: 2, .. }));
+    /// # }
+    /// ```
+    pub fn with_regex(mut self, property: impl Into<String>) -> Self {
+        self.constraints
+            .insert(property.into(), ValueConstraint::Regex);
+        self
+    }
+
     /// Set ignore_missing to true - accessing non-existent instances returns None instead of error
     pub fn with_ignore_missing(mut self) -> Self {
         self.ignore_missing = true;
@@ -79,6 +192,98 @@ impl SpecialCategoryDescriptor {
     }
 }
 
+/// Values a special category property accepts, see
+/// [`SpecialCategoryDescriptor::constraints`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValueConstraint {
+    /// One of the listed values, compared as written
+    OneOf(Vec<String>),
+
+    /// Numbers within `min..=max`
+    ///
+    /// Every whitespace-separated number in the value is checked and other words are
+    /// ignored, so `0.8 0.9 override` passes for `0..=1`. The value must hold at least
+    /// one number.
+    Range { min: f64, max: f64 },
+
+    /// A regex matched against a whole value, optionally prefixed with `negative:`
+    ///
+    /// Only checked with the `regex` feature.
+    Regex,
+}
+
+impl ValueConstraint {
+    /// Check a value, returning the reason it is rejected
+    pub fn check(&self, value: &ConfigValue) -> Result<(), String> {
+        let text = value.to_string();
+        match self {
+            ValueConstraint::OneOf(allowed) => {
+                if allowed.contains(&text) {
+                    Ok(())
+                } else {
+                    Err(format!("must be {}", self))
+                }
+            }
+            ValueConstraint::Range { min, max } => {
+                let numbers: Vec<f64> = text
+                    .split_whitespace()
+                    .filter_map(|word| ConfigValue::parse_float(word).ok())
+                    .collect();
+                if !numbers.is_empty() && numbers.iter().all(|n| (min..=max).contains(&n)) {
+                    Ok(())
+                } else {
+                    Err(format!("must be {}", self))
+                }
+            }
+            #[cfg(feature = "regex")]
+            ValueConstraint::Regex => match matcher_regex(&text) {
+                Ok(_) => Ok(()),
+                Err(e) => {
+                    // Syntax errors show the pattern with a caret; keep only the message
+                    let message = e.to_string();
+                    let reason = message.lines().rfind(|line| !line.trim().is_empty());
+                    Err(format!(
+                        "must be {} ({})",
+                        self,
+                        reason.unwrap_or_default().trim_start_matches("error: ")
+                    ))
+                }
+            },
+            #[cfg(not(feature = "regex"))]
+            ValueConstraint::Regex => Ok(()),
+        }
+    }
+}
+
+/// Compile a Hyprland matcher: the regex must match the whole value and a `negative:`
+/// prefix inverts it
+///
+/// Returns the regex and whether the match is inverted.
+#[cfg(feature = "regex")]
+pub(crate) fn matcher_regex(pattern: &str) -> Result<(regex::Regex, bool), regex::Error> {
+    let (negated, pattern) = match pattern.strip_prefix("negative:") {
+        Some(pattern) => (true, pattern),
+        None => (false, pattern),
+    };
+    let regex = regex::Regex::new(&format!("^(?:{})$", pattern))?;
+    Ok((regex, negated))
+}
+
+impl fmt::Display for ValueConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueConstraint::OneOf(allowed) => write!(f, "one of {}", allowed.join(", ")),
+            ValueConstraint::Range { min, max } => write!(
+                f,
+                "a number from {} to {}",
+                ConfigValue::format_float(*min),
+                ConfigValue::format_float(*max)
+            ),
+            ValueConstraint::Regex => write!(f, "a valid regex"),
+        }
+    }
+}
+
 /// A single instance of a special category
 #[derive(Debug, Clone)]
 pub struct SpecialCategoryInstance {
@@ -117,7 +322,140 @@ impl SpecialCategoryInstance {
     }
 }
 
+/// A special category instance with its category's defaults, returned by
+/// [`Config::special_category_view`](crate::Config::special_category_view)
+///
+/// Properties the instance doesn't hold fall back to the category's registered defaults,
+/// including defaults registered after the instance was created. The view is `Copy`;
+/// use [`snapshot`](Self::snapshot) to keep the values beyond the config's borrow.
+#[derive(Debug, Clone, Copy)]
+pub struct CategoryInstanceView<'a> {
+    category: &'a str,
+    instance: &'a SpecialCategoryInstance,
+    descriptor: Option<&'a SpecialCategoryDescriptor>,
+}
+
+impl<'a> CategoryInstanceView<'a> {
+    pub(crate) fn new(
+        category: &'a str,
+        instance: &'a SpecialCategoryInstance,
+        descriptor: Option<&'a SpecialCategoryDescriptor>,
+    ) -> Self {
+        Self {
+            category,
+            instance,
+            descriptor,
+        }
+    }
+
+    /// Name of the special category
+    pub fn category(&self) -> &'a str {
+        self.category
+    }
+
+    /// Key of the instance (`static` for static categories)
+    pub fn key(&self) -> Option<&'a str> {
+        self.instance.key.as_deref()
+    }
+
+    /// Get a property, falling back to the category's default
+    pub fn get(&self, property: &str) -> Option<&'a ConfigValue> {
+        match self.instance.get(property) {
+            Some(entry) => Some(&entry.value),
+            None => self.descriptor?.default_values.get(property),
+        }
+    }
+
+    /// Get a property converted to `T`
+    pub fn get_as<T: FromConfigValue>(&self, property: &str) -> ParseResult<T> {
+        match self.get(property) {
+            Some(value) => T::from_config_value(value),
+            None => Err(ConfigError::key_not_found(self.path(property))),
+        }
+    }
+
+    pub fn get_int(&self, property: &str) -> ParseResult<i64> {
+        self.get_as(property)
+    }
+
+    pub fn get_float(&self, property: &str) -> ParseResult<f64> {
+        self.get_as(property)
+    }
+
+    pub fn get_string(&self, property: &str) -> ParseResult<&'a str> {
+        self.get(property)
+            .ok_or_else(|| ConfigError::key_not_found(self.path(property)))?
+            .as_string()
+    }
+
+    pub fn get_vec2(&self, property: &str) -> ParseResult<Vec2> {
+        self.get_as(property)
+    }
+
+    pub fn get_color(&self, property: &str) -> ParseResult<Color> {
+        self.get_as(property)
+    }
+
+    /// Whether the property was set in the config or in code, rather than defaulted
+    pub fn is_set_by_user(&self, property: &str) -> bool {
+        self.instance
+            .get(property)
+            .is_some_and(|entry| entry.set_by_user)
+    }
+
+    /// Whether the property holds its category's default
+    pub fn is_default(&self, property: &str) -> bool {
+        self.get(property).is_some() && !self.is_set_by_user(property)
+    }
+
+    /// Names of every property, set or defaulted, sorted
+    pub fn properties(&self) -> Vec<&'a str> {
+        let mut properties: Vec<&str> = self.instance.values.keys().map(String::as_str).collect();
+        if let Some(descriptor) = self.descriptor {
+            properties.extend(
+                descriptor
+                    .default_values
+                    .keys()
+                    .filter(|property| !self.instance.contains(property))
+                    .map(String::as_str),
+            );
+        }
+        properties.sort_unstable();
+        properties
+    }
+
+    /// An owned copy of the instance with the defaults it doesn't hold filled in
+    ///
+    /// Filled-in defaults have `set_by_user` unset, like defaults applied when the instance
+    /// was created.
+    pub fn snapshot(&self) -> SpecialCategoryInstance {
+        let mut instance = self.instance.clone();
+        if let Some(descriptor) = self.descriptor {
+            for (property, value) in &descriptor.default_values {
+                if !instance.contains(property) {
+                    instance.set(
+                        property.clone(),
+                        ConfigValueEntry::with_default(value.clone()),
+                    );
+                }
+            }
+        }
+        instance
+    }
+
+    /// Full key of a property, for errors
+    fn path(&self, property: &str) -> String {
+        format!(
+            "{}[{}]:{}",
+            self.category,
+            self.key().unwrap_or_default(),
+            property
+        )
+    }
+}
+
 /// Manager for special categories
+#[derive(Clone)]
 pub struct SpecialCategoryManager {
     /// Descriptors for all registered special categories
     descriptors: HashMap<String, SpecialCategoryDescriptor>,
@@ -153,6 +491,13 @@ impl SpecialCategoryManager {
         self.descriptors.get(name)
     }
 
+    /// Get a mutable reference to the descriptor for a category
+    ///
+    /// Changes to default values only apply to instances created afterwards.
+    pub fn get_descriptor_mut(&mut self, name: &str) -> Option<&mut SpecialCategoryDescriptor> {
+        self.descriptors.get_mut(name)
+    }
+
     /// Create a new instance of a special category
     pub fn create_instance(
         &mut self,
@@ -200,10 +545,9 @@ impl SpecialCategoryManager {
 
         // Apply default values from descriptor
         for (prop_name, default_value) in &descriptor.default_values {
-            let raw = default_value.to_string();
             instance.set(
                 prop_name.clone(),
-                ConfigValueEntry::new(default_value.clone(), raw),
+                ConfigValueEntry::with_default(default_value.clone()),
             );
         }
 
@@ -215,6 +559,18 @@ impl SpecialCategoryManager {
         Ok(instance_key)
     }
 
+    /// Get a special category instance with the category name as stored
+    pub(crate) fn get_instance_entry(
+        &self,
+        category_name: &str,
+        key: &str,
+    ) -> ParseResult<(&str, &SpecialCategoryInstance)> {
+        self.instances
+            .get_key_value(category_name)
+            .and_then(|(name, instances)| Some((name.as_str(), instances.get(key)?)))
+            .ok_or_else(|| ConfigError::category_not_found(category_name, Some(key.to_string())))
+    }
+
     /// Get a special category instance
     pub fn get_instance(
         &self,
@@ -297,6 +653,15 @@ impl SpecialCategoryManager {
             .unwrap_or_default()
     }
 
+    /// Get the names of all categories that have at least one instance
+    pub fn category_names(&self) -> Vec<&str> {
+        self.instances
+            .iter()
+            .filter(|(_, instances)| !instances.is_empty())
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
     /// Get all instances for a category
     pub fn get_all_instances(&self, category_name: &str) -> Vec<&SpecialCategoryInstance> {
         self.instances
@@ -388,6 +753,39 @@ mod tests {
         assert_eq!(key3, "anonymous_2");
     }
 
+    #[test]
+    fn test_with_defaults() {
+        let mut manager = SpecialCategoryManager::new();
+        manager.register(
+            SpecialCategoryDescriptor::anonymous("item")
+                .with_default("enable", ConfigValue::Int(1))
+                .with_defaults([
+                    ("size", ConfigValue::Int(2)),
+                    ("enable", ConfigValue::Int(0)),
+                ]),
+        );
+
+        let key = manager.create_instance("item", None).unwrap();
+        let instance = manager.get_instance("item", &key).unwrap();
+        assert_eq!(instance.get("size").unwrap().value.as_int().unwrap(), 2);
+        assert_eq!(instance.get("enable").unwrap().value.as_int().unwrap(), 0);
+        assert!(!instance.get("size").unwrap().set_by_user);
+
+        manager
+            .get_descriptor_mut("item")
+            .unwrap()
+            .default_values
+            .clear();
+        let key = manager.create_instance("item", None).unwrap();
+        assert!(
+            manager
+                .get_instance("item", &key)
+                .unwrap()
+                .values
+                .is_empty()
+        );
+    }
+
     #[test]
     fn test_ignore_missing_flag() {
         let descriptor = SpecialCategoryDescriptor::keyed("device", "name").with_ignore_missing();
@@ -0,0 +1,166 @@
+//! Summary counts of a parsed configuration
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Counts describing a configuration, returned by [`Config::stats`](crate::Config::stats)
+///
+/// The [`Display`](fmt::Display) output is a short multi-line summary.
+///
+/// # Example
+///
+/// ```
+/// use hyprlang::Config;
+///
+/// let mut config = Config::new();
+/// config.parse("gaps = 5\ngeneral {\n    border_size = 2\n}\n").unwrap();
+///
+/// let stats = config.stats();
+/// assert_eq!(stats.total_keys(), 2);
+/// assert!(stats.to_string().starts_with("2 value(s) in 2 categories"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigStats {
+    /// Number of values per top-level category; root-level keys are counted under `""`
+    pub keys_per_category: BTreeMap<String, usize>,
+
+    /// Number of instances per special category
+    pub special_category_instances: BTreeMap<String, usize>,
+
+    /// Number of defined variables
+    pub variables: usize,
+
+    /// Number of handler calls per keyword, with category handlers as `category:keyword`
+    pub handler_calls: BTreeMap<String, usize>,
+
+    /// Number of files parsed, including `source`d ones
+    pub files: usize,
+
+    /// Number of source lines parsed
+    pub lines: usize,
+
+    /// Total time spent in [`Config::parse`](crate::Config::parse) and
+    /// [`Config::parse_file`](crate::Config::parse_file)
+    pub parse_duration: Duration,
+
+    /// Metrics of each parsed file, see [`Config::file_stats`](crate::Config::file_stats)
+    pub file_stats: Vec<FileStats>,
+}
+
+impl ConfigStats {
+    /// Number of values across all categories
+    pub fn total_keys(&self) -> usize {
+        self.keys_per_category.values().sum()
+    }
+
+    /// Number of handler calls across all keywords
+    pub fn total_handler_calls(&self) -> usize {
+        self.handler_calls.values().sum()
+    }
+
+    /// The category or handler keyword with the most entries, with its count
+    ///
+    /// Handy to spot a runaway file, e.g. one with thousands of window rules.
+    pub fn largest(&self) -> Option<(&str, usize)> {
+        self.keys_per_category
+            .iter()
+            .chain(&self.special_category_instances)
+            .chain(&self.handler_calls)
+            .filter(|(name, _)| !name.is_empty())
+            .max_by_key(|(_, count)| **count)
+            .map(|(name, count)| (name.as_str(), *count))
+    }
+
+    /// The parsed file that took longest on its own, not counting the files it sources
+    pub fn slowest_file(&self) -> Option<&FileStats> {
+        self.file_stats.iter().max_by_key(|file| file.duration)
+    }
+}
+
+impl fmt::Display for ConfigStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} value(s) in {} categories, {} variable(s), {} handler call(s)",
+            self.total_keys(),
+            self.keys_per_category.len(),
+            self.variables,
+            self.total_handler_calls()
+        )?;
+        write!(
+            f,
+            "\n{} line(s) in {} file(s), parsed in {:?}",
+            self.lines, self.files, self.parse_duration
+        )?;
+
+        for (category, count) in &self.keys_per_category {
+            let name = if category.is_empty() {
+                "(root)"
+            } else {
+                category
+            };
+            write!(f, "\n  {}: {} value(s)", name, count)?;
+        }
+        for (category, count) in &self.special_category_instances {
+            write!(f, "\n  {}[]: {} instance(s)", category, count)?;
+        }
+        for (keyword, count) in &self.handler_calls {
+            write!(f, "\n  {}: {} call(s)", keyword, count)?;
+        }
+        Ok(())
+    }
+}
+
+/// Metrics of one file read by [`Config::parse_file`](crate::Config::parse_file), directly
+/// or through `source =`
+///
+/// # Example
+///
+/// ```no_run
+/// use hyprlang::Config;
+///
+/// let mut config = Config::new();
+/// config.parse_file("hyprland.conf").unwrap();
+///
+/// for file in config.file_stats() {
+///     println!("{}", file);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStats {
+    /// Canonical path of the file
+    pub path: PathBuf,
+
+    /// Size of the file in bytes
+    pub bytes: usize,
+
+    /// Number of lines in the file
+    pub lines: usize,
+
+    /// Number of statements in the file, counting those inside blocks
+    pub statements: usize,
+
+    /// Time spent reading and processing the file, not counting the files it sources
+    pub duration: Duration,
+
+    /// Errors in the file's own lines, whether returned, collected under
+    /// `throw_all_errors`, ignored under `noerror` or reported by `check`
+    pub errors: usize,
+}
+
+impl fmt::Display for FileStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} byte(s), {} line(s), {} statement(s), {} error(s) in {:?}",
+            self.path.display(),
+            self.bytes,
+            self.lines,
+            self.statements,
+            self.errors,
+            self.duration
+        )
+    }
+}
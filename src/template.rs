@@ -0,0 +1,229 @@
+//! Config templates with `@{placeholder}` markers
+//!
+//! A [`Template`] is a hyprlang file in which some text is left open, e.g. colors or
+//! fonts supplied by a theme. Rendering it with a map of substitutions produces a
+//! concrete config, and fails if any placeholder was left without a value.
+//!
+//! Placeholder names may contain letters, digits, `_`, `-` and `.`, with optional
+//! whitespace inside the braces (`@{ accent }`). Write `@@{` for a literal `@{`.
+//! Substituted text is inserted verbatim, before variables and expressions are
+//! evaluated, so values may themselves use `$VARIABLES` or `{{expressions}}`.
+
+use crate::config::Config;
+use crate::error::{ConfigError, ParseResult};
+use std::collections::HashMap;
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Text(String),
+    Placeholder(String),
+}
+
+/// A parsed config template
+///
+/// # Example
+///
+/// ```
+/// use hyprlang::Template;
+/// use std::collections::HashMap;
+///
+/// let template = Template::new("general {\n    col.active_border = @{accent}\n}").unwrap();
+/// assert_eq!(template.placeholders(), vec!["accent"]);
+///
+/// let theme = HashMap::from([("accent".to_string(), "rgba(33ccffee)".to_string())]);
+/// let config = template.render_config(&theme).unwrap();
+/// assert!(config.get_color("general:col.active_border").is_ok());
+///
+/// // Missing substitutions are reported together
+/// assert!(template.render(&HashMap::new()).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    /// Parse a template from a string
+    ///
+    /// Returns a parse error for an unterminated or invalid placeholder.
+    pub fn new(source: &str) -> ParseResult<Self> {
+        let mut segments = Vec::new();
+        let mut text = String::new();
+        let mut rest = source;
+
+        while let Some(pos) = rest.find("@{") {
+            // `@@{` is an escaped, literal `@{`
+            if pos > 0 && rest.as_bytes()[pos - 1] == b'@' {
+                text.push_str(&rest[..pos - 1]);
+                text.push_str("@{");
+                rest = &rest[pos + 2..];
+                continue;
+            }
+
+            text.push_str(&rest[..pos]);
+            let start = source.len() - rest.len() + pos;
+            let body = &rest[pos + 2..];
+
+            let end = match body.find(['}', '\n']) {
+                Some(end) if body.as_bytes()[end] == b'}' => end,
+                _ => {
+                    let (line, column) = line_column(source, start);
+                    return Err(ConfigError::parse(line, column, "unterminated placeholder"));
+                }
+            };
+
+            let name = body[..end].trim();
+            if !is_valid_name(name) {
+                let (line, column) = line_column(source, start);
+                return Err(ConfigError::parse(
+                    line,
+                    column,
+                    format!("invalid placeholder name '{}'", name),
+                ));
+            }
+
+            if !text.is_empty() {
+                segments.push(Segment::Text(std::mem::take(&mut text)));
+            }
+            segments.push(Segment::Placeholder(name.to_string()));
+            rest = &body[end + 1..];
+        }
+
+        text.push_str(rest);
+        if !text.is_empty() {
+            segments.push(Segment::Text(text));
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// Read and parse a template file
+    pub fn from_file(path: &Path) -> ParseResult<Self> {
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| ConfigError::io(path.display().to_string(), e.to_string()))?;
+        Self::new(&source)
+    }
+
+    /// Placeholder names used by the template, in order of first use
+    pub fn placeholders(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        for segment in &self.segments {
+            if let Segment::Placeholder(name) = segment
+                && !names.contains(&name.as_str())
+            {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    /// Substitute every placeholder, producing the config source
+    ///
+    /// Values for names the template doesn't use are ignored. If any placeholder has no
+    /// value, returns [`ConfigError::MissingPlaceholders`] listing all of them.
+    pub fn render(&self, values: &HashMap<String, String>) -> ParseResult<String> {
+        let missing: Vec<String> = self
+            .placeholders()
+            .into_iter()
+            .filter(|name| !values.contains_key(*name))
+            .map(str::to_string)
+            .collect();
+        if !missing.is_empty() {
+            return Err(ConfigError::missing_placeholders(missing));
+        }
+
+        let mut output = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Text(text) => output.push_str(text),
+                Segment::Placeholder(name) => output.push_str(&values[name]),
+            }
+        }
+        Ok(output)
+    }
+
+    /// Render the template and parse the result into a new [`Config`]
+    pub fn render_config(&self, values: &HashMap<String, String>) -> ParseResult<Config> {
+        let mut config = Config::new();
+        self.render_into(&mut config, values)?;
+        Ok(config)
+    }
+
+    /// Render the template and parse the result into an existing config
+    ///
+    /// Use this when the config needs handlers or special categories registered first.
+    pub fn render_into(
+        &self,
+        config: &mut Config,
+        values: &HashMap<String, String>,
+    ) -> ParseResult<()> {
+        config.parse(&self.render(values)?)
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+/// 1-based line and column of a byte offset
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before.len() - before.rfind('\n').map_or(0, |pos| pos + 1) + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_render() {
+        let template = Template::new("a = @{x}\nb = @{ y }@{x}\n").unwrap();
+        assert_eq!(template.placeholders(), vec!["x", "y"]);
+        assert_eq!(
+            template.render(&values(&[("x", "1"), ("y", "2")])).unwrap(),
+            "a = 1\nb = 21\n"
+        );
+    }
+
+    #[test]
+    fn test_escaped_marker() {
+        let template = Template::new("a = @@{x} @{y}").unwrap();
+        assert_eq!(template.placeholders(), vec!["y"]);
+        assert_eq!(
+            template.render(&values(&[("y", "z")])).unwrap(),
+            "a = @{x} z"
+        );
+    }
+
+    #[test]
+    fn test_missing_placeholders() {
+        let template = Template::new("@{a} @{b} @{c}").unwrap();
+        match template.render(&values(&[("b", "1")])) {
+            Err(ConfigError::MissingPlaceholders { names }) => assert_eq!(names, vec!["a", "c"]),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_invalid_placeholders() {
+        match Template::new("a = 1\nb = @{open\n") {
+            Err(ConfigError::ParseError { line, column, .. }) => assert_eq!((line, column), (2, 5)),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(Template::new("@{}").is_err());
+        assert!(Template::new("@{has space}").is_err());
+    }
+}
@@ -0,0 +1,145 @@
+//! Sets of variables that make up a color scheme or other theme
+//!
+//! A [`Theme`] is taken out of one config with [`Config::extract_variables_matching`](crate::Config::extract_variables_matching)
+//! and applied to another (or the same one later) with [`Config::apply_theme`](crate::Config::apply_theme).
+
+use std::collections::BTreeMap;
+
+/// Named variable values, e.g. all `$col_*` variables of a config
+///
+/// # Example
+///
+/// ```
+/// use hyprlang::{Config, Theme};
+///
+/// let mut config = Config::new();
+/// config.parse("$col_bg = 0x1e1e2eff\n$col_fg = 0xcdd6f4ff\n$gaps = 5").unwrap();
+///
+/// let theme = config.extract_variables_matching("col_*");
+/// assert_eq!(theme.len(), 2);
+/// assert_eq!(theme.get("col_bg"), Some("0x1e1e2eff"));
+///
+/// let light = Theme::new()
+///     .with("col_bg", "0xeff1f5ff")
+///     .with("col_fg", "0x4c4f69ff");
+/// config.apply_theme(&light);
+/// assert_eq!(config.get_variable("col_bg"), Some("0xeff1f5ff"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Theme {
+    variables: BTreeMap<String, String>,
+}
+
+impl Theme {
+    /// Create an empty theme
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a variable, builder style
+    pub fn with(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    /// Set a variable
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.variables.insert(name.into(), value.into());
+    }
+
+    /// Get a variable value
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.variables.get(name).map(String::as_str)
+    }
+
+    /// Remove a variable, returning its value
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        self.variables.remove(name)
+    }
+
+    /// Iterate over variables, sorted by name
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.variables
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Variable names, sorted
+    pub fn names(&self) -> Vec<&str> {
+        self.variables.keys().map(String::as_str).collect()
+    }
+
+    /// Number of variables
+    pub fn len(&self) -> usize {
+        self.variables.len()
+    }
+
+    /// Whether the theme has no variables
+    pub fn is_empty(&self) -> bool {
+        self.variables.is_empty()
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for Theme {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self {
+            variables: iter
+                .into_iter()
+                .map(|(name, value)| (name.into(), value.into()))
+                .collect(),
+        }
+    }
+}
+
+/// Match a name against a glob pattern where `*` matches any run of characters and `?`
+/// matches exactly one
+pub(crate) fn glob_matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` and the name position it was tried at
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star, tried)) => {
+                    p = star + 1;
+                    n = tried + 1;
+                    backtrack = Some((star, tried + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("col_*", "col_bg"));
+        assert!(glob_matches("col_*", "col_"));
+        assert!(!glob_matches("col_*", "color"));
+        assert!(glob_matches("*_bg", "col_bg"));
+        assert!(glob_matches("c?l_*g", "col_bg"));
+        assert!(glob_matches("*", ""));
+        assert!(glob_matches("a*b*c", "aXbYbZc"));
+        assert!(!glob_matches("a*b*c", "aXbYbZ"));
+        assert!(glob_matches("exact", "exact"));
+        assert!(!glob_matches("exact", "exactly"));
+    }
+}
@@ -1,6 +1,9 @@
 use crate::error::{ConfigError, ParseResult};
 use std::any::Any;
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fmt;
+use std::path::PathBuf;
 use std::rc::Rc;
 
 /// A 2D vector with x and y components
@@ -14,6 +17,43 @@ impl Vec2 {
     pub fn new(x: f64, y: f64) -> Self {
         Self { x, y }
     }
+
+    /// Parse `(x, y)`, `x, y` or Hyprland's space-separated `x y`
+    ///
+    /// Both components must be plain numbers, so strings such as
+    /// `monitor_w*0.5 monitor_h*0.5` or `1 2 3` are rejected.
+    pub fn parse(s: &str) -> ParseResult<Self> {
+        let s = s.trim();
+        let inner = s
+            .strip_prefix('(')
+            .and_then(|rest| rest.strip_suffix(')'))
+            .unwrap_or(s);
+
+        let parts: Vec<&str> = if inner.contains(',') {
+            inner.split(',').map(str::trim).collect()
+        } else {
+            inner.split_whitespace().collect()
+        };
+
+        if parts.len() == 2
+            && let (Ok(x), Ok(y)) = (
+                ConfigValue::parse_float(parts[0]),
+                ConfigValue::parse_float(parts[1]),
+            )
+        {
+            return Ok(Vec2::new(x, y));
+        }
+
+        Err(ConfigError::custom("not a valid Vec2"))
+    }
+}
+
+impl std::str::FromStr for Vec2 {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
 }
 
 impl fmt::Display for Vec2 {
@@ -24,6 +64,7 @@ impl fmt::Display for Vec2 {
 
 /// RGBA color representation
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Color {
     pub r: u8,
     pub g: u8,
@@ -69,6 +110,145 @@ impl Color {
         Ok(Self { r, g, b, a })
     }
 
+    /// Parse a color written as in a config
+    ///
+    /// Accepts `rgba(RRGGBBAA)`, `rgba(r, g, b, a)`, `rgb(RRGGBB)`, `rgb(r, g, b)`,
+    /// `0xRRGGBB`/`0xRRGGBBAA` and alpha overrides such as `rgba(rgb(f38ba8), 0.5)`.
+    /// Named colors are only known to a [`Config`](crate::Config) that registered them.
+    pub fn parse(s: &str) -> ParseResult<Self> {
+        Self::parse_with_names(s, &HashMap::new())
+    }
+
+    /// Parse a color, resolving names registered with
+    /// [`Config::register_named_color`](crate::Config::register_named_color)
+    pub(crate) fn parse_with_names(s: &str, names: &HashMap<String, Color>) -> ParseResult<Self> {
+        let s = s.trim();
+        if s.starts_with("rgba(") && s.ends_with(')') {
+            Self::parse_rgba(s, names)
+        } else if s.starts_with("rgb(") && s.ends_with(')') {
+            Self::parse_rgb(s, names)
+        } else if s.starts_with("0x") && s.len() >= 8 && s.len() <= 10 {
+            Self::from_hex(s)
+        } else {
+            Err(ConfigError::invalid_color(s, "not a color"))
+        }
+    }
+
+    fn parse_rgba(s: &str, names: &HashMap<String, Color>) -> ParseResult<Self> {
+        // rgba(hex), rgba(name), rgba(color, a) or rgba(r, g, b, a)
+        let inner = &s[5..s.len() - 1]; // Remove "rgba(" and ")"
+        let parts = Self::split_args(inner);
+
+        match parts.as_slice() {
+            // Hex format: rgba(RRGGBBAA), or a named color
+            [single] => Self::parse_reference(single, s, names),
+            // Alpha override: rgba($red, 0.5) or rgba(rgb(f38ba8), 128)
+            [color, alpha] => {
+                let color = Self::parse_reference(color, s, names)?;
+                Ok(Color {
+                    a: Self::parse_alpha(alpha, s)?,
+                    ..color
+                })
+            }
+            // Component format: rgba(r, g, b, a)
+            [r, g, b, a] => {
+                let r = r
+                    .parse::<u8>()
+                    .map_err(|_| ConfigError::invalid_color(s, "invalid r"))?;
+                let g = g
+                    .parse::<u8>()
+                    .map_err(|_| ConfigError::invalid_color(s, "invalid g"))?;
+                let b = b
+                    .parse::<u8>()
+                    .map_err(|_| ConfigError::invalid_color(s, "invalid b"))?;
+
+                Ok(Color::from_rgba(r, g, b, Self::parse_alpha(a, s)?))
+            }
+            _ => Err(ConfigError::invalid_color(s, "rgba needs 4 components")),
+        }
+    }
+
+    fn parse_rgb(s: &str, names: &HashMap<String, Color>) -> ParseResult<Self> {
+        // rgb(hex), rgb(name) or rgb(r, g, b)
+        let inner = &s[4..s.len() - 1]; // Remove "rgb(" and ")"
+        let parts = Self::split_args(inner);
+
+        if let [single] = parts.as_slice() {
+            return Self::parse_reference(single, s, names);
+        }
+
+        if parts.len() != 3 {
+            return Err(ConfigError::invalid_color(s, "rgb needs 3 components"));
+        }
+
+        let r = parts[0]
+            .parse::<u8>()
+            .map_err(|_| ConfigError::invalid_color(s, "invalid r"))?;
+        let g = parts[1]
+            .parse::<u8>()
+            .map_err(|_| ConfigError::invalid_color(s, "invalid g"))?;
+        let b = parts[2]
+            .parse::<u8>()
+            .map_err(|_| ConfigError::invalid_color(s, "invalid b"))?;
+
+        Ok(Color::from_rgb(r, g, b))
+    }
+
+    /// Resolve a color given as hex digits, a registered name or a nested color function
+    fn parse_reference(
+        reference: &str,
+        s: &str,
+        names: &HashMap<String, Color>,
+    ) -> ParseResult<Self> {
+        if let Some(color) = names.get(reference) {
+            return Ok(*color);
+        }
+
+        if reference.starts_with("rgba(") && reference.ends_with(')') {
+            Self::parse_rgba(reference, names)
+        } else if reference.starts_with("rgb(") && reference.ends_with(')') {
+            Self::parse_rgb(reference, names)
+        } else {
+            Color::from_hex(reference)
+                .map_err(|_| ConfigError::invalid_color(s, "unknown color or invalid hex digits"))
+        }
+    }
+
+    /// Alpha can be float (0.0-1.0) or int (0-255)
+    fn parse_alpha(alpha: &str, s: &str) -> ParseResult<u8> {
+        if alpha.contains('.') {
+            let a_float = alpha
+                .parse::<f64>()
+                .map_err(|_| ConfigError::invalid_color(s, "invalid a"))?;
+            Ok((a_float * 255.0).round() as u8)
+        } else {
+            alpha
+                .parse::<u8>()
+                .map_err(|_| ConfigError::invalid_color(s, "invalid a"))
+        }
+    }
+
+    /// Split color function arguments on commas that aren't inside nested parentheses
+    fn split_args(inner: &str) -> Vec<&str> {
+        let mut parts = Vec::new();
+        let mut depth = 0usize;
+        let mut start = 0;
+
+        for (i, ch) in inner.char_indices() {
+            match ch {
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                ',' if depth == 0 => {
+                    parts.push(inner[start..i].trim());
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        parts.push(inner[start..].trim());
+        parts
+    }
+
     /// Create a color from RGBA float components (0.0-1.0)
     pub fn from_rgba_float(r: f64, g: f64, b: f64, a: f64) -> Self {
         Self {
@@ -90,12 +270,152 @@ impl Color {
     }
 }
 
+/// The Catppuccin Mocha palette, for use with
+/// [`Config::register_named_colors`](crate::Config::register_named_colors)
+pub const CATPPUCCIN_MOCHA: &[(&str, Color)] = &[
+    ("rosewater", hex_rgb(0xf5e0dc)),
+    ("flamingo", hex_rgb(0xf2cdcd)),
+    ("pink", hex_rgb(0xf5c2e7)),
+    ("mauve", hex_rgb(0xcba6f7)),
+    ("red", hex_rgb(0xf38ba8)),
+    ("maroon", hex_rgb(0xeba0ac)),
+    ("peach", hex_rgb(0xfab387)),
+    ("yellow", hex_rgb(0xf9e2af)),
+    ("green", hex_rgb(0xa6e3a1)),
+    ("teal", hex_rgb(0x94e2d5)),
+    ("sky", hex_rgb(0x89dceb)),
+    ("sapphire", hex_rgb(0x74c7ec)),
+    ("blue", hex_rgb(0x89b4fa)),
+    ("lavender", hex_rgb(0xb4befe)),
+    ("text", hex_rgb(0xcdd6f4)),
+    ("subtext1", hex_rgb(0xbac2de)),
+    ("subtext0", hex_rgb(0xa6adc8)),
+    ("overlay2", hex_rgb(0x9399b2)),
+    ("overlay1", hex_rgb(0x7f849c)),
+    ("overlay0", hex_rgb(0x6c7086)),
+    ("surface2", hex_rgb(0x585b70)),
+    ("surface1", hex_rgb(0x45475a)),
+    ("surface0", hex_rgb(0x313244)),
+    ("base", hex_rgb(0x1e1e2e)),
+    ("mantle", hex_rgb(0x181825)),
+    ("crust", hex_rgb(0x11111b)),
+];
+
+/// Opaque color from a `0xRRGGBB` literal
+const fn hex_rgb(rgb: u32) -> Color {
+    Color {
+        r: (rgb >> 16) as u8,
+        g: (rgb >> 8) as u8,
+        b: rgb as u8,
+        a: 255,
+    }
+}
+
+impl std::str::FromStr for Color {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
 impl fmt::Display for Color {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "rgba({}, {}, {}, {})", self.r, self.g, self.b, self.a)
     }
 }
 
+/// Unit suffix attached to a numeric [`ConfigValue::Quantity`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Unit {
+    /// Percentage (`50%`)
+    Percent,
+
+    /// Pixels (`10px`)
+    Pixels,
+
+    /// Milliseconds (`500ms`)
+    Milliseconds,
+
+    /// Seconds (`2s`)
+    Seconds,
+
+    /// Degrees (`45deg`)
+    Degrees,
+
+    /// Radians (`1.57rad`)
+    Radians,
+}
+
+impl Unit {
+    /// Every unit, with `ms` ahead of `s` so suffixes can be matched from the end
+    const ALL: [Unit; 6] = [
+        Unit::Percent,
+        Unit::Pixels,
+        Unit::Milliseconds,
+        Unit::Seconds,
+        Unit::Degrees,
+        Unit::Radians,
+    ];
+
+    /// The suffix used for this unit in config files
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            Unit::Percent => "%",
+            Unit::Pixels => "px",
+            Unit::Milliseconds => "ms",
+            Unit::Seconds => "s",
+            Unit::Degrees => "deg",
+            Unit::Radians => "rad",
+        }
+    }
+
+    /// Look up a unit by its suffix
+    pub fn from_suffix(suffix: &str) -> Option<Self> {
+        match suffix {
+            "%" => Some(Unit::Percent),
+            "px" => Some(Unit::Pixels),
+            "ms" => Some(Unit::Milliseconds),
+            "s" => Some(Unit::Seconds),
+            "deg" => Some(Unit::Degrees),
+            "rad" => Some(Unit::Radians),
+            _ => None,
+        }
+    }
+
+    /// Convert `value` from this unit to `target`
+    ///
+    /// Returns `None` if the units measure different things (e.g. `ms` and `deg`).
+    pub fn convert(&self, value: f64, target: Unit) -> Option<f64> {
+        match (self, target) {
+            (a, b) if *a == b => Some(value),
+            (Unit::Milliseconds, Unit::Seconds) => Some(value / 1000.0),
+            (Unit::Seconds, Unit::Milliseconds) => Some(value * 1000.0),
+            (Unit::Degrees, Unit::Radians) => Some(value.to_radians()),
+            (Unit::Radians, Unit::Degrees) => Some(value.to_degrees()),
+            _ => None,
+        }
+    }
+
+    /// Split a string like `500ms` into its number and unit
+    ///
+    /// Returns `None` if the suffix is not a known unit or the number is invalid.
+    pub fn parse_quantity(s: &str) -> Option<(f64, Unit)> {
+        let s = s.trim();
+        let (number, unit) = Self::ALL
+            .iter()
+            .find_map(|unit| Some((s.strip_suffix(unit.suffix())?, *unit)))?;
+        let value = ConfigValue::parse_float(number.trim_end()).ok()?;
+        Some((value, unit))
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.suffix())
+    }
+}
+
 /// Trait for custom value types
 pub trait CustomValueType: Any + fmt::Debug {
     /// Parse a value from a string
@@ -106,6 +426,39 @@ pub trait CustomValueType: Any + fmt::Debug {
 
     /// Clone the custom value
     fn clone_value(&self, value: &dyn Any) -> Box<dyn Any>;
+
+    /// Format a value as it should be written back to a config
+    ///
+    /// The result must parse back to the same value with [`parse`](Self::parse).
+    fn to_string(&self, value: &dyn Any) -> String;
+}
+
+/// The variant of a [`ConfigValue`], without its data
+///
+/// Returned by [`ConfigValue::kind`] and [`Config::kind_of`](crate::Config::kind_of).
+/// Booleans are stored as [`Int`](ValueKind::Int).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValueKind {
+    /// [`ConfigValue::Int`]
+    Int,
+
+    /// [`ConfigValue::Float`]
+    Float,
+
+    /// [`ConfigValue::String`]
+    String,
+
+    /// [`ConfigValue::Vec2`]
+    Vec2,
+
+    /// [`ConfigValue::Color`]
+    Color,
+
+    /// [`ConfigValue::Quantity`]
+    Quantity,
+
+    /// [`ConfigValue::Custom`], whatever its type name
+    Custom,
 }
 
 /// Configuration value types
@@ -126,6 +479,10 @@ pub enum ConfigValue {
     /// RGBA color
     Color(Color),
 
+    /// Number with a unit suffix (only produced for keys registered with
+    /// [`Config::register_quantity_key`](crate::Config::register_quantity_key))
+    Quantity { value: f64, unit: Unit },
+
     /// Custom type with handler
     Custom {
         type_name: String,
@@ -160,9 +517,14 @@ impl ConfigValue {
     }
 
     /// Try to get the value as a Vec2
+    ///
+    /// Strings holding a pair of numbers, like Hyprland's `size = 800 600`, are accepted too;
+    /// they stay strings when parsed so `get_string` keeps returning the text.
     pub fn as_vec2(&self) -> ParseResult<Vec2> {
         match self {
             ConfigValue::Vec2(v) => Ok(*v),
+            ConfigValue::String(s) => Vec2::parse(s)
+                .map_err(|_| ConfigError::type_error("value", "Vec2", self.type_name())),
             _ => Err(ConfigError::type_error("value", "Vec2", self.type_name())),
         }
     }
@@ -175,6 +537,27 @@ impl ConfigValue {
         }
     }
 
+    /// Try to get the value as a number and its unit
+    pub fn as_quantity(&self) -> ParseResult<(f64, Unit)> {
+        match self {
+            ConfigValue::Quantity { value, unit } => Ok((*value, *unit)),
+            _ => Err(ConfigError::type_error(
+                "value",
+                "Quantity",
+                self.type_name(),
+            )),
+        }
+    }
+
+    /// Try to get the value converted to the given unit
+    ///
+    /// Fails if the value is not a quantity or its unit can't be converted to `unit`.
+    pub fn as_quantity_in(&self, unit: Unit) -> ParseResult<f64> {
+        let (value, from) = self.as_quantity()?;
+        from.convert(value, unit)
+            .ok_or_else(|| ConfigError::type_error("value", unit.suffix(), from.suffix()))
+    }
+
     /// Try to get the value as a custom type
     pub fn as_custom<T: 'static>(&self) -> ParseResult<&T> {
         match self {
@@ -185,6 +568,19 @@ impl ConfigValue {
         }
     }
 
+    /// Get the variant of this value
+    pub fn kind(&self) -> ValueKind {
+        match self {
+            ConfigValue::Int(_) => ValueKind::Int,
+            ConfigValue::Float(_) => ValueKind::Float,
+            ConfigValue::String(_) => ValueKind::String,
+            ConfigValue::Vec2(_) => ValueKind::Vec2,
+            ConfigValue::Color(_) => ValueKind::Color,
+            ConfigValue::Quantity { .. } => ValueKind::Quantity,
+            ConfigValue::Custom { .. } => ValueKind::Custom,
+        }
+    }
+
     /// Get the type name of this value
     pub fn type_name(&self) -> &str {
         match self {
@@ -193,10 +589,44 @@ impl ConfigValue {
             ConfigValue::String(_) => "String",
             ConfigValue::Vec2(_) => "Vec2",
             ConfigValue::Color(_) => "Color",
+            ConfigValue::Quantity { .. } => "Quantity",
             ConfigValue::Custom { type_name, .. } => type_name,
         }
     }
 
+    /// Parse an unquoted value the way a config does, without variables or expressions
+    ///
+    /// Booleans become `Int(0|1)`; then colors, comma-separated Vec2s, integers and floats
+    /// are tried in turn, and anything else is kept as a trimmed `String`. Space-separated
+    /// pairs stay strings so arbitrary text isn't mistaken for a Vec2.
+    pub(crate) fn parse_with_colors(s: &str, names: &HashMap<String, Color>) -> Self {
+        let s = s.trim();
+
+        if let Ok(b) = ConfigValue::parse_bool(s) {
+            return ConfigValue::Int(if b { 1 } else { 0 });
+        }
+
+        if let Ok(color) = Color::parse_with_names(s, names) {
+            return ConfigValue::Color(color);
+        }
+
+        if s.contains(',')
+            && let Ok(vec2) = Vec2::parse(s)
+        {
+            return ConfigValue::Vec2(vec2);
+        }
+
+        if let Ok(i) = ConfigValue::parse_int(s) {
+            return ConfigValue::Int(i);
+        }
+
+        if let Ok(f) = ConfigValue::parse_float(s) {
+            return ConfigValue::Float(f);
+        }
+
+        ConfigValue::String(s.to_string())
+    }
+
     /// Parse a boolean value (true/false/on/off/yes/no)
     pub fn parse_bool(s: &str) -> ParseResult<bool> {
         match s.to_lowercase().as_str() {
@@ -218,10 +648,112 @@ impl ConfigValue {
     }
 
     /// Parse a float
+    ///
+    /// Accepts decimal notation with a `.` separator and an optional exponent (`1.5e-3`),
+    /// independent of the system locale. `inf` and `nan` are not numbers in a config.
     pub fn parse_float(s: &str) -> ParseResult<f64> {
+        if !is_float_literal(s) {
+            return Err(ConfigError::invalid_number(s, "invalid float"));
+        }
         s.parse::<f64>()
             .map_err(|_| ConfigError::invalid_number(s, "invalid float"))
     }
+
+    /// Format a float so it reads back as the same float
+    ///
+    /// Uses the shortest representation that round-trips (`0.95`, not `0.9500000000000001`),
+    /// always with a `.` or an exponent so it isn't read back as an integer.
+    pub fn format_float(value: f64) -> String {
+        format!("{:?}", value)
+    }
+}
+
+/// `[+-]digits[.digits][(e|E)[+-]digits]`, with at least one digit before the exponent
+fn is_float_literal(s: &str) -> bool {
+    let unsigned = s.strip_prefix(['+', '-']).unwrap_or(s);
+    let (mantissa, exponent) = match unsigned.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (unsigned, None),
+    };
+
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    let mantissa_ok =
+        !(whole.is_empty() && fraction.is_empty()) && digits(whole) && digits(fraction);
+
+    let exponent_ok = exponent.is_none_or(|exponent| {
+        let exponent = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+        !exponent.is_empty() && digits(exponent)
+    });
+
+    mantissa_ok && exponent_ok
+}
+
+/// Parses text with the same rules as an unquoted config value
+///
+/// Text that isn't a boolean, color, Vec2 or number is kept as a [`ConfigValue::String`],
+/// so `config.set("gaps", "5")` stores the same value as writing `gaps = 5`. Use
+/// [`ConfigValue::String`] directly to store text as-is. `ConfigValue::try_from` is
+/// available through this impl and never fails.
+///
+/// # Example
+///
+/// ```
+/// use hyprlang::{Color, ConfigValue};
+///
+/// let value = ConfigValue::from("rgba(ff0000ff)");
+/// assert_eq!(value.as_color().unwrap(), Color::from_rgba(255, 0, 0, 255));
+/// assert_eq!(ConfigValue::try_from("yes").unwrap().as_int().unwrap(), 1);
+/// assert_eq!(ConfigValue::from(" kitty ").as_string().unwrap(), "kitty");
+/// ```
+impl From<&str> for ConfigValue {
+    fn from(s: &str) -> Self {
+        Self::parse_with_colors(s, &HashMap::new())
+    }
+}
+
+/// Parses text like [`From<&str>`](#impl-From%3C%26str%3E-for-ConfigValue)
+impl From<String> for ConfigValue {
+    fn from(s: String) -> Self {
+        Self::from(s.as_str())
+    }
+}
+
+impl From<i64> for ConfigValue {
+    fn from(v: i64) -> Self {
+        ConfigValue::Int(v)
+    }
+}
+
+/// Lets integer literals be passed where a value is expected, as in `config.set("gaps", 5)`
+impl From<i32> for ConfigValue {
+    fn from(v: i32) -> Self {
+        ConfigValue::Int(v.into())
+    }
+}
+
+impl From<f64> for ConfigValue {
+    fn from(v: f64) -> Self {
+        ConfigValue::Float(v)
+    }
+}
+
+impl From<Color> for ConfigValue {
+    fn from(v: Color) -> Self {
+        ConfigValue::Color(v)
+    }
+}
+
+impl From<Vec2> for ConfigValue {
+    fn from(v: Vec2) -> Self {
+        ConfigValue::Vec2(v)
+    }
+}
+
+impl From<(f64, f64)> for ConfigValue {
+    fn from((x, y): (f64, f64)) -> Self {
+        ConfigValue::Vec2(Vec2::new(x, y))
+    }
 }
 
 impl fmt::Debug for ConfigValue {
@@ -232,6 +764,7 @@ impl fmt::Debug for ConfigValue {
             ConfigValue::String(v) => write!(f, "String({:?})", v),
             ConfigValue::Vec2(v) => write!(f, "Vec2({:?})", v),
             ConfigValue::Color(v) => write!(f, "Color({:?})", v),
+            ConfigValue::Quantity { value, unit } => write!(f, "Quantity({}{})", value, unit),
             ConfigValue::Custom { type_name, .. } => write!(f, "Custom({})", type_name),
         }
     }
@@ -241,15 +774,116 @@ impl fmt::Display for ConfigValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             ConfigValue::Int(v) => write!(f, "{}", v),
-            ConfigValue::Float(v) => write!(f, "{}", v),
+            ConfigValue::Float(v) => f.write_str(&ConfigValue::format_float(*v)),
             ConfigValue::String(v) => write!(f, "{}", v),
             ConfigValue::Vec2(v) => write!(f, "{}", v),
             ConfigValue::Color(v) => write!(f, "{}", v),
+            ConfigValue::Quantity { value, unit } => write!(f, "{}{}", value, unit),
             ConfigValue::Custom { type_name, .. } => write!(f, "<{}>", type_name),
         }
     }
 }
 
+/// Types that can be read out of a [`ConfigValue`], used by
+/// [`Config::get_first`](crate::Config::get_first)
+pub trait FromConfigValue: Sized {
+    /// Convert the value, or fail with a type error
+    fn from_config_value(value: &ConfigValue) -> ParseResult<Self>;
+}
+
+impl FromConfigValue for i64 {
+    fn from_config_value(value: &ConfigValue) -> ParseResult<Self> {
+        value.as_int()
+    }
+}
+
+impl FromConfigValue for f64 {
+    fn from_config_value(value: &ConfigValue) -> ParseResult<Self> {
+        value.as_float()
+    }
+}
+
+impl FromConfigValue for String {
+    fn from_config_value(value: &ConfigValue) -> ParseResult<Self> {
+        value.as_string().map(String::from)
+    }
+}
+
+impl FromConfigValue for Vec2 {
+    fn from_config_value(value: &ConfigValue) -> ParseResult<Self> {
+        value.as_vec2()
+    }
+}
+
+impl FromConfigValue for Color {
+    fn from_config_value(value: &ConfigValue) -> ParseResult<Self> {
+        value.as_color()
+    }
+}
+
+impl FromConfigValue for (f64, Unit) {
+    fn from_config_value(value: &ConfigValue) -> ParseResult<Self> {
+        value.as_quantity()
+    }
+}
+
+impl TryFrom<ConfigValue> for i64 {
+    type Error = ConfigError;
+
+    fn try_from(value: ConfigValue) -> ParseResult<Self> {
+        value.as_int()
+    }
+}
+
+/// Integers are widened, as with [`ConfigValue::as_float`]
+impl TryFrom<ConfigValue> for f64 {
+    type Error = ConfigError;
+
+    fn try_from(value: ConfigValue) -> ParseResult<Self> {
+        value.as_float()
+    }
+}
+
+impl TryFrom<ConfigValue> for String {
+    type Error = ConfigError;
+
+    fn try_from(value: ConfigValue) -> ParseResult<Self> {
+        match value {
+            ConfigValue::String(s) => Ok(s),
+            other => Err(ConfigError::type_error(
+                "value",
+                "String",
+                other.type_name(),
+            )),
+        }
+    }
+}
+
+impl TryFrom<ConfigValue> for Color {
+    type Error = ConfigError;
+
+    fn try_from(value: ConfigValue) -> ParseResult<Self> {
+        value.as_color()
+    }
+}
+
+/// Strings holding a pair of numbers are accepted, as with [`ConfigValue::as_vec2`]
+impl TryFrom<ConfigValue> for Vec2 {
+    type Error = ConfigError;
+
+    fn try_from(value: ConfigValue) -> ParseResult<Self> {
+        value.as_vec2()
+    }
+}
+
+impl TryFrom<ConfigValue> for (f64, f64) {
+    type Error = ConfigError;
+
+    fn try_from(value: ConfigValue) -> ParseResult<Self> {
+        value.as_vec2().map(|v| (v.x, v.y))
+    }
+}
+
 /// Wrapper for config values with metadata
 #[derive(Clone)]
 pub struct ConfigValueEntry {
@@ -261,6 +895,21 @@ pub struct ConfigValueEntry {
 
     /// The raw string representation (before parsing)
     pub raw: String,
+
+    /// File the value was read from (`None` for values parsed from strings or set in code)
+    pub source_file: Option<PathBuf>,
+
+    /// Line (1-based) of the assignment within its source
+    pub line: Option<usize>,
+
+    /// Category path as components, e.g. `["decoration", "blur"]` for `decoration:blur:size`
+    pub category_path: Vec<String>,
+
+    /// [`Config::generation`](crate::Config::generation) at which this value was last written
+    pub generation: u64,
+
+    /// Whether the assignment was parsed under `# hyprlang noerror true`
+    pub noerror: bool,
 }
 
 impl ConfigValueEntry {
@@ -269,6 +918,11 @@ impl ConfigValueEntry {
             value,
             set_by_user: true,
             raw,
+            source_file: None,
+            line: None,
+            category_path: Vec::new(),
+            generation: 0,
+            noerror: false,
         }
     }
 
@@ -277,6 +931,23 @@ impl ConfigValueEntry {
             value: value.clone(),
             set_by_user: false,
             raw: value.to_string(),
+            source_file: None,
+            line: None,
+            category_path: Vec::new(),
+            generation: 0,
+            noerror: false,
+        }
+    }
+
+    /// The value as text
+    ///
+    /// Strings are borrowed as stored and custom types as written; other values are
+    /// formatted.
+    pub fn text(&self) -> Cow<'_, str> {
+        match &self.value {
+            ConfigValue::String(s) => Cow::Borrowed(s),
+            ConfigValue::Custom { .. } => Cow::Borrowed(&self.raw),
+            value => Cow::Owned(value.to_string()),
         }
     }
 }
@@ -287,6 +958,226 @@ impl fmt::Debug for ConfigValueEntry {
             .field("value", &self.value)
             .field("set_by_user", &self.set_by_user)
             .field("raw", &self.raw)
+            .field("source_file", &self.source_file)
+            .field("line", &self.line)
+            .field("category_path", &self.category_path)
+            .field("generation", &self.generation)
+            .field("noerror", &self.noerror)
             .finish()
     }
 }
+
+/// How [`normalize_key`] treats letter case
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum KeyCase {
+    /// Keys are case-sensitive, as in Hyprland
+    #[default]
+    Sensitive,
+
+    /// Keys are lowercased, so `General:Border_Size` and `general:border_size` are the same
+    Insensitive,
+}
+
+/// What happens when a regular category block appears more than once
+///
+/// Blocks are compared by their full path, so `decoration { blur { } }` defines both
+/// `decoration` and `decoration:blur`. Blocks read from `source`d files count too.
+///
+/// Special category blocks naming the same instance, such as two `device[mouse]` blocks,
+/// repeat each other as well. When merged, a later block's values replace the earlier
+/// ones and values it doesn't set are kept. Every block of an anonymous category is a new
+/// instance, so it never repeats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CategoryMerge {
+    /// Values of every block are merged, as in Hyprland
+    #[default]
+    Merge,
+
+    /// Values are merged and each repeated block is recorded in
+    /// [`Config::category_redefinitions`](crate::Config::category_redefinitions)
+    Warn,
+
+    /// A repeated block is a [`ConfigError::DuplicateCategory`] error
+    Error,
+}
+
+/// Where a regular category block was written
+///
+/// See [`Config::category_definitions`](crate::Config::category_definitions).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategoryDefinition {
+    /// Full path of the category, e.g. `decoration:blur`
+    pub category: String,
+
+    /// File the block was read from (`None` for strings)
+    pub file: Option<PathBuf>,
+
+    /// 1-based line of the block's opening brace
+    pub line: usize,
+}
+
+impl fmt::Display for CategoryDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        write!(f, "{}: {}", self.line, self.category)
+    }
+}
+
+/// The canonical form of a `category:key` path
+///
+/// Whitespace around each `:`-separated part is removed and, with
+/// [`KeyCase::Insensitive`], letters are lowercased: `"General : Border_Size "` becomes
+/// `General:Border_Size`, or `general:border_size`. Keys already in canonical form are
+/// borrowed.
+///
+/// ```rust
+/// use hyprlang::{KeyCase, normalize_key};
+///
+/// let key = "General : Border_Size ";
+/// assert_eq!(normalize_key(key, KeyCase::Sensitive), "General:Border_Size");
+/// assert_eq!(normalize_key(key, KeyCase::Insensitive), "general:border_size");
+/// assert_eq!(normalize_key("general:border_size", KeyCase::Insensitive), "general:border_size");
+/// ```
+pub fn normalize_key(key: &str, case: KeyCase) -> Cow<'_, str> {
+    let lowercase = case == KeyCase::Insensitive;
+    let canonical = key.split(':').all(|part| part.trim() == part)
+        && !(lowercase && key.chars().any(char::is_uppercase));
+    if canonical {
+        return Cow::Borrowed(key);
+    }
+
+    let mut normalized = key.split(':').map(str::trim).collect::<Vec<_>>().join(":");
+    if lowercase {
+        normalized = normalized.to_lowercase();
+    }
+    Cow::Owned(normalized)
+}
+
+/// Where a substituted variable's value came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VariableSource {
+    /// Defined in the config with `$NAME = value` (or set in code, with no location)
+    Config {
+        file: Option<PathBuf>,
+        line: Option<usize>,
+    },
+
+    /// Read from the process environment
+    Environment,
+
+    /// Provided by the application with [`Config::seed_variable`](crate::Config::seed_variable)
+    Seeded,
+}
+
+/// A single `$VAR` substitution in an [`ExpansionTrace`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariableSubstitution {
+    /// Variable name (without `$`)
+    pub name: String,
+
+    /// Text the variable expanded to
+    pub value: String,
+
+    /// Where the variable was defined
+    pub source: VariableSource,
+}
+
+/// A variable as listed by [`Config::variable_entries`](crate::Config::variable_entries)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariableEntry {
+    /// Variable name (without `$`)
+    pub name: String,
+
+    /// Value after escapes and variable expansion
+    pub value: String,
+
+    /// Text the variable was defined with, as written after `=`
+    pub raw: String,
+
+    /// File the definition is in, if known
+    pub file: Option<PathBuf>,
+
+    /// Line of the definition, if known
+    pub line: Option<usize>,
+
+    /// Whether the value was seeded with
+    /// [`Config::seed_variable`](crate::Config::seed_variable) and not defined by the config
+    pub seeded: bool,
+}
+
+/// A single `{{expr}}` evaluation in an [`ExpansionTrace`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpressionStep {
+    /// Expression text after variable substitution
+    pub expression: String,
+
+    /// Result of the evaluation
+    pub result: i64,
+}
+
+/// Step-by-step account of how a value was produced, returned by
+/// [`Config::explain`](crate::Config::explain)
+#[derive(Debug, Clone)]
+pub struct ExpansionTrace {
+    /// The key being explained
+    pub key: String,
+
+    /// Raw text as written in the config
+    pub raw: String,
+
+    /// Variable substitutions, in the order they appear in `raw`
+    pub substitutions: Vec<VariableSubstitution>,
+
+    /// Text after variable substitution, before expressions are evaluated
+    pub expanded: String,
+
+    /// Expression evaluations, in order
+    pub expressions: Vec<ExpressionStep>,
+
+    /// The stored value
+    pub value: ConfigValue,
+
+    /// File the value was read from
+    pub source_file: Option<PathBuf>,
+
+    /// Line of the assignment within its source
+    pub line: Option<usize>,
+}
+
+impl fmt::Display for ExpansionTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} = {}", self.key, self.value)?;
+        match (&self.source_file, self.line) {
+            (Some(file), Some(line)) => writeln!(f, "  defined at {}:{}", file.display(), line)?,
+            (None, Some(line)) => writeln!(f, "  defined at line {}", line)?,
+            _ => {}
+        }
+        writeln!(f, "  raw: {}", self.raw)?;
+
+        for substitution in &self.substitutions {
+            write!(f, "  ${} -> {}", substitution.name, substitution.value)?;
+            match &substitution.source {
+                VariableSource::Config {
+                    file: Some(file),
+                    line: Some(line),
+                } => writeln!(f, " (defined at {}:{})", file.display(), line)?,
+                VariableSource::Config {
+                    line: Some(line), ..
+                } => writeln!(f, " (defined at line {})", line)?,
+                VariableSource::Config { .. } => writeln!(f)?,
+                VariableSource::Environment => writeln!(f, " (environment)")?,
+                VariableSource::Seeded => writeln!(f, " (seeded)")?,
+            }
+        }
+
+        if !self.substitutions.is_empty() {
+            writeln!(f, "  expanded: {}", self.expanded)?;
+        }
+        for step in &self.expressions {
+            writeln!(f, "  {{{{{}}}}} = {}", step.expression, step.result)?;
+        }
+        Ok(())
+    }
+}
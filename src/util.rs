@@ -0,0 +1,94 @@
+//! Helpers for working with handler values outside of a config
+//!
+//! [`split_args`] and [`join_args`] are the splitting rules used by
+//! [`HandlerContext::args`](crate::HandlerContext::args) and the typed Hyprland models, so a
+//! parser built on top of the crate reads arguments exactly as the crate does.
+
+/// Split a value into comma-separated arguments
+///
+/// Commas inside single or double quotes (with `\"` escapes) don't split, so
+/// `sh -c "a, b"` is one argument. A quote only opens after a character that isn't a letter
+/// or digit, which keeps apostrophes in text such as `Don't close` from swallowing the
+/// arguments after them. Arguments are trimmed and keep their quotes.
+///
+/// ```rust
+/// use hyprlang::util::split_args;
+///
+/// assert_eq!(split_args("SUPER, Q, exec, kitty"), vec!["SUPER", "Q", "exec", "kitty"]);
+/// assert_eq!(split_args(r#"exec, sh -c "a, b""#), vec!["exec", r#"sh -c "a, b""#]);
+/// assert_eq!(split_args("Don't close, killactive"), vec!["Don't close", "killactive"]);
+/// ```
+pub fn split_args(value: &str) -> Vec<String> {
+    splitn_args(value, usize::MAX)
+}
+
+/// Split a value into at most `n` arguments, the last one holding the rest of the value
+///
+/// Splitting follows [`split_args`]. This is how binds keep the commas in their dispatcher
+/// arguments.
+///
+/// ```rust
+/// use hyprlang::util::splitn_args;
+///
+/// assert_eq!(
+///     splitn_args("SUPER, E, exec, kitty, --hold", 4),
+///     vec!["SUPER", "E", "exec", "kitty, --hold"]
+/// );
+/// ```
+pub fn splitn_args(value: &str, n: usize) -> Vec<String> {
+    let mut args = Vec::new();
+    if n == 0 {
+        return args;
+    }
+
+    let mut current = String::new();
+    let mut quote = None;
+    let mut previous = None;
+    let mut chars = value.chars();
+
+    while let Some(ch) = chars.next() {
+        match (ch, quote) {
+            ('\\', Some('"')) if chars.as_str().starts_with('"') => {
+                current.push(ch);
+                current.extend(chars.next());
+            }
+            ('"' | '\'', None) if !previous.is_some_and(char::is_alphanumeric) => {
+                quote = Some(ch);
+                current.push(ch);
+            }
+            (_, Some(open)) if ch == open => {
+                quote = None;
+                current.push(ch);
+            }
+            (',', None) if args.len() + 1 < n => {
+                args.push(std::mem::take(&mut current).trim().to_string())
+            }
+            _ => current.push(ch),
+        }
+        previous = Some(ch);
+    }
+    args.push(current.trim().to_string());
+    args
+}
+
+/// Join arguments into a value, separated by `", "`
+///
+/// This is the inverse of [`split_args`] for trimmed arguments without unquoted commas.
+///
+/// ```rust
+/// use hyprlang::util::{join_args, split_args};
+///
+/// let value = join_args(&["SUPER", "Q", "exec", r#"sh -c "a, b""#]);
+/// assert_eq!(value, r#"SUPER, Q, exec, sh -c "a, b""#);
+/// assert_eq!(join_args(&split_args("a,b ,  c")), "a, b, c");
+/// ```
+pub fn join_args<S: AsRef<str>>(args: &[S]) -> String {
+    let mut value = String::new();
+    for (index, arg) in args.iter().enumerate() {
+        if index > 0 {
+            value.push_str(", ");
+        }
+        value.push_str(arg.as_ref());
+    }
+    value
+}
@@ -1,13 +1,24 @@
 use crate::error::{ConfigError, ParseResult};
+use crate::escaping::Syntax;
 use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+type EnvFn = Rc<dyn Fn(&str) -> Option<String>>;
 
 /// Variable storage and resolution system
+#[derive(Clone)]
 pub struct VariableManager {
     /// User-defined variables
     variables: HashMap<String, String>,
 
     /// Dependencies between variables (for cycle detection)
     dependencies: HashMap<String, HashSet<String>>,
+
+    /// Lookup for environment variables (`None` reads the process environment)
+    env: Option<EnvFn>,
+
+    /// How variable references are written
+    syntax: Syntax,
 }
 
 impl VariableManager {
@@ -15,6 +26,34 @@ impl VariableManager {
         Self {
             variables: HashMap::new(),
             dependencies: HashMap::new(),
+            env: None,
+            syntax: Syntax::default(),
+        }
+    }
+
+    /// Create a variable manager reading references written in `syntax`, e.g. `@VAR`
+    pub fn with_syntax(syntax: Syntax) -> Self {
+        Self {
+            syntax,
+            ..Self::new()
+        }
+    }
+
+    /// Replace the process environment with `provider` for environment lookups
+    ///
+    /// Used for `$VAR` expansion and `# hyprlang if env:VAR` conditions.
+    pub fn set_env_provider<F>(&mut self, provider: F)
+    where
+        F: Fn(&str) -> Option<String> + 'static,
+    {
+        self.env = Some(Rc::new(provider));
+    }
+
+    /// Look up an environment variable through the env provider
+    pub fn env(&self, name: &str) -> Option<String> {
+        match &self.env {
+            Some(provider) => provider(name),
+            None => std::env::var(name).ok(),
         }
     }
 
@@ -40,18 +79,91 @@ impl VariableManager {
 
     /// Expand all variables in a string (including environment variables)
     pub fn expand(&self, input: &str) -> ParseResult<String> {
-        self.expand_with_chain(input, &mut Vec::new())
+        self.expand_with_chain(input, &mut Vec::new(), None)
+    }
+
+    /// Expand all variables in a string, recording each substitution as `(name, value)`
+    ///
+    /// Only variables referenced directly by `input` are recorded, in the order they appear.
+    pub fn expand_traced(
+        &self,
+        input: &str,
+        substitutions: &mut Vec<(String, String)>,
+    ) -> ParseResult<String> {
+        self.expand_with_chain(input, &mut Vec::new(), Some(substitutions))
+    }
+
+    /// First variable referenced by `input` that is neither defined nor set in the environment
+    pub fn find_undefined(&self, input: &str) -> Option<String> {
+        let mut rest = input;
+        while let Some(ch) = rest.chars().next() {
+            let Some((name, length)) = self.syntax.variable_at(rest) else {
+                rest = &rest[ch.len_utf8()..];
+                continue;
+            };
+            if !name.is_empty()
+                && !self.variables.contains_key(name)
+                && self.env(name).is_none()
+                && self.longest_prefix_variable(name).is_none()
+            {
+                return Some(name.to_string());
+            }
+            rest = &rest[length..];
+        }
+        None
+    }
+
+    /// Defined variables referenced directly by `input`, in the order they appear
+    ///
+    /// A `$VARsuffix` reference names the variable it falls back to, as in expansion.
+    pub fn references(&self, input: &str) -> Vec<String> {
+        let mut names = Vec::new();
+        let mut rest = input;
+        while let Some(ch) = rest.chars().next() {
+            let Some((name, length)) = self.syntax.variable_at(rest) else {
+                rest = &rest[ch.len_utf8()..];
+                continue;
+            };
+            let defined = if self.variables.contains_key(name) {
+                Some(name)
+            } else if self.env(name).is_none() {
+                self.longest_prefix_variable(name)
+            } else {
+                None
+            };
+            if let Some(name) = defined {
+                names.push(name.to_string());
+            }
+            rest = &rest[length..];
+        }
+        names
     }
 
     /// Expand variables with cycle detection
-    fn expand_with_chain(&self, input: &str, chain: &mut Vec<String>) -> ParseResult<String> {
+    fn expand_with_chain(
+        &self,
+        input: &str,
+        chain: &mut Vec<String>,
+        mut substitutions: Option<&mut Vec<(String, String)>>,
+    ) -> ParseResult<String> {
         let mut result = String::new();
-        let mut chars = input.chars().peekable();
-
-        while let Some(ch) = chars.next() {
-            if ch == '$' {
-                // Read the variable name
-                let var_name = self.read_variable_name(&mut chars);
+        let mut rest = input;
+
+        while let Some(ch) = rest.chars().next() {
+            if let Some((name, length)) = self.syntax.variable_at(rest) {
+                let reference = &rest[..length];
+                rest = &rest[length..];
+                let mut var_name = name.to_string();
+
+                // Like upstream hyprlang, `$VARsuffix` falls back to the longest defined
+                // variable that prefixes the name, keeping the rest as literal text
+                let mut suffix = String::new();
+                if !self.variables.contains_key(&var_name)
+                    && self.env(&var_name).is_none()
+                    && let Some(prefix) = self.longest_prefix_variable(&var_name)
+                {
+                    suffix = var_name.split_off(prefix.len());
+                }
 
                 // Check for circular dependency
                 if chain.contains(&var_name) {
@@ -63,42 +175,44 @@ impl VariableManager {
                 let value = if let Some(val) = self.variables.get(&var_name) {
                     // User-defined variable
                     chain.push(var_name.clone());
-                    let expanded = self.expand_with_chain(val, chain)?;
+                    let expanded = self.expand_with_chain(val, chain, None)?;
                     chain.pop();
                     expanded
-                } else if let Ok(env_val) = std::env::var(&var_name) {
+                } else if let Some(env_val) = self.env(&var_name) {
                     // Environment variable
                     env_val
                 } else {
-                    // Variable not found - return as-is with $
-                    result.push('$');
-                    result.push_str(&var_name);
+                    // Variable not found - return as-is
+                    result.push_str(reference);
                     continue;
                 };
 
+                if let Some(substitutions) = substitutions.as_deref_mut() {
+                    substitutions.push((var_name, value.clone()));
+                }
                 result.push_str(&value);
+                result.push_str(&suffix);
             } else {
                 result.push(ch);
+                rest = &rest[ch.len_utf8()..];
             }
         }
 
         Ok(result)
     }
 
-    /// Read a variable name from the character stream
-    fn read_variable_name(&self, chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
-        let mut name = String::new();
-
-        while let Some(&ch) = chars.peek() {
-            if ch.is_alphanumeric() || ch == '_' {
-                name.push(ch);
-                chars.next();
-            } else {
-                break;
-            }
+    /// Find the longest defined variable name that `name` starts with
+    ///
+    /// A reference with a suffix, like `${VAR}`, names its variable exactly.
+    fn longest_prefix_variable(&self, name: &str) -> Option<&str> {
+        if !self.syntax.variable_suffix.is_empty() {
+            return None;
         }
-
-        name
+        self.variables
+            .keys()
+            .filter(|candidate| !candidate.is_empty() && name.starts_with(candidate.as_str()))
+            .max_by_key(|candidate| candidate.len())
+            .map(|candidate| candidate.as_str())
     }
 
     /// Get all variable names
@@ -117,6 +231,19 @@ impl VariableManager {
         self.dependencies.entry(from).or_default().insert(to);
     }
 
+    /// Replace the variables `name` depends on
+    pub fn set_dependencies(&mut self, name: String, dependencies: HashSet<String>) {
+        self.dependencies.insert(name, dependencies);
+    }
+
+    /// Get the variables a given variable depends on
+    pub fn get_dependencies(&self, var_name: &str) -> Vec<&str> {
+        self.dependencies
+            .get(var_name)
+            .map(|deps| deps.iter().map(|name| name.as_str()).collect())
+            .unwrap_or_default()
+    }
+
     /// Get all variables that depend on a given variable
     pub fn get_dependents(&self, var_name: &str) -> Vec<&str> {
         self.dependencies
@@ -175,6 +302,14 @@ mod tests {
         let vm = VariableManager::new();
         // Undefined variables are left as-is
         assert_eq!(vm.expand("$UNDEFINED").unwrap(), "$UNDEFINED");
+        assert_eq!(
+            vm.find_undefined("a $UNDEFINED"),
+            Some("UNDEFINED".to_string())
+        );
+
+        let mut vm = VariableManager::new();
+        vm.set("MOD".to_string(), "SUPER".to_string());
+        assert_eq!(vm.find_undefined("$MOD, Q, $MODSHIFT, $"), None);
     }
 
     #[test]
@@ -193,4 +328,18 @@ mod tests {
 
         assert_eq!(vm.expand("start $VAR end").unwrap(), "start middle end");
     }
+
+    #[test]
+    fn test_references() {
+        let mut vm = VariableManager::new();
+        vm.set_env_provider(|name| (name == "HOME").then(|| "/home/me".to_string()));
+        vm.set("MOD".to_string(), "SUPER".to_string());
+        vm.set("GAP".to_string(), "5".to_string());
+
+        assert_eq!(
+            vm.references("$MOD, $MODSHIFT, $HOME, $MISSING, $GAP"),
+            vec!["MOD", "MOD", "GAP"]
+        );
+        assert!(vm.references("no variables").is_empty());
+    }
 }
@@ -0,0 +1,120 @@
+//! Tests for building configs programmatically with `ConfigBuilder`.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Color, Config, ConfigBuilder, ConfigValue, Vec2};
+
+fn desktop() -> ConfigBuilder {
+    ConfigBuilder::new()
+        .variable("mod", "SUPER")
+        .variable("accent", "rgba(33ccffee)")
+        .category("general", |c| {
+            c.int("gaps_in", 5)
+                .float("opacity", 1.0)
+                .string("col.active_border", "$accent")
+        })
+        .category("animations", |c| {
+            c.bool("enabled", true)
+                .handler("bezier", "ease, 0.05, 0.9, 0.1, 1.05")
+        })
+        .handler("bind", "$mod, Q, exec, kitty")
+        .handler("bind", "$mod, C, killactive")
+}
+
+#[test]
+fn test_build_values() {
+    let config = desktop().build().unwrap();
+
+    assert_eq!(config.get_variable("mod"), Some("SUPER"));
+    assert_eq!(config.get_int("general:gaps_in").unwrap(), 5);
+    assert_eq!(config.get_float("general:opacity").unwrap(), 1.0);
+    assert!(config.get_color("general:col.active_border").is_ok());
+    assert_eq!(
+        config.get_handler_calls("bind").unwrap(),
+        &vec![
+            "SUPER, Q, exec, kitty".to_string(),
+            "SUPER, C, killactive".to_string()
+        ]
+    );
+    assert_eq!(
+        config.get_handler_calls("animations:bezier").unwrap().len(),
+        1
+    );
+}
+
+#[test]
+fn test_typed_values_round_trip() {
+    let config = ConfigBuilder::new()
+        .value("a", ConfigValue::Int(-3))
+        .value("b", ConfigValue::Float(0.5))
+        .value("c", ConfigValue::Color(Color::from_rgb(255, 0, 128)))
+        .value("d", ConfigValue::Vec2(Vec2 { x: 10.0, y: 20.5 }))
+        .value("e", ConfigValue::String("hello # world".to_string()))
+        .build()
+        .unwrap();
+
+    assert_eq!(config.get_int("a").unwrap(), -3);
+    assert_eq!(config.get_float("b").unwrap(), 0.5);
+    assert_eq!(config.get_color("c").unwrap(), Color::from_rgb(255, 0, 128));
+    assert_eq!(config.get_vec2("d").unwrap(), Vec2 { x: 10.0, y: 20.5 });
+    assert_eq!(config.get_string("e").unwrap(), "hello # world");
+}
+
+#[test]
+fn test_special_categories() {
+    let config = ConfigBuilder::new()
+        .special_category("device", "mouse", |c| c.float("sensitivity", -0.5))
+        .special_category("device", "keyboard", |c| c.string("kb_layout", "us"))
+        .build()
+        .unwrap();
+
+    let mouse = config.get_special_category("device", "mouse").unwrap();
+    assert_eq!(mouse.get("sensitivity").unwrap().as_float().unwrap(), -0.5);
+    let keyboard = config.get_special_category("device", "keyboard").unwrap();
+    assert_eq!(
+        keyboard.get("kb_layout").unwrap().as_string().unwrap(),
+        "us"
+    );
+}
+
+#[test]
+fn test_build_into_keeps_registrations() {
+    let mut config = Config::new();
+    config.register_option("general:gaps_in", ConfigValue::Int(0));
+
+    ConfigBuilder::new()
+        .category("general", |c| c.int("gaps_in", 8))
+        .build_into(&mut config)
+        .unwrap();
+    assert_eq!(config.get_int("general:gaps_in").unwrap(), 8);
+
+    let wrong_type = ConfigBuilder::new().category("general", |c| c.string("gaps_in", "wide"));
+    assert!(wrong_type.build_into(&mut config).is_err());
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_document_matches_source() {
+    let builder = desktop();
+    let config = builder.build().unwrap();
+
+    assert_eq!(config.serialize(), builder.to_source());
+}
+
+#[cfg(feature = "hyprland")]
+#[test]
+fn test_build_with_hyprland_profile() {
+    use hyprlang::HyprlandProfile;
+
+    let mut config = Config::new();
+    config.apply_profile(&HyprlandProfile);
+    desktop()
+        .handler("bindl", ", XF86AudioMute, exec, pamixer -t")
+        .build_into(&mut config)
+        .unwrap();
+
+    // `bindl` is a flagged `bind`, so the builder didn't register a handler for it
+    let records = config.get_handler_call_records("bind").unwrap();
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[2].flags.as_deref(), Some("l"));
+}
@@ -0,0 +1,80 @@
+//! Tests for viewing special category instances with their defaults.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, ConfigValue, SpecialCategoryDescriptor, SpecialCategoryInstance};
+
+fn devices() -> Config {
+    let mut config = Config::new();
+    config.register_special_category(
+        SpecialCategoryDescriptor::keyed("device", "name")
+            .with_defaults([("accel_profile", ConfigValue::String("adaptive".to_string()))]),
+    );
+    config
+        .parse("device[mouse] {\n    sensitivity = 0.5\n    accel_profile = flat\n}\n")
+        .unwrap();
+    config
+}
+
+#[test]
+fn test_view_reads_typed_values_and_origins() {
+    let config = devices();
+    let mouse = config.special_category_view("device", "mouse").unwrap();
+
+    assert_eq!(mouse.category(), "device");
+    assert_eq!(mouse.key(), Some("mouse"));
+    assert_eq!(mouse.get_float("sensitivity").unwrap(), 0.5);
+    assert_eq!(mouse.get_string("accel_profile").unwrap(), "flat");
+    assert!(mouse.is_set_by_user("accel_profile"));
+    assert!(!mouse.is_default("accel_profile"));
+
+    assert!(mouse.get_int("sensitivity").is_err());
+    assert_eq!(
+        mouse.get_int("missing").unwrap_err().to_string(),
+        "Configuration key 'device[mouse]:missing' not found"
+    );
+    assert!(config.special_category_view("device", "pad").is_err());
+}
+
+#[test]
+fn test_view_applies_defaults_registered_later() {
+    let mut config = devices();
+    config.register_special_category_value("device", "enabled", ConfigValue::Int(1));
+
+    let mouse = config.special_category_view("device", "mouse").unwrap();
+    assert_eq!(mouse.get_int("enabled").unwrap(), 1);
+    assert!(mouse.is_default("enabled"));
+    assert!(!mouse.is_set_by_user("enabled"));
+    assert_eq!(
+        mouse.properties(),
+        vec!["accel_profile", "enabled", "sensitivity"]
+    );
+
+    // `get_special_category` only has what the instance holds
+    let values = config.get_special_category("device", "mouse").unwrap();
+    assert!(!values.contains_key("enabled"));
+}
+
+#[test]
+fn test_snapshot_outlives_the_config() {
+    let snapshot: SpecialCategoryInstance = {
+        let mut config = devices();
+        config.register_special_category_value("device", "enabled", ConfigValue::Int(1));
+        let view = config.special_category_view("device", "mouse").unwrap();
+        view.snapshot()
+    };
+
+    assert_eq!(snapshot.key.as_deref(), Some("mouse"));
+    assert_eq!(
+        snapshot
+            .get("sensitivity")
+            .unwrap()
+            .value
+            .as_float()
+            .unwrap(),
+        0.5
+    );
+    assert!(snapshot.get("sensitivity").unwrap().set_by_user);
+    assert_eq!(snapshot.get("enabled").unwrap().value.as_int().unwrap(), 1);
+    assert!(!snapshot.get("enabled").unwrap().set_by_user);
+}
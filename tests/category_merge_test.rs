@@ -0,0 +1,244 @@
+//! Tests for repeated category blocks and where categories are defined.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{
+    CategoryMerge, Config, ConfigError, ConfigOptions, ConfigValue, Severity,
+    SpecialCategoryDescriptor,
+};
+use std::fs;
+
+const REPEATED: &str = "general {\n    gaps_in = 5\n}\ngeneral {\n    gaps_out = 10\n}\n";
+
+const REPEATED_DEVICE: &str = "device[mouse] {\n    sensitivity = 0.5\n    accel_profile = flat\n}\n\
+                               device[keyboard] {\n    repeat_rate = 50\n}\n\
+                               device[mouse] {\n    sensitivity = 0.8\n}\n";
+
+fn with_policy(category_merge: CategoryMerge) -> Config {
+    Config::with_options(ConfigOptions {
+        category_merge,
+        ..Default::default()
+    })
+}
+
+fn with_devices(category_merge: CategoryMerge) -> Config {
+    let mut config = with_policy(category_merge);
+    config.register_special_category(
+        SpecialCategoryDescriptor::keyed("device", "name")
+            .with_defaults([("natural_scroll", ConfigValue::Int(0))]),
+    );
+    config
+}
+
+#[test]
+fn test_blocks_merge_by_default() {
+    let mut config = Config::new();
+    config.parse(REPEATED).unwrap();
+
+    assert_eq!(config.get_int("general:gaps_in").unwrap(), 5);
+    assert_eq!(config.get_int("general:gaps_out").unwrap(), 10);
+    assert!(config.category_redefinitions().is_empty());
+}
+
+#[test]
+fn test_category_definitions_list_every_block() {
+    let mut config = Config::new();
+    config
+        .parse(
+            "decoration {\n    blur {\n        size = 3\n    }\n}\n\
+             decoration:rounding = 4\n\
+             decoration {\n    blur {\n        passes = 2\n    }\n}\n",
+        )
+        .unwrap();
+
+    let lines = |category| -> Vec<usize> {
+        config
+            .category_definitions(category)
+            .iter()
+            .map(|definition| definition.line)
+            .collect()
+    };
+    assert_eq!(lines("decoration"), vec![1, 7]);
+    assert_eq!(lines("decoration:blur"), vec![2, 8]);
+    assert_eq!(lines(" decoration : blur "), vec![2, 8]);
+    assert!(lines("general").is_empty());
+    assert_eq!(
+        config.category_definitions("decoration:blur")[1].to_string(),
+        "8: decoration:blur"
+    );
+}
+
+#[test]
+fn test_warn_records_redefinitions() {
+    let mut config = with_policy(CategoryMerge::Warn);
+    config.parse(REPEATED).unwrap();
+
+    assert_eq!(config.get_int("general:gaps_out").unwrap(), 10);
+    let repeated = config.category_redefinitions();
+    assert_eq!(repeated.len(), 1);
+    assert_eq!(
+        (repeated[0].category.as_str(), repeated[0].line),
+        ("general", 4)
+    );
+
+    config.clear_category_definitions();
+    assert!(config.category_redefinitions().is_empty());
+    assert!(config.category_definitions("general").is_empty());
+}
+
+#[test]
+fn test_check_warns_about_redefinitions() {
+    let config = with_policy(CategoryMerge::Warn);
+    let diagnostics = config.check(REPEATED);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert_eq!(diagnostics[0].line, Some(4));
+    assert_eq!(
+        diagnostics[0].message,
+        "category 'general' was already defined"
+    );
+}
+
+#[test]
+fn test_error_rejects_repeated_block() {
+    let mut config = with_policy(CategoryMerge::Error);
+    let error = config.parse(REPEATED).unwrap_err();
+
+    assert!(matches!(
+        error,
+        ConfigError::DuplicateCategory {
+            line: 4,
+            first_line: 1,
+            ..
+        }
+    ));
+    assert_eq!(
+        error.to_string(),
+        "Category 'general' at line 4 was already defined at line 1"
+    );
+    assert!(config.get("general:gaps_out").is_err());
+}
+
+#[test]
+fn test_definitions_across_sourced_files() {
+    let dir = std::env::temp_dir().join("hyprlang_category_merge_test");
+    fs::create_dir_all(&dir).unwrap();
+    let main = dir.join("main.conf");
+    let extra = dir.join("extra.conf");
+    fs::write(
+        &main,
+        "general {\n    gaps_in = 5\n}\nsource = ./extra.conf\n",
+    )
+    .unwrap();
+    fs::write(&extra, "\ngeneral {\n    gaps_out = 10\n}\n").unwrap();
+
+    let mut config = Config::new();
+    config.parse_file(&main).unwrap();
+    let files: Vec<_> = config
+        .category_definitions("general")
+        .iter()
+        .map(|definition| (definition.file.clone().unwrap(), definition.line))
+        .collect();
+    assert_eq!(files.len(), 2);
+    assert!(files[0].0.ends_with("main.conf") && files[0].1 == 1);
+    assert!(files[1].0.ends_with("extra.conf") && files[1].1 == 2);
+
+    let mut strict = with_policy(CategoryMerge::Error);
+    let error = strict.parse_file(&main).unwrap_err().to_string();
+    assert!(error.contains("extra.conf:2 was already defined at"));
+    assert!(error.ends_with("main.conf:1"));
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_repeated_special_instances_merge() {
+    let mut config = with_devices(CategoryMerge::Merge);
+    config.parse(REPEATED_DEVICE).unwrap();
+
+    let mouse = config.get_special_category("device", "mouse").unwrap();
+    assert_eq!(mouse["sensitivity"].as_float().unwrap(), 0.8);
+    assert_eq!(mouse["accel_profile"].as_string().unwrap(), "flat");
+    assert_eq!(mouse["natural_scroll"].as_int().unwrap(), 0);
+
+    let lines: Vec<usize> = config
+        .category_definitions("device[mouse]")
+        .iter()
+        .map(|definition| definition.line)
+        .collect();
+    assert_eq!(lines, vec![1, 8]);
+    assert!(config.category_redefinitions().is_empty());
+}
+
+#[test]
+fn test_repeated_special_instances_warn() {
+    let mut config = with_devices(CategoryMerge::Warn);
+    config.parse(REPEATED_DEVICE).unwrap();
+
+    let repeated = config.category_redefinitions();
+    assert_eq!(repeated.len(), 1);
+    assert_eq!(repeated[0].category, "device[mouse]");
+    assert_eq!(repeated[0].line, 8);
+}
+
+#[test]
+fn test_error_rejects_repeated_special_instance() {
+    let mut config = with_devices(CategoryMerge::Error);
+    let error = config.parse(REPEATED_DEVICE).unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Category 'device[mouse]' at line 8 was already defined at line 1"
+    );
+    // The first block's values are untouched
+    let mouse = config.get_special_category("device", "mouse").unwrap();
+    assert_eq!(mouse["sensitivity"].as_float().unwrap(), 0.5);
+}
+
+#[test]
+fn test_anonymous_instances_never_repeat() {
+    let mut config = with_policy(CategoryMerge::Error);
+    config.register_special_category(SpecialCategoryDescriptor::anonymous("rule"));
+    config
+        .parse("rule {\n    a = 1\n}\nrule {\n    a = 2\n}\n")
+        .unwrap();
+
+    assert_eq!(config.list_special_category_keys("rule").len(), 2);
+}
+
+#[test]
+fn test_special_instances_repeated_across_sourced_files() {
+    let dir = std::env::temp_dir().join("hyprlang_category_merge_special_test");
+    fs::create_dir_all(&dir).unwrap();
+    let main = dir.join("main.conf");
+    let extra = dir.join("extra.conf");
+    fs::write(
+        &main,
+        "device[mouse] {\n    sensitivity = 0.5\n}\nsource = ./extra.conf\n",
+    )
+    .unwrap();
+    fs::write(&extra, "device[mouse] {\n    accel_profile = flat\n}\n").unwrap();
+
+    let mut config = with_devices(CategoryMerge::Merge);
+    config.parse_file(&main).unwrap();
+    let mouse = config.get_special_category("device", "mouse").unwrap();
+    assert_eq!(mouse["sensitivity"].as_float().unwrap(), 0.5);
+    assert_eq!(mouse["accel_profile"].as_string().unwrap(), "flat");
+    let definitions = config.category_definitions("device[mouse]");
+    assert!(definitions[0].file.as_ref().unwrap().ends_with("main.conf"));
+    assert!(
+        definitions[1]
+            .file
+            .as_ref()
+            .unwrap()
+            .ends_with("extra.conf")
+    );
+
+    let mut strict = with_devices(CategoryMerge::Error);
+    let error = strict.parse_file(&main).unwrap_err().to_string();
+    assert!(error.contains("extra.conf:1 was already defined at"));
+    assert!(error.ends_with("main.conf:1"));
+
+    fs::remove_dir_all(&dir).ok();
+}
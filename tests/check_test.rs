@@ -0,0 +1,142 @@
+//! Tests for checking configs without changing the checked `Config`.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, ConfigOptions, FunctionHandler, Severity};
+use std::cell::Cell;
+use std::fs;
+use std::rc::Rc;
+
+fn configured() -> (Config, Rc<Cell<usize>>) {
+    let calls = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&calls);
+    let mut config = Config::with_options(ConfigOptions {
+        capture_unknown_handlers: true,
+        ..Default::default()
+    });
+    config.register_handler_fn("bind", move |_| {
+        counter.set(counter.get() + 1);
+        Ok(())
+    });
+    config.register_handler_alias("bindkey", "bind");
+    config.register_option("general:gaps_in", hyprlang::ConfigValue::Int(5));
+    config.register_option("general:border_size", hyprlang::ConfigValue::Int(1));
+    (config, calls)
+}
+
+#[test]
+fn test_check_reports_every_error_with_its_line() {
+    let (config, _) = configured();
+    let diagnostics = config.check(
+        "general {\n    gaps_in = {{5 +}}\n    border_size = 2\n}\n\
+         bind = SUPER, Q, exec, kitty\n\
+         $a = $b\n$b = $a\nbind = SUPER, W, exec, $a\n",
+    );
+
+    let errors: Vec<_> = diagnostics
+        .iter()
+        .map(|diagnostic| (diagnostic.severity, diagnostic.line))
+        .collect();
+    assert_eq!(
+        errors,
+        vec![(Severity::Error, Some(2)), (Severity::Error, Some(8))]
+    );
+    assert!(diagnostics[1].message.contains("bind = SUPER, W, exec, $a"));
+}
+
+#[test]
+fn test_check_leaves_config_unchanged() {
+    let (mut config, calls) = configured();
+    config.parse("general {\n    gaps_in = 10\n}\n").unwrap();
+
+    let diagnostics =
+        config.check("general {\n    gaps_in = 20\n}\n$mod = SUPER\nbind = $mod, Q, exec, kitty\n");
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+
+    assert_eq!(config.get_int("general:gaps_in").unwrap(), 10);
+    assert_eq!(config.get_variable("mod"), None);
+    assert!(config.get_handler_calls("bind").is_none());
+    // Handlers are not run while checking
+    assert_eq!(calls.get(), 0);
+
+    // The same config can be checked again
+    assert!(config.check("bind = SUPER, Q, exec, kitty").is_empty());
+}
+
+#[test]
+fn test_check_reports_grammar_errors() {
+    let (config, _) = configured();
+    let diagnostics = config.check("general {\n    gaps_in = 5\n");
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert!(diagnostics[0].line.is_some());
+}
+
+#[test]
+fn test_check_warnings() {
+    let (config, _) = configured();
+    let diagnostics = config.check("bindkey = SUPER, Q, exec, kitty\nplugin = foo.so\n");
+
+    let warnings: Vec<_> = diagnostics
+        .iter()
+        .map(|diagnostic| (diagnostic.severity, diagnostic.line, diagnostic.to_string()))
+        .collect();
+    assert_eq!(
+        warnings,
+        vec![
+            (
+                Severity::Warning,
+                Some(1),
+                "1: warning: 'bindkey' is deprecated, use 'bind' instead".to_string()
+            ),
+            (
+                Severity::Warning,
+                Some(2),
+                "2: warning: 'plugin' is not a known handler or option".to_string()
+            ),
+        ]
+    );
+    assert!(config.deprecations().is_empty());
+    assert!(config.unknown_handlers().is_empty());
+}
+
+#[test]
+fn test_check_does_not_need_handler_state() {
+    let mut config = Config::new();
+    config.register_handler(
+        "bind",
+        FunctionHandler::stateful_with_flags("bind", |_, _| Ok(())),
+    );
+
+    // Running the handler would fail without state; checking only looks it up
+    assert!(
+        config
+            .check("bindm = SUPER, mouse:272, movewindow\n")
+            .is_empty()
+    );
+    assert!(config.parse("bind = SUPER, Q, exec, kitty\n").is_err());
+}
+
+#[test]
+fn test_check_file_with_sources() {
+    let dir = std::env::temp_dir().join(format!("hyprlang_check_test_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("main.conf"),
+        "source = ./binds.conf\ngeneral:gaps_in = 5\n",
+    )
+    .unwrap();
+    fs::write(dir.join("binds.conf"), "\ngeneral:gaps_in = {{1 /}}\n").unwrap();
+
+    let (config, _) = configured();
+    let diagnostics = config.check_file(dir.join("main.conf"));
+
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    let diagnostic = &diagnostics[0];
+    assert!(diagnostic.file.as_ref().unwrap().ends_with("binds.conf"));
+    assert_eq!(diagnostic.line, Some(2));
+    assert_eq!(config.get_int("general:gaps_in").unwrap(), 5);
+
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,76 @@
+//! Tests for cloning a `Config` into an independent working copy.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, ConfigValue, SpecialCategoryDescriptor};
+use std::cell::Cell;
+use std::rc::Rc;
+
+#[test]
+fn test_clone_values_are_independent() {
+    let mut original = Config::new();
+    original
+        .parse("$GAP = 5\ngeneral {\n    gaps_in = $GAP\n}\n")
+        .unwrap();
+
+    let mut copy = original.clone();
+    copy.set("general:gaps_in", ConfigValue::Int(20)).unwrap();
+    copy.set_variable("GAP".to_string(), "10".to_string());
+
+    assert_eq!(original.get_int("general:gaps_in").unwrap(), 5);
+    assert_eq!(original.get_variable("GAP"), Some("5"));
+    assert_eq!(copy.get_int("general:gaps_in").unwrap(), 20);
+    assert_eq!(copy.get_variable("GAP"), Some("10"));
+}
+
+#[test]
+fn test_clone_shares_handlers() {
+    let calls = Rc::new(Cell::new(0));
+    let counter = calls.clone();
+
+    let mut original = Config::new();
+    original.register_handler_fn("exec", move |_| {
+        counter.set(counter.get() + 1);
+        Ok(())
+    });
+    original.parse("exec = one").unwrap();
+
+    let mut copy = original.clone();
+    copy.parse("exec = two").unwrap();
+
+    // Both configs run the same registered handler
+    assert_eq!(calls.get(), 2);
+    assert_eq!(original.get_handler_calls("exec").unwrap().len(), 1);
+    assert_eq!(copy.get_handler_calls("exec").unwrap().len(), 2);
+}
+
+#[test]
+fn test_clone_special_categories_are_independent() {
+    let mut original = Config::new();
+    original.register_special_category(SpecialCategoryDescriptor::keyed("device", "name"));
+    original
+        .parse("device[mouse] {\n    sensitivity = 1\n}\n")
+        .unwrap();
+
+    let mut copy = original.clone();
+    copy.parse("device[keyboard] {\n    repeat_rate = 50\n}\n")
+        .unwrap();
+
+    assert_eq!(original.list_special_category_keys("device"), vec!["mouse"]);
+    let mut keys = copy.list_special_category_keys("device");
+    keys.sort();
+    assert_eq!(keys, vec!["keyboard", "mouse"]);
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_clone_documents_are_independent() {
+    let mut original = Config::new();
+    original.parse("border_size = 2\n").unwrap();
+
+    let mut copy = original.clone();
+    copy.set_int("border_size", 8).unwrap();
+
+    assert_eq!(original.serialize(), "border_size = 2\n");
+    assert_eq!(copy.serialize(), "border_size = 8\n");
+}
@@ -0,0 +1,46 @@
+//! Fixtures shared by the integration tests.
+
+#![allow(dead_code)]
+
+use hyprlang::Config;
+#[cfg(feature = "hyprland")]
+use hyprlang::Hyprland;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static TEST_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Create an empty temporary directory for test files, named after the test file
+pub fn create_test_dir(name: &str) -> PathBuf {
+    let counter = TEST_COUNTER.fetch_add(1, Ordering::SeqCst);
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let dir =
+        std::env::temp_dir().join(format!("hyprlang_{}_test_{}_{}", name, timestamp, counter));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Remove a directory created by [`create_test_dir`]
+pub fn cleanup_test_dir(dir: &Path) {
+    let _ = fs::remove_dir_all(dir);
+}
+
+/// Parse `input` into a new [`Config`], panicking on errors
+pub fn parsed(input: &str) -> Config {
+    let mut config = Config::new();
+    config.parse(input).unwrap();
+    config
+}
+
+/// Parse `input` into a new [`Hyprland`], panicking on errors
+#[cfg(feature = "hyprland")]
+pub fn parsed_hyprland(input: &str) -> Hyprland {
+    let mut hypr = Hyprland::new();
+    hypr.parse(input).unwrap();
+    hypr
+}
@@ -1,4 +1,6 @@
-use hyprlang::Config;
+#![cfg(feature = "config")]
+
+use hyprlang::{ActiveCondition, Config, DirectiveState};
 
 #[test]
 fn test_basic_if_exists() {
@@ -285,3 +287,183 @@ fn test_unclosed_if() {
     // Note: The Config doesn't expose has_unclosed_blocks,
     // but in a real scenario this would be caught
 }
+
+fn with_env(vars: &[(&str, &str)]) -> Config {
+    let vars: Vec<(String, String)> = vars
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    let mut config = Config::new();
+    config.set_env_provider(move |name| {
+        vars.iter()
+            .find(|(var, _)| var == name)
+            .map(|(_, value)| value.clone())
+    });
+    config
+}
+
+#[test]
+fn test_env_conditions() {
+    let mut config = with_env(&[("WAYLAND_DISPLAY", "wayland-1"), ("HOSTNAME", "laptop")]);
+    config
+        .parse(
+            r#"
+        # hyprlang if env:WAYLAND_DISPLAY
+        wayland = set
+        # hyprlang endif
+
+        # hyprlang if !env:DISPLAY
+        no_x11 = set
+        # hyprlang endif
+
+        # hyprlang if env:HOSTNAME == laptop
+        scale = 1.5
+        # hyprlang endif
+
+        # hyprlang if env:HOSTNAME == "desktop"
+        scale = 1.0
+        # hyprlang endif
+
+        # hyprlang if env:HOSTNAME != desktop
+        battery = set
+        # hyprlang endif
+
+        # hyprlang if env:DISPLAY == :0
+        x11 = set
+        # hyprlang endif
+    "#,
+        )
+        .unwrap();
+
+    assert_eq!(config.get_string("wayland").unwrap(), "set");
+    assert_eq!(config.get_string("no_x11").unwrap(), "set");
+    assert_eq!(config.get_float("scale").unwrap(), 1.5);
+    assert_eq!(config.get_string("battery").unwrap(), "set");
+    assert!(config.get("x11").is_err());
+}
+
+#[test]
+fn test_variable_comparison() {
+    let mut config = with_env(&[]);
+    config
+        .parse(
+            r#"
+        $THEME = dark
+        # hyprlang if THEME == dark
+        background = black
+        # hyprlang endif
+        # hyprlang if !THEME == dark
+        background = white
+        # hyprlang endif
+    "#,
+        )
+        .unwrap();
+
+    assert_eq!(config.get_string("background").unwrap(), "black");
+}
+
+#[test]
+fn test_env_provider_expands_variables() {
+    let mut config = with_env(&[("TERMINAL", "foot")]);
+    config.parse("term = $TERMINAL\nhome = $HOME").unwrap();
+
+    assert_eq!(config.get_string("term").unwrap(), "foot");
+    // The process environment is not consulted
+    assert_eq!(config.get_string("home").unwrap(), "$HOME");
+}
+
+#[test]
+fn test_env_condition_requires_name() {
+    let mut config = with_env(&[]);
+    assert!(
+        config
+            .parse("# hyprlang if env:\n# hyprlang endif")
+            .is_err()
+    );
+}
+
+#[test]
+fn test_directive_state_of_dynamic_parsing() {
+    let mut config = Config::new();
+    config.parse("$VAR = value\n").unwrap();
+    assert!(config.directive_state().conditions.is_empty());
+
+    config.parse_dynamic("# hyprlang if VAR").unwrap();
+    config.parse_dynamic("# hyprlang if !VAR").unwrap();
+    let state = config.directive_state();
+    assert!(state.is_inactive());
+    assert_eq!(
+        state.conditions,
+        vec![
+            ActiveCondition {
+                condition: "VAR".to_string(),
+                value: true
+            },
+            ActiveCondition {
+                condition: "!VAR".to_string(),
+                value: false
+            },
+        ]
+    );
+
+    config.parse_dynamic("skipped = 1").unwrap();
+    config.parse_dynamic("# hyprlang endif").unwrap();
+    let state = config.directive_state();
+    assert!(!state.is_inactive());
+    assert_eq!(state.suppressed_statements, 1);
+}
+
+#[test]
+fn test_directive_states_recorded_per_file() {
+    let mut config = Config::new();
+    config
+        .parse(
+            "# hyprlang noerror true\n\
+             # hyprlang if MISSING\n\
+             a = 1\n\
+             general {\n\
+                 b = 2\n\
+             }\n\
+             # hyprlang endif\n\
+             # hyprlang if MISSING\n\
+             c = 3\n",
+        )
+        .unwrap();
+    config.parse("d = 4\n").unwrap();
+
+    let states = config.file_directive_states();
+    assert_eq!(states.len(), 2);
+    assert_eq!(states[0].file, None);
+    assert_eq!(states[0].suppressed_statements, 4);
+    assert!(states[0].noerror);
+    assert!(states[0].is_inactive());
+    assert_eq!(states[0].conditions[0].condition, "MISSING");
+
+    assert_eq!(states[1], DirectiveState::default());
+}
+
+#[test]
+fn test_directive_states_of_sourced_files() {
+    let dir = std::env::temp_dir().join(format!("hyprlang_directive_state_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let main = dir.join("main.conf");
+    std::fs::write(
+        &main,
+        "source = ./extra.conf\n# hyprlang if MISSING\na = 1\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("extra.conf"), "b = 2\n").unwrap();
+
+    let mut config = Config::new();
+    config.parse_file(&main).unwrap();
+    let states = config.file_directive_states();
+    std::fs::remove_dir_all(&dir).ok();
+
+    // A sourced file finishes before the file sourcing it
+    assert_eq!(states.len(), 2);
+    assert!(states[0].file.as_ref().unwrap().ends_with("extra.conf"));
+    assert!(!states[0].is_inactive());
+    assert!(states[1].file.as_ref().unwrap().ends_with("main.conf"));
+    assert!(states[1].is_inactive());
+    assert_eq!(states[1].suppressed_statements, 1);
+}
@@ -0,0 +1,119 @@
+//! Tests for listing, adding and removing `# hyprlang if` regions in the document.
+
+#![cfg(feature = "mutation")]
+
+mod common;
+
+use common::parsed;
+
+const INPUT: &str = "\
+$LAPTOP = 1
+# hyprlang if LAPTOP
+monitor = eDP-1
+# hyprlang endif
+general {
+    gaps_in = 5
+    # hyprlang if !LAPTOP
+    gaps_out = 20
+    # hyprlang endif
+}
+border_size = 2
+";
+
+#[test]
+fn test_list_regions() {
+    let config = parsed(INPUT);
+    let regions = config.conditional_regions();
+
+    assert_eq!(regions.len(), 2);
+    assert_eq!(regions[0].condition, "LAPTOP");
+    assert!(regions[0].parent.is_empty());
+    assert_eq!((regions[0].start, regions[0].end), (1, 3));
+
+    assert_eq!(regions[1].variable(), "LAPTOP");
+    assert!(regions[1].is_negated());
+    assert_eq!(regions[1].parent, vec![4]);
+    assert_eq!(regions[1].body(), 2..3);
+}
+
+#[test]
+fn test_nested_regions_in_document_order() {
+    let config =
+        parsed("# hyprlang if A\n# hyprlang if B\na = 1\n# hyprlang endif\n# hyprlang endif\n");
+    let conditions: Vec<_> = config
+        .conditional_regions()
+        .into_iter()
+        .map(|region| region.condition)
+        .collect();
+    assert_eq!(conditions, vec!["A", "B"]);
+}
+
+#[test]
+fn test_wrap_takes_effect_on_reparse() {
+    let mut config = parsed(INPUT);
+    let border = config
+        .document()
+        .unwrap()
+        .get_locations("border_size")
+        .unwrap()[0]
+        .path
+        .clone();
+
+    let region = config
+        .wrap_in_condition(&[], border[0]..border[0] + 1, "DESKTOP")
+        .unwrap();
+    assert_eq!(region.body(), 6..7);
+    assert!(config.conditional_regions().contains(&region));
+
+    let output = config.serialize();
+    assert!(output.ends_with("# hyprlang if DESKTOP\nborder_size = 2\n# hyprlang endif\n"));
+
+    let reparsed = parsed(&output);
+    assert!(reparsed.get("border_size").is_err());
+    assert_eq!(reparsed.get_int("general:gaps_in").unwrap(), 5);
+}
+
+#[test]
+fn test_wrap_inside_block() {
+    let mut config = parsed(INPUT);
+    config.wrap_in_condition(&[4], 0..1, "LAPTOP").unwrap();
+
+    assert!(
+        config.serialize().contains(
+            "general {\n    # hyprlang if LAPTOP\n    gaps_in = 5\n    # hyprlang endif\n"
+        )
+    );
+    assert_eq!(config.conditional_regions().len(), 3);
+}
+
+#[test]
+fn test_wrap_rejects_invalid_input() {
+    let mut config = parsed(INPUT);
+
+    // Would take the `if` but not its `endif`
+    assert!(config.wrap_in_condition(&[], 0..2, "X").is_err());
+    assert!(config.wrap_in_condition(&[], 5..9, "X").is_err());
+    assert!(config.wrap_in_condition(&[0], 0..1, "X").is_err());
+    assert!(config.wrap_in_condition(&[], 0..1, "").is_err());
+    assert!(config.wrap_in_condition(&[], 0..1, "TWO WORDS").is_err());
+
+    // Whole regions can be wrapped
+    assert!(config.wrap_in_condition(&[], 1..4, "X").is_ok());
+}
+
+#[test]
+fn test_remove_condition_keeps_body() {
+    let mut config = parsed(INPUT);
+    let region = config.conditional_regions().remove(1);
+
+    config.remove_condition(&region).unwrap();
+    assert!(
+        config
+            .serialize()
+            .contains("general {\n    gaps_in = 5\n    gaps_out = 20\n}")
+    );
+    assert_eq!(config.conditional_regions().len(), 1);
+
+    // The region no longer exists
+    assert!(config.remove_condition(&region).is_err());
+}
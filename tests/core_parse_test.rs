@@ -0,0 +1,55 @@
+//! Tests for read-only parsing into statements, available with just the `core-parse` feature.
+
+#![cfg(feature = "core-parse")]
+
+use hyprlang::{HyprlangParser, Statement, Value};
+
+#[test]
+fn test_parse_statements() {
+    let parsed = HyprlangParser::parse_config(
+        "$GAPS = 5\ngeneral {\n    gaps_in = $GAPS\n    col.active_border = rgba(33ccffee)\n}\n\
+         bind = SUPER, Q, exec, kitty\n",
+    )
+    .unwrap();
+
+    assert_eq!(parsed.statements.len(), 3);
+    match &parsed.statements[0] {
+        Statement::VariableDef { name, value, line } => {
+            assert_eq!((name.as_str(), value.as_str(), *line), ("GAPS", "5", 1));
+        }
+        other => panic!("unexpected statement: {:?}", other),
+    }
+
+    let Statement::SpecialCategoryBlock {
+        name, statements, ..
+    } = &parsed.statements[1]
+    else {
+        panic!("expected a block, got {:?}", parsed.statements[1]);
+    };
+    assert_eq!(name, "general");
+    // Values are kept as written; variables and colors are resolved by `Config`
+    assert!(matches!(
+        &statements[0],
+        Statement::Assignment { value: Value::String(value), .. } if value == "$GAPS"
+    ));
+    assert!(matches!(
+        &statements[1],
+        Statement::Assignment { value: Value::String(value), line: 4, .. }
+            if value == "rgba(33ccffee)"
+    ));
+
+    // Without registered handlers, every `key = value` line is a plain assignment
+    assert!(matches!(
+        &parsed.statements[2],
+        Statement::Assignment { key, .. } if key == &vec!["bind".to_string()]
+    ));
+}
+
+#[test]
+fn test_parse_error_location() {
+    let err = HyprlangParser::parse_config("valid = 1\ngeneral {\n").unwrap_err();
+    assert!(matches!(
+        err,
+        hyprlang::ConfigError::ParseError { line: 3, .. }
+    ));
+}
@@ -0,0 +1,144 @@
+//! Tests for variables and expressions written with other markers than `$VAR` and `{{expr}}`.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, ConfigOptions, Syntax};
+
+fn at_syntax() -> Syntax {
+    Syntax {
+        variable_prefix: "@".to_string(),
+        expression_open: "<%".to_string(),
+        expression_close: "%>".to_string(),
+        ..Syntax::default()
+    }
+}
+
+fn braced_syntax() -> Syntax {
+    Syntax {
+        variable_prefix: "${".to_string(),
+        variable_suffix: "}".to_string(),
+        ..Syntax::default()
+    }
+}
+
+fn config(syntax: Syntax) -> Config {
+    Config::with_options(ConfigOptions {
+        syntax,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_default_syntax() {
+    let syntax = Syntax::default();
+    assert_eq!(syntax.variable("GAPS"), "$GAPS");
+    assert_eq!(syntax.expression_open, "{{");
+    assert_eq!(syntax.expression_close, "}}");
+}
+
+#[test]
+fn test_custom_prefix_and_delimiters() {
+    let mut config = config(at_syntax());
+    config
+        .parse(
+            "@GAPS = 5\n\
+             @name = kitty\n\
+             general {\n\
+                 gaps_in = @GAPS\n\
+                 gaps_out = <% GAPS * 2 %>\n\
+                 border = <% @GAPS + 1 %>px\n\
+             }\n\
+             price = $5 {{not math}}\n\
+             title = \\@name is @name\n\
+             literal = \\<% 1 + 1 %>\n",
+        )
+        .unwrap();
+
+    assert_eq!(config.get_variable("GAPS"), Some("5"));
+    assert_eq!(config.get_int("general:gaps_in").unwrap(), 5);
+    assert_eq!(config.get_int("general:gaps_out").unwrap(), 10);
+    assert_eq!(config.get_string("general:border").unwrap(), "6px");
+    assert_eq!(config.get_string("price").unwrap(), "$5 {{not math}}");
+    assert_eq!(config.get_string("title").unwrap(), "@name is kitty");
+    assert_eq!(config.get_string("literal").unwrap(), "<% 1 + 1 %>");
+}
+
+#[test]
+fn test_standalone_default_expression_is_text() {
+    let mut config = config(at_syntax());
+    config.parse("value = {{1 + 2}}\n").unwrap();
+    assert_eq!(config.get_string("value").unwrap(), "{{1 + 2}}");
+}
+
+#[test]
+fn test_braced_variables() {
+    let mut config = config(braced_syntax());
+    config
+        .parse("${A} = x\n${AB} = y\none = ${A}B\ntwo = ${AB}\nopen = ${A\nplain = $A\n")
+        .unwrap();
+
+    assert_eq!(config.get_string("one").unwrap(), "xB");
+    assert_eq!(config.get_string("two").unwrap(), "y");
+    assert_eq!(config.get_string("open").unwrap(), "${A");
+    assert_eq!(config.get_string("plain").unwrap(), "$A");
+}
+
+#[test]
+fn test_handler_values_expand_custom_variables() {
+    let mut config = config(at_syntax());
+    config.register_handler_fn("exec", |_| Ok(()));
+    config
+        .parse("@term = kitty\nexec = @term --hold\n")
+        .unwrap();
+    assert_eq!(config.get_handler_calls("exec").unwrap(), &["kitty --hold"]);
+}
+
+#[test]
+fn test_escape_value_with_syntax() {
+    let syntax = at_syntax();
+    let written = syntax.escape_value("@HOME <% x %> $HOME {{x}}").unwrap();
+    assert_eq!(written, "\\@HOME <\\% x %> $HOME {{x}}");
+
+    let mut config = config(syntax);
+    config
+        .parse(&format!("@HOME = h\nvalue = {}\n", written))
+        .unwrap();
+    assert_eq!(
+        config.get_string("value").unwrap(),
+        "@HOME <% x %> $HOME {{x}}"
+    );
+}
+
+#[cfg(feature = "mutation")]
+mod mutation {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_keeps_custom_syntax() {
+        let input = "@GAPS = 5\ngeneral {\n    gaps_in = @GAPS\n}\n";
+        let mut config = config(at_syntax());
+        config.parse(input).unwrap();
+        assert_eq!(config.serialize(), input);
+
+        config.set_variable("GAPS".to_string(), "8".to_string());
+        config.set_variable("NEW".to_string(), "1".to_string());
+        config.set_string("title", "@GAPS").unwrap();
+        let serialized = config.serialize();
+        assert!(serialized.contains("@GAPS = 8\n"));
+        assert!(serialized.contains("@NEW = 1\n"));
+        assert!(serialized.contains("title = \\@GAPS\n"));
+
+        let mut reparsed = super::config(at_syntax());
+        reparsed.parse(&serialized).unwrap();
+        assert_eq!(reparsed.get_int("general:gaps_in").unwrap(), 8);
+        assert_eq!(reparsed.get_string("title").unwrap(), "@GAPS");
+    }
+
+    #[test]
+    fn test_braced_definitions_round_trip() {
+        let input = "${A} = x\none = ${A}\n";
+        let mut config = config(braced_syntax());
+        config.parse(input).unwrap();
+        assert_eq!(config.serialize(), input);
+    }
+}
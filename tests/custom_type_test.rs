@@ -0,0 +1,109 @@
+//! Tests for registered custom value types.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, ConfigError, ConfigValue, CustomValueType, ParseResult};
+use std::any::Any;
+use std::rc::Rc;
+
+/// Four margins written as `top right bottom left`
+#[derive(Debug, Clone, PartialEq)]
+struct Margins([i64; 4]);
+
+#[derive(Debug)]
+struct MarginsType;
+
+impl CustomValueType for MarginsType {
+    fn parse(&self, value: &str) -> ParseResult<Box<dyn Any>> {
+        let parts: Vec<i64> = value
+            .split_whitespace()
+            .map(|part| part.parse())
+            .collect::<Result<_, _>>()
+            .map_err(|_| ConfigError::custom(format!("invalid margins '{}'", value)))?;
+        let margins: [i64; 4] = parts
+            .try_into()
+            .map_err(|_| ConfigError::custom(format!("expected 4 margins in '{}'", value)))?;
+        Ok(Box::new(Margins(margins)))
+    }
+
+    fn type_name(&self) -> &str {
+        "margins"
+    }
+
+    fn clone_value(&self, value: &dyn Any) -> Box<dyn Any> {
+        Box::new(value.downcast_ref::<Margins>().unwrap().clone())
+    }
+
+    fn to_string(&self, value: &dyn Any) -> String {
+        let Margins(m) = value.downcast_ref::<Margins>().unwrap();
+        format!("{} {} {} {}", m[0], m[1], m[2], m[3])
+    }
+}
+
+fn margins(values: [i64; 4]) -> ConfigValue {
+    ConfigValue::Custom {
+        type_name: "margins".to_string(),
+        value: Rc::new(Margins(values)),
+    }
+}
+
+#[test]
+fn test_custom_types_lists_registered_names() {
+    let mut config = Config::new();
+    assert!(config.custom_types().is_empty());
+
+    config.register_custom_type("margins", MarginsType);
+    config.register_custom_type("insets", MarginsType);
+
+    assert_eq!(config.custom_types(), vec!["insets", "margins"]);
+}
+
+#[test]
+fn test_custom_value_is_formatted_by_its_type() {
+    let mut config = Config::new();
+    config.register_custom_type("margins", MarginsType);
+    config.set("layout:margins", margins([1, 2, 3, 4])).unwrap();
+
+    assert_eq!(config.entry("layout:margins").unwrap().text(), "1 2 3 4");
+    assert_eq!(
+        config
+            .get("layout:margins")
+            .unwrap()
+            .as_custom::<Margins>()
+            .unwrap(),
+        &Margins([1, 2, 3, 4])
+    );
+}
+
+#[test]
+fn test_unregistered_custom_value_keeps_placeholder_text() {
+    let mut config = Config::new();
+    config.set("layout:margins", margins([1, 2, 3, 4])).unwrap();
+
+    assert_eq!(config.entry("layout:margins").unwrap().text(), "<margins>");
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_custom_value_round_trips_through_document() {
+    let mut config = Config::new();
+    config.register_custom_type("margins", MarginsType);
+    config
+        .parse("layout {\n    margins = 0 0 0 0\n}\n")
+        .unwrap();
+    config
+        .set("layout:margins", margins([5, 10, 5, 10]))
+        .unwrap();
+
+    let serialized = config.serialize();
+    assert!(serialized.contains("margins = 5 10 5 10"));
+
+    let mut reparsed = Config::new();
+    reparsed.parse(&serialized).unwrap();
+    let text = reparsed.get_string("layout:margins").unwrap();
+    let value = MarginsType.parse(text).unwrap();
+    assert_eq!(
+        value.downcast_ref::<Margins>(),
+        Some(&Margins([5, 10, 5, 10]))
+    );
+}
@@ -0,0 +1,109 @@
+//! Tests for the Display tree of a config and value redaction.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, ConfigValue, SpecialCategoryDescriptor};
+
+#[test]
+fn test_display_prints_a_tree() {
+    let mut config = Config::new();
+    config.register_handler_fn("bind", |_| Ok(()));
+    config
+        .parse(
+            "$mod = SUPER\n\
+             general {\n\
+                 gaps_in = 5\n\
+                 snap {\n\
+                     enabled = true\n\
+                 }\n\
+                 border_size = 2\n\
+             }\n\
+             bind = $mod, Q, exit\n\
+             bind = $mod, T, exec, kitty\n\
+             rounding = 4\n",
+        )
+        .unwrap();
+
+    assert_eq!(
+        config.to_string(),
+        "$mod = SUPER\n\
+         rounding = 4\n\
+         general {\n\
+         \x20   border_size = 2\n\
+         \x20   gaps_in = 5\n\
+         \x20   snap {\n\
+         \x20       enabled = 1\n\
+         \x20   }\n\
+         }\n\
+         bind = SUPER, Q, exit\n\
+         bind = SUPER, T, exec, kitty\n"
+    );
+}
+
+#[test]
+fn test_display_includes_special_categories() {
+    let mut config = Config::new();
+    config.register_special_category(SpecialCategoryDescriptor::keyed("device", "name"));
+    config
+        .parse("device {\n    name = mouse\n    sensitivity = 0.5\n}\n")
+        .unwrap();
+
+    let shown = config.to_string();
+    assert!(shown.contains("device[mouse] {\n    name = mouse\n    sensitivity = 0.5\n}\n"));
+}
+
+#[test]
+fn test_redacted_patterns_mask_values() {
+    let mut config = Config::new();
+    config.register_handler_fn("exec-once", |_| Ok(()));
+    config.redact("*token*");
+    config.redact("$SECRET");
+    config.redact("exec-once");
+    config
+        .parse(
+            "$SECRET = s3cret\n\
+             $PUBLIC = visible\n\
+             plugin {\n\
+                 api_token = abc123\n\
+                 name = weather\n\
+             }\n\
+             exec-once = login --password hunter2\n",
+        )
+        .unwrap();
+
+    let shown = config.to_string();
+    assert!(shown.contains("$SECRET = [redacted]"));
+    assert!(shown.contains("$PUBLIC = visible"));
+    assert!(shown.contains("api_token = [redacted]"));
+    assert!(shown.contains("name = weather"));
+    assert!(shown.contains("exec-once = [redacted]"));
+    assert!(!shown.contains("abc123") && !shown.contains("hunter2") && !shown.contains("s3cret"));
+
+    // Stored values are untouched
+    assert_eq!(config.get_string("plugin:api_token").unwrap(), "abc123");
+    assert_eq!(config.redacted("plugin:name", "weather"), "weather");
+}
+
+#[test]
+fn test_redaction_hook_rewrites_values() {
+    let mut config = Config::new();
+    config.set_redaction_hook(|key, text| (key == "password").then(|| "*".repeat(text.len())));
+    config
+        .set("password", ConfigValue::String("hunter2".to_string()))
+        .unwrap();
+    config
+        .set("user", ConfigValue::String("alice".to_string()))
+        .unwrap();
+
+    assert_eq!(config.to_string(), "password = *******\nuser = alice\n");
+}
+
+#[test]
+fn test_debug_does_not_show_values() {
+    let mut config = Config::new();
+    config.parse("$SECRET = s3cret\ntoken = abc123\n").unwrap();
+
+    let debug = format!("{:?}", config);
+    assert!(debug.starts_with("Config { values: 1, variables: 1"));
+    assert!(!debug.contains("abc123") && !debug.contains("s3cret"));
+}
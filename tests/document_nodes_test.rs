@@ -0,0 +1,117 @@
+//! Tests for iterating, searching and rewriting document nodes.
+
+#![cfg(feature = "mutation")]
+
+use hyprlang::{Config, DocumentNode, HyprlangParser, SpecialCategoryDescriptor};
+
+const INPUT: &str = "\
+$mod = SUPER
+env = XCURSOR_SIZE,24
+general {
+    gaps_in = 5
+    gaps {
+        outer = 10
+    }
+}
+general:gaps_in = 8
+device[mouse] {
+    sensitivity = 0.5
+}
+env = QT_QPA_PLATFORM,wayland
+";
+
+#[test]
+fn test_iter_nodes_depth_first() {
+    let (_, doc) = HyprlangParser::parse_with_document(INPUT).unwrap();
+    let nodes: Vec<_> = doc
+        .iter_nodes()
+        .map(|node| (node.key().unwrap(), node.path.clone(), node.depth()))
+        .collect();
+
+    assert_eq!(
+        nodes,
+        vec![
+            ("$mod".to_string(), vec![0], 0),
+            ("env".to_string(), vec![1], 0),
+            ("general".to_string(), vec![2], 0),
+            ("general:gaps_in".to_string(), vec![2, 0], 1),
+            ("general:gaps".to_string(), vec![2, 1], 1),
+            ("general:gaps:outer".to_string(), vec![2, 1, 0], 2),
+            ("general:gaps_in".to_string(), vec![3], 0),
+            ("device[mouse]".to_string(), vec![4], 0),
+            ("device[mouse]:sensitivity".to_string(), vec![4, 0], 1),
+            ("env".to_string(), vec![5], 0),
+        ]
+    );
+}
+
+#[test]
+fn test_find_assignments() {
+    let mut config = Config::new();
+    config.register_special_category(SpecialCategoryDescriptor::keyed("device", "name"));
+    config.parse(INPUT).unwrap();
+    let doc = config.document().unwrap();
+
+    let found = doc.find_assignments("general:gaps_in");
+    assert_eq!(found.len(), 2);
+    assert_eq!(found[0].path, vec![2, 0]);
+    assert_eq!(found[0].category, vec!["general"]);
+    assert_eq!(found[1].path, vec![3]);
+    assert!(matches!(
+        found[1].node,
+        DocumentNode::Assignment { value, .. } if value == "8"
+    ));
+
+    assert!(doc.find_assignments("general:missing").is_empty());
+    assert!(doc.find_assignments("$mod").is_empty());
+}
+
+#[test]
+fn test_find_category() {
+    let (_, doc) = HyprlangParser::parse_with_document(INPUT).unwrap();
+
+    assert_eq!(doc.find_category("general:gaps").unwrap().path, vec![2, 1]);
+    let mouse = doc.find_category("device[mouse]").unwrap();
+    assert!(matches!(
+        mouse.node,
+        DocumentNode::SpecialCategoryBlock { nodes, .. } if nodes.len() == 1
+    ));
+    assert!(doc.find_category("general:gaps_in").is_none());
+    assert!(doc.find_category("decoration").is_none());
+}
+
+#[test]
+fn test_visit_mut_groups_nodes() {
+    let (_, mut doc) = HyprlangParser::parse_with_document(INPUT).unwrap();
+
+    // Move every env line to the top of its list, keeping their order
+    let mut visited = Vec::new();
+    doc.visit_mut(|category, nodes| {
+        visited.push(category.join(":"));
+        nodes.sort_by_key(
+            |node| !matches!(node, DocumentNode::Assignment { key, .. } if key[0] == "env"),
+        );
+    });
+
+    assert_eq!(
+        visited,
+        vec!["", "general", "general:gaps", "device[mouse]"]
+    );
+    assert!(
+        doc.serialize()
+            .starts_with("env = XCURSOR_SIZE,24\nenv = QT_QPA_PLATFORM,wayland\n$mod = SUPER\n")
+    );
+
+    // The index follows the rewrite
+    let paths: Vec<_> = doc
+        .get_locations("env")
+        .unwrap()
+        .iter()
+        .map(|location| location.path.clone())
+        .collect();
+    assert_eq!(paths, vec![vec![0], vec![1]]);
+    assert_eq!(
+        doc.find_assignments("general:gaps:outer")[0].path,
+        vec![3, 1, 0]
+    );
+}
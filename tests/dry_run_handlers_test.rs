@@ -0,0 +1,92 @@
+//! Tests for running handlers as dry runs.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, ConfigError, ConfigOptions, HandlerContext, Severity};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn dry_run_config() -> Config {
+    Config::with_options(ConfigOptions {
+        dry_run_handlers: true,
+        ..Default::default()
+    })
+}
+
+/// An `exec` handler that validates its value and "spawns" it outside of dry runs
+fn register_exec(config: &mut Config) -> Rc<RefCell<Vec<String>>> {
+    let spawned = Rc::new(RefCell::new(Vec::new()));
+    let log = spawned.clone();
+    config.register_handler_fn("exec", move |ctx: &HandlerContext| {
+        if ctx.value.starts_with('-') {
+            return Err(ConfigError::custom("not a command"));
+        }
+        if !ctx.dry_run {
+            log.borrow_mut().push(ctx.value.clone());
+        }
+        Ok(())
+    });
+    spawned
+}
+
+#[test]
+fn test_handlers_run_normally_by_default() {
+    let mut config = Config::new();
+    let spawned = register_exec(&mut config);
+    config.parse("exec = kitty").unwrap();
+
+    assert_eq!(*spawned.borrow(), vec!["kitty"]);
+}
+
+#[test]
+fn test_dry_run_skips_side_effects() {
+    let mut config = dry_run_config();
+    let spawned = register_exec(&mut config);
+    config.parse("exec = kitty\nexec = waybar").unwrap();
+
+    assert!(spawned.borrow().is_empty());
+    assert_eq!(config.get_handler_calls("exec").unwrap().len(), 2);
+}
+
+#[test]
+fn test_dry_run_still_validates() {
+    let mut config = dry_run_config();
+    register_exec(&mut config);
+    let error = config.parse("exec = kitty\nexec = --help").unwrap_err();
+
+    assert!(matches!(
+        error,
+        ConfigError::HandlerCallFailed { line: Some(2), .. }
+    ));
+}
+
+#[test]
+fn test_dry_run_flags_reach_the_handler() {
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let log = seen.clone();
+    let mut config = dry_run_config();
+    config.register_category_handler_fn("input", "device", move |ctx| {
+        log.borrow_mut().push((ctx.category_path(), ctx.dry_run));
+        Ok(())
+    });
+    config.parse("input {\n    device = mouse\n}").unwrap();
+
+    assert_eq!(*seen.borrow(), vec![("input".to_string(), true)]);
+}
+
+#[test]
+fn test_check_runs_handlers_as_dry_runs() {
+    let mut plain = Config::new();
+    register_exec(&mut plain);
+    let mut strict = dry_run_config();
+    let spawned = register_exec(&mut strict);
+
+    // Without dry runs, check only looks the handler up
+    assert!(plain.check("exec = --help").is_empty());
+
+    let diagnostics = strict.check("exec = kitty\nexec = --help");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert_eq!(diagnostics[0].line, Some(2));
+    assert!(spawned.borrow().is_empty());
+}
@@ -0,0 +1,82 @@
+//! Tests for empty category blocks.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, SpecialCategoryDescriptor};
+
+#[test]
+fn test_empty_blocks_parse() {
+    for input in [
+        "binds { }\n",
+        "binds {}\n",
+        "binds {\n}\n",
+        "binds {\n\n}\n",
+        "binds { # filled in later\n}\n",
+        "binds {\n    # filled in later\n}\n",
+        "decoration {\n    blur {\n    }\n    rounding = 4\n}\n",
+    ] {
+        let mut config = Config::new();
+        config
+            .parse(input)
+            .unwrap_or_else(|e| panic!("{:?}: {}", input, e));
+    }
+}
+
+#[test]
+fn test_values_around_empty_blocks() {
+    let mut config = Config::new();
+    config
+        .parse("gaps = 5\nbinds { }\ndecoration {\n    blur {\n    }\n    rounding = 4\n}\n")
+        .unwrap();
+
+    assert_eq!(config.get_int("gaps").unwrap(), 5);
+    assert_eq!(config.get_int("decoration:rounding").unwrap(), 4);
+    assert!(config.keys().iter().all(|key| !key.starts_with("binds")));
+}
+
+#[test]
+fn test_empty_special_category_block_creates_instance() {
+    let mut config = Config::new();
+    config.register_special_category(SpecialCategoryDescriptor::keyed("device", "name"));
+    config.parse("device[mouse] { }\n").unwrap();
+
+    assert_eq!(config.list_special_category_keys("device"), vec!["mouse"]);
+}
+
+#[test]
+fn test_deeply_nested_empty_blocks_parse_quickly() {
+    let input = format!("{}{}", "a {\n".repeat(40), "}\n".repeat(40));
+    let mut config = Config::new();
+    config.parse(&input).unwrap();
+}
+
+#[cfg(feature = "mutation")]
+mod mutation {
+    use hyprlang::Config;
+
+    #[test]
+    fn test_empty_blocks_round_trip() {
+        let input = "gaps = 5\nbinds { }\nplugins {}\ndecoration {\n    blur {\n    }\n}\n";
+        let mut config = Config::new();
+        config.parse(input).unwrap();
+
+        assert_eq!(config.serialize(), input);
+    }
+
+    #[test]
+    fn test_value_added_to_inline_empty_block() {
+        let mut config = Config::new();
+        config.parse("binds { } # filled in later\n").unwrap();
+        config.set_int("binds:scroll_event_delay", 300).unwrap();
+
+        let serialized = config.serialize();
+        assert_eq!(
+            serialized,
+            "binds { # filled in later\n  scroll_event_delay = 300\n}\n"
+        );
+
+        let mut reparsed = Config::new();
+        reparsed.parse(&serialized).unwrap();
+        assert_eq!(reparsed.get_int("binds:scroll_event_delay").unwrap(), 300);
+    }
+}
@@ -0,0 +1,119 @@
+//! Tests for the metadata carried by `ConfigValueEntry` and exposed via `Config::entry`.
+
+#![cfg(feature = "config")]
+
+mod common;
+
+use common::{cleanup_test_dir, create_test_dir};
+use hyprlang::{Config, ConfigValue, SpecialCategoryDescriptor};
+use std::fs;
+
+#[test]
+fn test_entry_line_and_category_path() {
+    let mut config = Config::new();
+    config
+        .parse(
+            r#"border_size = 2
+general {
+    gaps_in = 5
+    nested {
+        value = 1
+    }
+}
+a:b:c = 3
+"#,
+        )
+        .unwrap();
+
+    let entry = config.entry("border_size").unwrap();
+    assert_eq!(entry.line, Some(1));
+    assert!(entry.category_path.is_empty());
+    assert!(entry.source_file.is_none());
+    assert!(entry.set_by_user);
+
+    let entry = config.entry("general:gaps_in").unwrap();
+    assert_eq!(entry.line, Some(3));
+    assert_eq!(entry.category_path, vec!["general"]);
+
+    let entry = config.entry("general:nested:value").unwrap();
+    assert_eq!(entry.line, Some(5));
+    assert_eq!(entry.category_path, vec!["general", "nested"]);
+
+    let entry = config.entry("a:b:c").unwrap();
+    assert_eq!(entry.line, Some(8));
+    assert_eq!(entry.category_path, vec!["a", "b"]);
+
+    assert!(config.entry("missing").is_none());
+}
+
+#[test]
+fn test_entry_generation_increases() {
+    let mut config = Config::new();
+    config.parse("first = 1\nsecond = 2").unwrap();
+
+    let first = config.entry("first").unwrap().generation;
+    let second = config.entry("second").unwrap().generation;
+    assert!(first > 0);
+    assert!(second > first);
+    assert_eq!(config.generation(), second);
+
+    let before = config.generation();
+    config.set("first", ConfigValue::Int(10)).unwrap();
+
+    let entry = config.entry("first").unwrap();
+    assert!(entry.generation > before);
+    // Overwriting keeps pointing at the original definition
+    assert_eq!(entry.line, Some(1));
+
+    config.set("brand_new", ConfigValue::Int(1)).unwrap();
+    let entry = config.entry("brand_new").unwrap();
+    assert_eq!(entry.line, None);
+    assert!(entry.category_path.is_empty());
+}
+
+#[test]
+fn test_entry_source_file_across_source_directive() {
+    let dir = create_test_dir("entry_metadata");
+    let main_path = dir.join("main.conf");
+    let colors_path = dir.join("colors.conf");
+
+    fs::write(&colors_path, "\ncolors {\n    accent = 0xff00ff\n}\n").unwrap();
+    fs::write(
+        &main_path,
+        "before = 1\nsource = ./colors.conf\nafter = 2\n",
+    )
+    .unwrap();
+
+    let mut config = Config::new();
+    config.parse_file(&main_path).unwrap();
+
+    let main_canonical = main_path.canonicalize().unwrap();
+    let colors_canonical = colors_path.canonicalize().unwrap();
+
+    let entry = config.entry("colors:accent").unwrap();
+    assert_eq!(entry.source_file.as_ref(), Some(&colors_canonical));
+    assert_eq!(entry.line, Some(3));
+
+    let entry = config.entry("before").unwrap();
+    assert_eq!(entry.source_file.as_ref(), Some(&main_canonical));
+
+    // Values after a source line belong to the file that contains them
+    let entry = config.entry("after").unwrap();
+    assert_eq!(entry.source_file.as_ref(), Some(&main_canonical));
+    assert_eq!(entry.line, Some(3));
+
+    cleanup_test_dir(&dir);
+}
+
+#[test]
+fn test_special_category_entry_metadata() {
+    let mut config = Config::new();
+    config.register_special_category(SpecialCategoryDescriptor::keyed("device", "name"));
+    config
+        .parse("device[mouse] {\n    accel = flat\n}")
+        .unwrap();
+
+    let entry = config.entry("device[mouse]:accel").unwrap();
+    assert_eq!(entry.category_path, vec!["device[mouse]"]);
+    assert_eq!(entry.line, Some(2));
+}
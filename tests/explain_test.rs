@@ -0,0 +1,105 @@
+//! Tests for `Config::explain` expansion traces.
+
+#![cfg(feature = "config")]
+
+mod common;
+
+use common::{cleanup_test_dir, create_test_dir};
+use hyprlang::{Config, VariableSource};
+use std::fs;
+
+#[test]
+fn test_explain_variables_and_expressions() {
+    let mut config = Config::new();
+    config
+        .parse(
+            r#"$WIDTH = 800
+$GAP = 10
+monitor {
+    total_width = {{$WIDTH * 2}}
+    label = $WIDTH px with {{$GAP + 5}} gap
+}
+"#,
+        )
+        .unwrap();
+
+    let trace = config.explain("monitor:total_width").unwrap();
+    assert_eq!(trace.raw, "{{$WIDTH * 2}}");
+    assert_eq!(trace.expanded, "{{800 * 2}}");
+    assert_eq!(trace.substitutions.len(), 1);
+    assert_eq!(trace.substitutions[0].name, "WIDTH");
+    assert_eq!(
+        trace.substitutions[0].source,
+        VariableSource::Config {
+            file: None,
+            line: Some(1)
+        }
+    );
+    assert_eq!(trace.expressions.len(), 1);
+    assert_eq!(trace.expressions[0].result, 1600);
+    assert_eq!(trace.value.as_int().unwrap(), 1600);
+    assert_eq!(trace.line, Some(4));
+
+    let trace = config.explain("monitor:label").unwrap();
+    let names: Vec<&str> = trace
+        .substitutions
+        .iter()
+        .map(|s| s.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["WIDTH", "GAP"]);
+    assert_eq!(trace.expressions[0].expression, "10 + 5");
+    assert_eq!(trace.value.as_string().unwrap(), "800 px with 15 gap");
+}
+
+#[test]
+fn test_explain_plain_value() {
+    let mut config = Config::new();
+    config.parse("border_size = 2").unwrap();
+
+    let trace = config.explain("border_size").unwrap();
+    assert_eq!(trace.raw, "2");
+    assert_eq!(trace.expanded, "2");
+    assert!(trace.substitutions.is_empty());
+    assert!(trace.expressions.is_empty());
+
+    assert!(config.explain("missing").is_err());
+}
+
+#[test]
+fn test_explain_display() {
+    let mut config = Config::new();
+    config
+        .parse("$WIDTH = 800\ntotal_width = {{$WIDTH * 2}}")
+        .unwrap();
+
+    let text = config.explain("total_width").unwrap().to_string();
+    assert_eq!(
+        text,
+        "total_width = 1600\n  defined at line 2\n  raw: {{$WIDTH * 2}}\n  \
+         $WIDTH -> 800 (defined at line 1)\n  expanded: {{800 * 2}}\n  {{800 * 2}} = 1600\n"
+    );
+}
+
+#[test]
+fn test_explain_variable_from_sourced_file() {
+    let dir = create_test_dir("explain");
+    let vars_path = dir.join("vars.conf");
+    let main_path = dir.join("main.conf");
+
+    fs::write(&vars_path, "# sizes\n$GAP = 12\n").unwrap();
+    fs::write(&main_path, "source = ./vars.conf\ngaps_in = $GAP\n").unwrap();
+
+    let mut config = Config::new();
+    config.parse_file(&main_path).unwrap();
+
+    let trace = config.explain("gaps_in").unwrap();
+    assert_eq!(
+        trace.substitutions[0].source,
+        VariableSource::Config {
+            file: Some(vars_path.canonicalize().unwrap()),
+            line: Some(2)
+        }
+    );
+
+    cleanup_test_dir(&dir);
+}
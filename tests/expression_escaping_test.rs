@@ -1,3 +1,5 @@
+#![cfg(feature = "config")]
+
 use hyprlang::Config;
 
 #[test]
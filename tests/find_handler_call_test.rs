@@ -0,0 +1,148 @@
+//! Tests for finding handler call indices by content.
+
+#![cfg(feature = "config")]
+
+#[cfg(feature = "mutation")]
+mod common;
+
+use hyprlang::{Config, FunctionHandler};
+
+fn bind_config() -> Config {
+    let mut config = Config::new();
+    config.register_handler("bind", FunctionHandler::with_flags("bind", |_| Ok(())));
+    config
+}
+
+#[test]
+fn test_find_handler_call() {
+    let mut config = bind_config();
+    config
+        .parse(
+            "$term = kitty\nbind = SUPER, Q, exec, $term\nbind = SUPER, C, killactive\n\
+             bind = SUPER, C, killactive\n",
+        )
+        .unwrap();
+
+    // Values are matched as expanded, and the first of several equal calls is found
+    assert_eq!(
+        config.find_handler_call("bind", "SUPER, Q, exec, kitty"),
+        Some(0)
+    );
+    assert_eq!(
+        config.find_handler_call("bind", "SUPER, Q, exec, $term"),
+        None
+    );
+    assert_eq!(
+        config.find_handler_call("bind", "SUPER, C, killactive"),
+        Some(1)
+    );
+    assert_eq!(config.find_handler_call("exec", "kitty"), None);
+}
+
+#[test]
+fn test_indices_matching_records() {
+    let mut config = bind_config();
+    config
+        .parse(
+            "bind = SUPER, Q, exec, kitty\nbindl = , XF86AudioPlay, exec, playerctl play\n\
+             bind = SUPER, C, killactive\nbindl = , XF86AudioNext, exec, playerctl next\n",
+        )
+        .unwrap();
+
+    let locked =
+        config.handler_call_indices_matching("bind", |record| record.flags.as_deref() == Some("l"));
+    assert_eq!(locked, [1, 3]);
+    assert_eq!(
+        config.handler_call_indices_matching("bind", |record| record.line == Some(3)),
+        [2]
+    );
+    assert!(
+        config
+            .handler_call_indices_matching("exec", |_| true)
+            .is_empty()
+    );
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_found_indices_edit_the_matching_lines() {
+    let mut config = bind_config();
+    config
+        .parse(
+            "bind = SUPER, Q, exec, kitty\nbindl = , XF86AudioPlay, exec, playerctl play\n\
+             bind = SUPER, C, killactive\n",
+        )
+        .unwrap();
+
+    let index = config
+        .find_handler_call("bind", "SUPER, C, killactive")
+        .unwrap();
+    config
+        .update_handler_call("bind", index, "SUPER, W, killactive".to_string())
+        .unwrap();
+    let index = config
+        .find_handler_call("bind", ", XF86AudioPlay, exec, playerctl play")
+        .unwrap();
+    config.remove_handler_call("bind", index).unwrap();
+
+    assert_eq!(
+        config.serialize(),
+        "bind = SUPER, Q, exec, kitty\nbind = SUPER, W, killactive\n"
+    );
+}
+
+#[cfg(feature = "mutation")]
+mod multi_file {
+    use super::*;
+    use crate::common::{cleanup_test_dir, create_test_dir};
+    use std::fs;
+    use std::path::PathBuf;
+
+    /// A directory with `main.conf` sourcing `keys.conf` between its own binds
+    fn sourced_binds_dir() -> PathBuf {
+        let dir = create_test_dir("find_handler_call");
+        fs::write(
+            dir.join("main.conf"),
+            "bind = SUPER, Q, exec, kitty\nsource = keys.conf\nbind = SUPER, C, killactive\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("keys.conf"),
+            "bind = SUPER, B, exec, firefox\nbind = SUPER, E, exec, nautilus\n",
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_indices_edit_the_file_holding_the_call() {
+        let dir = sourced_binds_dir();
+        let mut config = bind_config();
+        config.parse_file(dir.join("main.conf")).unwrap();
+
+        let index = config
+            .find_handler_call("bind", "SUPER, E, exec, nautilus")
+            .unwrap();
+        assert_eq!(index, 2);
+        config.remove_handler_call("bind", index).unwrap();
+
+        let index = config
+            .find_handler_call("bind", "SUPER, C, killactive")
+            .unwrap();
+        config
+            .update_handler_call("bind", index, "SUPER, W, killactive".to_string())
+            .unwrap();
+        config.save_all().unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dir.join("keys.conf")).unwrap(),
+            "bind = SUPER, B, exec, firefox\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("main.conf")).unwrap(),
+            "bind = SUPER, Q, exec, kitty\nsource = keys.conf\nbind = SUPER, W, killactive\n"
+        );
+
+        cleanup_test_dir(&dir);
+    }
+}
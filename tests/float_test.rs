@@ -0,0 +1,102 @@
+//! Tests for float parsing and round-trip stable float formatting.
+
+#![cfg(feature = "config")]
+
+mod common;
+
+use common::parsed;
+use hyprlang::ConfigValue;
+use proptest::prelude::*;
+
+#[test]
+fn test_exponent_notation() {
+    let config = parsed("a = 1e3\nb = 1.5e-3\nc = -2.5E+2\nd = .5\n");
+
+    assert_eq!(config.get_float("a").unwrap(), 1000.0);
+    assert_eq!(config.get_float("b").unwrap(), 0.0015);
+    assert_eq!(config.get_float("c").unwrap(), -250.0);
+    assert_eq!(config.get_float("d").unwrap(), 0.5);
+}
+
+#[test]
+fn test_non_numbers_stay_strings() {
+    let config = parsed("a = nan\nb = inf\nc = 0,95\nd = 1e\ne = e5\nf = 1.5.2\n");
+
+    assert_eq!(config.get_string("a").unwrap(), "nan");
+    assert_eq!(config.get_string("b").unwrap(), "inf");
+    // A decimal comma is never a decimal separator
+    assert!(config.get_float("c").is_err());
+    assert_eq!(config.get_string("d").unwrap(), "1e");
+    assert_eq!(config.get_string("e").unwrap(), "e5");
+    assert_eq!(config.get_string("f").unwrap(), "1.5.2");
+
+    assert!(ConfigValue::parse_float("infinity").is_err());
+    assert!(ConfigValue::parse_float("").is_err());
+    assert!(ConfigValue::parse_float(".").is_err());
+}
+
+#[test]
+fn test_format_float() {
+    assert_eq!(ConfigValue::format_float(0.95), "0.95");
+    assert_eq!(ConfigValue::format_float(1.0), "1.0");
+    assert_eq!(ConfigValue::format_float(-0.5), "-0.5");
+    assert_eq!(ConfigValue::format_float(1e-7), "1e-7");
+    assert_eq!(ConfigValue::Float(2.0).to_string(), "2.0");
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_set_float_keeps_precision() {
+    let mut config = parsed("opacity = 0.950\nscale = 1.5\n");
+    config.set_float("scale", 0.95).unwrap();
+    config.set_float("whole", 2.0).unwrap();
+
+    // Untouched values keep the user's spelling
+    assert_eq!(
+        config.serialize(),
+        "opacity = 0.950\nscale = 0.95\nwhole = 2.0\n"
+    );
+
+    let reparsed = parsed(&config.serialize());
+    assert_eq!(reparsed.get_float("scale").unwrap(), 0.95);
+    assert!(matches!(
+        reparsed.get("whole").unwrap(),
+        ConfigValue::Float(_)
+    ));
+}
+
+proptest! {
+    #[test]
+    fn prop_format_parse_round_trip(value in any::<f64>().prop_filter("finite", |v| v.is_finite())) {
+        let formatted = ConfigValue::format_float(value);
+        prop_assert_eq!(ConfigValue::parse_float(&formatted).unwrap(), value);
+    }
+
+    #[test]
+    fn prop_config_round_trip(value in any::<f64>().prop_filter("finite", |v| v.is_finite())) {
+        let config = parsed(&format!("value = {}", ConfigValue::Float(value)));
+        prop_assert!(matches!(config.get("value").unwrap(), ConfigValue::Float(_)));
+        prop_assert_eq!(config.get_float("value").unwrap(), value);
+    }
+
+    #[test]
+    fn prop_decimal_literals_parse(whole in 0u32..100_000, fraction in 0u32..1_000_000, negative: bool) {
+        let literal = format!("{}{}.{}", if negative { "-" } else { "" }, whole, fraction);
+        prop_assert_eq!(
+            ConfigValue::parse_float(&literal).unwrap(),
+            literal.parse::<f64>().unwrap()
+        );
+    }
+}
+
+#[cfg(feature = "mutation")]
+proptest! {
+    #[test]
+    fn prop_set_float_serialize_round_trip(value in -1e6f64..1e6) {
+        let mut config = parsed("value = 0.0");
+        config.set_float("value", value).unwrap();
+
+        let reparsed = parsed(&config.serialize());
+        prop_assert_eq!(reparsed.get_float("value").unwrap(), value);
+    }
+}
@@ -0,0 +1,98 @@
+//! Tests for folding `category:key = value` lines into their category blocks.
+
+#![cfg(feature = "mutation")]
+
+use hyprlang::{Config, SerializeOptions};
+
+fn folded(input: &str) -> String {
+    let mut config = Config::new();
+    config.parse(input).unwrap();
+    config.serialize_with_options(&SerializeOptions {
+        fold_flat_assignments: true,
+        keep_indent: true,
+        ..Default::default()
+    })
+}
+
+fn values(input: &str) -> Vec<(String, String)> {
+    let mut config = Config::new();
+    config.parse(input).unwrap();
+    let mut values: Vec<_> = config
+        .keys()
+        .into_iter()
+        .map(|key| (key.to_string(), config.get(key).unwrap().to_string()))
+        .collect();
+    values.sort();
+    values
+}
+
+#[test]
+fn test_flat_lines_are_kept_without_the_option() {
+    let input = "decoration {\n    rounding = 4\n}\ndecoration:shadow = 1\n";
+    let mut config = Config::new();
+    config.parse(input).unwrap();
+    assert_eq!(config.serialize(), input);
+}
+
+#[test]
+fn test_flat_line_moves_into_block() {
+    let input = "decoration {\n    rounding = 4\n}\ngaps = 5\ndecoration:shadow = 1\n";
+    let output = folded(input);
+
+    assert_eq!(
+        output,
+        "decoration {\n    rounding = 4\n    shadow = 1\n}\ngaps = 5\n"
+    );
+    assert_eq!(values(&output), values(input));
+}
+
+#[test]
+fn test_flat_line_after_block_updates_its_value() {
+    let input = "decoration {\n    rounding = 4\n}\ndecoration:rounding = 10\n";
+    let output = folded(input);
+
+    assert_eq!(output, "decoration {\n    rounding = 10\n}\n");
+    assert_eq!(values(&output), values(input));
+}
+
+#[test]
+fn test_flat_line_before_block() {
+    let input =
+        "decoration:rounding = 10\ndecoration:shadow = 1\ndecoration {\n    rounding = 4\n}\n";
+    let output = folded(input);
+
+    assert_eq!(
+        output,
+        "decoration {\n    shadow = 1\n    rounding = 4\n}\n"
+    );
+    assert_eq!(values(&output), values(input));
+}
+
+#[test]
+fn test_flat_line_folds_into_nested_blocks() {
+    let input =
+        "decoration {\n    blur {\n        size = 3\n    }\n}\ndecoration:blur:passes = 2\n";
+    let output = folded(input);
+
+    assert_eq!(
+        output,
+        "decoration {\n    blur {\n        size = 3\n        passes = 2\n    }\n}\n"
+    );
+    assert_eq!(values(&output), values(input));
+}
+
+#[test]
+fn test_lines_without_block_or_in_conditions_stay() {
+    let input = "general:gaps_in = 5\n\
+                 decoration {\n    rounding = 4\n}\n\
+                 # hyprlang if LAPTOP\n\
+                 decoration:rounding = 0\n\
+                 # hyprlang endif\n";
+    assert_eq!(folded(input), input);
+}
+
+#[test]
+fn test_empty_block_receives_values() {
+    let output = folded("binds { }\nbinds:scroll_event_delay = 300\n");
+    assert_eq!(output, "binds {\n  scroll_event_delay = 300\n}\n");
+}
@@ -0,0 +1,59 @@
+//! Tests for parsing values from strings outside of a config.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Color, Config, ConfigValue, Vec2};
+
+#[test]
+fn test_color_from_str() {
+    let red = Color::from_rgba(255, 0, 0, 255);
+    assert_eq!("rgba(ff0000ff)".parse::<Color>().unwrap(), red);
+    assert_eq!("rgba(255, 0, 0, 1.0)".parse::<Color>().unwrap(), red);
+    assert_eq!("rgb(ff0000)".parse::<Color>().unwrap(), red);
+    assert_eq!("rgb(255, 0, 0)".parse::<Color>().unwrap(), red);
+    assert_eq!(
+        "0xffff0000".parse::<Color>().unwrap(),
+        Color::from_hex("ffff0000").unwrap()
+    );
+    assert_eq!(
+        "rgba(rgb(ff0000), 128)".parse::<Color>().unwrap(),
+        Color::from_rgba(255, 0, 0, 128)
+    );
+
+    assert!("red".parse::<Color>().is_err());
+    assert!("rgba(ff0000ff".parse::<Color>().is_err());
+    assert!("rgb(1, 2)".parse::<Color>().is_err());
+}
+
+#[test]
+fn test_vec2_from_str() {
+    assert_eq!("(1, 2)".parse::<Vec2>().unwrap(), Vec2::new(1.0, 2.0));
+    assert_eq!("1.5, -2".parse::<Vec2>().unwrap(), Vec2::new(1.5, -2.0));
+    assert_eq!("800 600".parse::<Vec2>().unwrap(), Vec2::new(800.0, 600.0));
+    assert!("1 2 3".parse::<Vec2>().is_err());
+}
+
+#[test]
+fn test_config_value_from_str_matches_config_parsing() {
+    let inputs = [
+        "5",
+        "0x10",
+        "1.5",
+        "true",
+        "off",
+        "rgba(33ccffee)",
+        "rgb(10, 20, 30)",
+        "10, 20",
+        "800 600",
+        "kitty --single-instance",
+    ];
+
+    for input in inputs {
+        let mut config = Config::new();
+        config.parse(&format!("value = {}", input)).unwrap();
+        let expected = config.get("value").unwrap();
+        let parsed = ConfigValue::from(input);
+        assert_eq!(parsed.to_string(), expected.to_string(), "input {}", input);
+        assert_eq!(parsed.kind(), expected.kind(), "input {}", input);
+    }
+}
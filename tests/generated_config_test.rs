@@ -0,0 +1,231 @@
+//! Property tests over randomly generated configs: nested categories, variables,
+//! expressions, escaped expressions and the value types.
+
+#![cfg(feature = "config")]
+
+mod common;
+
+use common::parsed;
+use hyprlang::{Color, Config, Vec2};
+use proptest::prelude::*;
+use std::collections::HashMap;
+
+/// A value as written, with the value it must parse to
+#[derive(Debug, Clone)]
+enum GenValue {
+    Int(i64),
+    Float(f64),
+    Word(String),
+    Color([u8; 4]),
+    Vec2(i32, i32),
+    Expression(i64, char, i64),
+    Escaped(i64, i64),
+    Variable(usize),
+}
+
+#[derive(Debug, Clone)]
+enum Item {
+    Value(String, GenValue),
+    Category(String, Vec<Item>),
+}
+
+/// A generated config: variables first, then values and categories
+#[derive(Debug, Clone)]
+struct GenConfig {
+    variables: Vec<String>,
+    items: Vec<Item>,
+}
+
+impl GenConfig {
+    fn render(&self) -> String {
+        let mut out = String::new();
+        for (index, value) in self.variables.iter().enumerate() {
+            out.push_str(&format!("$V{} = {}\n", index, value));
+        }
+        render_items(&self.items, 0, &mut out);
+        out
+    }
+
+    /// Full key -> the value it must read back as, last assignment winning
+    fn expected(&self) -> HashMap<String, GenValue> {
+        let mut expected = HashMap::new();
+        collect_expected(&self.items, "", &mut expected);
+        expected
+    }
+}
+
+fn render_items(items: &[Item], depth: usize, out: &mut String) {
+    let indent = "    ".repeat(depth);
+    for item in items {
+        match item {
+            Item::Value(key, value) => {
+                out.push_str(&format!("{}{} = {}\n", indent, key, render_value(value)));
+            }
+            Item::Category(name, children) => {
+                out.push_str(&format!("{}{} {{\n", indent, name));
+                render_items(children, depth + 1, out);
+                out.push_str(&format!("{}}}\n", indent));
+            }
+        }
+    }
+}
+
+fn render_value(value: &GenValue) -> String {
+    match value {
+        GenValue::Int(v) => v.to_string(),
+        GenValue::Float(v) => hyprlang::ConfigValue::format_float(*v),
+        GenValue::Word(word) => word.clone(),
+        GenValue::Color([r, g, b, a]) => format!("rgba({:02x}{:02x}{:02x}{:02x})", r, g, b, a),
+        GenValue::Vec2(x, y) => format!("{}, {}", x, y),
+        GenValue::Expression(a, op, b) => format!("{{{{{} {} {}}}}}", a, op, b),
+        GenValue::Escaped(a, b) => format!("\\{{{{{} + {}}}}}", a, b),
+        GenValue::Variable(index) => format!("$V{}", index),
+    }
+}
+
+fn collect_expected(items: &[Item], prefix: &str, expected: &mut HashMap<String, GenValue>) {
+    for item in items {
+        match item {
+            Item::Value(key, value) => {
+                expected.insert(format!("{}{}", prefix, key), value.clone());
+            }
+            Item::Category(name, children) => {
+                collect_expected(children, &format!("{}{}:", prefix, name), expected);
+            }
+        }
+    }
+}
+
+/// Check that a parsed config holds what the generated text says
+fn check_value(
+    config: &Config,
+    variables: &[String],
+    key: &str,
+    value: &GenValue,
+) -> Result<(), TestCaseError> {
+    match value {
+        GenValue::Int(v) => prop_assert_eq!(config.get_int(key).unwrap(), *v),
+        GenValue::Float(v) => prop_assert_eq!(config.get_float(key).unwrap(), *v),
+        GenValue::Word(word) => prop_assert_eq!(config.get_string(key).unwrap(), word),
+        GenValue::Color([r, g, b, a]) => prop_assert_eq!(
+            config.get_color(key).unwrap(),
+            Color::from_rgba(*r, *g, *b, *a)
+        ),
+        GenValue::Vec2(x, y) => prop_assert_eq!(
+            config.get_vec2(key).unwrap(),
+            Vec2::new(*x as f64, *y as f64)
+        ),
+        GenValue::Expression(a, op, b) => {
+            let result = match op {
+                '+' => a + b,
+                '-' => a - b,
+                _ => a * b,
+            };
+            prop_assert_eq!(config.get_int(key).unwrap(), result);
+        }
+        GenValue::Escaped(a, b) => prop_assert_eq!(
+            config.get_string(key).unwrap(),
+            format!("{{{{{} + {}}}}}", a, b)
+        ),
+        GenValue::Variable(index) => {
+            prop_assert_eq!(config.get_string(key).unwrap(), &variables[*index]);
+        }
+    }
+    Ok(())
+}
+
+fn word() -> impl Strategy<Value = String> {
+    "w[a-z]{0,7}"
+}
+
+fn value(variables: usize) -> impl Strategy<Value = GenValue> {
+    prop_oneof![
+        any::<i32>().prop_map(|v| GenValue::Int(v.into())),
+        (-1e6f64..1e6).prop_map(GenValue::Float),
+        word().prop_map(GenValue::Word),
+        any::<[u8; 4]>().prop_map(GenValue::Color),
+        (-5000i32..5000, -5000i32..5000).prop_map(|(x, y)| GenValue::Vec2(x, y)),
+        (
+            0i64..1000,
+            prop::sample::select(vec!['+', '-', '*']),
+            0i64..1000
+        )
+            .prop_map(|(a, op, b)| GenValue::Expression(a, op, b)),
+        (0i64..1000, 0i64..1000).prop_map(|(a, b)| GenValue::Escaped(a, b)),
+        (0..variables).prop_map(GenValue::Variable),
+    ]
+}
+
+fn items(variables: usize) -> impl Strategy<Value = Vec<Item>> {
+    let leaf = ("k[a-z]{1,5}", value(variables)).prop_map(|(key, value)| Item::Value(key, value));
+    let item = leaf.prop_recursive(3, 24, 4, |inner| {
+        ("c[a-z]{1,4}", prop::collection::vec(inner, 1..4))
+            .prop_map(|(name, children)| Item::Category(name, children))
+    });
+    prop::collection::vec(item, 1..8)
+}
+
+fn config() -> impl Strategy<Value = GenConfig> {
+    prop::collection::vec(word(), 1..4).prop_flat_map(|variables| {
+        let count = variables.len();
+        (Just(variables), items(count))
+            .prop_map(|(variables, items)| GenConfig { variables, items })
+    })
+}
+
+proptest! {
+    #[test]
+    fn prop_generated_values_parse(generated in config()) {
+        let config = parsed(&generated.render());
+
+        for (key, value) in generated.expected() {
+            check_value(&config, &generated.variables, &key, &value)?;
+        }
+    }
+}
+
+#[cfg(feature = "mutation")]
+proptest! {
+    #[test]
+    fn prop_serialize_round_trip(generated in config()) {
+        let config = parsed(&generated.render());
+        let serialized = config.serialize();
+
+        let reparsed = parsed(&serialized);
+        prop_assert!(config.semantically_equal(&reparsed));
+        prop_assert_eq!(reparsed.serialize(), serialized);
+
+        for (key, value) in generated.expected() {
+            check_value(&reparsed, &generated.variables, &key, &value)?;
+        }
+    }
+
+    #[test]
+    fn prop_mutations_survive_save_and_reload(
+        generated in config(),
+        edits in prop::collection::vec((any::<prop::sample::Index>(), any::<i32>()), 1..4),
+        new_value in word(),
+    ) {
+        let mut config = parsed(&generated.render());
+
+        let mut keys: Vec<String> = generated.expected().into_keys().collect();
+        keys.sort();
+        for (index, value) in edits {
+            config.set_int(index.get(&keys).as_str(), value.into()).unwrap();
+        }
+        config.set_string("cnew:knew", new_value).unwrap();
+
+        let dir = std::env::temp_dir().join(format!(
+            "hyprlang_generated_config_test_{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("saved.conf");
+        config.save_as(&path).unwrap();
+
+        let mut reloaded = Config::new();
+        reloaded.parse_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        prop_assert!(config.semantically_equal(&reloaded));
+    }
+}
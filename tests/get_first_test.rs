@@ -0,0 +1,68 @@
+//! Tests for reading the first matching key out of a fallback chain.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Color, Config, ConfigError, Unit};
+
+#[test]
+fn test_get_color_first_prefers_earlier_keys() {
+    let mut config = Config::new();
+    config
+        .parse(
+            "general {\n    col.border = rgba(ff0000ff)\n    col.active_border = rgba(00ff00ff)\n}",
+        )
+        .unwrap();
+
+    let color = config
+        .get_color_first(&["general:col.active_border", "general:col.border"])
+        .unwrap();
+    assert_eq!(color, Color::from_rgba(0, 255, 0, 255));
+}
+
+#[test]
+fn test_get_color_first_skips_unparsable_values() {
+    let mut config = Config::new();
+    config
+        .parse("general:col.active_border = gradient\ngeneral:col.border = 0x112233ff")
+        .unwrap();
+
+    let color = config
+        .get_color_first(&["general:col.active_border", "general:col.border"])
+        .unwrap();
+    assert_eq!(color, Color::from_rgba(0x11, 0x22, 0x33, 0xff));
+}
+
+#[test]
+fn test_get_first_errors() {
+    let mut config = Config::new();
+    config.parse("name = kitty").unwrap();
+
+    match config.get_first::<i64>(&["a", "b"]).unwrap_err() {
+        ConfigError::KeyNotFound { key } => assert_eq!(key, "a, b"),
+        other => panic!("expected KeyNotFound, got {other:?}"),
+    }
+    assert!(matches!(
+        config.get_first::<i64>(&["missing", "name"]).unwrap_err(),
+        ConfigError::TypeError { .. }
+    ));
+    assert!(config.get_first::<i64>(&[]).is_err());
+}
+
+#[test]
+fn test_get_first_other_types() {
+    let mut config = Config::new();
+    config.register_quantity_key("duration");
+    config
+        .parse("opacity = 0.9\nterminal = kitty\nduration = 250ms")
+        .unwrap();
+
+    assert_eq!(config.get_first::<f64>(&["alpha", "opacity"]).unwrap(), 0.9);
+    assert_eq!(
+        config.get_first::<String>(&["term", "terminal"]).unwrap(),
+        "kitty"
+    );
+    assert_eq!(
+        config.get_first::<(f64, Unit)>(&["duration"]).unwrap(),
+        (250.0, Unit::Milliseconds)
+    );
+}
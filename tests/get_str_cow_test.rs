@@ -0,0 +1,70 @@
+//! Tests for reading values as text without allocating.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, ConfigValue};
+use std::borrow::Cow;
+
+#[test]
+fn test_strings_are_borrowed() {
+    let mut config = Config::new();
+    config.parse("$LAYOUT = dwindle\nlayout = $LAYOUT").unwrap();
+
+    let text = config.get_str_cow("layout").unwrap();
+    assert!(matches!(text, Cow::Borrowed("dwindle")));
+    assert!(std::ptr::eq(
+        text.as_ptr(),
+        config.get_string("layout").unwrap().as_ptr()
+    ));
+}
+
+#[test]
+fn test_other_values_are_formatted_once() {
+    let mut config = Config::new();
+    config
+        .parse("gaps = 5\nopacity = 0.9\nborder = rgba(33ccffee)\noffset = 10, 20")
+        .unwrap();
+
+    assert_eq!(config.get_str_cow("gaps").unwrap(), "5");
+    assert_eq!(config.get_str_cow("opacity").unwrap(), "0.9");
+    assert_eq!(
+        config.get_str_cow("border").unwrap(),
+        config.get("border").unwrap().to_string()
+    );
+    assert_eq!(
+        config.get_str_cow("offset").unwrap(),
+        config.get("offset").unwrap().to_string()
+    );
+
+    // The second read borrows the text cached by the first
+    assert!(matches!(
+        config.get_str_cow("gaps").unwrap(),
+        Cow::Borrowed("5")
+    ));
+    let first = config.get_str_cow("gaps").unwrap().as_ptr();
+    let second = config.get_str_cow("gaps").unwrap().as_ptr();
+    assert!(std::ptr::eq(first, second));
+}
+
+#[test]
+fn test_missing_key() {
+    let config = Config::new();
+    assert!(config.get_str_cow("missing").is_err());
+}
+
+#[test]
+fn test_text_follows_new_values() {
+    let mut config = Config::new();
+    config.parse("gaps = 5").unwrap();
+    assert_eq!(config.get_str_cow("gaps").unwrap(), "5");
+
+    config.parse("gaps = 8").unwrap();
+    assert_eq!(config.get_str_cow("gaps").unwrap(), "8");
+
+    config.register_option("rounding", ConfigValue::Int(10));
+    assert_eq!(config.entry("rounding").unwrap().text(), "10");
+    assert!(matches!(
+        config.get_str_cow("rounding").unwrap(),
+        Cow::Borrowed("10")
+    ));
+}
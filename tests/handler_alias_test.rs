@@ -0,0 +1,132 @@
+//! Tests for handler keyword aliases and the deprecations they report.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, FunctionHandler};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn aliased_config() -> Config {
+    let mut config = Config::new();
+    config.register_handler_fn("windowrule", |_| Ok(()));
+    config.register_handler_alias("windowrulev2", "windowrule");
+    config
+}
+
+#[test]
+fn test_alias_calls_use_canonical_keyword() {
+    let mut config = aliased_config();
+    config
+        .parse("windowrule = float, class:kitty\nwindowrulev2 = pin, class:mpv\n")
+        .unwrap();
+
+    assert_eq!(
+        config.get_handler_calls("windowrule").unwrap(),
+        &vec![
+            "float, class:kitty".to_string(),
+            "pin, class:mpv".to_string()
+        ]
+    );
+    assert!(config.get_handler_calls("windowrulev2").is_none());
+
+    let records = config.get_handler_call_records("windowrule").unwrap();
+    assert_eq!(records[0].alias, None);
+    assert_eq!(records[1].alias.as_deref(), Some("windowrulev2"));
+}
+
+#[test]
+fn test_alias_executes_canonical_handler() {
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let captured = Rc::clone(&seen);
+
+    let mut config = Config::new();
+    config.register_handler_fn("windowrule", move |ctx| {
+        captured.borrow_mut().push(ctx.keyword.clone());
+        Ok(())
+    });
+    config.register_handler_alias("windowrulev2", "windowrule");
+    config.parse("windowrulev2 = float, class:kitty").unwrap();
+
+    assert_eq!(*seen.borrow(), vec!["windowrule"]);
+}
+
+#[test]
+fn test_deprecations_are_reported() {
+    let mut config = aliased_config();
+    config
+        .parse("windowrule = float, class:kitty\n\nwindowrulev2 = pin, class:mpv\n")
+        .unwrap();
+
+    let deprecations = config.deprecations();
+    assert_eq!(deprecations.len(), 1);
+    assert_eq!(deprecations[0].alias, "windowrulev2");
+    assert_eq!(deprecations[0].canonical, "windowrule");
+    assert_eq!(deprecations[0].line, 3);
+    assert_eq!(
+        deprecations[0].to_string(),
+        "3: 'windowrulev2' is deprecated, use 'windowrule' instead"
+    );
+
+    // Deprecations accumulate until cleared
+    config.parse("windowrulev2 = float, class:mpv").unwrap();
+    assert_eq!(config.deprecations().len(), 2);
+    config.clear_deprecations();
+    assert!(config.deprecations().is_empty());
+}
+
+#[test]
+fn test_alias_of_flagged_handler() {
+    let mut config = Config::new();
+    config.register_handler("bind", FunctionHandler::with_flags("bind", |_| Ok(())));
+    config.register_handler_alias("keybind", "bind");
+    config
+        .parse("keybind = SUPER, Q, exec, kitty\nbindl = , XF86AudioPlay, exec, playerctl play\n")
+        .unwrap();
+
+    let records = config.get_handler_call_records("bind").unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].alias.as_deref(), Some("keybind"));
+    assert_eq!(records[1].flags.as_deref(), Some("l"));
+}
+
+#[test]
+fn test_alias_table() {
+    let config = aliased_config();
+    assert_eq!(config.handler_aliases().len(), 1);
+    assert_eq!(
+        config
+            .handler_aliases()
+            .get("windowrulev2")
+            .map(String::as_str),
+        Some("windowrule")
+    );
+}
+
+#[test]
+fn test_alias_does_not_apply_to_nested_keys() {
+    let mut config = aliased_config();
+    config.parse("general:windowrulev2 = 5").unwrap();
+
+    assert_eq!(config.get_int("general:windowrulev2").unwrap(), 5);
+    assert!(config.deprecations().is_empty());
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_mutation_keeps_alias_spelling() {
+    let mut config = aliased_config();
+    config
+        .parse("windowrule = float, class:kitty\nwindowrulev2 = pin, class:mpv\n")
+        .unwrap();
+
+    config
+        .update_handler_call("windowrule", 1, "pin, class:vlc".to_string())
+        .unwrap();
+    assert_eq!(
+        config.serialize(),
+        "windowrule = float, class:kitty\nwindowrulev2 = pin, class:vlc\n"
+    );
+
+    config.remove_handler_call("windowrule", 1).unwrap();
+    assert_eq!(config.serialize(), "windowrule = float, class:kitty\n");
+}
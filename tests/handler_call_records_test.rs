@@ -0,0 +1,104 @@
+//! Tests for handler call records carrying flags and source locations.
+
+#![cfg(feature = "config")]
+
+mod common;
+
+use common::{cleanup_test_dir, create_test_dir};
+use hyprlang::Config;
+use std::fs;
+
+#[test]
+fn test_records_match_string_api() {
+    let mut config = Config::new();
+    config.register_handler_fn("bind", |_| Ok(()));
+    config
+        .parse("$MOD = SUPER\nbind = $MOD, Q, exec, kitty\n\nbind = $MOD, C, killactive\n")
+        .unwrap();
+
+    let values = config.get_handler_calls("bind").unwrap();
+    let records = config.get_handler_call_records("bind").unwrap();
+    assert_eq!(records.len(), values.len());
+
+    assert_eq!(records[0].value, "SUPER, Q, exec, kitty");
+    assert_eq!(records[0].line, Some(2));
+    assert_eq!(records[0].flags, None);
+    assert!(records[0].file.is_none());
+    assert_eq!(records[1].line, Some(4));
+
+    assert!(config.get_handler_call_records("missing").is_none());
+}
+
+#[test]
+fn test_records_in_category() {
+    let mut config = Config::new();
+    config.register_category_handler_fn("animations", "bezier", |_| Ok(()));
+    config
+        .parse("animations {\n    bezier = easeOut, 0.05, 0.9, 0.1, 1.05\n}\n")
+        .unwrap();
+
+    let records = config
+        .get_handler_call_records("animations:bezier")
+        .unwrap();
+    assert_eq!(records[0].line, Some(2));
+}
+
+#[test]
+fn test_records_track_source_file() {
+    let dir = create_test_dir("handler_records");
+    let main_path = dir.join("main.conf");
+    let binds_path = dir.join("binds.conf");
+
+    fs::write(&binds_path, "bind = SUPER, Q, exec, kitty\n").unwrap();
+    fs::write(
+        &main_path,
+        "source = ./binds.conf\nbind = SUPER, C, killactive\n",
+    )
+    .unwrap();
+
+    let mut config = Config::new();
+    config.register_handler_fn("bind", |_| Ok(()));
+    config.parse_file(&main_path).unwrap();
+
+    let records = config.get_handler_call_records("bind").unwrap();
+    assert_eq!(
+        records[0].file.as_ref(),
+        Some(&binds_path.canonicalize().unwrap())
+    );
+    assert_eq!(records[0].line, Some(1));
+    assert_eq!(
+        records[1].file.as_ref(),
+        Some(&main_path.canonicalize().unwrap())
+    );
+    assert_eq!(records[1].line, Some(2));
+
+    cleanup_test_dir(&dir);
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_records_follow_mutations() {
+    let mut config = Config::new();
+    config.register_handler_fn("bind", |_| Ok(()));
+    config
+        .parse("bind = SUPER, Q, exec, kitty\nbind = SUPER, C, killactive\n")
+        .unwrap();
+
+    config
+        .update_handler_call("bind", 1, "SUPER, W, killactive".to_string())
+        .unwrap();
+    config.remove_handler_call("bind", 0).unwrap();
+    config
+        .add_handler_call("bind", "SUPER, F, fullscreen".to_string())
+        .unwrap();
+
+    let records = config.get_handler_call_records("bind").unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].value, "SUPER, W, killactive");
+    assert_eq!(records[0].line, Some(2));
+    assert_eq!(records[1].value, "SUPER, F, fullscreen");
+    assert_eq!(records[1].line, None);
+
+    config.remove_handler_calls("bind");
+    assert!(config.get_handler_call_records("bind").is_none());
+}
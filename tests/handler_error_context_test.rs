@@ -0,0 +1,239 @@
+//! Tests for the call context attached to handler errors.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, ConfigError, ConfigOptions};
+use std::error::Error;
+use std::fs;
+
+fn failing_config() -> Config {
+    let mut config = Config::new();
+    config.register_handler_fn("bind", |ctx| {
+        if ctx.value.contains("bad") {
+            Err(ConfigError::handler("bind", "unknown key"))
+        } else {
+            Ok(())
+        }
+    });
+    config.register_category_handler_fn("animations", "bezier", |_| {
+        Err(ConfigError::custom("not a curve"))
+    });
+    config
+}
+
+#[test]
+fn test_handler_error_has_call_context() {
+    let mut config = failing_config();
+    let error = config
+        .parse("bind = SUPER, Q, exec, kitty\nbind = SUPER, bad, exec, kitty\n")
+        .unwrap_err();
+
+    let ConfigError::HandlerCallFailed {
+        keyword,
+        value,
+        category,
+        file,
+        line,
+        error: inner,
+    } = &error
+    else {
+        panic!("unexpected error: {:?}", error);
+    };
+    assert_eq!(keyword, "bind");
+    assert_eq!(value, "SUPER, bad, exec, kitty");
+    assert!(category.is_empty());
+    assert_eq!(*file, None);
+    assert_eq!(*line, Some(2));
+    assert!(matches!(**inner, ConfigError::HandlerError { .. }));
+
+    assert_eq!(
+        error.to_string(),
+        "Handler call 'bind = SUPER, bad, exec, kitty' at line 2 failed: \
+         Handler 'bind' error: unknown key"
+    );
+    assert_eq!(
+        error.source().unwrap().to_string(),
+        "Handler 'bind' error: unknown key"
+    );
+}
+
+#[test]
+fn test_category_handler_error() {
+    let mut config = failing_config();
+    let long_value = format!("ease, {}", "0.1, ".repeat(20));
+    let error = config
+        .parse(&format!("animations {{\n    bezier = {}\n}}", long_value))
+        .unwrap_err();
+
+    match &error {
+        ConfigError::HandlerCallFailed { category, line, .. } => {
+            assert_eq!(category, &vec!["animations".to_string()]);
+            assert_eq!(*line, Some(2));
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+    // Long values are cut short in the message
+    assert_eq!(
+        error.to_string(),
+        "Handler call 'animations:bezier = ease, 0.1, 0.1, 0.1, 0.1, 0.1, 0.1, 0.1,...' \
+         at line 2 failed: not a curve"
+    );
+}
+
+#[test]
+fn test_handler_error_in_sourced_file() {
+    let dir = std::env::temp_dir().join(format!(
+        "hyprlang_handler_error_test_{}",
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("main.conf"), "source = ./binds.conf\n").unwrap();
+    fs::write(dir.join("binds.conf"), "\nbind = SUPER, bad, exec, kitty\n").unwrap();
+
+    let mut config = failing_config();
+    let error = config.parse_file(dir.join("main.conf")).unwrap_err();
+    let message = error.to_string();
+    assert!(message.contains("binds.conf:2 failed"), "{}", message);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+fn strict_config(throw_all_errors: bool) -> Config {
+    let mut config = Config::with_options(ConfigOptions {
+        strict_handler_variables: true,
+        throw_all_errors,
+        ..Default::default()
+    });
+    config.register_handler_fn("bind", |_| Ok(()));
+    config
+}
+
+#[test]
+fn test_undefined_variable_in_handler_value() {
+    let mut config = strict_config(false);
+    let error = config
+        .parse("$mod = SUPER\nbind = $mod, Q, exec, $terminal\n")
+        .unwrap_err();
+
+    let ConfigError::HandlerExpansionFailed {
+        keyword,
+        value,
+        variable,
+        file,
+        line,
+        error: inner,
+    } = &error
+    else {
+        panic!("unexpected error: {:?}", error);
+    };
+    assert_eq!(keyword, "bind");
+    assert_eq!(value, "$mod, Q, exec, $terminal");
+    assert_eq!(variable.as_deref(), Some("terminal"));
+    assert_eq!(*file, None);
+    assert_eq!(*line, 2);
+    assert!(matches!(**inner, ConfigError::VariableNotFound { .. }));
+
+    assert_eq!(
+        error.to_string(),
+        "Handler call 'bind = $mod, Q, exec, $terminal' at line 2 failed: \
+         Variable 'terminal' not found"
+    );
+    assert_eq!(
+        error.source().unwrap().to_string(),
+        "Variable 'terminal' not found"
+    );
+}
+
+#[test]
+fn test_undefined_variable_kept_without_strict_option() {
+    let mut config = Config::new();
+    config.register_handler_fn("bind", |_| Ok(()));
+    config.parse("bind = SUPER, Q, exec, $terminal\n").unwrap();
+
+    assert_eq!(
+        config.get_handler_calls("bind").unwrap(),
+        &vec!["SUPER, Q, exec, $terminal".to_string()]
+    );
+}
+
+#[test]
+fn test_expansion_error_keeps_raw_value_under_throw_all_errors() {
+    let mut config = strict_config(true);
+    let error = config
+        .parse("bind = SUPER, Q, exec, $terminal\nbind = SUPER, W, exec, $browser\n")
+        .unwrap_err();
+
+    let ConfigError::Multiple { errors } = &error else {
+        panic!("unexpected error: {:?}", error);
+    };
+    let missing: Vec<_> = errors
+        .iter()
+        .map(|error| match error {
+            ConfigError::HandlerExpansionFailed { variable, line, .. } => {
+                (variable.clone().unwrap(), *line)
+            }
+            other => panic!("unexpected error: {:?}", other),
+        })
+        .collect();
+    assert_eq!(
+        missing,
+        vec![("terminal".to_string(), 1), ("browser".to_string(), 2)]
+    );
+
+    // Both calls were still made, with the value as written
+    assert_eq!(
+        config.get_handler_calls("bind").unwrap(),
+        &vec![
+            "SUPER, Q, exec, $terminal".to_string(),
+            "SUPER, W, exec, $browser".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_circular_variable_in_handler_value() {
+    let mut config = Config::new();
+    config.register_handler_fn("bind", |_| Ok(()));
+    let error = config
+        .parse("$a = $b\n$b = $a\nbind = SUPER, Q, exec, $a\n")
+        .unwrap_err();
+
+    match &error {
+        ConfigError::HandlerExpansionFailed { variable, line, .. } => {
+            assert_eq!(variable.as_deref(), Some("a"));
+            assert_eq!(*line, 3);
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_handlers_see_where_the_call_is() {
+    use std::cell::RefCell;
+    use std::path::PathBuf;
+    use std::rc::Rc;
+
+    type Location = (Option<PathBuf>, Option<usize>);
+    let seen: Rc<RefCell<Vec<Location>>> = Rc::default();
+    let mut config = Config::new();
+    let calls = seen.clone();
+    config.register_handler_fn("exec", move |ctx| {
+        calls.borrow_mut().push((ctx.file.clone(), ctx.line));
+        Ok(())
+    });
+
+    config.parse("gaps = 5\nexec = kitty\n").unwrap();
+
+    let dir =
+        std::env::temp_dir().join(format!("hyprlang_handler_location_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("hyprland.conf");
+    fs::write(&path, "\n\nexec = waybar\n").unwrap();
+    config.parse_file(&path).unwrap();
+    fs::remove_dir_all(&dir).ok();
+
+    let seen = seen.borrow();
+    assert_eq!(seen[0], (None, Some(2)));
+    assert!(seen[1].0.as_ref().unwrap().ends_with("hyprland.conf"));
+    assert_eq!(seen[1].1, Some(3));
+}
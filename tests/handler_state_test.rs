@@ -0,0 +1,134 @@
+//! Tests for handlers that write into application state set with `Config::set_handler_state`.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, ConfigError, FunctionHandler, HandlerContext};
+use std::any::Any;
+
+#[derive(Debug, Default)]
+struct Collected {
+    binds: Vec<(Option<String>, String)>,
+    env: Vec<(String, String)>,
+    beziers: Vec<String>,
+}
+
+fn collecting_config() -> Config {
+    let mut config = Config::new();
+    config.register_handler(
+        "bind",
+        FunctionHandler::stateful_with_flags(
+            "bind",
+            |ctx: &HandlerContext, state: &mut dyn Any| {
+                let collected = state.downcast_mut::<Collected>().unwrap();
+                let flags = ctx.flags.clone().filter(|flags| !flags.is_empty());
+                collected.binds.push((flags, ctx.value.clone()));
+                Ok(())
+            },
+        ),
+    );
+    config.register_handler_with_state("env", |ctx, collected: &mut Collected| {
+        let (name, value) = ctx.value.split_once(',').unwrap_or((&ctx.value, ""));
+        collected
+            .env
+            .push((name.trim().to_string(), value.trim().to_string()));
+        Ok(())
+    });
+    config.register_category_handler_with_state(
+        "animations",
+        "bezier",
+        |ctx, collected: &mut Collected| {
+            collected.beziers.push(ctx.value.clone());
+            Ok(())
+        },
+    );
+    config
+}
+
+#[test]
+fn test_collect_binds_into_vec() {
+    let mut config = Config::new();
+    config.register_handler_with_state("bind", |ctx, binds: &mut Vec<String>| {
+        binds.push(ctx.value.clone());
+        Ok(())
+    });
+
+    config.set_handler_state(Vec::<String>::new());
+    config
+        .parse("$mod = SUPER\nbind = $mod, Q, exec, kitty\nbind = $mod, C, killactive\n")
+        .unwrap();
+
+    assert_eq!(config.handler_state::<Vec<String>>().unwrap().len(), 2);
+    let binds: Vec<String> = config.take_handler_state().unwrap();
+    assert_eq!(binds, vec!["SUPER, Q, exec, kitty", "SUPER, C, killactive"]);
+    assert!(config.handler_state::<Vec<String>>().is_none());
+
+    // Calls are still recorded as usual
+    assert_eq!(config.get_handler_calls("bind").unwrap().len(), 2);
+}
+
+#[test]
+fn test_collect_into_application_struct() {
+    let mut config = collecting_config();
+    config.set_handler_state(Collected::default());
+    config
+        .parse(
+            "bind = SUPER, Q, exec, kitty\nbindl = , XF86AudioMute, exec, mute\n\
+             env = XCURSOR_SIZE, 24\nanimations {\n    bezier = ease, 0.25, 0.1, 0.25, 1\n}\n",
+        )
+        .unwrap();
+
+    let collected = config.handler_state::<Collected>().unwrap();
+    assert_eq!(
+        collected.binds,
+        vec![
+            (None, "SUPER, Q, exec, kitty".to_string()),
+            (
+                Some("l".to_string()),
+                ", XF86AudioMute, exec, mute".to_string()
+            ),
+        ]
+    );
+    assert_eq!(
+        collected.env,
+        vec![("XCURSOR_SIZE".to_string(), "24".to_string())]
+    );
+    assert_eq!(collected.beziers, vec!["ease, 0.25, 0.1, 0.25, 1"]);
+
+    // State can be updated between parses
+    config
+        .handler_state_mut::<Collected>()
+        .unwrap()
+        .binds
+        .clear();
+    config.parse("bind = SUPER, E, exec, files").unwrap();
+    assert_eq!(config.handler_state::<Collected>().unwrap().binds.len(), 1);
+}
+
+#[test]
+fn test_missing_or_mismatched_state_is_an_error() {
+    let mut config = collecting_config();
+    match config.parse("env = A, 1") {
+        Err(ConfigError::HandlerCallFailed { keyword, error, .. }) => {
+            assert_eq!(keyword, "env");
+            assert!(matches!(*error, ConfigError::HandlerError { .. }));
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    config.set_handler_state(42u32);
+    assert!(config.parse("env = A, 1").is_err());
+
+    // Taking state of the wrong type leaves it in place
+    assert!(config.take_handler_state::<Collected>().is_none());
+    assert_eq!(config.take_handler_state::<u32>(), Some(42));
+}
+
+#[test]
+fn test_clones_start_without_state() {
+    let mut config = collecting_config();
+    config.set_handler_state(Collected::default());
+
+    let clone = config.clone();
+    assert!(clone.handler_state::<Collected>().is_none());
+    assert!(config.handler_state::<Collected>().is_some());
+}
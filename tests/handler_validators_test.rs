@@ -0,0 +1,105 @@
+//! Tests for validators attached to handler keywords.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, ConfigError, ConfigOptions, Severity};
+use std::cell::Cell;
+use std::rc::Rc;
+
+fn validated(executed: Rc<Cell<usize>>) -> Config {
+    let mut config = Config::new();
+    config.register_handler_fn("exec", move |_| {
+        executed.set(executed.get() + 1);
+        Ok(())
+    });
+    config.register_category_handler_fn("animations", "bezier", |_| Ok(()));
+    config.add_handler_validator("exec", |ctx| {
+        if ctx.value.is_empty() {
+            Err("needs a command".to_string())
+        } else {
+            Ok(())
+        }
+    });
+    config.add_handler_validator("animations:bezier", |ctx| match ctx.args().len() {
+        5 => Ok(()),
+        n => Err(format!("expected 5 arguments, got {}", n)),
+    });
+    config
+}
+
+#[test]
+fn test_rejected_call_is_not_executed() {
+    let executed = Rc::new(Cell::new(0));
+    let mut config = validated(executed.clone());
+    config.parse("exec = kitty").unwrap();
+
+    let error = config.parse("\n\nexec = ").unwrap_err();
+    match &error {
+        ConfigError::HandlerCallFailed {
+            keyword,
+            line,
+            error,
+            ..
+        } => {
+            assert_eq!(keyword, "exec");
+            assert_eq!(*line, Some(3));
+            assert_eq!(
+                error.to_string(),
+                "Invalid value '' for 'exec': needs a command"
+            );
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+    assert_eq!(executed.get(), 1);
+}
+
+#[test]
+fn test_category_qualified_validator() {
+    let mut config = validated(Rc::default());
+    config
+        .parse("animations {\n    bezier = ease, 0.25, 0.1, 0.25, 1\n}\n")
+        .unwrap();
+
+    let error = config
+        .parse("animations {\n    bezier = ease, 0.25\n}\n")
+        .unwrap_err();
+    assert!(error.to_string().contains("expected 5 arguments, got 2"));
+}
+
+#[test]
+fn test_check_reports_validation_diagnostics() {
+    let executed = Rc::new(Cell::new(0));
+    let config = validated(executed.clone());
+    let diagnostics = config.check("exec = kitty\nexec = \nanimations {\n    bezier = a, 1\n}\n");
+
+    let lines: Vec<_> = diagnostics
+        .iter()
+        .map(|diagnostic| (diagnostic.severity, diagnostic.line))
+        .collect();
+    assert_eq!(
+        lines,
+        [(Severity::Error, Some(2)), (Severity::Error, Some(4))]
+    );
+    assert_eq!(executed.get(), 0);
+}
+
+#[test]
+fn test_throw_all_errors_collects_rejected_calls() {
+    let mut config = Config::with_options(ConfigOptions {
+        throw_all_errors: true,
+        ..Default::default()
+    });
+    config.register_handler_fn("exec", |_| Ok(()));
+    config.add_handler_validator("exec", |ctx| match ctx.value.as_str() {
+        "bad" => Err("rejected".to_string()),
+        _ => Ok(()),
+    });
+
+    let error = config
+        .parse("exec = bad\nexec = good\nexec = bad\n")
+        .unwrap_err();
+    match error {
+        ConfigError::Multiple { errors } => assert_eq!(errors.len(), 2),
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
@@ -0,0 +1,125 @@
+//! Tests for variables in special category keys and source paths.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, SpecialCategoryDescriptor};
+use std::fs;
+
+fn device_config() -> Config {
+    let mut config = Config::new();
+    config.register_special_category(SpecialCategoryDescriptor::keyed("device", "name"));
+    config
+}
+
+#[test]
+fn test_variables_in_special_category_keys() {
+    let mut config = device_config();
+    config
+        .parse(
+            "$MOUSE = logitech-g502\n\
+             $PREFIX = usb\n\
+             device[$MOUSE] {\n\
+                 sensitivity = 0.5\n\
+             }\n\
+             device[$PREFIX-keyboard] {\n\
+                 repeat_rate = 50\n\
+             }\n",
+        )
+        .unwrap();
+
+    let mut keys = config.list_special_category_keys("device");
+    keys.sort();
+    assert_eq!(keys, vec!["logitech-g502", "usb-keyboard"]);
+    let mouse = config
+        .get_special_category("device", "logitech-g502")
+        .unwrap();
+    assert_eq!(mouse["sensitivity"].as_float().unwrap(), 0.5);
+}
+
+#[test]
+fn test_undefined_variable_in_key() {
+    let mut config = device_config();
+    let error = config
+        .parse("gaps = 5\ndevice[$MOUSE] {\n    sensitivity = 0.5\n}\n")
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Parse error at line 2, column 1: variable '$MOUSE' in the key of 'device[$MOUSE]' is not defined"
+    );
+}
+
+#[test]
+fn test_variables_in_source_paths() {
+    let dir =
+        std::env::temp_dir().join(format!("hyprlang_header_variables_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("extra.conf"), "gaps = 7\n").unwrap();
+    let main = dir.join("main.conf");
+    fs::write(
+        &main,
+        format!(
+            "$confdir = {}\nsource = $confdir/extra.conf\n",
+            dir.display()
+        ),
+    )
+    .unwrap();
+    let broken = dir.join("broken.conf");
+    fs::write(&broken, "source = $nowhere/extra.conf\n").unwrap();
+
+    let mut config = Config::new();
+    config.parse_file(&main).unwrap();
+    assert_eq!(config.get_int("gaps").unwrap(), 7);
+
+    let error = Config::new().parse_file(&broken).unwrap_err();
+    assert_eq!(error.to_string(), "Variable 'nowhere' not found");
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[cfg(feature = "mutation")]
+mod mutation {
+    use super::device_config;
+
+    const INPUT: &str = "$MOUSE = logitech-g502\ndevice[$MOUSE] {\n    sensitivity = 0.5\n}\n";
+
+    #[test]
+    fn test_key_written_with_variable_round_trips() {
+        let mut config = device_config();
+        config.parse(INPUT).unwrap();
+
+        assert_eq!(config.serialize(), INPUT);
+    }
+
+    #[test]
+    fn test_set_writes_to_block_written_with_variable() {
+        let mut config = device_config();
+        config.parse(INPUT).unwrap();
+        config
+            .set_float("device[logitech-g502]:sensitivity", 0.8)
+            .unwrap();
+        config
+            .set_int("device[logitech-g502]:natural_scroll", 1)
+            .unwrap();
+
+        assert_eq!(
+            config.serialize(),
+            "$MOUSE = logitech-g502\n\
+             device[$MOUSE] {\n\
+             \x20   sensitivity = 0.8\n\
+             \x20   natural_scroll = 1\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn test_remove_instance_written_with_variable() {
+        let mut config = device_config();
+        config.parse(INPUT).unwrap();
+        config
+            .remove_special_category_instance("device", "logitech-g502")
+            .unwrap();
+
+        assert_eq!(config.serialize(), "$MOUSE = logitech-g502\n");
+    }
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "config")]
+
 use hyprlang::{Config, ConfigOptions};
 use std::path::PathBuf;
 
@@ -7,9 +9,11 @@ fn test_hyprland_config_comprehensive() {
     config_path.push("tests/config/hyprland.conf");
 
     // Create config with options for this test
-    let mut options = ConfigOptions::default();
-    options.base_dir = Some(config_path.parent().unwrap().to_path_buf());
-    options.throw_all_errors = false;
+    let options = ConfigOptions {
+        base_dir: Some(config_path.parent().unwrap().to_path_buf()),
+        throw_all_errors: false,
+        ..Default::default()
+    };
 
     let mut config = Config::with_options(options);
 
@@ -202,7 +206,7 @@ fn test_hyprland_config_with_handlers() {
 
     // Register handlers that Hyprland uses
     let mut monitor_set = false;
-    let monitor_set_ref = &mut monitor_set;
+    let _monitor_set_ref = &mut monitor_set;
 
     config.register_handler_fn("monitor", move |ctx| {
         // In real Hyprland, this would configure monitors
@@ -216,7 +220,7 @@ fn test_hyprland_config_with_handlers() {
         Ok(())
     });
 
-    config.register_handler_fn("bind", |ctx| {
+    config.register_handler_fn("bind", |_ctx| {
         // In real Hyprland, this would register keybindings
         Ok(())
     });
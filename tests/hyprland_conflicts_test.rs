@@ -0,0 +1,216 @@
+#![cfg(feature = "hyprland")]
+//! Tests for finding conflicting binds, monitors and window rules.
+
+mod common;
+
+use common::{cleanup_test_dir, create_test_dir, parsed_hyprland};
+use hyprlang::{ConflictKind, Hyprland};
+use std::fs;
+
+#[test]
+fn test_no_conflicts() {
+    let hypr = parsed_hyprland(
+        "bind = SUPER, Q, exec, kitty\nbind = SUPER SHIFT, Q, killactive\n\
+         monitor = DP-1, 1920x1080, 0x0, 1\nmonitor = HDMI-A-1, preferred, auto, 1\n",
+    );
+    assert!(hypr.conflicts().is_empty());
+}
+
+#[test]
+fn test_binds_across_variants() {
+    let hypr = parsed_hyprland(
+        "$mod = SUPER\nbind = $mod SHIFT, Q, exec, kitty\nbindl = SHIFT_SUPER, q, killactive\n\
+         binde = SUPERSHIFT, Q, resizeactive, 10 0\n",
+    );
+
+    let conflicts = hypr.conflicts();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].kind, ConflictKind::Keybind { submap: None });
+    assert_eq!(conflicts[0].subject, "SHIFT SUPER, Q");
+    let sites: Vec<_> = conflicts[0]
+        .sites
+        .iter()
+        .map(|site| (site.text.as_str(), site.line))
+        .collect();
+    assert_eq!(
+        sites,
+        [
+            ("bind = SUPER SHIFT, Q, exec, kitty", Some(2)),
+            ("bindl = SHIFT_SUPER, q, killactive", Some(3)),
+            ("binde = SUPERSHIFT, Q, resizeactive, 10 0", Some(4)),
+        ]
+    );
+}
+
+#[test]
+fn test_release_and_mouse_binds_are_other_triggers() {
+    let hypr = parsed_hyprland(
+        "bind = SUPER, SUPER_L, exec, true\nbindr = SUPER, SUPER_L, exec, rofi\n\
+         bindm = SUPER, mouse:272, movewindow\nbind = SUPER, mouse:272, exec, true\n",
+    );
+    assert!(hypr.conflicts().is_empty());
+
+    let hypr = parsed_hyprland(
+        "bindr = SUPER, SUPER_L, exec, rofi\nbindrl = SUPER, SUPER_L, exec, wofi\n",
+    );
+    assert_eq!(hypr.conflicts().len(), 1);
+}
+
+#[test]
+fn test_binds_in_submaps() {
+    let hypr = parsed_hyprland(
+        "bind = ALT, R, submap, resize\n\
+         submap = resize\n\
+         bind = , right, resizeactive, 10 0\n\
+         bind = , RIGHT, resizeactive, 20 0\n\
+         bind = ALT, R, exec, kitty\n\
+         submap = reset\n\
+         bind = , right, movefocus, r\n",
+    );
+
+    let conflicts = hypr.conflicts();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(
+        conflicts[0].kind,
+        ConflictKind::Keybind {
+            submap: Some("resize".to_string())
+        }
+    );
+    assert_eq!(conflicts[0].subject, ", right");
+    assert_eq!(conflicts[0].sites.len(), 2);
+}
+
+#[test]
+fn test_universal_binds_conflict_in_every_submap() {
+    let hypr = parsed_hyprland(
+        "bindu = SUPER, escape, submap, reset\n\
+         submap = resize\nbind = SUPER, escape, exec, true\nsubmap = reset\n\
+         submap = move\nbind = SUPER, escape, exec, false\nsubmap = reset\n",
+    );
+
+    let conflicts = hypr.conflicts();
+    let submaps: Vec<_> = conflicts.iter().map(|conflict| &conflict.kind).collect();
+    assert_eq!(
+        submaps,
+        [
+            &ConflictKind::Keybind {
+                submap: Some("resize".to_string())
+            },
+            &ConflictKind::Keybind {
+                submap: Some("move".to_string())
+            },
+        ]
+    );
+    assert!(
+        conflicts
+            .iter()
+            .all(|conflict| conflict.sites[0].text.starts_with("bindu"))
+    );
+}
+
+#[test]
+fn test_duplicate_monitors() {
+    let hypr = parsed_hyprland(
+        "monitor = DP-1, 1920x1080, 0x0, 1\nmonitor = HDMI-A-1, preferred, auto, 1\n\
+         monitor = DP-1, 2560x1440, 0x0, 1\nmonitor = , preferred, auto, 1\n",
+    );
+
+    let conflicts = hypr.conflicts();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].kind, ConflictKind::Monitor);
+    assert_eq!(conflicts[0].subject, "DP-1");
+    assert_eq!(
+        conflicts[0].sites[1].text,
+        "monitor = DP-1, 2560x1440, 0x0, 1"
+    );
+    assert_eq!(conflicts[0].sites[1].line, Some(3));
+}
+
+#[test]
+fn test_identical_windowrule_blocks() {
+    let hypr = parsed_hyprland(
+        r#"
+windowrule[float-kitty] {
+    match:class = ^(kitty)$
+    float = true
+}
+windowrule[kitty-floats] {
+    float = true
+    match:class = ^(kitty)$
+}
+windowrule[float-foot] {
+    match:class = ^(foot)$
+    float = true
+}
+"#,
+    );
+
+    let conflicts = hypr.conflicts();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].kind, ConflictKind::WindowRule);
+    assert_eq!(
+        conflicts[0].subject,
+        "float = true, match:class = ^(kitty)$"
+    );
+    let names: Vec<_> = conflicts[0]
+        .sites
+        .iter()
+        .map(|site| (site.text.as_str(), site.line))
+        .collect();
+    assert_eq!(names, [("float-kitty", Some(3)), ("kitty-floats", Some(7))]);
+}
+
+#[test]
+fn test_windowrule_blocks_compare_as_written() {
+    let hypr = parsed_hyprland(
+        r#"
+$on = true
+windowrule[a] {
+    match:class = ^(kitty)$
+    float = true
+}
+windowrule[b] {
+    match:class = ^(kitty)$
+    float = $on
+}
+windowrule[c] {
+    match:class = ^(kitty)$
+    float = 1
+}
+"#,
+    );
+
+    // Variables are expanded, but `1` is not the same text as `true`
+    let conflicts = hypr.conflicts();
+    assert_eq!(conflicts.len(), 1);
+    let names: Vec<_> = conflicts[0]
+        .sites
+        .iter()
+        .map(|site| site.text.as_str())
+        .collect();
+    assert_eq!(names, ["a", "b"]);
+}
+
+#[test]
+fn test_conflicts_name_their_files() {
+    let dir = create_test_dir("conflicts");
+    fs::write(
+        dir.join("hyprland.conf"),
+        "bind = SUPER, Q, exec, kitty\nsource = keys.conf\n",
+    )
+    .unwrap();
+    fs::write(dir.join("keys.conf"), "\nbind = SUPER, Q, killactive\n").unwrap();
+
+    let mut hypr = Hyprland::new();
+    hypr.parse_file(&dir.join("hyprland.conf")).unwrap();
+
+    let conflicts = hypr.conflicts();
+    assert_eq!(conflicts.len(), 1);
+    let sites = &conflicts[0].sites;
+    assert!(sites[0].file.as_ref().unwrap().ends_with("hyprland.conf"));
+    assert_eq!(sites[0].line, Some(1));
+    assert!(sites[1].file.as_ref().unwrap().ends_with("keys.conf"));
+    assert_eq!(sites[1].line, Some(2));
+
+    cleanup_test_dir(&dir);
+}
@@ -0,0 +1,151 @@
+//! Tests for the unified `Monitors` view over monitor lines and monitor blocks.
+
+#![cfg(feature = "hyprland")]
+
+use hyprlang::{Hyprland, Monitor};
+
+#[test]
+fn test_monitor_lines() {
+    let mut hypr = Hyprland::new();
+    hypr.parse(
+        r#"
+monitor = ,preferred,auto,1
+monitor = DP-1, 1920x1080@144, 0x0, 1, transform, 1, vrr, 2
+monitor = HDMI-A-1, disable
+monitor = DP-1, 2560x1440@165, 0x0, 1.25
+"#,
+    )
+    .unwrap();
+
+    let monitors = hypr.monitors();
+    assert_eq!(monitors.names(), vec!["", "DP-1", "HDMI-A-1"]);
+    assert!(monitors.conflicts().is_empty());
+
+    let fallback = monitors.get("").unwrap();
+    assert_eq!(fallback.mode.as_deref(), Some("preferred"));
+    assert_eq!(fallback.position.as_deref(), Some("auto"));
+
+    // The last line for a monitor replaces earlier ones
+    let dp1 = monitors.get("DP-1").unwrap();
+    assert_eq!(dp1.mode.as_deref(), Some("2560x1440@165"));
+    assert_eq!(dp1.scale.as_deref(), Some("1.25"));
+    assert!(dp1.options.is_empty());
+    assert!(dp1.from_handler && !dp1.from_block);
+
+    assert!(monitors.get("HDMI-A-1").unwrap().disabled);
+}
+
+#[test]
+fn test_monitor_blocks_merge_with_lines() {
+    let mut hypr = Hyprland::new();
+    hypr.parse(
+        r#"
+$POS = 1920x0
+monitor = DP-1, 1920x1080@144, 0x0, 1, transform, 1
+monitor[DP-1] {
+    position = 0x0
+    scale = 2
+    bitdepth = 10
+}
+monitor[DP-2] {
+    mode = 1920x1080@60
+    position = $POS
+    disabled = false
+}
+"#,
+    )
+    .unwrap();
+
+    let monitors = hypr.monitors();
+    assert_eq!(monitors.len(), 2);
+
+    let dp1 = monitors.get("DP-1").unwrap();
+    assert!(dp1.from_handler && dp1.from_block);
+    assert_eq!(dp1.position.as_deref(), Some("0x0"));
+    assert_eq!(dp1.scale.as_deref(), Some("2"));
+    assert_eq!(dp1.options["transform"], "1");
+    assert_eq!(dp1.options["bitdepth"], "10");
+
+    let dp2 = monitors.get("DP-2").unwrap();
+    assert!(!dp2.from_handler && dp2.from_block);
+    assert_eq!(dp2.mode.as_deref(), Some("1920x1080@60"));
+    assert_eq!(dp2.position.as_deref(), Some("1920x0"));
+    assert!(!dp2.disabled);
+
+    // Only the scale actually disagrees
+    let conflicts = monitors.conflicts();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].monitor, "DP-1");
+    assert_eq!(conflicts[0].property, "scale");
+    assert_eq!(conflicts[0].handler_value, "1");
+    assert_eq!(conflicts[0].block_value, "2");
+}
+
+#[test]
+fn test_monitor_parse_addreserved() {
+    let monitor = Monitor::parse("DP-1, addreserved, 10, 0, 0, 0");
+    assert_eq!(monitor.mode, None);
+    assert_eq!(monitor.options["addreserved"], "10, 0, 0, 0");
+}
+
+#[test]
+fn test_monitor_validation() {
+    for valid in [
+        "DP-1, 1920x1080@144, 0x0, 1",
+        "DP-1, 2560x1440@59.95, -2560x0, 1.25, transform, 1",
+        ", preferred, auto, auto",
+        "eDP-1, highres, auto-left, 2",
+        "HDMI-A-1, disable",
+        "DP-2, addreserved, 10, 0, 0, 0",
+    ] {
+        assert_eq!(Monitor::validate(valid), Ok(()), "{}", valid);
+    }
+
+    for (invalid, reason) in [
+        ("DP-1", "missing mode"),
+        ("DP-1, 1920x1080", "missing position"),
+        ("DP-1, 1920x1080, 0x0", "missing scale"),
+        ("DP-1, 1920by1080, 0x0, 1", "invalid mode '1920by1080'"),
+        (
+            "DP-1, 1920x1080@fast, 0x0, 1",
+            "invalid mode '1920x1080@fast'",
+        ),
+        ("DP-1, preferred, left, 1", "invalid position 'left'"),
+        ("DP-1, preferred, 0x0, 0", "invalid scale '0'"),
+    ] {
+        assert_eq!(
+            Monitor::validate(invalid).unwrap_err(),
+            reason,
+            "{}",
+            invalid
+        );
+    }
+}
+
+#[test]
+fn test_hyprland_validators_report_bad_lines() {
+    let mut hypr = Hyprland::new();
+    hypr.add_validators();
+
+    let diagnostics = hypr.config().check(
+        "monitor = DP-1, 1920x1080@144, 0x0, 1\n\
+         monitor = DP-2, 1920x1080, nowhere, 1\n\
+         bind = SUPER, Q, exec, kitty\n\
+         bindm = SUPER, mouse:272, movewindow\n\
+         bind = SUPER, T, launch, kitty\n\
+         bindd = SUPER, C, Close window, killactive\n",
+    );
+    let found: Vec<_> = diagnostics
+        .iter()
+        .map(|diagnostic| (diagnostic.line, diagnostic.message.as_str()))
+        .collect();
+    assert_eq!(found.len(), 2);
+    assert_eq!(found[0].0, Some(2));
+    assert!(found[0].1.contains("invalid position 'nowhere'"));
+    assert_eq!(found[1].0, Some(5));
+    assert!(found[1].1.contains("unknown dispatcher 'launch'"));
+
+    // Without validators the same lines parse
+    let mut lenient = Hyprland::new();
+    lenient.parse("bind = SUPER, T, launch, kitty\n").unwrap();
+}
@@ -0,0 +1,187 @@
+//! Tests for the Hyprland wrapper's document-aware setters.
+
+#![cfg(all(feature = "hyprland", feature = "mutation"))]
+
+use hyprlang::Hyprland;
+
+#[test]
+fn test_set_env_updates_existing_line() {
+    let mut hypr = Hyprland::new();
+    hypr.parse(
+        r#"env = XCURSOR_SIZE,24
+env = GTK_THEME,Breeze
+"#,
+    )
+    .unwrap();
+
+    hypr.set_env("GTK_THEME", "Adwaita").unwrap();
+
+    let envs = hypr.parsed_env();
+    assert_eq!(envs.len(), 2);
+    assert_eq!(envs[1].value, "Adwaita");
+
+    let output = hypr.config().serialize();
+    assert_eq!(output, "env = XCURSOR_SIZE,24\nenv = GTK_THEME,Adwaita\n");
+}
+
+#[test]
+fn test_set_env_appends_new_line() {
+    let mut hypr = Hyprland::new();
+    hypr.parse("env = XCURSOR_SIZE,24\n").unwrap();
+
+    hypr.set_env("QT_QPA_PLATFORM", "wayland;xcb").unwrap();
+
+    let envs = hypr.parsed_env();
+    assert_eq!(envs.len(), 2);
+    assert_eq!(envs[1].name, "QT_QPA_PLATFORM");
+
+    let output = hypr.config().serialize();
+    assert!(output.contains("env = XCURSOR_SIZE,24"));
+    assert!(output.contains("env = QT_QPA_PLATFORM,wayland;xcb"));
+}
+
+#[test]
+fn test_set_env_updates_last_duplicate() {
+    let mut hypr = Hyprland::new();
+    hypr.parse("env = EDITOR,vim\nenv = EDITOR,nano\n").unwrap();
+
+    hypr.set_env("EDITOR", "helix").unwrap();
+
+    let output = hypr.config().serialize();
+    assert_eq!(output, "env = EDITOR,vim\nenv = EDITOR,helix\n");
+}
+
+#[test]
+fn test_set_env_value_with_commas_round_trips() {
+    let mut hypr = Hyprland::new();
+    hypr.parse("env = XCURSOR_SIZE,24\n").unwrap();
+
+    hypr.set_env("XDG_CURRENT_DESKTOP", "Hyprland,sway")
+        .unwrap();
+
+    let mut reparsed = Hyprland::new();
+    reparsed.parse(&hypr.config().serialize()).unwrap();
+
+    let env = reparsed
+        .parsed_env()
+        .into_iter()
+        .find(|e| e.name == "XDG_CURRENT_DESKTOP")
+        .unwrap();
+    assert_eq!(env.value, "Hyprland,sway");
+}
+
+#[test]
+fn test_add_exec_once_skips_duplicates() {
+    let mut hypr = Hyprland::new();
+    hypr.parse("exec-once = waybar\nexec-once = dunst\n")
+        .unwrap();
+
+    assert!(!hypr.add_exec_once("waybar").unwrap());
+    assert!(!hypr.add_exec_once("  dunst ").unwrap());
+    assert!(hypr.add_exec_once("hyprpaper").unwrap());
+
+    assert_eq!(hypr.all_exec_once(), vec!["waybar", "dunst", "hyprpaper"]);
+    assert_eq!(
+        hypr.config().serialize(),
+        "exec-once = waybar\nexec-once = dunst\nexec-once = hyprpaper\n"
+    );
+}
+
+#[test]
+fn test_add_exec_is_independent_of_exec_once() {
+    let mut hypr = Hyprland::new();
+    hypr.parse("exec-once = waybar\n").unwrap();
+
+    assert!(hypr.add_exec("waybar").unwrap());
+    assert!(!hypr.add_exec("waybar").unwrap());
+    assert_eq!(hypr.all_exec(), vec!["waybar"]);
+    assert_eq!(hypr.all_exec_once(), vec!["waybar"]);
+}
+
+#[test]
+fn test_remove_exec_once_matching() {
+    let mut hypr = Hyprland::new();
+    hypr.parse(
+        r#"exec-once = waybar
+exec-once = swayidle -w timeout 300 'hyprlock'
+exec-once = dunst
+exec-once = hyprlock --immediate
+"#,
+    )
+    .unwrap();
+
+    let removed = hypr.remove_exec_once_matching("hyprlock").unwrap();
+    assert_eq!(
+        removed,
+        vec!["swayidle -w timeout 300 'hyprlock'", "hyprlock --immediate"]
+    );
+    assert_eq!(hypr.all_exec_once(), vec!["waybar", "dunst"]);
+    assert_eq!(
+        hypr.config().serialize(),
+        "exec-once = waybar\nexec-once = dunst\n"
+    );
+
+    assert!(
+        hypr.remove_exec_once_matching("nothing")
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_remove_exec_matching_without_entries() {
+    let mut hypr = Hyprland::new();
+    hypr.parse("exec-once = waybar\n").unwrap();
+
+    assert!(hypr.remove_exec_matching("waybar").unwrap().is_empty());
+    assert_eq!(hypr.all_exec_once(), vec!["waybar"]);
+}
+
+#[test]
+fn test_move_exec_once() {
+    let mut hypr = Hyprland::new();
+    hypr.parse(
+        r#"exec-once = waybar
+general {
+    border_size = 2
+}
+exec-once = dunst
+exec-once = hyprpaper
+"#,
+    )
+    .unwrap();
+
+    hypr.move_exec_once(0, 2).unwrap();
+    assert_eq!(hypr.all_exec_once(), vec!["dunst", "hyprpaper", "waybar"]);
+
+    let output = hypr.config().serialize();
+    let dunst = output.find("exec-once = dunst").unwrap();
+    let general = output.find("general {").unwrap();
+    let waybar = output.find("exec-once = waybar").unwrap();
+    assert!(dunst < general);
+    assert!(general < waybar);
+
+    assert!(hypr.move_exec_once(0, 3).is_err());
+}
+
+#[test]
+fn test_dedup_exec_once() {
+    let mut hypr = Hyprland::new();
+    hypr.parse(
+        r#"exec-once = waybar
+exec-once = dunst
+exec-once = waybar
+exec-once = dunst
+exec-once = hyprpaper
+"#,
+    )
+    .unwrap();
+
+    let removed = hypr.dedup_exec_once().unwrap();
+    assert_eq!(removed, vec!["waybar", "dunst"]);
+    assert_eq!(hypr.all_exec_once(), vec!["waybar", "dunst", "hyprpaper"]);
+    assert_eq!(
+        hypr.config().serialize(),
+        "exec-once = waybar\nexec-once = dunst\nexec-once = hyprpaper\n"
+    );
+}
@@ -9,7 +9,7 @@
 
 #![cfg(feature = "hyprland")]
 
-use hyprlang::Hyprland;
+use hyprlang::{Hyprland, Vec2};
 
 /// Test parsing the exact windowrule v3 syntax from Hyprland's test.conf
 #[test]
@@ -142,6 +142,7 @@ fn test_hyprland_test_conf_wr_kitty_stuff() {
     assert_eq!(rule.get_string("match:class").unwrap(), "wr_kitty");
     assert_eq!(rule.get_int("float").unwrap(), 1);
     assert_eq!(rule.get_string("size").unwrap(), "200 200");
+    assert_eq!(rule.get_vec2("size").unwrap(), Vec2::new(200.0, 200.0));
     assert_eq!(rule.get_int("pin").unwrap(), 0);
 }
 
@@ -293,6 +294,7 @@ fn test_hyprland_expression_rules() {
     // "yes" gets parsed as boolean true (1)
     assert_eq!(rule.get_int("float").unwrap(), 1);
     assert_eq!(rule.get_string("size").unwrap(), "monitor_w*0.5 monitor_h*0.5");
+    assert!(rule.get_vec2("size").is_err());
     assert_eq!(
         rule.get_string("move").unwrap(),
         "20+(monitor_w*0.1) monitor_h*0.5"
@@ -0,0 +1,336 @@
+//! Tests for `Hyprland::snapshot` and the `HyprlandSettings` data types.
+
+#![cfg(feature = "hyprland")]
+
+#[cfg(feature = "mutation")]
+mod common;
+
+#[cfg(feature = "mutation")]
+use common::{cleanup_test_dir, create_test_dir, parsed_hyprland};
+#[cfg(feature = "mutation")]
+use hyprlang::{BindFlag, Keybind, Monitor, RuleSettings};
+use hyprlang::{Color, EnvVar, Hyprland, HyprlandSettings};
+
+const CONFIG: &str = r#"
+$terminal = kitty
+
+general {
+    border_size = 2
+    gaps_in = 5 10
+    col.active_border = rgba(33ccffee)
+    layout = dwindle
+}
+
+decoration {
+    rounding = 8
+    blur {
+        enabled = true
+        passes = 3
+    }
+}
+
+animations {
+    enabled = true
+    bezier = ease, 0.05, 0.9, 0.1, 1.0
+    animation = windows, 1, 4, ease
+}
+
+monitor = DP-1, 1920x1080@144, 0x0, 1
+env = XCURSOR_SIZE,24
+exec-once = waybar
+workspace = 1, monitor:DP-1
+bind = SUPER, Q, exec, $terminal
+
+windowrule[float-kitty] {
+    match:class = ^(kitty)$
+    float = true
+}
+"#;
+
+fn snapshot() -> HyprlandSettings {
+    let mut hypr = Hyprland::new();
+    hypr.parse(CONFIG).unwrap();
+    hypr.snapshot()
+}
+
+#[test]
+fn test_snapshot_sections() {
+    let settings = snapshot();
+
+    assert_eq!(settings.general.border_size, Some(2));
+    assert_eq!(settings.general.gaps_in.as_deref(), Some("5 10"));
+    assert_eq!(
+        settings.general.active_border_color,
+        Some(Color::from_rgba(0x33, 0xcc, 0xff, 0xee))
+    );
+    assert_eq!(settings.general.layout.as_deref(), Some("dwindle"));
+    assert_eq!(settings.general.locale, None);
+
+    assert_eq!(settings.decoration.rounding, Some(8));
+    assert_eq!(settings.decoration.blur_enabled, Some(true));
+    assert_eq!(settings.decoration.blur_passes, Some(3));
+    assert_eq!(settings.decoration.blur_size, None);
+    assert_eq!(settings.input.kb_layout, None);
+
+    assert_eq!(settings.animations.enabled, Some(true));
+    assert_eq!(
+        settings.animations.beziers,
+        vec!["ease, 0.05, 0.9, 0.1, 1.0"]
+    );
+    assert_eq!(settings.animations.animations, vec!["windows, 1, 4, ease"]);
+
+    assert_eq!(settings.options["decoration:blur:passes"], "3");
+    assert_eq!(settings.variables["terminal"], "kitty");
+}
+
+#[test]
+fn test_snapshot_collections() {
+    let settings = snapshot();
+
+    assert_eq!(settings.binds.len(), 1);
+    assert_eq!(settings.binds[0].args, "kitty");
+    assert_eq!(settings.monitors[0].name, "DP-1");
+    assert_eq!(settings.monitors[0].mode.as_deref(), Some("1920x1080@144"));
+    assert_eq!(settings.env, vec![EnvVar::new("XCURSOR_SIZE", "24")]);
+    assert_eq!(settings.exec_once, vec!["waybar"]);
+    assert!(settings.exec.is_empty());
+    assert_eq!(settings.workspaces, vec!["1, monitor:DP-1"]);
+    assert!(settings.layerrules.is_empty());
+
+    // Only the properties written in the block, not every defaulted rule property
+    let rule = &settings.windowrules[0];
+    assert_eq!(rule.name, "float-kitty");
+    assert_eq!(rule.properties.len(), 2);
+    assert_eq!(rule.properties["match:class"], "^(kitty)$");
+    assert_eq!(rule.properties["float"], "1");
+}
+
+#[test]
+fn test_snapshot_outlives_config() {
+    let settings = {
+        let mut hypr = Hyprland::new();
+        hypr.parse(CONFIG).unwrap();
+        hypr.snapshot()
+    };
+
+    let handle = std::thread::spawn(move || settings.general.border_size);
+    assert_eq!(handle.join().unwrap(), Some(2));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_snapshot_serde_round_trip() {
+    let settings = snapshot();
+
+    let json = serde_json::to_string(&settings).unwrap();
+    assert!(json.contains("\"border_size\":2"));
+
+    let restored: HyprlandSettings = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, settings);
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_apply_unchanged_snapshot() {
+    let mut hypr = parsed_hyprland(CONFIG);
+    let before = hypr.config().serialize();
+
+    let changed = hypr.apply(&hypr.snapshot()).unwrap();
+    assert!(changed.is_empty());
+    assert_eq!(hypr.config().serialize(), before);
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_apply_options_and_variables() {
+    let mut hypr =
+        parsed_hyprland("$gap = 5\ngeneral {\n    gaps_in = $gap\n    layout = dwindle\n}\n");
+    let mut settings = hypr.snapshot();
+    settings.general.layout = Some("master".to_string());
+    settings.general.inactive_border_color = Some(Color::from_rgba(0x44, 0x44, 0x44, 0xff));
+    settings
+        .options
+        .insert("cursor:hide_on_tablet".to_string(), "true".to_string());
+    settings
+        .variables
+        .insert("terminal".to_string(), "foot".to_string());
+
+    let changed = hypr.apply(&settings).unwrap();
+    assert_eq!(
+        changed,
+        vec![
+            "cursor:hide_on_tablet",
+            "general:col.inactive_border",
+            "general:layout",
+            "$terminal",
+        ]
+    );
+    assert_eq!(hypr.general_layout().unwrap(), "master");
+    assert!(hypr.cursor_hide_on_tablet().unwrap());
+
+    // The untouched line keeps its variable reference
+    let output = hypr.config().serialize();
+    assert!(output.contains("    gaps_in = $gap\n"));
+    assert!(output.contains("    layout = master\n"));
+    assert!(output.contains("col.inactive_border = rgba(444444ff)"));
+    assert_eq!(parsed_hyprland(&output).snapshot(), hypr.snapshot());
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_apply_binds() {
+    let mut hypr = parsed_hyprland(
+        "bind = SUPER, Q, exec, kitty\nbind = SUPER, C, killactive\nbind = SUPER, F, fullscreen\n",
+    );
+    let mut settings = hypr.snapshot();
+    settings.binds[0].args = "foot".to_string();
+    settings.binds[1].flags = vec![BindFlag::Locked];
+    settings.binds.pop();
+    settings.binds.push(Keybind::parse(
+        &[BindFlag::Repeat],
+        ", XF86AudioRaiseVolume, exec, volume up",
+    ));
+
+    assert_eq!(hypr.apply(&settings).unwrap(), vec!["bind"]);
+    assert_eq!(hypr.keybinds(), settings.binds);
+    assert_eq!(
+        hypr.config().serialize(),
+        "bind = SUPER, Q, exec, foot\nbindl = SUPER, C, killactive\n\
+         binde = , XF86AudioRaiseVolume, exec, volume up\n"
+    );
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_apply_handler_lists_and_monitors() {
+    let mut hypr = parsed_hyprland(CONFIG);
+    let mut settings = hypr.snapshot();
+    settings.env[0].value = "32".to_string();
+    settings.exec_once.clear();
+    settings.workspaces.push("2, monitor:HDMI-A-1".to_string());
+    settings.monitors[0].scale = Some("1.5".to_string());
+    settings
+        .monitors
+        .push(Monitor::parse("HDMI-A-1, preferred, auto, 1"));
+
+    let changed = hypr.apply(&settings).unwrap();
+    assert_eq!(changed, vec!["workspace", "env", "exec-once", "monitor"]);
+
+    let output = hypr.config().serialize();
+    assert!(output.contains("env = XCURSOR_SIZE,32\n"));
+    assert!(!output.contains("exec-once"));
+    assert!(output.contains("monitor = DP-1, 1920x1080@144, 0x0, 1.5\n"));
+    assert!(output.contains("monitor = HDMI-A-1, preferred, auto, 1\n"));
+    assert_eq!(
+        parsed_hyprland(&output).snapshot().monitors,
+        settings.monitors
+    );
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_apply_rules() {
+    let mut hypr = parsed_hyprland(
+        "windowrule[float-kitty] {\n    match:class = ^(kitty)$\n    float = true\n    center = true\n}\n\
+         windowrule[pin-mpv] {\n    match:class = mpv\n    pin = true\n}\n",
+    );
+    let mut settings = hypr.snapshot();
+    let rule = &mut settings.windowrules[0];
+    rule.properties.remove("center");
+    rule.properties
+        .insert("opacity".to_string(), "0.9".to_string());
+    settings.windowrules.remove(1);
+    settings.windowrules.push(RuleSettings {
+        name: "no-blur".to_string(),
+        properties: [("match:class".to_string(), "firefox".to_string())].into(),
+    });
+
+    let changed = hypr.apply(&settings).unwrap();
+    assert_eq!(
+        changed,
+        vec![
+            "windowrule[pin-mpv]",
+            "windowrule[float-kitty]",
+            "windowrule[no-blur]",
+        ]
+    );
+    assert_eq!(hypr.snapshot().windowrules, settings.windowrules);
+    assert_eq!(
+        hypr.get_windowrule("float-kitty")
+            .unwrap()
+            .get_float("opacity")
+            .unwrap(),
+        0.9
+    );
+
+    // Existing lines are kept, changed ones edited in place
+    assert_eq!(
+        hypr.config().serialize(),
+        "windowrule[float-kitty] {\n    match:class = ^(kitty)$\n    float = true\n    opacity = 0.9\n}\n\
+         windowrule[no-blur] {\n    match:class = firefox\n}\n"
+    );
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_apply_rejects_unsupported_changes() {
+    let mut hypr = parsed_hyprland("monitor[DP-1] {\n    mode = 1920x1080\n}\n");
+    let mut settings = hypr.snapshot();
+    settings.monitors[0].scale = Some("2".to_string());
+    settings.general.border_size = Some(3);
+    assert!(hypr.apply(&settings).is_err());
+    assert!(hypr.general_border_size().is_err());
+
+    let mut hypr = parsed_hyprland(CONFIG);
+    let mut settings = hypr.snapshot();
+    settings.animations.beziers.clear();
+    assert!(hypr.apply(&settings).is_err());
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_apply_rules_in_sourced_file() {
+    let dir = create_test_dir("hyprland_snapshot");
+    std::fs::write(
+        dir.join("hyprland.conf"),
+        "source = rules.conf\ngeneral {\n    border_size = 2\n}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("rules.conf"),
+        "windowrule[kitty] {\n    match:class = kitty\n    float = true\n}\n",
+    )
+    .unwrap();
+
+    let mut hypr = Hyprland::new();
+    hypr.parse_file(&dir.join("hyprland.conf")).unwrap();
+    let mut settings = hypr.snapshot();
+    settings.windowrules[0]
+        .properties
+        .insert("opacity".to_string(), "0.9".to_string());
+
+    assert_eq!(hypr.apply(&settings).unwrap(), vec!["windowrule[kitty]"]);
+    let saved = hypr.config_mut().save_all().unwrap();
+    assert_eq!(saved.len(), 1);
+    assert!(saved[0].ends_with("rules.conf"));
+    assert_eq!(
+        std::fs::read_to_string(dir.join("rules.conf")).unwrap(),
+        "windowrule[kitty] {\n    match:class = kitty\n    float = true\n    opacity = 0.9\n}\n"
+    );
+
+    // A second edit still finds the file the rule lives in
+    let mut settings = hypr.snapshot();
+    settings.windowrules[0].properties.remove("float");
+    hypr.apply(&settings).unwrap();
+    hypr.config_mut().save_all().unwrap();
+    assert_eq!(
+        std::fs::read_to_string(dir.join("rules.conf")).unwrap(),
+        "windowrule[kitty] {\n    match:class = kitty\n    opacity = 0.9\n}\n"
+    );
+
+    let mut reparsed = Hyprland::new();
+    reparsed.parse_file(&dir.join("hyprland.conf")).unwrap();
+    assert_eq!(reparsed.snapshot().windowrules, settings.windowrules);
+
+    cleanup_test_dir(&dir);
+}
@@ -0,0 +1,180 @@
+//! Tests for version-targeted Hyprland profiles.
+#![cfg(feature = "hyprland")]
+
+use hyprlang::{Color, Hyprland, HyprlandOption, HyprlandVersion};
+
+#[test]
+fn test_version_parsing() {
+    assert_eq!(
+        HyprlandVersion::parse("0.53").unwrap(),
+        HyprlandVersion::new(0, 53, 0)
+    );
+    assert_eq!(
+        "v0.48.1".parse::<HyprlandVersion>().unwrap(),
+        HyprlandVersion::new(0, 48, 1)
+    );
+    assert_eq!(HyprlandVersion::new(0, 53, 1).to_string(), "0.53.1");
+
+    assert!(HyprlandVersion::parse("0").is_err());
+    assert!(HyprlandVersion::parse("0.53.1.2").is_err());
+    assert!(HyprlandVersion::parse("latest").is_err());
+    assert!(Hyprland::for_version("0.x").is_err());
+}
+
+#[test]
+fn test_version_ordering() {
+    assert!(HyprlandVersion::new(0, 9, 0) < HyprlandVersion::new(0, 10, 0));
+    assert!(HyprlandVersion::new(0, 53, 0) < HyprlandVersion::new(0, 53, 1));
+    assert!(HyprlandVersion::new(1, 0, 0) > HyprlandVersion::new(0, 99, 9));
+}
+
+#[test]
+fn test_option_availability() {
+    let options = HyprlandOption::all();
+    let option = |key: &str| options.iter().find(|option| option.key == key).unwrap();
+
+    let v044 = HyprlandVersion::new(0, 44, 0);
+    let v045 = HyprlandVersion::new(0, 45, 0);
+    assert!(option("decoration:drop_shadow").available_in(v044));
+    assert!(!option("decoration:drop_shadow").available_in(v045));
+    assert!(!option("decoration:shadow:enabled").available_in(v044));
+    assert!(option("decoration:shadow:enabled").available_in(v045));
+    assert!(option("general:border_size").available_in(v044));
+}
+
+#[test]
+fn test_renamed_handler_is_alias() {
+    let mut hypr = Hyprland::for_version("0.53").unwrap();
+    hypr.parse("windowrule = float, class:kitty\nwindowrulev2 = pin, class:mpv\n")
+        .unwrap();
+
+    assert_eq!(
+        hypr.config().get_handler_calls("windowrule").unwrap().len(),
+        2
+    );
+    assert!(hypr.config().get_handler_calls("windowrulev2").is_none());
+
+    let deprecations = hypr.config().deprecations();
+    assert_eq!(deprecations.len(), 1);
+    assert_eq!(deprecations[0].alias, "windowrulev2");
+    assert_eq!(deprecations[0].line, 2);
+}
+
+#[test]
+fn test_older_release_keeps_windowrulev2() {
+    let mut hypr = Hyprland::for_version("0.47").unwrap();
+    hypr.parse("windowrule = float, ^(kitty)$\nwindowrulev2 = pin, class:mpv\n")
+        .unwrap();
+
+    assert_eq!(
+        hypr.config().get_handler_calls("windowrule").unwrap().len(),
+        1
+    );
+    assert_eq!(
+        hypr.config()
+            .get_handler_calls("windowrulev2")
+            .unwrap()
+            .len(),
+        1
+    );
+    assert!(hypr.config().deprecations().is_empty());
+    assert!(hypr.config().handler_aliases().is_empty());
+}
+
+#[test]
+fn test_rule_blocks_need_0_53() {
+    let block = "windowrule[float-kitty] {\n    match:class = kitty\n    float = true\n}";
+
+    let mut hypr = Hyprland::for_version("0.53").unwrap();
+    hypr.parse(block).unwrap();
+    assert_eq!(hypr.windowrule_names(), vec!["float-kitty"]);
+
+    let mut hypr = Hyprland::for_version("0.52").unwrap();
+    assert!(hypr.parse(block).is_err());
+}
+
+#[test]
+fn test_option_defaults_and_types() {
+    let mut hypr = Hyprland::for_version("0.53").unwrap();
+
+    assert_eq!(hypr.general_border_size().unwrap(), 1);
+    assert_eq!(hypr.general_layout().unwrap(), "dwindle");
+    assert_eq!(hypr.master_new_status().unwrap(), "slave");
+    assert_eq!(hypr.quirks_prefer_hdr().unwrap(), 0);
+    assert!(!hypr.config().contains("decoration:drop_shadow"));
+
+    assert!(hypr.parse("general:border_size = thick").is_err());
+
+    let hypr = Hyprland::for_version("0.40").unwrap();
+    assert_eq!(hypr.config().get_int("master:new_is_master").unwrap(), 1);
+    assert!(hypr.master_new_status().is_err());
+    assert!(hypr.quirks_prefer_hdr().is_err());
+}
+
+#[test]
+fn test_gradient_border_colors() {
+    let mut hypr = Hyprland::for_version("0.53").unwrap();
+    assert_eq!(
+        hypr.general_active_border_color().unwrap(),
+        Color::from_rgba(255, 255, 255, 255)
+    );
+    assert_eq!(
+        hypr.general_inactive_border_color().unwrap(),
+        Color::from_rgba(0x44, 0x44, 0x44, 255)
+    );
+
+    // The border lines of the stock hyprland.conf
+    hypr.parse(
+        "general {\n    col.active_border = rgba(33ccffee) rgba(00ff99ee) 45deg\n    \
+         col.inactive_border = rgba(595959aa)\n}\n",
+    )
+    .unwrap();
+    assert!(hypr.general_active_border_color().is_err());
+    assert_eq!(
+        hypr.config()
+            .get_string("general:col.active_border")
+            .unwrap(),
+        "rgba(33ccffee) rgba(00ff99ee) 45deg"
+    );
+    assert_eq!(
+        hypr.general_inactive_border_color().unwrap(),
+        Color::from_rgba(0x59, 0x59, 0x59, 0xaa)
+    );
+}
+
+#[test]
+fn test_unsupported_options() {
+    let config =
+        "decoration {\n    drop_shadow = 1\n}\nquirks:prefer_hdr = 1\nmaster:new_status = master\n";
+
+    let mut hypr = Hyprland::for_version("0.44").unwrap();
+    hypr.parse(config).unwrap();
+    assert_eq!(hypr.version(), Some(HyprlandVersion::new(0, 44, 0)));
+    assert_eq!(hypr.unsupported_options(), vec!["quirks:prefer_hdr"]);
+
+    let mut hypr = Hyprland::for_version("0.53.1").unwrap();
+    hypr.parse(config).unwrap();
+    assert_eq!(hypr.unsupported_options(), vec!["decoration:drop_shadow"]);
+
+    // Defaults alone are never reported
+    let hypr = Hyprland::for_version("0.30").unwrap();
+    assert!(hypr.unsupported_options().is_empty());
+}
+
+#[test]
+fn test_unversioned_hyprland() {
+    let mut hypr = Hyprland::new();
+    hypr.parse("windowrulev2 = pin, class:mpv\ndecoration:drop_shadow = 1\n")
+        .unwrap();
+
+    assert_eq!(hypr.version(), None);
+    assert_eq!(
+        hypr.config()
+            .get_handler_calls("windowrulev2")
+            .unwrap()
+            .len(),
+        1
+    );
+    assert!(hypr.unsupported_options().is_empty());
+    assert!(hypr.config().deprecations().is_empty());
+}
@@ -0,0 +1,131 @@
+//! Tests for iterating over config values.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Color, Config, UnifiedEntry};
+
+const INPUT: &str = r#"
+border_size = 2
+general {
+    gaps_in = 5
+    col.active_border = rgba(33ccffee)
+}
+decoration {
+    rounding = 10
+    shadow_color = rgba(000000ff)
+    blur {
+        size = 8
+    }
+}
+general_extra = 1
+"#;
+
+fn parsed() -> Config {
+    let mut config = Config::new();
+    config.parse(INPUT).unwrap();
+    config
+}
+
+#[test]
+fn test_iter_yields_every_value() {
+    let config = parsed();
+
+    let mut keys: Vec<&str> = config.iter().map(|(key, _)| key).collect();
+    keys.sort_unstable();
+    let mut expected = config.keys();
+    expected.sort_unstable();
+    assert_eq!(keys, expected);
+
+    for (key, value) in config.iter() {
+        assert_eq!(value.to_string(), config.get(key).unwrap().to_string());
+    }
+}
+
+#[test]
+fn test_iter_colors() {
+    let config = parsed();
+
+    let mut colors: Vec<(&str, Color)> = config.iter_colors().collect();
+    colors.sort_by_key(|(key, _)| *key);
+    assert_eq!(
+        colors,
+        vec![
+            ("decoration:shadow_color", Color::from_rgba(0, 0, 0, 255)),
+            (
+                "general:col.active_border",
+                Color::from_rgba(0x33, 0xcc, 0xff, 0xee)
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_iter_in_category() {
+    let config = parsed();
+
+    let mut general: Vec<&str> = config.iter_in_category("general").map(|(k, _)| k).collect();
+    general.sort_unstable();
+    assert_eq!(
+        general,
+        vec!["general:col.active_border", "general:gaps_in"]
+    );
+
+    let mut decoration: Vec<&str> = config
+        .iter_in_category("decoration")
+        .map(|(k, _)| k)
+        .collect();
+    decoration.sort_unstable();
+    assert_eq!(
+        decoration,
+        vec![
+            "decoration:blur:size",
+            "decoration:rounding",
+            "decoration:shadow_color"
+        ]
+    );
+
+    let blur: Vec<_> = config.iter_in_category("decoration:blur").collect();
+    assert_eq!(blur.len(), 1);
+    assert_eq!(blur[0].1.as_int().unwrap(), 8);
+
+    assert_eq!(config.iter_in_category("missing").count(), 0);
+}
+
+#[test]
+fn test_unified_entries() {
+    let mut config = Config::new();
+    config.register_handler_fn("bind", |_| Ok(()));
+    config.register_category_handler_fn("animations", "bezier", |_| Ok(()));
+    config
+        .parse(
+            "animations {\n    enabled = true\n    bezier = b, 0, 0, 1, 1\n    bezier = a, 1, 1, 0, 0\n}\nbind = SUPER, Q, exit\ngaps = 5\n",
+        )
+        .unwrap();
+
+    let entries = config.unified_entries();
+    let texts: Vec<_> = entries.iter().map(UnifiedEntry::text).collect();
+    let listed: Vec<(&str, &str, bool)> = entries
+        .iter()
+        .zip(&texts)
+        .map(|(entry, text)| (entry.key(), text.as_ref(), entry.is_handler_call()))
+        .collect();
+    assert_eq!(
+        listed,
+        [
+            ("animations:bezier", "b, 0, 0, 1, 1", true),
+            ("animations:bezier", "a, 1, 1, 0, 0", true),
+            ("animations:enabled", "1", false),
+            ("bind", "SUPER, Q, exit", true),
+            ("gaps", "5", false),
+        ]
+    );
+
+    match entries[1] {
+        UnifiedEntry::HandlerCall { index, record, .. } => {
+            assert_eq!(index, 1);
+            assert_eq!(record.line, Some(4));
+        }
+        UnifiedEntry::Value { .. } => panic!("expected a handler call"),
+    }
+    assert_eq!(entries[2].line(), Some(2));
+}
@@ -0,0 +1,106 @@
+//! Tests for alias keys that resolve to the same value.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, ConfigValue};
+
+fn aliased() -> Config {
+    let mut config = Config::new();
+    config.alias_key("general:col.active_border", "general:active_border_color");
+    config
+}
+
+#[test]
+fn test_alias_reads_same_value() {
+    let mut config = aliased();
+    config
+        .parse("general {\n    active_border_color = rgba(33ccffee)\n}\n")
+        .unwrap();
+
+    let canonical = config.get_color("general:col.active_border").unwrap();
+    let alias = config.get_color("general:active_border_color").unwrap();
+    assert_eq!(canonical, alias);
+    assert_eq!(config.keys(), vec!["general:col.active_border"]);
+    assert!(config.contains("general : active_border_color"));
+    assert_eq!(
+        config
+            .key_aliases()
+            .get("general:active_border_color")
+            .map(String::as_str),
+        Some("general:col.active_border")
+    );
+}
+
+#[test]
+fn test_set_through_either_key() {
+    let mut config = aliased();
+    config
+        .set(
+            "general:active_border_color",
+            ConfigValue::String("a".into()),
+        )
+        .unwrap();
+    assert_eq!(config.get_string("general:col.active_border").unwrap(), "a");
+
+    config
+        .set("general:col.active_border", ConfigValue::String("b".into()))
+        .unwrap();
+    assert_eq!(
+        config.get_string("general:active_border_color").unwrap(),
+        "b"
+    );
+    assert_eq!(config.keys().len(), 1);
+}
+
+#[test]
+fn test_last_spelling_wins_when_both_are_written() {
+    let mut config = aliased();
+    config
+        .parse("general:col.active_border = a\ngeneral:active_border_color = b\n")
+        .unwrap();
+    assert_eq!(config.get_string("general:col.active_border").unwrap(), "b");
+}
+
+#[test]
+fn test_alias_registered_after_parse() {
+    let mut config = Config::new();
+    config.parse("general:active_border_color = a\n").unwrap();
+    config.alias_key("general:col.active_border", "general:active_border_color");
+
+    assert_eq!(config.get_string("general:col.active_border").unwrap(), "a");
+    assert_eq!(config.keys(), vec!["general:col.active_border"]);
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_document_keeps_written_spelling() {
+    let mut config = aliased();
+    config
+        .parse("general {\n    active_border_color = a\n}\n")
+        .unwrap();
+
+    config.set_string("general:col.active_border", "b").unwrap();
+    assert_eq!(
+        config.serialize(),
+        "general {\n    active_border_color = b\n}\n"
+    );
+
+    config.remove("general:col.active_border").unwrap();
+    assert_eq!(config.serialize(), "general {\n}\n");
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_new_value_uses_spelling_it_was_set_with() {
+    let mut config = aliased();
+    config.parse("general {\n    gaps_in = 5\n}\n").unwrap();
+
+    config
+        .set_string("general:active_border_color", "a")
+        .unwrap();
+    config.set_string("general:col.active_border", "b").unwrap();
+    assert_eq!(
+        config.serialize(),
+        "general {\n    gaps_in = 5\n    active_border_color = b\n}\n"
+    );
+}
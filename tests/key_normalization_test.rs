@@ -0,0 +1,94 @@
+//! Tests for canonical key forms in getters and setters.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, ConfigOptions, ConfigValue, KeyCase, normalize_key};
+
+#[test]
+fn test_normalize_key() {
+    assert_eq!(
+        normalize_key(" general : gaps_in ", KeyCase::Sensitive),
+        "general:gaps_in"
+    );
+    assert_eq!(
+        normalize_key("General:Col.Active_Border", KeyCase::Sensitive),
+        "General:Col.Active_Border"
+    );
+    assert_eq!(
+        normalize_key("General:Col.Active_Border", KeyCase::Insensitive),
+        "general:col.active_border"
+    );
+    // Whitespace inside a part is kept
+    assert_eq!(
+        normalize_key("plugin : my key", KeyCase::Sensitive),
+        "plugin:my key"
+    );
+}
+
+#[test]
+fn test_whitespace_is_ignored_in_lookups() {
+    let mut config = Config::new();
+    config.parse("general {\n    gaps_in = 5\n}\n").unwrap();
+
+    assert_eq!(config.get_int("general : gaps_in").unwrap(), 5);
+    assert_eq!(config.get_int(" general:gaps_in ").unwrap(), 5);
+    assert!(config.contains("general :gaps_in"));
+    assert!(config.entry("general: gaps_in").is_some());
+    assert_eq!(
+        config.explain("general : gaps_in").unwrap().key,
+        "general:gaps_in"
+    );
+
+    // Keys are case-sensitive by default
+    assert!(config.get_int("General:gaps_in").is_err());
+}
+
+#[test]
+fn test_case_insensitive_keys() {
+    let mut config = Config::with_options(ConfigOptions {
+        key_case: KeyCase::Insensitive,
+        ..Default::default()
+    });
+    config.register_option("General:Border_Size", ConfigValue::Int(1));
+    config
+        .parse("general {\n    border_size = 3\n}\nDecoration:Rounding = 10\n")
+        .unwrap();
+
+    assert_eq!(config.get_int("GENERAL:BORDER_SIZE").unwrap(), 3);
+    assert_eq!(config.get_int("decoration:rounding").unwrap(), 10);
+
+    let mut keys = config.keys();
+    keys.sort();
+    assert_eq!(keys, vec!["decoration:rounding", "general:border_size"]);
+}
+
+#[test]
+fn test_set_uses_canonical_key() {
+    let mut config = Config::new();
+    config
+        .set("general : gaps_out", ConfigValue::Int(10))
+        .unwrap();
+
+    assert_eq!(config.keys(), vec!["general:gaps_out"]);
+    assert_eq!(config.get_int("general:gaps_out").unwrap(), 10);
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_mutation_with_uncanonical_key() {
+    let mut config = Config::new();
+    config.parse("general {\n    gaps_in = 5\n}\n").unwrap();
+
+    config.set_int(" general: gaps_in", 8).unwrap();
+    assert_eq!(config.serialize(), "general {\n    gaps_in = 8\n}\n");
+
+    assert_eq!(
+        config
+            .remove("general : gaps_in")
+            .unwrap()
+            .as_int()
+            .unwrap(),
+        8
+    );
+    assert!(!config.contains("general:gaps_in"));
+}
@@ -0,0 +1,170 @@
+//! Tests for the bind family: flag suffixes, `Keybind` parsing and the `all_bind*` accessors.
+
+#![cfg(feature = "hyprland")]
+
+use hyprlang::{BindFlag, FunctionHandler, Hyprland, Keybind};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const BINDS: &str = "\
+bind = SUPER, Q, exec, kitty
+bindl = , switch:on:Lid Switch, exec, hyprlock
+bindm = SUPER, mouse:272, movewindow
+bindel = , XF86AudioRaiseVolume, exec, wpctl set-volume @DEFAULT_AUDIO_SINK@ 5%+
+bindle = , XF86AudioLowerVolume, exec, wpctl set-volume @DEFAULT_AUDIO_SINK@ 5%-
+bindu = SUPER, escape, submap, reset
+bindd = SUPER, T, Open terminal, exec, kitty
+";
+
+fn parsed() -> Hyprland {
+    let mut hypr = Hyprland::new();
+    hypr.parse(BINDS).unwrap();
+    hypr
+}
+
+#[test]
+fn test_variants_are_recorded_as_flagged_binds() {
+    let hypr = parsed();
+    let records = hypr.config().get_handler_call_records("bind").unwrap();
+
+    let flags: Vec<_> = records
+        .iter()
+        .map(|record| record.flags.as_deref().unwrap_or_default())
+        .collect();
+    assert_eq!(flags, vec!["", "l", "m", "el", "le", "u", "d"]);
+    assert!(hypr.config().get_handler_calls("bindl").is_none());
+}
+
+#[test]
+fn test_variant_keywords_have_no_handler_calls_of_their_own() {
+    let hypr = parsed();
+    let config = hypr.config();
+
+    for variant in ["bindl", "bindm", "bindel", "bindle", "bindu", "bindd"] {
+        assert!(config.get_handler_calls(variant).is_none(), "{variant}");
+        assert!(
+            config.get_handler_call_records(variant).is_none(),
+            "{variant}"
+        );
+    }
+    assert_eq!(config.get_handler_calls("bind").unwrap().len(), 7);
+    assert_eq!(
+        hypr.binds_with_flags(&[BindFlag::Locked]),
+        vec![", switch:on:Lid Switch, exec, hyprlock"]
+    );
+}
+
+#[test]
+fn test_handlers_see_base_keyword_and_flags() {
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let mut hypr = Hyprland::new();
+    let sink = Rc::clone(&seen);
+    hypr.config_mut().register_handler(
+        "bind",
+        FunctionHandler::with_flags("bind", move |ctx| {
+            sink.borrow_mut()
+                .push((ctx.keyword.clone(), ctx.flags.clone()));
+            Ok(())
+        }),
+    );
+
+    hypr.parse("bind = SUPER, Q, exec, kitty\nbindlr = , XF86AudioMute, exec, mute")
+        .unwrap();
+    assert_eq!(
+        *seen.borrow(),
+        vec![
+            ("bind".to_string(), Some(String::new())),
+            ("bind".to_string(), Some("lr".to_string()))
+        ]
+    );
+}
+
+#[test]
+fn test_unknown_flags_are_rejected() {
+    let mut hypr = Hyprland::new();
+    assert!(hypr.parse("bindx = SUPER, Q, exec, kitty").is_err());
+    assert!(hypr.parse("bindlz = SUPER, Q, exec, kitty").is_err());
+}
+
+#[test]
+fn test_variant_accessors() {
+    let hypr = parsed();
+
+    assert_eq!(hypr.all_binds(), vec!["SUPER, Q, exec, kitty"]);
+    assert_eq!(hypr.all_bindl().len(), 1);
+    assert_eq!(hypr.all_bindm(), vec!["SUPER, mouse:272, movewindow"]);
+    // `bindel` and `bindle` carry the same flags
+    assert_eq!(hypr.all_bindel().len(), 2);
+    assert_eq!(hypr.all_bindu(), vec!["SUPER, escape, submap, reset"]);
+    assert_eq!(hypr.binds_with_flags(&[BindFlag::Description]).len(), 1);
+}
+
+#[test]
+fn test_keybinds() {
+    let binds = parsed().keybinds();
+    assert_eq!(binds.len(), 7);
+
+    assert!(binds[0].flags.is_empty());
+    assert_eq!(binds[0].mods, "SUPER");
+    assert_eq!(binds[0].args, "kitty");
+
+    assert_eq!(binds[2].dispatcher, "movewindow");
+    assert_eq!(binds[2].args, "");
+
+    // Commas in the arguments are kept
+    assert_eq!(binds[3].keyword(), "bindel");
+    assert_eq!(binds[3].mods, "");
+
+    assert_eq!(binds[6].description.as_deref(), Some("Open terminal"));
+    assert_eq!(binds[6].dispatcher, "exec");
+    assert_eq!(
+        binds[6].to_string(),
+        "bindd = SUPER, T, Open terminal, exec, kitty"
+    );
+}
+
+#[test]
+fn test_keybind_from_keyword() {
+    let bind = Keybind::from_keyword("bindo", "SUPER, XF86PowerOff, exec, a, b").unwrap();
+    assert_eq!(bind.flags, vec![BindFlag::LongPress]);
+    assert_eq!(bind.args, "a, b");
+
+    assert!(Keybind::from_keyword("binding", "").is_some());
+    assert!(Keybind::from_keyword("bindz", "").is_none());
+    assert!(Keybind::from_keyword("unbind", "").is_none());
+
+    for flag in [BindFlag::Transparent, BindFlag::Bypass, BindFlag::Universal] {
+        assert_eq!(BindFlag::from_suffix(flag.suffix()), Some(flag));
+    }
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_mutating_flagged_binds_updates_the_right_line() {
+    let mut hypr = parsed();
+    let config = hypr.config_mut();
+
+    // Index 1 is the `bindl` line
+    let removed = config.remove_handler_call("bind", 1).unwrap();
+    assert_eq!(removed, ", switch:on:Lid Switch, exec, hyprlock");
+    // Index 1 is now the `bindm` line
+    config
+        .update_handler_call("bind", 1, "SUPER, mouse:273, resizewindow".to_string())
+        .unwrap();
+
+    let output = config.serialize();
+    assert!(!output.contains("hyprlock"));
+    assert!(output.contains("bindm = SUPER, mouse:273, resizewindow\n"));
+    assert!(output.contains("bind = SUPER, Q, exec, kitty\n"));
+}
+
+#[test]
+fn test_bind_fields_split_like_handler_args() {
+    let bind = Keybind::from_keyword("bindd", "SUPER, Q, \"Close, now\", killactive").unwrap();
+    assert_eq!(bind.description.as_deref(), Some("\"Close, now\""));
+    assert_eq!(bind.dispatcher, "killactive");
+
+    let bind = Keybind::from_keyword("bindd", "SUPER, Q, Don't close, killactive").unwrap();
+    assert_eq!(bind.description.as_deref(), Some("Don't close"));
+    assert_eq!(bind.value(), "SUPER, Q, Don't close, killactive");
+}
@@ -0,0 +1,107 @@
+//! Tests for byte order marks, CRLF line endings and end-of-file padding.
+
+#![cfg(feature = "config")]
+
+mod common;
+
+use common::parsed;
+use hyprlang::{HyprlangParser, Statement};
+
+const CRLF_INPUT: &str = "$gap = 5\r\ngeneral {\r\n    gaps_in = $gap\r\n    layout = dwindle\r\n}\r\nname = \"hello\"\r\n";
+
+#[test]
+fn test_byte_order_mark() {
+    let config = parsed("\u{feff}gaps = 5\nname = test\n");
+
+    assert_eq!(config.get_int("gaps").unwrap(), 5);
+    assert_eq!(config.get_string("name").unwrap(), "test");
+}
+
+#[test]
+fn test_crlf_values_have_no_carriage_return() {
+    let config = parsed(CRLF_INPUT);
+
+    assert_eq!(config.get_int("general:gaps_in").unwrap(), 5);
+    assert_eq!(config.get_string("general:layout").unwrap(), "dwindle");
+    assert_eq!(config.get_string("name").unwrap(), "hello");
+    assert_eq!(config.entry("general:layout").unwrap().line, Some(4));
+}
+
+#[test]
+fn test_lone_carriage_returns_and_padding() {
+    let config = parsed("a = 1\rb = two\r\0\0\u{1a}");
+
+    assert_eq!(config.get_int("a").unwrap(), 1);
+    assert_eq!(config.get_string("b").unwrap(), "two");
+}
+
+#[test]
+fn test_core_parser_normalizes_input() {
+    let parsed = HyprlangParser::parse_config("\u{feff}key = value\r\n").unwrap();
+
+    assert!(matches!(
+        &parsed.statements[0],
+        Statement::Assignment { key, .. } if key == &["key".to_string()]
+    ));
+}
+
+#[cfg(feature = "mutation")]
+mod mutation {
+    use super::*;
+    use hyprlang::{Config, LineEnding, SerializeOptions};
+    use std::fs;
+
+    #[test]
+    fn test_crlf_round_trip() {
+        let config = parsed(CRLF_INPUT);
+        assert_eq!(config.serialize(), CRLF_INPUT);
+    }
+
+    #[test]
+    fn test_edits_keep_crlf() {
+        let mut config = parsed(CRLF_INPUT);
+        config.set_int("general:gaps_in", 10).unwrap();
+        config.set_int("general:border_size", 2).unwrap();
+
+        let output = config.serialize();
+        assert!(output.contains("    gaps_in = 10\r\n"));
+        assert!(output.contains("border_size = 2\r\n"));
+        assert!(!output.replace("\r\n", "").contains('\n'));
+    }
+
+    #[test]
+    fn test_line_ending_override() {
+        let config = parsed(CRLF_INPUT);
+        let options = SerializeOptions {
+            line_ending: Some(LineEnding::Lf),
+            keep_indent: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.serialize_with_options(&options),
+            CRLF_INPUT.replace("\r\n", "\n")
+        );
+        assert_eq!(LineEnding::detect("a = 1\nb = 2\r\n"), LineEnding::Lf);
+    }
+
+    #[test]
+    fn test_save_keeps_crlf() {
+        let path = std::env::temp_dir().join(format!(
+            "hyprlang_line_endings_test_{}.conf",
+            std::process::id()
+        ));
+        fs::write(&path, format!("\u{feff}{}", CRLF_INPUT)).unwrap();
+
+        let mut config = Config::new();
+        config.parse_file(&path).unwrap();
+        config.set_string("name", "world").unwrap();
+        config.save_all().unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            CRLF_INPUT.replace("\"hello\"", "world")
+        );
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,98 @@
+//! Tests for keeping `{{...}}` in values instead of evaluating it.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, ConfigOptions, ValueKind};
+
+fn without_expressions() -> Config {
+    Config::with_options(ConfigOptions {
+        evaluate_expressions: false,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_expressions_evaluated_by_default() {
+    let mut config = Config::new();
+    config.parse("a = {{1 + 2}}\nb = x {{1 + 2}}").unwrap();
+
+    assert!(config.evaluates_expressions("a"));
+    assert_eq!(config.get_int("a").unwrap(), 3);
+    assert_eq!(config.get_string("b").unwrap(), "x 3");
+}
+
+#[test]
+fn test_global_opt_out() {
+    let mut config = without_expressions();
+    config
+        .parse("$NAME = kitty\na = {{1 + 2}}\nb = $NAME {{title}}\nc = \\{{escaped}}\nd = 5")
+        .unwrap();
+
+    assert!(!config.evaluates_expressions("a"));
+    assert_eq!(config.kind_of("a"), Some(ValueKind::String));
+    assert_eq!(config.get_string("a").unwrap(), "{{1 + 2}}");
+    // Variables are still expanded and escapes still processed
+    assert_eq!(config.get_string("b").unwrap(), "kitty {{title}}");
+    assert_eq!(config.get_string("c").unwrap(), "{{escaped}}");
+    assert_eq!(config.get_int("d").unwrap(), 5);
+}
+
+#[test]
+fn test_per_key_opt_out() {
+    let mut config = Config::new();
+    config.mark_literal("notify:template");
+    config
+        .parse("notify {\n    template = {{summary}} - {{body}}\n    timeout = {{5 * 1000}}\n}")
+        .unwrap();
+
+    assert!(!config.evaluates_expressions("notify:template"));
+    assert_eq!(
+        config.get_string("notify:template").unwrap(),
+        "{{summary}} - {{body}}"
+    );
+    assert_eq!(config.get_int("notify:timeout").unwrap(), 5000);
+}
+
+#[test]
+fn test_unmarked_unknown_expression_still_fails() {
+    let mut config = Config::new();
+    config.mark_literal("other");
+    assert!(config.parse("template = {{summary}}").is_err());
+}
+
+#[test]
+fn test_explain_literal_value() {
+    let mut config = Config::new();
+    config.mark_literal("template");
+    config.parse("$X = 2\ntemplate = $X {{a + b}}").unwrap();
+
+    let trace = config.explain("template").unwrap();
+    assert!(trace.expressions.is_empty());
+    assert_eq!(trace.expanded, "2 {{a + b}}");
+    assert_eq!(trace.substitutions[0].name, "X");
+}
+
+#[test]
+fn test_handler_values_keep_braces() {
+    let mut config = Config::new();
+    config.register_handler_fn("exec-once", |_| Ok(()));
+    config
+        .parse("exec-once = notify-send {{title}}\nexec-once = {{1 + 2}}")
+        .unwrap();
+
+    assert_eq!(
+        config.get_handler_calls("exec-once").unwrap(),
+        &vec!["notify-send {{title}}".to_string(), "{{1 + 2}}".to_string()]
+    );
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_literal_values_round_trip() {
+    let mut config = without_expressions();
+    config.parse("template = {{name}}").unwrap();
+
+    let mut reparsed = without_expressions();
+    reparsed.parse(&config.serialize()).unwrap();
+    assert_eq!(reparsed.get_string("template").unwrap(), "{{name}}");
+}
@@ -0,0 +1,112 @@
+#![cfg(feature = "mutation")]
+//! Tests for rewriting every call of a handler at once.
+
+mod common;
+
+use common::{cleanup_test_dir, create_test_dir};
+use hyprlang::{Config, FunctionHandler};
+use std::fs;
+
+fn bind_config() -> Config {
+    let mut config = Config::new();
+    config.register_handler("bind", FunctionHandler::with_flags("bind", |_| Ok(())));
+    config
+}
+
+#[test]
+fn test_map_updates_store_records_and_document() {
+    let mut config = bind_config();
+    config
+        .parse(
+            "$mod = SUPER\nbind = ALT, Q, exec, kitty\nbind = $mod, C, killactive\n\
+             bindl = ALT, XF86AudioPlay, exec, playerctl play\n",
+        )
+        .unwrap();
+
+    let changed = config.map_handler_calls("bind", |value| value.replace("ALT", "SUPER"));
+    assert_eq!(changed, 2);
+
+    assert_eq!(
+        config.get_handler_calls("bind").unwrap(),
+        &vec![
+            "SUPER, Q, exec, kitty".to_string(),
+            "SUPER, C, killactive".to_string(),
+            "SUPER, XF86AudioPlay, exec, playerctl play".to_string(),
+        ]
+    );
+    let records = config.get_handler_call_records("bind").unwrap();
+    assert_eq!(
+        records[2].value,
+        "SUPER, XF86AudioPlay, exec, playerctl play"
+    );
+    assert_eq!(records[2].flags.as_deref(), Some("l"));
+
+    // The untouched line keeps its variable
+    assert_eq!(
+        config.serialize(),
+        "$mod = SUPER\nbind = SUPER, Q, exec, kitty\nbind = $mod, C, killactive\n\
+         bindl = SUPER, XF86AudioPlay, exec, playerctl play\n"
+    );
+}
+
+#[test]
+fn test_map_without_calls_changes_nothing() {
+    let mut config = bind_config();
+    config.parse("gaps = 5\n").unwrap();
+
+    assert_eq!(
+        config.map_handler_calls("bind", |value| value.to_uppercase()),
+        0
+    );
+    assert!(config.get_handler_calls("bind").is_none());
+    assert_eq!(config.serialize(), "gaps = 5\n");
+}
+
+#[test]
+fn test_map_sees_values_before_any_change() {
+    let mut config = bind_config();
+    config
+        .parse("bind = SUPER, 1, workspace, 1\nbind = SUPER, 2, workspace, 2\n")
+        .unwrap();
+
+    let mut seen = Vec::new();
+    config.map_handler_calls("bind", |value| {
+        seen.push(value.to_string());
+        value.replace("SUPER", "ALT")
+    });
+    assert_eq!(seen, ["SUPER, 1, workspace, 1", "SUPER, 2, workspace, 2"]);
+}
+
+#[test]
+fn test_map_across_sourced_files() {
+    let dir = create_test_dir("map_handler_calls");
+    fs::write(
+        dir.join("main.conf"),
+        "bind = ALT, Q, exec, kitty\nsource = keys.conf\ngaps = 5\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("keys.conf"),
+        "bind = SUPER, B, exec, firefox\nbind = ALT, E, exec, nautilus\n",
+    )
+    .unwrap();
+
+    let mut config = bind_config();
+    config.parse_file(dir.join("main.conf")).unwrap();
+    assert_eq!(
+        config.map_handler_calls("bind", |value| value.replace("ALT", "SUPER")),
+        2
+    );
+    config.save_all().unwrap();
+
+    assert_eq!(
+        fs::read_to_string(dir.join("main.conf")).unwrap(),
+        "bind = SUPER, Q, exec, kitty\nsource = keys.conf\ngaps = 5\n"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("keys.conf")).unwrap(),
+        "bind = SUPER, B, exec, firefox\nbind = SUPER, E, exec, nautilus\n"
+    );
+
+    cleanup_test_dir(&dir);
+}
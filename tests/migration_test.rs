@@ -0,0 +1,262 @@
+//! Tests for `Config::migrate` and migration rules.
+
+#![cfg(feature = "mutation")]
+
+mod common;
+
+use common::{cleanup_test_dir, create_test_dir, parsed};
+use hyprlang::{
+    Color, Config, ConfigValue, MigratedBlock, Migration, MigrationChange,
+    SpecialCategoryDescriptor,
+};
+use std::fs;
+
+const INPUT: &str = "\
+$radius = 8
+general {
+    gaps_in = 5
+    col.inactive_border = 0xff444444
+}
+decoration {
+    drop_shadow = true
+    shadow_range = 4
+    rounding = $radius
+}
+misc {
+    no_direct_scanout = true
+    vfr = true
+}
+";
+
+#[test]
+fn test_rename_in_place() {
+    let mut config = parsed(INPUT);
+    let report = config
+        .migrate(
+            &Migration::new("0.45")
+                .rename("decoration:drop_shadow", "decoration:shadow:enabled")
+                .rename("decoration:shadow_range", "decoration:shadow:range"),
+        )
+        .unwrap();
+
+    assert_eq!(report.migration, "0.45");
+    assert_eq!(
+        report.changes[0],
+        MigrationChange::Renamed {
+            from: "decoration:drop_shadow".to_string(),
+            to: "decoration:shadow:enabled".to_string(),
+        }
+    );
+    assert!(config.get("decoration:drop_shadow").is_err());
+    assert_eq!(config.get_int("decoration:shadow:range").unwrap(), 4);
+    assert_eq!(
+        config.entry("decoration:shadow:range").unwrap().line,
+        Some(8)
+    );
+    assert!(config.serialize().contains(
+        "decoration {\n    shadow:enabled = true\n    shadow:range = 4\n    rounding = $radius\n}"
+    ));
+}
+
+#[test]
+fn test_rename_to_another_block() {
+    let mut config = parsed(INPUT);
+    config
+        .migrate(&Migration::new("move").rename("misc:no_direct_scanout", "render:direct_scanout"))
+        .unwrap();
+
+    let output = config.serialize();
+    assert!(!output.contains("no_direct_scanout"));
+    assert!(output.contains("render:direct_scanout = true"));
+
+    // The migrated document parses to the same values
+    let reparsed = parsed(&output);
+    assert!(config.semantically_equal(&reparsed));
+}
+
+#[test]
+fn test_rename_keeps_existing_target() {
+    let mut config = parsed("old = 1\nnew = 2\n");
+    let report = config
+        .migrate(&Migration::new("keep").rename("old", "new"))
+        .unwrap();
+
+    assert!(matches!(report.changes[0], MigrationChange::Removed { .. }));
+    assert_eq!(config.get_int("new").unwrap(), 2);
+    assert_eq!(config.serialize(), "new = 2\n");
+}
+
+#[test]
+fn test_rules_skip_defaults_and_missing_keys() {
+    let mut config = Config::new();
+    config.register_option("decoration:drop_shadow", ConfigValue::Int(0));
+    config.parse("general {\n    gaps_in = 5\n}").unwrap();
+
+    let report = config
+        .migrate(
+            &Migration::new("noop")
+                .rename("decoration:drop_shadow", "decoration:shadow:enabled")
+                .remove("misc:gone")
+                .rewrite_value("general:gaps_out", |_| Some("1".to_string())),
+        )
+        .unwrap();
+    assert!(report.is_empty());
+    assert!(config.contains("decoration:drop_shadow"));
+}
+
+#[test]
+fn test_remove_and_rewrite() {
+    let mut config = parsed(INPUT);
+    let report = config
+        .migrate(
+            &Migration::new("0.50")
+                .remove("misc:no_direct_scanout")
+                .rewrite_value("general:col.inactive_border", |value| {
+                    let hex = value.strip_prefix("0x")?;
+                    Some(format!("rgba({}{})", &hex[2..], &hex[..2]))
+                })
+                .rewrite_value("general:gaps_in", |value| Some(value.to_string())),
+        )
+        .unwrap();
+
+    assert_eq!(report.len(), 2);
+    assert_eq!(
+        report.to_string(),
+        "migration 0.50: 2 change(s)\n  removed misc:no_direct_scanout = true\n  \
+         rewrote general:col.inactive_border from 0xff444444 to rgba(444444ff)"
+    );
+    assert!(!config.contains("misc:no_direct_scanout"));
+    assert_eq!(
+        config.get_color("general:col.inactive_border").unwrap(),
+        Color::from_rgba(0x44, 0x44, 0x44, 0xff)
+    );
+    assert_eq!(
+        config.entry("general:col.inactive_border").unwrap().line,
+        Some(4)
+    );
+    assert!(
+        config
+            .serialize()
+            .contains("    col.inactive_border = rgba(444444ff)\n")
+    );
+}
+
+#[test]
+fn test_rewrite_rejected_by_validator() {
+    let mut config = parsed("gaps = 5");
+    config.add_validator("gaps", |value| match value {
+        ConfigValue::Int(n) if *n >= 0 => Ok(()),
+        _ => Err("must not be negative".to_string()),
+    });
+
+    let result =
+        config.migrate(&Migration::new("bad").rewrite_value("gaps", |_| Some("-1".into())));
+    assert!(result.is_err());
+    assert_eq!(config.get_int("gaps").unwrap(), 5);
+}
+
+fn windowrule_migration() -> Migration {
+    Migration::new("0.53").handler_to_special_category("windowrulev2", "windowrule", |value| {
+        let (effect, matcher) = value.split_once(", ")?;
+        let class = matcher.strip_prefix("class:")?;
+        Some(
+            MigratedBlock::new(format!("{}-{}", effect, class))
+                .with("match:class", class)
+                .with(effect, "true"),
+        )
+    })
+}
+
+fn windowrule_config() -> Config {
+    let mut config = Config::new();
+    config.register_handler_fn("windowrulev2", |_| Ok(()));
+    config.register_special_category(SpecialCategoryDescriptor::keyed("windowrule", "name"));
+    config
+}
+
+#[test]
+fn test_handler_calls_to_special_categories() {
+    let mut config = windowrule_config();
+    config
+        .parse(
+            "windowrulev2 = float, class:kitty\n\
+             gaps = 5\n\
+             windowrulev2 = opacity 0.9, title:Firefox\n\
+             windowrulev2 = pin, class:mpv\n",
+        )
+        .unwrap();
+
+    let report = config.migrate(&windowrule_migration()).unwrap();
+    let keys: Vec<_> = report
+        .changes
+        .iter()
+        .map(|change| match change {
+            MigrationChange::Converted { key, .. } => key.as_str(),
+            _ => "",
+        })
+        .collect();
+    assert_eq!(keys, vec!["float-kitty", "pin-mpv"]);
+
+    // Unconverted calls are kept
+    assert_eq!(
+        config.get_handler_calls("windowrulev2").unwrap(),
+        &vec!["opacity 0.9, title:Firefox".to_string()]
+    );
+    let rule = config
+        .get_special_category("windowrule", "pin-mpv")
+        .unwrap();
+    assert_eq!(rule.get("match:class").unwrap().as_string().unwrap(), "mpv");
+
+    assert_eq!(
+        config.serialize(),
+        "windowrule[float-kitty] {\n  match:class = kitty\n  float = true\n}\n\
+         gaps = 5\n\
+         windowrulev2 = opacity 0.9, title:Firefox\n\
+         windowrule[pin-mpv] {\n  match:class = mpv\n  pin = true\n}\n"
+    );
+}
+
+#[test]
+fn test_conversion_needs_registered_category() {
+    let mut config = Config::new();
+    config.register_handler_fn("windowrulev2", |_| Ok(()));
+    config.parse("windowrulev2 = float, class:kitty").unwrap();
+
+    assert!(config.migrate(&windowrule_migration()).is_err());
+    assert_eq!(config.get_handler_calls("windowrulev2").unwrap().len(), 1);
+}
+
+#[test]
+fn test_migrate_sourced_file() {
+    let dir = create_test_dir("migration");
+    fs::write(
+        dir.join("main.conf"),
+        "source = ./decoration.conf\ngeneral {\n    gaps_in = 5\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("decoration.conf"),
+        "decoration {\n    drop_shadow = false\n}\n",
+    )
+    .unwrap();
+
+    let mut config = Config::new();
+    config.parse_file(dir.join("main.conf")).unwrap();
+    config
+        .migrate(
+            &Migration::new("0.45").rename("decoration:drop_shadow", "decoration:shadow:enabled"),
+        )
+        .unwrap();
+
+    let modified = config.get_modified_files();
+    assert_eq!(modified.len(), 1);
+    assert!(modified[0].ends_with("decoration.conf"));
+
+    config.save_all().unwrap();
+    assert_eq!(
+        fs::read_to_string(dir.join("decoration.conf")).unwrap(),
+        "decoration {\n    shadow:enabled = false\n}\n"
+    );
+
+    cleanup_test_dir(&dir);
+}
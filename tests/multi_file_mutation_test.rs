@@ -1,37 +1,15 @@
 #![cfg(feature = "mutation")]
 
+mod common;
+
+use common::{cleanup_test_dir, create_test_dir};
 use hyprlang::Config;
 use std::fs;
 use std::path::PathBuf;
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::time::{SystemTime, UNIX_EPOCH};
-
-static TEST_COUNTER: AtomicU64 = AtomicU64::new(0);
-
-/// Helper to create a temporary directory for test files
-fn create_test_dir() -> PathBuf {
-    let counter = TEST_COUNTER.fetch_add(1, Ordering::SeqCst);
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_nanos();
-    let dir = std::env::temp_dir().join(format!(
-        "hyprlang_multi_file_test_{}_{}",
-        timestamp,
-        counter
-    ));
-    fs::create_dir_all(&dir).unwrap();
-    dir
-}
-
-/// Helper to clean up test directory
-fn cleanup_test_dir(dir: &PathBuf) {
-    let _ = fs::remove_dir_all(dir);
-}
 
 #[test]
 fn test_multi_file_source_tracking() {
-    let test_dir = create_test_dir();
+    let test_dir = create_test_dir("multi_file");
 
     // Create subconfig1.conf with variables
     let subconfig1_path = test_dir.join("subconfig1.conf");
@@ -100,7 +78,7 @@ general {{
 
 #[test]
 fn test_multi_file_mutation_updates_correct_file() {
-    let test_dir = create_test_dir();
+    let test_dir = create_test_dir("multi_file");
 
     // Create subconfig1.conf with a variable
     let subconfig1_path = test_dir.join("subconfig1.conf");
@@ -142,7 +120,7 @@ border_size = 3
     assert_eq!(config.get_int("border_size").unwrap(), 3);
 
     // Mutate the value from subconfig2
-    config.set_int("decoration:rounding", 15);
+    config.set_int("decoration:rounding", 15).unwrap();
 
     // Verify the mutation was tracked
     let modified = config.get_modified_files();
@@ -181,7 +159,7 @@ border_size = 3
 
 #[test]
 fn test_multi_file_variable_mutation() {
-    let test_dir = create_test_dir();
+    let test_dir = create_test_dir("multi_file");
 
     // Create vars.conf with variables
     let vars_path = test_dir.join("vars.conf");
@@ -234,7 +212,7 @@ border_size = $GAPS
 
 #[test]
 fn test_new_key_goes_to_primary_file() {
-    let test_dir = create_test_dir();
+    let test_dir = create_test_dir("multi_file");
 
     // Create subconfig.conf
     let subconfig_path = test_dir.join("subconfig.conf");
@@ -259,7 +237,7 @@ master_key = 456
     config.parse_file(&master_path).unwrap();
 
     // Add a completely new key
-    config.set_int("brand_new_key", 789);
+    config.set_int("brand_new_key", 789).unwrap();
 
     // Save all
     config.save_all().unwrap();
@@ -277,7 +255,7 @@ master_key = 456
 
 #[test]
 fn test_serialize_specific_file() {
-    let test_dir = create_test_dir();
+    let test_dir = create_test_dir("multi_file");
 
     // Create subconfig.conf
     let subconfig_path = test_dir.join("subconfig.conf");
@@ -316,7 +294,7 @@ master_key = 200
 
 #[test]
 fn test_round_trip_with_multi_file_mutation() {
-    let test_dir = create_test_dir();
+    let test_dir = create_test_dir("multi_file");
 
     // Create appearance.conf
     let appearance_path = test_dir.join("appearance.conf");
@@ -349,7 +327,7 @@ border_size = 2
     config1.parse_file(&master_path).unwrap();
 
     // Mutate
-    config1.set_int("decoration:rounding", 25);
+    config1.set_int("decoration:rounding", 25).unwrap();
 
     // Save
     config1.save_all().unwrap();
@@ -370,3 +348,477 @@ border_size = 2
 
     cleanup_test_dir(&test_dir);
 }
+
+#[test]
+fn test_variable_handle_updates_sourcing_file() {
+    let test_dir = create_test_dir("multi_file");
+
+    let vars_path = test_dir.join("vars.conf");
+    fs::write(&vars_path, "$GAPS = 10\n").unwrap();
+
+    let master_path = test_dir.join("master.conf");
+    fs::write(
+        &master_path,
+        format!("source = {}\n\nborder_size = 2\n", vars_path.display()),
+    )
+    .unwrap();
+
+    let mut config = Config::new();
+    config.parse_file(&master_path).unwrap();
+
+    // Setting through the handle behaves like `set_variable`
+    config.get_variable_mut("GAPS").unwrap().set("20").unwrap();
+
+    let saved = config.save_all().unwrap();
+    assert_eq!(saved, vec![vars_path.canonicalize().unwrap()]);
+    assert_eq!(fs::read_to_string(&vars_path).unwrap(), "$GAPS = 20\n");
+
+    cleanup_test_dir(&test_dir);
+}
+
+#[test]
+fn test_serialize_flattened_inlines_sources() {
+    let test_dir = create_test_dir("multi_file");
+    fs::create_dir_all(test_dir.join("conf")).unwrap();
+
+    fs::write(test_dir.join("vars.conf"), "$GAPS = 10\n").unwrap();
+    fs::write(
+        test_dir.join("conf/looks.conf"),
+        "source = ./conf/colors.conf\ndecoration {\n    rounding = 5\n}\n",
+    )
+    .unwrap();
+    fs::write(test_dir.join("conf/colors.conf"), "$accent = 0xff89b4fa\n").unwrap();
+
+    let master_path = test_dir.join("master.conf");
+    fs::write(
+        &master_path,
+        "source = ./vars.conf\ngeneral {\n    gaps_in = $GAPS\n}\nsource = ./conf/looks.conf\n",
+    )
+    .unwrap();
+
+    let mut config = Config::new();
+    config.parse_file(&master_path).unwrap();
+    config.set_int("decoration:rounding", 12).unwrap();
+
+    let flattened = config.serialize_flattened().unwrap();
+    assert_eq!(
+        flattened,
+        "# begin include: ./vars.conf\n\
+         $GAPS = 10\n\
+         # end include: ./vars.conf\n\
+         general {\n    gaps_in = $GAPS\n}\n\
+         # begin include: ./conf/looks.conf\n\
+         # begin include: ./conf/colors.conf\n\
+         $accent = 0xff89b4fa\n\
+         # end include: ./conf/colors.conf\n\
+         decoration {\n    rounding = 12\n}\n\
+         # end include: ./conf/looks.conf\n"
+    );
+
+    // The snapshot parses on its own to the same values
+    let mut snapshot = Config::new();
+    snapshot.parse(&flattened).unwrap();
+    assert!(config.semantically_equal(&snapshot));
+
+    // Files on disk are untouched
+    assert!(
+        fs::read_to_string(&master_path)
+            .unwrap()
+            .contains("source = ./vars.conf")
+    );
+
+    cleanup_test_dir(&test_dir);
+}
+
+#[test]
+fn test_serialize_flattened_needs_a_file() {
+    let mut config = Config::new();
+    config.parse("border_size = 2").unwrap();
+    assert!(config.serialize_flattened().is_err());
+}
+
+#[test]
+fn test_extract_to_file() {
+    let test_dir = create_test_dir("multi_file");
+    let master_path = test_dir.join("hyprland.conf");
+    fs::write(
+        &master_path,
+        "$mod = SUPER\n\
+         general {\n    gaps_in = 5\n}\n\
+         bind = $mod, Q, exec, kitty\n\
+         decoration {\n    rounding = 10\n    blur:enabled = true\n}\n\
+         binde = , XF86AudioRaiseVolume, exec, pamixer -i 5\n\
+         misc {\n    vfr = true\n}\n",
+    )
+    .unwrap();
+
+    let new_config = || {
+        let mut config = Config::new();
+        config.register_handler_fn("bind", |_| Ok(()));
+        config.register_handler_fn("binde", |_| Ok(()));
+        config
+    };
+    let mut config = new_config();
+    config.parse_file(&master_path).unwrap();
+
+    assert_eq!(config.extract_to_file("bind*", "binds.conf").unwrap(), 2);
+    assert_eq!(
+        config
+            .extract_to_file("decoration:*", "looks.conf")
+            .unwrap(),
+        2
+    );
+    assert_eq!(config.extract_to_file("input:*", "input.conf").unwrap(), 0);
+
+    assert_eq!(
+        config
+            .serialize_file(&master_path.canonicalize().unwrap())
+            .unwrap(),
+        "$mod = SUPER\n\
+         general {\n    gaps_in = 5\n}\n\
+         source = binds.conf\n\
+         source = looks.conf\n\
+         misc {\n    vfr = true\n}\n"
+    );
+    assert!(
+        config
+            .get_key_source_file("decoration:rounding")
+            .unwrap()
+            .ends_with("looks.conf")
+    );
+    assert!(
+        config
+            .entry("decoration:blur:enabled")
+            .unwrap()
+            .source_file
+            .as_ref()
+            .unwrap()
+            .ends_with("looks.conf")
+    );
+    assert_eq!(config.get_modified_files().len(), 3);
+
+    config.save_all().unwrap();
+    assert_eq!(
+        fs::read_to_string(test_dir.join("binds.conf")).unwrap(),
+        "bind = $mod, Q, exec, kitty\nbinde = , XF86AudioRaiseVolume, exec, pamixer -i 5\n"
+    );
+    assert_eq!(
+        fs::read_to_string(test_dir.join("looks.conf")).unwrap(),
+        "decoration {\n    rounding = 10\n    blur:enabled = true\n}\n"
+    );
+
+    // The split config parses to the same values
+    let mut reparsed = new_config();
+    reparsed.parse_file(&master_path).unwrap();
+    assert!(config.semantically_equal(&reparsed));
+
+    // Existing files are never overwritten
+    assert!(config.extract_to_file("misc:*", "binds.conf").is_err());
+    assert_eq!(config.get_int("misc:vfr").unwrap(), 1);
+
+    cleanup_test_dir(&test_dir);
+}
+
+#[test]
+fn test_handler_call_order_across_files() {
+    let test_dir = create_test_dir("multi_file");
+    let master_path = test_dir.join("hyprland.conf");
+    fs::write(
+        &master_path,
+        "exec-once = waybar\nbind = SUPER, A, exec, a\nsource = ./keys.conf\nbind = SUPER, D, exec, d\n",
+    )
+    .unwrap();
+    fs::write(
+        test_dir.join("keys.conf"),
+        "bind = SUPER, B, exec, b\nexec-once = dunst\nbind = SUPER, C, exec, c\n",
+    )
+    .unwrap();
+
+    let mut config = Config::new();
+    config.register_handler_fn("bind", |_| Ok(()));
+    config.register_handler_fn("exec-once", |_| Ok(()));
+    config.parse_file(&master_path).unwrap();
+
+    let merged = |config: &Config| -> Vec<String> {
+        config
+            .handler_call_records_in_order(&["bind", "exec-once"])
+            .into_iter()
+            .map(|(_, record)| record.value.clone())
+            .collect()
+    };
+    assert_eq!(
+        merged(&config),
+        [
+            "waybar",
+            "SUPER, A, exec, a",
+            "SUPER, B, exec, b",
+            "dunst",
+            "SUPER, C, exec, c",
+            "SUPER, D, exec, d"
+        ]
+    );
+
+    // Insert after the last bind of keys.conf, ahead of the bind that follows the source line
+    let keys_path = config
+        .get_source_files()
+        .into_iter()
+        .find(|path| path.ends_with("keys.conf"))
+        .unwrap()
+        .to_path_buf();
+    let index = config
+        .add_handler_call_after_file("bind", &keys_path, "SUPER, E, exec, e".to_string())
+        .unwrap();
+    assert_eq!(index, 3);
+    config
+        .insert_handler_call("bind", 0, "SUPER, Z, exec, z".to_string())
+        .unwrap();
+
+    let expected = [
+        "waybar",
+        "SUPER, Z, exec, z",
+        "SUPER, A, exec, a",
+        "SUPER, B, exec, b",
+        "dunst",
+        "SUPER, C, exec, c",
+        "SUPER, E, exec, e",
+        "SUPER, D, exec, d",
+    ];
+    assert_eq!(merged(&config), expected);
+    assert!(
+        config
+            .add_handler_call_after_file(
+                "exec-once",
+                &test_dir.join("missing.conf"),
+                "x".to_string()
+            )
+            .is_err()
+    );
+
+    config.save_all().unwrap();
+    assert_eq!(
+        fs::read_to_string(test_dir.join("keys.conf")).unwrap(),
+        "bind = SUPER, B, exec, b\nexec-once = dunst\nbind = SUPER, C, exec, c\nbind = SUPER, E, exec, e\n"
+    );
+
+    // The saved files parse back in the same order
+    let mut reparsed = Config::new();
+    reparsed.register_handler_fn("bind", |_| Ok(()));
+    reparsed.register_handler_fn("exec-once", |_| Ok(()));
+    reparsed.parse_file(&master_path).unwrap();
+    assert_eq!(merged(&reparsed), expected);
+    assert_eq!(
+        reparsed.get_handler_calls("bind"),
+        config.get_handler_calls("bind")
+    );
+
+    cleanup_test_dir(&test_dir);
+}
+
+#[test]
+fn test_remove_category_scoped_handler_calls_across_files() {
+    let test_dir = create_test_dir("multi_file");
+    let master_path = test_dir.join("hyprland.conf");
+    fs::write(
+        &master_path,
+        "animations {\n    animation = windows, 1, 4, default\n}\nsource = ./anims.conf\n",
+    )
+    .unwrap();
+    fs::write(
+        test_dir.join("anims.conf"),
+        "animations {\n    bezier = ease, 0.1, 0.9, 0.2, 1.0\n    animation = fade, 1, 3, ease\n}\n",
+    )
+    .unwrap();
+
+    let mut config = Config::new();
+    config.register_category_handler_fn("animations", "animation", |_| Ok(()));
+    config.register_category_handler_fn("animations", "bezier", |_| Ok(()));
+    config.parse_file(&master_path).unwrap();
+
+    assert_eq!(
+        config.remove_handler_calls("animations:animation").unwrap(),
+        ["windows, 1, 4, default", "fade, 1, 3, ease"]
+    );
+    config.save_all().unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&master_path).unwrap(),
+        "animations {\n}\nsource = ./anims.conf\n"
+    );
+    assert_eq!(
+        fs::read_to_string(test_dir.join("anims.conf")).unwrap(),
+        "animations {\n    bezier = ease, 0.1, 0.9, 0.2, 1.0\n}\n"
+    );
+
+    let mut reparsed = Config::new();
+    reparsed.register_category_handler_fn("animations", "animation", |_| Ok(()));
+    reparsed.register_category_handler_fn("animations", "bezier", |_| Ok(()));
+    reparsed.parse_file(&master_path).unwrap();
+    assert!(reparsed.get_handler_calls("animations:animation").is_none());
+    assert_eq!(
+        reparsed
+            .get_handler_calls("animations:bezier")
+            .unwrap()
+            .len(),
+        1
+    );
+
+    cleanup_test_dir(&test_dir);
+}
+
+/// Parse a main file sourcing `a.conf` and `b.conf`, change a value in each of them, and
+/// replace `b.conf` with a directory so that writing it fails
+fn config_with_unwritable_source(test_dir: &std::path::Path) -> Config {
+    fs::write(
+        test_dir.join("main.conf"),
+        "gaps = 1\nsource = ./a.conf\nsource = ./b.conf\n",
+    )
+    .unwrap();
+    fs::write(test_dir.join("a.conf"), "rounding = 2\n").unwrap();
+    fs::write(test_dir.join("b.conf"), "border = 3\n").unwrap();
+
+    let mut config = Config::new();
+    config.parse_file(test_dir.join("main.conf")).unwrap();
+    config.set_int("gaps", 10).unwrap();
+    config.set_int("rounding", 20).unwrap();
+    config.set_int("border", 30).unwrap();
+
+    fs::remove_file(test_dir.join("b.conf")).unwrap();
+    fs::create_dir(test_dir.join("b.conf")).unwrap();
+    config
+}
+
+#[test]
+fn test_save_all_reports_partial_failure() {
+    let test_dir = create_test_dir("multi_file");
+    let mut config = config_with_unwritable_source(&test_dir);
+
+    let report = config.save_all_with_report(false).unwrap();
+    assert!(!report.is_ok());
+    let names = |paths: Vec<&PathBuf>| -> Vec<String> {
+        paths
+            .into_iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect()
+    };
+    assert_eq!(
+        names(report.saved.iter().collect()),
+        ["a.conf", "main.conf"]
+    );
+    assert_eq!(
+        names(report.failed.iter().map(|(path, _)| path).collect()),
+        ["b.conf"]
+    );
+    assert!(report.rolled_back.is_empty());
+    assert_eq!(
+        fs::read_to_string(test_dir.join("a.conf")).unwrap(),
+        "rounding = 20\n"
+    );
+
+    assert!(config.save_all().is_err());
+
+    // Only the failed file is still dirty
+    fs::remove_dir(test_dir.join("b.conf")).unwrap();
+    let saved = config.save_all().unwrap();
+    assert_eq!(names(saved.iter().collect()), ["b.conf"]);
+    assert_eq!(
+        fs::read_to_string(test_dir.join("b.conf")).unwrap(),
+        "border = 30\n"
+    );
+
+    cleanup_test_dir(&test_dir);
+}
+
+#[test]
+fn test_save_all_rolls_back_on_failure() {
+    let test_dir = create_test_dir("multi_file");
+    let mut config = config_with_unwritable_source(&test_dir);
+
+    let report = config.save_all_with_report(true).unwrap();
+    assert!(report.saved.is_empty());
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.rolled_back.len(), 2);
+    assert_eq!(
+        fs::read_to_string(test_dir.join("main.conf")).unwrap(),
+        "gaps = 1\nsource = ./a.conf\nsource = ./b.conf\n"
+    );
+    assert_eq!(
+        fs::read_to_string(test_dir.join("a.conf")).unwrap(),
+        "rounding = 2\n"
+    );
+
+    // Rolled-back files stay dirty, so every file is written once the cause is fixed
+    fs::remove_dir(test_dir.join("b.conf")).unwrap();
+    assert_eq!(config.save_all().unwrap().len(), 3);
+    assert_eq!(
+        fs::read_to_string(test_dir.join("main.conf")).unwrap(),
+        "gaps = 10\nsource = ./a.conf\nsource = ./b.conf\n"
+    );
+
+    cleanup_test_dir(&test_dir);
+}
+
+#[test]
+fn test_set_many_across_files() {
+    let test_dir = create_test_dir("multi_file");
+    let extra_path = test_dir.join("extra.conf");
+    fs::write(&extra_path, "decoration {\n    rounding = 5\n}\n").unwrap();
+    let master_path = test_dir.join("master.conf");
+    fs::write(
+        &master_path,
+        format!("source = {}\nborder_size = 3\n", extra_path.display()),
+    )
+    .unwrap();
+
+    let mut config = Config::new();
+    config.parse_file(&master_path).unwrap();
+    config
+        .set_many([
+            ("decoration:rounding", 15),
+            ("border_size", 4),
+            ("gaps_in", 6),
+        ])
+        .unwrap();
+
+    assert_eq!(config.get_modified_files().len(), 2);
+    config.save_all().unwrap();
+    assert_eq!(
+        fs::read_to_string(&extra_path).unwrap(),
+        "decoration {\n    rounding = 15\n}\n"
+    );
+    let master = fs::read_to_string(&master_path).unwrap();
+    assert!(
+        master.ends_with("border_size = 4\ngaps_in = 6\n"),
+        "{}",
+        master
+    );
+
+    cleanup_test_dir(&test_dir);
+}
+
+#[test]
+fn test_variable_entries_name_their_file() {
+    let test_dir = create_test_dir("multi_file");
+
+    let vars_path = test_dir.join("vars.conf");
+    fs::write(&vars_path, "# colors\n$GAPS = 10\n").unwrap();
+
+    let master_path = test_dir.join("master.conf");
+    fs::write(
+        &master_path,
+        format!("$MOD = SUPER\nsource = {}\n", vars_path.display()),
+    )
+    .unwrap();
+
+    let mut config = Config::new();
+    config.parse_file(&master_path).unwrap();
+
+    let entries = config.variable_entries();
+    assert_eq!(entries[0].name, "GAPS");
+    assert_eq!(entries[0].file, Some(vars_path.canonicalize().unwrap()));
+    assert_eq!(entries[0].line, Some(2));
+    assert_eq!(entries[1].name, "MOD");
+    assert_eq!(entries[1].file, Some(master_path.canonicalize().unwrap()));
+    assert_eq!(entries[1].line, Some(1));
+
+    cleanup_test_dir(&test_dir);
+}
@@ -1,6 +1,6 @@
 #![cfg(feature = "mutation")]
 
-use hyprlang::{Config, ConfigValue};
+use hyprlang::Config;
 
 #[test]
 fn test_serialize_synthetic() {
@@ -109,7 +109,7 @@ active_opacity = 0.9
 
     // Mutate some values
     config.set_variable("GAPS".to_string(), "15".to_string());
-    config.set_int("border_size", 5);
+    config.set_int("border_size", 5).unwrap();
 
     // Serialize after mutation
     let mutated = config.serialize();
@@ -141,7 +141,7 @@ gaps = 10
         .unwrap();
 
     // Mutate a value
-    config.set_int("general:border_size", 5);
+    config.set_int("general:border_size", 5).unwrap();
 
     let serialized = config.serialize();
     println!("Serialized with structure:\n{}", serialized);
@@ -167,7 +167,7 @@ key = value1
         .unwrap();
 
     config1.set_variable("VAR".to_string(), "modified".to_string());
-    config1.set_string("key", "value2");
+    config1.set_string("key", "value2").unwrap();
 
     // Serialize
     let serialized = config1.serialize();
@@ -194,7 +194,7 @@ fn test_round_trip_with_all_types() {
             r#"
 $VAR = test
 int_val = 42
-float_val = 3.14
+float_val = 2.75
 string_val = hello world
 bind = SUPER, A, exec, terminal
 device[mouse] {
@@ -215,7 +215,7 @@ device[mouse] {
     // Verify all values preserved
     assert_eq!(config2.get_variable("VAR"), Some("test"));
     assert_eq!(config2.get_int("int_val").unwrap(), 42);
-    assert!((config2.get_float("float_val").unwrap() - 3.14).abs() < 0.001);
+    assert!((config2.get_float("float_val").unwrap() - 2.75).abs() < 0.001);
     assert_eq!(config2.get_string("string_val").unwrap(), "hello world");
 
     // Verify handler calls preserved
@@ -328,7 +328,7 @@ decoration {
         .unwrap();
 
     // Modify a nested value
-    config1.set_int("decoration:blur:size", 5);
+    config1.set_int("decoration:blur:size", 5).unwrap();
 
     // Serialize and re-parse
     let serialized = config1.serialize();
@@ -341,3 +341,225 @@ decoration {
     assert_eq!(config2.get_int("decoration:rounding").unwrap(), 8);
     assert_eq!(config2.get_int("decoration:blur:size").unwrap(), 5);
 }
+
+#[test]
+fn test_serialize_synthetic_declaration_order() {
+    let mut config = Config::new();
+
+    config.set_variable("MOD".to_string(), "SUPER".to_string());
+    config.set_int("general:border_size", 2).unwrap();
+    config
+        .add_handler_call("bind", "$MOD, Q, exec, kitty".to_string())
+        .unwrap();
+    config.set_int("decoration:rounding", 10).unwrap();
+    config.set_string("general:layout", "dwindle").unwrap();
+    config.set_int("decoration:blur:size", 8).unwrap();
+    config
+        .add_handler_call("bind", "$MOD, C, killactive".to_string())
+        .unwrap();
+    config
+        .add_handler_call("animations:bezier", "ease, 0.1, 1, 0.1, 1".to_string())
+        .unwrap();
+    config.set_int("zzz_root", 1).unwrap();
+    config.set_int("aaa_root", 2).unwrap();
+
+    assert_eq!(
+        config.serialize(),
+        r#"$MOD = SUPER
+
+general {
+  border_size = 2
+  layout = dwindle
+}
+
+bind = $MOD, Q, exec, kitty
+
+decoration {
+  rounding = 10
+  blur {
+    size = 8
+  }
+}
+
+bind = $MOD, C, killactive
+
+animations {
+  bezier = ease, 0.1, 1, 0.1, 1
+}
+
+zzz_root = 1
+aaa_root = 2
+"#
+    );
+}
+
+#[test]
+fn test_serialize_synthetic_after_removals() {
+    let mut config = Config::new();
+
+    config.set_int("first", 1).unwrap();
+    config.set_int("second", 2).unwrap();
+    config.add_handler_call("exec", "one".to_string()).unwrap();
+    config.add_handler_call("exec", "two".to_string()).unwrap();
+
+    config.remove("first").unwrap();
+    config.remove_handler_call("exec", 0).unwrap();
+    config.set_int("first", 3).unwrap();
+
+    let serialized = config.serialize();
+    assert_eq!(serialized, "first = 3\nsecond = 2\nexec = two\n");
+
+    // Output parses back to the same values
+    let mut reparsed = Config::new();
+    reparsed.parse(&serialized).unwrap();
+    assert_eq!(reparsed.get_int("first").unwrap(), 3);
+    assert_eq!(reparsed.get_int("second").unwrap(), 2);
+}
+
+#[test]
+fn test_new_values_reuse_tab_indentation() {
+    let mut config = Config::new();
+    config
+        .parse("general {\n\tborder_size = 2\n\tnested {\n\t\tvalue = 1\n\t}\n}\n")
+        .unwrap();
+
+    config.set_int("general:gaps_in", 5).unwrap();
+    config.set_int("general:nested:other", 3).unwrap();
+
+    assert_eq!(
+        config.serialize(),
+        "general {\n\tborder_size = 2\n\tnested {\n\t\tvalue = 1\n\t\tother = 3\n\t}\n\tgaps_in = 5\n}\n"
+    );
+
+    let mut reparsed = Config::new();
+    reparsed.parse(&config.serialize()).unwrap();
+    assert_eq!(reparsed.get_int("general:gaps_in").unwrap(), 5);
+    assert_eq!(reparsed.get_int("general:nested:other").unwrap(), 3);
+}
+
+#[test]
+fn test_new_values_reuse_four_space_indentation() {
+    let mut config = Config::new();
+    config
+        .parse("decoration {\n    rounding = 10\n}\n")
+        .unwrap();
+
+    config.set_int("decoration:blur:size", 8).unwrap();
+    // Categories without a block stay flattened at the root
+    config.set_int("input:sensitivity", 1).unwrap();
+
+    assert_eq!(
+        config.serialize(),
+        "decoration {\n    rounding = 10\n    blur:size = 8\n}\ninput:sensitivity = 1\n"
+    );
+    assert_eq!(config.get_int("decoration:blur:size").unwrap(), 8);
+}
+
+#[test]
+fn test_block_brace_comments_round_trip() {
+    let input = "general { # main settings\n  gaps_in = 5\n  nested {\n    value = 1\n  } # end nested\n} # end general\n";
+
+    let mut config = Config::new();
+    config.parse(input).unwrap();
+    assert_eq!(config.serialize(), input);
+
+    config.set_int("general:gaps_in", 10).unwrap();
+    assert_eq!(
+        config.serialize(),
+        input.replace("gaps_in = 5", "gaps_in = 10")
+    );
+}
+
+#[test]
+fn test_equals_spacing_round_trip() {
+    let input = "tight=1\nleft =2\nwide   =   3\ngeneral {\n  gaps_in= 5\n  empty =\n}\n";
+
+    let mut config = Config::new();
+    config.parse(input).unwrap();
+    assert_eq!(config.serialize(), input);
+
+    config.set_int("tight", 10).unwrap();
+    config.set_int("left", 20).unwrap();
+    config.set_int("wide", 30).unwrap();
+    config.set_int("general:gaps_in", 50).unwrap();
+    config.set_string("general:empty", "full").unwrap();
+    config.set_int("added", 4).unwrap();
+    assert_eq!(
+        config.serialize(),
+        "tight=10\nleft =20\nwide   =   30\ngeneral {\n  gaps_in= 50\n  empty = full\n}\nadded = 4\n"
+    );
+}
+
+#[test]
+fn test_remove_category_scoped_handler_calls() {
+    let mut config = Config::new();
+    config.register_category_handler_fn("animations", "animation", |_| Ok(()));
+    config.register_category_handler_fn("animations", "bezier", |_| Ok(()));
+    config
+        .parse(
+            "animations {\n    bezier = ease, 0.1, 0.9, 0.2, 1.0\n    animation = windows, 1, 4, ease\n    animation = fade, 1, 3, ease\n}\nanimations {\n    animation = border, 1, 2, ease\n}\n",
+        )
+        .unwrap();
+
+    let removed = config.remove_handler_calls("animations:animation").unwrap();
+    assert_eq!(removed.len(), 3);
+    assert_eq!(
+        config.serialize(),
+        "animations {\n    bezier = ease, 0.1, 0.9, 0.2, 1.0\n}\nanimations {\n}\n"
+    );
+    assert_eq!(
+        config.get_handler_calls("animations:bezier").unwrap().len(),
+        1
+    );
+}
+
+#[test]
+fn test_set_many_matches_repeated_set() {
+    let input = "gaps = 5\ngeneral {\n    border_size = 2\n}\n";
+    let values = [
+        ("gaps", hyprlang::ConfigValue::Int(8)),
+        (
+            "general:layout",
+            hyprlang::ConfigValue::String("dwindle".to_string()),
+        ),
+        ("general:border_size", hyprlang::ConfigValue::Int(3)),
+        ("decoration:rounding", hyprlang::ConfigValue::Int(4)),
+        (
+            "general:layout",
+            hyprlang::ConfigValue::String("master # 2".to_string()),
+        ),
+    ];
+
+    let mut one_by_one = Config::new();
+    one_by_one.parse(input).unwrap();
+    for (key, value) in values.clone() {
+        one_by_one.set(key, value).unwrap();
+    }
+
+    let mut batched = Config::new();
+    batched.parse(input).unwrap();
+    batched.set_many(values).unwrap();
+
+    assert_eq!(batched.serialize(), one_by_one.serialize());
+    assert_eq!(batched.get_string("general:layout").unwrap(), "master # 2");
+    assert_eq!(batched.get_int("decoration:rounding").unwrap(), 4);
+
+    // The document index knows the inserted values
+    batched.set_int("decoration:rounding", 6).unwrap();
+    assert_eq!(batched.serialize().matches("rounding").count(), 1);
+}
+
+#[test]
+fn test_set_many_rejects_all_or_nothing() {
+    let mut config = Config::new();
+    config.parse("gaps = 5\n").unwrap();
+    config.add_validator("border", |value| match value.as_int() {
+        Ok(n) if n >= 0 => Ok(()),
+        _ => Err("must not be negative".to_string()),
+    });
+
+    let error = config.set_many([("gaps", 8), ("border", -1)]).unwrap_err();
+    assert!(error.to_string().contains("must not be negative"));
+    assert_eq!(config.get_int("gaps").unwrap(), 5);
+    assert_eq!(config.serialize(), "gaps = 5\n");
+}
@@ -0,0 +1,89 @@
+//! Tests for palette variables, named colors and alpha overrides in color functions.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{CATPPUCCIN_MOCHA, Color, Config};
+
+#[test]
+fn test_rgb_with_hex_digits() {
+    let mut config = Config::new();
+    config.parse("color = rgb(f38ba8)").unwrap();
+    assert_eq!(
+        config.get_color("color").unwrap(),
+        Color::from_rgb(0xf3, 0x8b, 0xa8)
+    );
+}
+
+#[test]
+fn test_palette_variable_with_alpha_override() {
+    let mut config = Config::new();
+    config
+        .parse(
+            r#"
+$red = rgb(f38ba8)
+$blue = 89b4fa
+solid = $red
+faded = rgba($red, 0.5)
+blue_half = rgba($blue, 128)
+"#,
+        )
+        .unwrap();
+
+    assert_eq!(
+        config.get_color("solid").unwrap(),
+        Color::from_rgb(0xf3, 0x8b, 0xa8)
+    );
+    assert_eq!(
+        config.get_color("faded").unwrap(),
+        Color::from_rgba(0xf3, 0x8b, 0xa8, 128)
+    );
+    assert_eq!(
+        config.get_color("blue_half").unwrap(),
+        Color::from_rgba(0x89, 0xb4, 0xfa, 128)
+    );
+}
+
+#[test]
+fn test_alpha_override_of_nested_color_functions() {
+    let mut config = Config::new();
+    config
+        .parse("a = rgba(rgba(10, 20, 30, 255), 0.0)\nb = rgba(rgb(1, 2, 3), 7)")
+        .unwrap();
+
+    assert_eq!(
+        config.get_color("a").unwrap(),
+        Color::from_rgba(10, 20, 30, 0)
+    );
+    assert_eq!(config.get_color("b").unwrap(), Color::from_rgba(1, 2, 3, 7));
+}
+
+#[test]
+fn test_named_colors() {
+    let mut config = Config::new();
+    config.register_named_color("accent", Color::from_rgb(1, 2, 3));
+    config.register_named_colors(CATPPUCCIN_MOCHA.iter().copied());
+    config
+        .parse("a = rgb(accent)\nb = rgba(base, 0.5)\nc = rgba(mauve)\nlayout = base")
+        .unwrap();
+
+    assert_eq!(config.get_color("a").unwrap(), Color::from_rgb(1, 2, 3));
+    assert_eq!(
+        config.get_color("b").unwrap(),
+        Color::from_rgba(0x1e, 0x1e, 0x2e, 128)
+    );
+    assert_eq!(
+        config.get_color("c").unwrap(),
+        Color::from_rgb(0xcb, 0xa6, 0xf7)
+    );
+    // Names are only resolved inside color functions
+    assert_eq!(config.get_string("layout").unwrap(), "base");
+}
+
+#[test]
+fn test_unknown_color_names_stay_strings() {
+    let mut config = Config::new();
+    config.parse("a = rgb(nope)\nb = rgba(nope, 0.5)").unwrap();
+
+    assert!(config.get_color("a").is_err());
+    assert_eq!(config.get_string("b").unwrap(), "rgba(nope, 0.5)");
+}
@@ -0,0 +1,110 @@
+//! Tests for unbalanced and deeply nested category blocks.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, ConfigOptions, HyprlangParser};
+
+fn nested(depth: usize) -> String {
+    let mut input = "a {\n".repeat(depth);
+    input.push_str("x = 1\n");
+    input.push_str(&"}\n".repeat(depth));
+    input
+}
+
+#[test]
+fn test_unclosed_block_names_where_it_opened() {
+    let mut config = Config::new();
+    let error = config
+        .parse("gaps = 5\n\ngeneral {\n    border_size = 2\n")
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Parse error at line 5, column 1: unclosed category block 'general' opened at line 3"
+    );
+}
+
+#[test]
+fn test_unclosed_inner_block() {
+    let mut config = Config::new();
+    let error = config
+        .parse("decoration {\n    blur {\n        size = 3\n    }\n    shadow {\n")
+        .unwrap_err();
+
+    assert!(
+        error
+            .to_string()
+            .ends_with("unclosed category block 'shadow' opened at line 5")
+    );
+}
+
+#[test]
+fn test_stray_closing_brace() {
+    let mut config = Config::new();
+    let error = config
+        .parse("general {\n    gaps_in = 5\n}\n}\n")
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Parse error at line 4, column 1: unexpected '}' without an open category block"
+    );
+}
+
+#[test]
+fn test_braces_in_values_and_comments_are_ignored() {
+    let mut config = Config::new();
+    config
+        .parse(
+            "# general {\n\
+             size = {{2 * 3}}\n\
+             text = a } b\n\
+             general {\n\
+                 gaps_in = 5\n\
+             }\n",
+        )
+        .unwrap();
+
+    assert_eq!(config.get_int("size").unwrap(), 6);
+    assert_eq!(config.get_int("general:gaps_in").unwrap(), 5);
+}
+
+#[test]
+fn test_default_nesting_limit() {
+    let depth = HyprlangParser::DEFAULT_MAX_DEPTH;
+
+    let mut config = Config::new();
+    config.parse(&nested(depth)).unwrap();
+    let key = format!("{}x", "a:".repeat(depth));
+    assert_eq!(config.get_int(&key).unwrap(), 1);
+
+    let mut config = Config::new();
+    let error = config.parse(&nested(depth + 1)).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .ends_with(&format!("nested deeper than {} levels", depth))
+    );
+}
+
+#[test]
+fn test_very_deep_nesting_fails_without_overflowing() {
+    let mut config = Config::new();
+    let error = config.parse(&nested(100_000)).unwrap_err();
+    assert!(error.to_string().contains("nested deeper than"));
+}
+
+#[test]
+fn test_configured_nesting_limit() {
+    let mut config = Config::with_options(ConfigOptions {
+        max_nesting_depth: 2,
+        ..Default::default()
+    });
+
+    config.parse(&nested(2)).unwrap();
+    let error = config.parse(&nested(3)).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Parse error at line 3, column 1: category block 'a' is nested deeper than 2 levels"
+    );
+}
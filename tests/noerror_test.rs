@@ -0,0 +1,123 @@
+//! Tests for `# hyprlang noerror` scoping and `Config::suppressed_errors`.
+
+#![cfg(feature = "config")]
+
+mod common;
+
+use common::{cleanup_test_dir, create_test_dir};
+use hyprlang::{Config, ConfigError};
+use std::fs;
+
+#[test]
+fn test_suppressed_errors_are_recorded() {
+    let mut config = Config::new();
+    config
+        .parse(
+            "# hyprlang noerror true\n\
+             a = {{1 / 0}}\n\
+             b = {{missing + 1}}\n\
+             c = 3\n\
+             # hyprlang noerror false\n\
+             d = 4\n",
+        )
+        .unwrap();
+
+    let suppressed = config.suppressed_errors();
+    assert_eq!(suppressed.len(), 2);
+    assert_eq!(suppressed[0].line, Some(2));
+    assert!(matches!(
+        suppressed[0].error,
+        ConfigError::ExpressionError { .. }
+    ));
+    assert_eq!(suppressed[1].line, Some(3));
+    assert!(suppressed[1].file.is_none());
+    assert_eq!(
+        suppressed[1].to_string(),
+        format!("3: {}", suppressed[1].error)
+    );
+
+    assert!(config.get("a").is_err());
+    assert_eq!(config.get_int("c").unwrap(), 3);
+    assert_eq!(config.get_int("d").unwrap(), 4);
+
+    config.clear_suppressed_errors();
+    assert!(config.suppressed_errors().is_empty());
+}
+
+#[test]
+fn test_errors_fail_after_noerror_false() {
+    let mut config = Config::new();
+    let result = config.parse("# hyprlang noerror true\n# hyprlang noerror false\na = {{1 / 0}}\n");
+
+    assert!(result.is_err());
+    assert!(config.suppressed_errors().is_empty());
+}
+
+#[test]
+fn test_errors_inside_blocks() {
+    let mut config = Config::new();
+    config
+        .parse("# hyprlang noerror true\ngeneral {\n    a = {{1 / 0}}\n    b = 2\n}\nunknown[key] {\n    c = 1\n}\n")
+        .unwrap();
+
+    let suppressed = config.suppressed_errors();
+    assert_eq!(suppressed.len(), 2);
+    assert_eq!(suppressed[0].line, Some(3));
+    // Block errors have no single line
+    assert_eq!(suppressed[1].line, None);
+    assert_eq!(config.get_int("general:b").unwrap(), 2);
+}
+
+#[test]
+fn test_statements_report_noerror() {
+    let mut config = Config::new();
+    config.register_handler_fn("exec", |_| Ok(()));
+    config
+        .parse(
+            "before = 1\nexec = first\n# hyprlang noerror true\ninside = 2\nexec = second\n\
+             # hyprlang noerror false\nafter = 3\n",
+        )
+        .unwrap();
+
+    assert!(!config.entry("before").unwrap().noerror);
+    assert!(config.entry("inside").unwrap().noerror);
+    assert!(!config.entry("after").unwrap().noerror);
+
+    let records = config.get_handler_call_records("exec").unwrap();
+    assert!(!records[0].noerror);
+    assert!(records[1].noerror);
+}
+
+#[test]
+fn test_noerror_in_skipped_if_block_is_ignored() {
+    let mut config = Config::new();
+    let result = config
+        .parse("# hyprlang if MISSING\n# hyprlang noerror true\n# hyprlang endif\na = {{1 / 0}}\n");
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_noerror_is_scoped_to_its_file() {
+    let dir = create_test_dir("noerror");
+    let main_path = dir.join("main.conf");
+    fs::write(
+        dir.join("lenient.conf"),
+        "# hyprlang noerror true\nbroken = {{1 / 0}}\n",
+    )
+    .unwrap();
+    fs::write(&main_path, "source = ./lenient.conf\nafter = {{1 / 0}}\n").unwrap();
+
+    let mut config = Config::new();
+    // The sourced file's `noerror` doesn't carry over into main.conf
+    assert!(config.parse_file(&main_path).is_err());
+
+    let suppressed = config.suppressed_errors();
+    assert_eq!(suppressed.len(), 1);
+    assert_eq!(
+        suppressed[0].file.as_ref(),
+        Some(&dir.join("lenient.conf").canonicalize().unwrap())
+    );
+    assert_eq!(suppressed[0].line, Some(2));
+
+    cleanup_test_dir(&dir);
+}
@@ -1,6 +1,8 @@
 //! Edge case tests for parsing color and Vec2 values.
 
-use hyprlang::Config;
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, Vec2};
 
 // ========== COLOR PARSING EDGE CASES ==========
 
@@ -136,6 +138,39 @@ fn test_vec2_whitespace_variations() {
     assert!(config.get_vec2("size4").is_ok());
 }
 
+#[test]
+fn test_vec2_space_separated() {
+    let mut config = Config::new();
+    config
+        .parse(
+            "size = 800 600
+move = -10  20.5
+expr = monitor_w*0.5 monitor_h*0.5
+triple = 1 2 3
+name = a b",
+        )
+        .unwrap();
+
+    // Pairs stay strings but read as Vec2 when asked
+    assert_eq!(config.get_string("size").unwrap(), "800 600");
+    assert_eq!(config.get_vec2("size").unwrap(), Vec2::new(800.0, 600.0));
+    assert_eq!(config.get_vec2("move").unwrap(), Vec2::new(-10.0, 20.5));
+
+    assert!(config.get_vec2("expr").is_err());
+    assert!(config.get_vec2("triple").is_err());
+    assert!(config.get_vec2("name").is_err());
+}
+
+#[test]
+fn test_vec2_parse() {
+    assert_eq!(Vec2::parse("(1, 2)").unwrap(), Vec2::new(1.0, 2.0));
+    assert_eq!(Vec2::parse("1,2").unwrap(), Vec2::new(1.0, 2.0));
+    assert_eq!(Vec2::parse(" 1e2 2 ").unwrap(), Vec2::new(100.0, 2.0));
+    assert!(Vec2::parse("1").is_err());
+    assert!(Vec2::parse("1, 2, 3").is_err());
+    assert!(Vec2::parse("inf nan").is_err());
+}
+
 #[test]
 fn test_vec2_negative_values() {
     let mut config = Config::new();
@@ -212,3 +247,40 @@ fn test_unicode_in_value() {
     assert!(value.contains("🎉"));
     assert!(value.contains("世界"));
 }
+
+// ========== BLOCK COMMENT EDGE CASES ==========
+
+#[test]
+fn test_comments_after_block_braces() {
+    let mut config = Config::new();
+    config
+        .parse(
+            r#"general { # main settings
+    gaps_in = 5
+    nested {# no space
+        value = 1
+    } # end nested
+} # end general
+after = 2
+"#,
+        )
+        .unwrap();
+
+    assert_eq!(config.get_int("general:gaps_in").unwrap(), 5);
+    assert_eq!(config.get_int("general:nested:value").unwrap(), 1);
+    assert_eq!(config.get_int("after").unwrap(), 2);
+}
+
+#[test]
+fn test_comments_after_special_category_braces() {
+    use hyprlang::SpecialCategoryDescriptor;
+
+    let mut config = Config::new();
+    config.register_special_category(SpecialCategoryDescriptor::keyed("device", "name"));
+    config
+        .parse("device[mouse] { # pointer\n    sensitivity = 0.5\n} # mouse\n")
+        .unwrap();
+
+    let mouse = config.get_special_category("device", "mouse").unwrap();
+    assert_eq!(mouse["sensitivity"].as_float().unwrap(), 0.5);
+}
@@ -0,0 +1,144 @@
+//! Tests for registering handlers, special categories and options through `ConfigProfile`.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{
+    Config, ConfigProfile, ConfigValue, ProfileHandler, ProfileOption, SpecialCategoryDescriptor,
+};
+
+struct Launcher;
+
+impl ConfigProfile for Launcher {
+    fn name(&self) -> &str {
+        "launcher"
+    }
+
+    fn handlers(&self) -> Vec<ProfileHandler> {
+        vec![
+            ProfileHandler::global("entry"),
+            ProfileHandler::global("hotkey").with_flags(),
+            ProfileHandler::in_category("theme", "font"),
+        ]
+    }
+
+    fn special_categories(&self) -> Vec<SpecialCategoryDescriptor> {
+        vec![
+            SpecialCategoryDescriptor::keyed("plugin", "name")
+                .with_defaults([("enabled", ConfigValue::Int(1))]),
+        ]
+    }
+
+    fn options(&self) -> Vec<ProfileOption> {
+        vec![
+            ProfileOption::new("window:width", ConfigValue::Int(600)),
+            ProfileOption::new("window:opacity", ConfigValue::Float(1.0)),
+            ProfileOption::new("window:title", ConfigValue::String("Launcher".to_string())),
+        ]
+    }
+
+    fn configure(&self, config: &mut Config) {
+        config.set("configured", ConfigValue::Int(1)).unwrap();
+    }
+}
+
+fn launcher() -> Config {
+    let mut config = Config::new();
+    config.apply_profile(&Launcher);
+    config
+}
+
+#[test]
+fn test_profile_handlers() {
+    let mut config = launcher();
+    config
+        .parse("entry = firefox\nhotkeyr = SUPER, space\ntheme {\n    font = Iosevka\n}")
+        .unwrap();
+
+    assert_eq!(
+        config.get_handler_calls("entry").unwrap(),
+        &vec!["firefox".to_string()]
+    );
+    assert_eq!(config.get_handler_calls("hotkey").unwrap().len(), 1);
+    assert_eq!(config.get_handler_calls("theme:font").unwrap().len(), 1);
+}
+
+#[test]
+fn test_profile_special_categories() {
+    let mut config = launcher();
+    config
+        .parse("plugin[calc] {\n    prefix = =\n}\nplugin[files] {\n    enabled = 0\n}")
+        .unwrap();
+
+    let calc = config.get_special_category("plugin", "calc").unwrap();
+    assert_eq!(calc.get("enabled").unwrap().as_int().unwrap(), 1);
+    let files = config.get_special_category("plugin", "files").unwrap();
+    assert_eq!(files.get("enabled").unwrap().as_int().unwrap(), 0);
+}
+
+#[test]
+fn test_profile_options() {
+    let mut config = launcher();
+
+    assert_eq!(config.get_int("window:width").unwrap(), 600);
+    assert_eq!(config.get_string("window:title").unwrap(), "Launcher");
+    assert!(!config.entry("window:width").unwrap().set_by_user);
+
+    config
+        .parse("window {\n    width = 800\n    opacity = 1\n    title = Run\n}")
+        .unwrap();
+    assert_eq!(config.get_int("window:width").unwrap(), 800);
+    assert_eq!(config.get_string("window:title").unwrap(), "Run");
+
+    assert!(config.parse("window:width = wide").is_err());
+    assert!(config.parse("window:opacity = opaque").is_err());
+}
+
+#[test]
+fn test_profile_configure_hook() {
+    let config = launcher();
+    assert_eq!(config.get_int("configured").unwrap(), 1);
+}
+
+#[test]
+fn test_register_option_keeps_existing_value() {
+    let mut config = Config::new();
+    config.set("general:gaps_in", ConfigValue::Int(10)).unwrap();
+    config.register_option("general:gaps_in", ConfigValue::Int(5));
+
+    assert_eq!(config.get_int("general:gaps_in").unwrap(), 10);
+    assert!(config.set("general:gaps_in", ConfigValue::Int(3)).is_ok());
+}
+
+#[cfg(feature = "hyprland")]
+#[test]
+fn test_hyprland_profile_matches_wrapper() {
+    use hyprlang::{Hyprland, HyprlandProfile};
+
+    let input = "bind = SUPER, Q, exec, kitty\nanimations {\n    bezier = ease, 0, 0, 1, 1\n}\n\
+                 windowrule {\n    name = float-calc\n    match:class = calc\n}";
+
+    let mut config = Config::new();
+    config.apply_profile(&HyprlandProfile);
+    config.parse(input).unwrap();
+
+    let mut hypr = Hyprland::new();
+    hypr.parse(input).unwrap();
+
+    assert_eq!(
+        config.get_handler_calls("bind"),
+        hypr.config().get_handler_calls("bind")
+    );
+    assert_eq!(
+        config.get_handler_calls("animations:bezier"),
+        hypr.config().get_handler_calls("animations:bezier")
+    );
+    let rule = config
+        .get_special_category("windowrule", "float-calc")
+        .unwrap();
+    assert_eq!(rule.get("enable").unwrap().as_int().unwrap(), 1);
+    assert_eq!(
+        rule.get("match:class").unwrap().as_string().unwrap(),
+        "calc"
+    );
+    assert_eq!(HyprlandProfile.name(), "hyprland");
+}
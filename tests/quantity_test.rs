@@ -0,0 +1,144 @@
+//! Tests for unit-suffixed values on keys opted in with `register_quantity_key`.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, ConfigValue, Unit};
+
+#[test]
+fn test_quantities_are_opt_in() {
+    let mut config = Config::new();
+    config.parse("opacity = 50%\nduration = 500ms").unwrap();
+
+    assert_eq!(config.get_string("opacity").unwrap(), "50%");
+    assert_eq!(config.get_string("duration").unwrap(), "500ms");
+}
+
+#[test]
+fn test_parse_all_units() {
+    let mut config = Config::new();
+    for key in ["a", "b", "c", "d", "e", "f"] {
+        config.register_quantity_key(key);
+    }
+
+    config
+        .parse(
+            r#"
+            a = 50%
+            b = 10px
+            c = 500ms
+            d = 1.5s
+            e = 45deg
+            f = -0.5 rad
+        "#,
+        )
+        .unwrap();
+
+    assert_eq!(config.get_quantity("a").unwrap(), (50.0, Unit::Percent));
+    assert_eq!(config.get_quantity("b").unwrap(), (10.0, Unit::Pixels));
+    assert_eq!(
+        config.get_quantity("c").unwrap(),
+        (500.0, Unit::Milliseconds)
+    );
+    assert_eq!(config.get_quantity("d").unwrap(), (1.5, Unit::Seconds));
+    assert_eq!(config.get_quantity("e").unwrap(), (45.0, Unit::Degrees));
+    assert_eq!(config.get_quantity("f").unwrap(), (-0.5, Unit::Radians));
+}
+
+#[test]
+fn test_quantity_in_category_and_from_variable() {
+    let mut config = Config::new();
+    config.register_quantity_key("decoration:gap");
+
+    config
+        .parse(
+            r#"
+            $GAP = 12px
+            decoration {
+                gap = $GAP
+            }
+        "#,
+        )
+        .unwrap();
+
+    assert_eq!(
+        config.get_quantity("decoration:gap").unwrap(),
+        (12.0, Unit::Pixels)
+    );
+}
+
+#[test]
+fn test_unit_conversion() {
+    let mut config = Config::new();
+    config.register_quantity_key("delay");
+    config.register_quantity_key("angle");
+    config.register_quantity_key("size");
+    config
+        .parse("delay = 2s\nangle = 180deg\nsize = 50%")
+        .unwrap();
+
+    assert_eq!(
+        config.get_quantity_in("delay", Unit::Milliseconds).unwrap(),
+        2000.0
+    );
+    assert!(
+        (config.get_quantity_in("angle", Unit::Radians).unwrap() - std::f64::consts::PI).abs()
+            < 1e-9
+    );
+    assert_eq!(config.get_quantity_in("size", Unit::Percent).unwrap(), 50.0);
+
+    // Percent and pixels can't be converted without a reference size
+    assert!(config.get_quantity_in("size", Unit::Pixels).is_err());
+    assert!(config.get_quantity_in("delay", Unit::Degrees).is_err());
+}
+
+#[test]
+fn test_unitless_number_on_quantity_key() {
+    let mut config = Config::new();
+    config.register_quantity_key("duration");
+    config.parse("duration = 500").unwrap();
+
+    assert_eq!(config.get_int("duration").unwrap(), 500);
+    assert!(config.get_quantity("duration").is_err());
+}
+
+#[test]
+fn test_invalid_unit_is_an_error() {
+    let mut config = Config::new();
+    config.register_quantity_key("duration");
+
+    assert!(config.parse("duration = 5furlongs").is_err());
+    assert!(config.parse("duration = fast").is_err());
+}
+
+#[test]
+fn test_exponent_with_unit() {
+    assert_eq!(
+        Unit::parse_quantity("1.5e3ms"),
+        Some((1500.0, Unit::Milliseconds))
+    );
+    assert_eq!(Unit::parse_quantity("2E-1 s"), Some((0.2, Unit::Seconds)));
+
+    let mut config = Config::new();
+    config.register_quantity_key("duration");
+    config.parse("duration = 1.5e3ms").unwrap();
+    assert_eq!(
+        config.get_quantity("duration").unwrap(),
+        (1500.0, Unit::Milliseconds)
+    );
+}
+
+#[test]
+fn test_quantity_display_round_trip() {
+    let value = ConfigValue::Quantity {
+        value: 1.5,
+        unit: Unit::Seconds,
+    };
+    assert_eq!(value.to_string(), "1.5s");
+    assert_eq!(value.type_name(), "Quantity");
+    assert_eq!(
+        Unit::parse_quantity(&value.to_string()),
+        Some((1.5, Unit::Seconds))
+    );
+    assert_eq!(Unit::parse_quantity("10"), None);
+    assert_eq!(Unit::parse_quantity("px"), None);
+}
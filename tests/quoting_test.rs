@@ -0,0 +1,132 @@
+//! Tests for quoted strings in values and handler calls.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, HandlerContext};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn exec_values(input: &str) -> Vec<String> {
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let mut config = Config::new();
+    let seen_in_handler = seen.clone();
+    config.register_handler_fn("exec-once", move |ctx| {
+        seen_in_handler.borrow_mut().push(ctx.value.clone());
+        Ok(())
+    });
+    config.parse(input).unwrap();
+
+    // Stored calls match what the handler saw
+    assert_eq!(
+        config.get_handler_calls("exec-once").unwrap(),
+        &*seen.borrow()
+    );
+    seen.take()
+}
+
+#[test]
+fn test_handler_values_keep_quotes() {
+    assert_eq!(
+        exec_values(
+            r#"
+exec-once = sh -c "sleep 1 && waybar"
+exec-once = "my app"
+exec-once = "my app" --flag
+exec-once = sh -c 'echo "hi"'
+"#
+        ),
+        vec![
+            r#"sh -c "sleep 1 && waybar""#,
+            r#""my app""#,
+            r#""my app" --flag"#,
+            r#"sh -c 'echo "hi"'"#,
+        ]
+    );
+}
+
+#[test]
+fn test_escaped_quotes() {
+    assert_eq!(
+        exec_values(r#"exec-once = sh -c "echo \"a b\"""#),
+        vec![r#"sh -c "echo \"a b\"""#]
+    );
+
+    let mut config = Config::new();
+    config.parse(r#"title = "say \"hi\"""#).unwrap();
+    assert_eq!(config.get_string("title").unwrap(), r#"say "hi""#);
+}
+
+#[test]
+fn test_multiline_values_keep_quotes() {
+    assert_eq!(
+        exec_values("exec-once = notify-send \"a\" \\\n    \"b c\"\n"),
+        vec!["notify-send \"a\"\n\"b c\""]
+    );
+}
+
+#[test]
+fn test_plain_values_drop_quotes() {
+    let mut config = Config::new();
+    config
+        .parse(
+            r#"
+$name = "My App"
+title = "Hello, World"
+label = $name
+partial = "a" b
+"#,
+        )
+        .unwrap();
+
+    assert_eq!(config.get_string("title").unwrap(), "Hello, World");
+    assert_eq!(config.get_variable("name").unwrap(), "My App");
+    assert_eq!(config.get_string("label").unwrap(), "My App");
+    // Only a value that is one quoted string is unquoted
+    assert_eq!(config.get_string("partial").unwrap(), r#""a" b"#);
+}
+
+#[test]
+fn test_args() {
+    let ctx = HandlerContext::new(
+        "exec".to_string(),
+        r#"notify-send "Hello, world" , 'a, b', "x \", y", plain"#.to_string(),
+    );
+    assert_eq!(
+        ctx.args(),
+        vec![
+            r#"notify-send "Hello, world""#,
+            "'a, b'",
+            r#""x \", y""#,
+            "plain"
+        ]
+    );
+
+    let ctx = HandlerContext::new("bind".to_string(), "SUPER, Q, exec, kitty".to_string());
+    assert_eq!(ctx.args(), vec!["SUPER", "Q", "exec", "kitty"]);
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_quoted_values_round_trip() {
+    let input = "\
+exec-once = sh -c \"sleep 1 && waybar\"
+exec-once = \"my app\" --flag
+title = \"Hello, World\"
+";
+    let mut config = Config::new();
+    config.register_handler_fn("exec-once", |_| Ok(()));
+    config.parse(input).unwrap();
+    assert_eq!(config.serialize(), input);
+
+    config
+        .add_handler_call("exec-once", r#"sh -c "dunst & mako""#.to_string())
+        .unwrap();
+    let mut reparsed = Config::new();
+    reparsed.register_handler_fn("exec-once", |_| Ok(()));
+    reparsed.parse(&config.serialize()).unwrap();
+    assert_eq!(
+        reparsed.get_handler_calls("exec-once").unwrap()[2],
+        r#"sh -c "dunst & mako""#
+    );
+    assert_eq!(reparsed.get_string("title").unwrap(), "Hello, World");
+}
@@ -0,0 +1,64 @@
+//! Tests for configs opened without document tracking.
+
+#![cfg(feature = "mutation")]
+
+use hyprlang::{Config, ConfigOptions};
+use std::fs;
+
+fn read_only() -> Config {
+    Config::with_options(ConfigOptions {
+        track_document: false,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_read_only_config_parses_without_documents() {
+    let dir = std::env::temp_dir().join(format!("hyprlang_read_only_test_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("main.conf"),
+        "# comment\nsource = ./vars.conf\ngeneral {\n    gaps_in = $GAPS\n}\n",
+    )
+    .unwrap();
+    fs::write(dir.join("vars.conf"), "$GAPS = 8\n").unwrap();
+
+    let mut config = read_only();
+    config.parse_file(dir.join("main.conf")).unwrap();
+
+    assert_eq!(config.get_int("general:gaps_in").unwrap(), 8);
+    assert!(config.document().is_none());
+    assert!(config.get_source_files().is_empty());
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_read_only_config_cannot_be_saved() {
+    let mut config = read_only();
+    config.parse("gaps_in = 5\n").unwrap();
+    config.set_int("gaps_in", 6).unwrap();
+
+    let path = std::env::temp_dir().join(format!(
+        "hyprlang_read_only_save_{}.conf",
+        std::process::id()
+    ));
+    for result in [config.save(), config.save_as(&path)] {
+        let message = result.unwrap_err().to_string();
+        assert!(message.contains("track_document"), "{}", message);
+    }
+    assert!(config.save_all().is_err());
+    assert!(config.serialize_flattened().is_err());
+    assert!(!path.exists());
+
+    // Serializing falls back to synthetic output
+    assert_eq!(config.serialize(), "gaps_in = 6\n");
+}
+
+#[test]
+fn test_documents_are_tracked_by_default() {
+    let mut config = Config::new();
+    config.parse("general {\ngaps_in=5\n}\n").unwrap();
+    assert!(config.document().is_some());
+    assert_eq!(config.serialize(), "general {\ngaps_in=5\n}\n");
+}
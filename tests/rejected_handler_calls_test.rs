@@ -0,0 +1,163 @@
+//! Tests for handlers rejecting calls so they are not stored.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, ConfigError, ConfigOptions, FunctionHandler, HandlerContext, Severity};
+
+/// A `bind` handler that rejects calls without a key and dispatcher
+fn reject_short_binds(ctx: &HandlerContext) -> Result<(), ConfigError> {
+    if ctx.args().len() < 3 {
+        return Err(ConfigError::rejected("expected MODS, key, dispatcher"));
+    }
+    Ok(())
+}
+
+fn bind_config() -> Config {
+    let mut config = Config::new();
+    config.register_handler(
+        "bind",
+        FunctionHandler::with_flags("bind", reject_short_binds),
+    );
+    config
+}
+
+#[test]
+fn test_rejected_calls_are_not_stored() {
+    let mut config = bind_config();
+    config
+        .parse("bind = SUPER, Q, exec, kitty\nbind = SUPER\nbindl = , XF86AudioMute\nbind = ALT, F4, killactive\n")
+        .unwrap();
+
+    assert_eq!(
+        config.get_handler_calls("bind").unwrap(),
+        &["SUPER, Q, exec, kitty", "ALT, F4, killactive"]
+    );
+    let lines: Vec<_> = config
+        .get_handler_call_records("bind")
+        .unwrap()
+        .iter()
+        .map(|record| record.line)
+        .collect();
+    assert_eq!(lines, [Some(1), Some(4)]);
+
+    let rejected = config.rejected_handler_calls();
+    assert_eq!(rejected.len(), 2);
+    assert_eq!(rejected[0].handler, "bind");
+    assert_eq!(rejected[0].value, "SUPER");
+    assert_eq!(rejected[0].reason, "expected MODS, key, dispatcher");
+    assert_eq!(rejected[0].line, 2);
+    assert_eq!(rejected[1].value, ", XF86AudioMute");
+    assert_eq!(
+        rejected[1].to_string(),
+        "3: 'bind' call rejected: expected MODS, key, dispatcher"
+    );
+}
+
+#[test]
+fn test_other_handler_errors_still_store_the_call() {
+    let mut config = Config::new();
+    config.register_handler_fn("exec", |_| Err(ConfigError::custom("spawn failed")));
+
+    assert!(config.parse("exec = kitty").is_err());
+    assert_eq!(config.get_handler_calls("exec").unwrap(), &["kitty"]);
+    assert!(config.rejected_handler_calls().is_empty());
+}
+
+#[test]
+fn test_rejected_category_handler_call() {
+    let mut config = Config::new();
+    config.register_category_handler_fn("plugin", "load", |ctx| {
+        if !ctx.value.ends_with(".so") {
+            return Err(ConfigError::rejected("not a shared object"));
+        }
+        Ok(())
+    });
+    config
+        .parse("plugin {\n    load = a.so\n    load = readme.md\n}\n")
+        .unwrap();
+
+    assert_eq!(config.get_handler_calls("plugin:load").unwrap(), &["a.so"]);
+    assert_eq!(config.rejected_handler_calls()[0].handler, "plugin:load");
+    assert_eq!(config.rejected_handler_calls()[0].line, 3);
+}
+
+#[test]
+fn test_rejected_calls_accumulate_until_cleared() {
+    let mut config = bind_config();
+    config.parse("bind = SUPER").unwrap();
+    config.parse("bind = ALT").unwrap();
+    assert_eq!(config.rejected_handler_calls().len(), 2);
+
+    config.clear_rejected_handler_calls();
+    assert!(config.rejected_handler_calls().is_empty());
+}
+
+#[test]
+fn test_rejections_are_not_suppressed_errors() {
+    let mut config = bind_config();
+    config
+        .parse("# hyprlang noerror true\nbind = SUPER\n# hyprlang noerror false\n")
+        .unwrap();
+
+    assert!(config.suppressed_errors().is_empty());
+    assert_eq!(config.rejected_handler_calls().len(), 1);
+}
+
+#[test]
+fn test_check_reports_rejections_as_warnings() {
+    let mut config = Config::with_options(ConfigOptions {
+        dry_run_handlers: true,
+        ..Default::default()
+    });
+    config.register_handler(
+        "bind",
+        FunctionHandler::with_flags("bind", reject_short_binds),
+    );
+
+    let diagnostics = config.check("bind = SUPER, Q, exec, kitty\nbind = SUPER\n");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert_eq!(diagnostics[0].line, Some(2));
+    assert_eq!(
+        diagnostics[0].message,
+        "'bind' call rejected: expected MODS, key, dispatcher"
+    );
+
+    // Checking leaves the config itself untouched
+    assert!(config.rejected_handler_calls().is_empty());
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_edits_by_index_skip_rejected_lines() {
+    let mut config = bind_config();
+    config
+        .parse("bind = SUPER\nbind = ALT, F4, killactive\nbind = SUPER, Q, exec, kitty\n")
+        .unwrap();
+
+    config
+        .update_handler_call("bind", 0, "ALT, F5, killactive".to_string())
+        .unwrap();
+    assert_eq!(
+        config.serialize(),
+        "bind = SUPER\nbind = ALT, F5, killactive\nbind = SUPER, Q, exec, kitty\n"
+    );
+
+    config
+        .insert_handler_call("bind", 0, "SUPER, F, fullscreen".to_string())
+        .unwrap();
+    assert_eq!(
+        config.serialize(),
+        "bind = SUPER\nbind = SUPER, F, fullscreen\nbind = ALT, F5, killactive\n\
+         bind = SUPER, Q, exec, kitty\n"
+    );
+
+    assert_eq!(
+        config.remove_handler_call("bind", 2).unwrap(),
+        "SUPER, Q, exec, kitty"
+    );
+    assert_eq!(
+        config.serialize(),
+        "bind = SUPER\nbind = SUPER, F, fullscreen\nbind = ALT, F5, killactive\n"
+    );
+}
@@ -0,0 +1,102 @@
+//! Tests for required properties of special categories.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, ConfigError, ConfigOptions, ConfigValue, SpecialCategoryDescriptor};
+use std::fs;
+
+fn windowrule_config(options: ConfigOptions) -> Config {
+    let mut config = Config::with_options(options);
+    config.register_special_category(
+        SpecialCategoryDescriptor::keyed("windowrule", "name")
+            .with_default("match:title", ConfigValue::String(String::new()))
+            .with_required("match:*")
+            .with_required("effect"),
+    );
+    config
+}
+
+#[test]
+fn test_required_properties_present() {
+    let mut config = windowrule_config(ConfigOptions::default());
+    config
+        .parse("windowrule[kitty] {\n  match:class = kitty\n  effect = float\n}")
+        .unwrap();
+
+    assert!(config.get_special_category("windowrule", "kitty").is_ok());
+}
+
+#[test]
+fn test_missing_required_properties() {
+    let mut config = windowrule_config(ConfigOptions::default());
+    // Defaults do not count as set
+    let error = config
+        .parse("gaps = 5\n\nwindowrule[any] {\n  float = true\n}")
+        .unwrap_err();
+
+    match &error {
+        ConfigError::MissingProperties {
+            category,
+            key,
+            properties,
+            file,
+            line,
+        } => {
+            assert_eq!(category, "windowrule");
+            assert_eq!(key.as_deref(), Some("any"));
+            assert_eq!(properties, &["match:*", "effect"]);
+            assert_eq!(*file, None);
+            assert_eq!(*line, 3);
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+    assert_eq!(
+        error.to_string(),
+        "Special category 'windowrule[any]' at line 3 is missing required properties: match:*, effect"
+    );
+}
+
+#[test]
+fn test_missing_required_in_file() {
+    let dir = std::env::temp_dir().join(format!("hyprlang_required_test_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("rules.conf");
+    fs::write(&path, "windowrule {\n  name = any\n  effect = float\n}\n").unwrap();
+
+    let mut config = windowrule_config(ConfigOptions::default());
+    let error = config.parse_file(&path).unwrap_err();
+    assert!(matches!(
+        &error,
+        ConfigError::MissingProperties { file: Some(file), line: 1, .. } if file.ends_with("rules.conf")
+    ));
+    assert!(error.to_string().contains("rules.conf:1 is missing"));
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_missing_required_collected_and_suppressed() {
+    let mut config = windowrule_config(ConfigOptions {
+        throw_all_errors: true,
+        ..Default::default()
+    });
+    let error = config
+        .parse("windowrule[a] {\n  effect = float\n}\nwindowrule[b] {\n  match:class = b\n}")
+        .unwrap_err();
+    let ConfigError::Multiple { errors } = error else {
+        panic!("expected collected errors");
+    };
+    assert_eq!(errors.len(), 2);
+
+    let mut config = windowrule_config(ConfigOptions::default());
+    config
+        .parse("# hyprlang noerror true\nwindowrule[a] {\n  effect = float\n}")
+        .unwrap();
+    let suppressed = config.suppressed_errors();
+    assert_eq!(suppressed.len(), 1);
+    // The block's line is part of the error
+    assert!(matches!(
+        suppressed[0].error,
+        ConfigError::MissingProperties { line: 2, .. }
+    ));
+}
@@ -0,0 +1,68 @@
+//! Tests for variables provided by the application before parsing.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, VariableSource};
+
+#[test]
+fn test_seeded_variables_expand() {
+    let mut config = Config::new();
+    config.seed_variable("HOSTNAME", "laptop");
+    config.seed_variable("MONITOR_COUNT", "2");
+    config
+        .parse(
+            "name = $HOSTNAME\n\
+             columns = {{MONITOR_COUNT * 3}}\n\
+             # hyprlang if MONITOR_COUNT\n\
+             multi = true\n\
+             # hyprlang endif\n",
+        )
+        .unwrap();
+
+    assert_eq!(config.get_string("name").unwrap(), "laptop");
+    assert_eq!(config.get_int("columns").unwrap(), 6);
+    assert_eq!(config.get_int("multi").unwrap(), 1);
+    assert_eq!(config.get_variable("HOSTNAME"), Some("laptop"));
+
+    let trace = config.explain("name").unwrap();
+    assert_eq!(trace.substitutions[0].source, VariableSource::Seeded);
+}
+
+#[test]
+fn test_config_overrides_seeded_variable() {
+    let mut config = Config::new();
+    config.seed_variable("GAPS", "5");
+    config.parse("$GAPS = 10\ngaps = {{GAPS + 1}}\n").unwrap();
+    assert_eq!(config.get_int("gaps").unwrap(), 11);
+
+    // Seeding a variable the config defines does nothing
+    config.seed_variable("GAPS", "20");
+    assert_eq!(config.get_variable("GAPS"), Some("10"));
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_seeded_variables_are_not_serialized() {
+    let mut config = Config::new();
+    config.seed_variable("HOSTNAME", "laptop");
+    config.parse("name = $HOSTNAME\n").unwrap();
+    assert_eq!(config.serialize(), "name = $HOSTNAME\n");
+
+    let mut built = Config::new();
+    built.seed_variable("HOSTNAME", "laptop");
+    built.set_string("name", "x").unwrap();
+    assert_eq!(built.serialize(), "name = x\n");
+
+    // Setting it in code makes it part of the config
+    built.set_variable("HOSTNAME".to_string(), "desktop".to_string());
+    assert!(built.serialize().contains("$HOSTNAME = desktop"));
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_overridden_seed_is_serialized() {
+    let mut built = Config::new();
+    built.seed_variable("MOD", "ALT");
+    built.parse("$MOD = SUPER\n").unwrap();
+    assert!(built.serialize().contains("$MOD = SUPER"));
+}
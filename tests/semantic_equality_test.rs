@@ -0,0 +1,82 @@
+//! Tests for `Config::semantically_equal`.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, SpecialCategoryDescriptor};
+
+fn parse(input: &str) -> Config {
+    let mut config = Config::new();
+    config.register_handler_fn("bind", |_| Ok(()));
+    config.register_special_category(SpecialCategoryDescriptor::keyed("device", "name"));
+    config.parse(input).unwrap();
+    config
+}
+
+#[test]
+fn test_formatting_is_ignored() {
+    let a = parse(
+        r#"
+# Appearance
+$MOD = SUPER
+general {
+    gaps_in = 5
+    col.active_border = rgba(ff0000ff)
+}
+bind = $MOD, Q, exec, kitty
+device[mouse] {
+    sensitivity = 0.5
+}
+"#,
+    );
+    let b = parse(
+        "$MOD=SUPER\ngeneral:gaps_in=5\ngeneral:col.active_border = 0xff0000ff\n\
+         bind=SUPER, Q, exec, kitty\ndevice[mouse] {\n  sensitivity = 0.5\n}\n",
+    );
+
+    assert!(a.semantically_equal(&b));
+    assert!(b.semantically_equal(&a));
+}
+
+#[test]
+fn test_value_differences_are_detected() {
+    let a = parse("gaps_in = 5\nborder_size = 2");
+
+    assert!(!a.semantically_equal(&parse("gaps_in = 6\nborder_size = 2")));
+    assert!(!a.semantically_equal(&parse("gaps_in = 5")));
+    assert!(!a.semantically_equal(&parse("gaps_in = 5\nborder_size = 2\nextra = 1")));
+    // Same text, different type
+    assert!(!parse("value = 5").semantically_equal(&parse("value = five")));
+}
+
+#[test]
+fn test_variable_and_handler_differences_are_detected() {
+    let a = parse("$A = 1\nbind = SUPER, Q, exec, kitty\nbind = SUPER, W, killactive");
+
+    assert!(!a.semantically_equal(&parse(
+        "$A = 2\nbind = SUPER, Q, exec, kitty\nbind = SUPER, W, killactive"
+    )));
+    // Handler call order matters
+    assert!(!a.semantically_equal(&parse(
+        "$A = 1\nbind = SUPER, W, killactive\nbind = SUPER, Q, exec, kitty"
+    )));
+}
+
+#[test]
+fn test_special_category_differences_are_detected() {
+    let a = parse("device[mouse] {\n  sensitivity = 0.5\n}");
+
+    assert!(!a.semantically_equal(&parse("device[mouse] {\n  sensitivity = 1.0\n}")));
+    assert!(!a.semantically_equal(&parse("device[touchpad] {\n  sensitivity = 0.5\n}")));
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_serialized_output_round_trips() {
+    let mut config = parse("general {\n  gaps_in = 5\n}\nbind = SUPER, Q, exec, kitty\n");
+    let reparsed = parse(&config.serialize());
+    assert!(config.semantically_equal(&reparsed));
+
+    config.set_int("general:gaps_in", 10).unwrap();
+    assert!(!config.semantically_equal(&reparsed));
+    assert!(config.semantically_equal(&parse(&config.serialize())));
+}
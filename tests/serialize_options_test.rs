@@ -0,0 +1,348 @@
+//! Tests for `SerializeOptions` formatting of serialized output.
+
+#![cfg(feature = "mutation")]
+
+use hyprlang::{BraceStyle, Config, IndentStyle, SectionLayout, SerializeOptions};
+
+const INPUT: &str = r#"$MOD = SUPER
+border_size = 2
+general {
+  layout = dwindle
+  gaps_in = 5
+  col.active_border = 0xffffffff
+}
+bind = $MOD, Q, exec, kitty
+decoration {
+  rounding = 10
+  blur {
+    size = 8
+  }
+}
+"#;
+
+fn parsed() -> Config {
+    let mut config = Config::new();
+    config.register_handler_fn("bind", |_| Ok(()));
+    config.parse(INPUT).unwrap();
+    config
+}
+
+#[test]
+fn test_default_options_match_serialize() {
+    let config = parsed();
+    assert_eq!(
+        config.serialize_with_options(&SerializeOptions::default()),
+        config.serialize()
+    );
+    assert_eq!(config.serialize(), INPUT);
+}
+
+#[test]
+fn test_indent_styles() {
+    let config = parsed();
+
+    let tabs = config.serialize_with_options(&SerializeOptions {
+        indent: IndentStyle::Tabs,
+        ..Default::default()
+    });
+    assert!(tabs.contains("general {\n\tlayout = dwindle\n"));
+    assert!(tabs.contains("\tblur {\n\t\tsize = 8\n\t}\n"));
+
+    let four = config.serialize_with_options(&SerializeOptions {
+        indent: IndentStyle::Spaces(4),
+        ..Default::default()
+    });
+    assert!(four.contains("    blur {\n        size = 8\n    }\n"));
+
+    // With `keep_indent`, the two-space indentation of the parsed file wins
+    let kept = config.serialize_with_options(&SerializeOptions {
+        indent: IndentStyle::Tabs,
+        keep_indent: true,
+        ..Default::default()
+    });
+    assert_eq!(kept, INPUT);
+}
+
+#[test]
+fn test_indent_applies_to_detected_indentation() {
+    let mut config = Config::new();
+    config
+        .parse("general {\n    layout = dwindle\n}\n")
+        .unwrap();
+
+    // `serialize` keeps the file's four spaces, while an explicit `indent` replaces them
+    assert_eq!(config.serialize(), "general {\n    layout = dwindle\n}\n");
+    assert_eq!(
+        config.serialize_with_options(&SerializeOptions {
+            indent: IndentStyle::Tabs,
+            ..Default::default()
+        }),
+        "general {\n\tlayout = dwindle\n}\n"
+    );
+    assert_eq!(
+        config.serialize_with_options(&SerializeOptions::default()),
+        "general {\n  layout = dwindle\n}\n"
+    );
+}
+
+#[test]
+fn test_align_equals() {
+    let config = parsed();
+    let output = config.serialize_with_options(&SerializeOptions {
+        align_equals: true,
+        ..Default::default()
+    });
+
+    assert!(output.contains(
+        "  layout            = dwindle\n  gaps_in           = 5\n  col.active_border = 0xffffffff\n"
+    ));
+    // Root-level runs are aligned independently of block contents
+    assert!(output.starts_with("$MOD        = SUPER\nborder_size = 2\n"));
+}
+
+#[test]
+fn test_blank_line_between_categories() {
+    let config = parsed();
+    let output = config.serialize_with_options(&SerializeOptions {
+        blank_line_between_categories: true,
+        ..Default::default()
+    });
+
+    assert!(output.contains("border_size = 2\n\ngeneral {"));
+    assert!(output.contains("}\n\nbind = $MOD, Q, exec, kitty\n\ndecoration {"));
+    // Nested blocks are separated too, but never doubled up
+    assert!(!output.contains("\n\n\n"));
+}
+
+#[test]
+fn test_sort_keys() {
+    let config = parsed();
+    let output = config.serialize_with_options(&SerializeOptions {
+        sort_keys: true,
+        ..Default::default()
+    });
+
+    assert!(output.contains(
+        "general {\n  col.active_border = 0xffffffff\n  gaps_in = 5\n  layout = dwindle\n}"
+    ));
+
+    let mut reparsed = Config::new();
+    reparsed.register_handler_fn("bind", |_| Ok(()));
+    reparsed.parse(&output).unwrap();
+    assert_eq!(reparsed.get_int("general:gaps_in").unwrap(), 5);
+    assert_eq!(
+        reparsed.get_handler_calls("bind").unwrap(),
+        &vec!["SUPER, Q, exec, kitty".to_string()]
+    );
+}
+
+#[test]
+fn test_sort_keys_keeps_handler_calls_in_place() {
+    let mut config = Config::new();
+    config.register_handler_fn("bind", |_| Ok(()));
+    config.register_category_handler_fn("plugin", "load", |_| Ok(()));
+    config
+        .parse(
+            "zeta = 1\nbind = SUPER, Q, exec, kitty\nalpha = 2\nbind = SUPER, C, killactive\n\
+             plugin {\n  load = b.so\n  a = 1\n  load = a.so\n}\n",
+        )
+        .unwrap();
+
+    let output = config.serialize_with_options(&SerializeOptions {
+        sort_keys: true,
+        ..Default::default()
+    });
+    assert_eq!(output, config.serialize());
+}
+
+#[test]
+fn test_options_apply_to_synthetic_output() {
+    let mut config = Config::new();
+    config.set_int("general:border_size", 2).unwrap();
+    config.set_int("general:gaps_in", 5).unwrap();
+
+    let output = config.serialize_with_options(&SerializeOptions {
+        indent: IndentStyle::Tabs,
+        align_equals: true,
+        ..Default::default()
+    });
+    assert_eq!(
+        output,
+        "general {\n\tborder_size = 2\n\tgaps_in     = 5\n}\n"
+    );
+}
+
+#[test]
+fn test_sections_group_synthetic_handler_calls() {
+    let mut config = Config::new();
+    config.set_variable("MOD".to_string(), "SUPER".to_string());
+    config
+        .add_handler_call("bind", "SUPER, Q, exec, kitty".to_string())
+        .unwrap();
+    config
+        .add_handler_call("windowrule", "float, class:^(pavucontrol)$".to_string())
+        .unwrap();
+    config.set_int("general:gaps_in", 5).unwrap();
+    config
+        .add_handler_call("bindm", "SUPER, mouse:272, movewindow".to_string())
+        .unwrap();
+    config
+        .add_handler_call("env", "XCURSOR_SIZE,24".to_string())
+        .unwrap();
+    config
+        .add_handler_call("monitor", ",preferred,auto,1".to_string())
+        .unwrap();
+    config
+        .add_handler_call("animations:bezier", "ease, 0.2, 1, 0.3, 1".to_string())
+        .unwrap();
+
+    let output = config.serialize_with_options(&SerializeOptions {
+        sections: Some(SectionLayout::hyprland()),
+        ..Default::default()
+    });
+    assert_eq!(
+        output,
+        "$MOD = SUPER\n\
+         \n\
+         general {\n  gaps_in = 5\n}\n\
+         \n\
+         monitor = ,preferred,auto,1\n\
+         \n\
+         animations {\n  bezier = ease, 0.2, 1, 0.3, 1\n}\n\
+         \n\
+         # Environment\n\
+         env = XCURSOR_SIZE,24\n\
+         \n\
+         # Keybindings\n\
+         bind = SUPER, Q, exec, kitty\n\
+         bindm = SUPER, mouse:272, movewindow\n\
+         \n\
+         # Window rules\n\
+         windowrule = float, class:^(pavucontrol)$\n"
+    );
+
+    // The grouped output parses back to the same config
+    let mut reparsed = Config::new();
+    for keyword in ["bind", "bindm", "windowrule", "env", "monitor"] {
+        reparsed.register_handler_fn(keyword, |_| Ok(()));
+    }
+    reparsed.register_category_handler_fn("animations", "bezier", |_| Ok(()));
+    reparsed.parse(&output).unwrap();
+    assert!(config.semantically_equal(&reparsed));
+}
+
+#[test]
+fn test_section_layout_patterns() {
+    let layout = SectionLayout::new()
+        .section("Keys", ["bind*"])
+        .section("Binds", ["bind"])
+        .section("Rules", ["windowrule"]);
+
+    assert_eq!(layout.section_of("bind"), Some(0));
+    assert_eq!(layout.section_of("binde"), Some(0));
+    assert_eq!(layout.section_of("windowrule"), Some(2));
+    assert_eq!(layout.section_of("windowrulev2"), None);
+    assert_eq!(
+        layout.titles().collect::<Vec<_>>(),
+        vec!["Keys", "Binds", "Rules"]
+    );
+}
+
+#[test]
+fn test_sections_do_not_reorder_parsed_documents() {
+    let config = parsed();
+    let output = config.serialize_with_options(&SerializeOptions {
+        sections: Some(SectionLayout::hyprland()),
+        ..Default::default()
+    });
+    assert_eq!(output, INPUT);
+}
+
+#[test]
+fn test_save_as_with_options() {
+    let config = parsed();
+    let path = std::env::temp_dir().join(format!(
+        "hyprlang_serialize_options_{}.conf",
+        std::process::id()
+    ));
+
+    config
+        .save_as_with_options(
+            &path,
+            &SerializeOptions {
+                indent: IndentStyle::Tabs,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(contents.contains("\tlayout = dwindle"));
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_block_brace_styles() {
+    let mut config = Config::new();
+    config.register_special_category(hyprlang::SpecialCategoryDescriptor::keyed("device", "name"));
+    config
+        .parse("general{\n  gaps_in = 5\n}\ndecoration   { # looks\n  rounding = 4\n}\ndevice[mouse]{\n  sensitivity = 0.5\n}\n")
+        .unwrap();
+
+    let doc = config.document().unwrap();
+    assert_eq!(doc.block_raw_open("general"), Some("general{"));
+    assert_eq!(
+        doc.block_raw_open("decoration"),
+        Some("decoration   { # looks")
+    );
+    assert_eq!(doc.block_raw_open("missing"), None);
+    assert_eq!(doc.block_brace_style("general"), Some(BraceStyle::Attached));
+    assert_eq!(
+        doc.block_brace_style("decoration"),
+        Some(BraceStyle::Spaced)
+    );
+    assert_eq!(
+        doc.block_brace_style("device[mouse]"),
+        Some(BraceStyle::Attached)
+    );
+    assert_eq!(doc.detected_brace_style(), None);
+
+    let spaced = config.serialize_with_options(&SerializeOptions {
+        brace_style: Some(BraceStyle::Spaced),
+        ..Default::default()
+    });
+    assert_eq!(
+        spaced,
+        "general {\n  gaps_in = 5\n}\ndecoration { # looks\n  rounding = 4\n}\ndevice[mouse] {\n  sensitivity = 0.5\n}\n"
+    );
+
+    let attached = config.serialize_with_options(&SerializeOptions {
+        brace_style: Some(BraceStyle::Attached),
+        ..Default::default()
+    });
+    assert!(attached.starts_with("general{\n"));
+    assert!(attached.contains("decoration{ # looks\n"));
+
+    // Without the option, blocks keep their opening lines as written
+    assert_eq!(
+        config.serialize(),
+        "general{\n  gaps_in = 5\n}\ndecoration   { # looks\n  rounding = 4\n}\ndevice[mouse]{\n  sensitivity = 0.5\n}\n"
+    );
+}
+
+#[test]
+fn test_brace_style_keeps_inline_empty_blocks() {
+    let mut config = Config::new();
+    config.parse("general{ }\nborder_size = 2\n").unwrap();
+    assert_eq!(
+        config.document().unwrap().detected_brace_style(),
+        Some(BraceStyle::Attached)
+    );
+
+    let output = config.serialize_with_options(&SerializeOptions {
+        brace_style: Some(BraceStyle::Spaced),
+        ..Default::default()
+    });
+    assert_eq!(output, "general { }\nborder_size = 2\n");
+}
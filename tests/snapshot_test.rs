@@ -0,0 +1,130 @@
+//! Tests for `Config::snapshot` and `SharedSnapshot`.
+
+#![cfg(feature = "snapshot")]
+
+use hyprlang::{
+    Color, Config, ConfigSnapshot, SharedSnapshot, SnapshotValue, SpecialCategoryDescriptor,
+    ValueKind,
+};
+use std::sync::Arc;
+use std::thread;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+fn config(input: &str) -> Config {
+    let mut config = Config::new();
+    config.register_handler_fn("bind", |_| Ok(()));
+    config.register_special_category(SpecialCategoryDescriptor::keyed("windowrule", "name"));
+    config.parse(input).unwrap();
+    config
+}
+
+const INPUT: &str = "\
+$term = kitty
+general {
+    gaps_in = 5
+    col.active_border = rgba(33ccffee)
+    size = 800 600
+}
+bind = SUPER, Q, exec, $term
+windowrule[float-kitty] {
+    match:class = kitty
+    float = true
+}
+";
+
+#[test]
+fn test_snapshot_is_send_and_sync() {
+    assert_send_sync::<ConfigSnapshot>();
+    assert_send_sync::<SharedSnapshot>();
+}
+
+#[test]
+fn test_snapshot_contents() {
+    let config = config(INPUT);
+    let snapshot = config.snapshot();
+
+    assert_eq!(snapshot.get_int("general:gaps_in").unwrap(), 5);
+    assert_eq!(
+        snapshot.get_color("general:col.active_border").unwrap(),
+        Color::from_rgba(0x33, 0xcc, 0xff, 0xee)
+    );
+    assert_eq!(snapshot.get_vec2("general:size").unwrap().x, 800.0);
+    assert_eq!(
+        snapshot.get("general:gaps_in").unwrap().kind(),
+        ValueKind::Int
+    );
+    assert!(snapshot.get_string("general:gaps_in").is_err());
+    assert!(snapshot.get("missing").is_err());
+
+    assert_eq!(snapshot.get_variable("term"), Some("kitty"));
+    assert_eq!(
+        snapshot.get_handler_calls("bind").unwrap(),
+        ["SUPER, Q, exec, kitty"]
+    );
+
+    assert_eq!(
+        snapshot.list_special_category_keys("windowrule"),
+        vec!["float-kitty"]
+    );
+    let rule = snapshot
+        .get_special_category("windowrule", "float-kitty")
+        .unwrap();
+    assert_eq!(
+        rule["match:class"],
+        SnapshotValue::String("kitty".to_string())
+    );
+    assert_eq!(snapshot.special_category_instances("windowrule").count(), 1);
+    assert_eq!(snapshot.generation(), config.generation());
+}
+
+#[test]
+fn test_snapshot_is_independent_of_config() {
+    let mut config = config("gaps = 5");
+    let snapshot = config.snapshot();
+
+    config.parse("gaps = 10").unwrap();
+    assert_eq!(snapshot.get_int("gaps").unwrap(), 5);
+    assert_eq!(config.snapshot().get_int("gaps").unwrap(), 10);
+}
+
+#[test]
+fn test_readers_see_published_snapshots() {
+    let mut config = config("gaps = 0");
+    let shared = Arc::new(SharedSnapshot::new(config.snapshot()));
+
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            let shared = Arc::clone(&shared);
+            thread::spawn(move || {
+                let mut last = 0;
+                while last < 10 {
+                    let current = shared.load().get_int("gaps").unwrap();
+                    // Each published snapshot is complete and they arrive in order
+                    assert!(current >= last);
+                    last = current;
+                    thread::yield_now();
+                }
+            })
+        })
+        .collect();
+
+    for gaps in 1..=10 {
+        config.parse(&format!("gaps = {}", gaps)).unwrap();
+        shared.publish(&config);
+    }
+
+    for reader in readers {
+        reader.join().unwrap();
+    }
+}
+
+#[test]
+fn test_store_returns_previous_snapshot() {
+    let shared = SharedSnapshot::default();
+    assert!(shared.load().keys().is_empty());
+
+    let previous = shared.store(config("gaps = 5").snapshot());
+    assert!(!previous.contains("gaps"));
+    assert!(shared.load().contains("gaps"));
+}
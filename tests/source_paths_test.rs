@@ -0,0 +1,96 @@
+#![cfg(feature = "config")]
+
+mod common;
+
+use common::{cleanup_test_dir, create_test_dir};
+use hyprlang::{Config, ConfigError};
+use std::fs;
+use std::path::PathBuf;
+
+/// A temporary directory with `main.conf` sourcing `source_line` and `sub/colors.conf`
+fn sourcing_dir(source_line: &str) -> PathBuf {
+    let dir = create_test_dir("source_paths");
+    fs::create_dir_all(dir.join("sub")).unwrap();
+    fs::write(dir.join("sub/colors.conf"), "border = 2\n").unwrap();
+    fs::write(dir.join("main.conf"), format!("{}\n", source_line)).unwrap();
+    dir
+}
+
+fn parse_with_source(source_line: &str) -> (PathBuf, Result<Config, ConfigError>) {
+    let dir = sourcing_dir(source_line);
+    let mut config = Config::new();
+    let result = config.parse_file(dir.join("main.conf")).map(|_| config);
+    (dir, result)
+}
+
+#[test]
+fn test_backslash_separators() {
+    for line in [
+        r"source = sub\colors.conf",
+        r"source = .\sub\colors.conf",
+        r"source = sub/..\sub\colors.conf",
+    ] {
+        let (dir, result) = parse_with_source(line);
+        let config = result.unwrap_or_else(|e| panic!("{}: {}", line, e));
+        assert_eq!(config.get_int("border").unwrap(), 2, "{}", line);
+        cleanup_test_dir(&dir);
+    }
+}
+
+#[test]
+fn test_backslash_separators_in_variables() {
+    let (dir, result) = parse_with_source("$DIR = .\\sub\nsource = $DIR\\colors.conf");
+    assert_eq!(result.unwrap().get_int("border").unwrap(), 2);
+    cleanup_test_dir(&dir);
+}
+
+#[cfg(not(windows))]
+#[test]
+fn test_windows_paths_are_rejected_elsewhere() {
+    for line in [
+        r"source = C:\Users\me\hypr.conf",
+        "source = D:/hypr.conf",
+        r"source = \\server\share\hypr.conf",
+    ] {
+        let (dir, result) = parse_with_source(line);
+        let error = result
+            .err()
+            .unwrap_or_else(|| panic!("{} should fail", line));
+        assert!(
+            error.to_string().contains("only supported on Windows"),
+            "{}: {}",
+            line,
+            error
+        );
+        cleanup_test_dir(&dir);
+    }
+}
+
+#[cfg(windows)]
+#[test]
+fn test_drive_letter_paths() {
+    let dir = sourcing_dir("");
+    let absolute = dir.join("sub").join("colors.conf");
+    fs::write(
+        dir.join("main.conf"),
+        format!("source = {}\n", absolute.display()),
+    )
+    .unwrap();
+
+    let mut config = Config::new();
+    config.parse_file(dir.join("main.conf")).unwrap();
+    assert_eq!(config.get_int("border").unwrap(), 2);
+
+    cleanup_test_dir(&dir);
+}
+
+#[cfg(all(windows, feature = "mutation"))]
+#[test]
+fn test_tracked_paths_are_not_verbatim() {
+    let (dir, result) = parse_with_source(r"source = sub\colors.conf");
+    let config = result.unwrap();
+    let source = config.get_key_source_file("border").unwrap();
+    assert!(!source.to_string_lossy().starts_with(r"\\?\"));
+    assert!(source.ends_with(r"sub\colors.conf"));
+    cleanup_test_dir(&dir);
+}
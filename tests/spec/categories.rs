@@ -0,0 +1,170 @@
+//! Regular, nested and special categories.
+
+use crate::{parse_corpus, upstream_config};
+use hyprlang::SpecialCategoryDescriptor;
+
+#[test]
+fn test_category_values() {
+    let config = parse_corpus();
+    assert_eq!(config.get_int("testCategory:testValueInt").unwrap(), 123456);
+    assert_eq!(
+        config
+            .get_color("testCategory:testColor1")
+            .unwrap()
+            .to_rgba(),
+        0xFFFFFFFF
+    );
+    assert_eq!(
+        config
+            .get_color("testCategory:testColor2")
+            .unwrap()
+            .to_rgba(),
+        0x000000FF
+    );
+    assert_eq!(
+        config
+            .get_color("testCategory:testColor3")
+            .unwrap()
+            .to_rgba(),
+        0xFFEEFF22
+    );
+    assert_eq!(
+        config
+            .get_int("testCategory:nested1:testValueNest")
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        config
+            .get_int("testCategory:nested1:nested2:testValueNest")
+            .unwrap(),
+        1
+    );
+}
+
+#[test]
+fn test_keyed_special_category() {
+    let config = parse_corpus();
+
+    // `special { key = a }` names its instance through the key field
+    let a = config.get_special_category("special", "a").unwrap();
+    assert_eq!(a["value"].as_int().unwrap(), 1);
+
+    let b = config.get_special_category("special", "b").unwrap();
+    assert_eq!(b["value"].as_int().unwrap(), 2);
+}
+
+#[test]
+fn test_anonymous_special_category() {
+    let config = parse_corpus();
+
+    let mut keys = config.list_special_category_keys("specialAnonymous");
+    keys.sort();
+    assert_eq!(keys.len(), 2);
+
+    let values: Vec<i64> = keys
+        .iter()
+        .map(|key| {
+            config
+                .get_special_category("specialAnonymous", key)
+                .unwrap()["value"]
+                .as_int()
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(values, vec![2, 3]);
+}
+
+#[test]
+fn test_anonymous_nested_special_category() {
+    let config = parse_corpus();
+
+    let mut keys = config.list_special_category_keys("specialAnonymousNested");
+    keys.sort();
+    assert_eq!(keys.len(), 2);
+
+    // Flat `a:b = x` keys and nested blocks produce the same properties
+    let first = config
+        .get_special_category("specialAnonymousNested", &keys[0])
+        .unwrap();
+    assert_eq!(first["nested:value1"].as_int().unwrap(), 1);
+    assert_eq!(first["nested1:nested2:value2"].as_int().unwrap(), 11);
+
+    let second = config
+        .get_special_category("specialAnonymousNested", &keys[1])
+        .unwrap();
+    assert_eq!(second["nested:value2"].as_int().unwrap(), 4);
+    assert_eq!(second["nested1:nested2:value1"].as_int().unwrap(), 12);
+}
+
+#[test]
+fn test_anonymous_blocks_accumulate_by_full_path() {
+    let mut config = upstream_config();
+    config.register_special_category(SpecialCategoryDescriptor::anonymous("plugin:button"));
+    config
+        .parse(
+            r#"
+plugin {
+    button {
+        icon = a
+    }
+    button {
+        icon = b
+    }
+}
+button {
+    icon = c
+}
+"#,
+        )
+        .unwrap();
+
+    let mut keys = config.list_special_category_keys("plugin:button");
+    keys.sort();
+    let icons: Vec<String> = keys
+        .iter()
+        .map(|key| {
+            config.get_special_category("plugin:button", key).unwrap()["icon"]
+                .as_string()
+                .unwrap()
+                .to_string()
+        })
+        .collect();
+    assert_eq!(icons, vec!["a", "b"]);
+
+    // Outside `plugin` the same block name is a regular category
+    assert_eq!(config.get_string("button:icon").unwrap(), "c");
+}
+
+#[test]
+fn test_static_blocks_share_one_instance() {
+    let mut config = upstream_config();
+    config.register_special_category(SpecialCategoryDescriptor::static_category("global"));
+    config
+        .parse("global {\n    a = 1\n}\nglobal {\n    b = 2\n}\n")
+        .unwrap();
+
+    assert_eq!(config.list_special_category_keys("global").len(), 1);
+    let global = config.get_special_category("global", "static").unwrap();
+    assert_eq!(global["a"].as_int().unwrap(), 1);
+    assert_eq!(global["b"].as_int().unwrap(), 2);
+}
+
+#[test]
+fn test_repeated_keyed_blocks_merge_into_one_instance() {
+    let mut config = upstream_config();
+    config
+        .parse(
+            "special[a] {\n    value = 1\n}\nspecial[b] {\n    value = 2\n}\n\
+             special[a] {\n    extra = 3\n}\n",
+        )
+        .unwrap();
+
+    let a = config.get_special_category("special", "a").unwrap();
+    assert_eq!(a["value"].as_int().unwrap(), 1);
+    assert_eq!(a["extra"].as_int().unwrap(), 3);
+
+    let b = config.get_special_category("special", "b").unwrap();
+    assert_eq!(b["value"].as_int().unwrap(), 2);
+    assert!(!b.contains_key("extra"));
+}
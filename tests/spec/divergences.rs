@@ -0,0 +1,67 @@
+//! Behaviors listed by `CompatibilityReport`, pinned to what this crate does today.
+//!
+//! When one of these is brought in line with upstream, its entry is removed from
+//! the report and its test here moves into the module for that area.
+
+use crate::{parse_corpus, upstream_config};
+use hyprlang::CompatibilityReport;
+
+#[test]
+fn test_report_lists_pinned_divergences() {
+    let report = CompatibilityReport::current();
+    let ids: Vec<&str> = report.divergences().iter().map(|d| d.id).collect();
+    assert_eq!(
+        ids,
+        vec![
+            "quoted-strings",
+            "unregistered-keys",
+            "nested-category-handlers"
+        ]
+    );
+    assert!(!report.is_fully_compatible());
+    assert!(
+        report
+            .get("quoted-strings")
+            .unwrap()
+            .upstream
+            .contains("kept")
+    );
+    assert!(report.to_string().contains("- nested-category-handlers\n"));
+}
+
+#[test]
+fn test_quoted_strings() {
+    let config = parse_corpus();
+    assert!(CompatibilityReport::current().diverges("quoted-strings"));
+    // Upstream: "\"Hello World!\""
+    assert_eq!(
+        config.get_string("testStringQuotes").unwrap(),
+        "Hello World!"
+    );
+}
+
+#[test]
+fn test_unregistered_keys() {
+    assert!(CompatibilityReport::current().diverges("unregistered-keys"));
+
+    let mut config = upstream_config();
+    config.parse("someUnknownKey = 1").unwrap();
+    assert_eq!(config.get_int("someUnknownKey").unwrap(), 1);
+
+    // Upstream only parses this without errors because of the noerror block
+    let config = parse_corpus();
+    assert_eq!(config.get_int("errorVariable").unwrap(), 1);
+}
+
+#[test]
+fn test_nested_category_handlers() {
+    let config = parse_corpus();
+    assert!(CompatibilityReport::current().diverges("nested-category-handlers"));
+    // Upstream: not dispatched to the `testCategory` handler
+    assert_eq!(
+        config
+            .get_handler_calls("testCategory:nested1:categoryKeyword")
+            .unwrap(),
+        &vec!["this one should not either".to_string()]
+    );
+}
@@ -0,0 +1,49 @@
+//! Escaped expressions: `\{{`, `{\{`, `\\{{` and their combinations.
+
+use crate::parse_corpus;
+
+#[test]
+fn test_escaped_expressions() {
+    let config = parse_corpus();
+    assert_eq!(
+        config.get_string("testEscapedExpr").unwrap(),
+        "{{testInt + 7}}"
+    );
+    assert_eq!(
+        config.get_string("testEscapedExpr2").unwrap(),
+        "{{testInt + 7}}"
+    );
+    assert_eq!(config.get_string("testEscapedExpr3").unwrap(), "{{3 + 8}}");
+}
+
+#[test]
+fn test_escaped_backslash_keeps_expression() {
+    let config = parse_corpus();
+    assert_eq!(config.get_string("testEscapedEscape").unwrap(), "\\5");
+}
+
+#[test]
+fn test_mixed_escaped_expressions() {
+    let config = parse_corpus();
+    assert_eq!(
+        config.get_string("testMixedEscapedExpression").unwrap(),
+        "-2 {{ {{50 + 50}} / {{10 * 5}} }}"
+    );
+    assert_eq!(
+        config.get_string("testMixedEscapedExpression2").unwrap(),
+        "{{8\\13}} should equal \"{{8\\13}}\""
+    );
+}
+
+#[test]
+fn test_escapes_through_variables() {
+    let config = parse_corpus();
+    assert_eq!(
+        config.get_string("testImbeddedEscapedExpression").unwrap(),
+        "{{10 + 10}}"
+    );
+    assert_eq!(
+        config.get_string("testDynamicEscapedExpression").unwrap(),
+        "{{ moved: 500 expr: {{1000 / 2}} }}"
+    );
+}
@@ -0,0 +1,39 @@
+//! Keyword handlers, handler flags and category-scoped handlers.
+
+use crate::parse_corpus;
+
+#[test]
+fn test_handler_value_excludes_comment() {
+    let config = parse_corpus();
+    assert_eq!(
+        config.get_handler_calls("doABarrelRoll").unwrap(),
+        &vec!["woohoo, some, params".to_string()]
+    );
+}
+
+#[test]
+fn test_handler_flags_suffix() {
+    let config = parse_corpus();
+
+    // `flagsabc = test` calls the flag-accepting `flags` handler with flags `abc`
+    let records = config.get_handler_call_records("flags").unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].value, "test");
+    assert_eq!(records[0].flags.as_deref(), Some("abc"));
+    assert!(!config.contains("flagsabc"));
+}
+
+#[test]
+fn test_category_keyword_calls_in_order() {
+    let config = parse_corpus();
+    assert_eq!(
+        config
+            .get_handler_calls("testCategory:categoryKeyword")
+            .unwrap(),
+        &vec![
+            "we are having fun".to_string(),
+            "so much fun".to_string(),
+            "im the fun one at parties".to_string(),
+        ]
+    );
+}
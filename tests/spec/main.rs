@@ -0,0 +1,39 @@
+//! Spec-compliance suite ported from upstream hyprlang's test corpus.
+//!
+//! Each module checks one area of `tests/config/config.conf` against the values
+//! upstream's own tests expect. Behaviors that knowingly differ are listed by
+//! `CompatibilityReport` and pinned in `divergences.rs`.
+
+#![cfg(feature = "config")]
+
+mod categories;
+mod divergences;
+mod escapes;
+mod handlers;
+mod values;
+mod variables;
+
+use hyprlang::{Config, FunctionHandler, SpecialCategoryDescriptor};
+use std::path::Path;
+
+/// Register the handlers and special categories upstream's test harness registers
+pub fn upstream_config() -> Config {
+    let mut config = Config::new();
+    config.register_handler_fn("doABarrelRoll", |_| Ok(()));
+    config.register_handler("flags", FunctionHandler::with_flags("flags", |_| Ok(())));
+    config.register_category_handler_fn("testCategory", "categoryKeyword", |_| Ok(()));
+    config.register_special_category(SpecialCategoryDescriptor::keyed("special", "key"));
+    config.register_special_category(SpecialCategoryDescriptor::anonymous("specialAnonymous"));
+    config.register_special_category(SpecialCategoryDescriptor::anonymous(
+        "specialAnonymousNested",
+    ));
+    config
+}
+
+/// Parse the whole upstream corpus
+pub fn parse_corpus() -> Config {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/config/config.conf");
+    let mut config = upstream_config();
+    config.parse_file(&path).unwrap();
+    config
+}
@@ -0,0 +1,47 @@
+//! Plain values, comments, conditionals and line continuations.
+
+use crate::{parse_corpus, upstream_config};
+
+#[test]
+fn test_basic_values() {
+    let config = parse_corpus();
+    assert_eq!(config.get_int("testInt").unwrap(), 123);
+    assert_eq!(config.get_float("testFloat").unwrap(), 123.456);
+}
+
+#[test]
+fn test_double_hash_is_a_literal_hash() {
+    let config = parse_corpus();
+    assert_eq!(
+        config.get_string("testString").unwrap(),
+        "Hello World! # This is not a comment!"
+    );
+}
+
+#[test]
+fn test_nested_conditionals() {
+    let config = parse_corpus();
+    // Only the branch guarded by negated, undefined variables is taken
+    assert_eq!(config.get_string("testStringColon").unwrap(), "ee:ee:ee");
+    assert_eq!(config.get_string("customType").unwrap(), "abc");
+}
+
+#[test]
+fn test_line_continuation() {
+    let config = parse_corpus();
+    assert_eq!(config.get_string("multiline").unwrap(), "very long command");
+
+    let mut config = upstream_config();
+    config
+        .parse("exec = first \\\n    second \\\n    third\nafter = 1\n")
+        .unwrap();
+    assert_eq!(config.get_string("exec").unwrap(), "first second third");
+    assert_eq!(config.get_int("after").unwrap(), 1);
+}
+
+#[test]
+fn test_later_assignment_overrides_category_value() {
+    let config = parse_corpus();
+    let color = config.get_color("testCategory:testValueHex").unwrap();
+    assert_eq!(color.to_rgba(), 0xFFFFAABB);
+}
@@ -0,0 +1,50 @@
+//! Variable expansion, expression variables and sourced files.
+
+use crate::{parse_corpus, upstream_config};
+
+#[test]
+fn test_variables_in_variables() {
+    let config = parse_corpus();
+    assert_eq!(config.get_int("testVar").unwrap(), 13371337);
+}
+
+#[test]
+fn test_expression_defined_variable_in_expression() {
+    let config = parse_corpus();
+    assert_eq!(config.get_int("testExpr").unwrap(), 1335);
+}
+
+#[test]
+fn test_variable_name_prefix_expansion() {
+    let config = parse_corpus();
+    assert_eq!(config.get_string("testStringRecursive").unwrap(), "abc");
+
+    // A full variable name always wins over a shorter prefix
+    let mut config = upstream_config();
+    config
+        .parse("$A = short\n$AB = long\nexact = $AB\nprefixed = $ABC\n")
+        .unwrap();
+    assert_eq!(config.get_string("exact").unwrap(), "long");
+    assert_eq!(config.get_string("prefixed").unwrap(), "longC");
+}
+
+#[test]
+fn test_environment_variable() {
+    let config = parse_corpus();
+    if let Ok(shell) = std::env::var("SHELL") {
+        assert_eq!(config.get_string("testEnv").unwrap(), shell);
+    }
+}
+
+#[test]
+fn test_sourced_file_sees_parent_variables() {
+    let config = parse_corpus();
+    let pink = config.get_color("myColors:pink").unwrap();
+    assert_eq!((pink.r, pink.g, pink.b, pink.a), (200, 0, 200, 255));
+    let green = config.get_color("myColors:green").unwrap();
+    assert_eq!((green.r, green.g, green.b), (20, 240, 20));
+    assert_eq!(
+        config.get_color("myColors:random").unwrap().to_rgba(),
+        0xFFFF1337
+    );
+}
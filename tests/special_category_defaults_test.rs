@@ -0,0 +1,66 @@
+//! Tests for registering and clearing special category defaults in bulk.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, ConfigValue, SpecialCategoryDescriptor};
+
+#[test]
+fn test_extend_special_category_defaults() {
+    let mut config = Config::new();
+    config.register_special_category(SpecialCategoryDescriptor::keyed("device", "name"));
+    config.register_special_category_value("device", "enabled", ConfigValue::Int(1));
+    config.extend_special_category_defaults(
+        "device",
+        [
+            ("sensitivity", ConfigValue::Float(0.0)),
+            ("accel_profile", ConfigValue::String("adaptive".to_string())),
+        ],
+    );
+
+    config
+        .parse("device[mouse] {\n    sensitivity = 0.5\n}")
+        .unwrap();
+
+    let mouse = config.get_special_category("device", "mouse").unwrap();
+    assert_eq!(mouse["enabled"].as_int().unwrap(), 1);
+    assert_eq!(mouse["sensitivity"].as_float().unwrap(), 0.5);
+    assert_eq!(mouse["accel_profile"].as_string().unwrap(), "adaptive");
+}
+
+#[test]
+fn test_clear_special_category_defaults() {
+    let mut config = Config::new();
+    config.register_special_category(
+        SpecialCategoryDescriptor::anonymous("item")
+            .with_defaults([("a", ConfigValue::Int(1)), ("b", ConfigValue::Int(2))]),
+    );
+
+    config.parse("item {\n    c = 3\n}").unwrap();
+    config.clear_special_category_defaults("item");
+    config.extend_special_category_defaults("item", [("d", ConfigValue::Int(4))]);
+    config.parse("item {\n    c = 5\n}").unwrap();
+
+    let mut keys = config.list_special_category_keys("item");
+    keys.sort();
+
+    // The first instance was created before the defaults were replaced
+    let first = config.get_special_category("item", &keys[0]).unwrap();
+    assert_eq!(first.len(), 3);
+    assert_eq!(first["b"].as_int().unwrap(), 2);
+
+    let second = config.get_special_category("item", &keys[1]).unwrap();
+    assert_eq!(second.len(), 2);
+    assert_eq!(second["d"].as_int().unwrap(), 4);
+    assert!(!second.contains_key("a"));
+}
+
+#[test]
+fn test_defaults_for_unregistered_category_are_ignored() {
+    let mut config = Config::new();
+    config.extend_special_category_defaults("missing", [("a", ConfigValue::Int(1))]);
+    config.clear_special_category_defaults("missing");
+    config.parse("missing {\n    a = 2\n}").unwrap();
+
+    assert_eq!(config.get_int("missing:a").unwrap(), 2);
+    assert!(config.list_special_category_keys("missing").is_empty());
+}
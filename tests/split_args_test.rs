@@ -0,0 +1,63 @@
+//! Tests for the argument splitting shared by handlers and the typed Hyprland models.
+
+use hyprlang::util::{join_args, split_args, splitn_args};
+
+#[test]
+fn test_split_args() {
+    assert_eq!(split_args(""), vec![""]);
+    assert_eq!(split_args(" a ,b,, c "), vec!["a", "b", "", "c"]);
+    assert_eq!(
+        split_args(r#"notify-send "Hello, world" , 'a, b', "x \", y", plain"#),
+        vec![
+            r#"notify-send "Hello, world""#,
+            "'a, b'",
+            r#""x \", y""#,
+            "plain"
+        ]
+    );
+    assert_eq!(
+        split_args("kitty --title='a, b', x"),
+        vec!["kitty --title='a, b'", "x"]
+    );
+}
+
+#[test]
+fn test_apostrophes_inside_words_do_not_quote() {
+    assert_eq!(
+        split_args("SUPER, Q, Don't close, killactive"),
+        vec!["SUPER", "Q", "Don't close", "killactive"]
+    );
+}
+
+#[test]
+fn test_splitn_args_keeps_the_rest() {
+    assert_eq!(
+        splitn_args("SUPER, E, exec, kitty, --hold", 4),
+        vec!["SUPER", "E", "exec", "kitty, --hold"]
+    );
+    assert_eq!(splitn_args("a, b", 4), vec!["a", "b"]);
+    assert_eq!(splitn_args("a, b", 1), vec!["a, b"]);
+    assert!(splitn_args("a, b", 0).is_empty());
+}
+
+#[test]
+fn test_join_args_inverts_split_args() {
+    for value in [
+        "SUPER, Q, exec, kitty",
+        r#"exec, sh -c "a, b", 'c, d'"#,
+        ", XF86AudioMute, exec, wpctl",
+    ] {
+        assert_eq!(join_args(&split_args(value)), value);
+    }
+    assert_eq!(join_args::<&str>(&[]), "");
+}
+
+#[cfg(feature = "config")]
+#[test]
+fn test_handler_args_use_split_args() {
+    use hyprlang::HandlerContext;
+
+    let value = r#"notify-send "a, b", Don't, -t 500"#;
+    let ctx = HandlerContext::new("exec".to_string(), value.to_string());
+    assert_eq!(ctx.args(), split_args(value));
+}
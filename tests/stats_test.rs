@@ -0,0 +1,169 @@
+//! Tests for `Config::stats`.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, ConfigOptions, SpecialCategoryDescriptor};
+use std::fs;
+
+fn config() -> Config {
+    let mut config = Config::new();
+    config.register_handler_fn("bind", |_| Ok(()));
+    config.register_category_handler_fn("animations", "bezier", |_| Ok(()));
+    config.register_special_category(SpecialCategoryDescriptor::keyed("device", "name"));
+    config
+}
+
+const INPUT: &str = "\
+$mod = SUPER
+$term = kitty
+gaps = 5
+general {
+    border_size = 2
+    col.active_border = rgba(33ccffee)
+}
+decoration:blur:size = 8
+device[mouse] {
+    sensitivity = 0.5
+}
+device[touchpad] {
+    sensitivity = 1.0
+}
+bind = $mod, Q, exec, $term
+bind = $mod, M, exit
+animations {
+    bezier = ease, 0.25, 0.1, 0.25, 1
+}
+";
+
+#[test]
+fn test_counts() {
+    let mut config = config();
+    config.parse(INPUT).unwrap();
+
+    let stats = config.stats();
+    assert_eq!(stats.keys_per_category[""], 1);
+    assert_eq!(stats.keys_per_category["general"], 2);
+    assert_eq!(stats.keys_per_category["decoration"], 1);
+    assert_eq!(stats.keys_per_category["device"], 2);
+    assert_eq!(stats.special_category_instances["device"], 2);
+    assert_eq!(stats.handler_calls["bind"], 2);
+    assert_eq!(stats.handler_calls["animations:bezier"], 1);
+    assert_eq!(stats.variables, 2);
+    assert_eq!(stats.total_keys(), 6);
+    assert_eq!(stats.total_handler_calls(), 3);
+    assert_eq!(stats.lines, 19);
+    assert_eq!(stats.files, 0);
+    assert_eq!(stats.largest(), Some(("bind", 2)));
+}
+
+#[test]
+fn test_counts_accumulate_over_files() {
+    let dir = std::env::temp_dir().join(format!("hyprlang_stats_test_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("main.conf"), "source = ./binds.conf\ngaps = 5\n").unwrap();
+    fs::write(dir.join("binds.conf"), "bind = SUPER, Q, exit\n").unwrap();
+
+    let mut config = config();
+    config.parse_file(dir.join("main.conf")).unwrap();
+
+    let stats = config.stats();
+    assert_eq!(stats.files, 2);
+    assert_eq!(stats.lines, 3);
+    assert_eq!(stats.handler_calls["bind"], 1);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_summary() {
+    let mut config = config();
+    config.parse("gaps = 5\nbind = SUPER, Q, exit").unwrap();
+
+    let summary = config.stats().to_string();
+    let lines: Vec<_> = summary.lines().collect();
+    assert_eq!(
+        lines[0],
+        "1 value(s) in 1 categories, 0 variable(s), 1 handler call(s)"
+    );
+    assert!(lines[1].starts_with("2 line(s) in 0 file(s), parsed in "));
+    assert_eq!(lines[2..], ["  (root): 1 value(s)", "  bind: 1 call(s)"]);
+}
+
+#[test]
+fn test_file_stats() {
+    let dir = std::env::temp_dir().join(format!("hyprlang_file_stats_test_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let main = "source = ./binds.conf\ngaps = 5\ngeneral {\n    border_size = 2\n}\n";
+    let binds =
+        "bind = SUPER, Q, exit\nbind = SUPER, M\n# hyprlang noerror true\nnope = {{1 / 0}}\n";
+    fs::write(dir.join("main.conf"), main).unwrap();
+    fs::write(dir.join("binds.conf"), binds).unwrap();
+
+    let mut config = config();
+    config.parse_file(dir.join("main.conf")).unwrap();
+
+    let files = config.file_stats();
+    assert_eq!(files.len(), 2);
+    assert!(files[0].path.ends_with("main.conf"));
+    assert_eq!(
+        (
+            files[0].bytes,
+            files[0].lines,
+            files[0].statements,
+            files[0].errors
+        ),
+        (main.len(), 5, 4, 0)
+    );
+    assert!(files[1].path.ends_with("binds.conf"));
+    assert_eq!(
+        (
+            files[1].bytes,
+            files[1].lines,
+            files[1].statements,
+            files[1].errors
+        ),
+        (binds.len(), 4, 4, 1)
+    );
+    assert!(files[1].to_string().ends_with(&format!(
+        "binds.conf: {} byte(s), 4 line(s), 4 statement(s), 1 error(s) in {:?}",
+        binds.len(),
+        files[1].duration
+    )));
+
+    let stats = config.stats();
+    assert_eq!(stats.file_stats, files);
+    assert!(stats.slowest_file().is_some());
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_file_stats_count_errors_where_they_arise() {
+    let dir =
+        std::env::temp_dir().join(format!("hyprlang_file_errors_test_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("main.conf"),
+        "source = ./bad.conf\nsource = ./missing.conf\n",
+    )
+    .unwrap();
+    fs::write(dir.join("bad.conf"), "general {\n    gaps = {{1 / 0}}\n}\n").unwrap();
+
+    let mut config = Config::with_options(ConfigOptions {
+        throw_all_errors: true,
+        ..Default::default()
+    });
+    assert!(config.parse_file(dir.join("main.conf")).is_err());
+
+    let errors: Vec<_> = config
+        .file_stats()
+        .iter()
+        .map(|file| (file.path.file_name().unwrap().to_owned(), file.errors))
+        .collect();
+    assert_eq!(
+        errors,
+        vec![("main.conf".into(), 1), ("bad.conf".into(), 1)]
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,129 @@
+#![cfg(all(unix, feature = "mutation"))]
+
+mod common;
+
+use common::{cleanup_test_dir, create_test_dir};
+use hyprlang::{Config, ConfigOptions};
+use std::fs;
+use std::os::unix::fs::symlink;
+use std::path::PathBuf;
+
+/// A temporary directory holding `dotfiles/` with the real files and `config/` linking to them
+fn create_linked_dir() -> PathBuf {
+    let dir = create_test_dir("symlink");
+    let dotfiles = dir.join("dotfiles");
+    fs::create_dir_all(&dotfiles).unwrap();
+    fs::write(
+        dotfiles.join("main.conf"),
+        "source = colors.conf\ngeneral {\n    gaps_in = 5\n}\n",
+    )
+    .unwrap();
+    fs::write(dotfiles.join("colors.conf"), "$accent = red\nborder = 2\n").unwrap();
+    symlink(&dotfiles, dir.join("config")).unwrap();
+    dir
+}
+
+fn preserving_config() -> Config {
+    Config::with_options(ConfigOptions {
+        canonicalize_paths: false,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_symlinks_are_resolved_by_default() {
+    let dir = create_linked_dir();
+    let mut config = Config::new();
+    config.parse_file(dir.join("config/main.conf")).unwrap();
+
+    let dotfiles = dir.join("dotfiles").canonicalize().unwrap();
+    assert_eq!(
+        config.get_key_source_file("border").unwrap(),
+        dotfiles.join("colors.conf")
+    );
+    assert_eq!(
+        config.get_key_source_file("general:gaps_in").unwrap(),
+        dotfiles.join("main.conf")
+    );
+
+    cleanup_test_dir(&dir);
+}
+
+#[test]
+fn test_symlink_paths_are_kept_when_not_canonicalizing() {
+    let dir = create_linked_dir();
+    let mut config = preserving_config();
+    config.parse_file(dir.join("config/main.conf")).unwrap();
+
+    let linked = dir.join("config");
+    assert_eq!(
+        config.get_key_source_file("border").unwrap(),
+        linked.join("colors.conf")
+    );
+    assert_eq!(
+        config.get_key_source_file("general:gaps_in").unwrap(),
+        linked.join("main.conf")
+    );
+    assert!(
+        config
+            .get_source_files()
+            .iter()
+            .all(|path| path.starts_with(&linked))
+    );
+
+    cleanup_test_dir(&dir);
+}
+
+#[test]
+fn test_save_through_symlink_updates_target() {
+    let dir = create_linked_dir();
+    let mut config = preserving_config();
+    config.parse_file(dir.join("config/main.conf")).unwrap();
+
+    config.set_int("border", 4).unwrap();
+    let saved = config.save_all().unwrap();
+    assert_eq!(saved, vec![dir.join("config/colors.conf")]);
+
+    let target = fs::read_to_string(dir.join("dotfiles/colors.conf")).unwrap();
+    assert!(target.contains("border = 4"));
+    assert!(
+        fs::symlink_metadata(dir.join("config"))
+            .unwrap()
+            .file_type()
+            .is_symlink()
+    );
+
+    cleanup_test_dir(&dir);
+}
+
+#[test]
+fn test_missing_source_is_still_an_error_when_not_canonicalizing() {
+    let dir = create_linked_dir();
+    fs::write(dir.join("dotfiles/main.conf"), "source = missing.conf\n").unwrap();
+    let mut config = preserving_config();
+
+    let error = config.parse_file(dir.join("config/main.conf")).unwrap_err();
+    assert!(error.to_string().contains("missing.conf"));
+
+    cleanup_test_dir(&dir);
+}
+
+#[test]
+fn test_parent_components_are_normalized_when_not_canonicalizing() {
+    let dir = create_linked_dir();
+    fs::create_dir_all(dir.join("dotfiles/sub")).unwrap();
+    fs::write(
+        dir.join("dotfiles/main.conf"),
+        "source = ./sub/../colors.conf\n",
+    )
+    .unwrap();
+    let mut config = preserving_config();
+    config.parse_file(dir.join("config/main.conf")).unwrap();
+
+    assert_eq!(
+        config.get_key_source_file("border").unwrap(),
+        dir.join("config/colors.conf")
+    );
+
+    cleanup_test_dir(&dir);
+}
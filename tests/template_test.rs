@@ -0,0 +1,109 @@
+//! Tests for rendering configs from `Template` files with `@{placeholder}` markers.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, ConfigError, Template};
+use std::collections::HashMap;
+use std::fs;
+
+const THEME_TEMPLATE: &str = r#"# Generated from a theme
+$accent = @{accent}
+
+general {
+    border_size = @{border}
+    col.active_border = $accent
+    gaps_out = {{@{border} * 4}}
+}
+
+decoration {
+    rounding = @{ rounding }
+}
+"#;
+
+fn theme() -> HashMap<String, String> {
+    HashMap::from([
+        ("accent".to_string(), "rgba(33ccffee)".to_string()),
+        ("border".to_string(), "2".to_string()),
+        ("rounding".to_string(), "10".to_string()),
+        ("unused".to_string(), "ignored".to_string()),
+    ])
+}
+
+#[test]
+fn test_render_config_from_theme() {
+    let template = Template::new(THEME_TEMPLATE).unwrap();
+    assert_eq!(
+        template.placeholders(),
+        vec!["accent", "border", "rounding"]
+    );
+
+    let config = template.render_config(&theme()).unwrap();
+    assert_eq!(config.get_int("general:border_size").unwrap(), 2);
+    assert_eq!(config.get_int("general:gaps_out").unwrap(), 8);
+    assert_eq!(config.get_int("decoration:rounding").unwrap(), 10);
+    let color = config.get_color("general:col.active_border").unwrap();
+    assert_eq!(
+        (color.r, color.g, color.b, color.a),
+        (0x33, 0xcc, 0xff, 0xee)
+    );
+}
+
+#[test]
+fn test_missing_values_are_reported_together() {
+    let template = Template::new(THEME_TEMPLATE).unwrap();
+    let mut values = theme();
+    values.remove("accent");
+    values.remove("rounding");
+
+    let err = template.render(&values).unwrap_err();
+    match &err {
+        ConfigError::MissingPlaceholders { names } => {
+            assert_eq!(names, &vec!["accent".to_string(), "rounding".to_string()])
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+    assert_eq!(
+        err.to_string(),
+        "No value provided for placeholder(s): accent, rounding"
+    );
+}
+
+#[test]
+fn test_render_into_registered_config() {
+    let template = Template::new("bind = @{mod}, Q, exec, @{terminal}\n").unwrap();
+    let values = HashMap::from([
+        ("mod".to_string(), "SUPER".to_string()),
+        ("terminal".to_string(), "kitty".to_string()),
+    ]);
+
+    let mut config = Config::new();
+    config.register_handler_fn("bind", |_| Ok(()));
+    template.render_into(&mut config, &values).unwrap();
+
+    assert_eq!(
+        config.get_handler_calls("bind").unwrap(),
+        &vec!["SUPER, Q, exec, kitty".to_string()]
+    );
+}
+
+#[test]
+fn test_template_from_file() {
+    let path = std::env::temp_dir().join(format!(
+        "hyprlang_template_test_{}.conf",
+        std::process::id()
+    ));
+    fs::write(&path, "size = @{size}\nliteral = @@{size}\n").unwrap();
+
+    let template = Template::from_file(&path).unwrap();
+    let values = HashMap::from([("size".to_string(), "3".to_string())]);
+    assert_eq!(
+        template.render(&values).unwrap(),
+        "size = 3\nliteral = @{size}\n"
+    );
+
+    let _ = fs::remove_file(&path);
+    assert!(matches!(
+        Template::from_file(&path),
+        Err(ConfigError::IoError { .. })
+    ));
+}
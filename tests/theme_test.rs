@@ -0,0 +1,100 @@
+//! Tests for extracting variables into a `Theme` and applying it back to a config.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, Theme};
+
+const CONFIG: &str = r#"$col_bg = 0x1e1e2eff
+$col_fg = 0xcdd6f4ff
+$col_accent = 0x89b4faff
+$gaps = 5
+
+general {
+    col.active_border = $col_accent
+    gaps_in = $gaps
+}
+"#;
+
+#[test]
+fn test_extract_variables_matching() {
+    let mut config = Config::new();
+    config.parse(CONFIG).unwrap();
+
+    let theme = config.extract_variables_matching("col_*");
+    assert_eq!(theme.names(), vec!["col_accent", "col_bg", "col_fg"]);
+    assert_eq!(theme.get("col_fg"), Some("0xcdd6f4ff"));
+    assert_eq!(theme.get("gaps"), None);
+
+    assert_eq!(config.extract_variables_matching("*").len(), 4);
+    assert!(config.extract_variables_matching("nothing*").is_empty());
+}
+
+#[test]
+fn test_apply_theme_between_configs() {
+    let mut dark = Config::new();
+    dark.parse(CONFIG).unwrap();
+    let mut light = Config::new();
+    light
+        .parse("$col_bg = 0xeff1f5ff\n$col_fg = 0x4c4f69ff\n$col_accent = 0x1e66f5ff\n")
+        .unwrap();
+
+    let skipped = dark.apply_theme(&light.extract_variables_matching("col_*"));
+    assert!(skipped.is_empty());
+    assert_eq!(dark.get_variable("col_bg"), Some("0xeff1f5ff"));
+    assert_eq!(dark.get_variable("col_accent"), Some("0x1e66f5ff"));
+    assert_eq!(dark.get_variable("gaps"), Some("5"));
+}
+
+#[test]
+fn test_apply_theme_skips_undefined_variables() {
+    let mut config = Config::new();
+    config.parse(CONFIG).unwrap();
+
+    let theme: Theme = [("col_bg", "0x000000ff"), ("col_border", "0xffffffff")]
+        .into_iter()
+        .collect();
+    let skipped = config.apply_theme(&theme);
+
+    assert_eq!(skipped, vec!["col_border".to_string()]);
+    assert_eq!(config.get_variable("col_bg"), Some("0x000000ff"));
+    assert_eq!(config.get_variable("col_border"), None);
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_apply_theme_rewrites_definitions_across_files() {
+    use std::fs;
+
+    let dir = std::env::temp_dir().join(format!("hyprlang_theme_test_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let colors_path = dir.join("colors.conf");
+    let main_path = dir.join("main.conf");
+    fs::write(&colors_path, "$col_bg = 0x1e1e2eff\n$col_fg = 0xcdd6f4ff\n").unwrap();
+    fs::write(
+        &main_path,
+        "source = ./colors.conf\n\ndecoration {\n    shadow_color = $col_bg\n}\n",
+    )
+    .unwrap();
+
+    let mut config = Config::new();
+    config.parse_file(&main_path).unwrap();
+    config.apply_theme(&Theme::new().with("col_bg", "0xeff1f5ff"));
+
+    let saved = config.save_all().unwrap();
+    assert_eq!(saved, vec![colors_path.canonicalize().unwrap()]);
+    assert_eq!(
+        fs::read_to_string(&colors_path).unwrap(),
+        "$col_bg = 0xeff1f5ff\n$col_fg = 0xcdd6f4ff\n"
+    );
+    assert_eq!(
+        fs::read_to_string(&main_path).unwrap(),
+        "source = ./colors.conf\n\ndecoration {\n    shadow_color = $col_bg\n}\n"
+    );
+
+    let mut reloaded = Config::new();
+    reloaded.parse_file(&main_path).unwrap();
+    let color = reloaded.get_color("decoration:shadow_color").unwrap();
+    assert_eq!((color.r, color.g, color.b), (0xef, 0xf1, 0xf5));
+
+    let _ = fs::remove_dir_all(&dir);
+}
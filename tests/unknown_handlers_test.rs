@@ -0,0 +1,100 @@
+//! Tests for capturing handler-style lines that reach no registered handler.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, ConfigOptions, ConfigValue};
+use std::fs;
+
+fn capturing_config() -> Config {
+    Config::with_options(ConfigOptions {
+        capture_unknown_handlers: true,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_capture_is_off_by_default() {
+    let mut config = Config::new();
+    config.parse("load = a.so\nload = b.so").unwrap();
+
+    assert!(config.unknown_handlers().is_empty());
+}
+
+#[test]
+fn test_unknown_calls_keep_every_line() {
+    let mut config = capturing_config();
+    config
+        .parse(
+            "windowrulev3 = float, class:kitty\n\nplugin {\n    load = a.so\n    load = b.so\n}\n",
+        )
+        .unwrap();
+
+    let unknown = config.unknown_handlers();
+    assert_eq!(unknown.len(), 2);
+
+    let rules = &unknown["windowrulev3"];
+    assert_eq!(rules.len(), 1);
+    assert_eq!(rules[0].value, "float, class:kitty");
+    assert_eq!(rules[0].line, 1);
+    assert_eq!(rules[0].file, None);
+
+    let loads: Vec<(&str, usize)> = unknown["plugin:load"]
+        .iter()
+        .map(|call| (call.value.as_str(), call.line))
+        .collect();
+    assert_eq!(loads, vec![("a.so", 4), ("b.so", 5)]);
+
+    // The values themselves are stored as before
+    assert_eq!(config.get_string("plugin:load").unwrap(), "b.so");
+}
+
+#[test]
+fn test_handlers_and_options_are_not_captured() {
+    let mut config = capturing_config();
+    config.register_handler_fn("bind", |_| Ok(()));
+    config.register_option("general:gaps_in", ConfigValue::Int(5));
+    config
+        .parse("bind = SUPER, Q, exec, kitty\ngeneral {\n    gaps_in = 10\n    gaps_typo = 10\n}\ngeneral:border:size = 2\n")
+        .unwrap();
+
+    let keys: Vec<&String> = config.unknown_handlers().keys().collect();
+    assert_eq!(keys, vec!["general:gaps_typo"]);
+}
+
+#[test]
+fn test_special_category_properties_are_not_captured() {
+    let mut config = capturing_config();
+    config.register_special_category(hyprlang::SpecialCategoryDescriptor::keyed("device", "name"));
+    config
+        .parse("device[mouse] {\n    sensitivity = 0.5\n}")
+        .unwrap();
+
+    assert!(config.unknown_handlers().is_empty());
+}
+
+#[test]
+fn test_capture_records_source_files() {
+    let dir = std::env::temp_dir().join(format!(
+        "hyprlang_unknown_handlers_test_{}",
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir).unwrap();
+    let main = dir.join("main.conf");
+    let extra = dir.join("extra.conf");
+    fs::write(&main, "source = ./extra.conf\nfoo = 1\n").unwrap();
+    fs::write(&extra, "\nfoo = 2\n").unwrap();
+
+    let mut config = capturing_config();
+    config.parse_file(&main).unwrap();
+
+    let calls = &config.unknown_handlers()["foo"];
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[0].line, 2);
+    assert!(calls[0].file.as_ref().unwrap().ends_with("extra.conf"));
+    assert!(calls[1].file.as_ref().unwrap().ends_with("main.conf"));
+
+    config.clear_unknown_handlers();
+    assert!(config.unknown_handlers().is_empty());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,102 @@
+//! Tests for finding variables that nothing refers to.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, ConfigOptions, Severity, SpecialCategoryDescriptor};
+
+fn unused_names(config: &Config) -> Vec<String> {
+    config
+        .unused_variables()
+        .into_iter()
+        .map(|entry| entry.name)
+        .collect()
+}
+
+#[test]
+fn test_unused_variables_in_values() {
+    let mut config = Config::new();
+    config
+        .parse(
+            "$gap = 5\n$outer = 10\n$scale = 2\n$color = red\n$stale = blue\n\
+             general {\n    gaps_in = $gap\n    gaps_out = {{outer * scale}}\n}\n\
+             border = \"$color\"\n",
+        )
+        .unwrap();
+
+    assert_eq!(unused_names(&config), vec!["stale"]);
+}
+
+#[test]
+fn test_variables_used_through_other_variables() {
+    let mut config = Config::new();
+    config
+        .parse(
+            "$base = 4\n$double = {{base * 2}}\n$accent = red\n$border = $accent\n\
+             $old_base = 1\n$old = $old_base\nsize = $double\nborder = $border\n",
+        )
+        .unwrap();
+
+    // `old` is never used, so neither is the variable it was defined from
+    assert_eq!(unused_names(&config), vec!["old", "old_base"]);
+}
+
+#[test]
+fn test_variables_used_outside_values() {
+    let mut config = Config::new();
+    config.register_handler_fn("bind", |_| Ok(()));
+    config.register_special_category(SpecialCategoryDescriptor::keyed("device", "name"));
+    config
+        .parse(
+            "$mod = SUPER\n$mouse = logitech\n$laptop = 1\n$unused = 0\n\
+             bind = $mod, Q, exec, kitty\n\
+             device[$mouse] {\n    sensitivity = 0.5\n}\n\
+             # hyprlang if laptop\nscale = 2\n# hyprlang endif\n",
+        )
+        .unwrap();
+
+    assert_eq!(unused_names(&config), vec!["unused"]);
+}
+
+#[test]
+fn test_escaped_and_unevaluated_references_are_not_uses() {
+    let mut config = Config::new();
+    config.mark_literal("literal");
+    config
+        .parse("$price = 5\n$count = 2\nlabel = costs \\$price\nliteral = {{count}}\n")
+        .unwrap();
+
+    assert_eq!(unused_names(&config), vec!["count", "price"]);
+}
+
+#[test]
+fn test_seeded_variables_are_not_reported() {
+    let mut config = Config::new();
+    config.seed_variable("HOSTNAME", "laptop");
+    config.seed_variable("THEME", "dark");
+    config
+        .parse("$THEME = light\n$gap = 5\ngaps = $gap\n")
+        .unwrap();
+
+    let unused = config.unused_variables();
+    assert_eq!(unused.len(), 1);
+    assert_eq!(unused[0].name, "THEME");
+    assert_eq!(unused[0].line, Some(1));
+}
+
+#[test]
+fn test_check_reports_unused_variables_when_enabled() {
+    let input = "$gap = 5\n$stale = 1\ngaps = $gap\n";
+
+    let config = Config::new();
+    assert!(config.check(input).is_empty());
+
+    let config = Config::with_options(ConfigOptions {
+        warn_unused_variables: true,
+        ..Default::default()
+    });
+    let diagnostics = config.check(input);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert_eq!(diagnostics[0].line, Some(2));
+    assert_eq!(diagnostics[0].message, "variable '$stale' is never used");
+}
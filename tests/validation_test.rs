@@ -0,0 +1,94 @@
+//! Tests for value validators registered with `Config::add_validator`.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, ConfigError, ConfigValue};
+
+fn non_negative(value: &ConfigValue) -> Result<(), String> {
+    match value.as_int() {
+        Ok(n) if n >= 0 => Ok(()),
+        _ => Err("must be a non-negative integer".to_string()),
+    }
+}
+
+#[test]
+fn test_validator_rejects_parsed_assignment() {
+    let mut config = Config::new();
+    config.add_validator("decoration:rounding", non_negative);
+
+    let err = config
+        .parse("decoration {\n    rounding = -5\n}")
+        .unwrap_err();
+    match err {
+        ConfigError::ValidationError { key, value, reason } => {
+            assert_eq!(key, "decoration:rounding");
+            assert_eq!(value, "-5");
+            assert_eq!(reason, "must be a non-negative integer");
+        }
+        other => panic!("expected a validation error, got {other:?}"),
+    }
+    assert!(!config.contains("decoration:rounding"));
+
+    config.parse("decoration:rounding = 10").unwrap();
+    assert_eq!(config.get_int("decoration:rounding").unwrap(), 10);
+}
+
+#[test]
+fn test_validator_rejects_set() {
+    let mut config = Config::new();
+    config.parse("border_size = 2").unwrap();
+    config.add_validator("border_size", non_negative);
+
+    let err = config.set("border_size", ConfigValue::Int(-1)).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid value '-1' for 'border_size': must be a non-negative integer"
+    );
+    assert_eq!(config.get_int("border_size").unwrap(), 2);
+
+    config.set("border_size", ConfigValue::Int(4)).unwrap();
+    assert_eq!(config.get_int("border_size").unwrap(), 4);
+}
+
+#[test]
+fn test_category_prefix_validator() {
+    let mut config = Config::new();
+    config.add_validator("decoration", non_negative);
+
+    assert!(config.parse("decoration:blur:size = -1").is_err());
+    config.parse("decoration:blur:size = 8").unwrap();
+
+    // Only whole path components match
+    config.parse("decorations = -1").unwrap();
+    config.parse("general:rounding = -1").unwrap();
+}
+
+#[test]
+fn test_all_matching_validators_run() {
+    let mut config = Config::new();
+    config.add_validator("general", non_negative);
+    config.add_validator("general:gaps_in", |value| match value.as_int() {
+        Ok(n) if n <= 50 => Ok(()),
+        _ => Err("must be at most 50".to_string()),
+    });
+
+    assert!(config.parse("general:gaps_in = -1").is_err());
+    assert!(config.parse("general:gaps_in = 100").is_err());
+    config.parse("general:gaps_in = 20").unwrap();
+    assert_eq!(config.get_int("general:gaps_in").unwrap(), 20);
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_rejected_values_never_reach_document() {
+    let mut config = Config::new();
+    config.parse("decoration {\n  rounding = 10\n}\n").unwrap();
+    config.add_validator("decoration:rounding", non_negative);
+
+    assert!(config.set_int("decoration:rounding", -3).is_err());
+    assert!(config.set_string("decoration:rounding", "round").is_err());
+    assert_eq!(config.serialize(), "decoration {\n  rounding = 10\n}\n");
+
+    config.set_int("decoration:rounding", 12).unwrap();
+    assert_eq!(config.serialize(), "decoration {\n  rounding = 12\n}\n");
+}
@@ -0,0 +1,145 @@
+//! Tests for allowed values and ranges on special category properties.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{
+    Config, ConfigError, ConfigOptions, ConfigValue, SpecialCategoryDescriptor, ValueConstraint,
+};
+
+fn rule_config(options: ConfigOptions) -> Config {
+    let mut config = Config::with_options(options);
+    config.register_special_category(
+        SpecialCategoryDescriptor::keyed("rule", "name")
+            .with_range("opacity", 0.0, 1.0)
+            .with_allowed_values("mode", ["none", "focus"]),
+    );
+    config
+}
+
+#[test]
+fn test_values_within_constraints() {
+    let mut config = rule_config(ConfigOptions::default());
+    config
+        .parse("$OPACITY = 0.4\nrule[a] {\n  opacity = $OPACITY 1 override\n  mode = focus\n  other = 7\n}")
+        .unwrap();
+
+    let rule = config.get_special_category("rule", "a").unwrap();
+    assert_eq!(rule["mode"].as_string().unwrap(), "focus");
+    // Constraints only apply inside the category's blocks
+    config.parse("opacity = 5\nmode = always").unwrap();
+}
+
+#[test]
+fn test_violations_report_line() {
+    let mut config = rule_config(ConfigOptions::default());
+    let error = config
+        .parse("rule[a] {\n  mode = none\n  opacity = 0.5 1.2\n}")
+        .unwrap_err();
+
+    match &error {
+        ConfigError::ConstraintViolation {
+            key,
+            value,
+            file,
+            line,
+            ..
+        } => {
+            assert_eq!(key, "rule[a]:opacity");
+            assert_eq!(value, "0.5 1.2");
+            assert_eq!(*file, None);
+            assert_eq!(*line, 3);
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+    assert_eq!(
+        error.to_string(),
+        "Invalid value '0.5 1.2' for 'rule[a]:opacity' at line 3: must be a number from 0.0 to 1.0"
+    );
+}
+
+#[test]
+fn test_violations_collected_and_suppressed() {
+    let mut config = rule_config(ConfigOptions {
+        throw_all_errors: true,
+        ..Default::default()
+    });
+    let error = config
+        .parse("rule[a] {\n  mode = always\n  opacity = opaque\n}")
+        .unwrap_err();
+    let ConfigError::Multiple { errors } = error else {
+        panic!("expected collected errors");
+    };
+    assert_eq!(errors.len(), 2);
+    assert_eq!(
+        errors[0].to_string(),
+        "Invalid value 'always' for 'rule[a]:mode' at line 2: must be one of none, focus"
+    );
+
+    let mut config = rule_config(ConfigOptions::default());
+    config
+        .parse("# hyprlang noerror true\nrule[a] {\n  mode = always\n}")
+        .unwrap();
+    assert_eq!(config.suppressed_errors()[0].line, Some(3));
+}
+
+#[test]
+fn test_constraint_check() {
+    let range = ValueConstraint::Range {
+        min: 1.0,
+        max: 10.0,
+    };
+    assert!(range.check(&ConfigValue::Int(10)).is_ok());
+    assert!(range.check(&ConfigValue::Float(0.5)).is_err());
+    assert!(range.check(&ConfigValue::String(String::new())).is_err());
+
+    let one_of = ValueConstraint::OneOf(vec!["1".to_string()]);
+    assert!(one_of.check(&ConfigValue::Int(1)).is_ok());
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn test_regex_constraint() {
+    let regex = ValueConstraint::Regex;
+    assert!(
+        regex
+            .check(&ConfigValue::String("^(kitty|foot)$".into()))
+            .is_ok()
+    );
+    assert!(
+        regex
+            .check(&ConfigValue::String("negative:kitty".into()))
+            .is_ok()
+    );
+    assert_eq!(
+        regex.check(&ConfigValue::String("^(kitty".into())),
+        Err("must be a valid regex (unclosed group)".to_string())
+    );
+
+    let mut config = Config::new();
+    config.register_special_category(
+        SpecialCategoryDescriptor::keyed("rule", "name").with_regex("match:class"),
+    );
+    let error = config
+        .parse("rule[a] {\n  match:class = [a-\n}")
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        ConfigError::ConstraintViolation { line: 2, .. }
+    ));
+}
+
+#[cfg(all(feature = "hyprland", feature = "regex"))]
+#[test]
+fn test_hyprland_rule_matchers_are_validated() {
+    let mut hypr = hyprlang::Hyprland::new();
+    hypr.parse("windowrule[ok] {\n  match:title = ^(.*Firefox)$\n  float = true\n}")
+        .unwrap();
+
+    let error = hypr
+        .parse("\nlayerrule[bad] {\n  match:namespace = (waybar\n  blur = true\n}")
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        ConfigError::ConstraintViolation { line: 3, .. }
+    ));
+}
@@ -0,0 +1,87 @@
+//! Tests for conversions between ConfigValue and native Rust types.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Color, Config, ConfigValue, Vec2};
+
+#[test]
+fn test_set_accepts_native_values() {
+    let mut config = Config::new();
+    config.set("int", 5).unwrap();
+    config.set("wide", 5_000_000_000i64).unwrap();
+    config.set("float", 0.5).unwrap();
+    config.set("color", Color::from_rgb(255, 0, 0)).unwrap();
+    config.set("pair", (10.0, 20.0)).unwrap();
+    config.set("vec", Vec2::new(1.0, 2.0)).unwrap();
+    config.set("text", "kitty").unwrap();
+    config.set("owned", String::from("firefox")).unwrap();
+
+    assert_eq!(config.get_int("int").unwrap(), 5);
+    assert_eq!(config.get_int("wide").unwrap(), 5_000_000_000);
+    assert_eq!(config.get_float("float").unwrap(), 0.5);
+    assert_eq!(
+        config.get_color("color").unwrap(),
+        Color::from_rgb(255, 0, 0)
+    );
+    assert_eq!(config.get_vec2("pair").unwrap(), Vec2::new(10.0, 20.0));
+    assert_eq!(config.get_vec2("vec").unwrap(), Vec2::new(1.0, 2.0));
+    assert_eq!(config.get_string("text").unwrap(), "kitty");
+    assert_eq!(config.get_string("owned").unwrap(), "firefox");
+}
+
+#[test]
+fn test_text_is_read_like_a_config_value() {
+    let mut config = Config::new();
+    config.set("gaps", "5").unwrap();
+    config.set("border", "rgba(33ccffee)").unwrap();
+    config
+        .set("quoted", ConfigValue::String("5".to_string()))
+        .unwrap();
+
+    assert_eq!(config.get_int("gaps").unwrap(), 5);
+    assert!(config.get_color("border").is_ok());
+    assert_eq!(config.get_string("quoted").unwrap(), "5");
+}
+
+#[test]
+fn test_try_from_config_value() {
+    assert_eq!(i64::try_from(ConfigValue::Int(3)).unwrap(), 3);
+    assert_eq!(f64::try_from(ConfigValue::Int(3)).unwrap(), 3.0);
+    assert_eq!(
+        String::try_from(ConfigValue::String("a".to_string())).unwrap(),
+        "a"
+    );
+    assert_eq!(
+        Color::try_from(ConfigValue::from(Color::from_rgb(1, 2, 3))).unwrap(),
+        Color::from_rgb(1, 2, 3)
+    );
+    assert_eq!(
+        Vec2::try_from(ConfigValue::String("800 600".to_string())).unwrap(),
+        Vec2::new(800.0, 600.0)
+    );
+    assert_eq!(
+        <(f64, f64)>::try_from(ConfigValue::from((1.0, 2.0))).unwrap(),
+        (1.0, 2.0)
+    );
+
+    assert!(i64::try_from(ConfigValue::Float(1.5)).is_err());
+    assert!(String::try_from(ConfigValue::Int(1)).is_err());
+    assert!(Color::try_from(ConfigValue::Int(1)).is_err());
+}
+
+#[test]
+fn test_try_into_from_get() {
+    let mut config = Config::new();
+    config.parse("general {\n    gaps_in = 5\n}").unwrap();
+
+    let gaps: i64 = config
+        .get("general:gaps_in")
+        .unwrap()
+        .clone()
+        .try_into()
+        .unwrap();
+    assert_eq!(gaps, 5);
+
+    let wrong: Result<Color, _> = config.get("general:gaps_in").unwrap().clone().try_into();
+    assert!(wrong.is_err());
+}
@@ -0,0 +1,108 @@
+//! Tests for escaping values so that they read back unchanged.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, ConfigValue, escape_value};
+use proptest::prelude::*;
+
+/// Parse `key = <written>` next to variables the text may refer to
+fn read_back(written: &str) -> ConfigValue {
+    let mut config = Config::new();
+    config
+        .parse(&format!("$a = A\n$b_1 = B\nkey = {}\n", written))
+        .unwrap_or_else(|e| panic!("{:?}: {}", written, e));
+    config.get("key").unwrap().clone()
+}
+
+/// Text made of the characters that mean something in a value
+fn text() -> impl Strategy<Value = String> {
+    prop::collection::vec(
+        prop::sample::select(vec![
+            "a", "b_1", " ", "\t", "#", "$", "{", "}", "{{", "}}", "\\", "\"", "1", "+", ",",
+        ]),
+        0..12,
+    )
+    .prop_map(|parts| parts.concat())
+}
+
+#[test]
+fn test_escaped_values() {
+    for (text, written) in [
+        ("kitty --single-instance", "kitty --single-instance"),
+        ("#ff0000 # not a comment", "##ff0000 ## not a comment"),
+        ("  padded\t", "\"  padded\t\""),
+        ("\"quoted\"", "\"\\\"quoted\\\"\""),
+        ("cost: $a or $", "cost: \\$a or $"),
+        ("{{1 + 2}} = 3", "{\\{1 + 2}} = 3"),
+        ("C:\\path\\", "C:\\path\\ #"),
+        ("", ""),
+    ] {
+        assert_eq!(escape_value(text).unwrap(), written);
+        assert_eq!(read_back(written).to_string(), text, "{:?}", written);
+    }
+}
+
+#[test]
+fn test_values_with_no_escaped_form() {
+    assert!(escape_value("line\nbreak").is_none());
+    assert!(escape_value("carriage\rreturn").is_none());
+    assert!(escape_value(" ends in \\").is_none());
+    assert!(escape_value("{\\{").is_none());
+}
+
+#[test]
+fn test_quoted_values_keep_whitespace() {
+    assert_eq!(read_back("\"  a  \"").as_string().unwrap(), "  a  ");
+    // Values of other types are still read from quoted text
+    assert_eq!(read_back("\" 5 \"").as_int().unwrap(), 5);
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_set_string_serializes_escaped() {
+    let mut config = Config::new();
+    config.parse("$a = A\ntitle = x\n").unwrap();
+    config.set_string("title", "# $a {{1}} ").unwrap();
+    config.set_string("new", "a#b").unwrap();
+
+    assert_eq!(
+        config.serialize(),
+        "$a = A\ntitle = \"# \\$a {\\{1}} \"\nnew = a##b\n"
+    );
+    assert!(config.set_string("title", "two\nlines").is_err());
+    assert_eq!(config.get_string("title").unwrap(), "# $a {{1}} ");
+}
+
+proptest! {
+    #[test]
+    fn prop_escaped_values_read_back(text in text()) {
+        let Some(written) = escape_value(&text) else {
+            // Only backslashes can make text impossible to write
+            prop_assert!(text.contains('\\'));
+            return Ok(());
+        };
+
+        // Text that reads as another type, like `1`, reads back as that type
+        if let ConfigValue::String(read) = read_back(&written) {
+            prop_assert_eq!(read, text);
+        }
+    }
+}
+
+#[cfg(feature = "mutation")]
+proptest! {
+    #[test]
+    fn prop_set_strings_survive_serialize(text in text()) {
+        let mut config = Config::new();
+        config.parse("$a = A\n$b_1 = B\n").unwrap();
+        if config.set_string("general:title", text.clone()).is_err() {
+            return Ok(());
+        }
+
+        let mut reparsed = Config::new();
+        reparsed.parse(&config.serialize()).unwrap();
+        if let ConfigValue::String(read) = reparsed.get("general:title").unwrap() {
+            prop_assert_eq!(read, &text);
+        }
+    }
+}
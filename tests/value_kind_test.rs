@@ -0,0 +1,42 @@
+//! Tests for checking the stored type of a key without converting it.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, ConfigValue, ValueKind};
+
+#[test]
+fn test_kind_of() {
+    let mut config = Config::new();
+    config.register_quantity_key("speed");
+    config
+        .parse(
+            "gaps = 5\nenabled = true\nopacity = 0.9\nlayout = dwindle\nsize = 10, 20\n\
+             border = rgba(33ccffee)\nspeed = 200ms",
+        )
+        .unwrap();
+
+    assert_eq!(config.kind_of("gaps"), Some(ValueKind::Int));
+    assert_eq!(config.kind_of("enabled"), Some(ValueKind::Int));
+    assert_eq!(config.kind_of("opacity"), Some(ValueKind::Float));
+    assert_eq!(config.kind_of("layout"), Some(ValueKind::String));
+    assert_eq!(config.kind_of("size"), Some(ValueKind::Vec2));
+    assert_eq!(config.kind_of("border"), Some(ValueKind::Color));
+    assert_eq!(config.kind_of("speed"), Some(ValueKind::Quantity));
+    assert_eq!(config.kind_of("missing"), None);
+}
+
+#[test]
+fn test_contains_typed() {
+    let mut config = Config::new();
+    config.parse("gaps = 5\nlayout = dwindle").unwrap();
+
+    assert!(config.contains_typed("gaps", ValueKind::Int));
+    assert!(!config.contains_typed("gaps", ValueKind::String));
+    assert!(config.contains_typed("layout", ValueKind::String));
+    assert!(!config.contains_typed("missing", ValueKind::Int));
+
+    // An Int can still be read as a float, but its kind stays Int
+    assert!(config.get_float("gaps").is_ok());
+    assert!(!config.contains_typed("gaps", ValueKind::Float));
+    assert_eq!(ConfigValue::Float(1.0).kind(), ValueKind::Float);
+}
@@ -0,0 +1,98 @@
+//! Tests for listing variables with their definitions and locations.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, VariableEntry};
+
+#[test]
+fn test_variable_entries_list_definitions() {
+    let mut config = Config::new();
+    config.seed_variable("HOSTNAME", "laptop");
+    config
+        .parse("$gap = 5\n\n$outer = {{gap * 2}}\n$title = on $HOSTNAME\n")
+        .unwrap();
+
+    assert_eq!(
+        config.variable_entries(),
+        vec![
+            VariableEntry {
+                name: "HOSTNAME".to_string(),
+                value: "laptop".to_string(),
+                raw: "laptop".to_string(),
+                file: None,
+                line: None,
+                seeded: true,
+            },
+            VariableEntry {
+                name: "gap".to_string(),
+                value: "5".to_string(),
+                raw: "5".to_string(),
+                file: None,
+                line: Some(1),
+                seeded: false,
+            },
+            VariableEntry {
+                name: "outer".to_string(),
+                value: "{{gap * 2}}".to_string(),
+                raw: "{{gap * 2}}".to_string(),
+                file: None,
+                line: Some(3),
+                seeded: false,
+            },
+            VariableEntry {
+                name: "title".to_string(),
+                value: "on laptop".to_string(),
+                raw: "on $HOSTNAME".to_string(),
+                file: None,
+                line: Some(4),
+                seeded: false,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_set_variable_keeps_definition_location() {
+    let mut config = Config::new();
+    config.seed_variable("MOD", "ALT");
+    config.parse("gaps = 5\n$GAPS = 10\n").unwrap();
+    config.set_variable("GAPS".to_string(), "20".to_string());
+    config.set_variable("MOD".to_string(), "SUPER".to_string());
+    config.set_variable("NEW".to_string(), "1".to_string());
+
+    let entries = config.variable_entries();
+    let located: Vec<_> = entries
+        .iter()
+        .map(|entry| {
+            (
+                entry.name.as_str(),
+                entry.raw.as_str(),
+                entry.line,
+                entry.seeded,
+            )
+        })
+        .collect();
+    assert_eq!(
+        located,
+        [
+            ("GAPS", "20", Some(2), false),
+            ("MOD", "SUPER", None, false),
+            ("NEW", "1", None, false),
+        ]
+    );
+}
+
+#[cfg(feature = "mutation")]
+#[test]
+fn test_removed_variable_is_not_listed() {
+    let mut config = Config::new();
+    config.parse("$A = 1\n$B = 2\n").unwrap();
+    config.remove_variable("A");
+
+    let names: Vec<_> = config
+        .variable_entries()
+        .into_iter()
+        .map(|entry| entry.name)
+        .collect();
+    assert_eq!(names, ["B"]);
+}
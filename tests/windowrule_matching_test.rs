@@ -0,0 +1,157 @@
+//! Tests for finding special category instances and matching windowrules against windows.
+
+#![cfg(feature = "config")]
+
+use hyprlang::{Config, SpecialCategoryDescriptor};
+
+#[test]
+fn test_find_special_category_instances_in_declaration_order() {
+    let mut config = Config::new();
+    config.register_special_category(
+        SpecialCategoryDescriptor::keyed("device", "name")
+            .with_default("enabled", hyprlang::ConfigValue::Int(1)),
+    );
+    config
+        .parse(
+            "device[zeta] {\n    sensitivity = 0.5\n}\n\
+             device[alpha] {\n    sensitivity = 1.0\n}\n\
+             device[mid] {\n    enabled = false\n}\n",
+        )
+        .unwrap();
+
+    let keys = |instances: Vec<&hyprlang::SpecialCategoryInstance>| -> Vec<String> {
+        instances
+            .into_iter()
+            .filter_map(|instance| instance.key.clone())
+            .collect()
+    };
+
+    assert_eq!(
+        keys(config.find_special_category_instances("device", |_| true)),
+        ["zeta", "alpha", "mid"]
+    );
+    assert_eq!(
+        keys(
+            config.find_special_category_instances("device", |instance| {
+                instance.contains("sensitivity")
+            })
+        ),
+        ["zeta", "alpha"]
+    );
+    assert!(
+        config
+            .find_special_category_instances("missing", |_| true)
+            .is_empty()
+    );
+}
+
+#[cfg(all(feature = "hyprland", feature = "regex"))]
+mod windowrules {
+    use hyprlang::{Hyprland, WindowProperties};
+
+    fn hyprland() -> Hyprland {
+        let mut hypr = Hyprland::new();
+        hypr.parse(
+            r#"
+windowrule[float-kitty] {
+    match:class = ^(kitty)$
+    float = true
+}
+windowrule[terminals] {
+    match:class = kitty|foot|Alacritty
+    opacity = 0.9
+}
+windowrule[floating-kitty-only] {
+    match:class = kitty
+    match:float = true
+    border_size = 4
+}
+windowrule[firefox-pip] {
+    match:class = firefox
+    match:title = Picture-in-Picture
+    pin = true
+}
+windowrule[not-kitty] {
+    match:class = negative:kitty
+    no_blur = true
+}
+windowrule[disabled] {
+    match:class = kitty
+    enable = false
+}
+windowrule[tagged] {
+    match:tag = games
+    fullscreen = true
+}
+"#,
+        )
+        .unwrap();
+        hypr
+    }
+
+    #[test]
+    fn test_matching_class() {
+        let hypr = hyprland();
+
+        assert_eq!(
+            hypr.windowrules_matching_class("kitty").unwrap(),
+            ["float-kitty", "terminals", "floating-kitty-only"]
+        );
+        assert_eq!(
+            hypr.windowrules_matching_class("foot").unwrap(),
+            ["terminals", "not-kitty"]
+        );
+        // Regexes must match the whole class
+        assert_eq!(
+            hypr.windowrules_matching_class("kitty-beta").unwrap(),
+            ["not-kitty"]
+        );
+    }
+
+    #[test]
+    fn test_matching_all_known_properties() {
+        let hypr = hyprland();
+
+        let window = WindowProperties {
+            class: Some("kitty".to_string()),
+            floating: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(
+            hypr.windowrules_matching(&window).unwrap(),
+            ["float-kitty", "terminals"]
+        );
+
+        let pip = WindowProperties {
+            class: Some("firefox".to_string()),
+            title: Some("Picture-in-Picture".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            hypr.windowrules_matching(&pip).unwrap(),
+            ["firefox-pip", "not-kitty"]
+        );
+
+        let game = WindowProperties {
+            tags: Some(vec!["steam".to_string(), "games".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(hypr.windowrules_matching(&game).unwrap(), ["tagged"]);
+
+        assert!(
+            hypr.windowrules_matching(&WindowProperties::default())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_invalid_regex_is_rejected_when_parsing() {
+        let mut hypr = Hyprland::new();
+        let error = hypr
+            .parse("windowrule[broken] {\n    match:class = ^(kitty\n    float = true\n}")
+            .unwrap_err();
+        assert!(error.to_string().contains("match:class"));
+        assert!(hypr.windowrules_matching_class("kitty").unwrap().is_empty());
+    }
+}
@@ -1,6 +1,6 @@
 #![cfg(feature = "hyprland")]
 
-use hyprlang::Hyprland;
+use hyprlang::{Hyprland, LayerRule, RuleOrigin};
 
 #[test]
 fn test_basic_windowrule_v3() {
@@ -151,6 +151,7 @@ fn test_windowrule_with_complex_properties() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_backward_compat_handler_syntax() {
     let mut hypr = Hyprland::new();
 
@@ -168,6 +169,7 @@ fn test_backward_compat_handler_syntax() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_mixed_v2_and_v3_syntax() {
     let mut hypr = Hyprland::new();
 
@@ -699,3 +701,135 @@ fn test_layerrule_effect_aliases() {
     assert_eq!(rule.get_int("no_anim").unwrap(), 1);
     assert_eq!(rule.get_int("noscreenshare").unwrap(), 1);
 }
+
+#[test]
+fn test_rule_value_constraints() {
+    let mut hypr = Hyprland::new();
+    hypr.parse(
+        r#"
+        windowrule[valid] {
+            match:class = kitty
+            opacity = 0.8 override 0.5 override
+            idle_inhibit = fullscreen
+        }
+    "#,
+    )
+    .unwrap();
+
+    let error = Hyprland::new()
+        .parse("windowrule[bad] {\n    match:class = kitty\n    idleinhibit = sometimes\n}")
+        .unwrap_err();
+    assert!(error.to_string().contains("at line 3"));
+
+    assert!(
+        Hyprland::new()
+            .parse("windowrule[bad] {\n    opacity = 1.5\n}")
+            .is_err()
+    );
+    assert!(
+        Hyprland::new()
+            .parse("layerrule[bad] {\n    ignorealpha = 2\n}")
+            .is_err()
+    );
+}
+
+#[test]
+fn test_layerrules_merge_lines_and_blocks() {
+    let mut hypr = Hyprland::new();
+    hypr.parse(
+        r#"
+        layerrule = blur, waybar
+        layerrule[launcher] {
+            match:namespace = rofi
+            match:monitor = DP-1
+            ignorealpha = 0.5
+            enable = false
+        }
+        layerrule = ignorealpha 0.3, ^(mako|dunst)$
+        layerrule[bar] {
+            match:namespace = waybar
+            no_anim = true
+        }
+    "#,
+    )
+    .unwrap();
+
+    let rules = hypr.layerrules();
+    assert_eq!(rules.len(), 4);
+
+    assert_eq!(rules[0].origin, RuleOrigin::Handler { index: 0 });
+    assert_eq!(rules[0].namespace, "waybar");
+    assert_eq!(rules[0].effects["blur"], "true");
+    assert_eq!(rules[0].line, Some(2));
+
+    assert_eq!(rules[1].origin, RuleOrigin::Handler { index: 1 });
+    assert_eq!(rules[1].namespace, "^(mako|dunst)$");
+    assert_eq!(rules[1].effects["ignore_alpha"], "0.3");
+
+    assert_eq!(rules[2].name(), Some("launcher"));
+    assert_eq!(rules[2].namespace, "rofi");
+    assert_eq!(rules[2].matchers["monitor"], "DP-1");
+    assert_eq!(rules[2].effects["ignore_alpha"], "0.5");
+    assert!(!rules[2].enabled);
+    assert_eq!(rules[2].line, Some(4));
+
+    assert_eq!(rules[3].name(), Some("bar"));
+    assert_eq!(rules[3].effects["no_anim"], "true");
+    assert!(rules[3].enabled);
+    assert!(rules[3].effects.keys().all(|effect| effect != "blur"));
+}
+
+#[test]
+fn test_layerrule_line_parsing() {
+    let rule = LayerRule::parse("animation slide top, ^(rofi)$");
+    assert_eq!(rule.effects["animation"], "slide top");
+    assert_eq!(rule.namespace, "^(rofi)$");
+    assert_eq!(rule.name(), None);
+
+    let rule = LayerRule::parse("noanim, a,b");
+    assert_eq!(rule.effects["no_anim"], "true");
+    assert_eq!(rule.namespace, "a,b");
+}
+
+#[test]
+fn test_rule_names_in_declaration_order() {
+    let input = r#"
+        windowrule[zeta] {
+            match:class = zeta
+            float = true
+        }
+        windowrule[alpha] {
+            match:class = alpha
+            float = true
+        }
+        windowrule[mid] {
+            match:class = mid
+            float = true
+        }
+        windowrule[alpha] {
+            center = true
+        }
+
+        layerrule[waybar] {
+            match:namespace = waybar
+            blur = true
+        }
+        layerrule[dunst] {
+            match:namespace = dunst
+            blur = true
+        }
+    "#;
+
+    // Repeated parses would expose any dependence on hash order
+    for _ in 0..16 {
+        let mut hypr = Hyprland::new();
+        hypr.parse(input).unwrap();
+
+        assert_eq!(hypr.windowrule_names(), ["zeta", "alpha", "mid"]);
+        assert_eq!(hypr.layerrule_names(), ["waybar", "dunst"]);
+        assert_eq!(
+            hypr.config().list_special_category_keys("windowrule"),
+            ["zeta", "alpha", "mid"]
+        );
+    }
+}